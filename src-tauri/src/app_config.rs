@@ -72,6 +72,28 @@ pub struct McpServer {
     pub tags: Vec<String>,
 }
 
+/// MCP 服务器排序更新，决定列表及写入 live 配置时的条目顺序
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpSortUpdate {
+    pub id: String,
+    pub sort_index: usize,
+}
+
+/// 导入时无法解析的 MCP 条目（隔离区），保留原始内容以便修正后重试
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuarantinedMcpEntry {
+    /// `<app>:<server_id>`，同一来源的同名条目重复导入时覆盖
+    pub id: String,
+    pub app_type: String,
+    pub server_id: String,
+    /// Claude/Gemini 为 JSON，Codex 为 TOML 片段
+    pub raw: String,
+    pub error: String,
+    pub created_at: i64,
+}
+
 /// MCP 配置：单客户端维度（v3.6.x 及以前，保留用于向后兼容）
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct McpConfig {
@@ -512,6 +534,7 @@ impl MultiAppConfig {
             enabled: true, // 自动启用
             created_at: Some(timestamp),
             updated_at: Some(timestamp),
+            version: None,
         };
 
         // 插入到对应的应用配置中
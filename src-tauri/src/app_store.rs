@@ -1,5 +1,5 @@
 use serde_json::Value;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{OnceLock, RwLock};
 use tauri_plugin_store::StoreExt;
 
@@ -7,6 +7,8 @@ use crate::error::AppError;
 
 /// Store 中的键名
 const STORE_KEY_APP_CONFIG_DIR: &str = "app_config_dir_override";
+const STORE_KEY_DEMO_MODE: &str = "demo_mode";
+const STORE_KEY_DB_PATH: &str = "db_path_override";
 
 /// 缓存当前的 app_config_dir 覆盖路径，避免存储 AppHandle
 static APP_CONFIG_DIR_OVERRIDE: OnceLock<RwLock<Option<PathBuf>>> = OnceLock::new();
@@ -105,8 +107,100 @@ pub fn set_app_config_dir_to_store(
     Ok(())
 }
 
+/// 缓存当前的数据库文件覆盖路径
+static DB_PATH_OVERRIDE: OnceLock<RwLock<Option<PathBuf>>> = OnceLock::new();
+
+fn db_override_cache() -> &'static RwLock<Option<PathBuf>> {
+    DB_PATH_OVERRIDE.get_or_init(|| RwLock::new(None))
+}
+
+/// 获取缓存中的数据库文件覆盖路径
+pub fn get_db_path_override() -> Option<PathBuf> {
+    db_override_cache().read().ok()?.clone()
+}
+
+/// 从 Store 刷新数据库文件覆盖路径并更新缓存
+///
+/// 所在目录不存在（如加密卷未挂载）时回退默认路径，与 app_config_dir 的处理一致
+pub fn refresh_db_path_override(app: &tauri::AppHandle) -> Option<PathBuf> {
+    let value = app
+        .store_builder("app_paths.json")
+        .build()
+        .ok()
+        .and_then(|store| store.get(STORE_KEY_DB_PATH))
+        .and_then(|v| v.as_str().map(str::trim).map(str::to_string))
+        .filter(|s| !s.is_empty())
+        .map(|s| resolve_path(&s))
+        .filter(|path| {
+            let mounted = path.parent().is_some_and(|p| p.is_dir());
+            if !mounted {
+                log::warn!("Store 中配置的数据库目录不存在: {path:?}\n将使用默认路径。");
+            }
+            mounted
+        });
+
+    if let Ok(mut guard) = db_override_cache().write() {
+        *guard = value.clone();
+    }
+    value
+}
+
+/// 写入数据库文件覆盖路径，None 表示恢复默认位置；重启后生效
+pub fn set_db_path_to_store(app: &tauri::AppHandle, path: Option<&Path>) -> Result<(), AppError> {
+    let store = app
+        .store_builder("app_paths.json")
+        .build()
+        .map_err(|e| AppError::Message(format!("创建 Store 失败: {e}")))?;
+
+    match path {
+        Some(p) => {
+            store.set(
+                STORE_KEY_DB_PATH,
+                Value::String(p.to_string_lossy().to_string()),
+            );
+            log::info!("已将数据库路径写入 Store: {}", p.display());
+        }
+        None => {
+            store.delete(STORE_KEY_DB_PATH);
+            log::info!("已从 Store 中删除数据库路径配置");
+        }
+    }
+
+    store
+        .save()
+        .map_err(|e| AppError::Message(format!("保存 Store 失败: {e}")))
+}
+
+/// 读取下次启动是否进入演示模式（需在打开数据库前判断，因此不放在 settings 表中）
+pub fn get_demo_mode_from_store(app: &tauri::AppHandle) -> bool {
+    app.store_builder("app_paths.json")
+        .build()
+        .ok()
+        .and_then(|store| store.get(STORE_KEY_DEMO_MODE))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// 写入演示模式开关，重启后生效
+pub fn set_demo_mode_to_store(app: &tauri::AppHandle, enabled: bool) -> Result<(), AppError> {
+    let store = app
+        .store_builder("app_paths.json")
+        .build()
+        .map_err(|e| AppError::Message(format!("创建 Store 失败: {e}")))?;
+
+    if enabled {
+        store.set(STORE_KEY_DEMO_MODE, Value::Bool(true));
+    } else {
+        store.delete(STORE_KEY_DEMO_MODE);
+    }
+
+    store
+        .save()
+        .map_err(|e| AppError::Message(format!("保存 Store 失败: {e}")))
+}
+
 /// 解析路径，支持 ~ 开头的相对路径
-fn resolve_path(raw: &str) -> PathBuf {
+pub(crate) fn resolve_path(raw: &str) -> PathBuf {
     if raw == "~" {
         if let Some(home) = dirs::home_dir() {
             return home;
@@ -1,10 +1,13 @@
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::config::{atomic_write, get_claude_mcp_path, get_default_claude_mcp_path};
+use crate::config::{
+    atomic_write, file_exists, get_claude_mcp_path, get_default_claude_mcp_path, read_text_file,
+};
 use crate::error::AppError;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -62,15 +65,15 @@ fn ensure_mcp_override_migrated() {
 }
 
 fn read_json_value(path: &Path) -> Result<Value, AppError> {
-    if !path.exists() {
+    if !file_exists(path) {
         return Ok(serde_json::json!({}));
     }
-    let content = fs::read_to_string(path).map_err(|e| AppError::io(path, e))?;
+    let content = read_text_file(path)?;
     let value: Value = serde_json::from_str(&content).map_err(|e| AppError::json(path, e))?;
     Ok(value)
 }
 
-fn write_json_value(path: &Path, value: &Value) -> Result<(), AppError> {
+fn write_json_value(path: &Path, value: &impl Serialize) -> Result<(), AppError> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
     }
@@ -81,7 +84,7 @@ fn write_json_value(path: &Path, value: &Value) -> Result<(), AppError> {
 
 pub fn get_mcp_status() -> Result<McpStatus, AppError> {
     let path = user_config_path();
-    let (exists, count) = if path.exists() {
+    let (exists, count) = if file_exists(&path) {
         let v = read_json_value(&path)?;
         let servers = v.get("mcpServers").and_then(|x| x.as_object());
         (true, servers.map(|m| m.len()).unwrap_or(0))
@@ -98,10 +101,10 @@ pub fn get_mcp_status() -> Result<McpStatus, AppError> {
 
 pub fn read_mcp_json() -> Result<Option<String>, AppError> {
     let path = user_config_path();
-    if !path.exists() {
+    if !file_exists(&path) {
         return Ok(None);
     }
-    let content = fs::read_to_string(&path).map_err(|e| AppError::io(&path, e))?;
+    let content = read_text_file(&path)?;
     Ok(Some(content))
 }
 
@@ -148,7 +151,7 @@ pub fn upsert_mcp_server(id: &str, spec: Value) -> Result<bool, AppError> {
     }
 
     let path = user_config_path();
-    let mut root = if path.exists() {
+    let mut root = if file_exists(&path) {
         read_json_value(&path)?
     } else {
         serde_json::json!({})
@@ -169,7 +172,7 @@ pub fn upsert_mcp_server(id: &str, spec: Value) -> Result<bool, AppError> {
         servers.insert(id.to_string(), spec);
     }
 
-    if before == root && path.exists() {
+    if before == root && file_exists(&path) {
         return Ok(false);
     }
 
@@ -182,7 +185,7 @@ pub fn delete_mcp_server(id: &str) -> Result<bool, AppError> {
         return Err(AppError::InvalidInput("MCP 服务器 ID 不能为空".into()));
     }
     let path = user_config_path();
-    if !path.exists() {
+    if !file_exists(&path) {
         return Ok(false);
     }
     let mut root = read_json_value(&path)?;
@@ -235,36 +238,34 @@ pub fn validate_command_in_path(cmd: &str) -> Result<bool, AppError> {
 }
 
 /// 读取 ~/.claude.json 中的 mcpServers 映射
-pub fn read_mcp_servers_map() -> Result<std::collections::HashMap<String, Value>, AppError> {
+pub fn read_mcp_servers_map() -> Result<IndexMap<String, Value>, AppError> {
     let path = user_config_path();
-    if !path.exists() {
-        return Ok(std::collections::HashMap::new());
+    if !file_exists(&path) {
+        return Ok(IndexMap::new());
     }
 
-    let root = read_json_value(&path)?;
-    let servers = root
-        .get("mcpServers")
-        .and_then(|v| v.as_object())
-        .map(|obj| obj.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
-        .unwrap_or_default();
-
-    Ok(servers)
+    // 先按 Value 解析以校验 JSON，再按文件中的顺序取出条目
+    read_json_value(&path)?;
+    Ok(crate::mcp::ordered_mcp_servers(&read_text_file(&path)?))
 }
 
 /// 将给定的启用 MCP 服务器映射写入到用户级 ~/.claude.json 的 mcpServers 字段
-/// 仅覆盖 mcpServers，其他字段保持不变
-pub fn set_mcp_servers_map(
-    servers: &std::collections::HashMap<String, Value>,
-) -> Result<(), AppError> {
+/// 条目按映射中的顺序写出；仅覆盖 mcpServers，其他字段保持不变
+pub fn set_mcp_servers_map(servers: &IndexMap<String, Value>) -> Result<(), AppError> {
     let path = user_config_path();
-    let mut root = if path.exists() {
-        read_json_value(&path)?
+    let existing = if file_exists(&path) {
+        Some(read_json_value(&path)?)
     } else {
-        serde_json::json!({})
+        None
+    };
+    let mut root = match existing.clone() {
+        Some(Value::Object(map)) => map,
+        Some(_) => return Err(AppError::Config("~/.claude.json 根必须是对象".into())),
+        None => Map::new(),
     };
 
     // 构建 mcpServers 对象：移除 UI 辅助字段（enabled/source），仅保留实际 MCP 规范
-    let mut out: Map<String, Value> = Map::new();
+    let mut out: IndexMap<String, Value> = IndexMap::new();
     for (id, spec) in servers.iter() {
         let mut obj = if let Some(map) = spec.as_object() {
             map.clone()
@@ -292,14 +293,27 @@ pub fn set_mcp_servers_map(
 
         out.insert(id.clone(), Value::Object(obj));
     }
-
-    {
-        let obj = root
-            .as_object_mut()
-            .ok_or_else(|| AppError::Config("~/.claude.json 根必须是对象".into()))?;
-        obj.insert("mcpServers".into(), Value::Object(out));
+    root.insert(
+        "mcpServers".into(),
+        Value::Object(out.iter().map(|(k, v)| (k.clone(), v.clone())).collect()),
+    );
+
+    // mcpServers 未变化时跳过写入，保持文件原样
+    // JSON 对象相等不区分键顺序，条目顺序需单独比较
+    let order_unchanged = existing.is_some()
+        && crate::mcp::ordered_mcp_servers(&read_text_file(&path)?)
+            .keys()
+            .eq(out.keys());
+    if order_unchanged && existing.as_ref().and_then(Value::as_object) == Some(&root) {
+        return Ok(());
     }
 
-    write_json_value(&path, &root)?;
-    Ok(())
+    // serde_json 的 Map 按键排序，mcpServers 需按 out 的顺序单独写出
+    write_json_value(
+        &path,
+        &crate::mcp::OrderedMcpConfig {
+            root: &root,
+            servers: &out,
+        },
+    )
 }
@@ -80,6 +80,8 @@ pub fn write_claude_config() -> Result<bool, AppError> {
     if changed || !path.exists() {
         let serialized = serde_json::to_string_pretty(&obj)
             .map_err(|e| AppError::JsonSerialize { source: e })?;
+        crate::services::demo::ensure_live_write_allowed(&path)?;
+        crate::services::WriteAccessService::ensure_path_writable(&path)?;
         fs::write(&path, format!("{serialized}\n")).map_err(|e| AppError::io(&path, e))?;
         Ok(true)
     } else {
@@ -114,6 +116,8 @@ pub fn clear_claude_config() -> Result<bool, AppError> {
 
     let serialized =
         serde_json::to_string_pretty(&value).map_err(|e| AppError::JsonSerialize { source: e })?;
+    crate::services::demo::ensure_live_write_allowed(&path)?;
+    crate::services::WriteAccessService::ensure_path_writable(&path)?;
     fs::write(&path, format!("{serialized}\n")).map_err(|e| AppError::io(&path, e))?;
     Ok(true)
 }
@@ -5,6 +5,7 @@ use crate::config::{
     atomic_write, delete_file, sanitize_provider_name, write_json_file, write_text_file,
 };
 use crate::error::AppError;
+use serde::Serialize;
 use serde_json::Value;
 use std::fs;
 use std::path::Path;
@@ -28,6 +29,11 @@ pub fn get_codex_config_path() -> PathBuf {
     get_codex_config_dir().join("config.toml")
 }
 
+/// 本应用管理的 config.toml 备份目录
+pub fn get_codex_backup_dir() -> PathBuf {
+    get_codex_config_dir().join("backups")
+}
+
 /// 获取 Codex 供应商配置文件路径
 #[allow(dead_code)]
 pub fn get_codex_provider_paths(
@@ -111,8 +117,8 @@ pub fn write_codex_live_atomic(
 /// 读取 `~/.codex/config.toml`，若不存在返回空字符串
 pub fn read_codex_config_text() -> Result<String, AppError> {
     let path = get_codex_config_path();
-    if path.exists() {
-        std::fs::read_to_string(&path).map_err(|e| AppError::io(&path, e))
+    if crate::config::file_exists(&path) {
+        crate::config::read_text_file(&path)
     } else {
         Ok(String::new())
     }
@@ -134,3 +140,120 @@ pub fn read_and_validate_codex_config_text() -> Result<String, AppError> {
     validate_config_toml(&s)?;
     Ok(s)
 }
+
+/// config.toml 自动备份保留的份数
+const CONFIG_BACKUP_RETAIN: usize = 10;
+const CONFIG_BACKUP_PREFIX: &str = "config-";
+
+/// 一份 config.toml 自动备份
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexConfigBackup {
+    /// 备份 ID（不含扩展名的文件名，按时间排序）
+    pub id: String,
+    pub path: String,
+    pub size: u64,
+    /// 备份时间（Unix 秒）
+    pub created_at: i64,
+}
+
+/// 改写 config.toml 前备份当前文件，只保留最近的若干份
+///
+/// 内容与最近一份备份相同时不重复备份，返回已有备份的 ID；文件不存在时返回 None
+pub fn backup_codex_config() -> Result<Option<String>, AppError> {
+    let path = get_codex_config_path();
+    // 预览切换时不落盘，也就无需备份
+    if !path.exists() || crate::config::is_capturing_writes() {
+        return Ok(None);
+    }
+    crate::services::demo::ensure_live_write_allowed(&path)?;
+    crate::services::WriteAccessService::ensure_path_writable(&path)?;
+
+    let content = fs::read(&path).map_err(|e| AppError::io(&path, e))?;
+    let backups = list_codex_config_backups()?;
+    if let Some(latest) = backups.first() {
+        if fs::read(&latest.path).ok().as_deref() == Some(content.as_slice()) {
+            return Ok(Some(latest.id.clone()));
+        }
+    }
+
+    let stamp = format!(
+        "{CONFIG_BACKUP_PREFIX}{}",
+        chrono::Local::now().format("%Y%m%d_%H%M%S_%3f")
+    );
+    let dir = get_codex_backup_dir();
+    // 同一毫秒内多次写入时追加序号，避免覆盖刚保存的备份
+    let mut id = stamp.clone();
+    let mut n = 1;
+    while dir.join(format!("{id}.toml")).exists() {
+        id = format!("{stamp}-{n}");
+        n += 1;
+    }
+    atomic_write(&dir.join(format!("{id}.toml")), &content)?;
+
+    for stale in backups.iter().skip(CONFIG_BACKUP_RETAIN - 1) {
+        if let Err(e) = fs::remove_file(&stale.path) {
+            log::warn!("删除旧的 config.toml 备份失败 {}: {e}", stale.path);
+        }
+    }
+    Ok(Some(id))
+}
+
+/// 列出 config.toml 的自动备份，最新的在前
+pub fn list_codex_config_backups() -> Result<Vec<CodexConfigBackup>, AppError> {
+    let dir = get_codex_backup_dir();
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Ok(Vec::new());
+    };
+
+    let mut backups: Vec<CodexConfigBackup> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let id = path
+                .file_name()?
+                .to_str()?
+                .strip_suffix(".toml")
+                .filter(|stem| stem.starts_with(CONFIG_BACKUP_PREFIX))?
+                .to_string();
+            let meta = entry.metadata().ok()?;
+            let created_at = meta
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_secs() as i64)
+                .unwrap_or_default();
+            Some(CodexConfigBackup {
+                id,
+                path: path.to_string_lossy().to_string(),
+                size: meta.len(),
+                created_at,
+            })
+        })
+        .collect();
+    backups.sort_by(|a, b| b.id.cmp(&a.id));
+    Ok(backups)
+}
+
+/// 用指定备份覆盖 config.toml；覆盖前会先备份当前文件，恢复操作本身也可撤销
+pub fn restore_codex_config_backup(id: &str) -> Result<(), AppError> {
+    let valid_id = id.starts_with(CONFIG_BACKUP_PREFIX)
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    let backup_path = get_codex_backup_dir().join(format!("{id}.toml"));
+    if !valid_id || !backup_path.is_file() {
+        return Err(AppError::localized(
+            "codex.config_backup_not_found",
+            format!("未找到 config.toml 备份: {id}"),
+            format!("config.toml backup not found: {id}"),
+        ));
+    }
+
+    let text = fs::read_to_string(&backup_path).map_err(|e| AppError::io(&backup_path, e))?;
+    validate_config_toml(&text)?;
+    backup_codex_config()?;
+    write_text_file(&get_codex_config_path(), &text)?;
+    log::info!("已从备份 {id} 恢复 Codex config.toml");
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use tauri::State;
+
+use crate::database::dao::ApiTokenEntry;
+use crate::services::{ApiTokenScope, ApiTokenService, CreatedApiToken};
+use crate::store::AppState;
+
+/// 为外部控制入口签发令牌，明文仅返回这一次
+#[tauri::command]
+pub fn create_api_token(
+    state: State<'_, AppState>,
+    name: String,
+    scope: ApiTokenScope,
+) -> Result<CreatedApiToken, String> {
+    ApiTokenService::create(&state, &name, scope).map_err(|e| e.to_string())
+}
+
+/// 列出已签发的令牌（不含明文）
+#[tauri::command]
+pub fn list_api_tokens(state: State<'_, AppState>) -> Result<Vec<ApiTokenEntry>, String> {
+    ApiTokenService::list(&state).map_err(|e| e.to_string())
+}
+
+/// 吊销令牌，之后使用该令牌的调用都会被拒绝
+#[tauri::command]
+pub fn revoke_api_token(state: State<'_, AppState>, id: String) -> Result<bool, String> {
+    ApiTokenService::revoke(&state, &id).map_err(|e| e.to_string())
+}
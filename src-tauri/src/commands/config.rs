@@ -5,8 +5,14 @@ use tauri_plugin_dialog::DialogExt;
 use tauri_plugin_opener::OpenerExt;
 
 use crate::app_config::AppType;
-use crate::codex_config;
+use crate::codex_config::{self, CodexConfigBackup};
 use crate::config::{self, get_claude_settings_path, ConfigStatus};
+use crate::services::{
+    ClaudeEffectiveSettings, ClaudeHook, ClaudeHookService, ClaudePermissionService,
+    ClaudePermissions, ClaudeSettingsLayerService, ConfigConvertService, ConfigRepairService,
+    ConversionResult, CorruptedLiveFile, LiveConfigRepairReport, McpConvertDirection,
+    PermissionList, PermissionPreset, ProviderService, ResumeSyncPreview, SyncPauseService,
+};
 
 /// 获取 Claude Code 配置状态
 #[tauri::command]
@@ -41,6 +47,116 @@ pub async fn get_config_status(app: String) -> Result<ConfigStatus, String> {
     }
 }
 
+/// 合并 settings.json、settings.local.json 与托管策略后的 Claude 实际配置，
+/// 并列出 CLI Hub 写入但被更高优先级层级覆盖的键
+#[tauri::command]
+pub async fn get_claude_effective_settings() -> Result<ClaudeEffectiveSettings, String> {
+    Ok(ClaudeSettingsLayerService::effective())
+}
+
+/// 列出 Claude 钩子；providerId 为空时返回通用配置片段中的全局钩子
+#[tauri::command]
+pub async fn list_claude_hooks(
+    providerId: Option<String>,
+    state: tauri::State<'_, crate::store::AppState>,
+) -> Result<Vec<ClaudeHook>, String> {
+    ClaudeHookService::list(&state, providerId.as_deref()).map_err(|e| e.to_string())
+}
+
+/// 新增 Claude 钩子并同步到 settings.json，返回该范围内的全部钩子
+#[tauri::command]
+pub async fn add_claude_hook(
+    providerId: Option<String>,
+    hook: ClaudeHook,
+    state: tauri::State<'_, crate::store::AppState>,
+) -> Result<Vec<ClaudeHook>, String> {
+    ClaudeHookService::add(&state, providerId.as_deref(), hook).map_err(|e| e.to_string())
+}
+
+/// 修改指定位置的 Claude 钩子
+#[tauri::command]
+pub async fn update_claude_hook(
+    providerId: Option<String>,
+    index: usize,
+    hook: ClaudeHook,
+    state: tauri::State<'_, crate::store::AppState>,
+) -> Result<Vec<ClaudeHook>, String> {
+    ClaudeHookService::update(&state, providerId.as_deref(), index, hook)
+        .map_err(|e| e.to_string())
+}
+
+/// 删除指定位置的 Claude 钩子
+#[tauri::command]
+pub async fn remove_claude_hook(
+    providerId: Option<String>,
+    index: usize,
+    state: tauri::State<'_, crate::store::AppState>,
+) -> Result<Vec<ClaudeHook>, String> {
+    ClaudeHookService::remove(&state, providerId.as_deref(), index)
+        .map_err(|e| e.to_string())
+}
+
+/// 获取 Claude 工具权限；providerId 为空时返回通用配置片段中的全局权限
+#[tauri::command]
+pub async fn get_claude_permissions(
+    providerId: Option<String>,
+    state: tauri::State<'_, crate::store::AppState>,
+) -> Result<ClaudePermissions, String> {
+    ClaudePermissionService::get(&state, providerId.as_deref()).map_err(|e| e.to_string())
+}
+
+/// 整体保存 Claude 工具权限并同步到 settings.json
+#[tauri::command]
+pub async fn set_claude_permissions(
+    providerId: Option<String>,
+    permissions: ClaudePermissions,
+    state: tauri::State<'_, crate::store::AppState>,
+) -> Result<ClaudePermissions, String> {
+    ClaudePermissionService::set(&state, providerId.as_deref(), permissions)
+        .map_err(|e| e.to_string())
+}
+
+/// 向放行 / 拒绝 / 询问列表添加一条规则
+#[tauri::command]
+pub async fn add_claude_permission_rule(
+    providerId: Option<String>,
+    list: PermissionList,
+    rule: String,
+    state: tauri::State<'_, crate::store::AppState>,
+) -> Result<ClaudePermissions, String> {
+    ClaudePermissionService::add_rule(&state, providerId.as_deref(), list, &rule)
+        .map_err(|e| e.to_string())
+}
+
+/// 从指定列表移除一条规则
+#[tauri::command]
+pub async fn remove_claude_permission_rule(
+    providerId: Option<String>,
+    list: PermissionList,
+    rule: String,
+    state: tauri::State<'_, crate::store::AppState>,
+) -> Result<ClaudePermissions, String> {
+    ClaudePermissionService::remove_rule(&state, providerId.as_deref(), list, &rule)
+        .map_err(|e| e.to_string())
+}
+
+/// 内置权限预设（只读模式等）
+#[tauri::command]
+pub async fn list_claude_permission_presets() -> Result<Vec<PermissionPreset>, String> {
+    Ok(ClaudePermissionService::presets())
+}
+
+/// 把权限预设合并进现有规则
+#[tauri::command]
+pub async fn apply_claude_permission_preset(
+    providerId: Option<String>,
+    presetId: String,
+    state: tauri::State<'_, crate::store::AppState>,
+) -> Result<ClaudePermissions, String> {
+    ClaudePermissionService::apply_preset(&state, providerId.as_deref(), &presetId)
+        .map_err(|e| e.to_string())
+}
+
 /// 获取 Claude Code 配置文件路径
 #[tauri::command]
 pub async fn get_claude_code_config_path() -> Result<String, String> {
@@ -217,5 +333,93 @@ pub async fn set_common_config_snippet(
         .db
         .set_config_snippet(&app_type, value)
         .map_err(|e| e.to_string())?;
+
+    // 通用片段中的 hooks / permissions 即全局钩子与权限，直接编辑片段时同样需要同步
+    if app_type == AppType::Claude.as_str() {
+        ProviderService::sync_claude_overlays(&state, &AppType::Claude);
+    }
     Ok(())
 }
+
+/// 检测指定应用中无法解析的 live 配置文件
+#[tauri::command]
+pub async fn check_live_config(app: String) -> Result<Vec<CorruptedLiveFile>, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    Ok(ConfigRepairService::detect(&app_type))
+}
+
+/// 备份损坏的 live 配置文件，并按数据库中的当前供应商与 MCP 状态重建
+#[tauri::command]
+pub async fn repair_live_config(
+    state: tauri::State<'_, crate::store::AppState>,
+    app: String,
+) -> Result<LiveConfigRepairReport, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    ConfigRepairService::repair(&state, app_type).map_err(|e| e.to_string())
+}
+
+/// 是否已暂停对 live 配置的管理
+#[tauri::command]
+pub async fn get_management_paused() -> Result<bool, String> {
+    Ok(crate::services::is_management_paused())
+}
+
+/// 暂停管理：此后供应商切换、MCP 与提示词变更仅写入数据库
+#[tauri::command]
+pub async fn pause_management(app: AppHandle) -> Result<bool, String> {
+    crate::tray::set_management_paused_internal(&app, true).map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+/// 预览恢复管理时将写入 live 配置的差异
+#[tauri::command]
+pub async fn preview_resume_management(
+    state: tauri::State<'_, crate::store::AppState>,
+) -> Result<Vec<ResumeSyncPreview>, String> {
+    SyncPauseService::preview(&state).map_err(|e| e.to_string())
+}
+
+/// 恢复管理并统一同步暂停期间的变更
+#[tauri::command]
+pub async fn resume_management(app: AppHandle) -> Result<Vec<ResumeSyncPreview>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::tray::set_management_paused_internal(&app, false)
+    })
+    .await
+    .map_err(|e| format!("恢复管理失败: {e}"))?
+    .map_err(|e| e.to_string())
+}
+
+/// MCP 配置 JSON 与 TOML 互转，内容错误时在结果中返回出错位置
+#[tauri::command]
+pub async fn convert_mcp_config(
+    direction: McpConvertDirection,
+    content: String,
+) -> Result<ConversionResult, String> {
+    Ok(ConfigConvertService::convert_mcp(direction, &content))
+}
+
+/// 将供应商配置从一个应用的格式转换为另一个应用的格式
+#[tauri::command]
+pub async fn convert_provider_settings(
+    app_type_from: String,
+    app_type_to: String,
+    content: String,
+) -> Result<ConversionResult, String> {
+    let from = AppType::from_str(&app_type_from).map_err(|e| e.to_string())?;
+    let to = AppType::from_str(&app_type_to).map_err(|e| e.to_string())?;
+    Ok(ConfigConvertService::convert_provider_settings(&from, &to, &content))
+}
+
+/// 列出 MCP 同步改写前自动保存的 Codex config.toml 备份
+#[tauri::command]
+pub async fn list_codex_config_backups() -> Result<Vec<CodexConfigBackup>, String> {
+    codex_config::list_codex_config_backups().map_err(|e| e.to_string())
+}
+
+/// 用指定备份恢复 Codex config.toml
+#[tauri::command]
+pub async fn restore_codex_config_backup(id: String) -> Result<bool, String> {
+    codex_config::restore_codex_config_backup(&id).map_err(|e| e.to_string())?;
+    Ok(true)
+}
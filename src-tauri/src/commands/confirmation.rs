@@ -0,0 +1,42 @@
+use std::str::FromStr;
+use tauri::State;
+
+use crate::app_config::AppType;
+use crate::services::confirmation::{backup_display_name, ConfirmationChallenge};
+use crate::services::{ConfirmAction, ConfirmationService, McpService};
+use crate::store::AppState;
+
+/// 为破坏性操作申请确认令牌
+///
+/// `resource` 与目标命令的资源标识一致：供应商为 `app:id`，MCP 为服务器 ID，
+/// SQL 导入为文件路径，备份恢复为备份路径或 ID。
+#[tauri::command]
+pub fn request_confirmation(
+    state: State<'_, AppState>,
+    action: ConfirmAction,
+    resource: String,
+) -> Result<ConfirmationChallenge, String> {
+    let display_name = match action {
+        ConfirmAction::DeleteProvider => {
+            let (app, id) = resource
+                .split_once(':')
+                .ok_or_else(|| format!("Invalid provider resource: {resource}"))?;
+            let app_type = AppType::from_str(app).map_err(|e| e.to_string())?;
+            state
+                .storage
+                .get_all_providers(app_type.as_str())
+                .map_err(|e| e.to_string())?
+                .get(id)
+                .map(|p| p.name.clone())
+                .ok_or_else(|| format!("供应商 {id} 不存在"))?
+        }
+        ConfirmAction::DeleteMcpServer => McpService::get_all_servers(&state)
+            .map_err(|e| e.to_string())?
+            .get(&resource)
+            .map(|s| s.name.clone())
+            .ok_or_else(|| format!("MCP server not found: {resource}"))?,
+        ConfirmAction::ImportSql | ConfirmAction::RestoreBackup => backup_display_name(&resource),
+    };
+
+    ConfirmationService::request(action, &resource, &display_name).map_err(|e| e.to_string())
+}
@@ -1,15 +1,47 @@
+use crate::commands::skill::SkillServiceState;
 use crate::deeplink::{
-    import_mcp_from_deeplink, import_prompt_from_deeplink, import_provider_from_deeplink,
-    import_skill_from_deeplink, parse_deeplink_url, DeepLinkImportRequest,
+    build_mcp_deeplink, confirm_deeplink, dismiss_deeplink, fill_mcp_secrets,
+    get_deeplink_audit_log, import_bundle_from_deeplink, import_mcp_from_deeplink,
+    import_prompt_from_deeplink, import_provider_from_deeplink, import_skill_from_deeplink,
+    install_skill_from_deeplink, list_pending_deeplinks, mcp_secret_prompts, parse_bundle_manifest,
+    parse_mcp_apps, receive_deeplink, DeepLinkAuditEntry, DeepLinkBundle, DeepLinkImportRequest,
+    McpSecretAnswer, McpSecretPrompt, PendingDeepLinkImport, SkillInstallProgress,
+    SKILL_INSTALL_PROGRESS_EVENT,
 };
 use crate::store::AppState;
-use tauri::State;
+use tauri::{AppHandle, Emitter, State};
 
 /// Parse a deep link URL and return the parsed request for frontend confirmation
+///
+/// The request is quarantined and carries a confirmation token required by the import commands
 #[tauri::command]
-pub fn parse_deeplink(url: String) -> Result<DeepLinkImportRequest, String> {
-    log::info!("Parsing deep link URL: {url}");
-    parse_deeplink_url(&url).map_err(|e| e.to_string())
+pub fn parse_deeplink(
+    state: State<AppState>,
+    url: String,
+) -> Result<DeepLinkImportRequest, String> {
+    log::info!(
+        "Parsing deep link URL: {}",
+        crate::log_sanitizer::redact(&url)
+    );
+    receive_deeplink(Some(&state.db), &url, "frontend").map_err(|e| e.to_string())
+}
+
+/// List deep links waiting for confirmation
+#[tauri::command]
+pub fn list_pending_deeplink_imports() -> Result<Vec<PendingDeepLinkImport>, String> {
+    list_pending_deeplinks().map_err(|e| e.to_string())
+}
+
+/// Reject a quarantined deep link
+#[tauri::command]
+pub fn dismiss_deeplink_import(state: State<AppState>, token: String) -> Result<bool, String> {
+    dismiss_deeplink(Some(&state.db), &token).map_err(|e| e.to_string())
+}
+
+/// Get the deep link import-source log
+#[tauri::command]
+pub fn get_deeplink_import_log(state: State<AppState>) -> Result<Vec<DeepLinkAuditEntry>, String> {
+    get_deeplink_audit_log(&state.db).map_err(|e| e.to_string())
 }
 
 /// Merge configuration from Base64/URL into a deep link request
@@ -22,6 +54,52 @@ pub fn merge_deeplink_config(
     crate::deeplink::parse_and_merge_config(&request).map_err(|e| e.to_string())
 }
 
+/// Decode a bundle deep link manifest for the single confirmation dialog
+#[tauri::command]
+pub fn preview_deeplink_bundle(request: DeepLinkImportRequest) -> Result<DeepLinkBundle, String> {
+    log::info!("Previewing deep link bundle: {:?}", request.name);
+    parse_bundle_manifest(&request).map_err(|e| e.to_string())
+}
+
+/// Build a shareable MCP import link; credential-like env vars and headers become `${ASK}`
+#[tauri::command]
+pub fn generate_mcp_deeplink(
+    state: State<AppState>,
+    ids: Vec<String>,
+    apps: Vec<String>,
+) -> Result<String, String> {
+    let apps = parse_mcp_apps(&apps.join(",")).map_err(|e| e.to_string())?;
+    let mut all = state
+        .storage
+        .get_all_mcp_servers()
+        .map_err(|e| e.to_string())?;
+    let servers = ids
+        .iter()
+        .map(|id| {
+            all.shift_remove(id)
+                .ok_or_else(|| format!("MCP server '{id}' not found"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    build_mcp_deeplink(&servers, &apps).map_err(|e| e.to_string())
+}
+
+/// List the `${ASK}` placeholders in an MCP deep link that the user must fill in
+#[tauri::command]
+pub fn get_mcp_deeplink_secrets(
+    request: DeepLinkImportRequest,
+) -> Result<Vec<McpSecretPrompt>, String> {
+    mcp_secret_prompts(&request).map_err(|e| e.to_string())
+}
+
+/// Write the user's own values over the placeholders before importing
+#[tauri::command]
+pub fn fill_mcp_deeplink_secrets(
+    request: DeepLinkImportRequest,
+    answers: Vec<McpSecretAnswer>,
+) -> Result<DeepLinkImportRequest, String> {
+    fill_mcp_secrets(&request, &answers).map_err(|e| e.to_string())
+}
+
 /// Import a provider from a deep link request (legacy, kept for compatibility)
 #[tauri::command]
 pub fn import_from_deeplink(
@@ -34,6 +112,8 @@ pub fn import_from_deeplink(
         request.app
     );
 
+    let request = confirm_deeplink(Some(&state.db), &request).map_err(|e| e.to_string())?;
+
     let provider_id = import_provider_from_deeplink(&state, request).map_err(|e| e.to_string())?;
 
     log::info!("Successfully imported provider with ID: {provider_id}");
@@ -42,13 +122,24 @@ pub fn import_from_deeplink(
 }
 
 /// Import resource from a deep link request (unified handler)
+///
+/// Imports the quarantined request the token refers to; for MCP links the recipient's
+/// `answers` are written over the `${ASK}` placeholders first.
 #[tauri::command]
 pub async fn import_from_deeplink_unified(
+    app: AppHandle,
     state: State<'_, AppState>,
+    skills: State<'_, SkillServiceState>,
     request: DeepLinkImportRequest,
+    answers: Option<Vec<McpSecretAnswer>>,
 ) -> Result<serde_json::Value, String> {
+    let mut request = confirm_deeplink(Some(&state.db), &request).map_err(|e| e.to_string())?;
     log::info!("Importing {} resource from deep link", request.resource);
 
+    if let Some(answers) = answers.filter(|a| !a.is_empty() && request.resource == "mcp") {
+        request = fill_mcp_secrets(&request, &answers).map_err(|e| e.to_string())?;
+    }
+
     match request.resource.as_str() {
         "provider" => {
             let provider_id =
@@ -77,11 +168,41 @@ pub async fn import_from_deeplink_unified(
             }))
         }
         "skill" => {
+            let install = request.install.unwrap_or(false);
+            let directory = request.directory.clone();
             let skill_key =
                 import_skill_from_deeplink(&state, request).map_err(|e| e.to_string())?;
+
+            // The repo stays registered even if the install step fails
+            let installed = match directory.filter(|_| install) {
+                Some(directory) => {
+                    let service = skills.get().await?;
+                    let emit = |progress: &SkillInstallProgress| {
+                        if let Err(e) = app.emit(SKILL_INSTALL_PROGRESS_EVENT, progress) {
+                            log::warn!("Failed to emit skill install progress: {e}");
+                        }
+                    };
+                    Some(
+                        install_skill_from_deeplink(&state, &service, &skill_key, &directory, emit)
+                            .await
+                            .map_err(|e| e.to_string())?,
+                    )
+                }
+                None => None,
+            };
             Ok(serde_json::json!({
                 "type": "skill",
-                "key": skill_key
+                "key": skill_key,
+                "installed": installed
+            }))
+        }
+        "bundle" => {
+            let result = import_bundle_from_deeplink(&state, request).map_err(|e| e.to_string())?;
+            Ok(serde_json::json!({
+                "type": "bundle",
+                "providerId": result.provider_id,
+                "mcpIds": result.mcp_ids,
+                "promptId": result.prompt_id
             }))
         }
         _ => Err(format!("Unsupported resource type: {}", request.resource)),
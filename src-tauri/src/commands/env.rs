@@ -1,7 +1,11 @@
+use crate::services::demo::ensure_not_demo;
 use crate::services::env_checker::{check_env_conflicts as check_conflicts, EnvConflict};
 use crate::services::env_manager::{
-    delete_env_vars as delete_vars, restore_from_backup, BackupInfo,
+    delete_env_vars as delete_vars, list_env_backups as list_backups, preview_env_deletion,
+    preview_env_restore, restore_env_vars as restore_vars, restore_from_backup, BackupInfo,
+    ShellFileDiff,
 };
+use crate::services::{ConfirmAction, ConfirmationInput, ConfirmationService};
 
 /// Check environment variable conflicts for a specific app
 #[tauri::command]
@@ -12,11 +16,66 @@ pub fn check_env_conflicts(app: String) -> Result<Vec<EnvConflict>, String> {
 /// Delete environment variables with backup
 #[tauri::command]
 pub fn delete_env_vars(conflicts: Vec<EnvConflict>) -> Result<BackupInfo, String> {
+    ensure_not_demo().map_err(|e| e.to_string())?;
     delete_vars(conflicts)
 }
 
 /// Restore environment variables from backup file
 #[tauri::command]
-pub fn restore_env_backup(backup_path: String) -> Result<(), String> {
+pub fn restore_env_backup(
+    backup_path: String,
+    confirmation_token: Option<String>,
+    confirmation_text: Option<String>,
+    force: Option<bool>,
+) -> Result<(), String> {
+    ConfirmationService::verify(
+        ConfirmAction::RestoreBackup,
+        &backup_path,
+        &ConfirmationInput::new(confirmation_token, confirmation_text, force),
+    )
+    .map_err(|e| e.to_string())?;
+    ensure_not_demo().map_err(|e| e.to_string())?;
     restore_from_backup(backup_path)
 }
+
+/// List environment variable backups, newest first
+#[tauri::command]
+pub fn list_env_backups() -> Result<Vec<BackupInfo>, String> {
+    list_backups()
+}
+
+/// Restore only the selected variables from a backup (all when `vars` is empty)
+#[tauri::command]
+pub fn restore_env_vars(
+    backup_id: String,
+    vars: Vec<String>,
+    confirmation_token: Option<String>,
+    confirmation_text: Option<String>,
+    force: Option<bool>,
+) -> Result<(), String> {
+    ConfirmationService::verify(
+        ConfirmAction::RestoreBackup,
+        &backup_id,
+        &ConfirmationInput::new(confirmation_token, confirmation_text, force),
+    )
+    .map_err(|e| e.to_string())?;
+    ensure_not_demo().map_err(|e| e.to_string())?;
+    restore_vars(&backup_id, &vars)
+}
+
+/// Preview shell profile changes before deleting variables
+#[tauri::command]
+pub fn preview_env_vars_deletion(
+    conflicts: Vec<EnvConflict>,
+) -> Result<Vec<ShellFileDiff>, String> {
+    preview_env_deletion(&conflicts)
+}
+
+/// Preview shell profile changes before restoring variables from a backup
+#[tauri::command]
+pub fn preview_env_vars_restore(
+    backup_id: String,
+    vars: Vec<String>,
+) -> Result<Vec<ShellFileDiff>, String> {
+    preview_env_restore(&backup_id, &vars)
+}
@@ -2,23 +2,36 @@
 
 use serde_json::{json, Value};
 use std::path::PathBuf;
-use tauri::State;
+use tauri::{AppHandle, Manager, State};
 use tauri_plugin_dialog::DialogExt;
 
+use crate::database::{
+    BackupVerification, Database, DbBackupInfo, OrphanCleanupReport, RestorePreview,
+    SqlExportOptions,
+};
 use crate::error::AppError;
 use crate::services::provider::ProviderService;
+use crate::services::{
+    ConfirmAction, ConfirmationInput, ConfirmationService, DiagnosticsBundle, DiagnosticsService,
+    ExternalBackupService, LegacyConfigReport, LegacyItemRef, LegacyMigrationResult,
+    LegacyMigrationService, TransferService, TransferSession, VcsExportService,
+};
 use crate::store::AppState;
 
 /// 导出数据库为 SQL 备份
+///
+/// `options.domains` 为空时导出整个数据库；指定后仅导出对应的供应商/MCP/提示词数据，
+/// 导入方会合并而非覆盖。`redactSecrets` 会抹去 JSON 字段中的密钥
 #[tauri::command]
 pub async fn export_config_to_file(
     #[allow(non_snake_case)] filePath: String,
+    options: Option<SqlExportOptions>,
     state: State<'_, AppState>,
 ) -> Result<Value, String> {
     let db = state.db.clone();
     tauri::async_runtime::spawn_blocking(move || {
         let target_path = PathBuf::from(&filePath);
-        db.export_sql(&target_path)?;
+        db.export_sql_with(&target_path, &options.unwrap_or_default())?;
         Ok::<_, AppError>(json!({
             "success": true,
             "message": "SQL exported successfully",
@@ -34,8 +47,18 @@ pub async fn export_config_to_file(
 #[tauri::command]
 pub async fn import_config_from_file(
     #[allow(non_snake_case)] filePath: String,
+    #[allow(non_snake_case)] confirmationToken: Option<String>,
+    #[allow(non_snake_case)] confirmationText: Option<String>,
+    force: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<Value, String> {
+    ConfirmationService::verify(
+        ConfirmAction::ImportSql,
+        &filePath,
+        &ConfirmationInput::new(confirmationToken, confirmationText, force),
+    )
+    .map_err(|e| e.to_string())?;
+
     let db = state.db.clone();
     let db_for_state = db.clone();
     tauri::async_runtime::spawn_blocking(move || {
@@ -64,6 +87,60 @@ pub async fn import_config_from_file(
     .map_err(|e: AppError| e.to_string())
 }
 
+/// 启动局域网传输服务，供另一台设备拉取加密的完整导出
+#[tauri::command]
+pub async fn start_transfer_server(state: State<'_, AppState>) -> Result<TransferSession, String> {
+    TransferService::start_server(state.db.clone())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn stop_transfer_server() -> Result<bool, String> {
+    TransferService::stop_server().map_err(|e| e.to_string())
+}
+
+/// 从另一台设备的传输服务拉取完整导出并导入
+#[tauri::command]
+pub async fn receive_transfer(
+    address: String,
+    code: String,
+    #[allow(non_snake_case)] confirmationToken: Option<String>,
+    #[allow(non_snake_case)] confirmationText: Option<String>,
+    force: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Value, String> {
+    ConfirmationService::verify(
+        ConfirmAction::ImportSql,
+        &address,
+        &ConfirmationInput::new(confirmationToken, confirmationText, force),
+    )
+    .map_err(|e| e.to_string())?;
+
+    let backup_id = TransferService::receive(state.db.clone(), &address, &code)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let db = state.db.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let app_state = AppState::new(db);
+        if let Err(err) = ProviderService::sync_current_from_db(&app_state) {
+            log::warn!("传输导入后同步 live 配置失败: {err}");
+        }
+        if let Err(err) = crate::settings::reload_settings() {
+            log::warn!("传输导入后重载设置失败: {err}");
+        }
+    })
+    .await
+    .map_err(|e| format!("传输导入后同步失败: {e}"))?;
+
+    Ok(json!({
+        "success": true,
+        "message": "Transfer imported successfully",
+        "backupId": backup_id
+    }))
+}
+
 #[tauri::command]
 pub async fn sync_current_providers_live(state: State<'_, AppState>) -> Result<Value, String> {
     let db = state.db.clone();
@@ -80,6 +157,180 @@ pub async fn sync_current_providers_live(state: State<'_, AppState>) -> Result<V
     .map_err(|e: AppError| e.to_string())
 }
 
+/// 将供应商、MCP 与提示词导出为适合提交到 Git 的目录结构
+#[tauri::command]
+pub async fn write_vcs_export(dir: String, state: State<'_, AppState>) -> Result<Value, String> {
+    let db = state.db.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let app_state = AppState::new(db);
+        let summary = VcsExportService::write(&app_state, &PathBuf::from(&dir))?;
+        serde_json::to_value(summary).map_err(|e| AppError::JsonSerialize { source: e })
+    })
+    .await
+    .map_err(|e| format!("导出到版本控制目录失败: {e}"))?
+    .map_err(|e: AppError| e.to_string())
+}
+
+/// 生成脱敏后的诊断包（zip），供用户附加到 Issue
+#[tauri::command]
+pub async fn create_diagnostics_bundle(
+    app: AppHandle,
+    filePath: String,
+    state: State<'_, AppState>,
+) -> Result<DiagnosticsBundle, String> {
+    let db = state.db.clone();
+    let log_dir = app.path().app_log_dir().ok();
+    tauri::async_runtime::spawn_blocking(move || {
+        let app_state = AppState::new(db);
+        DiagnosticsService::create_bundle(&app_state, log_dir.as_deref(), &PathBuf::from(&filePath))
+    })
+    .await
+    .map_err(|e| format!("生成诊断包失败: {e}"))?
+    .map_err(|e: AppError| e.to_string())
+}
+
+/// 立即导出一次到外部备份目录
+#[tauri::command]
+pub async fn backup_now_to_external(state: State<'_, AppState>) -> Result<Value, String> {
+    let db = state.db.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let app_state = AppState::new(db);
+        let status = ExternalBackupService::backup_now(&app_state)?;
+        serde_json::to_value(status).map_err(|e| AppError::JsonSerialize { source: e })
+    })
+    .await
+    .map_err(|e| format!("外部备份失败: {e}"))?
+    .map_err(|e: AppError| e.to_string())
+}
+
+/// 获取外部备份最近一次成功/失败的状态
+#[tauri::command]
+pub fn get_external_backup_status(state: State<'_, AppState>) -> Result<Value, String> {
+    let status = ExternalBackupService::status(&state).map_err(|e| e.to_string())?;
+    serde_json::to_value(status).map_err(|e| e.to_string())
+}
+
+/// 清理已删除供应商遗留的端点与不再使用的设置项；dry_run 时只返回报告
+#[tauri::command]
+pub fn cleanup_orphaned_data(
+    state: State<'_, AppState>,
+    dry_run: Option<bool>,
+) -> Result<OrphanCleanupReport, String> {
+    state
+        .db
+        .cleanup_orphaned_data(dry_run.unwrap_or(false))
+        .map_err(|e| e.to_string())
+}
+
+/// 列出自动生成的数据库备份（导入等操作前写入），按时间倒序
+#[tauri::command]
+pub fn list_db_backups() -> Result<Vec<DbBackupInfo>, String> {
+    Database::list_db_backups().map_err(|e| e.to_string())
+}
+
+/// 校验数据库备份的校验和、完整性与 schema 版本兼容性
+#[tauri::command]
+pub async fn verify_backup(id: String) -> Result<BackupVerification, String> {
+    tauri::async_runtime::spawn_blocking(move || Database::verify_backup(&id))
+        .await
+        .map_err(|e| format!("校验备份失败: {e}"))?
+        .map_err(|e| e.to_string())
+}
+
+/// 预览恢复备份后供应商、MCP 与提示词的新增/删除/变更数量，不做任何修改
+#[tauri::command]
+pub async fn preview_restore(
+    id: String,
+    state: State<'_, AppState>,
+) -> Result<RestorePreview, String> {
+    let db = state.db.clone();
+    tauri::async_runtime::spawn_blocking(move || db.preview_restore(&id))
+        .await
+        .map_err(|e| format!("预览恢复失败: {e}"))?
+        .map_err(|e| e.to_string())
+}
+
+/// 用数据库备份覆盖当前数据；校验不通过时拒绝恢复，恢复前会先备份当前数据库
+#[tauri::command]
+pub async fn restore_db_backup(
+    id: String,
+    #[allow(non_snake_case)] confirmationToken: Option<String>,
+    #[allow(non_snake_case)] confirmationText: Option<String>,
+    force: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Value, String> {
+    ConfirmationService::verify(
+        ConfirmAction::RestoreBackup,
+        &id,
+        &ConfirmationInput::new(confirmationToken, confirmationText, force),
+    )
+    .map_err(|e| e.to_string())?;
+
+    let db = state.db.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let backup_id = db.restore_db_backup(&id)?;
+
+        let app_state = AppState::new(db);
+        if let Err(err) = ProviderService::sync_current_from_db(&app_state) {
+            log::warn!("恢复备份后同步 live 配置失败: {err}");
+        }
+        if let Err(err) = crate::settings::reload_settings() {
+            log::warn!("恢复备份后重载设置失败: {err}");
+        }
+
+        Ok::<_, AppError>(json!({
+            "success": true,
+            "message": "Backup restored successfully",
+            "restoredFrom": id,
+            "backupId": backup_id
+        }))
+    })
+    .await
+    .map_err(|e| format!("恢复备份失败: {e}"))?
+    .map_err(|e: AppError| e.to_string())
+}
+
+/// 删除一个自动生成的数据库备份及其校验清单
+#[tauri::command]
+pub fn delete_db_backup(id: String) -> Result<bool, String> {
+    Database::delete_db_backup(&id).map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+/// 逐项分析旧版 config.json 中可迁移到数据库的条目及其有效性
+#[tauri::command]
+pub fn analyze_legacy_config(state: State<'_, AppState>) -> Result<LegacyConfigReport, String> {
+    LegacyMigrationService::analyze(&state).map_err(|e| e.to_string())
+}
+
+/// 仅迁移选中的 config.json 条目；全部有效条目迁移完成后 config.json 会被归档
+#[tauri::command]
+pub async fn migrate_selected(
+    items: Vec<LegacyItemRef>,
+    state: State<'_, AppState>,
+) -> Result<LegacyMigrationResult, String> {
+    let db = state.db.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let app_state = AppState::new(db);
+        let result = LegacyMigrationService::migrate_selected(&app_state, items)?;
+        if !result.migrated.is_empty() {
+            if let Err(err) = ProviderService::sync_current_from_db(&app_state) {
+                log::warn!("迁移后同步 live 配置失败: {err}");
+            }
+        }
+        Ok::<_, AppError>(result)
+    })
+    .await
+    .map_err(|e| format!("迁移配置失败: {e}"))?
+    .map_err(|e: AppError| e.to_string())
+}
+
+/// 将归档的 config.json.migrated 恢复为 config.json
+#[tauri::command]
+pub fn restore_legacy_config() -> Result<String, String> {
+    LegacyMigrationService::restore_archived().map_err(|e| e.to_string())
+}
+
 /// 保存文件对话框
 #[tauri::command]
 pub async fn save_file_dialog<R: tauri::Runtime>(
@@ -8,7 +8,11 @@ use tauri::State;
 
 use crate::app_config::AppType;
 use crate::claude_mcp;
-use crate::services::McpService;
+use crate::services::{
+    ConfirmAction, ConfirmationInput, ConfirmationService, McpProfile, McpProfileService,
+    McpService,
+};
+use crate::startup::StartupState;
 use crate::store::AppState;
 
 /// 获取 Claude MCP 状态
@@ -41,6 +45,18 @@ pub async fn validate_mcp_command(cmd: String) -> Result<bool, String> {
     claude_mcp::validate_command_in_path(&cmd).map_err(|e| e.to_string())
 }
 
+/// 将 MCP 服务器规格转换为目标传输方式（stdio ⇄ http/sse），并检查桥接所需命令
+#[tauri::command]
+pub async fn convert_mcp_transport(
+    spec: serde_json::Value,
+    target: crate::mcp::McpTransport,
+    bridge: Option<crate::mcp::McpBridge>,
+    port: Option<u16>,
+) -> Result<crate::mcp::McpTransportConversion, String> {
+    crate::mcp::convert_transport(&spec, target, bridge.unwrap_or_default(), port)
+        .map_err(|e| e.to_string())
+}
+
 #[derive(Serialize)]
 pub struct McpConfigResponse {
     pub config_path: String,
@@ -83,7 +99,10 @@ pub async fn upsert_mcp_server_in_config(
 
     // 读取现有的服务器（如果存在）
     let existing_server = {
-        let servers = state.db.get_all_mcp_servers().map_err(|e| e.to_string())?;
+        let servers = state
+            .storage
+            .get_all_mcp_servers()
+            .map_err(|e| e.to_string())?;
         servers.get(&id).cloned()
     };
 
@@ -156,14 +175,57 @@ pub async fn set_mcp_enabled(
 // v3.7.0 新增：统一 MCP 管理命令
 // ============================================================================
 
-use crate::app_config::McpServer;
+use crate::app_config::{McpServer, McpSortUpdate, QuarantinedMcpEntry};
 
-/// 获取所有 MCP 服务器（统一结构）
+/// 获取所有 MCP 服务器（统一结构）；masked 为 true 时遮蔽 env 值
 #[tauri::command]
 pub async fn get_mcp_servers(
     state: State<'_, AppState>,
+    startup: State<'_, StartupState>,
+    masked: Option<bool>,
 ) -> Result<IndexMap<String, McpServer>, String> {
-    McpService::get_all_servers(&state).map_err(|e| e.to_string())
+    startup.wait_ready().await;
+    let servers = McpService::get_all_servers(&state).map_err(|e| e.to_string())?;
+    if !masked.unwrap_or(false) {
+        return Ok(servers);
+    }
+    Ok(servers
+        .into_iter()
+        .map(|(id, server)| (id, McpService::masked(&server)))
+        .collect())
+}
+
+/// 设置 MCP 服务器的单个环境变量，默认返回遮蔽后的服务器
+#[tauri::command]
+pub async fn set_mcp_env_var(
+    state: State<'_, AppState>,
+    id: String,
+    key: String,
+    value: String,
+    reveal: Option<bool>,
+) -> Result<McpServer, String> {
+    let server = McpService::set_env_var(&state, &id, &key, &value).map_err(|e| e.to_string())?;
+    Ok(mask_unless(server, reveal))
+}
+
+/// 删除 MCP 服务器的单个环境变量，默认返回遮蔽后的服务器
+#[tauri::command]
+pub async fn remove_mcp_env_var(
+    state: State<'_, AppState>,
+    id: String,
+    key: String,
+    reveal: Option<bool>,
+) -> Result<McpServer, String> {
+    let server = McpService::remove_env_var(&state, &id, &key).map_err(|e| e.to_string())?;
+    Ok(mask_unless(server, reveal))
+}
+
+fn mask_unless(server: McpServer, reveal: Option<bool>) -> McpServer {
+    if reveal.unwrap_or(false) {
+        server
+    } else {
+        McpService::masked(&server)
+    }
 }
 
 /// 添加或更新 MCP 服务器
@@ -177,10 +239,32 @@ pub async fn upsert_mcp_server(
 
 /// 删除 MCP 服务器
 #[tauri::command]
-pub async fn delete_mcp_server(state: State<'_, AppState>, id: String) -> Result<bool, String> {
+pub async fn delete_mcp_server(
+    state: State<'_, AppState>,
+    id: String,
+    confirmation_token: Option<String>,
+    confirmation_text: Option<String>,
+    force: Option<bool>,
+) -> Result<bool, String> {
+    ConfirmationService::verify(
+        ConfirmAction::DeleteMcpServer,
+        &id,
+        &ConfirmationInput::new(confirmation_token, confirmation_text, force),
+    )
+    .map_err(|e| e.to_string())?;
     McpService::delete_server(&state, &id).map_err(|e| e.to_string())
 }
 
+/// 复制 MCP 服务器为新 ID
+#[tauri::command]
+pub async fn duplicate_mcp_server(
+    state: State<'_, AppState>,
+    id: String,
+    new_id: String,
+) -> Result<McpServer, String> {
+    McpService::duplicate_server(&state, &id, &new_id).map_err(|e| e.to_string())
+}
+
 /// 切换 MCP 服务器在指定应用的启用状态
 #[tauri::command]
 pub async fn toggle_mcp_app(
@@ -192,3 +276,82 @@ pub async fn toggle_mcp_app(
     let app_ty = AppType::from_str(&app).map_err(|e| e.to_string())?;
     McpService::toggle_app(&state, &server_id, app_ty, enabled).map_err(|e| e.to_string())
 }
+
+/// 更新 MCP 服务器排序（同时决定写入各应用 live 配置的条目顺序）
+#[tauri::command]
+pub async fn update_mcp_sort_order(
+    state: State<'_, AppState>,
+    updates: Vec<McpSortUpdate>,
+) -> Result<bool, String> {
+    McpService::update_sort_order(&state, updates).map_err(|e| e.to_string())
+}
+
+/// 列出 MCP 方案（可按应用过滤）
+#[tauri::command]
+pub async fn list_mcp_profiles(
+    state: State<'_, AppState>,
+    app: Option<String>,
+) -> Result<Vec<McpProfile>, String> {
+    let app_ty = app
+        .map(|a| AppType::from_str(&a))
+        .transpose()
+        .map_err(|e| e.to_string())?;
+    McpProfileService::list(&state, app_ty.as_ref()).map_err(|e| e.to_string())
+}
+
+/// 将应用当前的 MCP 启用状态保存为方案
+#[tauri::command]
+pub async fn save_current_as_profile(
+    state: State<'_, AppState>,
+    name: String,
+    app: String,
+) -> Result<McpProfile, String> {
+    let app_ty = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    McpProfileService::save_current(&state, &name, &app_ty).map_err(|e| e.to_string())
+}
+
+/// 应用 MCP 方案：批量切换启用状态并同步一次
+#[tauri::command]
+pub async fn apply_mcp_profile(
+    state: State<'_, AppState>,
+    name: String,
+    app: String,
+) -> Result<IndexMap<String, McpServer>, String> {
+    let app_ty = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    McpProfileService::apply(&state, &name, &app_ty).map_err(|e| e.to_string())
+}
+
+/// 删除 MCP 方案
+#[tauri::command]
+pub async fn delete_mcp_profile(
+    state: State<'_, AppState>,
+    name: String,
+    app: String,
+) -> Result<bool, String> {
+    let app_ty = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    McpProfileService::delete(&state, &name, &app_ty).map_err(|e| e.to_string())
+}
+
+/// 列出导入时被隔离的 MCP 条目
+#[tauri::command]
+pub async fn list_quarantined_mcp(
+    state: State<'_, AppState>,
+) -> Result<Vec<QuarantinedMcpEntry>, String> {
+    McpService::list_quarantined(&state).map_err(|e| e.to_string())
+}
+
+/// 重试导入隔离的 MCP 条目，`raw` 为用户修正后的内容（缺省时使用原始内容）
+#[tauri::command]
+pub async fn retry_quarantined_mcp(
+    state: State<'_, AppState>,
+    id: String,
+    raw: Option<String>,
+) -> Result<McpServer, String> {
+    McpService::retry_quarantined(&state, &id, raw).map_err(|e| e.to_string())
+}
+
+/// 放弃隔离的 MCP 条目
+#[tauri::command]
+pub async fn discard_quarantined_mcp(state: State<'_, AppState>, id: String) -> Result<(), String> {
+    McpService::discard_quarantined(&state, &id).map_err(|e| e.to_string())
+}
@@ -1,7 +1,14 @@
 #![allow(non_snake_case)]
 
+use crate::database::{MigrationStatus, ProviderIntegrityIssue};
 use crate::init_status::InitErrorPayload;
-use tauri::AppHandle;
+use crate::services::{
+    AppWriteCapability, ProviderUpgradeReport, ProviderUpgradeService, StatusSummary,
+    StatusSummaryService, WriteAccessService,
+};
+use crate::startup::StartupState;
+use crate::store::AppState;
+use tauri::{AppHandle, State};
 use tauri_plugin_opener::OpenerExt;
 
 /// 打开外部链接
@@ -51,3 +58,48 @@ pub async fn is_portable_mode() -> Result<bool, String> {
 pub async fn get_init_error() -> Result<Option<InitErrorPayload>, String> {
     Ok(crate::init_status::get_init_error())
 }
+
+/// 获取启动时供应商数据完整性检查发现的问题（含已自动修复的项）
+#[tauri::command]
+pub fn get_init_warnings() -> Vec<ProviderIntegrityIssue> {
+    crate::init_status::get_init_warnings()
+}
+
+/// 获取启动时数据库结构升级与 config.json 迁移的进度，供前端在订阅事件前补齐当前状态
+#[tauri::command]
+pub fn get_migration_status() -> MigrationStatus {
+    crate::database::migration_status()
+}
+
+/// 获取最近一次应用升级后供应商配置迁移的报告（改写了哪些供应商）
+#[tauri::command]
+pub fn get_provider_upgrade_report(
+    state: State<'_, AppState>,
+) -> Result<Option<ProviderUpgradeReport>, String> {
+    ProviderUpgradeService::last_report(&state).map_err(|e| e.to_string())
+}
+
+/// 各应用当前供应商、最近用量占比与健康标记的精简摘要，带短时缓存，适合小组件高频轮询
+#[tauri::command]
+pub fn get_status_summary(state: State<'_, AppState>) -> Result<StatusSummary, String> {
+    StatusSummaryService::get(&state).map_err(|e| e.to_string())
+}
+
+/// 重新检测各应用配置目录是否可写
+#[tauri::command]
+pub fn get_write_capabilities() -> Vec<AppWriteCapability> {
+    WriteAccessService::detect()
+}
+
+/// 后台初始化（首次导入、配置修复、技能服务）是否已完成
+#[tauri::command]
+pub fn is_startup_ready(startup: State<'_, StartupState>) -> bool {
+    startup.is_ready()
+}
+
+/// 等待后台初始化完成；进度通过 `startup-progress` 事件推送
+#[tauri::command]
+pub async fn wait_for_startup(startup: State<'_, StartupState>) -> Result<bool, String> {
+    startup.wait_ready().await;
+    Ok(true)
+}
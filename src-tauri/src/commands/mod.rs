@@ -1,6 +1,8 @@
 #![allow(non_snake_case)]
 
+mod api_token;
 mod config;
+mod confirmation;
 mod deeplink;
 mod env;
 mod import_export;
@@ -9,10 +11,15 @@ mod misc;
 mod plugin;
 mod prompt;
 mod provider;
+mod quick_actions;
+mod session;
 mod settings;
 pub mod skill;
+mod stack;
 
+pub use api_token::*;
 pub use config::*;
+pub use confirmation::*;
 pub use deeplink::*;
 pub use env::*;
 pub use import_export::*;
@@ -21,5 +28,8 @@ pub use misc::*;
 pub use plugin::*;
 pub use prompt::*;
 pub use provider::*;
+pub use quick_actions::*;
+pub use session::*;
 pub use settings::*;
 pub use skill::*;
+pub use stack::*;
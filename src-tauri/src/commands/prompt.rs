@@ -1,22 +1,53 @@
 use indexmap::IndexMap;
+use std::path::Path;
 use std::str::FromStr;
 
-use tauri::State;
+use tauri::{AppHandle, Emitter, State};
 
 use crate::app_config::AppType;
-use crate::prompt::Prompt;
-use crate::services::PromptService;
+use crate::prompt::{GeminiContextFile, Prompt, PromptSummary};
+use crate::services::{
+    CodexAgentsFile, CodexAgentsLevel, CodexAgentsService, CodexEffectiveAgents,
+    GeminiContextService, PromptCryptoService, PromptSchedule, PromptScheduleService,
+    PromptService, PromptTokenEstimate, PromptTokenService, SlashCommandExportResult,
+    SlashCommandImportResult, SlashCommandService,
+};
+use crate::startup::StartupState;
 use crate::store::AppState;
 
 #[tauri::command]
 pub async fn get_prompts(
     app: String,
     state: State<'_, AppState>,
+    startup: State<'_, StartupState>,
 ) -> Result<IndexMap<String, Prompt>, String> {
+    startup.wait_ready().await;
     let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
     PromptService::get_prompts(&state, app_type).map_err(|e| e.to_string())
 }
 
+/// 仅返回元数据，正文通过 `get_prompt_content` 按需加载
+#[tauri::command]
+pub async fn get_prompt_summaries(
+    app: String,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<PromptSummary>, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    PromptService::get_prompt_summaries(&state, app_type, limit, offset).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_prompt_content(
+    app: String,
+    id: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    PromptService::get_prompt_content(&state, app_type, &id).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn upsert_prompt(
     app: String,
@@ -48,6 +79,20 @@ pub async fn enable_prompt(
     PromptService::enable_prompt(&state, app_type, &id).map_err(|e| e.to_string())
 }
 
+/// 估算提示词占用的上下文 token；参数可为已保存提示词的 ID 或直接传入正文
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn estimate_prompt_tokens(
+    app: String,
+    promptIdOrContent: String,
+    model: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<PromptTokenEstimate, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    PromptTokenService::estimate(&state, app_type, &promptIdOrContent, model.as_deref())
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn import_prompt_from_file(
     app: String,
@@ -57,8 +102,198 @@ pub async fn import_prompt_from_file(
     PromptService::import_from_file(&state, app_type).map_err(|e| e.to_string())
 }
 
+/// 将选中的提示词导出为 Claude Code 斜杠命令；已存在的命令文件默认不覆盖
+#[tauri::command]
+pub async fn export_prompts_to_slash_commands(
+    app: String,
+    ids: Vec<String>,
+    overwrite: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<SlashCommandExportResult, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    SlashCommandService::export(&state, app_type, &ids, overwrite.unwrap_or(false))
+        .map_err(|e| e.to_string())
+}
+
+/// 从 ~/.claude/commands 导入斜杠命令为提示词
+#[tauri::command]
+pub async fn import_slash_commands(
+    app: String,
+    state: State<'_, AppState>,
+) -> Result<SlashCommandImportResult, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    SlashCommandService::import(&state, app_type).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn get_current_prompt_file_content(app: String) -> Result<Option<String>, String> {
     let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
     PromptService::get_current_file_content(app_type).map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+pub async fn get_gemini_context_files(
+    state: State<'_, AppState>,
+) -> Result<Vec<GeminiContextFile>, String> {
+    GeminiContextService::list(&state).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn upsert_gemini_context_file(
+    file_name: String,
+    content: String,
+    state: State<'_, AppState>,
+) -> Result<GeminiContextFile, String> {
+    GeminiContextService::upsert(&state, &file_name, content).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_gemini_context_file_enabled(
+    file_name: String,
+    enabled: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    GeminiContextService::set_enabled(&state, &file_name, enabled).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_gemini_context_file(
+    file_name: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    GeminiContextService::delete(&state, &file_name).map_err(|e| e.to_string())
+}
+
+/// 列出 Codex 全局及项目各层的 AGENTS.md
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn detect_codex_agents_files(
+    projectDir: Option<String>,
+) -> Result<Vec<CodexAgentsFile>, String> {
+    CodexAgentsService::detect(projectDir.as_deref().map(Path::new)).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn write_codex_agents_file(
+    level: CodexAgentsLevel,
+    projectDir: Option<String>,
+    content: String,
+) -> Result<String, String> {
+    CodexAgentsService::write(level, projectDir.as_deref().map(Path::new), &content)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn import_codex_agents_file(
+    level: CodexAgentsLevel,
+    projectDir: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    CodexAgentsService::import(&state, level, projectDir.as_deref().map(Path::new))
+        .map_err(|e| e.to_string())
+}
+
+/// Codex 在指定项目目录下实际读到的合并指令
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn get_codex_effective_agents(
+    projectDir: Option<String>,
+) -> Result<CodexEffectiveAgents, String> {
+    CodexAgentsService::effective(projectDir.as_deref().map(Path::new)).map_err(|e| e.to_string())
+}
+
+/// 加密提示词正文；不传口令时使用本机密钥
+#[tauri::command]
+pub async fn encrypt_prompt(
+    app: String,
+    id: String,
+    passphrase: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Prompt, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    PromptCryptoService::encrypt_prompt(&state, &app_type, &id, passphrase.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+/// 解除加密，恢复为明文保存
+#[tauri::command]
+pub async fn decrypt_prompt(
+    app: String,
+    id: String,
+    passphrase: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Prompt, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    PromptCryptoService::decrypt_prompt(&state, &app_type, &id, passphrase.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+/// 本次运行中解锁口令加密的提示词，返回明文
+#[tauri::command]
+pub async fn unlock_prompt(
+    app: String,
+    id: String,
+    passphrase: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    PromptCryptoService::unlock_prompt(&state, &app_type, &id, &passphrase)
+        .map_err(|e| e.to_string())
+}
+
+/// 列出提示词定时方案，指定应用时只返回该应用的方案
+#[tauri::command]
+pub async fn list_prompt_schedules(
+    app: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<PromptSchedule>, String> {
+    let app_type = app
+        .map(|a| AppType::from_str(&a))
+        .transpose()
+        .map_err(|e| e.to_string())?;
+    PromptScheduleService::list(&state, app_type.as_ref()).map_err(|e| e.to_string())
+}
+
+/// 保存应用的提示词定时方案，替换该应用已有的方案
+#[tauri::command]
+pub async fn save_prompt_schedule(
+    schedule: PromptSchedule,
+    state: State<'_, AppState>,
+) -> Result<PromptSchedule, String> {
+    PromptScheduleService::save(&state, schedule).map_err(|e| e.to_string())
+}
+
+/// 删除应用的提示词定时方案
+#[tauri::command]
+pub async fn delete_prompt_schedule(
+    app: String,
+    state: State<'_, AppState>,
+) -> Result<bool, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    PromptScheduleService::delete(&state, &app_type).map_err(|e| e.to_string())
+}
+
+/// 批量启用/停用提示词，只同步一次 live 文件，并发出一次汇总的变更事件
+#[tauri::command]
+pub async fn set_prompts_enabled(
+    handle: AppHandle,
+    app: String,
+    changes: Vec<(String, bool)>,
+    state: State<'_, AppState>,
+) -> Result<Option<String>, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    let enabled_id = PromptService::set_prompts_enabled(&state, app_type, &changes)
+        .map_err(|e| e.to_string())?;
+
+    let payload = serde_json::json!({
+        "appType": app,
+        "enabledId": enabled_id,
+        "changed": changes.iter().map(|(id, _)| id).collect::<Vec<_>>(),
+    });
+    if let Err(e) = handle.emit("prompts-changed", payload) {
+        log::error!("发射提示词变更事件失败: {e}");
+    }
+    Ok(enabled_id)
+}
@@ -1,23 +1,55 @@
 use indexmap::IndexMap;
-use tauri::State;
+use std::collections::HashMap;
+use tauri::{Emitter, State};
 
 use crate::app_config::AppType;
 use crate::error::AppError;
-use crate::provider::Provider;
-use crate::services::{EndpointLatency, ProviderService, ProviderSortUpdate, SpeedtestService};
+use crate::gemini_config::GeminiModelRouting;
+use crate::provider::{
+    LocalModelConfig, ModelFidelity, Provider, ProviderErrorNote, ProviderOperation,
+};
+use crate::provider_templates::{ProviderTemplate, TemplatePlaceholder};
+use crate::services::provider::{
+    probe_health, CodexLoginAuth, CodexLoginStatus, KeyProviderDraft, KeyProviderImporter,
+    LiveMergePreview, ProviderSchema, ProviderSchemaDescriber, SwitchCheckStatus, SwitchOutcome,
+    SwitchPipeline, SwitchPreview, SwitchValidation,
+};
+use crate::services::provider_csv::{CsvImportResult, CsvProviderRow};
+use crate::services::{
+    ConfirmAction, ConfirmationInput, ConfirmationService, CredentialProbeService,
+    CsvColumnMapping, DebugProxyService, DebugProxyStatus, EndpointLatency, InferenceLatency,
+    LocalModelService, LocalModelStatus, ManagedImportSummary, ManagedProviderService,
+    ModelFidelityService, PresetRefreshResult, ProbeOutcome, ProviderCsvImportService,
+    ProviderDraftResult, ProviderDraftService, ProviderErrorService, ProviderExportService,
+    ProviderImportConflict, ProviderImportResult, ProviderPresetService, ProviderService,
+    ProviderSortUpdate, ProviderTemplateService, ProxyLogEntry, RelayDirectoryService, RelayEntry,
+    SecurityFinding, SecurityReviewService, SharePageResult, SharePageService, SpeedtestService,
+};
+use crate::settings::CredentialProbeMode;
+use crate::startup::StartupState;
 use crate::store::AppState;
 use std::str::FromStr;
 
 /// 获取所有供应商
 #[tauri::command]
-pub fn get_providers(
+pub async fn get_providers(
     state: State<'_, AppState>,
+    startup: State<'_, StartupState>,
     app: String,
 ) -> Result<IndexMap<String, Provider>, String> {
+    // 首次导入在后台进行，完成前返回的列表不完整
+    startup.wait_ready().await;
     let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
     ProviderService::list(state.inner(), app_type).map_err(|e| e.to_string())
 }
 
+/// 描述指定应用的 settings_config 结构（字段、类型、是否必填、示例）
+#[tauri::command]
+pub fn describe_provider_schema(app: String) -> Result<ProviderSchema, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    Ok(ProviderSchemaDescriber::describe(&app_type))
+}
+
 /// 获取当前供应商ID
 #[tauri::command]
 pub fn get_current_provider(state: State<'_, AppState>, app: String) -> Result<String, String> {
@@ -47,14 +79,73 @@ pub fn update_provider(
     ProviderService::update(state.inner(), app_type, provider).map_err(|e| e.to_string())
 }
 
+/// 把当前 ~/.codex/auth.json（ChatGPT 登录）整体保存到指定 Codex 供应商
+#[tauri::command]
+pub fn capture_codex_login(state: State<'_, AppState>, id: String) -> Result<Provider, String> {
+    CodexLoginAuth::capture_live(state.inner(), &id).map_err(|e| e.to_string())
+}
+
+/// 获取 Codex 登录账号的过期信息；非登录模式返回 null
+#[tauri::command]
+pub fn get_codex_login_status(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<Option<CodexLoginStatus>, String> {
+    let providers = state
+        .storage
+        .get_all_providers(AppType::Codex.as_str())
+        .map_err(|e| e.to_string())?;
+    let provider = providers
+        .get(&id)
+        .ok_or_else(|| format!("供应商 {id} 不存在"))?;
+    Ok(CodexLoginAuth::status(provider))
+}
+
+/// 按端点域名重新匹配中转目录，补全供应商缺失的官网、图标与文档链接
+#[tauri::command]
+pub fn refresh_provider_metadata(
+    state: State<'_, AppState>,
+    app: String,
+    id: String,
+) -> Result<Provider, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    RelayDirectoryService::refresh_provider(state.inner(), app_type, &id).map_err(|e| e.to_string())
+}
+
+/// 获取当前生效的中转目录（内置 + 远程缓存）
+#[tauri::command]
+pub fn get_relay_directory(state: State<'_, AppState>) -> Result<Vec<RelayEntry>, String> {
+    RelayDirectoryService::entries(state.inner()).map_err(|e| e.to_string())
+}
+
+/// 从远程地址更新中转目录
+#[tauri::command]
+pub async fn update_relay_directory(
+    state: State<'_, AppState>,
+    url: String,
+) -> Result<usize, String> {
+    RelayDirectoryService::update_from_url(state.inner(), &url)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// 删除供应商
 #[tauri::command]
 pub fn delete_provider(
     state: State<'_, AppState>,
     app: String,
     id: String,
+    confirmation_token: Option<String>,
+    confirmation_text: Option<String>,
+    force: Option<bool>,
 ) -> Result<bool, String> {
     let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    ConfirmationService::verify(
+        ConfirmAction::DeleteProvider,
+        &format!("{}:{id}", app_type.as_str()),
+        &ConfirmationInput::new(confirmation_token, confirmation_text, force),
+    )
+    .map_err(|e| e.to_string())?;
     ProviderService::delete(state.inner(), app_type, &id)
         .map(|_| true)
         .map_err(|e| e.to_string())
@@ -75,15 +166,226 @@ pub fn switch_provider_test_hook(
 }
 
 #[tauri::command]
-pub fn switch_provider(
+pub async fn switch_provider(
+    handle: tauri::AppHandle,
     state: State<'_, AppState>,
     app: String,
     id: String,
+    force: Option<bool>,
 ) -> Result<bool, String> {
     let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
-    switch_provider_internal(&state, app_type, &id)
-        .map(|_| true)
-        .map_err(|e| e.to_string())
+    let provider = ProviderService::list(state.inner(), app_type.clone())
+        .map_err(|e| e.to_string())?
+        .shift_remove(&id);
+
+    if let Some(provider) = &provider {
+        check_before_switch(&handle, &state, &app_type, provider).await?;
+    }
+
+    // force 只放开可用时段限制，其余切换前检查照常执行
+    ProviderService::switch_with(&state, app_type.clone(), &id, force.unwrap_or(false))
+        .map_err(|e| e.to_string())?;
+    crate::tray::mark_tray_current(&handle, &app_type, &id);
+    Ok(true)
+}
+
+/// 按名称切换供应商（ID、名称精确匹配或唯一前缀），供外部自动化调用
+///
+/// 目标已是当前供应商时返回 changed=false，可安全重试
+#[tauri::command]
+pub async fn switch_provider_by_name(
+    handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    app: String,
+    name: String,
+) -> Result<SwitchOutcome, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    let provider =
+        ProviderService::find(state.inner(), &app_type, &name).map_err(|e| e.to_string())?;
+
+    let current = state
+        .storage
+        .get_current_provider(app_type.as_str())
+        .map_err(|e| e.to_string())?;
+    if current.as_deref() != Some(provider.id.as_str()) {
+        check_before_switch(&handle, &state, &app_type, &provider).await?;
+    }
+
+    let outcome = ProviderService::switch_idempotent(state.inner(), app_type.clone(), &provider.id)
+        .map_err(|e| e.to_string())?;
+    if outcome.changed {
+        crate::tray::mark_tray_current(&handle, &app_type, &provider.id);
+        let payload = serde_json::json!({
+            "appType": outcome.app,
+            "providerId": outcome.provider_id,
+        });
+        if let Err(e) = handle.emit("provider-switched", payload) {
+            log::error!("发射供应商切换事件失败: {e}");
+        }
+    }
+    Ok(outcome)
+}
+
+/// 本地模型供应商先确认 Ollama 可用，其余按设置探测凭证；未通过时记入供应商的错误历史
+async fn check_before_switch(
+    handle: &tauri::AppHandle,
+    state: &AppState,
+    app_type: &AppType,
+    provider: &Provider,
+) -> Result<(), String> {
+    let result = async {
+        if let Some(local_model) = provider.meta.as_ref().and_then(|m| m.local_model.as_ref()) {
+            LocalModelService::check_health(local_model)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+        probe_credentials(handle, state, app_type, provider).await
+    }
+    .await;
+    if let Err(message) = &result {
+        ProviderErrorService::record(
+            state,
+            app_type,
+            &provider.id,
+            ProviderOperation::Switch,
+            message,
+        );
+    }
+    result
+}
+
+/// 生成供应商分享页（静态 HTML）；默认不包含 API Key
+#[tauri::command]
+pub fn generate_provider_share_page(
+    state: State<'_, AppState>,
+    app: String,
+    id: String,
+    file_path: String,
+    include_secret: Option<bool>,
+) -> Result<SharePageResult, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    SharePageService::generate(
+        state.inner(),
+        app_type,
+        &id,
+        std::path::Path::new(&file_path),
+        include_secret.unwrap_or(false),
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// 切换前检查：供应商、凭证、运行中的会话与配置目录；probe 为 true 时额外联网探测
+#[tauri::command]
+pub async fn validate_switch(
+    state: State<'_, AppState>,
+    app: String,
+    id: String,
+    probe: Option<bool>,
+) -> Result<SwitchValidation, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    let mut validation = SwitchPipeline::standard()
+        .run(state.inner(), &app_type, &id)
+        .map_err(|e| e.to_string())?;
+
+    if probe.unwrap_or(false) {
+        let provider = ProviderService::list(state.inner(), app_type.clone())
+            .map_err(|e| e.to_string())?
+            .shift_remove(&id);
+        if let Some(provider) = provider {
+            let health = probe_health(&app_type, &provider).await;
+            if health.status == SwitchCheckStatus::Failed {
+                ProviderErrorService::record(
+                    state.inner(),
+                    &app_type,
+                    &id,
+                    ProviderOperation::HealthCheck,
+                    health.message.as_deref().unwrap_or_default(),
+                );
+            }
+            validation.checks.push(health);
+            validation.can_switch = !validation
+                .checks
+                .iter()
+                .any(|c| c.status == SwitchCheckStatus::Failed);
+        }
+    }
+
+    Ok(validation)
+}
+
+/// 供应商最近的失败记录（切换、健康检查、用量查询），按时间倒序
+#[tauri::command]
+pub fn get_provider_error_history(
+    state: State<'_, AppState>,
+    app: String,
+    id: String,
+) -> Result<Vec<ProviderErrorNote>, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    ProviderErrorService::history(state.inner(), &app_type, &id).map_err(|e| e.to_string())
+}
+
+/// 预览切换会改动的配置文件及逐行差异，不写入任何内容
+#[tauri::command]
+pub fn preview_switch(
+    state: State<'_, AppState>,
+    app: String,
+    id: String,
+) -> Result<SwitchPreview, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    ProviderService::preview_switch(state.inner(), app_type, &id).map_err(|e| e.to_string())
+}
+
+/// 按设置探测即将写入的 API Key，失效时标记供应商并通知前端
+async fn probe_credentials(
+    handle: &tauri::AppHandle,
+    state: &AppState,
+    app_type: &AppType,
+    provider: &Provider,
+) -> Result<(), String> {
+    let mode = crate::settings::get_settings().credential_probe;
+    if mode == CredentialProbeMode::Off {
+        return Ok(());
+    }
+
+    let outcome = CredentialProbeService::probe(app_type, provider).await;
+    if let Err(err) = CredentialProbeService::record(state, app_type, provider, &outcome) {
+        log::warn!("记录凭证探测结果失败: {err}");
+    }
+
+    let status = match outcome {
+        ProbeOutcome::Invalid(status) => status,
+        ProbeOutcome::Valid => return Ok(()),
+        ProbeOutcome::Unknown(reason) => {
+            log::debug!("凭证探测未得出结论 {}: {reason}", provider.id);
+            return Ok(());
+        }
+    };
+
+    log::warn!("供应商 {} 的凭证已失效 (HTTP {status})", provider.id);
+    let payload = serde_json::json!({
+        "appType": app_type.as_str(),
+        "providerId": provider.id,
+        "status": status,
+    });
+    if let Err(e) = handle.emit("provider-credentials-invalid", payload) {
+        log::error!("发射凭证失效事件失败: {e}");
+    }
+
+    if mode == CredentialProbeMode::Abort {
+        return Err(AppError::localized(
+            "provider.credentials_invalid",
+            format!(
+                "供应商 {} 的 API Key 无效或已过期 (HTTP {status})，已取消切换",
+                provider.name
+            ),
+            format!(
+                "API key for provider {} is invalid or expired (HTTP {status}); switch aborted",
+                provider.name
+            ),
+        )
+        .to_string());
+    }
+    Ok(())
 }
 
 fn import_default_config_internal(state: &AppState, app_type: AppType) -> Result<(), AppError> {
@@ -121,6 +423,32 @@ pub async fn queryProviderUsage(
         .map_err(|e| e.to_string())
 }
 
+/// 获取 Gemini 供应商的模型路由（默认/回退/按任务模型）
+#[tauri::command]
+pub fn get_gemini_model_routing(
+    state: State<'_, AppState>,
+    #[allow(non_snake_case)] providerId: String,
+) -> Result<GeminiModelRouting, String> {
+    ProviderService::get_gemini_model_routing(state.inner(), &providerId).map_err(|e| e.to_string())
+}
+
+/// 设置 Gemini 供应商的模型路由，返回更新后的供应商
+#[tauri::command]
+pub fn set_gemini_model_routing(
+    state: State<'_, AppState>,
+    #[allow(non_snake_case)] providerId: String,
+    routing: GeminiModelRouting,
+) -> Result<Provider, String> {
+    ProviderService::set_gemini_model_routing(state.inner(), &providerId, &routing)
+        .map_err(|e| e.to_string())
+}
+
+/// 取消供应商正在进行的用量查询；没有进行中的查询时返回 false
+#[tauri::command]
+pub fn cancel_usage_query(#[allow(non_snake_case)] providerId: String) -> bool {
+    ProviderService::cancel_usage_query(&providerId)
+}
+
 /// 测试用量脚本（使用当前编辑器中的脚本，不保存）
 #[allow(non_snake_case)]
 #[allow(clippy::too_many_arguments)]
@@ -152,6 +480,27 @@ pub async fn testUsageScript(
     .map_err(|e| e.to_string())
 }
 
+/// 以模拟响应试运行用量脚本，不访问真实接口
+#[tauri::command]
+pub async fn dry_run_usage_script(
+    #[allow(non_snake_case)] scriptCode: String,
+    #[allow(non_snake_case)] mockResponses: Vec<crate::usage_script::MockHttpResponse>,
+    #[allow(non_snake_case)] apiKey: Option<String>,
+    #[allow(non_snake_case)] baseUrl: Option<String>,
+    #[allow(non_snake_case)] accessToken: Option<String>,
+    #[allow(non_snake_case)] userId: Option<String>,
+) -> Result<crate::usage_script::UsageDryRunResult, String> {
+    crate::usage_script::dry_run_usage_script(
+        &scriptCode,
+        &mockResponses,
+        apiKey.as_deref(),
+        baseUrl.as_deref(),
+        accessToken.as_deref(),
+        userId.as_deref(),
+    )
+    .map_err(|e| e.to_string())
+}
+
 /// 读取当前生效的配置内容
 #[tauri::command]
 pub fn read_live_provider_settings(app: String) -> Result<serde_json::Value, String> {
@@ -159,14 +508,225 @@ pub fn read_live_provider_settings(app: String) -> Result<serde_json::Value, Str
     ProviderService::read_live_settings(app_type).map_err(|e| e.to_string())
 }
 
+/// 把 live 配置中选定的字段回填到供应商，dry_run 为 true 时仅返回差异预览
+#[tauri::command]
+pub fn merge_live_into_provider(
+    state: State<'_, AppState>,
+    app: String,
+    id: String,
+    paths: Vec<String>,
+    dry_run: Option<bool>,
+) -> Result<LiveMergePreview, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    ProviderService::merge_live_into_provider(
+        state.inner(),
+        app_type,
+        &id,
+        &paths,
+        dry_run.unwrap_or(false),
+    )
+    .map_err(|e| e.to_string())
+}
+
 /// 测试第三方/自定义供应商端点的网络延迟
+///
+/// 同时传入 app 与 providerId 时，将最快的结果记录到该供应商，供按延迟排序使用。
 #[tauri::command]
 pub async fn test_api_endpoints(
+    state: State<'_, AppState>,
     urls: Vec<String>,
     #[allow(non_snake_case)] timeoutSecs: Option<u64>,
+    app: Option<String>,
+    #[allow(non_snake_case)] providerId: Option<String>,
 ) -> Result<Vec<EndpointLatency>, String> {
-    SpeedtestService::test_endpoints(urls, timeoutSecs)
+    let results = SpeedtestService::test_endpoints(urls, timeoutSecs)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if let (Some(app), Some(provider_id)) = (app, providerId) {
+        let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+        if let Err(err) =
+            ProviderService::record_latency(state.inner(), app_type, &provider_id, &results)
+        {
+            log::warn!("记录供应商 {provider_id} 测速结果失败: {err}");
+        }
+    }
+
+    Ok(results)
+}
+
+/// 以供应商实际使用的模型发送一次流式请求，测量首 token 延迟与生成速度
+#[tauri::command]
+pub async fn test_inference_latency(
+    state: State<'_, AppState>,
+    app: String,
+    #[allow(non_snake_case)] providerId: String,
+    #[allow(non_snake_case)] timeoutSecs: Option<u64>,
+) -> Result<InferenceLatency, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    let providers = state
+        .storage
+        .get_all_providers(app_type.as_str())
+        .map_err(|e| e.to_string())?;
+    let provider = providers
+        .get(&providerId)
+        .ok_or_else(|| format!("供应商 {providerId} 不存在"))?;
+    SpeedtestService::test_inference(&app_type, provider, timeoutSecs)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 检查所有供应商的安全隐患；probeTls 为 true 时额外联网校验 HTTPS 端点证书
+#[tauri::command]
+pub async fn get_security_findings(
+    state: State<'_, AppState>,
+    #[allow(non_snake_case)] probeTls: Option<bool>,
+) -> Result<Vec<SecurityFinding>, String> {
+    SecurityReviewService::findings(&state, probeTls.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 调试代理记录的请求（新的在前），app 为空时返回全部应用
+#[tauri::command]
+pub fn get_proxy_log(
+    app: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<ProxyLogEntry>, String> {
+    DebugProxyService::log(app.as_deref(), limit).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn clear_proxy_log() -> Result<(), String> {
+    DebugProxyService::clear_log().map_err(|e| e.to_string())
+}
+
+/// 当前运行中的调试代理
+#[tauri::command]
+pub fn get_debug_proxies() -> Result<Vec<DebugProxyStatus>, String> {
+    DebugProxyService::status().map_err(|e| e.to_string())
+}
+
+/// 重新发送测试请求，检查中转是否以其他模型响应；结果写入供应商并刷新托盘提示
+#[tauri::command]
+pub async fn recheck_model_fidelity(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    app: String,
+    #[allow(non_snake_case)] providerId: String,
+) -> Result<ModelFidelity, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    let fidelity = ModelFidelityService::recheck(&state, &app_type, &providerId)
+        .await
+        .map_err(|e| e.to_string())?;
+    crate::tray::refresh_tray_tooltip(&app_handle, &state);
+    Ok(fidelity)
+}
+
+/// 识别粘贴的 API Key（sk-ant- / sk- / AIza）并生成指向官方端点的供应商草稿，不会保存
+#[tauri::command]
+pub fn create_provider_from_key(
+    key: String,
+    name: Option<String>,
+) -> Result<KeyProviderDraft, String> {
+    KeyProviderImporter::draft(&key, name.as_deref()).map_err(|e| e.to_string())
+}
+
+/// 为尚无供应商的应用按设置从环境变量 / 1Password 生成待确认的草稿供应商
+#[tauri::command]
+pub fn detect_provider_drafts(
+    state: State<'_, AppState>,
+) -> Result<Vec<ProviderDraftResult>, String> {
+    let sources = crate::settings::get_settings().first_import_sources;
+    ProviderDraftService::import_missing(state.inner(), &sources).map_err(|e| e.to_string())
+}
+
+/// 确认草稿供应商，去掉待确认标记
+#[tauri::command]
+pub fn mark_provider_reviewed(
+    state: State<'_, AppState>,
+    app: String,
+    id: String,
+) -> Result<bool, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    ProviderDraftService::mark_reviewed(state.inner(), app_type, &id)
+        .map(|_| true)
+        .map_err(|e| e.to_string())
+}
+
+/// 列出需要补全数值的供应商模板
+#[tauri::command]
+pub fn list_provider_templates(
+    state: State<'_, AppState>,
+    app: Option<String>,
+) -> Result<Vec<ProviderTemplate>, String> {
+    let app_type = app
+        .map(|app| AppType::from_str(&app))
+        .transpose()
+        .map_err(|e| e.to_string())?;
+    ProviderTemplateService::list(state.inner(), app_type.as_ref()).map_err(|e| e.to_string())
+}
+
+/// 拉取远程供应商预设索引（校验签名后缓存）；force 为 true 时忽略缓存有效期
+#[tauri::command]
+pub async fn refresh_provider_presets(
+    state: State<'_, AppState>,
+    force: Option<bool>,
+) -> Result<PresetRefreshResult, String> {
+    ProviderPresetService::refresh(state.inner(), force.unwrap_or(true))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 返回模板中仍需用户填写的占位符
+#[tauri::command]
+pub fn get_template_missing_placeholders(
+    state: State<'_, AppState>,
+    #[allow(non_snake_case)] templateId: String,
+    values: HashMap<String, String>,
+) -> Result<Vec<TemplatePlaceholder>, String> {
+    ProviderTemplateService::missing_placeholders(state.inner(), &templateId, &values)
+        .map_err(|e| e.to_string())
+}
+
+/// 校验所有占位符均已填写后按模板创建供应商
+#[tauri::command]
+pub fn instantiate_template(
+    state: State<'_, AppState>,
+    #[allow(non_snake_case)] templateId: String,
+    values: HashMap<String, String>,
+    name: Option<String>,
+) -> Result<Provider, String> {
+    ProviderTemplateService::instantiate(state.inner(), &templateId, &values, name.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+/// 探测本地 Ollama 实例并列出模型
+#[tauri::command]
+pub async fn detect_local_models(
+    #[allow(non_snake_case)] baseUrl: Option<String>,
+) -> Result<LocalModelStatus, String> {
+    LocalModelService::detect_ollama(baseUrl)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 生成本地模型供应商的 settingsConfig
+#[tauri::command]
+pub fn build_local_model_settings(
+    app: String,
+    config: LocalModelConfig,
+) -> Result<serde_json::Value, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    LocalModelService::build_settings(&app_type, &config).map_err(|e| e.to_string())
+}
+
+/// 检查本地模型端点是否可用
+#[tauri::command]
+pub async fn check_local_model_health(config: LocalModelConfig) -> Result<bool, String> {
+    LocalModelService::check_health(&config)
         .await
+        .map(|_| true)
         .map_err(|e| e.to_string())
 }
 
@@ -182,6 +742,18 @@ pub fn get_custom_endpoints(
         .map_err(|e| e.to_string())
 }
 
+/// 获取后台探测得到的各端点可用性分数
+#[tauri::command]
+pub fn get_endpoint_health(
+    state: State<'_, AppState>,
+    app: String,
+    #[allow(non_snake_case)] providerId: String,
+) -> Result<Vec<crate::database::dao::EndpointHealth>, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    crate::services::EndpointHealthService::get_health(state.inner(), app_type, &providerId)
+        .map_err(|e| e.to_string())
+}
+
 /// 添加自定义端点
 #[tauri::command]
 pub fn add_custom_endpoint(
@@ -208,6 +780,55 @@ pub fn remove_custom_endpoint(
         .map_err(|e| e.to_string())
 }
 
+/// 导出自定义端点为 JSON 片段，便于分享给他人
+#[tauri::command]
+pub fn export_custom_endpoints(
+    state: State<'_, AppState>,
+    app: String,
+    #[allow(non_snake_case)] providerId: String,
+) -> Result<String, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    ProviderService::export_custom_endpoints(state.inner(), app_type, &providerId)
+        .map_err(|e| e.to_string())
+}
+
+/// 导入自定义端点：支持导出的 JSON 片段、URL 数组或带 endpoints 参数的供应商深链接
+#[tauri::command]
+pub fn import_custom_endpoints(
+    state: State<'_, AppState>,
+    app: String,
+    #[allow(non_snake_case)] providerId: String,
+    snippet: String,
+) -> Result<crate::services::EndpointImportResult, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    ProviderService::import_custom_endpoints(state.inner(), app_type, &providerId, &snippet)
+        .map_err(|e| e.to_string())
+}
+
+/// 导出单个供应商（含自定义端点、用量脚本与图标）为 JSON 文档
+#[tauri::command]
+pub fn export_provider(
+    state: State<'_, AppState>,
+    app: String,
+    id: String,
+    #[allow(non_snake_case)] includeSecrets: bool,
+) -> Result<String, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    ProviderExportService::export(state.inner(), app_type, &id, includeSecrets)
+        .map_err(|e| e.to_string())
+}
+
+/// 导入单个供应商文档；id 已存在时按 onConflict 另存、覆盖或跳过（默认另存）
+#[tauri::command]
+pub fn import_provider(
+    state: State<'_, AppState>,
+    document: String,
+    #[allow(non_snake_case)] onConflict: Option<ProviderImportConflict>,
+) -> Result<ProviderImportResult, String> {
+    ProviderExportService::import(state.inner(), &document, onConflict.unwrap_or_default())
+        .map_err(|e| e.to_string())
+}
+
 /// 更新端点最后使用时间
 #[tauri::command]
 pub fn update_endpoint_last_used(
@@ -231,3 +852,49 @@ pub fn update_providers_sort_order(
     let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
     ProviderService::update_sort_order(state.inner(), app_type, updates).map_err(|e| e.to_string())
 }
+
+/// 预览从共享表格 / CSV 链接导入的供应商（逐行校验，不写入）
+#[tauri::command]
+pub async fn preview_providers_from_url(
+    state: State<'_, AppState>,
+    url: String,
+    mapping: CsvColumnMapping,
+) -> Result<Vec<CsvProviderRow>, String> {
+    let csv = ProviderCsvImportService::download(&url)
+        .await
+        .map_err(|e| e.to_string())?;
+    ProviderCsvImportService::preview(state.inner(), &csv, &mapping).map_err(|e| e.to_string())
+}
+
+/// 从共享表格 / CSV 链接批量导入供应商
+#[tauri::command]
+pub async fn import_providers_from_url(
+    state: State<'_, AppState>,
+    url: String,
+    mapping: CsvColumnMapping,
+) -> Result<CsvImportResult, String> {
+    let csv = ProviderCsvImportService::download(&url)
+        .await
+        .map_err(|e| e.to_string())?;
+    ProviderCsvImportService::import(state.inner(), &csv, &mapping).map_err(|e| e.to_string())
+}
+
+/// 从 JSON 数组或 CSV 文本批量导入供应商，校验通过的行在同一事务中写入
+#[tauri::command]
+pub fn import_providers_bulk(
+    state: State<'_, AppState>,
+    payload: String,
+) -> Result<CsvImportResult, String> {
+    ProviderCsvImportService::import_bulk(state.inner(), &payload).map_err(|e| e.to_string())
+}
+
+/// 导入管理员下发的供应商清单：清单中的供应商标记为组织管理，不再出现的解除管理
+#[tauri::command]
+#[allow(non_snake_case)]
+pub fn import_managed_providers(
+    state: State<'_, AppState>,
+    filePath: String,
+) -> Result<ManagedImportSummary, String> {
+    ManagedProviderService::import_file(state.inner(), std::path::Path::new(&filePath))
+        .map_err(|e| e.to_string())
+}
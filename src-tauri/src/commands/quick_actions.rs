@@ -0,0 +1,49 @@
+use tauri::{AppHandle, State};
+use tauri_plugin_opener::OpenerExt;
+
+use crate::services::{
+    LauncherManifest, LauncherService, QuickAction, QuickActionKind, QuickActionOutcome,
+    QuickActionService,
+};
+use crate::store::AppState;
+
+/// 命令面板：模糊搜索可执行操作
+#[tauri::command]
+pub fn get_quick_actions(
+    state: State<'_, AppState>,
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<QuickAction>, String> {
+    QuickActionService::search(&state, &query, limit).map_err(|e| e.to_string())
+}
+
+/// 命令面板：执行选中的操作
+#[tauri::command]
+pub fn execute_quick_action(
+    handle: AppHandle,
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<QuickActionOutcome, String> {
+    let outcome = QuickActionService::execute(&state, &id).map_err(|e| e.to_string())?;
+
+    if let Some(path) = outcome.open_path.as_deref() {
+        std::fs::create_dir_all(path).map_err(|e| format!("创建目录失败: {e}"))?;
+        handle
+            .opener()
+            .open_path(path.to_string(), None::<String>)
+            .map_err(|e| format!("打开文件夹失败: {e}"))?;
+    }
+
+    // 切换供应商后同步托盘菜单的勾选状态
+    if outcome.kind == QuickActionKind::SwitchProvider {
+        crate::tray::schedule_tray_rebuild(&handle);
+    }
+
+    Ok(outcome)
+}
+
+/// 导出启动器清单（Raycast / Alfred 等扩展据此生成命令）
+#[tauri::command]
+pub fn get_launcher_manifest(state: State<'_, AppState>) -> Result<LauncherManifest, String> {
+    LauncherService::manifest(&state).map_err(|e| e.to_string())
+}
@@ -0,0 +1,30 @@
+use std::str::FromStr;
+
+use tauri::State;
+
+use crate::app_config::AppType;
+use crate::services::{CliSessionPage, CliSessionSummary, SessionLogService};
+use crate::store::AppState;
+
+/// 列出 CLI 本地会话记录（按最近修改排序）
+#[tauri::command]
+pub fn list_cli_sessions(
+    state: State<'_, AppState>,
+    app: String,
+) -> Result<Vec<CliSessionSummary>, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    SessionLogService::list(state.inner(), app_type).map_err(|e| e.to_string())
+}
+
+/// 分页读取单个会话的消息，page 从 0 开始
+#[tauri::command]
+pub fn get_cli_session(
+    state: State<'_, AppState>,
+    app: String,
+    id: String,
+    page: Option<usize>,
+) -> Result<CliSessionPage, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    SessionLogService::get(state.inner(), app_type, &id, page.unwrap_or(0))
+        .map_err(|e| e.to_string())
+}
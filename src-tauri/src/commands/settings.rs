@@ -1,6 +1,10 @@
 #![allow(non_snake_case)]
 
-use tauri::AppHandle;
+use std::path::Path;
+use tauri::{AppHandle, State};
+
+use crate::services::{DbLocationInfo, DbLocationService, DbMoveResult};
+use crate::store::AppState;
 
 /// 获取设置
 #[tauri::command]
@@ -10,8 +14,15 @@ pub async fn get_settings() -> Result<crate::settings::AppSettings, String> {
 
 /// 保存设置
 #[tauri::command]
-pub async fn save_settings(settings: crate::settings::AppSettings) -> Result<bool, String> {
+pub async fn save_settings(
+    app: AppHandle,
+    settings: crate::settings::AppSettings,
+) -> Result<bool, String> {
+    let icon_changed = crate::settings::get_settings().tray_icon_style != settings.tray_icon_style;
     crate::settings::update_settings(settings).map_err(|e| e.to_string())?;
+    if icon_changed {
+        crate::tray::refresh_tray_icon(&app);
+    }
     Ok(true)
 }
 
@@ -43,6 +54,92 @@ pub async fn set_app_config_dir_override(
     Ok(true)
 }
 
+/// 导出偏好设置（不含本机路径与供应商 / MCP 数据）
+#[tauri::command]
+pub async fn export_app_settings(file_path: String) -> Result<bool, String> {
+    crate::settings::export_app_settings(Path::new(&file_path)).map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+/// 导入偏好设置，保留本机路径等字段
+#[tauri::command]
+pub async fn import_app_settings(
+    file_path: String,
+) -> Result<crate::settings::AppSettings, String> {
+    crate::settings::import_app_settings(Path::new(&file_path)).map_err(|e| e.to_string())
+}
+
+/// 获取数据库文件位置及其风险提示
+#[tauri::command]
+pub async fn get_db_location() -> Result<DbLocationInfo, String> {
+    Ok(DbLocationService::current())
+}
+
+/// 检查候选数据库位置（网络文件系统、同步盘等）
+#[tauri::command]
+pub async fn check_db_location(path: String) -> Result<DbLocationInfo, String> {
+    let target = DbLocationService::resolve_target(&path).map_err(|e| e.to_string())?;
+    Ok(DbLocationService::inspect(&target))
+}
+
+/// 复制数据库到新位置并在重启后启用；path 为空时迁回默认位置
+///
+/// 成功后当前数据库保持只读直到重启，期间的任何修改都会报错而不是写进旧文件，
+/// 前端应在返回后尽快调用 `restart_app`。
+#[tauri::command]
+pub async fn move_database(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    path: Option<String>,
+) -> Result<DbMoveResult, String> {
+    DbLocationService::relocate(&app, &state, path.as_deref()).map_err(|e| e.to_string())
+}
+
+/// 当前是否运行在演示模式
+#[tauri::command]
+pub async fn get_demo_mode() -> Result<bool, String> {
+    Ok(crate::services::is_demo_mode())
+}
+
+/// 当前是否以安全模式启动（`--safe-mode` 或 CLI_HUB_SAFE_MODE=1）
+#[tauri::command]
+pub async fn get_safe_mode() -> Result<bool, String> {
+    Ok(crate::services::is_safe_mode())
+}
+
+/// 设置下次启动是否进入演示模式（需重启生效）
+#[tauri::command]
+pub async fn set_demo_mode(app: AppHandle, enabled: bool) -> Result<bool, String> {
+    crate::app_store::set_demo_mode_to_store(&app, enabled).map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+/// 获取配置档的设置覆盖；profile 为空时读取当前配置档
+#[tauri::command]
+pub async fn get_settings_profile(
+    profile: Option<String>,
+) -> Result<crate::settings::SettingsProfile, String> {
+    crate::settings::get_settings_profile(profile.as_deref()).map_err(|e| e.to_string())
+}
+
+/// 获取叠加当前配置档覆盖后的生效设置
+#[tauri::command]
+pub async fn get_effective_settings() -> Result<crate::settings::AppSettings, String> {
+    Ok(crate::settings::get_effective_settings())
+}
+
+/// 保存配置档的设置覆盖，并刷新托盘菜单以应用语言与密度
+#[tauri::command]
+pub async fn save_settings_overrides(
+    app: AppHandle,
+    profile: String,
+    overrides: crate::settings::SettingsOverrides,
+) -> Result<bool, String> {
+    crate::settings::update_profile_overrides(&profile, overrides).map_err(|e| e.to_string())?;
+    crate::tray::schedule_tray_rebuild(&app);
+    Ok(true)
+}
+
 /// 设置开机自启
 #[tauri::command]
 pub async fn set_auto_launch(enabled: bool) -> Result<bool, String> {
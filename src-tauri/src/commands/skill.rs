@@ -1,38 +1,63 @@
 use crate::error::format_skill_error;
-use crate::services::skill::SkillState;
-use crate::services::{Skill, SkillRepo, SkillService};
+use crate::services::skill::{ScannedSkill, SkillState, UNMANAGED_SKILL_SOURCE};
+use crate::services::{GitHubRateLimit, Skill, SkillRepo, SkillService};
 use crate::store::AppState;
 use chrono::Utc;
 use std::sync::Arc;
 use tauri::State;
 
-pub struct SkillServiceState(pub Arc<SkillService>);
+/// 技能服务在启动后台任务中初始化，命令通过 `get` 等待其就绪
+#[derive(Clone, Default)]
+pub struct SkillServiceState(Arc<tokio::sync::OnceCell<Arc<SkillService>>>);
+
+impl SkillServiceState {
+    pub async fn get(&self) -> Result<Arc<SkillService>, String> {
+        self.0
+            .get_or_try_init(|| async {
+                SkillService::new()
+                    .map(Arc::new)
+                    .map_err(|e| format!("初始化 SkillService 失败: {e}"))
+            })
+            .await
+            .cloned()
+    }
+}
 
 #[tauri::command]
 pub async fn get_skills(
     service: State<'_, SkillServiceState>,
     app_state: State<'_, AppState>,
 ) -> Result<Vec<Skill>, String> {
-    let repos = app_state.db.get_skill_repos().map_err(|e| e.to_string())?;
+    let repos = app_state
+        .storage
+        .get_skill_repos()
+        .map_err(|e| e.to_string())?;
 
     let skills = service
-        .0
+        .get()
+        .await?
         .list_skills(repos)
         .await
         .map_err(|e| e.to_string())?;
 
     // 自动同步本地已安装的 skills 到数据库
     // 这样用户在首次运行时，已有的 skills 会被自动记录
-    let existing_states = app_state.db.get_skills().unwrap_or_default();
+    let existing_states = app_state.storage.get_skills().unwrap_or_default();
 
     for skill in &skills {
         if skill.installed && !existing_states.contains_key(&skill.directory) {
             // 本地有该 skill，但数据库中没有记录，自动添加
-            if let Err(e) = app_state.db.update_skill_state(
+            // 仓库中找不到的本地技能记为 unmanaged
+            let source = skill
+                .key
+                .starts_with("local:")
+                .then(|| UNMANAGED_SKILL_SOURCE.to_string());
+            if let Err(e) = app_state.storage.update_skill_state(
                 &skill.directory,
                 &SkillState {
                     installed: true,
                     installed_at: Utc::now(),
+                    source,
                 },
             ) {
                 log::warn!("同步本地 skill {} 状态到数据库失败: {}", skill.directory, e);
@@ -49,11 +74,16 @@ pub async fn install_skill(
     service: State<'_, SkillServiceState>,
     app_state: State<'_, AppState>,
 ) -> Result<bool, String> {
+    crate::services::demo::ensure_not_demo().map_err(|e| e.to_string())?;
     // 先在不持有写锁的情况下收集仓库与技能信息
-    let repos = app_state.db.get_skill_repos().map_err(|e| e.to_string())?;
+    let repos = app_state
+        .storage
+        .get_skill_repos()
+        .map_err(|e| e.to_string())?;
 
     let skills = service
-        .0
+        .get()
+        .await?
         .list_skills(repos)
         .await
         .map_err(|e| e.to_string())?;
@@ -94,19 +124,21 @@ pub async fn install_skill(
         };
 
         service
-            .0
+            .get()
+            .await?
             .install_skill(directory.clone(), repo)
             .await
             .map_err(|e| e.to_string())?;
     }
 
     app_state
-        .db
+        .storage
         .update_skill_state(
             &directory,
             &SkillState {
                 installed: true,
                 installed_at: Utc::now(),
+                source: None,
             },
         )
         .map_err(|e| e.to_string())?;
@@ -115,24 +147,27 @@ pub async fn install_skill(
 }
 
 #[tauri::command]
-pub fn uninstall_skill(
+pub async fn uninstall_skill(
     directory: String,
     service: State<'_, SkillServiceState>,
     app_state: State<'_, AppState>,
 ) -> Result<bool, String> {
+    crate::services::demo::ensure_not_demo().map_err(|e| e.to_string())?;
     service
-        .0
+        .get()
+        .await?
         .uninstall_skill(directory.clone())
         .map_err(|e| e.to_string())?;
 
     // Remove from database by setting installed = false
     app_state
-        .db
+        .storage
         .update_skill_state(
             &directory,
             &SkillState {
                 installed: false,
                 installed_at: Utc::now(),
+                source: None,
             },
         )
         .map_err(|e| e.to_string())?;
@@ -140,12 +175,51 @@ pub fn uninstall_skill(
     Ok(true)
 }
 
+/// 扫描 ~/.claude/skills 中手动放入（如 git clone）的技能并记录到数据库
+#[tauri::command]
+pub async fn scan_installed_skills(
+    service: State<'_, SkillServiceState>,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<ScannedSkill>, String> {
+    let known = app_state.storage.get_skills().map_err(|e| e.to_string())?;
+    let repos = app_state
+        .storage
+        .get_skill_repos()
+        .map_err(|e| e.to_string())?;
+
+    let scanned = service
+        .get()
+        .await?
+        .scan_installed_skills(&known, &repos)
+        .map_err(|e| e.to_string())?;
+
+    for skill in &scanned {
+        app_state
+            .storage
+            .update_skill_state(
+                &skill.directory,
+                &SkillState {
+                    installed: true,
+                    installed_at: Utc::now(),
+                    source: Some(skill.source.clone()),
+                },
+            )
+            .map_err(|e| e.to_string())?;
+    }
+
+    log::info!("扫描到 {} 个未记录的本地技能", scanned.len());
+    Ok(scanned)
+}
+
 #[tauri::command]
 pub fn get_skill_repos(
     _service: State<'_, SkillServiceState>,
     app_state: State<'_, AppState>,
 ) -> Result<Vec<SkillRepo>, String> {
-    app_state.db.get_skill_repos().map_err(|e| e.to_string())
+    app_state
+        .storage
+        .get_skill_repos()
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -155,7 +229,7 @@ pub fn add_skill_repo(
     app_state: State<'_, AppState>,
 ) -> Result<bool, String> {
     app_state
-        .db
+        .storage
         .save_skill_repo(&repo)
         .map_err(|e| e.to_string())?;
     Ok(true)
@@ -169,8 +243,14 @@ pub fn remove_skill_repo(
     app_state: State<'_, AppState>,
 ) -> Result<bool, String> {
     app_state
-        .db
+        .storage
         .delete_skill_repo(&owner, &name)
         .map_err(|e| e.to_string())?;
     Ok(true)
 }
+
+/// 最近一次 GitHub 响应中的限流状态
+#[tauri::command]
+pub fn get_github_rate_limit() -> Result<Option<GitHubRateLimit>, String> {
+    Ok(crate::services::github::rate_limit_status())
+}
@@ -0,0 +1,35 @@
+use std::str::FromStr;
+
+use tauri::State;
+
+use crate::app_config::AppType;
+use crate::commands::skill::SkillServiceState;
+use crate::services::stack::{StackApplyReport, StackService};
+use crate::stacks::StackDefinition;
+use crate::store::AppState;
+
+/// 获取内置套件列表
+#[tauri::command]
+pub fn list_stacks() -> Result<Vec<StackDefinition>, String> {
+    Ok(StackService::list())
+}
+
+/// 一键安装并启用套件中的 MCP、提示词与技能
+#[tauri::command]
+pub async fn apply_stack(
+    name: String,
+    apps: Vec<String>,
+    service: State<'_, SkillServiceState>,
+    app_state: State<'_, AppState>,
+) -> Result<StackApplyReport, String> {
+    let apps = apps
+        .iter()
+        .map(|app| AppType::from_str(app))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let service = service.get().await?;
+    StackService::apply(&app_state, &service, &name, &apps)
+        .await
+        .map_err(|e| e.to_string())
+}
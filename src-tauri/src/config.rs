@@ -1,9 +1,12 @@
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
 use crate::error::AppError;
+use crate::settings::ClaudeSettingsLayer;
 
 /// 获取 Claude Code 配置目录路径
 pub fn get_claude_config_dir() -> PathBuf {
@@ -46,8 +49,32 @@ pub fn get_claude_mcp_path() -> PathBuf {
     get_default_claude_mcp_path()
 }
 
-/// 获取 Claude Code 主配置文件路径
+/// 获取 Claude Code 主配置文件路径（即 CLI Hub 写入的层级）
 pub fn get_claude_settings_path() -> PathBuf {
+    match crate::settings::get_claude_settings_layer() {
+        ClaudeSettingsLayer::Local => get_claude_local_settings_path(),
+        _ => get_claude_user_settings_path(),
+    }
+}
+
+/// 获取 ~/.claude/settings.local.json 路径
+pub fn get_claude_local_settings_path() -> PathBuf {
+    get_claude_config_dir().join("settings.local.json")
+}
+
+/// 获取 Claude Code 托管策略文件路径（管理员维护，优先级最高）
+pub fn get_claude_managed_settings_path() -> PathBuf {
+    #[cfg(target_os = "macos")]
+    let dir = PathBuf::from("/Library/Application Support/ClaudeCode");
+    #[cfg(target_os = "windows")]
+    let dir = PathBuf::from(r"C:\ProgramData\ClaudeCode");
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let dir = PathBuf::from("/etc/claude-code");
+    dir.join("managed-settings.json")
+}
+
+/// 获取用户级 settings.json 路径（兼容旧版 claude.json）
+pub fn get_claude_user_settings_path() -> PathBuf {
     let dir = get_claude_config_dir();
     let settings = dir.join("settings.json");
     if settings.exists() {
@@ -62,6 +89,9 @@ pub fn get_claude_settings_path() -> PathBuf {
     settings
 }
 
+/// 数据库默认文件名
+pub const DB_FILE_NAME: &str = "cli-hub.db";
+
 /// 获取应用配置目录路径 (~/.cli-hub)
 pub fn get_app_config_dir() -> PathBuf {
     if let Some(custom) = crate::app_store::get_app_config_dir_override() {
@@ -73,6 +103,12 @@ pub fn get_app_config_dir() -> PathBuf {
         .join(".cli-hub")
 }
 
+/// 获取数据库文件路径：Store 中设置了 db_path_override 时优先使用，否则位于应用配置目录
+pub fn get_db_path() -> PathBuf {
+    crate::app_store::get_db_path_override()
+        .unwrap_or_else(|| get_app_config_dir().join(DB_FILE_NAME))
+}
+
 /// 获取应用配置文件路径
 pub fn get_app_config_path() -> PathBuf {
     get_app_config_dir().join("config.json")
@@ -100,24 +136,96 @@ pub fn get_provider_config_path(provider_id: &str, provider_name: Option<&str>)
     get_claude_config_dir().join(format!("settings-{base_name}.json"))
 }
 
+/// 捕获模式下记录的写入：路径 -> 新内容，`None` 表示文件被删除
+pub type CapturedWrites = IndexMap<PathBuf, Option<Vec<u8>>>;
+
+thread_local! {
+    static CAPTURED_WRITES: RefCell<Option<CapturedWrites>> = const { RefCell::new(None) };
+}
+
+struct CaptureGuard;
+
+impl Drop for CaptureGuard {
+    fn drop(&mut self) {
+        CAPTURED_WRITES.with(|cell| cell.borrow_mut().take());
+    }
+}
+
+/// 在捕获模式下执行 `f`：当前线程内经由本模块的写入与删除只记录、不落盘，
+/// 之后的读取会看到已记录的内容，用于预览一次操作会改动哪些文件
+pub fn capture_writes<T>(
+    f: impl FnOnce() -> Result<T, AppError>,
+) -> Result<(T, CapturedWrites), AppError> {
+    if is_capturing_writes() {
+        return Err(AppError::Config("不支持嵌套的写入捕获".to_string()));
+    }
+    CAPTURED_WRITES.with(|cell| *cell.borrow_mut() = Some(CapturedWrites::new()));
+    let _guard = CaptureGuard;
+    let value = f()?;
+    let captured = CAPTURED_WRITES
+        .with(|cell| cell.borrow_mut().take())
+        .unwrap_or_default();
+    Ok((value, captured))
+}
+
+/// 当前线程是否处于 [`capture_writes`] 中
+pub fn is_capturing_writes() -> bool {
+    CAPTURED_WRITES.with(|cell| cell.borrow().is_some())
+}
+
+/// 捕获模式下记录写入，返回 true 表示已被捕获、无需真正写入
+fn capture_write(path: &Path, data: Option<&[u8]>) -> bool {
+    CAPTURED_WRITES.with(|cell| match cell.borrow_mut().as_mut() {
+        Some(captured) => {
+            captured.insert(path.to_path_buf(), data.map(<[u8]>::to_vec));
+            true
+        }
+        None => false,
+    })
+}
+
+/// 捕获模式下该路径已记录的内容：外层 `None` 表示未被捕获
+fn captured_content(path: &Path) -> Option<Option<Vec<u8>>> {
+    CAPTURED_WRITES.with(|cell| {
+        cell.borrow()
+            .as_ref()
+            .and_then(|captured| captured.get(path).cloned())
+    })
+}
+
+/// 读取文本文件；捕获模式下优先返回已记录的内容
+pub fn read_text_file(path: &Path) -> Result<String, AppError> {
+    match captured_content(path) {
+        Some(Some(data)) => Ok(String::from_utf8_lossy(&data).into_owned()),
+        Some(None) => Err(AppError::io(
+            path,
+            std::io::Error::from(std::io::ErrorKind::NotFound),
+        )),
+        None => fs::read_to_string(path).map_err(|e| AppError::io(path, e)),
+    }
+}
+
+/// 文件是否存在；捕获模式下计入已记录的写入与删除
+pub fn file_exists(path: &Path) -> bool {
+    match captured_content(path) {
+        Some(data) => data.is_some(),
+        None => path.exists(),
+    }
+}
+
 /// 读取 JSON 配置文件
 pub fn read_json_file<T: for<'a> Deserialize<'a>>(path: &Path) -> Result<T, AppError> {
-    if !path.exists() {
+    if !file_exists(path) {
         return Err(AppError::Config(format!("文件不存在: {}", path.display())));
     }
 
-    let content = fs::read_to_string(path).map_err(|e| AppError::io(path, e))?;
+    let content = read_text_file(path)?;
 
     serde_json::from_str(&content).map_err(|e| AppError::json(path, e))
 }
 
 /// 写入 JSON 配置文件
 pub fn write_json_file<T: Serialize>(path: &Path, data: &T) -> Result<(), AppError> {
-    // 确保目录存在
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
-    }
-
     let json =
         serde_json::to_string_pretty(data).map_err(|e| AppError::JsonSerialize { source: e })?;
 
@@ -126,14 +234,18 @@ pub fn write_json_file<T: Serialize>(path: &Path, data: &T) -> Result<(), AppErr
 
 /// 原子写入文本文件（用于 TOML/纯文本）
 pub fn write_text_file(path: &Path, data: &str) -> Result<(), AppError> {
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
-    }
     atomic_write(path, data.as_bytes())
 }
 
 /// 原子写入：写入临时文件后 rename 替换，避免半写状态
+///
+/// 父目录不存在时会先创建
 pub fn atomic_write(path: &Path, data: &[u8]) -> Result<(), AppError> {
+    if capture_write(path, Some(data)) {
+        return Ok(());
+    }
+    crate::services::demo::ensure_live_write_allowed(path)?;
+    crate::services::WriteAccessService::ensure_path_writable(path)?;
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
     }
@@ -236,6 +348,11 @@ pub fn copy_file(from: &Path, to: &Path) -> Result<(), AppError> {
 
 /// 删除文件
 pub fn delete_file(path: &Path) -> Result<(), AppError> {
+    if capture_write(path, None) {
+        return Ok(());
+    }
+    crate::services::demo::ensure_live_write_allowed(path)?;
+    crate::services::WriteAccessService::ensure_path_writable(path)?;
     if path.exists() {
         fs::remove_file(path).map_err(|e| AppError::io(path, e))?;
     }
@@ -2,19 +2,77 @@ use crate::error::AppError;
 use rusqlite::backup::Backup;
 use rusqlite::types::ValueRef;
 use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
 use tempfile::NamedTempFile;
 
+use super::restore::DbBackupOrigin;
 use super::{lock_conn, Database};
 
 const DB_BACKUP_RETAIN: usize = 10;
 
+/// Header line that marks a dump as partial; its value lists the exported domains
+const DOMAINS_HEADER: &str = "-- domains: ";
+
+/// Tables holding device-local credentials, left out of redacted full exports
+const CREDENTIAL_TABLES: [&str; 2] = ["settings", "api_tokens"];
+
+/// Data domain that can be exported on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportDomain {
+    Providers,
+    Mcp,
+    Prompts,
+}
+
+impl ExportDomain {
+    fn as_str(self) -> &'static str {
+        match self {
+            ExportDomain::Providers => "providers",
+            ExportDomain::Mcp => "mcp",
+            ExportDomain::Prompts => "prompts",
+        }
+    }
+
+    fn tables(self) -> &'static [&'static str] {
+        match self {
+            ExportDomain::Providers => &["providers", "provider_endpoints"],
+            ExportDomain::Mcp => &["mcp_servers"],
+            ExportDomain::Prompts => &["prompts"],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SqlExportOptions {
+    /// Empty exports the whole database
+    #[serde(default)]
+    pub domains: Vec<ExportDomain>,
+    /// Blank out secret-looking fields inside JSON columns (API keys, tokens, MCP env)
+    #[serde(default)]
+    pub redact_secrets: bool,
+}
+
 impl Database {
     /// Export database as SQLite-compatible SQL text
     pub fn export_sql(&self, target_path: &Path) -> Result<(), AppError> {
+        self.export_sql_with(target_path, &SqlExportOptions::default())
+    }
+
+    /// Export selected domains, optionally redacted.
+    ///
+    /// A partial dump carries no schema and uses `INSERT OR REPLACE`, so importing it
+    /// merges into the existing data instead of replacing the whole database.
+    pub fn export_sql_with(
+        &self,
+        target_path: &Path,
+        options: &SqlExportOptions,
+    ) -> Result<(), AppError> {
         let snapshot = self.snapshot_to_memory()?;
-        let dump = Self::dump_sql(&snapshot)?;
+        let dump = Self::dump_sql(&snapshot, options)?;
 
         if let Some(parent) = target_path.parent() {
             fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
@@ -33,10 +91,11 @@ impl Database {
         }
 
         let sql_raw = fs::read_to_string(source_path).map_err(|e| AppError::io(source_path, e))?;
+        let partial = Self::is_partial_dump(&sql_raw);
         let sql_content = Self::sanitize_import_sql(&sql_raw);
 
         // Backup before import
-        let backup_path = self.backup_database_file()?;
+        let backup_path = self.backup_database_file(DbBackupOrigin::SqlImport)?;
 
         // Execute import in temp database to avoid polluting main DB if failed
         let temp_file = NamedTempFile::new().map_err(|e| AppError::IoContext {
@@ -44,9 +103,19 @@ impl Database {
             source: e,
         })?;
         let temp_path = temp_file.path().to_path_buf();
-        let temp_conn =
+        let mut temp_conn =
             Connection::open(&temp_path).map_err(|e| AppError::Database(e.to_string()))?;
 
+        // Partial dumps only carry rows, so they are merged into a copy of the live data
+        if partial {
+            let conn = lock_conn!(self.conn);
+            let backup = Backup::new(&conn, &mut temp_conn)
+                .map_err(|e| AppError::Database(e.to_string()))?;
+            backup
+                .step(-1)
+                .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+
         temp_conn
             .execute_batch(&sql_content)
             .map_err(|e| AppError::Database(format!("Failed to execute SQL import: {e}")))?;
@@ -54,7 +123,10 @@ impl Database {
         // Fill missing tables/indexes and validate
         Self::create_tables_on_conn(&temp_conn)?;
         Self::apply_schema_migrations_on_conn(&temp_conn)?;
-        Self::validate_basic_state(&temp_conn)?;
+        // A merge cannot empty the database, so only full dumps need the sanity check
+        if !partial {
+            Self::validate_basic_state(&temp_conn)?;
+        }
 
         // Atomic write back to main DB using Backup
         {
@@ -74,21 +146,26 @@ impl Database {
     }
 
     /// Create consistent snapshot backup, returns backup file path (None if main DB not exist)
-    pub(crate) fn backup_database_file(&self) -> Result<Option<PathBuf>, AppError> {
-        let db_path = crate::config::get_app_config_dir().join("cli-hub.db");
+    pub(crate) fn backup_database_file(
+        &self,
+        origin: DbBackupOrigin,
+    ) -> Result<Option<PathBuf>, AppError> {
+        let db_path = crate::config::get_db_path();
         if !db_path.exists() {
             return Ok(None);
         }
 
-        let backup_dir = db_path
-            .parent()
-            .ok_or_else(|| AppError::Config("Invalid database path".to_string()))?
-            .join("backups");
+        let backup_dir = Self::db_backup_dir()?;
 
         fs::create_dir_all(&backup_dir).map_err(|e| AppError::io(&backup_dir, e))?;
 
         let backup_id = format!("db_backup_{}", chrono::Utc::now().format("%Y%m%d_%H%M%S"));
-        let backup_path = backup_dir.join(format!("{backup_id}.db"));
+        // A restore right after an import can land in the same second; never overwrite
+        let backup_path = std::iter::once(backup_id.clone())
+            .chain((2..).map(|n| format!("{backup_id}_{n}")))
+            .map(|id| backup_dir.join(format!("{id}.db")))
+            .find(|path| !path.exists())
+            .expect("unbounded candidates");
 
         {
             let conn = lock_conn!(self.conn);
@@ -101,25 +178,86 @@ impl Database {
                 .map_err(|e| AppError::Database(e.to_string()))?;
         }
 
+        if let Err(err) = Self::write_backup_manifest(&backup_path, origin) {
+            log::warn!("Failed to record checksum for {}: {}", backup_path.display(), err);
+        }
+
         Self::cleanup_db_backups(&backup_dir)?;
         Ok(Some(backup_path))
     }
+
+    /// Copy the live database into a new file and verify it with `PRAGMA integrity_check`
+    pub(crate) fn copy_to_file(&self, target: &Path) -> Result<(), AppError> {
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+        }
+
+        let mut dest_conn =
+            Connection::open(target).map_err(|e| AppError::Database(e.to_string()))?;
+        {
+            let conn = lock_conn!(self.conn);
+            let backup = Backup::new(&conn, &mut dest_conn)
+                .map_err(|e| AppError::Database(e.to_string()))?;
+            backup
+                .step(-1)
+                .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+
+        let check: String = dest_conn
+            .query_row("PRAGMA integrity_check;", [], |row| row.get(0))
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        if check != "ok" {
+            return Err(AppError::Database(format!(
+                "Integrity check failed for {}: {check}",
+                target.display()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Toggle `PRAGMA query_only`; while on, every write on this connection fails
+    pub(crate) fn set_read_only(&self, read_only: bool) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.pragma_update(None, "query_only", read_only)
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// Restore main DB from an in-memory snapshot (see `snapshot_to_memory`)
+    pub(crate) fn restore_from_snapshot(&self, snapshot: &Connection) -> Result<(), AppError> {
+        let mut main_conn = lock_conn!(self.conn);
+        let backup =
+            Backup::new(snapshot, &mut main_conn).map_err(|e| AppError::Database(e.to_string()))?;
+        backup
+            .step(-1)
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
 }
 
 // SQL dump/import helpers
 impl Database {
-    fn dump_sql(conn: &Connection) -> Result<String, AppError> {
+    fn dump_sql(conn: &Connection, options: &SqlExportOptions) -> Result<String, AppError> {
         let mut output = String::new();
         let timestamp = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
         let user_version: i64 = conn
             .query_row("PRAGMA user_version;", [], |row| row.get(0))
             .unwrap_or(0);
+        let partial = !options.domains.is_empty();
 
         output.push_str(&format!(
             "-- CLI Hub SQLite Export\n-- Generated: {timestamp}\n-- user_version: {user_version}\n"
         ));
+        if partial {
+            let domains: Vec<&str> = options.domains.iter().map(|d| d.as_str()).collect();
+            output.push_str(&format!("{DOMAINS_HEADER}{}\n", domains.join(",")));
+        }
+        if options.redact_secrets {
+            output.push_str("-- secrets: redacted\n");
+        }
         output.push_str("PRAGMA foreign_keys=OFF;\n");
-        output.push_str(&format!("PRAGMA user_version={user_version};\n"));
+        if !partial {
+            output.push_str(&format!("PRAGMA user_version={user_version};\n"));
+        }
         output.push_str("BEGIN TRANSACTION;\n");
 
         // Export schema
@@ -146,14 +284,30 @@ impl Database {
                 continue;
             }
 
-            output.push_str(&sql);
-            output.push_str(";\n");
+            // Partial dumps rely on the importing side's schema
+            if !partial {
+                output.push_str(&sql);
+                output.push_str(";\n");
+            }
 
             if obj_type == "table" && !name.starts_with("sqlite_") {
                 tables.push(name);
             }
         }
 
+        if partial {
+            let wanted: Vec<&str> = options
+                .domains
+                .iter()
+                .flat_map(|d| d.tables())
+                .copied()
+                .collect();
+            tables.retain(|t| wanted.contains(&t.as_str()));
+        } else if options.redact_secrets {
+            tables.retain(|t| !CREDENTIAL_TABLES.contains(&t.as_str()));
+        }
+        let insert = if partial { "INSERT OR REPLACE" } else { "INSERT" };
+
         // Export data
         for table in tables {
             let columns = Self::get_table_columns(conn, &table)?;
@@ -170,11 +324,24 @@ impl Database {
 
             while let Some(row) = rows.next().map_err(|e| AppError::Database(e.to_string()))? {
                 let mut values = Vec::with_capacity(columns.len());
-                for idx in 0..columns.len() {
+                for (idx, column) in columns.iter().enumerate() {
                     let value = row
                         .get_ref(idx)
                         .map_err(|e| AppError::Database(e.to_string()))?;
-                    values.push(Self::format_sql_value(value)?);
+                    // Merged providers must not compete with the importer's current one
+                    if partial && table == "providers" && column == "is_current" {
+                        values.push("0".to_string());
+                        continue;
+                    }
+                    let redacted = match value {
+                        ValueRef::Text(t) if options.redact_secrets => Self::redact_json_text(t),
+                        _ => None,
+                    };
+                    let formatted = match redacted {
+                        Some(text) => Self::format_sql_value(ValueRef::Text(text.as_bytes()))?,
+                        None => Self::format_sql_value(value)?,
+                    };
+                    values.push(formatted);
                 }
 
                 let cols = columns
@@ -183,7 +350,7 @@ impl Database {
                     .collect::<Vec<_>>()
                     .join(", ");
                 output.push_str(&format!(
-                    "INSERT INTO \"{table}\" ({cols}) VALUES ({});\n",
+                    "{insert} INTO \"{table}\" ({cols}) VALUES ({});\n",
                     values.join(", ")
                 ));
             }
@@ -231,6 +398,21 @@ impl Database {
         }
     }
 
+    /// Redacted copy of a JSON object/array column; other text is left as is
+    fn redact_json_text(raw: &[u8]) -> Option<String> {
+        let value: serde_json::Value = serde_json::from_slice(raw).ok()?;
+        if !value.is_object() && !value.is_array() {
+            return None;
+        }
+        serde_json::to_string(&crate::services::vcs_export::redact_value(value)).ok()
+    }
+
+    fn is_partial_dump(sql: &str) -> bool {
+        sql.lines()
+            .take_while(|line| line.starts_with("--"))
+            .any(|line| line.starts_with(DOMAINS_HEADER))
+    }
+
     /// Remove SQLite reserved object statements (like sqlite_sequence) to avoid import errors
     fn sanitize_import_sql(sql: &str) -> String {
         let mut cleaned = String::new();
@@ -298,8 +480,66 @@ impl Database {
         for entry in sorted.into_iter().take(remove_count) {
             if let Err(err) = fs::remove_file(entry.path()) {
                 log::warn!("Failed to delete old database backup {}: {}", entry.path().display(), err);
+                continue;
             }
+            // Drop the checksum manifest along with its backup
+            let _ = fs::remove_file(entry.path().with_extension("json"));
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partial_redacted_dump_merges_into_existing_data() {
+        let source = Database::memory().unwrap();
+        source
+            .conn
+            .lock()
+            .unwrap()
+            .execute_batch(
+                r#"INSERT INTO providers (id, app_type, name, settings_config, is_current)
+                       VALUES ('p1', 'claude', 'Mine', '{"env":{"API_KEY":"sk-1"}}', 1);
+                   INSERT INTO mcp_servers (id, name, server_config)
+                       VALUES ('gh', 'GitHub', '{"command":"gh","env":{"GH_TOKEN":"ghp_x"}}');"#,
+            )
+            .unwrap();
+
+        let options = SqlExportOptions {
+            domains: vec![ExportDomain::Mcp],
+            redact_secrets: true,
+        };
+        let dump = Database::dump_sql(&source.snapshot_to_memory().unwrap(), &options).unwrap();
+        assert!(Database::is_partial_dump(&dump));
+        assert!(!dump.contains("CREATE TABLE"));
+        assert!(!dump.contains("providers"));
+        assert!(!dump.contains("ghp_x"));
+
+        let target = Database::memory().unwrap();
+        let conn = target.conn.lock().unwrap();
+        conn.execute_batch(
+            "INSERT INTO providers (id, app_type, name, settings_config)
+                 VALUES ('t1', 'claude', 'Theirs', '{}');",
+        )
+        .unwrap();
+        conn.execute_batch(&Database::sanitize_import_sql(&dump))
+            .unwrap();
+
+        let providers: i64 = conn
+            .query_row("SELECT COUNT(*) FROM providers", [], |row| row.get(0))
+            .unwrap();
+        let server: String = conn
+            .query_row(
+                "SELECT server_config FROM mcp_servers WHERE id = 'gh'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(providers, 1);
+        assert!(server.contains("\"command\":\"gh\""));
+        assert!(server.contains("<redacted>"));
+    }
+}
@@ -0,0 +1,112 @@
+use crate::error::AppError;
+use rusqlite::{params, OptionalExtension};
+use serde::Serialize;
+
+use crate::database::{lock_conn, Database};
+
+/// An issued API token; the hash never leaves the database layer
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiTokenEntry {
+    pub id: String,
+    pub name: String,
+    pub scope: String,
+    /// Leading characters of the secret, shown so users can tell tokens apart
+    pub prefix: String,
+    /// Unix milliseconds
+    pub created_at: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<i64>,
+}
+
+const SELECT_COLUMNS: &str =
+    "SELECT id, name, scope, prefix, created_at, last_used_at, revoked_at FROM api_tokens";
+
+fn entry_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<ApiTokenEntry> {
+    Ok(ApiTokenEntry {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        scope: row.get(2)?,
+        prefix: row.get(3)?,
+        created_at: row.get(4)?,
+        last_used_at: row.get(5)?,
+        revoked_at: row.get(6)?,
+    })
+}
+
+impl Database {
+    pub fn insert_api_token(
+        &self,
+        entry: &ApiTokenEntry,
+        token_hash: &str,
+    ) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "INSERT INTO api_tokens (id, name, scope, token_hash, prefix, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                entry.id,
+                entry.name,
+                entry.scope,
+                token_hash,
+                entry.prefix,
+                entry.created_at,
+            ],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// All tokens, newest first (revoked ones included)
+    pub fn get_api_tokens(&self) -> Result<Vec<ApiTokenEntry>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare(&format!(
+                "{SELECT_COLUMNS} ORDER BY created_at DESC, id ASC"
+            ))
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map([], entry_from_row)
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    pub fn find_api_token_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<ApiTokenEntry>, AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.query_row(
+            &format!("{SELECT_COLUMNS} WHERE token_hash = ?1"),
+            params![token_hash],
+            entry_from_row,
+        )
+        .optional()
+        .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    pub fn touch_api_token(&self, id: &str, used_at: i64) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "UPDATE api_tokens SET last_used_at = ?1 WHERE id = ?2",
+            params![used_at, id],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Mark a token as revoked; returns false if it does not exist or was already revoked
+    pub fn revoke_api_token(&self, id: &str, revoked_at: i64) -> Result<bool, AppError> {
+        let conn = lock_conn!(self.conn);
+        let changed = conn
+            .execute(
+                "UPDATE api_tokens SET revoked_at = ?1 WHERE id = ?2 AND revoked_at IS NULL",
+                params![revoked_at, id],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(changed > 0)
+    }
+}
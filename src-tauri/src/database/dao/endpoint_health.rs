@@ -0,0 +1,140 @@
+use crate::error::AppError;
+use rusqlite::params;
+use serde::Serialize;
+
+use crate::database::{lock_conn, Database};
+
+/// Weight of the newest probe in the moving average, so one blip does not flip an endpoint
+const PROBE_WEIGHT: f64 = 0.3;
+
+/// Rolling availability of one provider endpoint
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointHealth {
+    pub url: String,
+    /// 0.0 (always failing) to 1.0 (always reachable)
+    pub score: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// Unix seconds
+    pub checked_at: i64,
+}
+
+impl Database {
+    /// Fold one probe result into the endpoint's score and return the new score
+    pub fn record_endpoint_probe(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+        url: &str,
+        latency_ms: Option<u64>,
+        error: Option<&str>,
+    ) -> Result<f64, AppError> {
+        let conn = lock_conn!(self.conn);
+        let sample = if error.is_none() { 1.0 } else { 0.0 };
+        conn.query_row(
+            "INSERT INTO endpoint_health
+                (app_type, provider_id, url, score, latency_ms, last_error, checked_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT (app_type, provider_id, url) DO UPDATE SET
+                score = score * (1.0 - ?8) + excluded.score * ?8,
+                latency_ms = excluded.latency_ms,
+                last_error = excluded.last_error,
+                checked_at = excluded.checked_at
+             RETURNING score",
+            params![
+                app_type,
+                provider_id,
+                url,
+                sample,
+                latency_ms.map(|ms| ms as i64),
+                error,
+                chrono::Utc::now().timestamp(),
+                PROBE_WEIGHT,
+            ],
+            |row| row.get(0),
+        )
+        .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    pub fn get_endpoint_health(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+    ) -> Result<Vec<EndpointHealth>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare(
+                "SELECT url, score, latency_ms, last_error, checked_at FROM endpoint_health
+                 WHERE app_type = ?1 AND provider_id = ?2
+                 ORDER BY score DESC, latency_ms ASC, url ASC",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map(params![app_type, provider_id], |row| {
+                Ok(EndpointHealth {
+                    url: row.get(0)?,
+                    score: row.get(1)?,
+                    latency_ms: row.get::<_, Option<i64>>(2)?.map(|ms| ms.max(0) as u64),
+                    last_error: row.get(3)?,
+                    checked_at: row.get(4)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// Drop scores for endpoints the provider no longer lists
+    pub fn prune_endpoint_health(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+        keep: &[String],
+    ) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        let keep = serde_json::to_string(keep).map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "DELETE FROM endpoint_health
+             WHERE app_type = ?1 AND provider_id = ?2
+               AND url NOT IN (SELECT value FROM json_each(?3))",
+            params![app_type, provider_id, keep],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn probes_blend_into_a_moving_score() {
+        let db = Database::memory().expect("create memory db");
+        let url = "https://a.example";
+        assert_eq!(
+            db.record_endpoint_probe("claude", "p", url, Some(80), None)
+                .unwrap(),
+            1.0
+        );
+        let degraded = db
+            .record_endpoint_probe("claude", "p", url, None, Some("timeout"))
+            .unwrap();
+        assert!((degraded - 0.7).abs() < 1e-9);
+
+        db.record_endpoint_probe("claude", "p", "https://b.example", Some(50), None)
+            .unwrap();
+        let health = db.get_endpoint_health("claude", "p").unwrap();
+        assert_eq!(health[0].url, "https://b.example");
+        assert_eq!(health[1].last_error.as_deref(), Some("timeout"));
+
+        db.prune_endpoint_health("claude", "p", &[url.to_string()])
+            .unwrap();
+        let health = db.get_endpoint_health("claude", "p").unwrap();
+        assert_eq!(health.len(), 1);
+        assert_eq!(health[0].url, url);
+    }
+}
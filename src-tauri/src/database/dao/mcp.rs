@@ -1,4 +1,4 @@
-use crate::app_config::{McpApps, McpServer};
+use crate::app_config::{AppType, McpApps, McpServer, McpSortUpdate, QuarantinedMcpEntry};
 use crate::error::AppError;
 use indexmap::IndexMap;
 use rusqlite::params;
@@ -11,7 +11,7 @@ impl Database {
         let mut stmt = conn.prepare(
             "SELECT id, name, server_config, description, homepage, docs, tags, enabled_claude, enabled_codex, enabled_gemini
              FROM mcp_servers
-             ORDER BY name ASC, id ASC"
+             ORDER BY COALESCE(sort_index, 999999), name ASC, id ASC"
         ).map_err(|e| AppError::Database(e.to_string()))?;
 
         let server_iter = stmt
@@ -61,10 +61,21 @@ impl Database {
     pub fn save_mcp_server(&self, server: &McpServer) -> Result<(), AppError> {
         let conn = lock_conn!(self.conn);
         conn.execute(
-            "INSERT OR REPLACE INTO mcp_servers (
+            // Upsert instead of REPLACE so the user-defined sort_index survives edits
+            "INSERT INTO mcp_servers (
                 id, name, server_config, description, homepage, docs, tags,
                 enabled_claude, enabled_codex, enabled_gemini
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+            ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name,
+                server_config = excluded.server_config,
+                description = excluded.description,
+                homepage = excluded.homepage,
+                docs = excluded.docs,
+                tags = excluded.tags,
+                enabled_claude = excluded.enabled_claude,
+                enabled_codex = excluded.enabled_codex,
+                enabled_gemini = excluded.enabled_gemini",
             params![
                 server.id,
                 server.name,
@@ -82,10 +93,113 @@ impl Database {
         Ok(())
     }
 
+    /// 在一个事务内设置某应用的启用集合：列表内的服务器启用，其余全部停用
+    pub fn set_mcp_enabled_set(
+        &self,
+        app: &AppType,
+        enabled_ids: &[String],
+    ) -> Result<(), AppError> {
+        let column = match app {
+            AppType::Claude => "enabled_claude",
+            AppType::Codex => "enabled_codex",
+            AppType::Gemini => "enabled_gemini",
+        };
+        let mut conn = lock_conn!(self.conn);
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        tx.execute(&format!("UPDATE mcp_servers SET {column} = 0"), [])
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        for id in enabled_ids {
+            tx.execute(
+                &format!("UPDATE mcp_servers SET {column} = 1 WHERE id = ?1"),
+                params![id],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+
+        tx.commit().map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Persist a new display/sync order; servers missing from `updates` keep their index
+    pub fn update_mcp_sort_order(&self, updates: &[McpSortUpdate]) -> Result<(), AppError> {
+        let mut conn = lock_conn!(self.conn);
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        for update in updates {
+            tx.execute(
+                "UPDATE mcp_servers SET sort_index = ?1 WHERE id = ?2",
+                params![update.sort_index as i64, update.id],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+        tx.commit().map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
     pub fn delete_mcp_server(&self, id: &str) -> Result<(), AppError> {
         let conn = lock_conn!(self.conn);
         conn.execute("DELETE FROM mcp_servers WHERE id = ?1", params![id])
             .map_err(|e| AppError::Database(e.to_string()))?;
         Ok(())
     }
+
+    pub fn get_quarantined_mcp(&self) -> Result<Vec<QuarantinedMcpEntry>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, app_type, server_id, raw, error, created_at
+             FROM mcp_quarantine
+             ORDER BY created_at DESC, id ASC",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let entry_iter = stmt
+            .query_map([], |row| {
+                Ok(QuarantinedMcpEntry {
+                    id: row.get(0)?,
+                    app_type: row.get(1)?,
+                    server_id: row.get(2)?,
+                    raw: row.get(3)?,
+                    error: row.get(4)?,
+                    created_at: row.get(5)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut entries = Vec::new();
+        for entry_res in entry_iter {
+            entries.push(entry_res.map_err(|e| AppError::Database(e.to_string()))?);
+        }
+        Ok(entries)
+    }
+
+    pub fn save_quarantined_mcp(&self, entry: &QuarantinedMcpEntry) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "INSERT OR REPLACE INTO mcp_quarantine (
+                id, app_type, server_id, raw, error, created_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                entry.id,
+                entry.app_type,
+                entry.server_id,
+                entry.raw,
+                entry.error,
+                entry.created_at,
+            ],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    pub fn delete_quarantined_mcp(&self, id: &str) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute("DELETE FROM mcp_quarantine WHERE id = ?1", params![id])
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
 }
@@ -1,5 +1,12 @@
+mod api_token;
+mod endpoint_health;
 mod mcp;
 mod prompt;
 mod provider;
+mod provider_error;
 mod settings;
 mod skill;
+
+pub use api_token::ApiTokenEntry;
+pub use endpoint_health::EndpointHealth;
+pub use provider::ProviderSwitchEntry;
@@ -1,7 +1,8 @@
 use crate::error::AppError;
-use crate::prompt::Prompt;
+use crate::prompt::{GeminiContextFile, Prompt, PromptSummary};
 use indexmap::IndexMap;
 use rusqlite::params;
+use rusqlite::OptionalExtension;
 
 use crate::database::{lock_conn, Database};
 
@@ -10,7 +11,7 @@ impl Database {
         let conn = lock_conn!(self.conn);
         let mut stmt = conn
             .prepare(
-                "SELECT id, name, content, description, enabled, created_at, updated_at
+                "SELECT id, name, content, description, enabled, created_at, updated_at, version
              FROM prompts WHERE app_type = ?1
              ORDER BY created_at ASC, id ASC",
             )
@@ -25,6 +26,7 @@ impl Database {
                 let enabled: bool = row.get(4)?;
                 let created_at: Option<i64> = row.get(5)?;
                 let updated_at: Option<i64> = row.get(6)?;
+                let version: i64 = row.get(7)?;
 
                 Ok((
                     id.clone(),
@@ -36,6 +38,7 @@ impl Database {
                         enabled,
                         created_at,
                         updated_at,
+                        version: Some(version),
                     },
                 ))
             })
@@ -49,12 +52,203 @@ impl Database {
         Ok(prompts)
     }
 
+    /// 仅查询元数据；`limit` 为 None 时返回全部
+    pub fn get_prompt_summaries(
+        &self,
+        app_type: &str,
+        limit: Option<usize>,
+        offset: usize,
+    ) -> Result<Vec<PromptSummary>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, name, description, enabled, created_at, updated_at, length(content),
+             substr(content, 1, 14) = 'clihub-enc:v1:'
+             FROM prompts WHERE app_type = ?1
+             ORDER BY created_at ASC, id ASC
+             LIMIT ?2 OFFSET ?3",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let limit = limit.map(|l| l as i64).unwrap_or(-1);
+        let summary_iter = stmt
+            .query_map(params![app_type, limit, offset as i64], |row| {
+                Ok(PromptSummary {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    description: row.get(2)?,
+                    enabled: row.get(3)?,
+                    created_at: row.get(4)?,
+                    updated_at: row.get(5)?,
+                    content_length: row.get(6)?,
+                    encrypted: row.get(7)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut summaries = Vec::new();
+        for summary_res in summary_iter {
+            summaries.push(summary_res.map_err(|e| AppError::Database(e.to_string()))?);
+        }
+        Ok(summaries)
+    }
+
+    pub fn get_prompt(&self, app_type: &str, id: &str) -> Result<Option<Prompt>, AppError> {
+        self.query_one_prompt("id = ?2", params![app_type, id])
+    }
+
+    /// 当前已启用的提示词（若存在多个，取最早创建的一个）
+    pub fn get_enabled_prompt(&self, app_type: &str) -> Result<Option<Prompt>, AppError> {
+        self.query_one_prompt("enabled = 1", params![app_type])
+    }
+
+    pub fn get_prompt_content(&self, app_type: &str, id: &str) -> Result<Option<String>, AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.query_row(
+            "SELECT content FROM prompts WHERE app_type = ?1 AND id = ?2",
+            params![app_type, id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 是否已有内容相同（忽略首尾空白）的提示词
+    pub fn prompt_content_exists(&self, app_type: &str, content: &str) -> Result<bool, AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.query_row(
+            "SELECT EXISTS(
+                SELECT 1 FROM prompts
+                WHERE app_type = ?1
+                  AND trim(content, ' ' || char(9) || char(10) || char(13)) = ?2
+            )",
+            params![app_type, content.trim()],
+            |row| row.get(0),
+        )
+        .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 启用指定提示词，同时停用该应用下的其他提示词
+    pub fn set_enabled_prompt(&self, app_type: &str, id: &str) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "UPDATE prompts SET enabled = (id = ?2) WHERE app_type = ?1",
+            params![app_type, id],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 在一个事务中批量设置启用状态；启用某项时先停用该应用下的其他提示词，
+    /// 任一 ID 不存在时整体回滚
+    pub fn set_prompts_enabled(
+        &self,
+        app_type: &str,
+        changes: &[(String, bool)],
+    ) -> Result<(), AppError> {
+        let mut conn = lock_conn!(self.conn);
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        if let Some((id, _)) = changes.iter().find(|(_, enabled)| *enabled) {
+            tx.execute(
+                "UPDATE prompts SET enabled = (id = ?2) WHERE app_type = ?1",
+                params![app_type, id],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+        for (id, enabled) in changes {
+            let updated = tx
+                .execute(
+                    "UPDATE prompts SET enabled = ?3 WHERE app_type = ?1 AND id = ?2",
+                    params![app_type, id, enabled],
+                )
+                .map_err(|e| AppError::Database(e.to_string()))?;
+            if updated == 0 {
+                return Err(AppError::InvalidInput(format!("提示词 {id} 不存在")));
+            }
+        }
+
+        tx.commit().map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    fn query_one_prompt(
+        &self,
+        condition: &str,
+        params: impl rusqlite::Params,
+    ) -> Result<Option<Prompt>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let sql = format!(
+            "SELECT id, name, content, description, enabled, created_at, updated_at, version
+             FROM prompts WHERE app_type = ?1 AND {condition}
+             ORDER BY created_at ASC, id ASC
+             LIMIT 1"
+        );
+        conn.query_row(&sql, params, |row| {
+            Ok(Prompt {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                content: row.get(2)?,
+                description: row.get(3)?,
+                enabled: row.get(4)?,
+                created_at: row.get(5)?,
+                updated_at: row.get(6)?,
+                version: row.get(7)?,
+            })
+        })
+        .optional()
+        .map_err(|e| AppError::Database(e.to_string()))
+    }
+
     pub fn save_prompt(&self, app_type: &str, prompt: &Prompt) -> Result<(), AppError> {
+        self.write_prompt(app_type, prompt, None).map(|_| ())
+    }
+
+    /// Save only if the stored row is still at `prompt.version`; see `save_provider_checked`
+    pub fn save_prompt_checked(&self, app_type: &str, prompt: &Prompt) -> Result<(), AppError> {
+        let Some(expected) = prompt.version else {
+            return self.save_prompt(app_type, prompt);
+        };
+        let Some(actual) = self.write_prompt(app_type, prompt, Some(expected))? else {
+            return Ok(());
+        };
+        let current = self.get_prompt(app_type, &prompt.id)?;
+        Err(AppError::conflict(
+            "prompt",
+            &prompt.id,
+            expected,
+            actual,
+            serde_json::to_value(current).unwrap_or_default(),
+        ))
+    }
+
+    /// Returns the stored version instead of writing when it differs from `expected_version`
+    fn write_prompt(
+        &self,
+        app_type: &str,
+        prompt: &Prompt,
+        expected_version: Option<i64>,
+    ) -> Result<Option<i64>, AppError> {
         let conn = lock_conn!(self.conn);
+        let version: i64 = conn
+            .query_row(
+                "SELECT version FROM prompts WHERE id = ?1 AND app_type = ?2",
+                params![prompt.id, app_type],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| AppError::Database(e.to_string()))?
+            .unwrap_or(0);
+        if expected_version.is_some_and(|expected| expected != version) {
+            return Ok(Some(version));
+        }
+
         conn.execute(
             "INSERT OR REPLACE INTO prompts (
-                id, app_type, name, content, description, enabled, created_at, updated_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                id, app_type, name, content, description, enabled, created_at, updated_at, version
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
             params![
                 prompt.id,
                 app_type,
@@ -64,10 +258,11 @@ impl Database {
                 prompt.enabled,
                 prompt.created_at,
                 prompt.updated_at,
+                version + 1,
             ],
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
-        Ok(())
+        Ok(None)
     }
 
     pub fn delete_prompt(&self, app_type: &str, id: &str) -> Result<(), AppError> {
@@ -79,4 +274,154 @@ impl Database {
         .map_err(|e| AppError::Database(e.to_string()))?;
         Ok(())
     }
+
+    pub fn get_gemini_context_files(&self) -> Result<Vec<GeminiContextFile>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare(
+                "SELECT file_name, content, enabled, created_at, updated_at
+             FROM gemini_context_files
+             ORDER BY created_at ASC, file_name ASC",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let file_iter = stmt
+            .query_map([], |row| {
+                Ok(GeminiContextFile {
+                    file_name: row.get(0)?,
+                    content: row.get(1)?,
+                    enabled: row.get(2)?,
+                    created_at: row.get(3)?,
+                    updated_at: row.get(4)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut files = Vec::new();
+        for file_res in file_iter {
+            files.push(file_res.map_err(|e| AppError::Database(e.to_string()))?);
+        }
+        Ok(files)
+    }
+
+    pub fn save_gemini_context_file(&self, file: &GeminiContextFile) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "INSERT OR REPLACE INTO gemini_context_files (
+                file_name, content, enabled, created_at, updated_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                file.file_name,
+                file.content,
+                file.enabled,
+                file.created_at,
+                file.updated_at,
+            ],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    pub fn delete_gemini_context_file(&self, file_name: &str) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "DELETE FROM gemini_context_files WHERE file_name = ?1",
+            params![file_name],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prompt(id: &str, content: &str, created_at: i64) -> Prompt {
+        Prompt {
+            id: id.to_string(),
+            name: id.to_uppercase(),
+            content: content.to_string(),
+            description: None,
+            enabled: false,
+            created_at: Some(created_at),
+            updated_at: Some(created_at),
+            version: None,
+        }
+    }
+
+    #[test]
+    fn test_summaries_and_targeted_queries() {
+        let db = Database::memory().expect("create memory db");
+        db.save_prompt("claude", &prompt("a", "alpha", 1)).unwrap();
+        db.save_prompt("claude", &prompt("b", "  beta\n", 2))
+            .unwrap();
+        db.save_prompt("claude", &prompt("c", "gamma", 3)).unwrap();
+        db.save_prompt("codex", &prompt("x", "other app", 1))
+            .unwrap();
+
+        let page = db.get_prompt_summaries("claude", Some(2), 1).unwrap();
+        let ids: Vec<_> = page.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, ["b", "c"]);
+        assert_eq!(page[0].content_length, 7);
+        assert_eq!(db.get_prompt_summaries("claude", None, 0).unwrap().len(), 3);
+
+        assert_eq!(
+            db.get_prompt_content("claude", "c").unwrap().as_deref(),
+            Some("gamma")
+        );
+        assert!(db.get_prompt_content("codex", "c").unwrap().is_none());
+
+        assert!(db.prompt_content_exists("claude", "beta").unwrap());
+        assert!(!db.prompt_content_exists("claude", "other app").unwrap());
+
+        assert!(db.get_enabled_prompt("claude").unwrap().is_none());
+        db.set_enabled_prompt("claude", "b").unwrap();
+        db.set_enabled_prompt("claude", "c").unwrap();
+        assert_eq!(db.get_enabled_prompt("claude").unwrap().unwrap().id, "c");
+        assert!(!db.get_prompt("claude", "b").unwrap().unwrap().enabled);
+        assert!(!db.get_prompt("codex", "x").unwrap().unwrap().enabled);
+    }
+
+    #[test]
+    fn checked_save_rejects_stale_version() {
+        let db = Database::memory().expect("create memory db");
+        db.save_prompt("claude", &prompt("a", "alpha", 1)).unwrap();
+        let loaded = db.get_prompt("claude", "a").unwrap().unwrap();
+        assert_eq!(loaded.version, Some(1));
+
+        let mut first = loaded.clone();
+        first.content = "first window".to_string();
+        db.save_prompt_checked("claude", &first).unwrap();
+
+        let mut stale = loaded;
+        stale.content = "second window".to_string();
+        let err = db.save_prompt_checked("claude", &stale).unwrap_err();
+        let payload: serde_json::Value = serde_json::from_str(&err.to_string()).unwrap();
+        assert_eq!(payload["code"], "VERSION_CONFLICT");
+        assert_eq!(payload["expectedVersion"], 1);
+        assert_eq!(payload["currentVersion"], 2);
+        assert_eq!(payload["current"]["content"], "first window");
+
+        let stored = db.get_prompt("claude", "a").unwrap().unwrap();
+        assert_eq!(stored.content, "first window");
+    }
+
+    #[test]
+    fn bulk_enable_is_atomic() {
+        let db = Database::memory().expect("create memory db");
+        for (i, id) in ["a", "b", "c"].into_iter().enumerate() {
+            db.save_prompt("claude", &prompt(id, id, i as i64)).unwrap();
+        }
+        db.set_enabled_prompt("claude", "a").unwrap();
+
+        let changes = [("b".to_string(), true), ("c".to_string(), false)];
+        db.set_prompts_enabled("claude", &changes).unwrap();
+        assert_eq!(db.get_enabled_prompt("claude").unwrap().unwrap().id, "b");
+        assert!(!db.get_prompt("claude", "a").unwrap().unwrap().enabled);
+
+        let bad = [("c".to_string(), true), ("missing".to_string(), false)];
+        assert!(db.set_prompts_enabled("claude", &bad).is_err());
+        assert_eq!(db.get_enabled_prompt("claude").unwrap().unwrap().id, "b");
+    }
 }
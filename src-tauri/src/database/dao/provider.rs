@@ -1,11 +1,25 @@
 use crate::error::AppError;
 use crate::provider::{Provider, ProviderMeta};
 use indexmap::IndexMap;
-use rusqlite::params;
+use rusqlite::{params, OptionalExtension, Transaction};
+use serde::Serialize;
 use std::collections::HashMap;
 
 use crate::database::{lock_conn, Database};
 
+/// Switch history kept per app; older rows are pruned on insert
+const SWITCH_LOG_LIMIT: i64 = 1000;
+
+/// One provider switch, used to correlate CLI sessions with the provider that served them
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderSwitchEntry {
+    pub provider_id: String,
+    pub provider_name: String,
+    /// Unix milliseconds
+    pub switched_at: i64,
+}
+
 impl Database {
     pub fn get_all_providers(
         &self,
@@ -13,7 +27,8 @@ impl Database {
     ) -> Result<IndexMap<String, Provider>, AppError> {
         let conn = lock_conn!(self.conn);
         let mut stmt = conn.prepare(
-            "SELECT id, name, settings_config, website_url, category, created_at, sort_index, notes, icon, icon_color, meta
+            "SELECT id, name, settings_config, website_url, category, created_at, sort_index, notes, icon, icon_color, meta,
+                    last_switched_at, switch_count, version
              FROM providers WHERE app_type = ?1
              ORDER BY COALESCE(sort_index, 999999), created_at ASC, id ASC"
         ).map_err(|e| AppError::Database(e.to_string()))?;
@@ -31,6 +46,9 @@ impl Database {
                 let icon: Option<String> = row.get(8)?;
                 let icon_color: Option<String> = row.get(9)?;
                 let meta_str: String = row.get(10)?;
+                let last_switched_at: Option<i64> = row.get(11)?;
+                let switch_count: u32 = row.get(12)?;
+                let version: i64 = row.get(13)?;
 
                 let settings_config =
                     serde_json::from_str(&settings_config_str).unwrap_or(serde_json::Value::Null);
@@ -50,6 +68,9 @@ impl Database {
                         meta: Some(meta),
                         icon,
                         icon_color,
+                        last_switched_at,
+                        switch_count,
+                        version: Some(version),
                     },
                 ))
             })
@@ -117,65 +138,66 @@ impl Database {
     }
 
     pub fn save_provider(&self, app_type: &str, provider: &Provider) -> Result<(), AppError> {
+        self.write_provider(app_type, provider, None).map(|_| ())
+    }
+
+    /// Save only if the stored row is still at `provider.version`.
+    ///
+    /// A provider without a version (new, or from a caller that does not track one) is saved
+    /// unconditionally. On mismatch the error carries the stored row so the UI can merge.
+    pub fn save_provider_checked(
+        &self,
+        app_type: &str,
+        provider: &Provider,
+    ) -> Result<(), AppError> {
+        let Some(expected) = provider.version else {
+            return self.save_provider(app_type, provider);
+        };
+        let Some(actual) = self.write_provider(app_type, provider, Some(expected))? else {
+            return Ok(());
+        };
+        let current = self.get_all_providers(app_type)?.shift_remove(&provider.id);
+        Err(AppError::conflict(
+            "provider",
+            &provider.id,
+            expected,
+            actual,
+            serde_json::to_value(current).unwrap_or_default(),
+        ))
+    }
+
+    /// Save several providers, possibly across apps, in one transaction.
+    ///
+    /// Either every row is written or none is; rows are otherwise saved exactly as
+    /// [`Self::save_provider`] would.
+    pub fn save_providers(&self, providers: &[(String, Provider)]) -> Result<(), AppError> {
         let mut conn = lock_conn!(self.conn);
         let tx = conn
             .transaction()
             .map_err(|e| AppError::Database(e.to_string()))?;
+        for (app_type, provider) in providers {
+            write_provider_row(&tx, app_type, provider, None)?;
+        }
+        tx.commit().map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
 
-        // Handle meta and endpoints
-        let mut meta_clone = provider.meta.clone().unwrap_or_default();
-        let endpoints = std::mem::take(&mut meta_clone.custom_endpoints);
-
-        // Check if it exists to preserve is_current
-        let is_current: bool = tx
-            .query_row(
-                "SELECT is_current FROM providers WHERE id = ?1 AND app_type = ?2",
-                params![provider.id, app_type],
-                |row| row.get(0),
-            )
-            .unwrap_or(false);
-
-        tx.execute(
-            "INSERT OR REPLACE INTO providers (
-                id, app_type, name, settings_config, website_url, category,
-                created_at, sort_index, notes, icon, icon_color, meta, is_current
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
-            params![
-                provider.id,
-                app_type,
-                provider.name,
-                serde_json::to_string(&provider.settings_config).unwrap(),
-                provider.website_url,
-                provider.category,
-                provider.created_at,
-                provider.sort_index,
-                provider.notes,
-                provider.icon,
-                provider.icon_color,
-                serde_json::to_string(&meta_clone).unwrap(),
-                is_current,
-            ],
-        )
-        .map_err(|e| AppError::Database(e.to_string()))?;
-
-        // Sync endpoints: Delete all and re-insert
-        tx.execute(
-            "DELETE FROM provider_endpoints WHERE provider_id = ?1 AND app_type = ?2",
-            params![provider.id, app_type],
-        )
-        .map_err(|e| AppError::Database(e.to_string()))?;
-
-        for (url, endpoint) in endpoints {
-            tx.execute(
-                "INSERT INTO provider_endpoints (provider_id, app_type, url, added_at)
-                 VALUES (?1, ?2, ?3, ?4)",
-                params![provider.id, app_type, url, endpoint.added_at],
-            )
+    /// Single-row transaction around [`write_provider_row`]
+    fn write_provider(
+        &self,
+        app_type: &str,
+        provider: &Provider,
+        expected_version: Option<i64>,
+    ) -> Result<Option<i64>, AppError> {
+        let mut conn = lock_conn!(self.conn);
+        let tx = conn
+            .transaction()
             .map_err(|e| AppError::Database(e.to_string()))?;
+        if let Some(actual) = write_provider_row(&tx, app_type, provider, expected_version)? {
+            return Ok(Some(actual));
         }
-
         tx.commit().map_err(|e| AppError::Database(e.to_string()))?;
-        Ok(())
+        Ok(None)
     }
 
     pub fn delete_provider(&self, app_type: &str, id: &str) -> Result<(), AppError> {
@@ -185,6 +207,11 @@ impl Database {
             params![id, app_type],
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "DELETE FROM provider_errors WHERE provider_id = ?1 AND app_type = ?2",
+            params![id, app_type],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
         Ok(())
     }
 
@@ -212,6 +239,58 @@ impl Database {
         Ok(())
     }
 
+    /// 记录一次切换：更新最近切换时间并累加切换次数
+    pub fn record_provider_switch(&self, app_type: &str, id: &str) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        let now = chrono::Utc::now();
+        conn.execute(
+            "UPDATE providers SET last_switched_at = ?1, switch_count = switch_count + 1
+             WHERE id = ?2 AND app_type = ?3",
+            params![now.timestamp(), id, app_type],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "INSERT INTO provider_switch_log (app_type, provider_id, provider_name, switched_at)
+             SELECT app_type, id, name, ?1 FROM providers WHERE id = ?2 AND app_type = ?3",
+            params![now.timestamp_millis(), id, app_type],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "DELETE FROM provider_switch_log WHERE app_type = ?1 AND id NOT IN (
+                SELECT id FROM provider_switch_log WHERE app_type = ?1
+                ORDER BY switched_at DESC, id DESC LIMIT ?2
+             )",
+            params![app_type, SWITCH_LOG_LIMIT],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Switch history for an app, oldest first
+    pub fn get_provider_switch_log(
+        &self,
+        app_type: &str,
+    ) -> Result<Vec<ProviderSwitchEntry>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare(
+                "SELECT provider_id, provider_name, switched_at FROM provider_switch_log
+                 WHERE app_type = ?1 ORDER BY switched_at ASC, id ASC",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map(params![app_type], |row| {
+                Ok(ProviderSwitchEntry {
+                    provider_id: row.get(0)?,
+                    provider_name: row.get(1)?,
+                    switched_at: row.get(2)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
     pub fn add_custom_endpoint(
         &self,
         app_type: &str,
@@ -242,3 +321,79 @@ impl Database {
         Ok(())
     }
 }
+
+/// Upsert one provider row and its endpoints inside `tx` without committing.
+///
+/// Returns the stored version instead of writing when it differs from `expected_version`.
+fn write_provider_row(
+    tx: &Transaction<'_>,
+    app_type: &str,
+    provider: &Provider,
+    expected_version: Option<i64>,
+) -> Result<Option<i64>, AppError> {
+    // Handle meta and endpoints
+    let mut meta_clone = provider.meta.clone().unwrap_or_default();
+    let endpoints = std::mem::take(&mut meta_clone.custom_endpoints);
+
+    // Check if it exists to preserve is_current and usage stats (maintained by record_provider_switch)
+    let existing: Option<(bool, Option<i64>, u32, i64)> = tx
+        .query_row(
+            "SELECT is_current, last_switched_at, switch_count, version
+             FROM providers WHERE id = ?1 AND app_type = ?2",
+            params![provider.id, app_type],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .optional()
+        .map_err(|e| AppError::Database(e.to_string()))?;
+    let (is_current, last_switched_at, switch_count, version) =
+        existing.unwrap_or((false, None, 0, 0));
+    // A row deleted in the meantime reads as version 0 and so conflicts as well
+    if expected_version.is_some_and(|expected| expected != version) {
+        return Ok(Some(version));
+    }
+
+    tx.execute(
+        "INSERT OR REPLACE INTO providers (
+            id, app_type, name, settings_config, website_url, category,
+            created_at, sort_index, notes, icon, icon_color, meta, is_current,
+            last_switched_at, switch_count, version
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+        params![
+            provider.id,
+            app_type,
+            provider.name,
+            serde_json::to_string(&provider.settings_config).unwrap(),
+            provider.website_url,
+            provider.category,
+            provider.created_at,
+            provider.sort_index,
+            provider.notes,
+            provider.icon,
+            provider.icon_color,
+            serde_json::to_string(&meta_clone).unwrap(),
+            is_current,
+            last_switched_at,
+            switch_count,
+            version + 1,
+        ],
+    )
+    .map_err(|e| AppError::Database(e.to_string()))?;
+
+    // Sync endpoints: Delete all and re-insert
+    tx.execute(
+        "DELETE FROM provider_endpoints WHERE provider_id = ?1 AND app_type = ?2",
+        params![provider.id, app_type],
+    )
+    .map_err(|e| AppError::Database(e.to_string()))?;
+
+    for (url, endpoint) in endpoints {
+        tx.execute(
+            "INSERT INTO provider_endpoints (provider_id, app_type, url, added_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![provider.id, app_type, url, endpoint.added_at],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+    }
+
+    Ok(None)
+}
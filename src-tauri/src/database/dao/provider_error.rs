@@ -0,0 +1,111 @@
+use crate::error::AppError;
+use crate::provider::{ProviderErrorNote, ProviderOperation};
+use rusqlite::params;
+use serde_json::Value;
+
+use crate::database::{lock_conn, Database};
+
+/// Failures kept per provider; older rows are pruned on insert
+const ERROR_HISTORY_LIMIT: i64 = 50;
+
+impl Database {
+    pub fn record_provider_error(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+        note: &ProviderErrorNote,
+    ) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "INSERT INTO provider_errors (app_type, provider_id, operation, message, occurred_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                app_type,
+                provider_id,
+                note.operation.as_str(),
+                note.message,
+                note.occurred_at
+            ],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "DELETE FROM provider_errors WHERE app_type = ?1 AND provider_id = ?2 AND id NOT IN (
+                SELECT id FROM provider_errors WHERE app_type = ?1 AND provider_id = ?2
+                ORDER BY occurred_at DESC, id DESC LIMIT ?3
+             )",
+            params![app_type, provider_id, ERROR_HISTORY_LIMIT],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Failure history for one provider, newest first
+    pub fn get_provider_errors(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+    ) -> Result<Vec<ProviderErrorNote>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare(
+                "SELECT operation, message, occurred_at FROM provider_errors
+                 WHERE app_type = ?1 AND provider_id = ?2
+                 ORDER BY occurred_at DESC, id DESC",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map(params![app_type, provider_id], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i64>(2)?,
+                ))
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut notes = Vec::new();
+        for row in rows {
+            let (operation, message, occurred_at) =
+                row.map_err(|e| AppError::Database(e.to_string()))?;
+            // Rows written by a newer version with an unknown operation are skipped
+            let Ok(operation) =
+                serde_json::from_value::<ProviderOperation>(Value::String(operation))
+            else {
+                continue;
+            };
+            notes.push(ProviderErrorNote {
+                operation,
+                message,
+                occurred_at,
+            });
+        }
+        Ok(notes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history_is_newest_first_and_capped_per_provider() {
+        let db = Database::memory().expect("create memory db");
+        let note = |occurred_at: i64| ProviderErrorNote {
+            operation: ProviderOperation::Usage,
+            message: format!("failure {occurred_at}"),
+            occurred_at,
+        };
+        for at in 0..ERROR_HISTORY_LIMIT + 5 {
+            db.record_provider_error("claude", "relay", &note(at))
+                .unwrap();
+        }
+        db.record_provider_error("claude", "other", &note(1))
+            .unwrap();
+
+        let history = db.get_provider_errors("claude", "relay").unwrap();
+        assert_eq!(history.len(), ERROR_HISTORY_LIMIT as usize);
+        assert_eq!(history[0].occurred_at, ERROR_HISTORY_LIMIT + 4);
+        assert_eq!(db.get_provider_errors("claude", "other").unwrap().len(), 1);
+        assert!(db.get_provider_errors("codex", "relay").unwrap().is_empty());
+    }
+}
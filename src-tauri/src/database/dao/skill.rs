@@ -9,7 +9,7 @@ impl Database {
     pub fn get_skills(&self) -> Result<IndexMap<String, SkillState>, AppError> {
         let conn = lock_conn!(self.conn);
         let mut stmt = conn
-            .prepare("SELECT key, installed, installed_at FROM skills ORDER BY key ASC")
+            .prepare("SELECT key, installed, installed_at, source FROM skills ORDER BY key ASC")
             .map_err(|e| AppError::Database(e.to_string()))?;
 
         let skill_iter = stmt
@@ -17,6 +17,7 @@ impl Database {
                 let key: String = row.get(0)?;
                 let installed: bool = row.get(1)?;
                 let installed_at_ts: i64 = row.get(2)?;
+                let source: Option<String> = row.get(3)?;
 
                 let installed_at =
                     chrono::DateTime::from_timestamp(installed_at_ts, 0).unwrap_or_default();
@@ -26,6 +27,7 @@ impl Database {
                     SkillState {
                         installed,
                         installed_at,
+                        source,
                     },
                 ))
             })
@@ -42,8 +44,13 @@ impl Database {
     pub fn update_skill_state(&self, key: &str, state: &SkillState) -> Result<(), AppError> {
         let conn = lock_conn!(self.conn);
         conn.execute(
-            "INSERT OR REPLACE INTO skills (key, installed, installed_at) VALUES (?1, ?2, ?3)",
-            params![key, state.installed, state.installed_at.timestamp()],
+            "INSERT OR REPLACE INTO skills (key, installed, installed_at, source) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                key,
+                state.installed,
+                state.installed_at.timestamp(),
+                state.source
+            ],
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
         Ok(())
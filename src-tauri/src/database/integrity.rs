@@ -0,0 +1,283 @@
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use serde_json::Value;
+
+use super::{lock_conn, Database};
+use crate::error::AppError;
+
+const APP_TYPES: [&str; 3] = ["claude", "codex", "gemini"];
+
+/// Kind of problem found by the startup provider integrity pass
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProviderIssueKind {
+    /// settings_config is not valid JSON
+    InvalidSettingsJson,
+    /// settings_config is missing a required key or a key has the wrong type
+    InvalidSettingsShape,
+    /// meta is not valid JSON; it will be replaced by defaults on the next save
+    InvalidMeta,
+    /// The app has providers but none is marked current
+    NoCurrentProvider,
+    /// More than one provider of the same app is marked current
+    MultipleCurrentProviders,
+}
+
+/// One finding of the integrity pass
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderIntegrityIssue {
+    pub app_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider_id: Option<String>,
+    pub kind: ProviderIssueKind,
+    pub message: String,
+    /// Repaired automatically; unfixed issues need the user's attention
+    pub fixed: bool,
+}
+
+struct ProviderRow {
+    id: String,
+    settings_config: String,
+    meta: String,
+    is_current: bool,
+    last_switched_at: Option<i64>,
+}
+
+impl Database {
+    /// Check every provider row and repair what is safe to repair.
+    ///
+    /// Only the current-provider flags are rewritten (no current: the first readable provider
+    /// in display order is picked; several current: the most recently switched one is kept).
+    /// Broken JSON is reported and left untouched.
+    pub fn check_provider_integrity(&self) -> Result<Vec<ProviderIntegrityIssue>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut issues = Vec::new();
+        for app_type in APP_TYPES {
+            let rows = Self::provider_rows(&conn, app_type)?;
+            for row in &rows {
+                check_row(app_type, row, &mut issues);
+            }
+            Self::repair_current_flag(&conn, app_type, &rows, &mut issues)?;
+        }
+        Ok(issues)
+    }
+
+    fn provider_rows(conn: &Connection, app_type: &str) -> Result<Vec<ProviderRow>, AppError> {
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, settings_config, meta, is_current, last_switched_at FROM providers
+                 WHERE app_type = ?1
+                 ORDER BY COALESCE(sort_index, 999999), created_at ASC, id ASC",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map(params![app_type], |row| {
+                Ok(ProviderRow {
+                    id: row.get(0)?,
+                    settings_config: row.get(1)?,
+                    meta: row.get(2)?,
+                    is_current: row.get(3)?,
+                    last_switched_at: row.get(4)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    fn repair_current_flag(
+        conn: &Connection,
+        app_type: &str,
+        rows: &[ProviderRow],
+        issues: &mut Vec<ProviderIntegrityIssue>,
+    ) -> Result<(), AppError> {
+        let current: Vec<&ProviderRow> = rows.iter().filter(|r| r.is_current).collect();
+        let (keep, kind, message) = match current.as_slice() {
+            [_] => return Ok(()),
+            [] => match rows
+                .iter()
+                .find(|r| serde_json::from_str::<Value>(&r.settings_config).is_ok())
+                .or(rows.first())
+            {
+                Some(first) => (
+                    first,
+                    ProviderIssueKind::NoCurrentProvider,
+                    format!("No current provider; selected '{}'", first.id),
+                ),
+                None => return Ok(()),
+            },
+            many => {
+                let latest = many
+                    .iter()
+                    .copied()
+                    .reduce(|best, row| {
+                        if row.last_switched_at.unwrap_or_default()
+                            > best.last_switched_at.unwrap_or_default()
+                        {
+                            row
+                        } else {
+                            best
+                        }
+                    })
+                    .unwrap_or(many[0]);
+                (
+                    latest,
+                    ProviderIssueKind::MultipleCurrentProviders,
+                    format!(
+                        "{} providers were marked current; kept '{}'",
+                        many.len(),
+                        latest.id
+                    ),
+                )
+            }
+        };
+
+        conn.execute(
+            "UPDATE providers SET is_current = (id = ?1) WHERE app_type = ?2",
+            params![keep.id, app_type],
+        )
+        .map_err(|e| AppError::Database(format!("Failed to repair current provider: {e}")))?;
+        log::warn!("Provider integrity ({app_type}): {message}");
+        issues.push(ProviderIntegrityIssue {
+            app_type: app_type.to_string(),
+            provider_id: Some(keep.id.clone()),
+            kind,
+            message,
+            fixed: true,
+        });
+        Ok(())
+    }
+}
+
+fn check_row(app_type: &str, row: &ProviderRow, issues: &mut Vec<ProviderIntegrityIssue>) {
+    let mut report = |kind, message: String| {
+        log::warn!("Provider integrity ({app_type}/{}): {message}", row.id);
+        issues.push(ProviderIntegrityIssue {
+            app_type: app_type.to_string(),
+            provider_id: Some(row.id.clone()),
+            kind,
+            message,
+            fixed: false,
+        });
+    };
+
+    match serde_json::from_str::<Value>(&row.settings_config) {
+        Ok(settings) => {
+            if let Some(problem) = settings_shape_problem(app_type, &settings) {
+                report(ProviderIssueKind::InvalidSettingsShape, problem);
+            }
+        }
+        Err(e) => report(
+            ProviderIssueKind::InvalidSettingsJson,
+            format!("settings_config is not valid JSON: {e}"),
+        ),
+    }
+
+    if let Err(e) = serde_json::from_str::<crate::provider::ProviderMeta>(&row.meta) {
+        report(
+            ProviderIssueKind::InvalidMeta,
+            format!("meta cannot be parsed: {e}"),
+        );
+    }
+}
+
+/// Structural requirements per app, matching what the live-config writers rely on
+fn settings_shape_problem(app_type: &str, settings: &Value) -> Option<String> {
+    let Some(obj) = settings.as_object() else {
+        return Some("settings_config must be a JSON object".to_string());
+    };
+    let expect = |key: &str, ok: fn(&Value) -> bool, expected: &str| {
+        obj.get(key)
+            .filter(|value| !ok(value))
+            .map(|_| format!("'{key}' must be {expected}"))
+    };
+    match app_type {
+        "codex" => {
+            if !obj.contains_key("auth") {
+                return Some("missing required key 'auth'".to_string());
+            }
+            expect("auth", Value::is_object, "an object")
+                .or_else(|| expect("config", |v| v.is_string() || v.is_null(), "a string"))
+        }
+        "gemini" => expect("env", Value::is_object, "an object")
+            .or_else(|| expect("config", |v| v.is_object() || v.is_null(), "an object")),
+        _ => expect("env", Value::is_object, "an object"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seeded() -> Database {
+        let db = Database::memory().unwrap();
+        db.conn
+            .lock()
+            .unwrap()
+            .execute_batch(
+                "INSERT INTO providers (id, app_type, name, settings_config, sort_index, is_current)
+                     VALUES ('c1', 'claude', 'C1', '{\"env\":{}}', 1, 0);
+                 INSERT INTO providers (id, app_type, name, settings_config, sort_index, is_current)
+                     VALUES ('c2', 'claude', 'C2', '{broken', 0, 0);
+                 INSERT INTO providers (id, app_type, name, settings_config, meta, is_current, last_switched_at)
+                     VALUES ('x1', 'codex', 'X1', '{\"config\":\"\"}', 'not json', 1, 10);
+                 INSERT INTO providers (id, app_type, name, settings_config, is_current, last_switched_at)
+                     VALUES ('x2', 'codex', 'X2', '{\"auth\":{}}', 1, 20);",
+            )
+            .unwrap();
+        db
+    }
+
+    fn kinds(issues: &[ProviderIntegrityIssue], app: &str) -> Vec<(String, ProviderIssueKind)> {
+        issues
+            .iter()
+            .filter(|i| i.app_type == app)
+            .map(|i| (i.provider_id.clone().unwrap_or_default(), i.kind))
+            .collect()
+    }
+
+    #[test]
+    fn reports_broken_rows_and_repairs_current_flags() {
+        let db = seeded();
+        let issues = db.check_provider_integrity().unwrap();
+
+        assert_eq!(
+            kinds(&issues, "claude"),
+            [
+                ("c2".to_string(), ProviderIssueKind::InvalidSettingsJson),
+                ("c1".to_string(), ProviderIssueKind::NoCurrentProvider),
+            ]
+        );
+        assert_eq!(
+            kinds(&issues, "codex"),
+            [
+                ("x1".to_string(), ProviderIssueKind::InvalidSettingsShape),
+                ("x1".to_string(), ProviderIssueKind::InvalidMeta),
+                (
+                    "x2".to_string(),
+                    ProviderIssueKind::MultipleCurrentProviders
+                ),
+            ]
+        );
+        assert!(issues.iter().all(|i| i.fixed
+            == matches!(
+                i.kind,
+                ProviderIssueKind::NoCurrentProvider | ProviderIssueKind::MultipleCurrentProviders
+            )));
+
+        assert_eq!(
+            db.get_current_provider("claude").unwrap().as_deref(),
+            Some("c1")
+        );
+        assert_eq!(
+            db.get_current_provider("codex").unwrap().as_deref(),
+            Some("x2")
+        );
+
+        // The flags are fixed now, only the unrepairable rows are reported again
+        let again = db.check_provider_integrity().unwrap();
+        assert_eq!(again.len(), 3);
+        assert!(again.iter().all(|i| !i.fixed));
+    }
+}
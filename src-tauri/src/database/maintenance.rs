@@ -0,0 +1,258 @@
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use super::{lock_conn, Database};
+use crate::error::AppError;
+
+/// Prefix of per-app common config snippets (`common_config_<app>`)
+const COMMON_CONFIG_PREFIX: &str = "common_config_";
+const APP_TYPES: [&str; 3] = ["claude", "codex", "gemini"];
+/// Unknown settings keys are renamed under this prefix rather than deleted, so a key
+/// missing from [`known_setting_keys`] can still be restored by hand
+const QUARANTINE_PREFIX: &str = "orphaned:";
+
+/// Settings keys still read by the app. Anything else in the settings table was
+/// left behind by a removed feature; new keys must be registered here.
+fn known_setting_keys() -> Vec<String> {
+    let mut keys: Vec<String> = [
+        crate::settings::APP_SETTINGS_KEY,
+        crate::services::sync_pause::PAUSED_KEY,
+        crate::services::external_backup::STATUS_KEY,
+        crate::services::relay_directory::CACHE_KEY,
+        crate::services::provider_presets::CACHE_KEY,
+        crate::services::mcp_profile::PROFILES_KEY,
+        crate::services::prompt_schedule::SCHEDULES_KEY,
+        crate::services::provider_upgrade::UPGRADE_KEY,
+        crate::deeplink::AUDIT_LOG_KEY,
+    ]
+    .iter()
+    .map(|k| k.to_string())
+    .collect();
+    keys.extend(
+        APP_TYPES
+            .iter()
+            .map(|app| format!("{COMMON_CONFIG_PREFIX}{app}")),
+    );
+    keys
+}
+
+/// Endpoint row whose provider no longer exists
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanEndpoint {
+    pub provider_id: String,
+    pub app_type: String,
+    pub url: String,
+}
+
+/// Orphaned rows found (unless `dry_run`, endpoints are removed and setting keys quarantined)
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanCleanupReport {
+    pub endpoints: Vec<OrphanEndpoint>,
+    pub setting_keys: Vec<String>,
+    pub dry_run: bool,
+}
+
+impl OrphanCleanupReport {
+    pub fn is_empty(&self) -> bool {
+        self.endpoints.is_empty() && self.setting_keys.is_empty()
+    }
+}
+
+impl Database {
+    /// Report orphaned endpoint rows and unknown settings keys
+    ///
+    /// Unless `dry_run`, orphaned endpoints are deleted and unknown keys are moved under
+    /// [`QUARANTINE_PREFIX`] with their values intact.
+    pub fn cleanup_orphaned_data(&self, dry_run: bool) -> Result<OrphanCleanupReport, AppError> {
+        let conn = lock_conn!(self.conn);
+        Self::cleanup_orphans_on_conn(&conn, dry_run)
+    }
+
+    pub(crate) fn cleanup_orphans_on_conn(
+        conn: &Connection,
+        dry_run: bool,
+    ) -> Result<OrphanCleanupReport, AppError> {
+        let endpoints = Self::orphan_endpoints(conn)?;
+        let setting_keys = Self::orphan_setting_keys(conn)?;
+
+        if !dry_run {
+            conn.execute(
+                "DELETE FROM provider_endpoints WHERE NOT EXISTS (
+                    SELECT 1 FROM providers p
+                    WHERE p.id = provider_endpoints.provider_id
+                      AND p.app_type = provider_endpoints.app_type
+                )",
+                [],
+            )
+            .map_err(|e| AppError::Database(format!("Failed to delete orphaned endpoints: {e}")))?;
+            for key in &setting_keys {
+                conn.execute(
+                    "UPDATE OR REPLACE settings SET key = ?2 WHERE key = ?1",
+                    params![key, format!("{QUARANTINE_PREFIX}{key}")],
+                )
+                .map_err(|e| {
+                    AppError::Database(format!("Failed to quarantine setting {key}: {e}"))
+                })?;
+            }
+        }
+
+        Ok(OrphanCleanupReport {
+            endpoints,
+            setting_keys,
+            dry_run,
+        })
+    }
+
+    fn orphan_endpoints(conn: &Connection) -> Result<Vec<OrphanEndpoint>, AppError> {
+        let mut stmt = conn
+            .prepare(
+                "SELECT e.provider_id, e.app_type, e.url FROM provider_endpoints e
+                 WHERE NOT EXISTS (
+                    SELECT 1 FROM providers p
+                    WHERE p.id = e.provider_id AND p.app_type = e.app_type
+                 )
+                 ORDER BY e.app_type, e.provider_id, e.id",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(OrphanEndpoint {
+                    provider_id: row.get(0)?,
+                    app_type: row.get(1)?,
+                    url: row.get(2)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    fn orphan_setting_keys(conn: &Connection) -> Result<Vec<String>, AppError> {
+        let known = known_setting_keys();
+        let mut stmt = conn
+            .prepare("SELECT key FROM settings ORDER BY key")
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let keys = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .map_err(|e| AppError::Database(e.to_string()))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(keys
+            .into_iter()
+            .filter(|k| {
+                !known.contains(k)
+                    && !k.starts_with(QUARANTINE_PREFIX)
+                    && !crate::settings::is_profile_settings_key(k)
+            })
+            .collect())
+    }
+
+    /// Current `PRAGMA user_version`
+    pub fn schema_version(&self) -> Result<i32, AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.query_row("PRAGMA user_version;", [], |row| row.get(0))
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// Row count of every user table, for diagnostics
+    pub fn table_row_counts(&self) -> Result<BTreeMap<String, i64>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare(
+                "SELECT name FROM sqlite_master
+                 WHERE type = 'table' AND name NOT LIKE 'sqlite_%'",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let tables = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .map_err(|e| AppError::Database(e.to_string()))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut counts = BTreeMap::new();
+        for table in tables {
+            let sql = format!("SELECT COUNT(*) FROM \"{}\"", table.replace('"', "\"\""));
+            let count = conn
+                .query_row(&sql, [], |row| row.get(0))
+                .map_err(|e| AppError::Database(e.to_string()))?;
+            counts.insert(table, count);
+        }
+        Ok(counts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seeded() -> Database {
+        let db = Database::memory().unwrap();
+        {
+            let conn = db.conn.lock().unwrap();
+            conn.execute_batch(
+                "PRAGMA foreign_keys = OFF;
+                 INSERT INTO providers (id, app_type, name, settings_config) VALUES ('p1', 'claude', 'P1', '{}');
+                 INSERT INTO provider_endpoints (provider_id, app_type, url) VALUES ('p1', 'claude', 'https://a.example');
+                 INSERT INTO provider_endpoints (provider_id, app_type, url) VALUES ('gone', 'claude', 'https://b.example');
+                 INSERT INTO provider_endpoints (provider_id, app_type, url) VALUES ('p1', 'codex', 'https://c.example');
+                 INSERT INTO settings (key, value) VALUES ('app_settings', '{}');
+                 INSERT INTO settings (key, value) VALUES ('app_settings@work', '{}');
+                 INSERT INTO settings (key, value) VALUES ('common_config_codex', '');
+                 INSERT INTO settings (key, value) VALUES ('legacy_feature_flag', '1');
+                 PRAGMA foreign_keys = ON;",
+            )
+            .unwrap();
+        }
+        db
+    }
+
+    #[test]
+    fn dry_run_reports_without_deleting() {
+        let db = seeded();
+        let report = db.cleanup_orphaned_data(true).unwrap();
+
+        assert_eq!(
+            report
+                .endpoints
+                .iter()
+                .map(|e| e.url.as_str())
+                .collect::<Vec<_>>(),
+            ["https://b.example", "https://c.example"]
+        );
+        assert_eq!(report.setting_keys, ["legacy_feature_flag"]);
+        assert_eq!(
+            db.get_setting("legacy_feature_flag").unwrap().as_deref(),
+            Some("1")
+        );
+    }
+
+    #[test]
+    fn cleanup_removes_only_orphans() {
+        let db = seeded();
+        let report = db.cleanup_orphaned_data(false).unwrap();
+        assert!(!report.is_empty());
+
+        assert!(db.cleanup_orphaned_data(true).unwrap().is_empty());
+        assert!(db.get_setting("legacy_feature_flag").unwrap().is_none());
+        // Unknown keys are kept under the quarantine prefix, not deleted
+        assert_eq!(
+            db.get_setting("orphaned:legacy_feature_flag")
+                .unwrap()
+                .as_deref(),
+            Some("1")
+        );
+        assert!(db.get_setting("common_config_codex").unwrap().is_some());
+        let remaining: i64 = db
+            .conn
+            .lock()
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM provider_endpoints", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(remaining, 1);
+    }
+}
@@ -2,21 +2,28 @@ use crate::app_config::MultiAppConfig;
 use crate::error::AppError;
 use rusqlite::{params, Connection};
 
+use super::progress::{MigrationPhase, MigrationProgress};
 use super::{lock_conn, to_json_string, Database};
 
 impl Database {
-    /// Migrate data from MultiAppConfig (JSON)
+    /// Migrate data from MultiAppConfig (JSON), reporting progress per migrated item
     pub fn migrate_from_json(&self, config: &MultiAppConfig) -> Result<(), AppError> {
-        let mut conn = lock_conn!(self.conn);
-        let tx = conn
-            .transaction()
-            .map_err(|e| AppError::Database(e.to_string()))?;
+        let mut progress = MigrationProgress::new(MigrationPhase::Json, json_item_count(config));
+        let result = (|| -> Result<(), AppError> {
+            let mut conn = lock_conn!(self.conn);
+            let tx = conn
+                .transaction()
+                .map_err(|e| AppError::Database(e.to_string()))?;
 
-        Self::migrate_from_json_tx(&tx, config)?;
+            Self::migrate_from_json_tx(&tx, config, &mut progress)?;
 
-        tx.commit()
-            .map_err(|e| AppError::Database(format!("Commit migration failed: {e}")))?;
-        Ok(())
+            progress.step("commit");
+            tx.commit()
+                .map_err(|e| AppError::Database(format!("Commit migration failed: {e}")))?;
+            Ok(())
+        })();
+        progress.finish(&result);
+        result
     }
 
     /// Run migration dry-run in memory for pre-deployment validation (no disk writes)
@@ -29,7 +36,11 @@ impl Database {
         let tx = conn
             .transaction()
             .map_err(|e| AppError::Database(e.to_string()))?;
-        Self::migrate_from_json_tx(&tx, config)?;
+        Self::migrate_from_json_tx(
+            &tx,
+            config,
+            &mut MigrationProgress::silent(MigrationPhase::Json),
+        )?;
 
         // Explicitly drop transaction without committing (in-memory DB discarded anyway)
         drop(tx);
@@ -39,8 +50,10 @@ impl Database {
     pub(crate) fn migrate_from_json_tx(
         tx: &rusqlite::Transaction<'_>,
         config: &MultiAppConfig,
+        progress: &mut MigrationProgress,
     ) -> Result<(), AppError> {
         // 1. Migrate Providers
+        progress.step("providers");
         for (app_key, manager) in &config.apps {
             let app_type = app_key;
             let current_id = &manager.current;
@@ -84,10 +97,12 @@ impl Database {
                     )
                     .map_err(|e| AppError::Database(format!("Migrate endpoint failed: {e}")))?;
                 }
+                progress.advance();
             }
         }
 
         // 2. Migrate MCP Servers
+        progress.step("mcp");
         if let Some(servers) = &config.mcp.servers {
             for (id, server) in servers {
                 tx.execute(
@@ -109,11 +124,13 @@ impl Database {
                     ],
                 )
                 .map_err(|e| AppError::Database(format!("Migrate mcp server failed: {e}")))?;
+                progress.advance();
             }
         }
 
         // 3. Migrate Prompts
-        let migrate_prompts =
+        progress.step("prompts");
+        let mut migrate_prompts =
             |prompts_map: &std::collections::HashMap<String, crate::prompt::Prompt>,
              app_type: &str|
              -> Result<(), AppError> {
@@ -134,6 +151,7 @@ impl Database {
                         ],
                     )
                     .map_err(|e| AppError::Database(format!("Migrate prompt failed: {e}")))?;
+                    progress.advance();
                 }
                 Ok(())
             };
@@ -143,12 +161,14 @@ impl Database {
         migrate_prompts(&config.prompts.gemini.prompts, "gemini")?;
 
         // 4. Migrate Skills
+        progress.step("skills");
         for (key, state) in &config.skills.skills {
             tx.execute(
                 "INSERT OR REPLACE INTO skills (key, installed, installed_at) VALUES (?1, ?2, ?3)",
                 params![key, state.installed, state.installed_at.timestamp()],
             )
             .map_err(|e| AppError::Database(format!("Migrate skill failed: {e}")))?;
+            progress.advance();
         }
 
         for repo in &config.skills.repos {
@@ -156,9 +176,11 @@ impl Database {
                 "INSERT OR REPLACE INTO skill_repos (owner, name, branch, enabled, skills_path) VALUES (?1, ?2, ?3, ?4, ?5)",
                 params![repo.owner, repo.name, repo.branch, repo.enabled, repo.skills_path],
             ).map_err(|e| AppError::Database(format!("Migrate skill repo failed: {e}")))?;
+            progress.advance();
         }
 
         // 5. Migrate Common Config
+        progress.step("common_config");
         if let Some(snippet) = &config.common_config_snippets.claude {
             tx.execute(
                 "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
@@ -180,11 +202,26 @@ impl Database {
             )
             .map_err(|e| AppError::Database(format!("Migrate settings failed: {e}")))?;
         }
+        progress.advance();
 
         Ok(())
     }
 }
 
+/// Units of work reported by the JSON migration: one per row group plus the common config
+fn json_item_count(config: &MultiAppConfig) -> usize {
+    let providers: usize = config.apps.values().map(|m| m.providers.len()).sum();
+    let mcp = config
+        .mcp
+        .servers
+        .as_ref()
+        .map_or(0, |servers| servers.len());
+    let prompts = config.prompts.claude.prompts.len()
+        + config.prompts.codex.prompts.len()
+        + config.prompts.gemini.prompts.len();
+    providers + mcp + prompts + config.skills.skills.len() + config.skills.repos.len() + 1
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -238,6 +275,9 @@ mod tests {
                 meta: None,
                 icon: None,
                 icon_color: None,
+                last_switched_at: None,
+                switch_count: 0,
+                version: None,
             },
         );
 
@@ -3,10 +3,26 @@ use rusqlite::Connection;
 use std::sync::Mutex;
 
 mod backup;
+mod integrity;
+mod maintenance;
 mod migration;
+mod progress;
+mod repo;
+mod restore;
 mod schema;
 pub mod dao;
 
+pub use backup::{ExportDomain, SqlExportOptions};
+pub use integrity::{ProviderIntegrityIssue, ProviderIssueKind};
+pub use maintenance::{OrphanCleanupReport, OrphanEndpoint};
+pub use progress::{
+    migration_status, set_migration_progress_sink, MigrationStatus, MIGRATION_PROGRESS_EVENT,
+};
+pub use repo::{ApiTokensRepo, McpRepo, PromptsRepo, ProvidersRepo, SettingsRepo, Storage};
+pub use restore::{
+    BackupVerification, DbBackupInfo, DbBackupOrigin, RestoreChange, RestorePreview,
+};
+
 /// Safe JSON serialization helper
 pub(crate) fn to_json_string<T: serde::Serialize>(value: &T) -> Result<String, AppError> {
     serde_json::to_string(value)
@@ -31,7 +47,7 @@ pub struct Database {
 impl Database {
     /// Initialize database connection and create tables
     pub fn init() -> Result<Self, AppError> {
-        let db_path = crate::config::get_app_config_dir().join("cli-hub.db");
+        let db_path = crate::config::get_db_path();
 
         if let Some(parent) = db_path.parent() {
             std::fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
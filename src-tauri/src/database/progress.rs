@@ -0,0 +1,164 @@
+use serde::Serialize;
+use std::sync::{OnceLock, RwLock};
+
+use crate::error::AppError;
+
+/// Event emitted to the frontend whenever the migration status changes
+pub const MIGRATION_PROGRESS_EVENT: &str = "migration-progress";
+
+/// Which migration is currently running
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MigrationPhase {
+    Schema,
+    Json,
+}
+
+/// Progress of the startup migrations, polled through `get_migration_status`
+/// and pushed through [`MIGRATION_PROGRESS_EVENT`]
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationStatus {
+    pub running: bool,
+    pub phase: Option<MigrationPhase>,
+    pub step: Option<String>,
+    /// Progress of the current phase, 0-100
+    pub percent: u8,
+    pub error: Option<String>,
+}
+
+type ProgressSink = Box<dyn Fn(&MigrationStatus) + Send + Sync>;
+
+static STATUS: OnceLock<RwLock<MigrationStatus>> = OnceLock::new();
+static SINK: OnceLock<ProgressSink> = OnceLock::new();
+
+fn cell() -> &'static RwLock<MigrationStatus> {
+    STATUS.get_or_init(|| RwLock::new(MigrationStatus::default()))
+}
+
+/// Register the callback that forwards status changes (bound to the app handle at startup)
+pub fn set_migration_progress_sink(sink: impl Fn(&MigrationStatus) + Send + Sync + 'static) {
+    let _ = SINK.set(Box::new(sink));
+}
+
+pub fn migration_status() -> MigrationStatus {
+    cell().read().map(|guard| guard.clone()).unwrap_or_default()
+}
+
+fn update(apply: impl FnOnce(&mut MigrationStatus)) {
+    let snapshot = match cell().write() {
+        Ok(mut guard) => {
+            apply(&mut guard);
+            guard.clone()
+        }
+        Err(_) => return,
+    };
+    if let Some(sink) = SINK.get() {
+        sink(&snapshot);
+    }
+}
+
+/// Counts finished units of work and reports only when the whole percent changes,
+/// so migrating thousands of rows doesn't flood the event channel
+pub(crate) struct MigrationProgress {
+    phase: MigrationPhase,
+    silent: bool,
+    total: usize,
+    done: usize,
+    last: Option<(String, u8)>,
+}
+
+impl MigrationProgress {
+    pub(crate) fn new(phase: MigrationPhase, total: usize) -> Self {
+        Self {
+            phase,
+            silent: false,
+            total: total.max(1),
+            done: 0,
+            last: None,
+        }
+    }
+
+    /// Used by dry-runs, which must not touch the startup status
+    pub(crate) fn silent(phase: MigrationPhase) -> Self {
+        Self {
+            silent: true,
+            ..Self::new(phase, 0)
+        }
+    }
+
+    pub(crate) fn percent(&self) -> u8 {
+        (self.done.min(self.total) * 100 / self.total) as u8
+    }
+
+    /// Announce the step that is about to run
+    pub(crate) fn step(&mut self, step: &str) {
+        if self.silent {
+            return;
+        }
+        let percent = self.percent();
+        if self
+            .last
+            .as_ref()
+            .is_some_and(|(last_step, last_percent)| last_step == step && *last_percent == percent)
+        {
+            return;
+        }
+        self.last = Some((step.to_string(), percent));
+        let phase = self.phase;
+        update(|status| {
+            status.running = true;
+            status.phase = Some(phase);
+            status.step = Some(step.to_string());
+            status.percent = percent;
+            status.error = None;
+        });
+    }
+
+    /// Mark one unit of work as finished within the current step
+    pub(crate) fn advance(&mut self) {
+        self.done += 1;
+        if let Some((step, percent)) = self.last.clone() {
+            if percent != self.percent() {
+                self.step(&step);
+            }
+        }
+    }
+
+    pub(crate) fn finish(self, result: &Result<(), AppError>) {
+        if self.silent {
+            return;
+        }
+        let error = result.as_ref().err().map(|e| e.to_string());
+        update(|status| {
+            status.running = false;
+            if error.is_none() {
+                status.percent = 100;
+            }
+            status.error = error;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_whole_percent_changes_only() {
+        let mut progress = MigrationProgress::new(MigrationPhase::Json, 400);
+        progress.step("providers");
+        for _ in 0..3 {
+            progress.advance();
+        }
+        assert_eq!(progress.percent(), 0);
+        assert_eq!(progress.last, Some(("providers".to_string(), 0)));
+
+        progress.advance();
+        assert_eq!(progress.last, Some(("providers".to_string(), 1)));
+
+        let mut silent = MigrationProgress::silent(MigrationPhase::Json);
+        silent.step("providers");
+        assert!(silent.last.is_none());
+    }
+}
@@ -0,0 +1,471 @@
+//! Storage traits the services depend on.
+//!
+//! `Database` (SQLite) is the only backend today; `Database::memory()` doubles as the
+//! in-memory backend for tests. A new backend implements these traits and is handed to
+//! `AppState::with_storage`.
+
+use indexmap::IndexMap;
+
+use crate::app_config::{AppType, McpServer, McpSortUpdate, QuarantinedMcpEntry};
+use crate::database::dao::{ApiTokenEntry, EndpointHealth, ProviderSwitchEntry};
+use crate::database::Database;
+use crate::error::AppError;
+use crate::prompt::{GeminiContextFile, Prompt, PromptSummary};
+use crate::provider::{Provider, ProviderErrorNote};
+use crate::services::skill::{SkillRepo, SkillState};
+
+pub trait ProvidersRepo: Send + Sync {
+    fn get_all_providers(&self, app_type: &str) -> Result<IndexMap<String, Provider>, AppError>;
+    fn get_current_provider(&self, app_type: &str) -> Result<Option<String>, AppError>;
+    fn save_provider(&self, app_type: &str, provider: &Provider) -> Result<(), AppError>;
+    /// Optimistic save; fails with a version conflict when the stored row has moved on.
+    fn save_provider_checked(&self, app_type: &str, provider: &Provider) -> Result<(), AppError>;
+    /// Saves `(app_type, provider)` pairs atomically: all rows are written or none.
+    fn save_providers(&self, providers: &[(String, Provider)]) -> Result<(), AppError>;
+    fn delete_provider(&self, app_type: &str, id: &str) -> Result<(), AppError>;
+    fn set_current_provider(&self, app_type: &str, id: &str) -> Result<(), AppError>;
+    fn record_provider_switch(&self, app_type: &str, id: &str) -> Result<(), AppError>;
+    fn get_provider_switch_log(&self, app_type: &str)
+        -> Result<Vec<ProviderSwitchEntry>, AppError>;
+    fn record_provider_error(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+        note: &ProviderErrorNote,
+    ) -> Result<(), AppError>;
+    fn get_provider_errors(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+    ) -> Result<Vec<ProviderErrorNote>, AppError>;
+    /// Returns the updated health score of the endpoint
+    fn record_endpoint_probe(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+        url: &str,
+        latency_ms: Option<u64>,
+        error: Option<&str>,
+    ) -> Result<f64, AppError>;
+    fn get_endpoint_health(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+    ) -> Result<Vec<EndpointHealth>, AppError>;
+    /// Drop health rows for URLs the provider no longer uses
+    fn prune_endpoint_health(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+        keep: &[String],
+    ) -> Result<(), AppError>;
+}
+
+pub trait McpRepo: Send + Sync {
+    fn get_all_mcp_servers(&self) -> Result<IndexMap<String, McpServer>, AppError>;
+    fn save_mcp_server(&self, server: &McpServer) -> Result<(), AppError>;
+    /// Enables exactly `enabled_ids` for `app` and disables every other server, atomically.
+    fn set_mcp_enabled_set(&self, app: &AppType, enabled_ids: &[String]) -> Result<(), AppError>;
+    fn update_mcp_sort_order(&self, updates: &[McpSortUpdate]) -> Result<(), AppError>;
+    fn delete_mcp_server(&self, id: &str) -> Result<(), AppError>;
+    fn get_quarantined_mcp(&self) -> Result<Vec<QuarantinedMcpEntry>, AppError>;
+    fn save_quarantined_mcp(&self, entry: &QuarantinedMcpEntry) -> Result<(), AppError>;
+    fn delete_quarantined_mcp(&self, id: &str) -> Result<(), AppError>;
+}
+
+pub trait PromptsRepo: Send + Sync {
+    fn get_prompts(&self, app_type: &str) -> Result<IndexMap<String, Prompt>, AppError>;
+    fn get_prompt_summaries(
+        &self,
+        app_type: &str,
+        limit: Option<usize>,
+        offset: usize,
+    ) -> Result<Vec<PromptSummary>, AppError>;
+    fn get_prompt(&self, app_type: &str, id: &str) -> Result<Option<Prompt>, AppError>;
+    fn get_enabled_prompt(&self, app_type: &str) -> Result<Option<Prompt>, AppError>;
+    fn get_prompt_content(&self, app_type: &str, id: &str) -> Result<Option<String>, AppError>;
+    fn prompt_content_exists(&self, app_type: &str, content: &str) -> Result<bool, AppError>;
+    /// Enables `id` and disables the app's other prompts in one step.
+    fn set_enabled_prompt(&self, app_type: &str, id: &str) -> Result<(), AppError>;
+    /// Applies several enable flags in one transaction; enabling a prompt disables the rest.
+    fn set_prompts_enabled(
+        &self,
+        app_type: &str,
+        changes: &[(String, bool)],
+    ) -> Result<(), AppError>;
+    fn save_prompt(&self, app_type: &str, prompt: &Prompt) -> Result<(), AppError>;
+    fn save_prompt_checked(&self, app_type: &str, prompt: &Prompt) -> Result<(), AppError>;
+    fn delete_prompt(&self, app_type: &str, id: &str) -> Result<(), AppError>;
+    fn get_gemini_context_files(&self) -> Result<Vec<GeminiContextFile>, AppError>;
+    fn save_gemini_context_file(&self, file: &GeminiContextFile) -> Result<(), AppError>;
+    fn delete_gemini_context_file(&self, file_name: &str) -> Result<(), AppError>;
+}
+
+pub trait SettingsRepo: Send + Sync {
+    fn get_setting(&self, key: &str) -> Result<Option<String>, AppError>;
+    fn set_setting(&self, key: &str, value: &str) -> Result<(), AppError>;
+    fn get_config_snippet(&self, app_type: &str) -> Result<Option<String>, AppError>;
+    /// `None` removes the snippet
+    fn set_config_snippet(&self, app_type: &str, snippet: Option<String>) -> Result<(), AppError>;
+}
+
+pub trait ApiTokensRepo: Send + Sync {
+    fn insert_api_token(&self, entry: &ApiTokenEntry, token_hash: &str) -> Result<(), AppError>;
+    fn get_api_tokens(&self) -> Result<Vec<ApiTokenEntry>, AppError>;
+    fn find_api_token_by_hash(&self, token_hash: &str) -> Result<Option<ApiTokenEntry>, AppError>;
+    fn touch_api_token(&self, id: &str, used_at: i64) -> Result<(), AppError>;
+    fn revoke_api_token(&self, id: &str, revoked_at: i64) -> Result<bool, AppError>;
+}
+
+pub trait SkillsRepo: Send + Sync {
+    /// Install state keyed by skill directory
+    fn get_skills(&self) -> Result<IndexMap<String, SkillState>, AppError>;
+    fn update_skill_state(&self, key: &str, state: &SkillState) -> Result<(), AppError>;
+    fn get_skill_repos(&self) -> Result<Vec<SkillRepo>, AppError>;
+    fn save_skill_repo(&self, repo: &SkillRepo) -> Result<(), AppError>;
+    fn delete_skill_repo(&self, owner: &str, name: &str) -> Result<(), AppError>;
+}
+
+/// Everything a backend must provide to run the app.
+pub trait Storage:
+    ProvidersRepo + McpRepo + PromptsRepo + SettingsRepo + ApiTokensRepo + SkillsRepo
+{
+}
+
+impl<T> Storage for T where
+    T: ProvidersRepo + McpRepo + PromptsRepo + SettingsRepo + ApiTokensRepo + SkillsRepo
+{
+}
+
+impl ProvidersRepo for Database {
+    fn get_all_providers(&self, app_type: &str) -> Result<IndexMap<String, Provider>, AppError> {
+        Database::get_all_providers(self, app_type)
+    }
+
+    fn get_current_provider(&self, app_type: &str) -> Result<Option<String>, AppError> {
+        Database::get_current_provider(self, app_type)
+    }
+
+    fn save_provider(&self, app_type: &str, provider: &Provider) -> Result<(), AppError> {
+        Database::save_provider(self, app_type, provider)
+    }
+
+    fn save_provider_checked(&self, app_type: &str, provider: &Provider) -> Result<(), AppError> {
+        Database::save_provider_checked(self, app_type, provider)
+    }
+
+    fn save_providers(&self, providers: &[(String, Provider)]) -> Result<(), AppError> {
+        Database::save_providers(self, providers)
+    }
+
+    fn delete_provider(&self, app_type: &str, id: &str) -> Result<(), AppError> {
+        Database::delete_provider(self, app_type, id)
+    }
+
+    fn set_current_provider(&self, app_type: &str, id: &str) -> Result<(), AppError> {
+        Database::set_current_provider(self, app_type, id)
+    }
+
+    fn record_provider_switch(&self, app_type: &str, id: &str) -> Result<(), AppError> {
+        Database::record_provider_switch(self, app_type, id)
+    }
+
+    fn get_provider_switch_log(
+        &self,
+        app_type: &str,
+    ) -> Result<Vec<ProviderSwitchEntry>, AppError> {
+        Database::get_provider_switch_log(self, app_type)
+    }
+
+    fn record_provider_error(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+        note: &ProviderErrorNote,
+    ) -> Result<(), AppError> {
+        Database::record_provider_error(self, app_type, provider_id, note)
+    }
+
+    fn get_provider_errors(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+    ) -> Result<Vec<ProviderErrorNote>, AppError> {
+        Database::get_provider_errors(self, app_type, provider_id)
+    }
+
+    fn record_endpoint_probe(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+        url: &str,
+        latency_ms: Option<u64>,
+        error: Option<&str>,
+    ) -> Result<f64, AppError> {
+        Database::record_endpoint_probe(self, app_type, provider_id, url, latency_ms, error)
+    }
+
+    fn get_endpoint_health(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+    ) -> Result<Vec<EndpointHealth>, AppError> {
+        Database::get_endpoint_health(self, app_type, provider_id)
+    }
+
+    fn prune_endpoint_health(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+        keep: &[String],
+    ) -> Result<(), AppError> {
+        Database::prune_endpoint_health(self, app_type, provider_id, keep)
+    }
+}
+
+impl McpRepo for Database {
+    fn get_all_mcp_servers(&self) -> Result<IndexMap<String, McpServer>, AppError> {
+        Database::get_all_mcp_servers(self)
+    }
+
+    fn save_mcp_server(&self, server: &McpServer) -> Result<(), AppError> {
+        Database::save_mcp_server(self, server)
+    }
+
+    fn set_mcp_enabled_set(&self, app: &AppType, enabled_ids: &[String]) -> Result<(), AppError> {
+        Database::set_mcp_enabled_set(self, app, enabled_ids)
+    }
+
+    fn update_mcp_sort_order(&self, updates: &[McpSortUpdate]) -> Result<(), AppError> {
+        Database::update_mcp_sort_order(self, updates)
+    }
+
+    fn delete_mcp_server(&self, id: &str) -> Result<(), AppError> {
+        Database::delete_mcp_server(self, id)
+    }
+
+    fn get_quarantined_mcp(&self) -> Result<Vec<QuarantinedMcpEntry>, AppError> {
+        Database::get_quarantined_mcp(self)
+    }
+
+    fn save_quarantined_mcp(&self, entry: &QuarantinedMcpEntry) -> Result<(), AppError> {
+        Database::save_quarantined_mcp(self, entry)
+    }
+
+    fn delete_quarantined_mcp(&self, id: &str) -> Result<(), AppError> {
+        Database::delete_quarantined_mcp(self, id)
+    }
+}
+
+impl PromptsRepo for Database {
+    fn get_prompts(&self, app_type: &str) -> Result<IndexMap<String, Prompt>, AppError> {
+        Database::get_prompts(self, app_type)
+    }
+
+    fn get_prompt_summaries(
+        &self,
+        app_type: &str,
+        limit: Option<usize>,
+        offset: usize,
+    ) -> Result<Vec<PromptSummary>, AppError> {
+        Database::get_prompt_summaries(self, app_type, limit, offset)
+    }
+
+    fn get_prompt(&self, app_type: &str, id: &str) -> Result<Option<Prompt>, AppError> {
+        Database::get_prompt(self, app_type, id)
+    }
+
+    fn get_enabled_prompt(&self, app_type: &str) -> Result<Option<Prompt>, AppError> {
+        Database::get_enabled_prompt(self, app_type)
+    }
+
+    fn get_prompt_content(&self, app_type: &str, id: &str) -> Result<Option<String>, AppError> {
+        Database::get_prompt_content(self, app_type, id)
+    }
+
+    fn prompt_content_exists(&self, app_type: &str, content: &str) -> Result<bool, AppError> {
+        Database::prompt_content_exists(self, app_type, content)
+    }
+
+    fn set_enabled_prompt(&self, app_type: &str, id: &str) -> Result<(), AppError> {
+        Database::set_enabled_prompt(self, app_type, id)
+    }
+
+    fn set_prompts_enabled(
+        &self,
+        app_type: &str,
+        changes: &[(String, bool)],
+    ) -> Result<(), AppError> {
+        Database::set_prompts_enabled(self, app_type, changes)
+    }
+
+    fn save_prompt(&self, app_type: &str, prompt: &Prompt) -> Result<(), AppError> {
+        Database::save_prompt(self, app_type, prompt)
+    }
+
+    fn save_prompt_checked(&self, app_type: &str, prompt: &Prompt) -> Result<(), AppError> {
+        Database::save_prompt_checked(self, app_type, prompt)
+    }
+
+    fn delete_prompt(&self, app_type: &str, id: &str) -> Result<(), AppError> {
+        Database::delete_prompt(self, app_type, id)
+    }
+
+    fn get_gemini_context_files(&self) -> Result<Vec<GeminiContextFile>, AppError> {
+        Database::get_gemini_context_files(self)
+    }
+
+    fn save_gemini_context_file(&self, file: &GeminiContextFile) -> Result<(), AppError> {
+        Database::save_gemini_context_file(self, file)
+    }
+
+    fn delete_gemini_context_file(&self, file_name: &str) -> Result<(), AppError> {
+        Database::delete_gemini_context_file(self, file_name)
+    }
+}
+
+impl SettingsRepo for Database {
+    fn get_setting(&self, key: &str) -> Result<Option<String>, AppError> {
+        Database::get_setting(self, key)
+    }
+
+    fn set_setting(&self, key: &str, value: &str) -> Result<(), AppError> {
+        Database::set_setting(self, key, value)
+    }
+
+    fn get_config_snippet(&self, app_type: &str) -> Result<Option<String>, AppError> {
+        Database::get_config_snippet(self, app_type)
+    }
+
+    fn set_config_snippet(&self, app_type: &str, snippet: Option<String>) -> Result<(), AppError> {
+        Database::set_config_snippet(self, app_type, snippet)
+    }
+}
+
+impl ApiTokensRepo for Database {
+    fn insert_api_token(&self, entry: &ApiTokenEntry, token_hash: &str) -> Result<(), AppError> {
+        Database::insert_api_token(self, entry, token_hash)
+    }
+
+    fn get_api_tokens(&self) -> Result<Vec<ApiTokenEntry>, AppError> {
+        Database::get_api_tokens(self)
+    }
+
+    fn find_api_token_by_hash(&self, token_hash: &str) -> Result<Option<ApiTokenEntry>, AppError> {
+        Database::find_api_token_by_hash(self, token_hash)
+    }
+
+    fn touch_api_token(&self, id: &str, used_at: i64) -> Result<(), AppError> {
+        Database::touch_api_token(self, id, used_at)
+    }
+
+    fn revoke_api_token(&self, id: &str, revoked_at: i64) -> Result<bool, AppError> {
+        Database::revoke_api_token(self, id, revoked_at)
+    }
+}
+
+impl SkillsRepo for Database {
+    fn get_skills(&self) -> Result<IndexMap<String, SkillState>, AppError> {
+        Database::get_skills(self)
+    }
+
+    fn update_skill_state(&self, key: &str, state: &SkillState) -> Result<(), AppError> {
+        Database::update_skill_state(self, key, state)
+    }
+
+    fn get_skill_repos(&self) -> Result<Vec<SkillRepo>, AppError> {
+        Database::get_skill_repos(self)
+    }
+
+    fn save_skill_repo(&self, repo: &SkillRepo) -> Result<(), AppError> {
+        Database::save_skill_repo(self, repo)
+    }
+
+    fn delete_skill_repo(&self, owner: &str, name: &str) -> Result<(), AppError> {
+        Database::delete_skill_repo(self, owner, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Exercises the traits through `dyn Storage` so a second backend can reuse the checks.
+    fn round_trip(storage: &dyn Storage) {
+        let prompt = Prompt {
+            id: "p1".to_string(),
+            name: "Review".to_string(),
+            content: "Be terse.".to_string(),
+            description: None,
+            enabled: false,
+            created_at: Some(1),
+            updated_at: Some(1),
+            version: None,
+        };
+        storage.save_prompt("claude", &prompt).unwrap();
+        storage.set_enabled_prompt("claude", "p1").unwrap();
+        let enabled = storage.get_enabled_prompt("claude").unwrap().unwrap();
+        assert_eq!(enabled.content, "Be terse.");
+        assert!(storage.get_prompts("codex").unwrap().is_empty());
+
+        storage.set_setting("repo_test", "1").unwrap();
+        assert_eq!(
+            storage.get_setting("repo_test").unwrap().as_deref(),
+            Some("1")
+        );
+
+        storage
+            .set_config_snippet("claude", Some("{}".to_string()))
+            .unwrap();
+        assert_eq!(
+            storage.get_config_snippet("claude").unwrap().as_deref(),
+            Some("{}")
+        );
+        storage.set_config_snippet("claude", None).unwrap();
+        assert!(storage.get_config_snippet("claude").unwrap().is_none());
+
+        assert!(storage.get_current_provider("claude").unwrap().is_none());
+        assert!(storage.get_all_mcp_servers().unwrap().is_empty());
+        assert!(storage.get_api_tokens().unwrap().is_empty());
+
+        let repo = SkillRepo {
+            owner: "acme".to_string(),
+            name: "skills".to_string(),
+            branch: "main".to_string(),
+            enabled: true,
+            skills_path: None,
+        };
+        storage.save_skill_repo(&repo).unwrap();
+        assert!(storage
+            .get_skill_repos()
+            .unwrap()
+            .iter()
+            .any(|r| r.owner == "acme" && r.name == "skills"));
+        storage.delete_skill_repo("acme", "skills").unwrap();
+        assert!(!storage
+            .get_skill_repos()
+            .unwrap()
+            .iter()
+            .any(|r| r.owner == "acme"));
+        storage
+            .update_skill_state(
+                "review",
+                &SkillState {
+                    installed: true,
+                    installed_at: chrono::Utc::now(),
+                    source: None,
+                },
+            )
+            .unwrap();
+        assert!(storage.get_skills().unwrap()["review"].installed);
+        assert!(storage
+            .get_endpoint_health("claude", "p1")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn sqlite_memory_backend_satisfies_storage() {
+        round_trip(&Database::memory().unwrap());
+    }
+}
@@ -0,0 +1,453 @@
+//! Verification and restore of the automatic database backups in `<db dir>/backups`.
+//!
+//! Every backup gets a `<id>.json` manifest next to it recording the SHA-256 and
+//! schema version at write time, so bit rot or a truncated copy is caught before a
+//! restore overwrites the live database.
+
+use rusqlite::backup::Backup;
+use rusqlite::{Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::schema::SCHEMA_VERSION;
+use super::{lock_conn, Database};
+use crate::error::AppError;
+
+const BACKUP_ID_PREFIX: &str = "db_backup_";
+
+/// Tables compared by `preview_restore`: (table, key expression, content expression)
+const RESTORE_TABLES: [(&str, &str, &str); 3] = [
+    (
+        "providers",
+        "app_type || ':' || id",
+        "name || char(0) || settings_config",
+    ),
+    ("mcp_servers", "id", "name || char(0) || server_config"),
+    (
+        "prompts",
+        "app_type || ':' || id",
+        "name || char(0) || content",
+    ),
+];
+
+/// Operation that triggered an automatic backup
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DbBackupOrigin {
+    SqlImport,
+    /// Safety snapshot taken right before restoring another backup
+    PreRestore,
+    /// Manifest missing or written before origins were recorded
+    #[default]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BackupManifest {
+    sha256: String,
+    size: u64,
+    schema_version: i32,
+    created_at: i64,
+    #[serde(default)]
+    origin: DbBackupOrigin,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DbBackupInfo {
+    pub id: String,
+    pub size: u64,
+    pub created_at: i64,
+    pub origin: DbBackupOrigin,
+    /// Backups written before manifests existed have no recorded checksum
+    pub checksum_recorded: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupVerification {
+    pub id: String,
+    /// `None` when no checksum was recorded for this backup
+    pub checksum_matches: Option<bool>,
+    pub integrity_ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub integrity_error: Option<String>,
+    pub schema_version: i32,
+    pub supported_schema_version: i32,
+    /// Older schemas are migrated on restore; newer ones need an app upgrade
+    pub schema_compatible: bool,
+    pub ok: bool,
+}
+
+/// Row counts that a restore would change, relative to the live database
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreChange {
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestorePreview {
+    pub id: String,
+    pub verification: BackupVerification,
+    pub providers: RestoreChange,
+    pub mcp_servers: RestoreChange,
+    pub prompts: RestoreChange,
+}
+
+impl Database {
+    /// List automatic database backups, newest first
+    pub fn list_db_backups() -> Result<Vec<DbBackupInfo>, AppError> {
+        let dir = Self::db_backup_dir()?;
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => return Ok(Vec::new()),
+        };
+
+        let mut backups = Vec::new();
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if path.extension().map(|ext| ext != "db").unwrap_or(true) {
+                continue;
+            }
+            let Some(id) = path.file_stem().map(|s| s.to_string_lossy().to_string()) else {
+                continue;
+            };
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            // An unreadable manifest only loses the origin; verification reports it
+            let manifest = read_manifest(&path).ok().flatten();
+            let modified_at = meta
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_secs() as i64)
+                .unwrap_or_default();
+            backups.push(DbBackupInfo {
+                id,
+                size: meta.len(),
+                created_at: manifest.as_ref().map_or(modified_at, |m| m.created_at),
+                origin: manifest.as_ref().map(|m| m.origin).unwrap_or_default(),
+                checksum_recorded: manifest_path(&path).exists(),
+            });
+        }
+        backups.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+        Ok(backups)
+    }
+
+    /// Check a backup's recorded checksum, SQLite integrity and schema version
+    pub fn verify_backup(id: &str) -> Result<BackupVerification, AppError> {
+        let path = Self::db_backup_path(id)?;
+        Self::verify_backup_file(id, &path)
+    }
+
+    /// Summarize what restoring the backup would add, remove or change
+    pub fn preview_restore(&self, id: &str) -> Result<RestorePreview, AppError> {
+        let path = Self::db_backup_path(id)?;
+        self.preview_restore_file(id, &path)
+    }
+
+    /// Replace the live database with a verified backup.
+    ///
+    /// The current database is backed up first; returns that backup's ID.
+    pub fn restore_db_backup(&self, id: &str) -> Result<String, AppError> {
+        let path = Self::db_backup_path(id)?;
+        let verification = Self::verify_backup_file(id, &path)?;
+        if !verification.ok {
+            return Err(AppError::Database(format!(
+                "Backup {id} failed verification and was not restored"
+            )));
+        }
+
+        // Stage in memory so older schemas are migrated before touching the live DB
+        let source = open_read_only(&path)?;
+        let mut staged =
+            Connection::open_in_memory().map_err(|e| AppError::Database(e.to_string()))?;
+        {
+            let backup =
+                Backup::new(&source, &mut staged).map_err(|e| AppError::Database(e.to_string()))?;
+            backup
+                .step(-1)
+                .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+        Self::create_tables_on_conn(&staged)?;
+        Self::apply_schema_migrations_on_conn(&staged)?;
+
+        let safety = self.backup_database_file(DbBackupOrigin::PreRestore)?;
+        self.restore_from_snapshot(&staged)?;
+
+        Ok(safety
+            .and_then(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
+            .unwrap_or_default())
+    }
+
+    /// Delete a backup together with its manifest
+    pub fn delete_db_backup(id: &str) -> Result<(), AppError> {
+        let path = Self::db_backup_path(id)?;
+        fs::remove_file(&path).map_err(|e| AppError::io(&path, e))?;
+        let manifest = manifest_path(&path);
+        if manifest.exists() {
+            fs::remove_file(&manifest).map_err(|e| AppError::io(&manifest, e))?;
+        }
+        Ok(())
+    }
+
+    /// Record checksum, schema version and origin next to a freshly written backup
+    pub(crate) fn write_backup_manifest(
+        backup_path: &Path,
+        origin: DbBackupOrigin,
+    ) -> Result<(), AppError> {
+        let bytes = fs::read(backup_path).map_err(|e| AppError::io(backup_path, e))?;
+        let conn = open_read_only(backup_path)?;
+        let manifest = BackupManifest {
+            sha256: sha256_hex(&bytes),
+            size: bytes.len() as u64,
+            schema_version: user_version(&conn)?,
+            created_at: chrono::Utc::now().timestamp(),
+            origin,
+        };
+        let json = serde_json::to_vec_pretty(&manifest)
+            .map_err(|e| AppError::Config(format!("JSON serialization failed: {e}")))?;
+        crate::config::atomic_write(&manifest_path(backup_path), &json)
+    }
+
+    pub(crate) fn db_backup_dir() -> Result<PathBuf, AppError> {
+        Ok(crate::config::get_db_path()
+            .parent()
+            .ok_or_else(|| AppError::Config("Invalid database path".to_string()))?
+            .join("backups"))
+    }
+
+    fn db_backup_path(id: &str) -> Result<PathBuf, AppError> {
+        let valid = id.starts_with(BACKUP_ID_PREFIX)
+            && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid {
+            return Err(AppError::InvalidInput(format!("Invalid backup ID: {id}")));
+        }
+        let path = Self::db_backup_dir()?.join(format!("{id}.db"));
+        if !path.exists() {
+            return Err(AppError::InvalidInput(format!("Backup not found: {id}")));
+        }
+        Ok(path)
+    }
+
+    fn verify_backup_file(id: &str, path: &Path) -> Result<BackupVerification, AppError> {
+        let checksum_matches = match read_manifest(path)? {
+            Some(manifest) => {
+                let bytes = fs::read(path).map_err(|e| AppError::io(path, e))?;
+                Some(bytes.len() as u64 == manifest.size && sha256_hex(&bytes) == manifest.sha256)
+            }
+            None => None,
+        };
+
+        let (integrity_error, schema_version) = match open_read_only(path) {
+            Ok(conn) => {
+                let check = conn
+                    .query_row("PRAGMA integrity_check;", [], |row| row.get::<_, String>(0))
+                    .unwrap_or_else(|e| e.to_string());
+                let error = (check != "ok").then_some(check);
+                (error, user_version(&conn).unwrap_or_default())
+            }
+            Err(e) => (Some(e.to_string()), 0),
+        };
+
+        let integrity_ok = integrity_error.is_none();
+        let schema_compatible = integrity_ok && schema_version <= SCHEMA_VERSION;
+        Ok(BackupVerification {
+            id: id.to_string(),
+            checksum_matches,
+            integrity_ok,
+            integrity_error,
+            schema_version,
+            supported_schema_version: SCHEMA_VERSION,
+            schema_compatible,
+            ok: integrity_ok && schema_compatible && checksum_matches != Some(false),
+        })
+    }
+
+    fn preview_restore_file(&self, id: &str, path: &Path) -> Result<RestorePreview, AppError> {
+        let verification = Self::verify_backup_file(id, path)?;
+        let backup = open_read_only(path)?;
+
+        let mut changes = Vec::with_capacity(RESTORE_TABLES.len());
+        for (table, key, content) in RESTORE_TABLES {
+            let incoming = fingerprints(&backup, table, key, content)?;
+            let current = {
+                let conn = lock_conn!(self.conn);
+                fingerprints(&conn, table, key, content)?
+            };
+            changes.push(diff(&current, &incoming));
+        }
+
+        let [providers, mcp_servers, prompts]: [RestoreChange; 3] = changes
+            .try_into()
+            .map_err(|_| AppError::Database("Unexpected restore preview shape".to_string()))?;
+        Ok(RestorePreview {
+            id: id.to_string(),
+            verification,
+            providers,
+            mcp_servers,
+            prompts,
+        })
+    }
+}
+
+fn manifest_path(backup_path: &Path) -> PathBuf {
+    backup_path.with_extension("json")
+}
+
+fn read_manifest(backup_path: &Path) -> Result<Option<BackupManifest>, AppError> {
+    let path = manifest_path(backup_path);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let raw = fs::read_to_string(&path).map_err(|e| AppError::io(&path, e))?;
+    serde_json::from_str(&raw)
+        .map(Some)
+        .map_err(|e| AppError::Config(format!("Invalid backup manifest {}: {e}", path.display())))
+}
+
+fn open_read_only(path: &Path) -> Result<Connection, AppError> {
+    Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| AppError::Database(e.to_string()))
+}
+
+fn user_version(conn: &Connection) -> Result<i32, AppError> {
+    conn.query_row("PRAGMA user_version;", [], |row| row.get(0))
+        .map_err(|e| AppError::Database(format!("Failed to read user_version: {e}")))
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Key -> content map for one table; a table missing from an old backup is empty
+fn fingerprints(
+    conn: &Connection,
+    table: &str,
+    key: &str,
+    content: &str,
+) -> Result<HashMap<String, String>, AppError> {
+    let exists: bool = conn
+        .query_row(
+            "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1)",
+            [table],
+            |row| row.get(0),
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+    if !exists {
+        return Ok(HashMap::new());
+    }
+
+    let mut stmt = conn
+        .prepare(&format!("SELECT {key}, {content} FROM \"{table}\""))
+        .map_err(|e| AppError::Database(e.to_string()))?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| AppError::Database(e.to_string()))?;
+    let map = rows
+        .collect::<Result<HashMap<String, String>, _>>()
+        .map_err(|e| AppError::Database(e.to_string()))?;
+    Ok(map)
+}
+
+fn diff(current: &HashMap<String, String>, incoming: &HashMap<String, String>) -> RestoreChange {
+    let mut change = RestoreChange::default();
+    for (key, content) in incoming {
+        match current.get(key) {
+            None => change.added += 1,
+            Some(existing) if existing != content => change.changed += 1,
+            Some(_) => {}
+        }
+    }
+    change.removed = current
+        .keys()
+        .filter(|k| !incoming.contains_key(*k))
+        .count();
+    change
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verifies_checksum_and_previews_row_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("db_backup_20240101_000000.db");
+        let id = "db_backup_20240101_000000";
+
+        let db = Database::memory().unwrap();
+        db.conn
+            .lock()
+            .unwrap()
+            .execute_batch(
+                "INSERT INTO providers (id, app_type, name, settings_config)
+                     VALUES ('p1', 'claude', 'P1', '{}'), ('p2', 'claude', 'P2', '{}');
+                 INSERT INTO mcp_servers (id, name, server_config) VALUES ('m1', 'M1', '{}');",
+            )
+            .unwrap();
+        db.copy_to_file(&path).unwrap();
+        Database::write_backup_manifest(&path, DbBackupOrigin::SqlImport).unwrap();
+        let manifest = read_manifest(&path).unwrap().unwrap();
+        assert_eq!(manifest.origin, DbBackupOrigin::SqlImport);
+
+        let verification = Database::verify_backup_file(id, &path).unwrap();
+        assert_eq!(verification.checksum_matches, Some(true));
+        assert!(verification.ok);
+
+        // Live DB drifts: p2 deleted, p1 edited, p3 and a prompt added
+        db.conn
+            .lock()
+            .unwrap()
+            .execute_batch(
+                "DELETE FROM providers WHERE id = 'p2';
+                 UPDATE providers SET settings_config = '{\"env\":{}}' WHERE id = 'p1';
+                 INSERT INTO providers (id, app_type, name, settings_config)
+                     VALUES ('p3', 'codex', 'P3', '{}');
+                 INSERT INTO prompts (id, app_type, name, content)
+                     VALUES ('r1', 'claude', 'R1', 'hello');",
+            )
+            .unwrap();
+
+        let preview = db.preview_restore_file(id, &path).unwrap();
+        let expected = |added, removed, changed| RestoreChange {
+            added,
+            removed,
+            changed,
+        };
+        assert_eq!(preview.providers, expected(1, 1, 1));
+        assert_eq!(preview.mcp_servers, expected(0, 0, 0));
+        assert_eq!(preview.prompts, expected(0, 1, 0));
+
+        // A modified backup no longer matches its recorded checksum
+        Connection::open(&path)
+            .unwrap()
+            .execute("DELETE FROM mcp_servers", [])
+            .unwrap();
+        let tampered = Database::verify_backup_file(id, &path).unwrap();
+        assert_eq!(tampered.checksum_matches, Some(false));
+        assert!(tampered.integrity_ok);
+        assert!(!tampered.ok);
+
+        // Manifests written before origins were recorded still parse
+        fs::write(
+            manifest_path(&path),
+            r#"{"sha256":"00","size":1,"schemaVersion":1,"createdAt":0}"#,
+        )
+        .unwrap();
+        let legacy = read_manifest(&path).unwrap().unwrap();
+        assert_eq!(legacy.origin, DbBackupOrigin::Unknown);
+    }
+}
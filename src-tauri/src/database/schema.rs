@@ -1,9 +1,10 @@
 use crate::error::AppError;
 use rusqlite::Connection;
 
+use super::progress::{MigrationPhase, MigrationProgress};
 use super::{lock_conn, Database};
 
-const SCHEMA_VERSION: i32 = 1;
+pub(super) const SCHEMA_VERSION: i32 = 6;
 
 impl Database {
     pub(super) fn create_tables(&self) -> Result<(), AppError> {
@@ -28,6 +29,9 @@ impl Database {
                 icon_color TEXT,
                 meta TEXT NOT NULL DEFAULT '{}',
                 is_current BOOLEAN NOT NULL DEFAULT 0,
+                last_switched_at INTEGER,
+                switch_count INTEGER NOT NULL DEFAULT 0,
+                version INTEGER NOT NULL DEFAULT 0,
                 PRIMARY KEY (id, app_type)
             )",
             [],
@@ -59,7 +63,8 @@ impl Database {
                 tags TEXT NOT NULL DEFAULT '[]',
                 enabled_claude BOOLEAN NOT NULL DEFAULT 0,
                 enabled_codex BOOLEAN NOT NULL DEFAULT 0,
-                enabled_gemini BOOLEAN NOT NULL DEFAULT 0
+                enabled_gemini BOOLEAN NOT NULL DEFAULT 0,
+                sort_index INTEGER
             )",
             [],
         )
@@ -76,6 +81,7 @@ impl Database {
                 enabled BOOLEAN NOT NULL DEFAULT 1,
                 created_at INTEGER,
                 updated_at INTEGER,
+                version INTEGER NOT NULL DEFAULT 0,
                 PRIMARY KEY (id, app_type)
             )",
             [],
@@ -87,7 +93,8 @@ impl Database {
             "CREATE TABLE IF NOT EXISTS skills (
                 key TEXT PRIMARY KEY,
                 installed BOOLEAN NOT NULL DEFAULT 0,
-                installed_at INTEGER NOT NULL DEFAULT 0
+                installed_at INTEGER NOT NULL DEFAULT 0,
+                source TEXT
             )",
             [],
         )
@@ -117,15 +124,107 @@ impl Database {
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
 
+        // 8. Gemini context files table
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS gemini_context_files (
+                file_name TEXT PRIMARY KEY,
+                content TEXT NOT NULL,
+                enabled BOOLEAN NOT NULL DEFAULT 0,
+                created_at INTEGER,
+                updated_at INTEGER
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        // 9. MCP import quarantine table
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS mcp_quarantine (
+                id TEXT PRIMARY KEY,
+                app_type TEXT NOT NULL,
+                server_id TEXT NOT NULL,
+                raw TEXT NOT NULL,
+                error TEXT NOT NULL,
+                created_at INTEGER NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        // 10. Provider switch history (name is copied so entries survive provider deletion)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS provider_switch_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                app_type TEXT NOT NULL,
+                provider_id TEXT NOT NULL,
+                provider_name TEXT NOT NULL,
+                switched_at INTEGER NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        // 11. API tokens for external control surfaces (only the SHA-256 hash is stored)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS api_tokens (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                scope TEXT NOT NULL,
+                token_hash TEXT NOT NULL UNIQUE,
+                prefix TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                last_used_at INTEGER,
+                revoked_at INTEGER
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        // 12. Endpoint availability from background probes (kept apart from provider_endpoints,
+        //     which is rewritten on every provider save)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS endpoint_health (
+                app_type TEXT NOT NULL,
+                provider_id TEXT NOT NULL,
+                url TEXT NOT NULL,
+                score REAL NOT NULL,
+                latency_ms INTEGER,
+                last_error TEXT,
+                checked_at INTEGER NOT NULL,
+                PRIMARY KEY (app_type, provider_id, url)
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        // 13. Failures per provider (switch, health check, usage query) for troubleshooting
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS provider_errors (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                app_type TEXT NOT NULL,
+                provider_id TEXT NOT NULL,
+                operation TEXT NOT NULL,
+                message TEXT NOT NULL,
+                occurred_at INTEGER NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
         Ok(())
     }
 
+    /// Upgrade the app database, reporting each version step to the startup progress screen
     pub(super) fn apply_schema_migrations(&self) -> Result<(), AppError> {
         let conn = lock_conn!(self.conn);
-        Self::apply_schema_migrations_on_conn(&conn)
+        Self::apply_schema_migrations_inner(&conn, true)
     }
 
     pub(crate) fn apply_schema_migrations_on_conn(conn: &Connection) -> Result<(), AppError> {
+        Self::apply_schema_migrations_inner(conn, false)
+    }
+
+    fn apply_schema_migrations_inner(conn: &Connection, report: bool) -> Result<(), AppError> {
         conn.execute("SAVEPOINT schema_migration;", [])
             .map_err(|e| AppError::Database(format!("Failed to start migration savepoint: {e}")))?;
 
@@ -139,8 +238,19 @@ impl Database {
             )));
         }
 
+        // One unit per version step plus the orphan cleanup
+        let mut progress = (report && version < SCHEMA_VERSION).then(|| {
+            MigrationProgress::new(
+                MigrationPhase::Schema,
+                (SCHEMA_VERSION - version) as usize + 1,
+            )
+        });
+
         let result = (|| {
             while version < SCHEMA_VERSION {
+                if let Some(progress) = progress.as_mut() {
+                    progress.step(&format!("v{}", version + 1));
+                }
                 match version {
                     0 => {
                         log::info!("Detected user_version=0, migrating to 1 (add missing columns)");
@@ -223,7 +333,49 @@ impl Database {
                         )?;
                         Self::add_column_if_missing(conn, "skill_repos", "skills_path", "TEXT")?;
 
-                        Self::set_user_version(conn, SCHEMA_VERSION)?;
+                        Self::set_user_version(conn, 1)?;
+                    }
+                    1 => {
+                        log::info!("Migrating user_version=1 to 2 (skills.source)");
+                        Self::add_column_if_missing(conn, "skills", "source", "TEXT")?;
+                        Self::set_user_version(conn, 2)?;
+                    }
+                    2 => {
+                        log::info!("Migrating user_version=2 to 3 (providers.last_switched_at)");
+                        Self::add_column_if_missing(
+                            conn,
+                            "providers",
+                            "last_switched_at",
+                            "INTEGER",
+                        )?;
+                        Self::set_user_version(conn, 3)?;
+                    }
+                    3 => {
+                        log::info!("Migrating user_version=3 to 4 (providers.switch_count)");
+                        Self::add_column_if_missing(
+                            conn,
+                            "providers",
+                            "switch_count",
+                            "INTEGER NOT NULL DEFAULT 0",
+                        )?;
+                        Self::set_user_version(conn, 4)?;
+                    }
+                    4 => {
+                        log::info!("Migrating user_version=4 to 5 (providers/prompts.version)");
+                        for table in ["providers", "prompts"] {
+                            Self::add_column_if_missing(
+                                conn,
+                                table,
+                                "version",
+                                "INTEGER NOT NULL DEFAULT 0",
+                            )?;
+                        }
+                        Self::set_user_version(conn, 5)?;
+                    }
+                    5 => {
+                        log::info!("Migrating user_version=5 to 6 (mcp_servers.sort_index)");
+                        Self::add_column_if_missing(conn, "mcp_servers", "sort_index", "INTEGER")?;
+                        Self::set_user_version(conn, 6)?;
                     }
                     _ => {
                         return Err(AppError::Database(format!(
@@ -233,24 +385,44 @@ impl Database {
                 }
 
                 version = Self::get_user_version(conn)?;
+                if let Some(progress) = progress.as_mut() {
+                    progress.advance();
+                }
+            }
+
+            if let Some(progress) = progress.as_mut() {
+                progress.step("cleanup");
+            }
+            let report = Self::cleanup_orphans_on_conn(conn, false)?;
+            if !report.is_empty() {
+                log::info!(
+                    "Removed {} orphaned endpoint(s), quarantined {} unused setting key(s): {:?}",
+                    report.endpoints.len(),
+                    report.setting_keys.len(),
+                    report.setting_keys
+                );
             }
 
             Ok(())
         })();
 
-        match result {
-            Ok(_) => {
-                conn.execute("RELEASE schema_migration;", []).map_err(|e| {
+        let outcome = match result {
+            Ok(_) => conn
+                .execute("RELEASE schema_migration;", [])
+                .map(|_| ())
+                .map_err(|e| {
                     AppError::Database(format!("Failed to commit migration savepoint: {e}"))
-                })?;
-                Ok(())
-            }
+                }),
             Err(e) => {
                 conn.execute("ROLLBACK TO schema_migration;", []).ok();
                 conn.execute("RELEASE schema_migration;", []).ok();
                 Err(e)
             }
+        };
+        if let Some(progress) = progress {
+            progress.finish(&outcome);
         }
+        outcome
     }
 }
 
@@ -480,8 +652,12 @@ mod tests {
             ("providers", "is_current"),
             ("provider_endpoints", "added_at"),
             ("mcp_servers", "enabled_gemini"),
+            ("mcp_servers", "sort_index"),
             ("prompts", "updated_at"),
+            ("prompts", "version"),
+            ("providers", "version"),
             ("skills", "installed_at"),
+            ("skills", "source"),
             ("skill_repos", "enabled"),
         ] {
             assert!(
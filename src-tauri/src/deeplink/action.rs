@@ -0,0 +1,180 @@
+use url::Url;
+
+use crate::error::AppError;
+use crate::services::{ApiTokenService, QuickActionService};
+use crate::store::AppState;
+
+use super::security::{
+    record, require_signature, sign_deeplink, DeepLinkAuditEntry, DeepLinkAuditStatus,
+};
+
+/// Build a `clihub://v1/action?id=...` link that runs a quick action without opening the UI
+///
+/// When a deep link secret is configured the link is signed with it (`sig`), otherwise the
+/// receiving side would refuse it. The signature only covers this action id, so the secret
+/// itself never ends up in launcher entries or shell history.
+pub fn build_action_deeplink(action_id: &str, secret: Option<&str>) -> Result<String, AppError> {
+    let mut url = Url::parse("clihub://v1/action")
+        .map_err(|e| AppError::Message(format!("Failed to build deep link: {e}")))?;
+    url.query_pairs_mut().append_pair("id", action_id);
+    match secret.filter(|s| !s.is_empty()) {
+        Some(secret) => sign_deeplink(url.as_str(), secret),
+        None => Ok(url.into()),
+    }
+}
+
+/// Extract the quick action id from an action link
+///
+/// Returns `Ok(None)` for any other clihub:// link so the import flow can handle it.
+/// The signature is not checked here; use [`receive_action_deeplink`] before running it.
+pub fn parse_action_deeplink(url_str: &str) -> Result<Option<String>, AppError> {
+    let Ok(url) = Url::parse(url_str) else {
+        return Ok(None);
+    };
+    if url.scheme() != "clihub" || url.host_str() != Some("v1") || url.path() != "/action" {
+        return Ok(None);
+    }
+
+    let id = url
+        .query_pairs()
+        .find(|(key, _)| key == "id")
+        .map(|(_, value)| value.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .ok_or_else(|| AppError::InvalidInput("Missing 'id' parameter".to_string()))?;
+    Ok(Some(id))
+}
+
+/// Authenticate an incoming action link and record the attempt in the import-source log
+///
+/// Action links skip the confirmation dialog, so unlike imports they are refused unless
+/// they carry either an API token (`token`) whose scope covers the action, or a
+/// signature made with the configured deep link secret (`sig`).
+pub fn receive_action_deeplink(
+    state: Option<&AppState>,
+    url_str: &str,
+    source: &str,
+) -> Result<Option<String>, AppError> {
+    let parsed = match parse_action_deeplink(url_str) {
+        Ok(None) => return Ok(None),
+        Ok(Some(id)) => authenticate(state, url_str, &id).map(|_| id),
+        Err(err) => Err(err),
+    };
+
+    let status = if parsed.is_ok() {
+        DeepLinkAuditStatus::Executed
+    } else {
+        DeepLinkAuditStatus::Rejected
+    };
+    let mut entry = DeepLinkAuditEntry::new(source, status, None);
+    entry.resource = Some("action".to_string());
+    match &parsed {
+        Ok(id) => entry.name = Some(id.clone()),
+        Err(err) => entry.message = Some(err.to_string()),
+    }
+    record(state.map(|s| s.db.as_ref()), entry);
+
+    parsed.map(Some)
+}
+
+fn authenticate(state: Option<&AppState>, url_str: &str, action_id: &str) -> Result<(), AppError> {
+    let token = Url::parse(url_str).ok().and_then(|url| {
+        url.query_pairs()
+            .find(|(key, _)| key == "token")
+            .map(|(_, value)| value.into_owned())
+    });
+    let Some(token) = token else {
+        return require_signature(url_str);
+    };
+
+    let state =
+        state.ok_or_else(|| AppError::Message("App state is not initialized".to_string()))?;
+    let (kind, _, _) = QuickActionService::parse_action_id(action_id)?;
+    ApiTokenService::authorize(state, &token, kind.command()).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use crate::deeplink::get_deeplink_audit_log;
+    use crate::services::ApiTokenScope;
+    use std::sync::Arc;
+
+    #[test]
+    fn action_links_round_trip_and_ignore_imports() {
+        let link = build_action_deeplink("provider:claude:my id", None).unwrap();
+        assert!(link.starts_with("clihub://v1/action?id="));
+        assert_eq!(
+            parse_action_deeplink(&link).unwrap().as_deref(),
+            Some("provider:claude:my id")
+        );
+
+        assert_eq!(
+            parse_action_deeplink("clihub://v1/import?resource=provider").unwrap(),
+            None
+        );
+        assert!(parse_action_deeplink("clihub://v1/action").is_err());
+
+        // Signed links carry a per-action HMAC, never the secret itself
+        let signed = build_action_deeplink("provider:claude:relay", Some("s3cret")).unwrap();
+        let other = build_action_deeplink("provider:claude:other", Some("s3cret")).unwrap();
+        assert!(!signed.contains("s3cret"));
+        assert!(signed.contains("&sig="));
+        assert_ne!(signed.split("sig=").last(), other.split("sig=").last());
+    }
+
+    #[test]
+    fn unsigned_action_links_are_refused_and_logged() {
+        let state = AppState::new(Arc::new(Database::memory().unwrap()));
+        let link = build_action_deeplink("provider:claude:relay", None).unwrap();
+
+        // No deep link secret is configured in tests
+        assert!(receive_action_deeplink(Some(&state), &link, "test").is_err());
+        assert_eq!(
+            receive_action_deeplink(Some(&state), "clihub://v1/import?resource=provider", "test")
+                .unwrap(),
+            None
+        );
+
+        let log = get_deeplink_audit_log(&state.db).unwrap();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].status, DeepLinkAuditStatus::Rejected);
+        assert_eq!(log[0].resource.as_deref(), Some("action"));
+    }
+
+    #[test]
+    fn action_links_require_a_token_scoped_for_the_action() {
+        let state = AppState::new(Arc::new(Database::memory().unwrap()));
+        let reader = ApiTokenService::create(&state, "widget", ApiTokenScope::ReadOnly).unwrap();
+        let switcher =
+            ApiTokenService::create(&state, "hotkey", ApiTokenScope::SwitchOnly).unwrap();
+
+        let link = |id: &str, token: &str| {
+            let mut url = Url::parse(&build_action_deeplink(id, None).unwrap()).unwrap();
+            url.query_pairs_mut().append_pair("token", token);
+            url.to_string()
+        };
+
+        // Switching is a mutating entry point: read-only tokens are refused
+        let switch = link("provider:claude:relay", &reader.secret);
+        assert!(receive_action_deeplink(Some(&state), &switch, "test").is_err());
+        let switch = link("provider:claude:relay", &switcher.secret);
+        assert_eq!(
+            receive_action_deeplink(Some(&state), &switch, "test")
+                .unwrap()
+                .as_deref(),
+            Some("provider:claude:relay")
+        );
+        let toggle = link("mcp:claude:fetch", &switcher.secret);
+        assert!(receive_action_deeplink(Some(&state), &toggle, "test").is_err());
+
+        let log = get_deeplink_audit_log(&state.db).unwrap();
+        assert_eq!(log.len(), 3);
+        assert_eq!(
+            log.iter()
+                .filter(|e| e.status == DeepLinkAuditStatus::Executed)
+                .count(),
+            1
+        );
+    }
+}
@@ -0,0 +1,335 @@
+use crate::config::write_text_file;
+use crate::error::AppError;
+use crate::prompt_files::prompt_file_path;
+use crate::services::mcp::McpDirtySet;
+use crate::services::{McpService, ProviderService};
+use crate::store::AppState;
+use crate::AppType;
+use base64::prelude::*;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use super::mcp::{import_mcp_servers, parse_mcp_apps};
+use super::parser::parse_provider_deeplink;
+use super::prompt::import_prompt_content;
+use super::provider::{import_provider_from_deeplink, parse_and_merge_config};
+use super::types::{
+    BundleImportResult, BundleMcp, BundlePrompt, DeepLinkBundle, DeepLinkImportRequest,
+};
+use super::utils::decode_base64_param;
+
+/// Decode and validate the manifest carried by a bundle deep link
+///
+/// Manifest format (JSON, Base64 encoded into the `config` parameter):
+/// ```json
+/// {
+///   "name": "Vendor onboarding",
+///   "provider": { "app": "claude", "name": "Vendor", "endpoint": "...", "apiKey": "..." },
+///   "mcp": { "apps": "claude,codex", "mcpServers": { "fetch": { "command": "uvx" } } },
+///   "prompt": { "app": "claude", "name": "Vendor rules", "content": "# Markdown" }
+/// }
+/// ```
+///
+/// Used both for the confirmation dialog and right before import
+pub fn parse_bundle_manifest(request: &DeepLinkImportRequest) -> Result<DeepLinkBundle, AppError> {
+    if request.resource != "bundle" {
+        return Err(AppError::InvalidInput(format!(
+            "Expected bundle resource, got '{}'",
+            request.resource
+        )));
+    }
+
+    let config_b64 = request.config.as_ref().ok_or_else(|| {
+        AppError::InvalidInput("Missing 'config' parameter for bundle".to_string())
+    })?;
+
+    let decoded = decode_base64_param("config", config_b64)?;
+    let manifest_str = String::from_utf8(decoded)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid UTF-8 in bundle manifest: {e}")))?;
+
+    let manifest: Value = serde_json::from_str(&manifest_str)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid JSON in bundle manifest: {e}")))?;
+    let obj = manifest.as_object().ok_or_else(|| {
+        AppError::InvalidInput("Bundle manifest must be a JSON object".to_string())
+    })?;
+
+    let provider = match obj.get("provider") {
+        Some(value) => Some(parse_bundle_provider(value, &request.version)?),
+        None => None,
+    };
+
+    let mcp = match obj.get("mcp") {
+        Some(value) => {
+            let mcp: BundleMcp = serde_json::from_value(value.clone()).map_err(|e| {
+                AppError::InvalidInput(format!("Invalid 'mcp' section in bundle: {e}"))
+            })?;
+            parse_mcp_apps(&mcp.apps)?;
+            if mcp.mcp_servers.is_empty() {
+                return Err(AppError::InvalidInput(
+                    "No MCP servers found in bundle".to_string(),
+                ));
+            }
+            Some(mcp)
+        }
+        None => None,
+    };
+
+    let prompt = match obj.get("prompt") {
+        Some(value) => {
+            let prompt: BundlePrompt = serde_json::from_value(value.clone()).map_err(|e| {
+                AppError::InvalidInput(format!("Invalid 'prompt' section in bundle: {e}"))
+            })?;
+            AppType::from_str(&prompt.app)
+                .map_err(|_| AppError::InvalidInput(format!("Invalid app type: {}", prompt.app)))?;
+            if prompt.name.trim().is_empty() {
+                return Err(AppError::InvalidInput(
+                    "Prompt name in bundle cannot be empty".to_string(),
+                ));
+            }
+            Some(prompt)
+        }
+        None => None,
+    };
+
+    if provider.is_none() && mcp.is_none() && prompt.is_none() {
+        return Err(AppError::InvalidInput(
+            "Bundle must contain at least one of 'provider', 'mcp' or 'prompt'".to_string(),
+        ));
+    }
+
+    let str_field = |key: &str| obj.get(key).and_then(|v| v.as_str()).map(String::from);
+
+    Ok(DeepLinkBundle {
+        // URL params take priority over the manifest
+        name: request.name.clone().or_else(|| str_field("name")),
+        description: str_field("description"),
+        provider,
+        mcp,
+        prompt,
+    })
+}
+
+/// Convert the provider section into a provider request, reusing the URL param validation
+fn parse_bundle_provider(value: &Value, version: &str) -> Result<DeepLinkImportRequest, AppError> {
+    let obj = value.as_object().ok_or_else(|| {
+        AppError::InvalidInput("Bundle 'provider' section must be a JSON object".to_string())
+    })?;
+
+    let mut params: HashMap<String, String> = HashMap::new();
+    for (key, val) in obj {
+        let text = match val {
+            Value::String(s) => s.clone(),
+            Value::Bool(_) | Value::Number(_) => val.to_string(),
+            Value::Null => continue,
+            // Nested provider config may be given as plain JSON instead of Base64
+            Value::Object(_) if key == "config" => BASE64_STANDARD.encode(val.to_string()),
+            _ => {
+                return Err(AppError::InvalidInput(format!(
+                    "Invalid value for provider field '{key}' in bundle"
+                )))
+            }
+        };
+        params.insert(key.clone(), text);
+    }
+
+    let request = parse_provider_deeplink(&params, version.to_string(), "provider".to_string())?;
+    parse_and_merge_config(&request)
+}
+
+/// Import a bundle deep link as a single unit
+///
+/// The database is snapshotted first; if any part fails the snapshot is restored and
+/// every live entry the import may have written is put back, so no half-imported bundle
+/// is left behind.
+pub fn import_bundle_from_deeplink(
+    state: &AppState,
+    request: DeepLinkImportRequest,
+) -> Result<BundleImportResult, AppError> {
+    let bundle = parse_bundle_manifest(&request)?;
+
+    let snapshot = state.db.snapshot_to_memory()?;
+    let live = LiveWrites::capture(&bundle)?;
+
+    match apply_bundle(state, bundle) {
+        Ok(result) => Ok(result),
+        Err(err) => {
+            log::warn!("Bundle import failed, rolling back: {err}");
+            state.db.restore_from_snapshot(&snapshot)?;
+            if let Err(sync_err) = ProviderService::sync_current_from_db(state) {
+                log::warn!("Failed to re-sync live config after bundle rollback: {sync_err}");
+            }
+            live.restore(state);
+            Err(err)
+        }
+    }
+}
+
+/// Live config entries a bundle import may write, recorded before it runs
+struct LiveWrites {
+    /// MCP servers the bundle adds to each app
+    mcp: McpDirtySet,
+    /// Prompt file the bundle enables a prompt into, with its content beforehand
+    prompt_file: Option<(PathBuf, Option<String>)>,
+}
+
+impl LiveWrites {
+    fn capture(bundle: &DeepLinkBundle) -> Result<Self, AppError> {
+        let mut mcp = McpDirtySet::default();
+        if let Some(section) = &bundle.mcp {
+            let apps = parse_mcp_apps(&section.apps)?.enabled_apps();
+            for id in section.mcp_servers.keys() {
+                for app in &apps {
+                    mcp.mark(id, app);
+                }
+            }
+        }
+
+        let prompt_file = match &bundle.prompt {
+            Some(prompt) if prompt.enabled.unwrap_or(false) => {
+                let app_type = AppType::from_str(&prompt.app).map_err(|_| {
+                    AppError::InvalidInput(format!("Invalid app type: {}", prompt.app))
+                })?;
+                let path = prompt_file_path(&app_type)?;
+                let original = match std::fs::read_to_string(&path) {
+                    Ok(content) => Some(content),
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+                    Err(e) => return Err(AppError::io(&path, e)),
+                };
+                Some((path, original))
+            }
+            _ => None,
+        };
+
+        Ok(Self { mcp, prompt_file })
+    }
+
+    /// Rewrite the recorded entries from the restored database; failures only get logged
+    fn restore(self, state: &AppState) {
+        // Servers the import created are gone from the DB and get removed from the live
+        // configs; servers that already existed are rewritten with their previous config
+        if let Err(e) = McpService::flush(state, self.mcp) {
+            log::warn!("Failed to restore live MCP config after bundle rollback: {e}");
+        }
+
+        if let Some((path, original)) = self.prompt_file {
+            let restored = match original {
+                Some(content) => write_text_file(&path, &content),
+                None => match std::fs::remove_file(&path) {
+                    Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                        Err(AppError::io(&path, e))
+                    }
+                    _ => Ok(()),
+                },
+            };
+            if let Err(e) = restored {
+                log::warn!("Failed to restore prompt file after bundle rollback: {e}");
+            }
+        }
+    }
+}
+
+fn apply_bundle(state: &AppState, bundle: DeepLinkBundle) -> Result<BundleImportResult, AppError> {
+    let provider_id = match bundle.provider {
+        Some(provider) => Some(import_provider_from_deeplink(state, provider)?),
+        None => None,
+    };
+
+    let mcp_ids = match bundle.mcp {
+        Some(mcp) => {
+            let apps = parse_mcp_apps(&mcp.apps)?;
+            let result = import_mcp_servers(state, &apps, &mcp.mcp_servers)?;
+            if let Some(failure) = result.failed.first() {
+                return Err(AppError::InvalidInput(format!(
+                    "Failed to import MCP server '{}': {}",
+                    failure.id, failure.error
+                )));
+            }
+            result.imported_ids
+        }
+        None => Vec::new(),
+    };
+
+    let prompt_id = match bundle.prompt {
+        Some(prompt) => {
+            let app_type = AppType::from_str(&prompt.app)
+                .map_err(|_| AppError::InvalidInput(format!("Invalid app type: {}", prompt.app)))?;
+            Some(import_prompt_content(
+                state,
+                app_type,
+                prompt.name,
+                prompt.content,
+                prompt.description,
+                prompt.enabled.unwrap_or(false),
+            )?)
+        }
+        None => None,
+    };
+
+    log::info!(
+        "Bundle imported: provider={provider_id:?}, mcp={}, prompt={prompt_id:?}",
+        mcp_ids.len()
+    );
+
+    Ok(BundleImportResult {
+        provider_id,
+        mcp_ids,
+        prompt_id,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::parser::parse_deeplink_url;
+    use super::*;
+
+    fn bundle_request(manifest: &str) -> DeepLinkImportRequest {
+        let url = format!(
+            "clihub://v1/import?resource=bundle&config={}",
+            BASE64_URL_SAFE_NO_PAD.encode(manifest)
+        );
+        parse_deeplink_url(&url).unwrap()
+    }
+
+    #[test]
+    fn test_parse_bundle_manifest_all_sections() {
+        let request = bundle_request(
+            r#"{
+                "name": "Vendor",
+                "provider": {"app": "claude", "name": "Vendor", "endpoint": "https://api.vendor.com", "apiKey": "sk-1", "enabled": true},
+                "mcp": {"apps": "claude", "mcpServers": {"fetch": {"command": "uvx"}}},
+                "prompt": {"app": "claude", "name": "Rules", "content": "be nice"}
+            }"#,
+        );
+
+        let bundle = parse_bundle_manifest(&request).unwrap();
+        assert_eq!(bundle.name.as_deref(), Some("Vendor"));
+
+        let provider = bundle.provider.unwrap();
+        assert_eq!(provider.resource, "provider");
+        assert_eq!(provider.api_key.as_deref(), Some("sk-1"));
+        assert_eq!(provider.enabled, Some(true));
+
+        assert!(bundle.mcp.unwrap().mcp_servers.contains_key("fetch"));
+        assert_eq!(bundle.prompt.unwrap().content, "be nice");
+    }
+
+    #[test]
+    fn test_parse_bundle_manifest_rejects_invalid_sections() {
+        let err = parse_bundle_manifest(&bundle_request("{}")).unwrap_err();
+        assert!(err.to_string().contains("at least one"));
+
+        let err = parse_bundle_manifest(&bundle_request(
+            r#"{"provider": {"app": "claude", "name": "X", "endpoint": "ftp://x"}}"#,
+        ))
+        .unwrap_err();
+        assert!(err.to_string().contains("must be http or https"));
+
+        let err = parse_bundle_manifest(&bundle_request(
+            r#"{"mcp": {"apps": "vim", "mcpServers": {"a": {"command": "x"}}}}"#,
+        ))
+        .unwrap_err();
+        assert!(err.to_string().contains("Invalid app"));
+    }
+}
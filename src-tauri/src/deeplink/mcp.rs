@@ -2,11 +2,54 @@ use crate::app_config::{McpApps, McpServer};
 use crate::error::AppError;
 use crate::services::McpService;
 use crate::store::AppState;
-use serde_json::Value;
+use base64::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::borrow::Cow;
+use url::Url;
 
 use super::types::{DeepLinkImportRequest, McpImportError, McpImportResult};
 use super::utils::decode_base64_param;
 
+/// Written in place of secret env/header values in shared links; the recipient must
+/// supply their own value before the server can be imported
+pub const SECRET_PLACEHOLDER: &str = "${ASK}";
+
+/// Spec sections whose values are treated as potential secrets
+const SECRET_SECTIONS: [&str; 2] = ["env", "headers"];
+
+/// Name fragments that mark an env var or header as secret
+const SECRET_KEY_HINTS: [&str; 8] = [
+    "KEY",
+    "TOKEN",
+    "SECRET",
+    "PASSWORD",
+    "PASSWD",
+    "AUTH",
+    "CREDENTIAL",
+    "COOKIE",
+];
+
+/// A `${ASK}` placeholder the recipient has to fill in
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpSecretPrompt {
+    pub server_id: String,
+    /// `env` or `headers`
+    pub section: String,
+    pub key: String,
+}
+
+/// Value supplied for a [`McpSecretPrompt`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpSecretAnswer {
+    pub server_id: String,
+    pub section: String,
+    pub key: String,
+    pub value: String,
+}
+
 /// Import MCP servers from deep link request
 ///
 /// This function handles batch import of MCP servers from standard MCP JSON format
@@ -37,17 +80,24 @@ pub fn import_mcp_from_deeplink(
         .as_ref()
         .ok_or_else(|| AppError::InvalidInput("Missing 'config' parameter for MCP".to_string()))?;
 
-    // Decode Base64 config
+    let config_json = decode_mcp_config(config_b64)?;
+    let mcp_servers = mcp_servers_of(&config_json)?;
+
+    import_mcp_servers(state, &target_apps, mcp_servers)
+}
+
+/// Decode the Base64 `config` parameter of an MCP link into JSON
+fn decode_mcp_config(config_b64: &str) -> Result<Value, AppError> {
     let decoded = decode_base64_param("config", config_b64)?;
 
     let config_str = String::from_utf8(decoded)
         .map_err(|e| AppError::InvalidInput(format!("Invalid UTF-8 in config: {e}")))?;
 
-    // Parse JSON
-    let config_json: Value = serde_json::from_str(&config_str)
-        .map_err(|e| AppError::InvalidInput(format!("Invalid JSON in MCP config: {e}")))?;
+    serde_json::from_str(&config_str)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid JSON in MCP config: {e}")))
+}
 
-    // Extract mcpServers object
+fn mcp_servers_of(config_json: &Value) -> Result<&Map<String, Value>, AppError> {
     let mcp_servers = config_json
         .get("mcpServers")
         .and_then(|v| v.as_object())
@@ -60,9 +110,145 @@ pub fn import_mcp_from_deeplink(
             "No MCP servers found in config".to_string(),
         ));
     }
+    Ok(mcp_servers)
+}
+
+/// Build a `clihub://v1/import?resource=mcp` link for the given servers
+///
+/// Env vars and headers that look like credentials are replaced by [`SECRET_PLACEHOLDER`],
+/// so the link never carries the sender's tokens.
+pub fn build_mcp_deeplink(servers: &[McpServer], apps: &McpApps) -> Result<String, AppError> {
+    if servers.is_empty() {
+        return Err(AppError::InvalidInput(
+            "Select at least one MCP server to share".to_string(),
+        ));
+    }
+    let enabled = apps.enabled_apps();
+    let app_names: Vec<&str> = enabled.iter().map(|app| app.as_str()).collect();
+    if app_names.is_empty() {
+        return Err(AppError::InvalidInput(
+            "At least one app must be specified in 'apps'".to_string(),
+        ));
+    }
+
+    let mcp_servers: Map<String, Value> = servers
+        .iter()
+        .map(|server| (server.id.clone(), mask_secrets(&server.server)))
+        .collect();
+    let config = serde_json::to_string(&json!({ "mcpServers": mcp_servers }))
+        .map_err(|e| AppError::Message(format!("Failed to serialize MCP config: {e}")))?;
+
+    let mut url = Url::parse("clihub://v1/import")
+        .map_err(|e| AppError::Message(format!("Failed to build deep link: {e}")))?;
+    url.query_pairs_mut()
+        .append_pair("resource", "mcp")
+        .append_pair("apps", &app_names.join(","))
+        .append_pair("config", &BASE64_STANDARD.encode(config));
+    Ok(url.into())
+}
+
+fn is_secret_entry(key: &str, value: &str) -> bool {
+    let upper = key.to_ascii_uppercase();
+    // `${VAR}` references resolve on the recipient's machine and are safe to share
+    let is_reference = value.starts_with("${") && value.ends_with('}');
+    !value.is_empty()
+        && !is_reference
+        && (SECRET_KEY_HINTS.iter().any(|hint| upper.contains(hint))
+            || matches!(crate::log_sanitizer::redact(value), Cow::Owned(_)))
+}
+
+fn mask_secrets(spec: &Value) -> Value {
+    let mut spec = spec.clone();
+    for section in SECRET_SECTIONS {
+        if let Some(entries) = spec.get_mut(section).and_then(Value::as_object_mut) {
+            for (key, value) in entries.iter_mut() {
+                if value.as_str().is_some_and(|v| is_secret_entry(key, v)) {
+                    *value = Value::String(SECRET_PLACEHOLDER.to_string());
+                }
+            }
+        }
+    }
+    spec
+}
 
+fn placeholders_in(server_id: &str, spec: &Value) -> Vec<McpSecretPrompt> {
+    SECRET_SECTIONS
+        .iter()
+        .filter_map(|section| Some((*section, spec.get(section)?.as_object()?)))
+        .flat_map(|(section, entries)| {
+            entries
+                .iter()
+                .filter(|(_, value)| value.as_str() == Some(SECRET_PLACEHOLDER))
+                .map(move |(key, _)| McpSecretPrompt {
+                    server_id: server_id.to_string(),
+                    section: section.to_string(),
+                    key: key.clone(),
+                })
+        })
+        .collect()
+}
+
+/// Placeholders in an MCP link that need a value before import
+pub fn mcp_secret_prompts(
+    request: &DeepLinkImportRequest,
+) -> Result<Vec<McpSecretPrompt>, AppError> {
+    let Some(config_b64) = request.config.as_deref() else {
+        return Ok(Vec::new());
+    };
+    let config_json = decode_mcp_config(config_b64)?;
+    Ok(mcp_servers_of(&config_json)?
+        .iter()
+        .flat_map(|(id, spec)| placeholders_in(id, spec))
+        .collect())
+}
+
+/// Return the request with the recipient's values written over the placeholders
+pub fn fill_mcp_secrets(
+    request: &DeepLinkImportRequest,
+    answers: &[McpSecretAnswer],
+) -> Result<DeepLinkImportRequest, AppError> {
+    let config_b64 = request
+        .config
+        .as_deref()
+        .ok_or_else(|| AppError::InvalidInput("Missing 'config' parameter for MCP".to_string()))?;
+    let mut config_json = decode_mcp_config(config_b64)?;
+    mcp_servers_of(&config_json)?;
+
+    for answer in answers {
+        let slot = config_json
+            .pointer_mut(&format!(
+                "/mcpServers/{}/{}/{}",
+                escape_pointer(&answer.server_id),
+                escape_pointer(&answer.section),
+                escape_pointer(&answer.key)
+            ))
+            .filter(|slot| slot.as_str() == Some(SECRET_PLACEHOLDER));
+        if let Some(slot) = slot {
+            *slot = Value::String(answer.value.clone());
+        }
+    }
+
+    let config = serde_json::to_string(&config_json)
+        .map_err(|e| AppError::Message(format!("Failed to serialize MCP config: {e}")))?;
+    let mut filled = request.clone();
+    filled.config = Some(BASE64_STANDARD.encode(config));
+    Ok(filled)
+}
+
+fn escape_pointer(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+/// Upsert a batch of MCP servers for the given apps
+///
+/// Existing servers only get the new apps merged in; failures are collected per server
+pub(super) fn import_mcp_servers(
+    state: &AppState,
+    target_apps: &McpApps,
+    mcp_servers: &serde_json::Map<String, Value>,
+) -> Result<McpImportResult, AppError> {
     // Get existing servers to check for duplicates
-    let existing_servers = state.db.get_all_mcp_servers()?;
+    let existing_servers = state.storage.get_all_mcp_servers()?;
 
     // Import each MCP server
     let mut imported_ids = Vec::new();
@@ -97,6 +283,18 @@ pub fn import_mcp_from_deeplink(
                 tags: existing.tags.clone(),
             }
         } else {
+            let unanswered = placeholders_in(id, server_spec);
+            if !unanswered.is_empty() {
+                let keys: Vec<String> = unanswered
+                    .iter()
+                    .map(|p| format!("{}.{}", p.section, p.key))
+                    .collect();
+                failed.push(McpImportError {
+                    id: id.clone(),
+                    error: format!("Missing values for {}", keys.join(", ")),
+                });
+                continue;
+            }
             // New server - create with provided config
             log::info!("Creating new MCP server: {id}");
             McpServer {
@@ -182,4 +380,55 @@ mod tests {
         let err = parse_mcp_apps("invalid").unwrap_err();
         assert!(err.to_string().contains("Invalid app"));
     }
+
+    #[test]
+    fn shared_link_masks_secrets_until_the_recipient_fills_them() {
+        let server = McpServer {
+            id: "github".to_string(),
+            name: "GitHub".to_string(),
+            server: json!({
+                "command": "npx",
+                "args": ["-y", "@modelcontextprotocol/server-github"],
+                "env": {
+                    "GITHUB_PERSONAL_ACCESS_TOKEN": "ghp_mine",
+                    "GITHUB_HOST": "github.com",
+                    "FROM_SHELL_KEY": "${MY_KEY}"
+                }
+            }),
+            apps: McpApps::default(),
+            description: None,
+            homepage: None,
+            docs: None,
+            tags: Vec::new(),
+        };
+        let apps = parse_mcp_apps("claude,codex").unwrap();
+        let link = build_mcp_deeplink(std::slice::from_ref(&server), &apps).unwrap();
+        let request = crate::deeplink::parse_deeplink_url(&link).unwrap();
+        assert_eq!(request.apps.as_deref(), Some("claude,codex"));
+
+        let prompts = mcp_secret_prompts(&request).unwrap();
+        assert_eq!(prompts.len(), 1);
+        assert_eq!(prompts[0].key, "GITHUB_PERSONAL_ACCESS_TOKEN");
+
+        let filled = fill_mcp_secrets(
+            &request,
+            &[McpSecretAnswer {
+                server_id: "github".to_string(),
+                section: "env".to_string(),
+                key: "GITHUB_PERSONAL_ACCESS_TOKEN".to_string(),
+                value: "ghp_theirs".to_string(),
+            }],
+        )
+        .unwrap();
+        assert!(mcp_secret_prompts(&filled).unwrap().is_empty());
+        let config = decode_mcp_config(filled.config.as_deref().unwrap()).unwrap();
+        assert_eq!(
+            config["mcpServers"]["github"]["env"],
+            json!({
+                "GITHUB_PERSONAL_ACCESS_TOKEN": "ghp_theirs",
+                "GITHUB_HOST": "github.com",
+                "FROM_SHELL_KEY": "${MY_KEY}"
+            })
+        );
+    }
 }
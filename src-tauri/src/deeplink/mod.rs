@@ -4,17 +4,39 @@
 /// via deep links. See docs/clihub-deeplink-design.md for detailed design.
 
 pub mod types;
+mod action;
 mod parser;
 mod provider;
 mod mcp;
 mod prompt;
 mod skill;
+mod bundle;
+mod security;
+mod share;
 mod utils;
 
 // Re-export public API
 pub use types::*;
+pub use action::{build_action_deeplink, parse_action_deeplink, receive_action_deeplink};
 pub use parser::parse_deeplink_url;
 pub use provider::{import_provider_from_deeplink, parse_and_merge_config};
-pub use mcp::import_mcp_from_deeplink;
+pub use mcp::{
+    build_mcp_deeplink, fill_mcp_secrets, import_mcp_from_deeplink, mcp_secret_prompts,
+    parse_mcp_apps, McpSecretAnswer, McpSecretPrompt, SECRET_PLACEHOLDER,
+};
 pub use prompt::import_prompt_from_deeplink;
-pub use skill::import_skill_from_deeplink;
+pub use skill::{
+    import_skill_from_deeplink, install_skill_from_deeplink, SkillInstallProgress,
+    SkillInstallStage, SKILL_INSTALL_PROGRESS_EVENT,
+};
+pub(crate) use parser::parse_provider_deeplink;
+pub(crate) use security::AUDIT_LOG_KEY;
+pub(crate) use provider::build_provider_from_request;
+pub use share::{build_provider_deeplink, API_KEY_PLACEHOLDER};
+pub(crate) use share::provider_to_request;
+pub use bundle::{import_bundle_from_deeplink, parse_bundle_manifest};
+pub use security::{
+    confirm_deeplink, dismiss_deeplink, get_deeplink_audit_log, list_pending_deeplinks,
+    receive_deeplink, sign_deeplink, DeepLinkAuditEntry, DeepLinkAuditStatus,
+    PendingDeepLinkImport,
+};
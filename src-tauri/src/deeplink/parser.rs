@@ -5,6 +5,12 @@ use url::Url;
 use super::types::DeepLinkImportRequest;
 use super::utils::validate_url;
 
+/// Size limit for the Base64 `usageScript` parameter (v1.1)
+pub(crate) const MAX_USAGE_SCRIPT_PARAM_LEN: usize = 64 * 1024;
+/// At most this many extra endpoints per provider link
+const MAX_EXTRA_ENDPOINTS: usize = 16;
+const MAX_PROMOTION_KEY_LEN: usize = 64;
+
 /// Parse a clihub:// URL into a DeepLinkImportRequest
 ///
 /// Expected format:
@@ -58,6 +64,7 @@ pub fn parse_deeplink_url(url_str: &str) -> Result<DeepLinkImportRequest, AppErr
         "prompt" => parse_prompt_deeplink(&params, version, resource),
         "mcp" => parse_mcp_deeplink(&params, version, resource),
         "skill" => parse_skill_deeplink(&params, version, resource),
+        "bundle" => parse_bundle_deeplink(&params, version, resource),
         _ => Err(AppError::InvalidInput(format!(
             "Unsupported resource type: {resource}"
         ))),
@@ -65,7 +72,7 @@ pub fn parse_deeplink_url(url_str: &str) -> Result<DeepLinkImportRequest, AppErr
 }
 
 /// Parse provider deep link parameters
-fn parse_provider_deeplink(
+pub(crate) fn parse_provider_deeplink(
     params: &HashMap<String, String>,
     version: String,
     resource: String,
@@ -119,6 +126,16 @@ fn parse_provider_deeplink(
     let config_url = params.get("configUrl").cloned();
     let enabled = params.get("enabled").and_then(|v| v.parse::<bool>().ok());
 
+    // v1.1 extras: usage script, extra endpoints, partner promotion key
+    let usage_script = params.get("usageScript").cloned();
+    let endpoints = params.get("endpoints").cloned();
+    let partner_promotion_key = params.get("partnerPromotionKey").cloned();
+    validate_provider_extras(
+        usage_script.as_deref(),
+        endpoints.as_deref(),
+        partner_promotion_key.as_deref(),
+    )?;
+
     Ok(DeepLinkImportRequest {
         version,
         resource,
@@ -141,12 +158,66 @@ fn parse_provider_deeplink(
         directory: None,
         branch: None,
         skills_path: None,
+        install: None,
         config,
         config_format,
         config_url,
+        token: None,
+        usage_script,
+        endpoints,
+        partner_promotion_key,
     })
 }
 
+/// Validate the optional v1.1 provider fields before the link is quarantined
+pub(crate) fn validate_provider_extras(
+    usage_script: Option<&str>,
+    endpoints: Option<&str>,
+    partner_promotion_key: Option<&str>,
+) -> Result<(), AppError> {
+    if let Some(script) = usage_script {
+        if script.len() > MAX_USAGE_SCRIPT_PARAM_LEN {
+            return Err(AppError::InvalidInput(format!(
+                "'usageScript' is too large: {} bytes (max {MAX_USAGE_SCRIPT_PARAM_LEN})",
+                script.len()
+            )));
+        }
+        super::utils::decode_base64_param("usageScript", script)?;
+    }
+
+    if let Some(list) = endpoints {
+        let urls: Vec<&str> = list
+            .split(',')
+            .map(str::trim)
+            .filter(|u| !u.is_empty())
+            .collect();
+        if urls.len() > MAX_EXTRA_ENDPOINTS {
+            return Err(AppError::InvalidInput(format!(
+                "Too many endpoints: {} (max {MAX_EXTRA_ENDPOINTS})",
+                urls.len()
+            )));
+        }
+        for url in urls {
+            validate_url(url, "endpoints")?;
+        }
+    }
+
+    if let Some(key) = partner_promotion_key {
+        let valid = !key.is_empty()
+            && key.len() <= MAX_PROMOTION_KEY_LEN
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(AppError::InvalidInput(format!(
+                "Invalid 'partnerPromotionKey': expected up to {MAX_PROMOTION_KEY_LEN} letters, digits, '-' or '_'"
+            )));
+        }
+    }
+
+    Ok(())
+}
+
 /// Parse prompt deep link parameters
 fn parse_prompt_deeplink(
     params: &HashMap<String, String>,
@@ -202,9 +273,14 @@ fn parse_prompt_deeplink(
         directory: None,
         branch: None,
         skills_path: None,
+        install: None,
         config: None,
         config_format: None,
         config_url: None,
+        token: None,
+        usage_script: None,
+        endpoints: None,
+        partner_promotion_key: None,
     })
 }
 
@@ -260,7 +336,12 @@ fn parse_mcp_deeplink(
         directory: None,
         branch: None,
         skills_path: None,
+        install: None,
         config_url: None,
+        token: None,
+        usage_script: None,
+        endpoints: None,
+        partner_promotion_key: None,
     })
 }
 
@@ -289,6 +370,12 @@ fn parse_skill_deeplink(
         .get("skills_path")
         .or_else(|| params.get("skillsPath"))
         .cloned();
+    let install = params.get("install").and_then(|v| v.parse::<bool>().ok());
+    if install == Some(true) && directory.is_none() {
+        return Err(AppError::InvalidInput(
+            "'install=true' requires the 'directory' parameter".to_string(),
+        ));
+    }
 
     Ok(DeepLinkImportRequest {
         version,
@@ -297,6 +384,7 @@ fn parse_skill_deeplink(
         directory,
         branch,
         skills_path,
+        install,
         icon: None,
         app: Some("claude".to_string()), // Skills are Claude-only
         name: None,
@@ -315,6 +403,58 @@ fn parse_skill_deeplink(
         config: None,
         config_format: None,
         config_url: None,
+        token: None,
+        usage_script: None,
+        endpoints: None,
+        partner_promotion_key: None,
+    })
+}
+
+/// Parse bundle deep link parameters
+///
+/// The manifest itself is only decoded on preview/import, see `bundle::parse_bundle_manifest`
+fn parse_bundle_deeplink(
+    params: &HashMap<String, String>,
+    version: String,
+    resource: String,
+) -> Result<DeepLinkImportRequest, AppError> {
+    let config = params
+        .get("config")
+        .ok_or_else(|| AppError::InvalidInput("Missing 'config' parameter for bundle".to_string()))?
+        .clone();
+
+    let name = params.get("name").cloned();
+
+    Ok(DeepLinkImportRequest {
+        version,
+        resource,
+        name,
+        config: Some(config),
+        config_format: Some("json".to_string()), // Bundle manifest is always JSON
+        app: None,
+        enabled: None,
+        icon: None,
+        homepage: None,
+        endpoint: None,
+        api_key: None,
+        model: None,
+        notes: None,
+        haiku_model: None,
+        sonnet_model: None,
+        opus_model: None,
+        content: None,
+        description: None,
+        apps: None,
+        repo: None,
+        directory: None,
+        branch: None,
+        skills_path: None,
+        install: None,
+        config_url: None,
+        token: None,
+        usage_script: None,
+        endpoints: None,
+        partner_promotion_key: None,
     })
 }
 
@@ -341,6 +481,32 @@ mod tests {
         assert_eq!(request.icon, Some("claude".to_string()));
     }
 
+    #[test]
+    fn test_parse_provider_extras_v1_1() {
+        use base64::prelude::*;
+
+        let script = BASE64_STANDARD.encode("({ request: {}, extractor: r => r })");
+        let url = format!(
+            "clihub://v1/import?resource=provider&app=claude&name=Vendor&endpoint=https%3A%2F%2Fapi.vendor.com&apiKey=sk-1&usageScript={}&endpoints=https%3A%2F%2Fa.vendor.com%2Chttps%3A%2F%2Fb.vendor.com&partnerPromotionKey=vendor_2025",
+            url::form_urlencoded::byte_serialize(script.as_bytes()).collect::<String>()
+        );
+        let request = parse_deeplink_url(&url).unwrap();
+        assert_eq!(request.usage_script.as_deref(), Some(script.as_str()));
+        assert_eq!(
+            request.endpoints.as_deref(),
+            Some("https://a.vendor.com,https://b.vendor.com")
+        );
+        assert_eq!(
+            request.partner_promotion_key.as_deref(),
+            Some("vendor_2025")
+        );
+
+        let oversized = "A".repeat(MAX_USAGE_SCRIPT_PARAM_LEN + 4);
+        assert!(validate_provider_extras(Some(&oversized), None, None).is_err());
+        assert!(validate_provider_extras(None, Some("ftp://x"), None).is_err());
+        assert!(validate_provider_extras(None, None, Some("bad key!")).is_err());
+    }
+
     #[test]
     fn test_parse_deeplink_with_notes() {
         let url = "clihub://v1/import?resource=provider&app=codex&name=Codex&homepage=https%3A%2F%2Fcodex.com&endpoint=https%3A%2F%2Fapi.codex.com&apiKey=key123&notes=Test%20notes";
@@ -420,6 +586,25 @@ mod tests {
         assert_eq!(request.enabled.unwrap(), true);
     }
 
+    #[test]
+    fn test_parse_bundle_deeplink() {
+        use base64::prelude::*;
+        let manifest = r#"{"prompt":{"app":"claude","name":"p","content":"hi"}}"#;
+        let manifest_b64 = BASE64_URL_SAFE_NO_PAD.encode(manifest);
+        let url = format!(
+            "clihub://v1/import?resource=bundle&name=Onboarding&config={}",
+            manifest_b64
+        );
+
+        let request = parse_deeplink_url(&url).unwrap();
+        assert_eq!(request.resource, "bundle");
+        assert_eq!(request.name.unwrap(), "Onboarding");
+        assert_eq!(request.config.unwrap(), manifest_b64);
+
+        let err = parse_deeplink_url("clihub://v1/import?resource=bundle").unwrap_err();
+        assert!(err.to_string().contains("Missing 'config' parameter"));
+    }
+
     #[test]
     fn test_parse_skill_deeplink() {
         let url = "clihub://v1/import?resource=skill&repo=owner/repo&directory=skills&branch=dev&skills_path=src";
@@ -431,4 +616,14 @@ mod tests {
         assert_eq!(request.branch.unwrap(), "dev");
         assert_eq!(request.skills_path.unwrap(), "src");
     }
+
+    #[test]
+    fn test_parse_skill_deeplink_install() {
+        let url = "clihub://v1/import?resource=skill&repo=owner/repo&directory=pdf&install=true";
+        let request = parse_deeplink_url(url).unwrap();
+        assert_eq!(request.install, Some(true));
+
+        let missing_dir = "clihub://v1/import?resource=skill&repo=owner/repo&install=true";
+        assert!(parse_deeplink_url(missing_dir).is_err());
+    }
 }
@@ -45,6 +45,27 @@ pub fn import_prompt_from_deeplink(
     let content = String::from_utf8(content)
         .map_err(|e| AppError::InvalidInput(format!("Invalid UTF-8 in content: {e}")))?;
 
+    import_prompt_content(
+        state,
+        app_type,
+        name,
+        content,
+        request.description,
+        request.enabled.unwrap_or(false),
+    )
+}
+
+/// Save decoded prompt content, optionally enabling it (which disables others)
+pub(super) fn import_prompt_content(
+    state: &AppState,
+    app_type: AppType,
+    name: String,
+    content: String,
+    description: Option<String>,
+    should_enable: bool,
+) -> Result<String, AppError> {
+    let app_str = app_type.as_str().to_string();
+
     // Generate ID
     let timestamp = chrono::Utc::now().timestamp_millis();
     let sanitized_name = name
@@ -54,18 +75,16 @@ pub fn import_prompt_from_deeplink(
         .to_lowercase();
     let id = format!("{sanitized_name}-{timestamp}");
 
-    // Check if we should enable this prompt
-    let should_enable = request.enabled.unwrap_or(false);
-
     // Create Prompt (initially disabled)
     let prompt = Prompt {
         id: id.clone(),
         name: name.clone(),
         content,
-        description: request.description,
+        description,
         enabled: false, // Always start as disabled, will be enabled later if needed
         created_at: Some(timestamp),
         updated_at: Some(timestamp),
+        version: None,
     };
 
     // Save using PromptService
@@ -1,12 +1,17 @@
 use crate::error::AppError;
-use crate::provider::Provider;
+use crate::provider::{Provider, ProviderMeta, UsageScript};
 use crate::services::ProviderService;
+use crate::settings::CustomEndpoint;
 use crate::store::AppState;
 use crate::AppType;
+use std::collections::HashMap;
 use std::str::FromStr;
 
 use super::types::DeepLinkImportRequest;
-use super::utils::infer_homepage_from_endpoint;
+use super::utils::{decode_base64_param, infer_homepage_from_endpoint, validate_url};
+
+/// Decoded usage script limit; the Base64 param is capped separately in the parser
+const MAX_USAGE_SCRIPT_BYTES: usize = 32 * 1024;
 
 /// Import a provider from a deep link request
 ///
@@ -263,22 +268,96 @@ requires_openai_auth = true
         created_at: None,
         sort_index: None,
         notes: request.notes.clone(),
-        meta: None,
+        meta: build_meta_from_request(request)?,
         icon: request.icon.clone(),
         icon_color: None,
+        last_switched_at: None,
+        switch_count: 0,
+        version: None,
     };
 
     Ok(provider)
 }
 
+/// Build provider meta from the v1.1 extras (usage script, endpoints, promotion key)
+fn build_meta_from_request(
+    request: &DeepLinkImportRequest,
+) -> Result<Option<ProviderMeta>, AppError> {
+    let usage_script = match &request.usage_script {
+        Some(raw) => {
+            let decoded = decode_base64_param("usageScript", raw)?;
+            if decoded.len() > MAX_USAGE_SCRIPT_BYTES {
+                return Err(AppError::InvalidInput(format!(
+                    "Usage script is too large: {} bytes (max {MAX_USAGE_SCRIPT_BYTES})",
+                    decoded.len()
+                )));
+            }
+            let code = String::from_utf8(decoded).map_err(|e| {
+                AppError::InvalidInput(format!("Invalid UTF-8 in usage script: {e}"))
+            })?;
+            if code.trim().is_empty() {
+                return Err(AppError::InvalidInput(
+                    "Usage script cannot be empty".to_string(),
+                ));
+            }
+            Some(UsageScript {
+                enabled: true,
+                language: "javascript".to_string(),
+                code,
+                timeout: None,
+                api_key: None,
+                base_url: None,
+                access_token: None,
+                user_id: None,
+                auto_query_interval: None,
+            })
+        }
+        None => None,
+    };
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let custom_endpoints: HashMap<String, CustomEndpoint> = request
+        .endpoints
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(|url| url.trim().trim_end_matches('/'))
+        .filter(|url| !url.is_empty())
+        .map(|url| {
+            validate_url(url, "endpoints")?;
+            Ok((
+                url.to_string(),
+                CustomEndpoint {
+                    url: url.to_string(),
+                    added_at: now,
+                    last_used: None,
+                },
+            ))
+        })
+        .collect::<Result<_, AppError>>()?;
+
+    let partner_promotion_key = request
+        .partner_promotion_key
+        .clone()
+        .filter(|k| !k.is_empty());
+
+    if usage_script.is_none() && custom_endpoints.is_empty() && partner_promotion_key.is_none() {
+        return Ok(None);
+    }
+    Ok(Some(ProviderMeta {
+        custom_endpoints,
+        usage_script,
+        partner_promotion_key,
+        ..Default::default()
+    }))
+}
+
 /// Parse and merge configuration from Base64 encoded config or remote URL
 ///
 /// Priority: URL params > inline config > remote config
 pub fn parse_and_merge_config(
     request: &DeepLinkImportRequest,
 ) -> Result<DeepLinkImportRequest, AppError> {
-    use super::utils::decode_base64_param;
-
     // If no config provided, return original request
     if request.config.is_none() && request.config_url.is_none() {
         return Ok(request.clone());
@@ -544,11 +623,16 @@ mod tests {
             config: None,
             config_format: None,
             config_url: None,
+            token: None,
+            usage_script: None,
+            endpoints: None,
+            partner_promotion_key: None,
             apps: None,
             repo: None,
             directory: None,
             branch: None,
             skills_path: None,
+            install: None,
             content: None,
             description: None,
             enabled: None,
@@ -591,11 +675,16 @@ mod tests {
             config: None,
             config_format: None,
             config_url: None,
+            token: None,
+            usage_script: None,
+            endpoints: None,
+            partner_promotion_key: None,
             apps: None,
             repo: None,
             directory: None,
             branch: None,
             skills_path: None,
+            install: None,
             content: None,
             description: None,
             enabled: None,
@@ -611,6 +700,29 @@ mod tests {
         assert!(env.get("GEMINI_MODEL").is_none());
     }
 
+    #[test]
+    fn test_build_provider_meta_from_extras() {
+        let mut request = parse_deeplink_request(
+            "clihub://v1/import?resource=provider&app=claude&name=Vendor&endpoint=https%3A%2F%2Fapi.vendor.com&apiKey=sk-1&endpoints=https%3A%2F%2Fa.vendor.com%2F&partnerPromotionKey=vendor",
+        );
+        request.usage_script = Some(BASE64_STANDARD.encode("return 1;"));
+
+        let provider = build_provider_from_request(&AppType::Claude, &request).unwrap();
+        let meta = provider.meta.unwrap();
+        let script = meta.usage_script.unwrap();
+        assert!(script.enabled);
+        assert_eq!(script.code, "return 1;");
+        assert!(meta.custom_endpoints.contains_key("https://a.vendor.com"));
+        assert_eq!(meta.partner_promotion_key.as_deref(), Some("vendor"));
+
+        request.usage_script = Some(BASE64_STANDARD.encode("x".repeat(MAX_USAGE_SCRIPT_BYTES + 1)));
+        assert!(build_provider_from_request(&AppType::Claude, &request).is_err());
+    }
+
+    fn parse_deeplink_request(url: &str) -> DeepLinkImportRequest {
+        super::super::parser::parse_deeplink_url(url).unwrap()
+    }
+
     #[test]
     fn test_parse_and_merge_config_claude() {
         use super::super::types::DeepLinkImportRequest;
@@ -636,11 +748,16 @@ mod tests {
             config: Some(config_b64),
             config_format: Some("json".to_string()),
             config_url: None,
+            token: None,
+            usage_script: None,
+            endpoints: None,
+            partner_promotion_key: None,
             apps: None,
             repo: None,
             directory: None,
             branch: None,
             skills_path: None,
+            install: None,
             content: None,
             description: None,
             enabled: None,
@@ -682,11 +799,16 @@ mod tests {
             config: Some(config_b64),
             config_format: Some("json".to_string()),
             config_url: None,
+            token: None,
+            usage_script: None,
+            endpoints: None,
+            partner_promotion_key: None,
             apps: None,
             repo: None,
             directory: None,
             branch: None,
             skills_path: None,
+            install: None,
             content: None,
             description: None,
             enabled: None,
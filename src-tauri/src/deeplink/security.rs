@@ -0,0 +1,455 @@
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::OsRng;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+use subtle::ConstantTimeEq;
+use url::Url;
+
+use crate::database::Database;
+use crate::error::AppError;
+
+use super::parser::parse_deeplink_url;
+use super::types::DeepLinkImportRequest;
+
+/// Quarantined links expire after 30 minutes
+const PENDING_TTL_MS: i64 = 30 * 60 * 1000;
+pub(crate) const AUDIT_LOG_KEY: &str = "deeplink_import_log";
+const AUDIT_LOG_LIMIT: usize = 200;
+/// Confirmation tokens carry 128 bits from the OS RNG
+const TOKEN_BYTES: usize = 16;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Outcome recorded for an incoming deep link
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DeepLinkAuditStatus {
+    /// Parsed and waiting for user confirmation
+    Pending,
+    /// Confirmed by the user and handed to the importer
+    Confirmed,
+    /// Dismissed by the user
+    Dismissed,
+    /// Refused before quarantine (parse error or bad signature)
+    Rejected,
+    /// Action link run directly after its signature was verified
+    Executed,
+}
+
+/// Import-source log entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeepLinkAuditEntry {
+    pub timestamp: i64,
+    /// Where the link came from (e.g. "on_open_url", "single_instance args", "frontend")
+    pub source: String,
+    pub status: DeepLinkAuditStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Endpoint host only, never the full URL or credentials
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint_host: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl DeepLinkAuditEntry {
+    pub(super) fn new(
+        source: &str,
+        status: DeepLinkAuditStatus,
+        request: Option<&DeepLinkImportRequest>,
+    ) -> Self {
+        Self {
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            source: source.to_string(),
+            status,
+            resource: request.map(|r| r.resource.clone()),
+            app: request.and_then(|r| r.app.clone()),
+            name: request.and_then(|r| r.name.clone()),
+            endpoint_host: request
+                .and_then(|r| r.endpoint.as_deref())
+                .and_then(|ep| Url::parse(ep).ok())
+                .and_then(|url| url.host_str().map(String::from)),
+            message: None,
+        }
+    }
+}
+
+/// A parsed deep link waiting for confirmation
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingDeepLinkImport {
+    pub token: String,
+    pub source: String,
+    pub received_at: i64,
+    pub request: DeepLinkImportRequest,
+}
+
+static PENDING: OnceLock<RwLock<HashMap<String, PendingDeepLinkImport>>> = OnceLock::new();
+
+fn cell() -> &'static RwLock<HashMap<String, PendingDeepLinkImport>> {
+    PENDING.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Parse an incoming clihub:// URL, check its signature and quarantine it
+///
+/// The returned request carries a `token`; nothing is imported until
+/// `confirm_deeplink` is called with that token.
+pub fn receive_deeplink(
+    db: Option<&Database>,
+    url_str: &str,
+    source: &str,
+) -> Result<DeepLinkImportRequest, AppError> {
+    let parsed = parse_deeplink_url(url_str).and_then(|request| {
+        verify_signature(url_str)?;
+        Ok(request)
+    });
+
+    let mut request = match parsed {
+        Ok(request) => request,
+        Err(err) => {
+            let mut entry = DeepLinkAuditEntry::new(source, DeepLinkAuditStatus::Rejected, None);
+            entry.message = Some(err.to_string());
+            record(db, entry);
+            return Err(err);
+        }
+    };
+
+    let token = generate_token();
+    request.token = Some(token.clone());
+
+    let now = chrono::Utc::now().timestamp_millis();
+    {
+        let mut guard = cell().write()?;
+        guard.retain(|_, pending| now - pending.received_at < PENDING_TTL_MS);
+        guard.insert(
+            token.clone(),
+            PendingDeepLinkImport {
+                token,
+                source: source.to_string(),
+                received_at: now,
+                request: request.clone(),
+            },
+        );
+    }
+
+    record(
+        db,
+        DeepLinkAuditEntry::new(source, DeepLinkAuditStatus::Pending, Some(&request)),
+    );
+    Ok(request)
+}
+
+/// Release a quarantined link for import
+///
+/// Only the token is read from `request`; the quarantined request is returned and is the
+/// one to import, so edits made by the frontend never reach the import. Tokens are
+/// single-use; unknown or expired tokens are refused.
+pub fn confirm_deeplink(
+    db: Option<&Database>,
+    request: &DeepLinkImportRequest,
+) -> Result<DeepLinkImportRequest, AppError> {
+    let token = request.token.as_deref().ok_or_else(|| {
+        AppError::localized(
+            "deeplink.token_missing",
+            "缺少深链接确认令牌，请通过确认对话框导入",
+            "Missing deep link confirmation token; imports must be confirmed first",
+        )
+    })?;
+
+    let pending = take_pending(token)?;
+    record(
+        db,
+        DeepLinkAuditEntry::new(
+            &pending.source,
+            DeepLinkAuditStatus::Confirmed,
+            Some(&pending.request),
+        ),
+    );
+    Ok(pending.request)
+}
+
+/// Drop a quarantined link without importing it
+pub fn dismiss_deeplink(db: Option<&Database>, token: &str) -> Result<bool, AppError> {
+    let removed = cell().write()?.remove(token);
+    if let Some(pending) = removed.as_ref() {
+        record(
+            db,
+            DeepLinkAuditEntry::new(
+                &pending.source,
+                DeepLinkAuditStatus::Dismissed,
+                Some(&pending.request),
+            ),
+        );
+    }
+    Ok(removed.is_some())
+}
+
+/// List links still waiting for confirmation (oldest first)
+pub fn list_pending_deeplinks() -> Result<Vec<PendingDeepLinkImport>, AppError> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let mut pending: Vec<_> = cell()
+        .read()?
+        .values()
+        .filter(|p| now - p.received_at < PENDING_TTL_MS)
+        .cloned()
+        .collect();
+    pending.sort_by_key(|p| p.received_at);
+    Ok(pending)
+}
+
+/// Read the import-source log (newest last)
+pub fn get_deeplink_audit_log(db: &Database) -> Result<Vec<DeepLinkAuditEntry>, AppError> {
+    match db.get_setting(AUDIT_LOG_KEY)? {
+        Some(raw) => serde_json::from_str(&raw)
+            .map_err(|e| AppError::Config(format!("Failed to parse deep link import log: {e}"))),
+        None => Ok(Vec::new()),
+    }
+}
+
+fn take_pending(token: &str) -> Result<PendingDeepLinkImport, AppError> {
+    let pending = cell().write()?.remove(token).ok_or_else(|| {
+        AppError::localized(
+            "deeplink.token_invalid",
+            "深链接确认令牌无效或已被使用",
+            "Deep link confirmation token is invalid or already used",
+        )
+    })?;
+
+    if chrono::Utc::now().timestamp_millis() - pending.received_at >= PENDING_TTL_MS {
+        return Err(AppError::localized(
+            "deeplink.token_expired",
+            "深链接确认已过期，请重新打开链接",
+            "Deep link confirmation expired; please open the link again",
+        ));
+    }
+
+    Ok(pending)
+}
+
+/// Sign a clihub:// link with the shared secret, appending the result as `sig`
+///
+/// The signature is a hex HMAC-SHA256 over the canonical link (see [`canonical_link`]),
+/// so it only vouches for this exact link and never reveals the secret itself.
+pub fn sign_deeplink(url_str: &str, secret: &str) -> Result<String, AppError> {
+    let mut url = Url::parse(url_str)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid deep link URL: {e}")))?;
+    url.query_pairs_mut()
+        .append_pair("sig", &compute_signature(&url, secret)?);
+    Ok(url.into())
+}
+
+/// Check the `sig` parameter against the shared secret configured in settings
+///
+/// Links are accepted unsigned while no secret is configured.
+pub(super) fn verify_signature(url_str: &str) -> Result<(), AppError> {
+    check_signature(url_str, false)
+}
+
+/// Like [`verify_signature`], but refuses every link while no secret is configured
+///
+/// Used for links that take effect without a confirmation dialog.
+pub(super) fn require_signature(url_str: &str) -> Result<(), AppError> {
+    check_signature(url_str, true)
+}
+
+fn check_signature(url_str: &str, required: bool) -> Result<(), AppError> {
+    let secret = crate::settings::get_settings()
+        .security
+        .and_then(|s| s.deeplink_secret)
+        .filter(|s| !s.is_empty());
+    match secret {
+        Some(secret) => verify_with_secret(url_str, &secret),
+        None if !required => Ok(()),
+        None => Err(AppError::localized(
+            "deeplink.secret_required",
+            "操作链接会直接修改配置，需先在设置中配置深链接签名密钥",
+            "Action links change configs directly; configure a deep link secret in settings first",
+        )),
+    }
+}
+
+fn verify_with_secret(url_str: &str, secret: &str) -> Result<(), AppError> {
+    let url = Url::parse(url_str)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid deep link URL: {e}")))?;
+    let sig = url
+        .query_pairs()
+        .find(|(key, _)| key == "sig")
+        .map(|(_, value)| value.to_ascii_lowercase());
+    let Some(sig) = sig else {
+        return Err(AppError::localized(
+            "deeplink.sig_missing",
+            "已启用深链接签名校验，但链接缺少 sig 参数",
+            "Deep link signature is required but the 'sig' parameter is missing",
+        ));
+    };
+
+    let expected = compute_signature(&url, secret)?;
+    if bool::from(sig.as_bytes().ct_eq(expected.as_bytes())) {
+        Ok(())
+    } else {
+        Err(AppError::localized(
+            "deeplink.sig_invalid",
+            "深链接签名 (sig) 校验失败",
+            "Deep link signature (sig) does not match",
+        ))
+    }
+}
+
+fn compute_signature(url: &Url, secret: &str) -> Result<String, AppError> {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
+        .map_err(|e| AppError::Message(format!("Failed to initialize deep link HMAC: {e}")))?;
+    mac.update(canonical_link(url).as_bytes());
+    Ok(to_hex(&mac.finalize().into_bytes()))
+}
+
+/// `scheme://host/path?query` with `sig` removed and the remaining pairs sorted
+///
+/// Sorting makes the signature independent of parameter order, so links rebuilt by
+/// other tools still verify as long as no value changes.
+fn canonical_link(url: &Url) -> String {
+    let mut pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(key, _)| key != "sig")
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    pairs.sort();
+    let query = url::form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(pairs)
+        .finish();
+    format!(
+        "{}://{}{}?{}",
+        url.scheme(),
+        url.host_str().unwrap_or_default(),
+        url.path(),
+        query
+    )
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn generate_token() -> String {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    OsRng.fill_bytes(&mut bytes);
+    to_hex(&bytes)
+}
+
+/// Append to the import-source log; failures only get logged
+pub(super) fn record(db: Option<&Database>, entry: DeepLinkAuditEntry) {
+    log::info!(
+        "Deep link {:?} from {}: resource={:?}, name={:?}",
+        entry.status,
+        entry.source,
+        entry.resource,
+        entry.name
+    );
+
+    let Some(db) = db else {
+        return;
+    };
+
+    let result = get_deeplink_audit_log(db).and_then(|mut entries| {
+        entries.push(entry);
+        if entries.len() > AUDIT_LOG_LIMIT {
+            let overflow = entries.len() - AUDIT_LOG_LIMIT;
+            entries.drain(..overflow);
+        }
+        let json =
+            serde_json::to_string(&entries).map_err(|e| AppError::JsonSerialize { source: e })?;
+        db.set_setting(AUDIT_LOG_KEY, &json)
+    });
+
+    if let Err(e) = result {
+        log::warn!("Failed to write deep link import log: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: &str = "clihub://v1/import?resource=provider&app=claude&name=Test&endpoint=https%3A%2F%2Fapi.example.com&apiKey=sk-1";
+
+    #[test]
+    fn test_confirm_requires_quarantined_token() {
+        let db = Database::memory().unwrap();
+
+        let request = receive_deeplink(Some(&db), URL, "test").unwrap();
+        assert_eq!(
+            request.token.as_deref().map(str::len),
+            Some(TOKEN_BYTES * 2)
+        );
+
+        let mut forged = request.clone();
+        forged.token = Some("forged".to_string());
+        assert!(confirm_deeplink(Some(&db), &forged).is_err());
+
+        // The quarantined request is returned, not the one sent back by the frontend
+        let mut tampered = request.clone();
+        tampered.endpoint = Some("https://evil.example.com".to_string());
+        let confirmed = confirm_deeplink(Some(&db), &tampered).unwrap();
+        assert_eq!(
+            confirmed.endpoint.as_deref(),
+            Some("https://api.example.com")
+        );
+        // Tokens are single-use
+        assert!(confirm_deeplink(Some(&db), &request).is_err());
+
+        let log = get_deeplink_audit_log(&db).unwrap();
+        let statuses: Vec<_> = log.iter().map(|e| e.status).collect();
+        assert_eq!(
+            statuses,
+            vec![DeepLinkAuditStatus::Pending, DeepLinkAuditStatus::Confirmed]
+        );
+        assert_eq!(log[0].endpoint_host.as_deref(), Some("api.example.com"));
+    }
+
+    #[test]
+    fn test_dismiss_removes_pending() {
+        let request = receive_deeplink(None, URL, "test").unwrap();
+        let token = request.token.clone().unwrap();
+
+        assert!(list_pending_deeplinks()
+            .unwrap()
+            .iter()
+            .any(|p| p.token == token));
+        assert!(dismiss_deeplink(None, &token).unwrap());
+        assert!(confirm_deeplink(None, &request).is_err());
+    }
+
+    #[test]
+    fn test_signature_covers_every_param() {
+        let signed = sign_deeplink(URL, "s3cret").unwrap();
+        assert!(!signed.contains("s3cret"));
+        assert!(verify_with_secret(&signed, "s3cret").is_ok());
+        assert!(verify_with_secret(&signed, "other").is_err());
+        assert!(verify_with_secret(URL, "s3cret").is_err());
+
+        // Reordering params keeps the signature valid
+        let sig = Url::parse(&signed)
+            .unwrap()
+            .query_pairs()
+            .find(|(key, _)| key == "sig")
+            .map(|(_, value)| value.into_owned())
+            .unwrap();
+        let reordered = format!(
+            "clihub://v1/import?sig={sig}&apiKey=sk-1&name=Test&app=claude&resource=provider&endpoint=https%3A%2F%2Fapi.example.com"
+        );
+        assert!(verify_with_secret(&reordered, "s3cret").is_ok());
+
+        // Changing any value breaks it
+        let tampered = signed.replace("api.example.com", "evil.example.com");
+        assert!(verify_with_secret(&tampered, "s3cret").is_err());
+        let tampered = signed.replace("apiKey=sk-1", "apiKey=sk-2");
+        assert!(verify_with_secret(&tampered, "s3cret").is_err());
+    }
+}
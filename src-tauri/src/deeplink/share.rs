@@ -0,0 +1,165 @@
+use base64::prelude::*;
+use url::Url;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::Provider;
+
+use super::provider::parse_and_merge_config;
+use super::types::DeepLinkImportRequest;
+
+/// Stand-in written to `apiKey` when the secret is not shared
+pub const API_KEY_PLACEHOLDER: &str = "YOUR_API_KEY";
+
+/// Connection details recovered from a stored provider, as a deep link would carry them
+pub(crate) fn provider_to_request(
+    app_type: &AppType,
+    provider: &Provider,
+) -> Result<DeepLinkImportRequest, AppError> {
+    // Reuse the import-side config merge in reverse: feed the stored settings
+    // back in as an inline config and let it pick out key, endpoint and models
+    let config = match app_type {
+        AppType::Gemini => provider
+            .settings_config
+            .get("env")
+            .cloned()
+            .unwrap_or_else(|| serde_json::json!({})),
+        _ => provider.settings_config.clone(),
+    };
+    let config = serde_json::to_string(&config)
+        .map_err(|e| AppError::Message(format!("Failed to serialize provider config: {e}")))?;
+
+    let meta = provider.meta.as_ref();
+    let endpoints: Vec<&str> = meta
+        .map(|m| m.custom_endpoints.keys().map(String::as_str).collect())
+        .unwrap_or_default();
+
+    let request = DeepLinkImportRequest {
+        version: "v1".to_string(),
+        resource: "provider".to_string(),
+        app: Some(app_type.as_str().to_string()),
+        name: Some(provider.name.clone()),
+        enabled: None,
+        homepage: provider.website_url.clone().filter(|u| !u.is_empty()),
+        endpoint: None,
+        api_key: None,
+        icon: provider.icon.clone(),
+        model: None,
+        notes: provider.notes.clone().filter(|n| !n.is_empty()),
+        haiku_model: None,
+        sonnet_model: None,
+        opus_model: None,
+        usage_script: None,
+        endpoints: (!endpoints.is_empty()).then(|| endpoints.join(",")),
+        partner_promotion_key: meta.and_then(|m| m.partner_promotion_key.clone()),
+        content: None,
+        description: None,
+        apps: None,
+        repo: None,
+        directory: None,
+        branch: None,
+        skills_path: None,
+        install: None,
+        config: Some(BASE64_STANDARD.encode(config)),
+        config_format: Some("json".to_string()),
+        config_url: None,
+        token: None,
+    };
+
+    let mut merged = parse_and_merge_config(&request)?;
+    merged.config = None;
+    merged.config_format = None;
+    Ok(merged)
+}
+
+/// Build a `clihub://v1/import?resource=provider` link for a stored provider
+///
+/// With `include_secret = false` the API key is replaced by [`API_KEY_PLACEHOLDER`],
+/// so the recipient still gets a valid link and fills in their own key after import
+pub fn build_provider_deeplink(
+    app_type: &AppType,
+    provider: &Provider,
+    include_secret: bool,
+) -> Result<String, AppError> {
+    let request = provider_to_request(app_type, provider)?;
+    if request.endpoint.as_deref().unwrap_or("").is_empty() {
+        return Err(AppError::InvalidInput(format!(
+            "Provider '{}' has no endpoint to share",
+            provider.name
+        )));
+    }
+
+    let api_key = match request.api_key.as_deref() {
+        Some(key) if include_secret && !key.is_empty() => key,
+        _ => API_KEY_PLACEHOLDER,
+    };
+
+    let mut url = Url::parse("clihub://v1/import")
+        .map_err(|e| AppError::Message(format!("Failed to build deep link: {e}")))?;
+    {
+        let mut query = url.query_pairs_mut();
+        query.append_pair("resource", "provider");
+        query.append_pair("app", app_type.as_str());
+        query.append_pair("name", &provider.name);
+        query.append_pair("apiKey", api_key);
+
+        let optional = [
+            ("homepage", &request.homepage),
+            ("endpoint", &request.endpoint),
+            ("model", &request.model),
+            ("haikuModel", &request.haiku_model),
+            ("sonnetModel", &request.sonnet_model),
+            ("opusModel", &request.opus_model),
+            ("icon", &request.icon),
+            ("notes", &request.notes),
+            ("endpoints", &request.endpoints),
+            ("partnerPromotionKey", &request.partner_promotion_key),
+        ];
+        for (key, value) in optional {
+            if let Some(value) = value.as_deref().filter(|v| !v.is_empty()) {
+                query.append_pair(key, value);
+            }
+        }
+    }
+
+    Ok(url.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deeplink::parse_deeplink_url;
+    use serde_json::json;
+
+    #[test]
+    fn share_link_round_trips_and_hides_secret() {
+        let provider = Provider::with_id(
+            "p1".into(),
+            "Relay & Co".into(),
+            json!({
+                "env": {
+                    "ANTHROPIC_AUTH_TOKEN": "sk-secret",
+                    "ANTHROPIC_BASE_URL": "https://api.relay.example/v1",
+                    "ANTHROPIC_MODEL": "claude-sonnet-4-5"
+                }
+            }),
+            Some("https://relay.example".into()),
+        );
+
+        let link = build_provider_deeplink(&AppType::Claude, &provider, true).unwrap();
+        let parsed = parse_deeplink_url(&link).unwrap();
+        assert_eq!(parsed.name.as_deref(), Some("Relay & Co"));
+        assert_eq!(parsed.api_key.as_deref(), Some("sk-secret"));
+        assert_eq!(
+            parsed.endpoint.as_deref(),
+            Some("https://api.relay.example/v1")
+        );
+        assert_eq!(parsed.model.as_deref(), Some("claude-sonnet-4-5"));
+        assert_eq!(parsed.homepage.as_deref(), Some("https://relay.example"));
+
+        let link = build_provider_deeplink(&AppType::Claude, &provider, false).unwrap();
+        assert!(!link.contains("sk-secret"));
+        let parsed = parse_deeplink_url(&link).unwrap();
+        assert_eq!(parsed.api_key.as_deref(), Some(API_KEY_PLACEHOLDER));
+    }
+}
@@ -1,9 +1,36 @@
+use chrono::Utc;
+use serde::Serialize;
+
 use crate::error::AppError;
-use crate::services::skill::SkillRepo;
+use crate::services::skill::{SkillRepo, SkillService, SkillState};
 use crate::store::AppState;
 
 use super::types::DeepLinkImportRequest;
 
+/// Event emitted while a deep link installs a skill
+pub const SKILL_INSTALL_PROGRESS_EVENT: &str = "skill-install-progress";
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SkillInstallStage {
+    /// Listing the repo to locate the requested directory
+    Fetching,
+    /// Downloading the repo and copying the skill directory
+    Installing,
+    Done,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillInstallProgress {
+    pub repo: String,
+    pub directory: String,
+    pub stage: SkillInstallStage,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 /// Import a skill from deep link request
 pub fn import_skill_from_deeplink(
     state: &AppState,
@@ -40,10 +67,92 @@ pub fn import_skill_from_deeplink(
         skills_path: request.skills_path,
     };
 
-    // Save using Database
-    state.db.save_skill_repo(&repo)?;
+    state.storage.save_skill_repo(&repo)?;
 
     log::info!("Successfully added skill repo '{owner}/{name}'");
 
     Ok(format!("{owner}/{name}"))
 }
+
+/// Install `directory` from a repo registered by [`import_skill_from_deeplink`].
+///
+/// Only directories present in the repo listing are installed, so a link cannot point the
+/// installer outside the skills folder. Returns the installed directory name.
+pub async fn install_skill_from_deeplink(
+    state: &AppState,
+    service: &SkillService,
+    repo_key: &str,
+    directory: &str,
+    on_progress: impl Fn(&SkillInstallProgress),
+) -> Result<String, AppError> {
+    let report = |stage, error: Option<String>| {
+        on_progress(&SkillInstallProgress {
+            repo: repo_key.to_string(),
+            directory: directory.to_string(),
+            stage,
+            error,
+        })
+    };
+
+    let result = install_listed_skill(state, service, repo_key, directory, &report).await;
+    match &result {
+        Ok(_) => report(SkillInstallStage::Done, None),
+        Err(e) => report(SkillInstallStage::Failed, Some(e.to_string())),
+    }
+    result
+}
+
+async fn install_listed_skill(
+    state: &AppState,
+    service: &SkillService,
+    repo_key: &str,
+    directory: &str,
+    report: &impl Fn(SkillInstallStage, Option<String>),
+) -> Result<String, AppError> {
+    crate::services::demo::ensure_not_demo()?;
+
+    let repo = state
+        .storage
+        .get_skill_repos()?
+        .into_iter()
+        .find(|r| format!("{}/{}", r.owner, r.name) == repo_key)
+        .ok_or_else(|| AppError::InvalidInput(format!("Skill repo '{repo_key}' not found")))?;
+
+    report(SkillInstallStage::Fetching, None);
+    let skills = service
+        .list_skills(vec![repo.clone()])
+        .await
+        .map_err(|e| AppError::Message(e.to_string()))?;
+    let skill = skills
+        .into_iter()
+        .find(|s| {
+            s.repo_owner.as_deref() == Some(repo.owner.as_str())
+                && s.repo_name.as_deref() == Some(repo.name.as_str())
+                && s.directory.eq_ignore_ascii_case(directory)
+        })
+        .ok_or_else(|| {
+            AppError::InvalidInput(format!(
+                "Skill directory '{directory}' not found in '{repo_key}'"
+            ))
+        })?;
+
+    if !skill.installed {
+        report(SkillInstallStage::Installing, None);
+        service
+            .install_skill(skill.directory.clone(), repo)
+            .await
+            .map_err(|e| AppError::Message(e.to_string()))?;
+    }
+
+    state.storage.update_skill_state(
+        &skill.directory,
+        &SkillState {
+            installed: true,
+            installed_at: Utc::now(),
+            source: None,
+        },
+    )?;
+    log::info!("Installed skill '{}' from '{repo_key}'", skill.directory);
+
+    Ok(skill.directory)
+}
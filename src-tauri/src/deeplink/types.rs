@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 pub struct DeepLinkImportRequest {
     /// Protocol version (e.g., "v1")
     pub version: String,
-    /// Resource type to import: "provider" | "prompt" | "mcp" | "skill"
+    /// Resource type to import: "provider" | "prompt" | "mcp" | "skill" | "bundle"
     pub resource: String,
 
     // ============ Common fields ============
@@ -50,6 +50,17 @@ pub struct DeepLinkImportRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub opus_model: Option<String>,
 
+    // ============ Provider extras (v1.1) ============
+    /// Base64 encoded usage query script (JavaScript), enabled on import
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage_script: Option<String>,
+    /// Extra endpoints for speed testing (comma-separated URLs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoints: Option<String>,
+    /// Partner promotion key stored in provider meta
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partner_promotion_key: Option<String>,
+
     // ============ Prompt-specific fields ============
     /// Base64 encoded Markdown content
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -76,9 +87,12 @@ pub struct DeepLinkImportRequest {
     /// Skills subdirectory path (e.g., "skills")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub skills_path: Option<String>,
+    /// Also install `directory` after registering the repo, instead of only adding the repo
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub install: Option<bool>,
 
     // ============ Config file fields (v3.8+) ============
-    /// Base64 encoded config content
+    /// Base64 encoded config content (bundle: Base64 encoded JSON manifest)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub config: Option<String>,
     /// Config format (json/toml)
@@ -87,6 +101,11 @@ pub struct DeepLinkImportRequest {
     /// Remote config URL
     #[serde(skip_serializing_if = "Option::is_none")]
     pub config_url: Option<String>,
+
+    // ============ Security fields ============
+    /// Confirmation token issued when the link was quarantined, required for import
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
 }
 
 /// MCP import result
@@ -110,3 +129,69 @@ pub struct McpImportError {
     /// Error message
     pub error: String,
 }
+
+/// Decoded bundle manifest (`resource=bundle`)
+///
+/// One link carrying a provider, a set of MCP servers and a prompt,
+/// shown to the user in a single confirmation dialog and imported together
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeepLinkBundle {
+    /// Bundle display name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Bundle description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Provider part, same fields as a provider deep link (config already merged)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<DeepLinkImportRequest>,
+    /// MCP servers part
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mcp: Option<BundleMcp>,
+    /// Prompt part
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<BundlePrompt>,
+}
+
+/// MCP servers inside a bundle manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleMcp {
+    /// Target applications (comma-separated: "claude,codex,gemini")
+    pub apps: String,
+    /// Servers in standard MCP JSON format (id -> server spec)
+    pub mcp_servers: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Prompt inside a bundle manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundlePrompt {
+    /// Target application (claude/codex/gemini)
+    pub app: String,
+    /// Prompt name
+    pub name: String,
+    /// Plain Markdown content (the manifest itself is already Base64 encoded)
+    pub content: String,
+    /// Prompt description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Whether to enable after import (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+}
+
+/// Bundle import result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleImportResult {
+    /// ID of the imported provider
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider_id: Option<String>,
+    /// IDs of imported/updated MCP servers
+    pub mcp_ids: Vec<String>,
+    /// ID of the imported prompt
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt_id: Option<String>,
+}
@@ -52,6 +52,9 @@ pub enum AppError {
     },
     #[error("数据库错误: {0}")]
     Database(String),
+    /// 乐观锁冲突，消息为 JSON，前端可解析出双方版本后提示合并
+    #[error("{0}")]
+    Conflict(String),
 }
 
 impl AppError {
@@ -83,6 +86,67 @@ impl AppError {
             en: en.into(),
         }
     }
+
+    /// 保存时记录已被其他窗口修改：附带期望版本、当前版本及当前数据
+    pub fn conflict(
+        resource: &str,
+        id: &str,
+        expected_version: i64,
+        current_version: i64,
+        current: serde_json::Value,
+    ) -> Self {
+        let payload = serde_json::json!({
+            "code": "VERSION_CONFLICT",
+            "resource": resource,
+            "id": id,
+            "expectedVersion": expected_version,
+            "currentVersion": current_version,
+            "current": current,
+        });
+        Self::Conflict(payload.to_string())
+    }
+
+    /// 模板仍有未填写的占位符：消息为 JSON，前端据此逐项提示输入
+    pub fn missing_placeholders(template_id: &str, missing: serde_json::Value) -> Self {
+        let payload = serde_json::json!({
+            "code": "MISSING_PLACEHOLDERS",
+            "templateId": template_id,
+            "missing": missing,
+        });
+        Self::Message(payload.to_string())
+    }
+
+    /// 目标配置目录只读：消息为 JSON，附带不可写的路径与处理建议
+    pub fn read_only_target(app: &str, paths: &[&str], hints: &[String]) -> Self {
+        let payload = serde_json::json!({
+            "code": "READ_ONLY_TARGET",
+            "app": app,
+            "paths": paths,
+            "hints": hints,
+        });
+        Self::Message(payload.to_string())
+    }
+
+    /// 供应商由组织统一管理：消息为 JSON，附带管理方名称
+    pub fn provider_managed(app: &str, id: &str, org: &str) -> Self {
+        let payload = serde_json::json!({
+            "code": "PROVIDER_MANAGED",
+            "app": app,
+            "id": id,
+            "org": org,
+        });
+        Self::Message(payload.to_string())
+    }
+
+    /// 提示词以口令加密且本次运行尚未解锁：消息为 JSON，前端据此弹出口令输入
+    pub fn prompt_locked(app: &str, id: &str) -> Self {
+        let payload = serde_json::json!({
+            "code": "PROMPT_LOCKED",
+            "app": app,
+            "id": id,
+        });
+        Self::Message(payload.to_string())
+    }
 }
 
 impl<T> From<PoisonError<T>> for AppError {
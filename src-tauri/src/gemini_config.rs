@@ -1,7 +1,8 @@
 use crate::config::write_text_file;
 use crate::error::AppError;
-use serde_json::Value;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::PathBuf;
 
@@ -145,11 +146,11 @@ pub fn serialize_env_file(map: &HashMap<String, String>) -> String {
 pub fn read_gemini_env() -> Result<HashMap<String, String>, AppError> {
     let path = get_gemini_env_path();
 
-    if !path.exists() {
+    if !crate::config::file_exists(&path) {
         return Ok(HashMap::new());
     }
 
-    let content = fs::read_to_string(&path).map_err(|e| AppError::io(&path, e))?;
+    let content = crate::config::read_text_file(&path)?;
 
     Ok(parse_env_file(&content))
 }
@@ -176,6 +177,10 @@ pub fn write_gemini_env_atomic(map: &HashMap<String, String>) -> Result<(), AppE
 
     let content = serialize_env_file(map);
     write_text_file(&path, &content)?;
+    if crate::config::is_capturing_writes() {
+        // 预览模式下文件并未写出，无需调整权限
+        return Ok(());
+    }
 
     // 设置文件权限为 600（仅所有者可读写）
     #[cfg(unix)]
@@ -245,6 +250,8 @@ pub fn validate_gemini_settings(settings: &Value) -> Result<(), AppError> {
                 "Gemini config invalid: config must be an object",
             ));
         }
+        // 手动编辑的模型路由同样需要通过校验
+        validate_model_routing(&read_model_routing(config)?)?;
     }
 
     Ok(())
@@ -302,9 +309,8 @@ fn update_selected_type(selected_type: &str) -> Result<(), AppError> {
     }
 
     // 读取现有的 settings.json（如果存在）
-    let mut settings_content = if settings_path.exists() {
-        let content =
-            fs::read_to_string(&settings_path).map_err(|e| AppError::io(&settings_path, e))?;
+    let mut settings_content = if crate::config::file_exists(&settings_path) {
+        let content = crate::config::read_text_file(&settings_path)?;
         serde_json::from_str::<Value>(&content).unwrap_or_else(|_| serde_json::json!({}))
     } else {
         serde_json::json!({})
@@ -372,6 +378,277 @@ pub fn write_google_oauth_settings() -> Result<(), AppError> {
     update_selected_type("oauth-personal")
 }
 
+/// 从 settings.json 中提取上下文文件名，兼容字符串与数组两种写法
+fn context_file_names_from(value: Option<&Value>) -> Option<Vec<String>> {
+    match value? {
+        Value::String(name) => Some(vec![name.clone()]),
+        Value::Array(items) => Some(
+            items
+                .iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+/// 读取 settings.json 中的上下文文件名
+///
+/// 优先 `context.fileName`，其次旧版顶层 `contextFileName`；均未配置时返回空列表。
+pub fn read_context_file_names() -> Result<Vec<String>, AppError> {
+    let settings_path = get_gemini_settings_path();
+    if !crate::config::file_exists(&settings_path) {
+        return Ok(Vec::new());
+    }
+
+    let content = crate::config::read_text_file(&settings_path)?;
+    let settings: Value = serde_json::from_str(&content).unwrap_or_else(|_| serde_json::json!({}));
+
+    Ok(
+        context_file_names_from(settings.pointer("/context/fileName"))
+            .or_else(|| context_file_names_from(settings.get("contextFileName")))
+            .unwrap_or_default(),
+    )
+}
+
+/// 写入上下文文件名列表，保留 settings.json 中的其他字段
+///
+/// 已在使用旧版顶层 `contextFileName` 时原地更新，否则写入 `context.fileName`。
+/// 列表只剩 `GEMINI.md` 时删除该字段，回到 Gemini CLI 的默认行为。
+pub fn write_context_file_names(names: &[String]) -> Result<(), AppError> {
+    let settings_path = get_gemini_settings_path();
+
+    let mut settings = if crate::config::file_exists(&settings_path) {
+        let content = crate::config::read_text_file(&settings_path)?;
+        serde_json::from_str::<Value>(&content).unwrap_or_else(|_| serde_json::json!({}))
+    } else {
+        serde_json::json!({})
+    };
+
+    let Some(obj) = settings.as_object_mut() else {
+        return Err(AppError::localized(
+            "gemini.settings.invalid",
+            "Gemini settings.json 顶层必须是对象",
+            "Gemini settings.json must be a JSON object",
+        ));
+    };
+
+    let is_default = names.len() == 1 && names[0] == "GEMINI.md";
+    let value = Value::Array(names.iter().cloned().map(Value::String).collect());
+
+    if obj.contains_key("contextFileName") {
+        if is_default {
+            obj.remove("contextFileName");
+        } else {
+            obj.insert("contextFileName".to_string(), value);
+        }
+    } else if is_default {
+        if let Some(context) = obj.get_mut("context").and_then(Value::as_object_mut) {
+            context.remove("fileName");
+        }
+    } else {
+        let context = obj
+            .entry("context")
+            .or_insert_with(|| serde_json::json!({}));
+        if let Some(context_obj) = context.as_object_mut() {
+            context_obj.insert("fileName".to_string(), value);
+        }
+    }
+
+    if let Some(parent) = settings_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+    }
+    crate::config::write_json_file(&settings_path, &settings)
+}
+
+/// Gemini 供应商的模型路由，保存在供应商 config（即 settings.json）中
+///
+/// - 默认模型写入 `model.name`
+/// - 回退模型写入 `modelConfigs.fallbackModel`
+/// - 按任务的模型写入 `modelConfigs.customOverrides`，每项形如
+///   `{ "match": { "overrideScope": "<task>" }, "modelConfig": { "model": "<model>" } }`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiModelRouting {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_model: Option<String>,
+    /// 任务名 -> 模型
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub task_models: BTreeMap<String, String>,
+}
+
+const MODEL_CONFIGS_KEY: &str = "modelConfigs";
+const FALLBACK_MODEL_KEY: &str = "fallbackModel";
+const OVERRIDES_KEY: &str = "customOverrides";
+
+/// 从供应商 config 对象中读取模型路由；非 CLI Hub 管理的覆盖项会被忽略
+pub fn read_model_routing(config: &Value) -> Result<GeminiModelRouting, AppError> {
+    let string_at = |pointer: &str| -> Result<Option<String>, AppError> {
+        match config.pointer(pointer) {
+            None | Some(Value::Null) => Ok(None),
+            Some(Value::String(model)) => Ok(Some(model.clone())),
+            Some(_) => Err(AppError::localized(
+                "gemini.model_routing.not_string",
+                format!("Gemini 配置中的 {pointer} 必须是字符串"),
+                format!("{pointer} in the Gemini config must be a string"),
+            )),
+        }
+    };
+
+    let mut routing = GeminiModelRouting {
+        default_model: string_at("/model/name")?,
+        fallback_model: string_at(&format!("/{MODEL_CONFIGS_KEY}/{FALLBACK_MODEL_KEY}"))?,
+        task_models: BTreeMap::new(),
+    };
+    let overrides = config
+        .get(MODEL_CONFIGS_KEY)
+        .and_then(|c| c.get(OVERRIDES_KEY))
+        .and_then(Value::as_array);
+    for entry in overrides.into_iter().flatten() {
+        if let Some((task, model)) = task_override(entry) {
+            routing
+                .task_models
+                .insert(task.to_string(), model.to_string());
+        }
+    }
+    Ok(routing)
+}
+
+/// 校验模型路由：模型名与任务名不能为空或包含空白，回退模型不能与默认模型相同
+pub fn validate_model_routing(routing: &GeminiModelRouting) -> Result<(), AppError> {
+    let invalid_model = |model: &str| {
+        AppError::localized(
+            "gemini.model_routing.invalid_model",
+            format!("无效的 Gemini 模型名: '{model}'"),
+            format!("Invalid Gemini model name: '{model}'"),
+        )
+    };
+    let is_valid_name = |name: &str| {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/' | ':'))
+    };
+
+    let models = routing
+        .default_model
+        .iter()
+        .chain(routing.fallback_model.iter())
+        .chain(routing.task_models.values());
+    for model in models {
+        if !is_valid_name(model) {
+            return Err(invalid_model(model));
+        }
+    }
+    if let Some(task) = routing.task_models.keys().find(|t| !is_valid_name(t)) {
+        return Err(AppError::localized(
+            "gemini.model_routing.invalid_task",
+            format!("无效的任务名: '{task}'"),
+            format!("Invalid task name: '{task}'"),
+        ));
+    }
+    if routing.fallback_model.is_some() && routing.fallback_model == routing.default_model {
+        return Err(AppError::localized(
+            "gemini.model_routing.same_fallback",
+            "回退模型不能与默认模型相同",
+            "The fallback model must differ from the default model",
+        ));
+    }
+    Ok(())
+}
+
+/// 将模型路由写入供应商 config 对象，保留其他字段与非 CLI Hub 管理的覆盖项；
+/// 未设置的项会从 config 中移除
+pub fn apply_model_routing(
+    config: &mut Value,
+    routing: &GeminiModelRouting,
+) -> Result<(), AppError> {
+    validate_model_routing(routing)?;
+    let Some(obj) = config.as_object_mut() else {
+        return Err(AppError::localized(
+            "gemini.validation.invalid_config",
+            "Gemini 配置格式错误: config 必须是对象",
+            "Gemini config invalid: config must be an object",
+        ));
+    };
+
+    let model = object_entry(obj, "model");
+    match &routing.default_model {
+        Some(name) => {
+            model.insert("name".to_string(), json!(name));
+        }
+        None => {
+            model.remove("name");
+        }
+    }
+    if model.is_empty() {
+        obj.remove("model");
+    }
+
+    let configs = object_entry(obj, MODEL_CONFIGS_KEY);
+    match &routing.fallback_model {
+        Some(fallback) => {
+            configs.insert(FALLBACK_MODEL_KEY.to_string(), json!(fallback));
+        }
+        None => {
+            configs.remove(FALLBACK_MODEL_KEY);
+        }
+    }
+    let mut overrides: Vec<Value> = configs
+        .get(OVERRIDES_KEY)
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter(|entry| task_override(entry).is_none())
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+    overrides.extend(routing.task_models.iter().map(|(task, model)| {
+        json!({
+            "match": { "overrideScope": task },
+            "modelConfig": { "model": model },
+        })
+    }));
+    if overrides.is_empty() {
+        configs.remove(OVERRIDES_KEY);
+    } else {
+        configs.insert(OVERRIDES_KEY.to_string(), Value::Array(overrides));
+    }
+    if configs.is_empty() {
+        obj.remove(MODEL_CONFIGS_KEY);
+    }
+    Ok(())
+}
+
+/// 仅按任务名匹配、只指定模型的覆盖项由 CLI Hub 管理
+fn task_override(entry: &Value) -> Option<(&str, &str)> {
+    let matcher = entry.get("match")?.as_object()?;
+    let model_config = entry.get("modelConfig")?.as_object()?;
+    if matcher.len() != 1 || model_config.len() != 1 {
+        return None;
+    }
+    Some((
+        matcher.get("overrideScope")?.as_str()?,
+        model_config.get("model")?.as_str()?,
+    ))
+}
+
+/// 取出（必要时创建）对象类型的子字段；原值不是对象时会被替换
+fn object_entry<'a>(obj: &'a mut Map<String, Value>, key: &str) -> &'a mut Map<String, Value> {
+    let entry = obj.entry(key).or_insert_with(|| json!({}));
+    if !entry.is_object() {
+        *entry = json!({});
+    }
+    match entry {
+        Value::Object(map) => map,
+        _ => unreachable!("entry was just replaced with an object"),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -653,4 +930,65 @@ KEY_WITH-DASH=value";
 
         assert!(validate_gemini_settings(&settings).is_err());
     }
+
+    #[test]
+    fn model_routing_round_trips_and_keeps_foreign_overrides() {
+        let mut config = json!({
+            "model": { "name": "gemini-2.5-flash", "maxSessionTurns": 20 },
+            "modelConfigs": {
+                "customOverrides": [
+                    {
+                        "match": { "model": "gemini-2.5-pro", "overrideScope": "core" },
+                        "modelConfig": { "generateContentConfig": { "temperature": 0.2 } }
+                    },
+                    { "match": { "overrideScope": "old" }, "modelConfig": { "model": "x" } }
+                ]
+            },
+            "ui": { "theme": "Dracula" }
+        });
+        let routing = GeminiModelRouting {
+            default_model: Some("gemini-2.5-pro".into()),
+            fallback_model: Some("gemini-2.5-flash".into()),
+            task_models: BTreeMap::from([("summarize".into(), "gemini-2.5-flash-lite".into())]),
+        };
+
+        apply_model_routing(&mut config, &routing).unwrap();
+        assert_eq!(read_model_routing(&config).unwrap(), routing);
+        assert_eq!(config["model"]["maxSessionTurns"], 20);
+        assert_eq!(config["ui"]["theme"], "Dracula");
+        let overrides = config["modelConfigs"]["customOverrides"]
+            .as_array()
+            .unwrap();
+        assert_eq!(
+            overrides.len(),
+            2,
+            "foreign override kept, stale task replaced"
+        );
+
+        apply_model_routing(&mut config, &GeminiModelRouting::default()).unwrap();
+        assert_eq!(config["model"], json!({ "maxSessionTurns": 20 }));
+        assert_eq!(
+            config["modelConfigs"]["customOverrides"]
+                .as_array()
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(config["modelConfigs"].get("fallbackModel").is_none());
+
+        let same = GeminiModelRouting {
+            default_model: Some("gemini-2.5-pro".into()),
+            fallback_model: Some("gemini-2.5-pro".into()),
+            ..Default::default()
+        };
+        assert!(validate_model_routing(&same).is_err());
+        let spaced = GeminiModelRouting {
+            default_model: Some("gemini pro".into()),
+            ..Default::default()
+        };
+        assert!(validate_model_routing(&spaced).is_err());
+        assert!(
+            validate_gemini_settings(&json!({ "config": { "model": { "name": 3 } } })).is_err()
+        );
+    }
 }
@@ -1,6 +1,8 @@
 use serde::Serialize;
 use std::sync::{OnceLock, RwLock};
 
+use crate::database::ProviderIntegrityIssue;
+
 #[derive(Debug, Clone, Serialize)]
 pub struct InitErrorPayload {
     pub path: String,
@@ -25,6 +27,26 @@ pub fn get_init_error() -> Option<InitErrorPayload> {
     cell().read().ok()?.clone()
 }
 
+static INIT_WARNINGS: OnceLock<RwLock<Vec<ProviderIntegrityIssue>>> = OnceLock::new();
+
+fn warnings_cell() -> &'static RwLock<Vec<ProviderIntegrityIssue>> {
+    INIT_WARNINGS.get_or_init(|| RwLock::new(Vec::new()))
+}
+
+/// 记录启动完整性检查的结果（含已自动修复的项），供前端稍后拉取
+pub fn set_init_warnings(warnings: Vec<ProviderIntegrityIssue>) {
+    if let Ok(mut guard) = warnings_cell().write() {
+        *guard = warnings;
+    }
+}
+
+pub fn get_init_warnings() -> Vec<ProviderIntegrityIssue> {
+    warnings_cell()
+        .read()
+        .map(|guard| guard.clone())
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
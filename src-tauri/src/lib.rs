@@ -12,23 +12,37 @@ mod error;
 mod gemini_config; // 新增
 mod gemini_mcp;
 mod init_status;
+mod log_sanitizer;
 mod mcp;
+mod notifications;
 mod prompt;
 mod prompt_files;
 mod provider;
 mod provider_defaults;
+mod provider_templates;
 mod services;
 mod settings;
+mod stacks;
+mod startup;
 mod store;
+#[cfg(feature = "test-support")]
+pub mod test_support;
 mod tray;
+mod usage_format;
 mod usage_script;
 
-pub use app_config::{AppType, McpApps, McpServer, MultiAppConfig};
-pub use codex_config::{get_codex_auth_path, get_codex_config_path, write_codex_live_atomic};
+pub use app_config::{AppType, McpApps, McpServer, McpSortUpdate, MultiAppConfig};
+pub use codex_config::{
+    get_codex_auth_path, get_codex_config_path, list_codex_config_backups,
+    restore_codex_config_backup, write_codex_live_atomic,
+};
 pub use commands::*;
 pub use config::{get_claude_mcp_path, get_claude_settings_path, read_json_file};
 pub use database::Database;
-pub use deeplink::{import_provider_from_deeplink, parse_deeplink_url, DeepLinkImportRequest};
+pub use deeplink::{
+    import_bundle_from_deeplink, import_provider_from_deeplink, parse_deeplink_url,
+    DeepLinkImportRequest,
+};
 pub use error::AppError;
 pub use mcp::{
     import_from_claude, import_from_codex, import_from_gemini, remove_server_from_claude,
@@ -36,12 +50,13 @@ pub use mcp::{
     sync_enabled_to_codex, sync_enabled_to_gemini, sync_single_server_to_claude,
     sync_single_server_to_codex, sync_single_server_to_gemini,
 };
-pub use provider::{Provider, ProviderMeta};
+pub use provider::{Provider, ProviderMeta, SyncScope, SyncScopeMode};
 pub use services::{
-    ConfigService, EndpointLatency, McpService, PromptService, ProviderService, SkillService,
-    SpeedtestService,
+    ConfigService, ConfirmAction, ConfirmationInput, ConfirmationService, CsvColumnMapping, EndpointLatency, GeminiContextService, McpService,
+    PromptService, ProviderCsvImportService, ProviderService, SkillService, SpeedtestService,
+    StackApplyReport, StackService, SyncPauseService,
 };
-pub use settings::{update_settings, AppSettings};
+pub use settings::{update_settings, AppSettings, ConfirmationLevel, ConfirmationPolicies};
 pub use store::AppState;
 pub use tray::update_tray_menu;
 use tauri_plugin_deep_link::DeepLinkExt;
@@ -75,7 +90,7 @@ fn json_migration_mode() -> JsonMigrationMode {
 
 /// 统一处理 clihub:// 深链接 URL
 ///
-/// - 解析 URL
+/// - 解析 URL，校验 sig 并放入待确认队列（导入前必须携带确认令牌）
 /// - 向前端发射 `deeplink-import` / `deeplink-error` 事件
 /// - 可选：在成功时聚焦主窗口
 fn handle_deeplink_url(
@@ -88,9 +103,38 @@ fn handle_deeplink_url(
         return false;
     }
 
-    log::info!("✓ Deep link URL detected from {source}: {url_str}");
+    log::info!(
+        "✓ Deep link URL detected from {source}: {}",
+        log_sanitizer::redact(url_str)
+    );
+
+    let state = app.try_state::<AppState>();
+    let db = state.as_ref().map(|s| s.db.as_ref());
+
+    // 启动器（Raycast/Alfred 等）的操作链接不经确认对话框，必须带有效签名或 API 令牌才会执行
+    match crate::deeplink::receive_action_deeplink(state.as_deref(), url_str, source) {
+        Ok(Some(action_id)) => {
+            log::info!("✓ Running launcher action: {action_id}");
+            crate::services::LauncherService::run_from_deeplink(app, url_str, action_id);
+            return true;
+        }
+        Ok(None) => {}
+        Err(e) => {
+            log::error!("✗ Rejected launcher action link: {e}");
+            if let Err(emit_err) = app.emit(
+                "deeplink-error",
+                serde_json::json!({
+                    "url": url_str,
+                    "error": e.to_string()
+                }),
+            ) {
+                log::error!("✗ Failed to emit deeplink-error event: {emit_err}");
+            }
+            return true;
+        }
+    }
 
-    match crate::deeplink::parse_deeplink_url(url_str) {
+    match crate::deeplink::receive_deeplink(db, url_str, source) {
         Ok(request) => {
             log::info!(
                 "✓ Successfully parsed deep link: resource={}, app={:?}, name={:?}",
@@ -142,7 +186,7 @@ pub fn run() {
             log::info!("=== Single Instance Callback Triggered ===");
             log::info!("Args count: {}", args.len());
             for (i, arg) in args.iter().enumerate() {
-                log::info!("  arg[{i}]: {arg}");
+                log::info!("  arg[{i}]: {}", log_sanitizer::redact(arg));
             }
 
             // Check for deep link URL in args (mainly for Windows/Linux command line)
@@ -172,8 +216,12 @@ pub fn run() {
         .plugin(tauri_plugin_deep_link::init())
         // 拦截窗口关闭：根据设置决定是否最小化到托盘
         .on_window_event(|window, event| {
+            // 单色托盘图标需要跟随系统深浅色切换
+            if let tauri::WindowEvent::ThemeChanged(_) = event {
+                tray::refresh_tray_icon(window.app_handle());
+            }
             if let tauri::WindowEvent::CloseRequested { api, .. } = event {
-                let settings = crate::settings::get_settings();
+                let settings = crate::settings::get_effective_settings();
 
                 if settings.minimize_to_tray_on_close {
                     api.prevent_close();
@@ -193,6 +241,7 @@ pub fn run() {
         })
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_store::Builder::new().build())
         .setup(|app| {
@@ -249,16 +298,30 @@ pub fn run() {
                 app.handle().plugin(
                     tauri_plugin_log::Builder::default()
                         .level(log::LevelFilter::Info)
+                        // 所有日志在输出前统一脱敏
+                        .format(|out, message, record| {
+                            out.finish(format_args!(
+                                "[{}][{}][{}] {}",
+                                chrono::Local::now().format("%Y-%m-%d][%H:%M:%S"),
+                                record.target(),
+                                record.level(),
+                                log_sanitizer::redact(&message.to_string())
+                            ))
+                        })
                         .build(),
                 )?;
             }
 
+            // 后台任务结果通过系统通知提示（受设置开关控制）
+            crate::notifications::init(app.handle());
+
             // 预先刷新 Store 覆盖配置，确保 AppState 初始化时可读取到最新路径
             app_store::refresh_app_config_dir_override(app.handle());
+            app_store::refresh_db_path_override(app.handle());
 
             // 初始化数据库
             let app_config_dir = crate::config::get_app_config_dir();
-            let db_path = app_config_dir.join("cli-hub.db");
+            let db_path = crate::config::get_db_path();
             let json_path = app_config_dir.join("config.json");
 
             // Check if config.json→SQLite migration needed (feature gated, disabled by default)
@@ -266,7 +329,30 @@ pub fn run() {
             let has_json = json_path.exists();
             let has_db = db_path.exists();
 
-            let db = match crate::database::Database::init() {
+            // 演示模式：使用预置假数据的内存数据库，且不写入任何 live 配置
+            let demo_mode = crate::services::DemoService::env_requested()
+                || app_store::get_demo_mode_from_store(app.handle());
+            if demo_mode {
+                crate::services::DemoService::enable();
+            }
+            // 安全模式：不启动后台任务、不做迁移与首次导入，也不写入 live 配置，便于排查损坏的数据
+            let safe_mode = crate::services::SafeModeService::requested();
+            if safe_mode {
+                crate::services::SafeModeService::enable();
+            }
+
+            // 数据库升级与 JSON 迁移在此同步执行，进度同时记录供前端拉取
+            let progress_handle = app.handle().clone();
+            crate::database::set_migration_progress_sink(move |status| {
+                let _ = progress_handle.emit(crate::database::MIGRATION_PROGRESS_EVENT, status);
+            });
+
+            let db_result = if demo_mode {
+                crate::database::Database::memory()
+            } else {
+                crate::database::Database::init()
+            };
+            let db = match db_result {
                 Ok(db) => Arc::new(db),
                 Err(e) => {
                     log::error!("Failed to init database: {e}");
@@ -276,12 +362,13 @@ pub fn run() {
                 }
             };
 
-            if !has_db && has_json {
+            if !demo_mode && !safe_mode && !has_db && has_json {
                 match migration_mode {
                     JsonMigrationMode::Disabled => {
                         log::warn!(
                             "Detected config.json but migration is disabled by default. \
-                             Set CLI_HUB_ENABLE_JSON_DB_MIGRATION=1 to migrate, or =dryrun to validate first."
+                             Set CLI_HUB_ENABLE_JSON_DB_MIGRATION=1 to migrate, or =dryrun to validate first, \
+                             or pick items in the in-app migration assistant."
                         );
                     }
                     JsonMigrationMode::DryRun => {
@@ -303,8 +390,24 @@ pub fn run() {
                             Ok(config) => {
                                 if let Err(e) = db.migrate_from_json(&config) {
                                     log::error!("Migration failed: {e}");
+                                    crate::notifications::notify(
+                                        crate::notifications::NotificationCategory::Migration,
+                                        false,
+                                        crate::notifications::NotificationText::new(
+                                            format!("config.json 迁移到数据库失败: {e}"),
+                                            format!("Migrating config.json to the database failed: {e}"),
+                                        ),
+                                    );
                                 } else {
                                     log::info!("Migration successful");
+                                    crate::notifications::notify(
+                                        crate::notifications::NotificationCategory::Migration,
+                                        true,
+                                        crate::notifications::NotificationText::new(
+                                            "config.json 已迁移到数据库",
+                                            "config.json has been migrated to the database",
+                                        ),
+                                    );
                                     // Optional: Rename config.json to prevent re-migration
                                     // let _ = std::fs::rename(&json_path, json_path.with_extension("json.migrated"));
                                 }
@@ -317,114 +420,43 @@ pub fn run() {
 
             crate::settings::bind_db(db.clone());
             let app_state = AppState::new(db);
-
-            // 检查是否需要首次导入（数据库为空）
-            let need_first_import = app_state
-                .db
-                .is_empty_for_first_import()
-                .unwrap_or_else(|e| {
-                    log::warn!("Failed to check if database is empty: {e}");
-                    false
-                });
-
-            if need_first_import {
-                // 数据库为空，尝试从用户现有的配置文件导入数据并初始化默认配置
-                log::info!(
-                    "Empty database detected, importing existing configurations and initializing defaults..."
+            crate::services::ModelFidelityService::bind(app_state.storage.clone());
+            if !demo_mode && !safe_mode {
+                // 提前发现只读的配置目录，之后的写入会直接给出处理建议
+                crate::services::WriteAccessService::detect();
+                // 调度器与命令共用同一份状态（同一存储），不再各自创建
+                crate::services::ExternalBackupService::spawn_scheduler(app_state.clone());
+                crate::services::AccessWindowService::spawn_scheduler(
+                    app.handle().clone(),
+                    app_state.clone(),
                 );
+                crate::services::EndpointHealthService::spawn_scheduler(
+                    app.handle().clone(),
+                    app_state.clone(),
+                );
+                crate::services::ProviderPresetService::spawn_scheduler(app_state.clone());
+                crate::services::PromptScheduleService::spawn_scheduler(
+                    app.handle().clone(),
+                    app_state.clone(),
+                );
+            }
 
-                // 1. 初始化默认 Skills 仓库（3个）
-                match app_state.db.init_default_skill_repos() {
-                    Ok(count) if count > 0 => {
-                        log::info!("✓ Initialized {count} default skill repositories");
-                    }
-                    Ok(_) => log::debug!("No default skill repositories to initialize"),
-                    Err(e) => log::warn!("✗ Failed to initialize default skill repos: {e}"),
-                }
-
-                // 2. 导入供应商配置（从 live 配置文件）
-                for app in [
-                    crate::app_config::AppType::Claude,
-                    crate::app_config::AppType::Codex,
-                    crate::app_config::AppType::Gemini,
-                ] {
-                    match crate::services::provider::ProviderService::import_default_config(
-                        &app_state,
-                        app.clone(),
-                    ) {
-                        Ok(_) => {
-                            log::info!("✓ Imported default provider for {}", app.as_str());
-                        }
-                        Err(e) => {
-                            log::debug!(
-                                "○ No default provider to import for {}: {}",
-                                app.as_str(),
-                                e
-                            );
-                        }
-                    }
-                }
-
-                // 3. 导入 MCP 服务器配置
-                match crate::services::mcp::McpService::import_from_claude(&app_state) {
-                    Ok(count) if count > 0 => {
-                        log::info!("✓ Imported {count} MCP server(s) from Claude");
-                    }
-                    Ok(_) => log::debug!("○ No Claude MCP servers found to import"),
-                    Err(e) => log::warn!("✗ Failed to import Claude MCP: {e}"),
-                }
-
-                match crate::services::mcp::McpService::import_from_codex(&app_state) {
-                    Ok(count) if count > 0 => {
-                        log::info!("✓ Imported {count} MCP server(s) from Codex");
-                    }
-                    Ok(_) => log::debug!("○ No Codex MCP servers found to import"),
-                    Err(e) => log::warn!("✗ Failed to import Codex MCP: {e}"),
-                }
-
-                match crate::services::mcp::McpService::import_from_gemini(&app_state) {
-                    Ok(count) if count > 0 => {
-                        log::info!("✓ Imported {count} MCP server(s) from Gemini");
-                    }
-                    Ok(_) => log::debug!("○ No Gemini MCP servers found to import"),
-                    Err(e) => log::warn!("✗ Failed to import Gemini MCP: {e}"),
+            if demo_mode {
+                if let Err(e) = crate::services::DemoService::seed(&app_state) {
+                    log::error!("写入演示数据失败: {e}");
                 }
+            }
 
-                // 4. 导入提示词文件
-                match crate::services::prompt::PromptService::import_from_file_on_first_launch(
-                    &app_state,
-                    crate::app_config::AppType::Claude,
-                ) {
-                    Ok(count) if count > 0 => {
-                        log::info!("✓ Imported {count} prompt(s) from Claude");
-                    }
-                    Ok(_) => log::debug!("○ No Claude prompt file found to import"),
-                    Err(e) => log::warn!("✗ Failed to import Claude prompt: {e}"),
-                }
+            // 恢复上次的暂停管理状态，须在任何 live 写入之前完成
+            if let Err(e) = crate::services::SyncPauseService::load(&app_state) {
+                log::warn!("读取暂停管理状态失败: {e}");
+            }
 
-                match crate::services::prompt::PromptService::import_from_file_on_first_launch(
-                    &app_state,
-                    crate::app_config::AppType::Codex,
-                ) {
-                    Ok(count) if count > 0 => {
-                        log::info!("✓ Imported {count} prompt(s) from Codex");
-                    }
-                    Ok(_) => log::debug!("○ No Codex prompt file found to import"),
-                    Err(e) => log::warn!("✗ Failed to import Codex prompt: {e}"),
+            // 调试代理的端口每次启动都不同，需重新启动并改写 live 配置
+            if !demo_mode && !safe_mode {
+                if let Err(e) = crate::services::DebugProxyService::restore(&app_state) {
+                    log::warn!("恢复调试代理失败: {e}");
                 }
-
-                match crate::services::prompt::PromptService::import_from_file_on_first_launch(
-                    &app_state,
-                    crate::app_config::AppType::Gemini,
-                ) {
-                    Ok(count) if count > 0 => {
-                        log::info!("✓ Imported {count} prompt(s) from Gemini");
-                    }
-                    Ok(_) => log::debug!("○ No Gemini prompt file found to import"),
-                    Err(e) => log::warn!("✗ Failed to import Gemini prompt: {e}"),
-                }
-
-                log::info!("First-time import completed");
             }
 
             // 迁移旧的 app_config_dir 配置到 Store
@@ -457,7 +489,7 @@ pub fn run() {
 
                     for (i, url) in urls.iter().enumerate() {
                         let url_str = url.as_str();
-                        log::info!("  URL[{i}]: {url_str}");
+                        log::info!("  URL[{i}]: {}", log_sanitizer::redact(url_str));
 
                         if handle_deeplink_url(&app_handle, url_str, true, "on_open_url") {
                             break; // Process only first clihub:// URL
@@ -478,50 +510,92 @@ pub fn run() {
                     _ => log::debug!("unhandled event {event:?}"),
                 })
                 .menu(&menu)
+                .tooltip(tray::tray_tooltip(&app_state))
                 .on_menu_event(|app, event| {
                     tray::handle_tray_menu_event(app, &event.id.0);
                 })
                 .show_menu_on_left_click(true);
 
-            // 统一使用应用默认图标；待托盘模板图标就绪后再启用
-            if let Some(icon) = app.default_window_icon() {
-                tray_builder = tray_builder.icon(icon.clone());
+            // macOS 默认使用模板图标，其余平台按设置在彩色与单色图标间选择
+            if let Some((icon, template)) = tray::tray_icon(app.handle()) {
+                tray_builder = tray_builder.icon(icon).icon_as_template(template);
             } else {
                 log::warn!("Failed to get default window icon for tray");
             }
 
             let _tray = tray_builder.build(app)?;
+            tray::remember_tray_menu(&menu);
             // 将同一个实例注入到全局状态，避免重复创建导致的不一致
+            let startup_db = app_state.db.clone();
             app.manage(app_state);
 
-            // 初始化 SkillService
-            match SkillService::new() {
-                Ok(skill_service) => {
-                    app.manage(commands::skill::SkillServiceState(Arc::new(skill_service)));
-                }
-                Err(e) => {
-                    log::warn!("初始化 SkillService 失败: {e}");
-                }
-            }
+            // 首次导入、配置修复与 SkillService 初始化放到后台，窗口无需等待
+            app.manage(commands::skill::SkillServiceState::default());
+            app.manage(startup::StartupState::default());
+            startup::spawn_background_init(app.handle().clone(), startup_db);
 
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             commands::get_providers,
             commands::get_current_provider,
+            commands::describe_provider_schema,
             commands::add_provider,
             commands::update_provider,
+            commands::refresh_provider_metadata,
+            commands::capture_codex_login,
+            commands::get_codex_login_status,
+            commands::get_relay_directory,
+            commands::update_relay_directory,
             commands::delete_provider,
             commands::switch_provider,
+            commands::validate_switch,
+            commands::preview_switch,
+            commands::get_provider_error_history,
+            commands::switch_provider_by_name,
+            commands::generate_provider_share_page,
             commands::import_default_config,
             commands::get_claude_config_status,
             commands::get_config_status,
+            commands::check_live_config,
+            commands::repair_live_config,
+            commands::get_management_paused,
+            commands::pause_management,
+            commands::preview_resume_management,
+            commands::resume_management,
+            commands::convert_mcp_config,
+            commands::convert_provider_settings,
+            commands::list_codex_config_backups,
+            commands::get_claude_effective_settings,
+            commands::list_claude_hooks,
+            commands::add_claude_hook,
+            commands::update_claude_hook,
+            commands::remove_claude_hook,
+            commands::get_claude_permissions,
+            commands::set_claude_permissions,
+            commands::add_claude_permission_rule,
+            commands::remove_claude_permission_rule,
+            commands::list_claude_permission_presets,
+            commands::apply_claude_permission_preset,
+            commands::restore_codex_config_backup,
+            commands::create_api_token,
+            commands::list_api_tokens,
+            commands::revoke_api_token,
             commands::get_claude_code_config_path,
             commands::get_config_dir,
             commands::open_config_folder,
             commands::pick_directory,
             commands::open_external,
             commands::get_init_error,
+            commands::get_init_warnings,
+            commands::get_migration_status,
+            commands::get_provider_upgrade_report,
+            commands::get_status_summary,
+            commands::get_write_capabilities,
+            commands::list_cli_sessions,
+            commands::get_cli_session,
+            commands::is_startup_ready,
+            commands::wait_for_startup,
             commands::get_app_config_path,
             commands::open_app_config_folder,
             commands::get_claude_common_config_snippet,
@@ -529,8 +603,12 @@ pub fn run() {
             commands::get_common_config_snippet,
             commands::set_common_config_snippet,
             commands::read_live_provider_settings,
+            commands::merge_live_into_provider,
             commands::get_settings,
             commands::save_settings,
+            commands::get_settings_profile,
+            commands::get_effective_settings,
+            commands::save_settings_overrides,
             commands::restart_app,
             commands::check_for_updates,
             commands::is_portable_mode,
@@ -544,9 +622,14 @@ pub fn run() {
             commands::upsert_claude_mcp_server,
             commands::delete_claude_mcp_server,
             commands::validate_mcp_command,
+            commands::convert_mcp_transport,
             // usage query
             commands::queryProviderUsage,
             commands::testUsageScript,
+            commands::cancel_usage_query,
+            commands::get_gemini_model_routing,
+            commands::set_gemini_model_routing,
+            commands::dry_run_usage_script,
             // New MCP via config.json (SSOT)
             commands::get_mcp_config,
             commands::upsert_mcp_server_in_config,
@@ -554,50 +637,151 @@ pub fn run() {
             commands::set_mcp_enabled,
             // v3.7.0: Unified MCP management
             commands::get_mcp_servers,
+            commands::set_mcp_env_var,
+            commands::remove_mcp_env_var,
             commands::upsert_mcp_server,
             commands::delete_mcp_server,
             commands::toggle_mcp_app,
+            commands::update_mcp_sort_order,
+            commands::list_mcp_profiles,
+            commands::save_current_as_profile,
+            commands::apply_mcp_profile,
+            commands::delete_mcp_profile,
+            commands::list_quarantined_mcp,
+            commands::retry_quarantined_mcp,
+            commands::discard_quarantined_mcp,
+            commands::duplicate_mcp_server,
             // Prompt management
             commands::get_prompts,
+            commands::get_prompt_summaries,
+            commands::get_prompt_content,
             commands::upsert_prompt,
             commands::delete_prompt,
             commands::enable_prompt,
+            commands::set_prompts_enabled,
+            commands::estimate_prompt_tokens,
             commands::import_prompt_from_file,
+            commands::export_prompts_to_slash_commands,
+            commands::import_slash_commands,
             commands::get_current_prompt_file_content,
+            commands::get_gemini_context_files,
+            commands::upsert_gemini_context_file,
+            commands::set_gemini_context_file_enabled,
+            commands::delete_gemini_context_file,
+            commands::detect_codex_agents_files,
+            commands::write_codex_agents_file,
+            commands::import_codex_agents_file,
+            commands::get_codex_effective_agents,
+            commands::encrypt_prompt,
+            commands::decrypt_prompt,
+            commands::unlock_prompt,
+            commands::list_prompt_schedules,
+            commands::save_prompt_schedule,
+            commands::delete_prompt_schedule,
             // ours: endpoint speed test + custom endpoint management
             commands::test_api_endpoints,
+            commands::test_inference_latency,
+            commands::get_security_findings,
+            commands::get_proxy_log,
+            commands::clear_proxy_log,
+            commands::get_debug_proxies,
+            commands::recheck_model_fidelity,
+            commands::create_provider_from_key,
+            commands::detect_provider_drafts,
+            commands::mark_provider_reviewed,
+            commands::list_provider_templates,
+            commands::refresh_provider_presets,
+            commands::get_template_missing_placeholders,
+            commands::instantiate_template,
+            commands::detect_local_models,
+            commands::build_local_model_settings,
+            commands::check_local_model_health,
             commands::get_custom_endpoints,
+            commands::get_endpoint_health,
             commands::add_custom_endpoint,
             commands::remove_custom_endpoint,
+            commands::export_custom_endpoints,
+            commands::import_custom_endpoints,
+            commands::export_provider,
+            commands::import_provider,
             commands::update_endpoint_last_used,
             // app_config_dir override via Store
             commands::get_app_config_dir_override,
             commands::set_app_config_dir_override,
+            commands::export_app_settings,
+            commands::import_app_settings,
+            commands::get_db_location,
+            commands::check_db_location,
+            commands::move_database,
+            commands::get_demo_mode,
+            commands::set_demo_mode,
+            commands::get_safe_mode,
             // provider sort order management
             commands::update_providers_sort_order,
+            commands::preview_providers_from_url,
+            commands::import_providers_from_url,
+            commands::import_providers_bulk,
+            commands::import_managed_providers,
             // theirs: config import/export and dialogs
             commands::export_config_to_file,
             commands::import_config_from_file,
+            commands::list_db_backups,
+            commands::verify_backup,
+            commands::preview_restore,
+            commands::restore_db_backup,
+            commands::delete_db_backup,
+            commands::analyze_legacy_config,
+            commands::migrate_selected,
+            commands::restore_legacy_config,
             commands::save_file_dialog,
             commands::open_file_dialog,
+            commands::start_transfer_server,
+            commands::stop_transfer_server,
+            commands::receive_transfer,
             commands::sync_current_providers_live,
+            commands::write_vcs_export,
+            commands::create_diagnostics_bundle,
             // Deep link import
             commands::parse_deeplink,
             commands::merge_deeplink_config,
+            commands::preview_deeplink_bundle,
+            commands::generate_mcp_deeplink,
+            commands::get_mcp_deeplink_secrets,
+            commands::fill_mcp_deeplink_secrets,
+            commands::list_pending_deeplink_imports,
+            commands::dismiss_deeplink_import,
+            commands::get_deeplink_import_log,
             commands::import_from_deeplink,
             commands::import_from_deeplink_unified,
             update_tray_menu,
+            tray::get_tray_menu_stats,
+            commands::get_quick_actions,
+            commands::execute_quick_action,
+            commands::get_launcher_manifest,
             // Environment variable management
             commands::check_env_conflicts,
             commands::delete_env_vars,
             commands::restore_env_backup,
+            commands::request_confirmation,
+            commands::backup_now_to_external,
+            commands::get_external_backup_status,
+            commands::cleanup_orphaned_data,
+            commands::list_env_backups,
+            commands::restore_env_vars,
+            commands::preview_env_vars_deletion,
+            commands::preview_env_vars_restore,
             // Skill management
             commands::get_skills,
             commands::install_skill,
             commands::uninstall_skill,
+            commands::scan_installed_skills,
             commands::get_skill_repos,
             commands::add_skill_repo,
             commands::remove_skill_repo,
+            commands::get_github_rate_limit,
+            // Stacks
+            commands::list_stacks,
+            commands::apply_stack,
             // Auto launch
             commands::set_auto_launch,
             commands::get_auto_launch_status,
@@ -626,53 +810,16 @@ pub fn run() {
                 }
                 // 处理通过自定义 URL 协议触发的打开事件（例如 clihub://...）
                 RunEvent::Opened { urls } => {
-                    if let Some(url) = urls.first() {
-                        let url_str = url.to_string();
-                        log::info!("RunEvent::Opened with URL: {url_str}");
-
-                        if url_str.starts_with("clihub://") {
-                            // 解析并广播深链接事件，复用与 single_instance 相同的逻辑
-                            match crate::deeplink::parse_deeplink_url(&url_str) {
-                                Ok(request) => {
-                                    log::info!(
-                                        "Successfully parsed deep link from RunEvent::Opened: resource={}, app={:?}",
-                                        request.resource,
-                                        request.app
-                                    );
-
-                                    if let Err(e) =
-                                        app_handle.emit("deeplink-import", &request)
-                                    {
-                                        log::error!(
-                                            "Failed to emit deep link event from RunEvent::Opened: {e}"
-                                        );
-                                    }
-                                }
-                                Err(e) => {
-                                    log::error!(
-                                        "Failed to parse deep link URL from RunEvent::Opened: {e}"
-                                    );
-
-                                    if let Err(emit_err) = app_handle.emit(
-                                        "deeplink-error",
-                                        serde_json::json!({
-                                            "url": url_str,
-                                            "error": e.to_string()
-                                        }),
-                                    ) {
-                                        log::error!(
-                                            "Failed to emit deep link error event from RunEvent::Opened: {emit_err}"
-                                        );
-                                    }
-                                }
-                            }
+                    // 与 on_open_url 共用同一入口，启动器操作链接在后台执行而不唤起主窗口
+                    for url in &urls {
+                        let url_str = url.as_str();
+                        log::info!(
+                            "RunEvent::Opened with URL: {}",
+                            log_sanitizer::redact(url_str)
+                        );
 
-                            // 确保主窗口可见
-                            if let Some(window) = app_handle.get_webview_window("main") {
-                                let _ = window.unminimize();
-                                let _ = window.show();
-                                let _ = window.set_focus();
-                            }
+                        if handle_deeplink_url(app_handle, url_str, true, "RunEvent::Opened") {
+                            break; // Process only first clihub:// URL
                         }
                     }
                 }
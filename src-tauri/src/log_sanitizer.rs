@@ -0,0 +1,113 @@
+//! 日志脱敏：在写入日志前遮蔽 API Key、Token 等敏感信息
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::borrow::Cow;
+
+/// `apiKey=...`、`ANTHROPIC_AUTH_TOKEN=...`、`?key=...`、`&sig=...` 等键值对
+static KEY_VALUE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)\b([\w-]*(?:api[_-]?key|token|secret|password)|key|sig)=([^&\s"',;]+)"#)
+        .expect("invalid key/value redaction regex")
+});
+
+/// JSON 中的 `"apiKey": "..."`、`"OPENAI_API_KEY": "..."` 等字段
+static JSON_FIELD: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)("[\w-]*(?:api[_-]?key|token|secret|password)"\s*:\s*")[^"]*(")"#)
+        .expect("invalid json field redaction regex")
+});
+
+/// `Authorization: Bearer xxx`
+static BEARER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(bearer\s+)[\w.~+/=-]+").expect("invalid bearer redaction regex")
+});
+
+/// 常见的前缀式密钥，如 `sk-...`、`sk-ant-...`
+static PREFIXED_KEY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(sk|pk|rk)-[\w-]{8,}").expect("invalid prefixed key redaction regex")
+});
+
+/// 遮蔽单个密钥值，只保留首尾各 4 个字符；8 个字符以内全部遮蔽
+pub fn mask_secret(key: &str) -> String {
+    let chars: Vec<char> = key.chars().collect();
+    if chars.len() <= 8 {
+        return "*".repeat(chars.len());
+    }
+    let head: String = chars[..4].iter().collect();
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("{head}…{tail}")
+}
+
+/// 遮蔽文本中已知格式的密钥；不含敏感信息时原样返回
+pub fn redact(input: &str) -> Cow<'_, str> {
+    let mut output = Cow::Borrowed(input);
+
+    for (regex, replacement) in [
+        (&*KEY_VALUE, "${1}=***"),
+        (&*JSON_FIELD, "${1}***${2}"),
+        (&*BEARER, "${1}***"),
+        (&*PREFIXED_KEY, "${1}-***"),
+    ] {
+        let replaced = match regex.replace_all(&output, replacement) {
+            Cow::Owned(replaced) => Some(replaced),
+            Cow::Borrowed(_) => None,
+        };
+        if let Some(replaced) = replaced {
+            output = Cow::Owned(replaced);
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_deeplink_query_params() {
+        let url = "clihub://v1/import?resource=provider&app=claude&name=Test&apiKey=sk-secret-123456&endpoint=https://api.example.com";
+        assert_eq!(
+            redact(url),
+            "clihub://v1/import?resource=provider&app=claude&name=Test&apiKey=***&endpoint=https://api.example.com"
+        );
+        assert_eq!(
+            redact("https://generativelanguage.googleapis.com/v1beta/models?key=AIzaSy123"),
+            "https://generativelanguage.googleapis.com/v1beta/models?key=***"
+        );
+        assert_eq!(
+            redact("clihub://v1/action?id=prompt%3Aclaude%3Areview&sig=9f86d081884c7d65"),
+            "clihub://v1/action?id=prompt%3Aclaude%3Areview&sig=***"
+        );
+    }
+
+    #[test]
+    fn redacts_env_assignments_and_json_fields() {
+        assert_eq!(
+            redact("export ANTHROPIC_AUTH_TOKEN=abc123"),
+            "export ANTHROPIC_AUTH_TOKEN=***"
+        );
+        assert_eq!(
+            redact(r#"{"OPENAI_API_KEY": "abc", "model": "gpt-5"}"#),
+            r#"{"OPENAI_API_KEY": "***", "model": "gpt-5"}"#
+        );
+    }
+
+    #[test]
+    fn redacts_bearer_and_prefixed_keys() {
+        assert_eq!(
+            redact("Authorization: Bearer eyJhbGciOi.abc"),
+            "Authorization: Bearer ***"
+        );
+        assert_eq!(
+            redact("HTTP 401 : invalid key sk-ant-api03-abcdefgh"),
+            "HTTP 401 : invalid key sk-***"
+        );
+    }
+
+    #[test]
+    fn leaves_plain_text_untouched() {
+        let text = "Provider 'packycode' set as current for Claude";
+        assert!(matches!(redact(text), Cow::Borrowed(_)));
+        assert_eq!(redact("sk-short"), "sk-short");
+    }
+}
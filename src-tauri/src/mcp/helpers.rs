@@ -1,4 +1,7 @@
-use serde_json::{json, Value};
+use indexmap::IndexMap;
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::{json, Map, Value};
 use std::collections::HashMap;
 
 use crate::app_config::{AppType, McpConfig, MultiAppConfig};
@@ -133,6 +136,61 @@ pub fn collect_enabled_servers(cfg: &McpConfig) -> HashMap<String, Value> {
     out
 }
 
+/// Position of a server in the user-defined order; ids unknown to the database rank last
+pub(crate) fn server_order_rank(order: &[String], id: &str) -> usize {
+    order.iter().position(|o| o == id).unwrap_or(usize::MAX)
+}
+
+/// Reorder live-config entries to follow `order` (the database sort_index order).
+/// The sort is stable, so entries the database does not know keep their relative order at the end
+pub(crate) fn sort_servers_by_order(servers: &mut IndexMap<String, Value>, order: &[String]) {
+    servers.sort_by(|a, _, b, _| server_order_rank(order, a).cmp(&server_order_rank(order, b)));
+}
+
+/// Enabled servers of the legacy config in id order, for writers without a database order
+pub(crate) fn sorted_enabled_servers(cfg: &McpConfig) -> IndexMap<String, Value> {
+    let mut enabled: IndexMap<String, Value> = collect_enabled_servers(cfg).into_iter().collect();
+    enabled.sort_keys();
+    enabled
+}
+
+/// `mcpServers` entries of a JSON config file in the order they appear in the file
+///
+/// serde_json's `Map` sorts its keys, so the entries are read straight into an `IndexMap`.
+/// A missing or non-object `mcpServers` yields an empty map.
+pub(crate) fn ordered_mcp_servers(text: &str) -> IndexMap<String, Value> {
+    #[derive(Deserialize)]
+    struct Root {
+        #[serde(rename = "mcpServers", default)]
+        servers: Option<IndexMap<String, Value>>,
+    }
+    serde_json::from_str::<Root>(text)
+        .ok()
+        .and_then(|root| root.servers)
+        .unwrap_or_default()
+}
+
+/// Serializes `root` with its `mcpServers` entries in `servers` order; the other keys
+/// keep serde_json's sorted order
+pub(crate) struct OrderedMcpConfig<'a> {
+    pub root: &'a Map<String, Value>,
+    pub servers: &'a IndexMap<String, Value>,
+}
+
+impl Serialize for OrderedMcpConfig<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.root.len()))?;
+        for (key, value) in self.root {
+            if key == "mcpServers" {
+                map.serialize_entry(key, self.servers)?;
+            } else {
+                map.serialize_entry(key, value)?;
+            }
+        }
+        map.end()
+    }
+}
+
 #[allow(dead_code)] // v3.7.0: Old per-app API, retained for future possible migration
 pub fn get_servers_snapshot_for(
     config: &mut MultiAppConfig,
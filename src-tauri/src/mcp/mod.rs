@@ -5,7 +5,12 @@
 mod validation;
 mod toml_convert;
 mod helpers;
+mod transport;
 pub mod sync;
 
 // Re-export only actively used public APIs
 pub use sync::*;
+pub use transport::{convert_transport, McpBridge, McpTransport, McpTransportConversion};
+pub(crate) use validation::validate_server_spec;
+pub(crate) use toml_convert::json_server_to_toml_table;
+pub(crate) use helpers::{ordered_mcp_servers, OrderedMcpConfig};
@@ -4,18 +4,27 @@ use std::collections::HashMap;
 use crate::app_config::{McpApps, McpServer, MultiAppConfig};
 use crate::error::AppError;
 
-use super::super::helpers::collect_enabled_servers;
+use super::super::helpers::{sort_servers_by_order, sorted_enabled_servers};
 use super::super::validation::validate_server_spec;
+use super::RejectedMcpEntry;
 
 /// Project enabled==true items from config.json to ~/.claude.json
 pub fn sync_enabled_to_claude(config: &MultiAppConfig) -> Result<(), AppError> {
-    let enabled = collect_enabled_servers(&config.mcp.claude);
+    let enabled = sorted_enabled_servers(&config.mcp.claude);
     crate::claude_mcp::set_mcp_servers_map(&enabled)
 }
 
 /// Import mcpServers from ~/.claude.json to unified structure (v3.7.0+)
 /// Existing servers will enable Claude app, without overwriting other fields and app states
 pub fn import_from_claude(config: &mut MultiAppConfig) -> Result<usize, AppError> {
+    import_from_claude_with_rejects(config, &mut Vec::new())
+}
+
+/// Same as `import_from_claude`, collecting entries that fail validation into `rejects`
+pub fn import_from_claude_with_rejects(
+    config: &mut MultiAppConfig,
+    rejects: &mut Vec<RejectedMcpEntry>,
+) -> Result<usize, AppError> {
     let text_opt = crate::claude_mcp::read_mcp_json()?;
     let Some(text) = text_opt else { return Ok(0) };
 
@@ -36,6 +45,11 @@ pub fn import_from_claude(config: &mut MultiAppConfig) -> Result<usize, AppError
         if let Err(e) = validate_server_spec(spec) {
             log::warn!("跳过无效 MCP 服务器 '{id}': {e}");
             errors.push(format!("{id}: {e}"));
+            rejects.push(RejectedMcpEntry::new(
+                id,
+                serde_json::to_string_pretty(spec).unwrap_or_default(),
+                &e,
+            ));
             continue;
         }
 
@@ -86,7 +100,7 @@ pub fn sync_single_server_to_claude(
     // Read existing MCP config
     let current = crate::claude_mcp::read_mcp_servers_map()?;
 
-    // Create new HashMap, containing existing servers + current server to sync
+    // Existing servers + current server to sync; a new entry is appended at the end
     let mut updated = current;
     updated.insert(id.to_string(), server_spec.clone());
 
@@ -94,13 +108,33 @@ pub fn sync_single_server_to_claude(
     crate::claude_mcp::set_mcp_servers_map(&updated)
 }
 
+/// Apply several upserts and removals to ~/.claude.json with a single write
+///
+/// Entries are written in `order` (database sort_index order); entries added to the file by
+/// hand keep their relative position after the managed ones.
+pub fn apply_servers_to_claude(
+    upserts: &HashMap<String, Value>,
+    removals: &[String],
+    order: &[String],
+) -> Result<(), AppError> {
+    let mut current = crate::claude_mcp::read_mcp_servers_map()?;
+    for id in removals {
+        current.shift_remove(id);
+    }
+    for (id, spec) in upserts {
+        current.insert(id.clone(), spec.clone());
+    }
+    sort_servers_by_order(&mut current, order);
+    crate::claude_mcp::set_mcp_servers_map(&current)
+}
+
 /// Remove single MCP server from Claude live config
 pub fn remove_server_from_claude(id: &str) -> Result<(), AppError> {
     // Read existing MCP config
     let mut current = crate::claude_mcp::read_mcp_servers_map()?;
 
-    // Remove specified server
-    current.remove(id);
+    // Remove specified server, keeping the order of the rest
+    current.shift_remove(id);
 
     // Write back
     crate::claude_mcp::set_mcp_servers_map(&current)
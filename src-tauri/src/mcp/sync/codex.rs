@@ -4,9 +4,10 @@ use std::collections::HashMap;
 use crate::app_config::{McpApps, McpServer, MultiAppConfig};
 use crate::error::AppError;
 
-use super::super::helpers::collect_enabled_servers;
+use super::super::helpers::{collect_enabled_servers, server_order_rank};
 use super::super::toml_convert::json_server_to_toml_table;
 use super::super::validation::validate_server_spec;
+use super::RejectedMcpEntry;
 
 /// Import MCP from ~/.codex/config.toml to unified structure (v3.7.0+)
 ///
@@ -16,6 +17,14 @@ use super::super::validation::validate_server_spec;
 ///
 /// Existing servers will enable Codex app, without overwriting other fields and app states
 pub fn import_from_codex(config: &mut MultiAppConfig) -> Result<usize, AppError> {
+    import_from_codex_with_rejects(config, &mut Vec::new())
+}
+
+/// Same as `import_from_codex`, collecting entries that fail to parse into `rejects`
+pub fn import_from_codex_with_rejects(
+    config: &mut MultiAppConfig,
+    rejects: &mut Vec<RejectedMcpEntry>,
+) -> Result<usize, AppError> {
     let text = crate::codex_config::read_and_validate_codex_config_text()?;
     if text.trim().is_empty() {
         return Ok(0);
@@ -34,153 +43,28 @@ pub fn import_from_codex(config: &mut MultiAppConfig) -> Result<usize, AppError>
         let mut changed = 0usize;
         for (id, entry_val) in servers_tbl.iter() {
             let Some(entry_tbl) = entry_val.as_table() else {
+                log::warn!("跳过无效 Codex MCP 项 '{id}': 不是 TOML 表");
+                rejects.push(RejectedMcpEntry::new(
+                    id,
+                    format!("{id} = {entry_val}"),
+                    "MCP 条目必须是 TOML 表",
+                ));
                 continue;
             };
 
-            // type defaults to stdio
-            let typ = entry_tbl
-                .get("type")
-                .and_then(|v| v.as_str())
-                .unwrap_or("stdio");
-
-            // Build JSON spec
-            let mut spec = serde_json::Map::new();
-            spec.insert("type".into(), json!(typ));
-
-            // Core fields (fields that need to be manually handled)
-            let core_fields = match typ {
-                "stdio" => vec!["type", "command", "args", "env", "cwd"],
-                "http" | "sse" => vec!["type", "url", "http_headers"],
-                _ => vec!["type"],
-            };
-
-            // 1. Handle core fields (strongly typed)
-            match typ {
-                "stdio" => {
-                    if let Some(cmd) = entry_tbl.get("command").and_then(|v| v.as_str()) {
-                        spec.insert("command".into(), json!(cmd));
-                    }
-                    if let Some(args) = entry_tbl.get("args").and_then(|v| v.as_array()) {
-                        let arr = args
-                            .iter()
-                            .filter_map(|x| x.as_str())
-                            .map(|s| json!(s))
-                            .collect::<Vec<_>>();
-                        if !arr.is_empty() {
-                            spec.insert("args".into(), serde_json::Value::Array(arr));
-                        }
-                    }
-                    if let Some(cwd) = entry_tbl.get("cwd").and_then(|v| v.as_str()) {
-                        if !cwd.trim().is_empty() {
-                            spec.insert("cwd".into(), json!(cwd));
-                        }
-                    }
-                    if let Some(env_tbl) = entry_tbl.get("env").and_then(|v| v.as_table()) {
-                        let mut env_json = serde_json::Map::new();
-                        for (k, v) in env_tbl.iter() {
-                            if let Some(sv) = v.as_str() {
-                                env_json.insert(k.clone(), json!(sv));
-                            }
-                        }
-                        if !env_json.is_empty() {
-                            spec.insert("env".into(), serde_json::Value::Object(env_json));
-                        }
-                    }
-                }
-                "http" | "sse" => {
-                    if let Some(url) = entry_tbl.get("url").and_then(|v| v.as_str()) {
-                        spec.insert("url".into(), json!(url));
-                    }
-                    // Read from http_headers (correct Codex format) or headers (legacy) with priority to http_headers
-                    let headers_tbl = entry_tbl
-                        .get("http_headers")
-                        .and_then(|v| v.as_table())
-                        .or_else(|| entry_tbl.get("headers").and_then(|v| v.as_table()));
-
-                    if let Some(headers_tbl) = headers_tbl {
-                        let mut headers_json = serde_json::Map::new();
-                        for (k, v) in headers_tbl.iter() {
-                            if let Some(sv) = v.as_str() {
-                                headers_json.insert(k.clone(), json!(sv));
-                            }
-                        }
-                        if !headers_json.is_empty() {
-                            spec.insert("headers".into(), serde_json::Value::Object(headers_json));
-                        }
-                    }
-                }
-                _ => {
-                    log::warn!("跳过未知类型 '{typ}' 的 Codex MCP 项 '{id}'");
-                    return changed;
-                }
-            }
-
-            // 2. Handle extended fields and other unknown fields (generic TOML → JSON conversion)
-            for (key, toml_val) in entry_tbl.iter() {
-                // Skip already processed core fields
-                if core_fields.contains(&key.as_str()) {
+            let spec_v = match codex_entry_to_spec(entry_tbl) {
+                Ok(spec_v) => spec_v,
+                Err(e) => {
+                    // Validation: single item failure continues processing
+                    log::warn!("跳过无效 Codex MCP 项 '{id}': {e}");
+                    rejects.push(RejectedMcpEntry::new(
+                        id,
+                        toml::to_string(entry_tbl).unwrap_or_default(),
+                        &e,
+                    ));
                     continue;
                 }
-
-                // Generic TOML value to JSON value conversion
-                let json_val = match toml_val {
-                    toml::Value::String(s) => Some(json!(s)),
-                    toml::Value::Integer(i) => Some(json!(i)),
-                    toml::Value::Float(f) => Some(json!(f)),
-                    toml::Value::Boolean(b) => Some(json!(b)),
-                    toml::Value::Array(arr) => {
-                        // Only support simple type arrays
-                        let json_arr: Vec<serde_json::Value> = arr
-                            .iter()
-                            .filter_map(|item| match item {
-                                toml::Value::String(s) => Some(json!(s)),
-                                toml::Value::Integer(i) => Some(json!(i)),
-                                toml::Value::Float(f) => Some(json!(f)),
-                                toml::Value::Boolean(b) => Some(json!(b)),
-                                _ => None,
-                            })
-                            .collect();
-                        if !json_arr.is_empty() {
-                            Some(serde_json::Value::Array(json_arr))
-                        } else {
-                            log::debug!("跳过复杂数组字段 '{key}' (TOML → JSON)");
-                            None
-                        }
-                    }
-                    toml::Value::Table(tbl) => {
-                        // Shallow table to JSON object (only support string values)
-                        let mut json_obj = serde_json::Map::new();
-                        for (k, v) in tbl.iter() {
-                            if let Some(s) = v.as_str() {
-                                json_obj.insert(k.clone(), json!(s));
-                            }
-                        }
-                        if !json_obj.is_empty() {
-                            Some(serde_json::Value::Object(json_obj))
-                        } else {
-                            log::debug!("跳过复杂对象字段 '{key}' (TOML → JSON)");
-                            None
-                        }
-                    }
-                    toml::Value::Datetime(_) => {
-                        log::debug!("跳过日期时间字段 '{key}' (TOML → JSON)");
-                        None
-                    }
-                };
-
-                if let Some(val) = json_val {
-                    spec.insert(key.clone(), val);
-                    log::debug!("导入扩展字段 '{key}' = {toml_val:?}");
-                }
-            }
-
-            let spec_v = serde_json::Value::Object(spec);
-
-            // Validation: single item failure continues processing
-            if let Err(e) = validate_server_spec(&spec_v) {
-                log::warn!("跳过无效 Codex MCP 项 '{id}': {e}");
-                continue;
-            }
+            };
 
             if let Some(existing) = servers.get_mut(id) {
                 // Already exists: only enable Codex app
@@ -236,6 +120,151 @@ pub fn import_from_codex(config: &mut MultiAppConfig) -> Result<usize, AppError>
     Ok(changed_total)
 }
 
+/// 将 Codex config.toml 中的单个 MCP 条目转换为统一的 JSON 定义并校验
+pub(crate) fn codex_entry_to_spec(entry_tbl: &toml::value::Table) -> Result<Value, AppError> {
+    // type defaults to stdio
+    let typ = entry_tbl
+        .get("type")
+        .and_then(|v| v.as_str())
+        .unwrap_or("stdio");
+
+    // Build JSON spec
+    let mut spec = serde_json::Map::new();
+    spec.insert("type".into(), json!(typ));
+
+    // Core fields (fields that need to be manually handled)
+    let core_fields = match typ {
+        "stdio" => vec!["type", "command", "args", "env", "cwd"],
+        "http" | "sse" => vec!["type", "url", "http_headers"],
+        _ => vec!["type"],
+    };
+
+    // 1. Handle core fields (strongly typed)
+    match typ {
+        "stdio" => {
+            if let Some(cmd) = entry_tbl.get("command").and_then(|v| v.as_str()) {
+                spec.insert("command".into(), json!(cmd));
+            }
+            if let Some(args) = entry_tbl.get("args").and_then(|v| v.as_array()) {
+                let arr = args
+                    .iter()
+                    .filter_map(|x| x.as_str())
+                    .map(|s| json!(s))
+                    .collect::<Vec<_>>();
+                if !arr.is_empty() {
+                    spec.insert("args".into(), serde_json::Value::Array(arr));
+                }
+            }
+            if let Some(cwd) = entry_tbl.get("cwd").and_then(|v| v.as_str()) {
+                if !cwd.trim().is_empty() {
+                    spec.insert("cwd".into(), json!(cwd));
+                }
+            }
+            if let Some(env_tbl) = entry_tbl.get("env").and_then(|v| v.as_table()) {
+                let mut env_json = serde_json::Map::new();
+                for (k, v) in env_tbl.iter() {
+                    if let Some(sv) = v.as_str() {
+                        env_json.insert(k.clone(), json!(sv));
+                    }
+                }
+                if !env_json.is_empty() {
+                    spec.insert("env".into(), serde_json::Value::Object(env_json));
+                }
+            }
+        }
+        "http" | "sse" => {
+            if let Some(url) = entry_tbl.get("url").and_then(|v| v.as_str()) {
+                spec.insert("url".into(), json!(url));
+            }
+            // Read from http_headers (correct Codex format) or headers (legacy) with priority to http_headers
+            let headers_tbl = entry_tbl
+                .get("http_headers")
+                .and_then(|v| v.as_table())
+                .or_else(|| entry_tbl.get("headers").and_then(|v| v.as_table()));
+
+            if let Some(headers_tbl) = headers_tbl {
+                let mut headers_json = serde_json::Map::new();
+                for (k, v) in headers_tbl.iter() {
+                    if let Some(sv) = v.as_str() {
+                        headers_json.insert(k.clone(), json!(sv));
+                    }
+                }
+                if !headers_json.is_empty() {
+                    spec.insert("headers".into(), serde_json::Value::Object(headers_json));
+                }
+            }
+        }
+        _ => {
+            return Err(AppError::McpValidation(format!(
+                "未知的 Codex MCP 类型 '{typ}'"
+            )));
+        }
+    }
+
+    // 2. Handle extended fields and other unknown fields (generic TOML → JSON conversion)
+    for (key, toml_val) in entry_tbl.iter() {
+        // Skip already processed core fields
+        if core_fields.contains(&key.as_str()) {
+            continue;
+        }
+
+        // Generic TOML value to JSON value conversion
+        let json_val = match toml_val {
+            toml::Value::String(s) => Some(json!(s)),
+            toml::Value::Integer(i) => Some(json!(i)),
+            toml::Value::Float(f) => Some(json!(f)),
+            toml::Value::Boolean(b) => Some(json!(b)),
+            toml::Value::Array(arr) => {
+                // Only support simple type arrays
+                let json_arr: Vec<serde_json::Value> = arr
+                    .iter()
+                    .filter_map(|item| match item {
+                        toml::Value::String(s) => Some(json!(s)),
+                        toml::Value::Integer(i) => Some(json!(i)),
+                        toml::Value::Float(f) => Some(json!(f)),
+                        toml::Value::Boolean(b) => Some(json!(b)),
+                        _ => None,
+                    })
+                    .collect();
+                if !json_arr.is_empty() {
+                    Some(serde_json::Value::Array(json_arr))
+                } else {
+                    log::debug!("跳过复杂数组字段 '{key}' (TOML → JSON)");
+                    None
+                }
+            }
+            toml::Value::Table(tbl) => {
+                // Shallow table to JSON object (only support string values)
+                let mut json_obj = serde_json::Map::new();
+                for (k, v) in tbl.iter() {
+                    if let Some(s) = v.as_str() {
+                        json_obj.insert(k.clone(), json!(s));
+                    }
+                }
+                if !json_obj.is_empty() {
+                    Some(serde_json::Value::Object(json_obj))
+                } else {
+                    log::debug!("跳过复杂对象字段 '{key}' (TOML → JSON)");
+                    None
+                }
+            }
+            toml::Value::Datetime(_) => {
+                log::debug!("跳过日期时间字段 '{key}' (TOML → JSON)");
+                None
+            }
+        };
+
+        if let Some(val) = json_val {
+            spec.insert(key.clone(), val);
+            log::debug!("导入扩展字段 '{key}' = {toml_val:?}");
+        }
+    }
+
+    let spec_v = serde_json::Value::Object(spec);
+    validate_server_spec(&spec_v)?;
+    Ok(spec_v)
+}
+
 /// Write enabled==true items from config.json to ~/.codex/config.toml in TOML format
 ///
 /// Format strategy:
@@ -300,6 +329,7 @@ pub fn sync_enabled_to_codex(config: &MultiAppConfig) -> Result<(), AppError> {
     // 6) Write back (only change TOML, do not touch auth.json); toml_edit will try to preserve comments/whitespace/order in unchanged areas
     let new_text = doc.to_string();
     let path = crate::codex_config::get_codex_config_path();
+    crate::codex_config::backup_codex_config()?;
     crate::config::write_text_file(&path, &new_text)?;
     Ok(())
 }
@@ -316,9 +346,8 @@ pub fn sync_single_server_to_codex(
     // Read existing config.toml
     let config_path = crate::codex_config::get_codex_config_path();
 
-    let mut doc = if config_path.exists() {
-        let content =
-            std::fs::read_to_string(&config_path).map_err(|e| AppError::io(&config_path, e))?;
+    let mut doc = if crate::config::file_exists(&config_path) {
+        let content = crate::config::read_text_file(&config_path)?;
         content
             .parse::<toml_edit::DocumentMut>()
             .map_err(|e| AppError::McpValidation(format!("解析 Codex config.toml 失败: {e}")))?
@@ -348,22 +377,135 @@ pub fn sync_single_server_to_codex(
     doc["mcp_servers"][id] = Item::Table(toml_table);
 
     // Write back file
-    std::fs::write(&config_path, doc.to_string()).map_err(|e| AppError::io(&config_path, e))?;
+    crate::codex_config::backup_codex_config()?;
+    crate::config::write_text_file(&config_path, &doc.to_string())?;
+
+    Ok(())
+}
+
+/// Apply several upserts and removals to the Codex [mcp_servers] table with a single write
+///
+/// Entries whose content already matches are left untouched (keeping their comments and
+/// layout), and the file is not rewritten at all when nothing changed. Tables are emitted in
+/// `order` (database sort_index order), entries unknown to the database stay after them.
+pub fn apply_servers_to_codex(
+    upserts: &HashMap<String, Value>,
+    removals: &[String],
+    order: &[String],
+) -> Result<(), AppError> {
+    use toml_edit::Item;
+
+    let config_path = crate::codex_config::get_codex_config_path();
+    let original = if crate::config::file_exists(&config_path) {
+        crate::config::read_text_file(&config_path)?
+    } else {
+        String::new()
+    };
+    let mut doc = original
+        .parse::<toml_edit::DocumentMut>()
+        .map_err(|e| AppError::McpValidation(format!("解析 Codex config.toml 失败: {e}")))?;
+
+    if let Some(servers) = doc.get_mut("mcp_servers").and_then(|s| s.as_table_mut()) {
+        for id in removals {
+            servers.remove(id);
+        }
+    }
+
+    if !upserts.is_empty() {
+        // Drop the incorrect [mcp.servers] layout, as the single-server sync does
+        if let Some(tbl) = doc.get_mut("mcp").and_then(|m| m.as_table_like_mut()) {
+            if tbl.remove("servers").is_some() {
+                log::warn!("检测到错误的 MCP 格式 [mcp.servers]，正在清理并迁移到 [mcp_servers]");
+            }
+        }
+        if !doc.contains_key("mcp_servers") {
+            doc["mcp_servers"] = toml_edit::table();
+        }
+        let mut ids: Vec<_> = upserts.keys().collect();
+        ids.sort();
+        for id in ids {
+            match json_server_to_toml_table(&upserts[id]) {
+                Ok(table) if same_entry(doc["mcp_servers"].get(id.as_str()), &table) => {}
+                Ok(table) => doc["mcp_servers"][id.as_str()] = Item::Table(table),
+                Err(err) => log::error!("跳过无效的 MCP 服务器 '{id}': {err}"),
+            }
+        }
+    }
 
+    if let Some(servers) = doc.get_mut("mcp_servers").and_then(|s| s.as_table_mut()) {
+        reorder_codex_servers(servers, order);
+    }
+
+    let new_text = doc.to_string();
+    if new_text == original {
+        return Ok(());
+    }
+    crate::codex_config::backup_codex_config()?;
+    crate::config::write_text_file(&config_path, &new_text)?;
     Ok(())
 }
 
+/// Reorder [mcp_servers.<id>] tables to follow `order`
+///
+/// toml_edit renders tables by document position, so besides sorting the items every server
+/// table (and its nested tables such as `.env`) is pinned to the same position; equal
+/// positions keep the item order. Nothing is touched when the order already matches.
+fn reorder_codex_servers(servers: &mut toml_edit::Table, order: &[String]) {
+    let rank = |key: &str| server_order_rank(order, key);
+    let ranks: Vec<usize> = servers.iter().map(|(key, _)| rank(key)).collect();
+    if ranks.windows(2).all(|w| w[0] <= w[1]) {
+        return;
+    }
+
+    servers.sort_values_by(|a, _, b, _| rank(a.get()).cmp(&rank(b.get())));
+
+    fn pin(table: &mut toml_edit::Table, position: usize) {
+        table.set_position(position);
+        for (_, item) in table.iter_mut() {
+            if let Some(child) = item.as_table_mut() {
+                if !child.is_dotted() {
+                    pin(child, position);
+                }
+            }
+        }
+    }
+    let position = servers
+        .iter()
+        .filter_map(|(_, item)| item.as_table().and_then(toml_edit::Table::position))
+        .min()
+        .or(servers.position())
+        .unwrap_or(0);
+    for (_, item) in servers.iter_mut() {
+        if let Some(table) = item.as_table_mut() {
+            pin(table, position);
+        }
+    }
+}
+
+/// Compare an existing [mcp_servers.<id>] entry with a freshly converted table by value,
+/// ignoring formatting, key order and comments
+fn same_entry(existing: Option<&toml_edit::Item>, table: &toml_edit::Table) -> bool {
+    let Some(current) = existing.and_then(toml_edit::Item::as_table) else {
+        return false;
+    };
+    let as_value = |t: &toml_edit::Table| {
+        let mut doc = toml_edit::DocumentMut::new();
+        doc["entry"] = toml_edit::Item::Table(t.clone());
+        toml::from_str::<toml::Table>(&doc.to_string()).ok()
+    };
+    matches!((as_value(current), as_value(table)), (Some(a), Some(b)) if a == b)
+}
+
 /// Remove single MCP server from Codex live config
 /// Delete from correct [mcp_servers] table, and clean data that may exist in incorrect location [mcp.servers]
 pub fn remove_server_from_codex(id: &str) -> Result<(), AppError> {
     let config_path = crate::codex_config::get_codex_config_path();
 
-    if !config_path.exists() {
+    if !crate::config::file_exists(&config_path) {
         return Ok(()); // File does not exist, no need to delete
     }
 
-    let content =
-        std::fs::read_to_string(&config_path).map_err(|e| AppError::io(&config_path, e))?;
+    let content = crate::config::read_text_file(&config_path)?;
 
     let mut doc = content
         .parse::<toml_edit::DocumentMut>()
@@ -384,7 +526,8 @@ pub fn remove_server_from_codex(id: &str) -> Result<(), AppError> {
     }
 
     // Write back file
-    std::fs::write(&config_path, doc.to_string()).map_err(|e| AppError::io(&config_path, e))?;
+    crate::codex_config::backup_codex_config()?;
+    crate::config::write_text_file(&config_path, &doc.to_string())?;
 
     Ok(())
 }
@@ -9,3 +9,23 @@ mod gemini;
 pub use claude::*;
 pub use codex::*;
 pub use gemini::*;
+
+/// MCP entry that failed to parse or validate during import.
+/// The raw content is kept so it can be corrected and retried.
+#[derive(Debug, Clone)]
+pub struct RejectedMcpEntry {
+    pub server_id: String,
+    /// Raw JSON (Claude/Gemini) or TOML fragment (Codex)
+    pub raw: String,
+    pub error: String,
+}
+
+impl RejectedMcpEntry {
+    pub(crate) fn new(server_id: &str, raw: String, error: impl ToString) -> Self {
+        Self {
+            server_id: server_id.to_string(),
+            raw,
+            error: error.to_string(),
+        }
+    }
+}
@@ -0,0 +1,341 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::claude_mcp::validate_command_in_path;
+use crate::error::AppError;
+
+use super::validation::validate_server_spec;
+
+/// Transport a CLI expects for an MCP server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum McpTransport {
+    Stdio,
+    Http,
+    Sse,
+}
+
+impl McpTransport {
+    fn of(spec: &Value) -> Self {
+        match spec.get("type").and_then(Value::as_str) {
+            Some("http") => Self::Http,
+            Some("sse") => Self::Sse,
+            _ => Self::Stdio,
+        }
+    }
+}
+
+/// Bridge tool used to translate between transports.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum McpBridge {
+    /// `npx -y supergateway`
+    #[default]
+    Supergateway,
+    /// `uvx mcp-proxy`
+    McpProxy,
+}
+
+impl McpBridge {
+    fn launcher(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Supergateway => ("npx", "supergateway"),
+            Self::McpProxy => ("uvx", "mcp-proxy"),
+        }
+    }
+}
+
+/// Availability of a binary the converted server depends on.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequiredBinary {
+    pub name: String,
+    pub available: bool,
+}
+
+/// Result of converting a server spec to another transport.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpTransportConversion {
+    /// Spec to write into the CLI's config
+    pub spec: Value,
+    /// When exposing a stdio server over HTTP, the gateway must be started separately
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub launch_command: Option<String>,
+    /// Environment the gateway needs (copied from the stdio spec)
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub launch_env: Map<String, Value>,
+    pub required_binaries: Vec<RequiredBinary>,
+}
+
+/// Port used when exposing a stdio server and no port is given.
+const DEFAULT_GATEWAY_PORT: u16 = 8000;
+
+/// Convert a server spec to the target transport.
+///
+/// - stdio → http/sse: the returned spec points at a local gateway and
+///   `launch_command` starts that gateway around the original command.
+/// - http/sse → stdio: the returned spec runs the bridge as a stdio server that
+///   forwards to the remote URL (headers are passed through).
+/// - http ⇄ sse is not bridged; both sides speak HTTP and need a server-side change.
+pub fn convert_transport(
+    spec: &Value,
+    target: McpTransport,
+    bridge: McpBridge,
+    port: Option<u16>,
+) -> Result<McpTransportConversion, AppError> {
+    validate_server_spec(spec)?;
+    let source = McpTransport::of(spec);
+
+    let (launcher, _) = bridge.launcher();
+    let mut binaries = vec![launcher.to_string()];
+
+    let mut conversion = match (source, target) {
+        (from, to) if from == to => McpTransportConversion {
+            spec: spec.clone(),
+            launch_command: None,
+            launch_env: Map::new(),
+            required_binaries: Vec::new(),
+        },
+        (McpTransport::Stdio, to) => {
+            let command = spec["command"].as_str().unwrap_or_default().to_string();
+            binaries.push(command.clone());
+            let port = port.unwrap_or(DEFAULT_GATEWAY_PORT);
+            let (launch, url) = expose_stdio(bridge, &stdio_command_line(spec), to, port);
+            McpTransportConversion {
+                spec: json!({ "type": to, "url": url }),
+                launch_command: Some(launch),
+                launch_env: spec
+                    .get("env")
+                    .and_then(Value::as_object)
+                    .cloned()
+                    .unwrap_or_default(),
+                required_binaries: Vec::new(),
+            }
+        }
+        (from, McpTransport::Stdio) => {
+            let url = spec["url"].as_str().unwrap_or_default();
+            let headers = spec
+                .get("headers")
+                .and_then(Value::as_object)
+                .map(|h| {
+                    h.iter()
+                        .filter_map(|(k, v)| v.as_str().map(|v| (k.as_str(), v)))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            McpTransportConversion {
+                spec: bridge_remote(bridge, from, url, &headers),
+                launch_command: None,
+                launch_env: Map::new(),
+                required_binaries: Vec::new(),
+            }
+        }
+        (from, to) => {
+            return Err(AppError::McpValidation(format!(
+                "不支持在 {} 与 {} 之间转换，请改用服务端提供的对应端点",
+                transport_name(from),
+                transport_name(to)
+            )))
+        }
+    };
+
+    if source != target {
+        conversion.required_binaries = check_binaries(&binaries);
+    }
+    Ok(conversion)
+}
+
+fn transport_name(transport: McpTransport) -> &'static str {
+    match transport {
+        McpTransport::Stdio => "stdio",
+        McpTransport::Http => "http",
+        McpTransport::Sse => "sse",
+    }
+}
+
+fn check_binaries(names: &[String]) -> Vec<RequiredBinary> {
+    names
+        .iter()
+        .map(|name| RequiredBinary {
+            name: name.clone(),
+            available: validate_command_in_path(name).unwrap_or(false),
+        })
+        .collect()
+}
+
+/// Build the gateway command for a stdio server and the URL the CLI should use.
+fn expose_stdio(
+    bridge: McpBridge,
+    command_line: &str,
+    target: McpTransport,
+    port: u16,
+) -> (String, String) {
+    let (launcher, package) = bridge.launcher();
+    let streamable = target == McpTransport::Http;
+    match bridge {
+        McpBridge::Supergateway => {
+            let mut launch = format!(
+                "{launcher} -y {package} --stdio {} --port {port}",
+                shell_quote(command_line)
+            );
+            if streamable {
+                launch.push_str(" --outputTransport streamableHttp");
+            }
+            let path = if streamable { "mcp" } else { "sse" };
+            (launch, format!("http://localhost:{port}/{path}"))
+        }
+        McpBridge::McpProxy => {
+            // mcp-proxy serves both /sse and /mcp
+            let launch = format!("{launcher} {package} --port={port} -- {command_line}");
+            let path = if streamable { "mcp" } else { "sse" };
+            (launch, format!("http://localhost:{port}/{path}"))
+        }
+    }
+}
+
+/// Build a stdio spec that forwards to a remote HTTP/SSE server.
+fn bridge_remote(
+    bridge: McpBridge,
+    source: McpTransport,
+    url: &str,
+    headers: &[(&str, &str)],
+) -> Value {
+    let (launcher, package) = bridge.launcher();
+    let mut args: Vec<String> = Vec::new();
+    match bridge {
+        McpBridge::Supergateway => {
+            args.extend(["-y".to_string(), package.to_string()]);
+            let flag = if source == McpTransport::Sse {
+                "--sse"
+            } else {
+                "--streamableHttp"
+            };
+            args.extend([flag.to_string(), url.to_string()]);
+            for (key, value) in headers {
+                args.extend(["--header".to_string(), format!("{key}: {value}")]);
+            }
+        }
+        McpBridge::McpProxy => {
+            args.push(package.to_string());
+            if source == McpTransport::Http {
+                args.extend(["--transport".to_string(), "streamablehttp".to_string()]);
+            }
+            for (key, value) in headers {
+                args.extend(["-H".to_string(), key.to_string(), value.to_string()]);
+            }
+            args.push(url.to_string());
+        }
+    }
+    json!({ "type": "stdio", "command": launcher, "args": args })
+}
+
+/// Join a stdio spec's command and args into a single shell command line.
+fn stdio_command_line(spec: &Value) -> String {
+    let command = spec["command"].as_str().unwrap_or_default();
+    let args = spec
+        .get("args")
+        .and_then(Value::as_array)
+        .map(|args| args.iter().filter_map(Value::as_str).collect::<Vec<_>>())
+        .unwrap_or_default();
+    std::iter::once(command)
+        .chain(args)
+        .map(shell_quote)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn shell_quote(arg: &str) -> String {
+    let safe = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c));
+    if safe {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stdio_server_is_exposed_through_gateway() {
+        let spec = json!({
+            "type": "stdio",
+            "command": "npx",
+            "args": ["-y", "@modelcontextprotocol/server-filesystem", "/tmp/my dir"],
+            "env": { "TOKEN": "x" }
+        });
+        let conversion = convert_transport(
+            &spec,
+            McpTransport::Http,
+            McpBridge::Supergateway,
+            Some(9000),
+        )
+        .unwrap();
+
+        assert_eq!(
+            conversion.spec,
+            json!({ "type": "http", "url": "http://localhost:9000/mcp" })
+        );
+        assert_eq!(
+            conversion.launch_command.as_deref(),
+            Some("npx -y supergateway --stdio 'npx -y @modelcontextprotocol/server-filesystem '\\''/tmp/my dir'\\''' --port 9000 --outputTransport streamableHttp")
+        );
+        assert_eq!(conversion.launch_env["TOKEN"], "x");
+        let names: Vec<_> = conversion
+            .required_binaries
+            .iter()
+            .map(|b| b.name.as_str())
+            .collect();
+        assert_eq!(names, ["npx", "npx"]);
+    }
+
+    #[test]
+    fn remote_server_is_bridged_to_stdio() {
+        let spec = json!({
+            "type": "sse",
+            "url": "https://mcp.example.com/sse",
+            "headers": { "Authorization": "Bearer t" }
+        });
+
+        let conversion =
+            convert_transport(&spec, McpTransport::Stdio, McpBridge::Supergateway, None).unwrap();
+        assert_eq!(
+            conversion.spec,
+            json!({
+                "type": "stdio",
+                "command": "npx",
+                "args": ["-y", "supergateway", "--sse", "https://mcp.example.com/sse", "--header", "Authorization: Bearer t"]
+            })
+        );
+
+        let conversion =
+            convert_transport(&spec, McpTransport::Stdio, McpBridge::McpProxy, None).unwrap();
+        assert_eq!(conversion.spec["command"], "uvx");
+        assert_eq!(
+            conversion.spec["args"],
+            json!([
+                "mcp-proxy",
+                "-H",
+                "Authorization",
+                "Bearer t",
+                "https://mcp.example.com/sse"
+            ])
+        );
+    }
+
+    #[test]
+    fn same_transport_is_a_no_op_and_http_sse_is_rejected() {
+        let spec = json!({ "type": "http", "url": "https://mcp.example.com/mcp" });
+        let conversion =
+            convert_transport(&spec, McpTransport::Http, McpBridge::default(), None).unwrap();
+        assert_eq!(conversion.spec, spec);
+        assert!(conversion.required_binaries.is_empty());
+
+        assert!(convert_transport(&spec, McpTransport::Sse, McpBridge::default(), None).is_err());
+    }
+}
@@ -0,0 +1,81 @@
+//! 后台任务结果的系统通知分发
+//!
+//! 服务层拿不到 AppHandle，因此在 setup 阶段登记一次；未登记（如测试环境）时静默忽略，调用方照常记录日志。
+
+use std::sync::OnceLock;
+
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+
+pub use crate::settings::NotificationCategory;
+
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+/// 登记用于发送通知的 AppHandle
+pub fn init(app: &AppHandle) {
+    let _ = APP_HANDLE.set(app.clone());
+}
+
+/// 通知内容（按界面语言选择中英文）
+pub struct NotificationText {
+    pub zh: String,
+    pub en: String,
+}
+
+impl NotificationText {
+    pub fn new(zh: impl Into<String>, en: impl Into<String>) -> Self {
+        Self {
+            zh: zh.into(),
+            en: en.into(),
+        }
+    }
+}
+
+fn title(category: NotificationCategory, success: bool, english: bool) -> &'static str {
+    use NotificationCategory::*;
+    match (category, success, english) {
+        (FirstImport, _, false) => "CLI Hub 已导入现有配置",
+        (FirstImport, _, true) => "CLI Hub imported existing configs",
+        (Migration, true, false) => "配置迁移完成",
+        (Migration, true, true) => "Config migration finished",
+        (Migration, false, false) => "配置迁移失败",
+        (Migration, false, true) => "Config migration failed",
+        (Backup, true, false) => "外部备份完成",
+        (Backup, true, true) => "External backup finished",
+        (Backup, false, false) => "外部备份失败",
+        (Backup, false, true) => "External backup failed",
+        (McpSync, _, false) => "MCP 同步存在问题",
+        (McpSync, _, true) => "MCP sync needs attention",
+        (AccessWindow, _, false) => "供应商已自动切换",
+        (AccessWindow, _, true) => "Provider switched automatically",
+        (EndpointFailover, _, false) => "已切换到备用端点",
+        (EndpointFailover, _, true) => "Switched to a backup endpoint",
+        (PromptSchedule, _, false) => "提示词已按计划切换",
+        (PromptSchedule, _, true) => "Prompt switched on schedule",
+    }
+}
+
+/// 发送一条后台结果通知；总开关关闭或该分类被单独关闭时忽略
+pub fn notify(category: NotificationCategory, success: bool, body: NotificationText) {
+    if !crate::settings::notification_allowed(category) {
+        return;
+    }
+    let Some(app) = APP_HANDLE.get() else {
+        return;
+    };
+
+    let english = crate::settings::get_effective_settings()
+        .language
+        .as_deref()
+        == Some("en");
+    let body = if english { body.en } else { body.zh };
+    if let Err(e) = app
+        .notification()
+        .builder()
+        .title(title(category, success, english))
+        .body(body)
+        .show()
+    {
+        log::warn!("发送系统通知失败: {e}");
+    }
+}
@@ -13,4 +13,43 @@ pub struct Prompt {
     pub created_at: Option<i64>,
     #[serde(rename = "updatedAt", skip_serializing_if = "Option::is_none")]
     pub updated_at: Option<i64>,
+    /// 乐观锁版本号，由数据库维护；编辑保存时原样带回，用于检测并发修改
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<i64>,
+}
+
+/// 不含正文的提示词摘要，用于大量提示词时的列表展示
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptSummary {
+    pub id: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(rename = "createdAt", skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<i64>,
+    #[serde(rename = "updatedAt", skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<i64>,
+    /// 正文字符数
+    #[serde(rename = "contentLength")]
+    pub content_length: i64,
+    /// 正文已加密；此时 content_length 为密文长度
+    #[serde(default)]
+    pub encrypted: bool,
+}
+
+/// Gemini 额外上下文文件（与 GEMINI.md 一同通过 contextFileName 加载）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeminiContextFile {
+    /// 文件名，如 `CONVENTIONS.md`，写入到 `~/.gemini/` 下
+    #[serde(rename = "fileName")]
+    pub file_name: String,
+    pub content: String,
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(rename = "createdAt", skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<i64>,
+    #[serde(rename = "updatedAt", skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<i64>,
 }
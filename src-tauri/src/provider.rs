@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 
+use crate::usage_format::UsageDisplay;
+
 // SSOT 模式：不再写供应商副本文件
 
 /// 供应商结构体
@@ -36,6 +38,21 @@ pub struct Provider {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "iconColor")]
     pub icon_color: Option<String>,
+    /// 最近一次切换为当前供应商的时间（由数据库维护，保存时忽略）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "lastSwitchedAt")]
+    pub last_switched_at: Option<i64>,
+    /// 累计切换次数（由数据库维护，保存时忽略）
+    #[serde(default, skip_serializing_if = "is_zero")]
+    #[serde(rename = "switchCount")]
+    pub switch_count: u32,
+    /// 乐观锁版本号（由数据库维护），编辑保存时原样带回
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<i64>,
+}
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
 }
 
 impl Provider {
@@ -58,6 +75,9 @@ impl Provider {
             meta: None,
             icon: None,
             icon_color: None,
+            last_switched_at: None,
+            switch_count: 0,
+            version: None,
         }
     }
 }
@@ -99,9 +119,14 @@ pub struct UsageScript {
     pub auto_query_interval: Option<u64>,
 }
 
-/// 用量数据
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 用量数据当前的结构版本；未声明版本的脚本输出按 v1 解析
+pub const USAGE_SCHEMA_VERSION: u32 = 2;
+
+/// 用量数据（v2），由脚本输出规范化而来
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct UsageData {
+    #[serde(default, rename = "schemaVersion")]
+    pub schema_version: u32,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "planName")]
     pub plan_name: Option<String>,
@@ -121,6 +146,39 @@ pub struct UsageData {
     pub remaining: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub unit: Option<String>,
+    /// ISO 4217 货币代码（大写），如 `USD`、`CNY`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    /// 额度重置时间（Unix 秒）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "resetAt")]
+    pub reset_at: Option<i64>,
+    /// 额度分桶（如每日、每月）；v1 输出会规范化为单个分桶
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub buckets: Vec<UsageBucket>,
+    /// 按界面语言格式化的文本，查询成功后由后端填充
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display: Option<UsageDisplay>,
+}
+
+/// 单个额度分桶
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageBucket {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub used: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remaining: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "resetAt")]
+    pub reset_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display: Option<UsageDisplay>,
 }
 
 /// 用量查询结果（支持多套餐）
@@ -151,6 +209,232 @@ pub struct ProviderMeta {
         skip_serializing_if = "Option::is_none"
     )]
     pub partner_promotion_key: Option<String>,
+    /// 切换时写入 live 配置的范围（缺省为完整配置）
+    #[serde(rename = "syncScope", skip_serializing_if = "Option::is_none")]
+    pub sync_scope: Option<SyncScope>,
+    /// Claude 接入方式（直连 / Bedrock / Vertex），缺省为直连
+    #[serde(rename = "claudeFlavor", skip_serializing_if = "Option::is_none")]
+    pub claude_flavor: Option<ClaudeFlavorConfig>,
+    /// 本地模型（Ollama）供应商，切换前会检查本地端点
+    #[serde(rename = "localModel", skip_serializing_if = "Option::is_none")]
+    pub local_model: Option<LocalModelConfig>,
+    /// 最近一次凭证探测结果，仅在探测到失效后记录
+    #[serde(rename = "credentialStatus", skip_serializing_if = "Option::is_none")]
+    pub credential_status: Option<CredentialStatus>,
+    /// 最近一次测速结果，用于按延迟排序
+    #[serde(rename = "latency", skip_serializing_if = "Option::is_none")]
+    pub latency: Option<ProviderLatency>,
+    /// 文档链接（按端点域名从中转目录补全）
+    #[serde(rename = "docsUrl", skip_serializing_if = "Option::is_none")]
+    pub docs_url: Option<String>,
+    /// 建议使用的用量查询模板键（如 `newapi`），配置用量脚本时预选
+    #[serde(rename = "usagePreset", skip_serializing_if = "Option::is_none")]
+    pub usage_preset: Option<String>,
+    /// Codex 认证方式（API Key / ChatGPT 登录），缺省时按 auth 内容推断
+    #[serde(rename = "codexAuthMode", skip_serializing_if = "Option::is_none")]
+    pub codex_auth_mode: Option<CodexAuthMode>,
+    /// 可用时段，时段外切换需要强制确认
+    #[serde(rename = "accessWindow", skip_serializing_if = "Option::is_none")]
+    pub access_window: Option<AccessWindow>,
+    /// 本地调试代理，启用后 live 配置指向本机代理并记录请求元数据
+    #[serde(rename = "debugProxy", skip_serializing_if = "Option::is_none")]
+    pub debug_proxy: Option<DebugProxyConfig>,
+    /// 最近一次模型一致性检查结果
+    #[serde(rename = "modelFidelity", skip_serializing_if = "Option::is_none")]
+    pub model_fidelity: Option<ModelFidelity>,
+    /// 由组织统一下发的供应商：可以切换，但不能在本地编辑或删除
+    #[serde(rename = "managedBy", skip_serializing_if = "Option::is_none")]
+    pub managed_by: Option<String>,
+    /// 首次导入时从环境变量或 1Password 生成、尚待用户确认的草稿
+    #[serde(rename = "pendingReview", skip_serializing_if = "Option::is_none")]
+    pub pending_review: Option<DraftSource>,
+    /// 最近一次切换、健康检查或用量查询失败的记录，完整历史见 `provider_errors` 表
+    #[serde(rename = "lastError", skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<ProviderErrorNote>,
+}
+
+/// 记录失败时正在执行的操作
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ProviderOperation {
+    Switch,
+    HealthCheck,
+    Usage,
+}
+
+impl ProviderOperation {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ProviderOperation::Switch => "switch",
+            ProviderOperation::HealthCheck => "healthCheck",
+            ProviderOperation::Usage => "usage",
+        }
+    }
+}
+
+/// 供应商的一次失败记录
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderErrorNote {
+    pub operation: ProviderOperation,
+    pub message: String,
+    /// Unix 毫秒
+    pub occurred_at: i64,
+}
+
+/// 草稿供应商的来源
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum DraftSource {
+    /// shell 环境变量（ANTHROPIC_* / OPENAI_*）
+    Env,
+    /// 1Password CLI（op）读取的条目
+    OnePassword,
+}
+
+/// Live 配置同步范围模式
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SyncScopeMode {
+    /// 完整覆盖 live 配置（默认行为）
+    #[default]
+    Full,
+    /// 仅同步凭证（API Key / Base URL），保留 live 中的其他设置
+    Credentials,
+    /// 仅同步 keys 中列出的字段
+    Custom,
+}
+
+/// 供应商级别的 live 同步范围
+///
+/// keys 为相对 settingsConfig 的点分路径，例如 `env.ANTHROPIC_MODEL`、
+/// `auth.OPENAI_API_KEY`、`config.model`（Codex 的 config 指 config.toml）
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncScope {
+    #[serde(default)]
+    pub mode: SyncScopeMode,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keys: Vec<String>,
+}
+
+/// Claude 供应商的接入方式
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ClaudeFlavor {
+    /// Anthropic API 或兼容端点（Base URL + Key）
+    #[default]
+    Direct,
+    /// Amazon Bedrock（CLAUDE_CODE_USE_BEDROCK）
+    Bedrock,
+    /// Google Vertex AI（CLAUDE_CODE_USE_VERTEX）
+    Vertex,
+}
+
+/// Claude 接入方式及其区域/项目设置，切换时生成对应的 env
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeFlavorConfig {
+    #[serde(default)]
+    pub flavor: ClaudeFlavor,
+    /// Bedrock 对应 AWS_REGION，Vertex 对应 CLOUD_ML_REGION
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    /// Vertex 项目 ID（ANTHROPIC_VERTEX_PROJECT_ID）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    /// Bedrock 使用的 AWS profile（AWS_PROFILE）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aws_profile: Option<String>,
+}
+
+/// Codex 供应商的认证方式
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum CodexAuthMode {
+    /// auth.json 中的 OPENAI_API_KEY
+    #[default]
+    ApiKey,
+    /// `codex login` 生成的 ChatGPT 账号令牌，整个 auth.json 原样保存
+    ChatgptLogin,
+}
+
+/// 本地模型供应商配置
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalModelConfig {
+    /// Ollama 地址，例如 http://localhost:11434
+    pub base_url: String,
+    pub model: String,
+    /// Claude 使用时的 Anthropic 兼容代理地址
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy_url: Option<String>,
+}
+
+/// 请求模型与上游实际返回模型的比对结果
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelFidelity {
+    pub requested_model: String,
+    pub served_model: String,
+    /// 中转返回了其他模型（日期后缀等版本差异不算）
+    pub mismatch: bool,
+    pub checked_at: i64,
+    pub source: ModelFidelitySource,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ModelFidelitySource {
+    /// 手动或定期发送的测试请求
+    Probe,
+    /// 调试代理观测到的真实请求
+    Proxy,
+}
+
+/// 供应商级调试代理配置
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DebugProxyConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// 同时记录请求与响应正文（各截断到 64 KiB），默认只记录元数据
+    #[serde(default)]
+    pub capture_bodies: bool,
+}
+
+/// 供应商可用时段（本地时间），例如仅在工作日工作时间使用官方 API
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessWindow {
+    /// 允许的星期，1 为周一、7 为周日；为空表示每天
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub days: Vec<u8>,
+    /// 开始时间，格式 HH:MM
+    pub start: String,
+    /// 结束时间，格式 HH:MM；早于开始时间表示跨越午夜，跨夜部分算作开始当天
+    pub end: String,
+    /// 时段结束时自动切换到的供应商；未设置时只拦截时段外的手动切换
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_provider_id: Option<String>,
+}
+
+/// 凭证探测结果
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialStatus {
+    pub valid: bool,
+    pub checked_at: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_status: Option<u16>,
+}
+
+/// 最近一次端点测速结果（取最快的可用端点）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderLatency {
+    pub latency_ms: u64,
+    pub tested_at: i64,
 }
 
 impl ProviderManager {
@@ -0,0 +1,391 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// 需要用户补全数值后才能创建供应商的模板
+///
+/// `settings_config` 中的字符串可包含 `${KEY}` 占位符，KEY 对应 `placeholders` 中的一项。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderTemplate {
+    pub id: String,
+    /// 目标应用（claude / codex / gemini）
+    pub app: String,
+    pub name: String,
+    #[serde(default)]
+    pub website_url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    pub settings_config: Value,
+    #[serde(default)]
+    pub placeholders: Vec<TemplatePlaceholder>,
+    /// 模板来源：随程序内置，或来自远程预设索引
+    #[serde(default)]
+    pub source: PresetSource,
+}
+
+/// 预设来源标记
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PresetSource {
+    #[default]
+    Builtin,
+    Remote,
+}
+
+/// 模板中的一个待填写值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplatePlaceholder {
+    pub key: String,
+    pub label: String,
+    /// 输入框中的示例值
+    #[serde(default)]
+    pub example: String,
+    /// 是否为密钥，前端应使用密码输入框
+    #[serde(default)]
+    pub secret: bool,
+    /// 未填写时使用的默认值；为空表示必填
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_value: Option<String>,
+}
+
+fn placeholder(
+    key: &str,
+    label: &str,
+    example: &str,
+    default_value: Option<&str>,
+) -> TemplatePlaceholder {
+    TemplatePlaceholder {
+        key: key.to_string(),
+        label: label.to_string(),
+        example: example.to_string(),
+        secret: false,
+        default_value: default_value.map(str::to_string),
+    }
+}
+
+fn api_key(label: &str) -> TemplatePlaceholder {
+    TemplatePlaceholder {
+        secret: true,
+        ..placeholder("API_KEY", label, "sk-...", None)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn template(
+    id: &str,
+    app: &str,
+    name: &str,
+    website_url: &str,
+    api_key_url: Option<&str>,
+    icon: Option<&str>,
+    settings_config: Value,
+    placeholders: Vec<TemplatePlaceholder>,
+) -> ProviderTemplate {
+    ProviderTemplate {
+        id: id.to_string(),
+        app: app.to_string(),
+        name: name.to_string(),
+        website_url: website_url.to_string(),
+        api_key_url: api_key_url.map(str::to_string),
+        icon: icon.map(str::to_string),
+        settings_config,
+        placeholders,
+        source: PresetSource::Builtin,
+    }
+}
+
+const AZURE_CODEX_CONFIG: &str = r#"model_provider = "azure"
+model = "${DEPLOYMENT}"
+model_reasoning_effort = "high"
+disable_response_storage = true
+
+[model_providers.azure]
+name = "Azure OpenAI"
+base_url = "https://${RESOURCE_NAME}.openai.azure.com/openai"
+env_key = "OPENAI_API_KEY"
+query_params = { "api-version" = "2025-04-01-preview" }
+wire_api = "responses"
+requires_openai_auth = true"#;
+
+const CUSTOM_CODEX_CONFIG: &str = r#"model_provider = "custom"
+model = "${MODEL}"
+model_reasoning_effort = "high"
+disable_response_storage = true
+
+[model_providers.custom]
+name = "custom"
+base_url = "${BASE_URL}"
+wire_api = "responses"
+requires_openai_auth = true"#;
+
+/// 中转服务的 Codex 配置，与前端 `generateThirdPartyConfig` 生成的结构一致，模型留作占位符
+fn codex_relay_config(provider: &str, base_url: &str) -> String {
+    format!(
+        r#"model_provider = "{provider}"
+model = "${{MODEL}}"
+model_reasoning_effort = "high"
+disable_response_storage = true
+
+[model_providers.{provider}]
+name = "{provider}"
+base_url = "{base_url}"
+wire_api = "responses"
+requires_openai_auth = true"#
+    )
+}
+
+fn codex_model() -> TemplatePlaceholder {
+    placeholder("MODEL", "Model", "gpt-5-codex", Some("gpt-5-codex"))
+}
+
+/// 内置模板列表：每个应用依次为官方 API、常用中转与自定义中转
+pub static PROVIDER_TEMPLATES: Lazy<Vec<ProviderTemplate>> = Lazy::new(|| {
+    vec![
+        template(
+            "claude-anthropic-api",
+            "claude",
+            "Anthropic API",
+            "https://console.anthropic.com",
+            Some("https://console.anthropic.com/settings/keys"),
+            Some("anthropic"),
+            json!({
+                "env": {
+                    "ANTHROPIC_BASE_URL": "https://api.anthropic.com",
+                    "ANTHROPIC_API_KEY": "${API_KEY}"
+                }
+            }),
+            vec![api_key("Anthropic API Key")],
+        ),
+        template(
+            "claude-packycode",
+            "claude",
+            "PackyCode",
+            "https://www.packyapi.com",
+            Some("https://www.packyapi.com/register?aff=cli-hub"),
+            Some("packycode"),
+            json!({
+                "env": {
+                    "ANTHROPIC_BASE_URL": "https://www.packyapi.com",
+                    "ANTHROPIC_AUTH_TOKEN": "${API_KEY}"
+                }
+            }),
+            vec![api_key("API Key")],
+        ),
+        // AiHubMix 与 DMXAPI 读取 ANTHROPIC_API_KEY 而非 ANTHROPIC_AUTH_TOKEN
+        template(
+            "claude-aihubmix",
+            "claude",
+            "AiHubMix",
+            "https://aihubmix.com",
+            Some("https://aihubmix.com"),
+            None,
+            json!({
+                "env": {
+                    "ANTHROPIC_BASE_URL": "https://aihubmix.com",
+                    "ANTHROPIC_API_KEY": "${API_KEY}"
+                }
+            }),
+            vec![api_key("API Key")],
+        ),
+        template(
+            "claude-dmxapi",
+            "claude",
+            "DMXAPI",
+            "https://www.dmxapi.cn",
+            Some("https://www.dmxapi.cn"),
+            None,
+            json!({
+                "env": {
+                    "ANTHROPIC_BASE_URL": "https://www.dmxapi.cn",
+                    "ANTHROPIC_API_KEY": "${API_KEY}"
+                }
+            }),
+            vec![api_key("API Key")],
+        ),
+        template(
+            "claude-kat-coder",
+            "claude",
+            "KAT-Coder",
+            "https://console.streamlake.ai",
+            Some("https://console.streamlake.ai/console/api-key"),
+            None,
+            json!({
+                "env": {
+                    "ANTHROPIC_BASE_URL": "https://vanchin.streamlake.ai/api/gateway/v1/endpoints/${ENDPOINT_ID}/claude-code-proxy",
+                    "ANTHROPIC_AUTH_TOKEN": "${API_KEY}",
+                    "ANTHROPIC_MODEL": "KAT-Coder-Pro V1",
+                    "ANTHROPIC_DEFAULT_HAIKU_MODEL": "KAT-Coder-Air V1",
+                    "ANTHROPIC_DEFAULT_SONNET_MODEL": "KAT-Coder-Pro V1",
+                    "ANTHROPIC_DEFAULT_OPUS_MODEL": "KAT-Coder-Pro V1"
+                }
+            }),
+            vec![
+                placeholder("ENDPOINT_ID", "Vanchin Endpoint ID", "ep-xxx-xxx", None),
+                api_key("API Key"),
+            ],
+        ),
+        template(
+            "claude-custom-relay",
+            "claude",
+            "Custom relay",
+            "",
+            None,
+            None,
+            json!({
+                "env": {
+                    "ANTHROPIC_BASE_URL": "${BASE_URL}",
+                    "ANTHROPIC_AUTH_TOKEN": "${API_KEY}"
+                }
+            }),
+            vec![
+                placeholder("BASE_URL", "Base URL", "https://relay.example.com", None),
+                api_key("API Key"),
+            ],
+        ),
+        template(
+            "codex-openai-api",
+            "codex",
+            "OpenAI API",
+            "https://platform.openai.com",
+            Some("https://platform.openai.com/api-keys"),
+            Some("openai"),
+            // 空 config.toml 即使用 Codex 内置的 OpenAI 端点
+            json!({
+                "auth": { "OPENAI_API_KEY": "${API_KEY}" },
+                "config": ""
+            }),
+            vec![api_key("OpenAI API Key")],
+        ),
+        template(
+            "codex-azure-openai",
+            "codex",
+            "Azure OpenAI",
+            "https://learn.microsoft.com/azure/ai-services/openai/how-to/overview",
+            None,
+            Some("azure"),
+            json!({
+                "auth": { "OPENAI_API_KEY": "${API_KEY}" },
+                "config": AZURE_CODEX_CONFIG
+            }),
+            vec![
+                placeholder("RESOURCE_NAME", "Azure resource name", "my-openai", None),
+                placeholder(
+                    "DEPLOYMENT",
+                    "Deployment name",
+                    "gpt-5-codex",
+                    Some("gpt-5-codex"),
+                ),
+                api_key("Azure API Key"),
+            ],
+        ),
+        template(
+            "codex-packycode",
+            "codex",
+            "PackyCode",
+            "https://www.packyapi.com",
+            Some("https://www.packyapi.com/register?aff=cli-hub"),
+            Some("packycode"),
+            json!({
+                "auth": { "OPENAI_API_KEY": "${API_KEY}" },
+                "config": codex_relay_config("packycode", "https://www.packyapi.com/v1")
+            }),
+            vec![codex_model(), api_key("API Key")],
+        ),
+        template(
+            "codex-aihubmix",
+            "codex",
+            "AiHubMix",
+            "https://aihubmix.com",
+            Some("https://aihubmix.com"),
+            None,
+            json!({
+                "auth": { "OPENAI_API_KEY": "${API_KEY}" },
+                "config": codex_relay_config("aihubmix", "https://aihubmix.com/v1")
+            }),
+            vec![codex_model(), api_key("API Key")],
+        ),
+        template(
+            "codex-custom-relay",
+            "codex",
+            "Custom relay",
+            "",
+            None,
+            None,
+            json!({
+                "auth": { "OPENAI_API_KEY": "${API_KEY}" },
+                "config": CUSTOM_CODEX_CONFIG
+            }),
+            vec![
+                placeholder("BASE_URL", "Base URL", "https://relay.example.com/v1", None),
+                codex_model(),
+                api_key("API Key"),
+            ],
+        ),
+        template(
+            "gemini-google-api",
+            "gemini",
+            "Google AI Studio",
+            "https://ai.google.dev/",
+            Some("https://aistudio.google.com/apikey"),
+            Some("gemini"),
+            json!({
+                "env": {
+                    "GEMINI_API_KEY": "${API_KEY}",
+                    "GEMINI_MODEL": "${MODEL}"
+                }
+            }),
+            vec![
+                placeholder("MODEL", "Model", "gemini-2.5-pro", Some("gemini-2.5-pro")),
+                api_key("Gemini API Key"),
+            ],
+        ),
+        template(
+            "gemini-packycode",
+            "gemini",
+            "PackyCode",
+            "https://www.packyapi.com",
+            Some("https://www.packyapi.com/register?aff=cli-hub"),
+            Some("packycode"),
+            json!({
+                "env": {
+                    "GOOGLE_GEMINI_BASE_URL": "https://www.packyapi.com",
+                    "GEMINI_API_KEY": "${API_KEY}",
+                    "GEMINI_MODEL": "${MODEL}"
+                }
+            }),
+            vec![
+                placeholder(
+                    "MODEL",
+                    "Model",
+                    "gemini-3-pro-preview",
+                    Some("gemini-3-pro-preview"),
+                ),
+                api_key("API Key"),
+            ],
+        ),
+        template(
+            "gemini-custom-relay",
+            "gemini",
+            "Custom relay",
+            "",
+            None,
+            None,
+            json!({
+                "env": {
+                    "GOOGLE_GEMINI_BASE_URL": "${BASE_URL}",
+                    "GEMINI_API_KEY": "${API_KEY}",
+                    "GEMINI_MODEL": "${MODEL}"
+                }
+            }),
+            vec![
+                placeholder("BASE_URL", "Base URL", "https://relay.example.com", None),
+                placeholder("MODEL", "Model", "gemini-2.5-pro", Some("gemini-2.5-pro")),
+                api_key("API Key"),
+            ],
+        ),
+    ]
+});
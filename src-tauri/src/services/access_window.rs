@@ -0,0 +1,290 @@
+use chrono::{Datelike, Local, NaiveDateTime, NaiveTime};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::notifications::{notify, NotificationCategory, NotificationText};
+use crate::provider::{AccessWindow, Provider};
+use crate::services::ProviderService;
+use crate::store::AppState;
+
+/// 时段按分钟配置，每分钟检查一次即可
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 调度器在时段结束时执行的一次自动切换
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowAutoSwitch {
+    pub app: String,
+    pub from_id: String,
+    pub from_name: String,
+    pub to_id: String,
+    pub to_name: String,
+}
+
+/// 供应商可用时段的校验、切换拦截与到期自动切换
+pub struct AccessWindowService;
+
+impl AccessWindowService {
+    /// 校验时段格式，保存供应商时调用
+    pub fn validate(window: &AccessWindow) -> Result<(), AppError> {
+        parse_time(&window.start)?;
+        parse_time(&window.end)?;
+        if let Some(day) = window.days.iter().find(|d| !(1..=7).contains(*d)) {
+            return Err(AppError::localized(
+                "provider.access_window_invalid_day",
+                format!("可用时段的星期取值无效: {day}（应为 1-7）"),
+                format!("Invalid weekday in access window: {day} (expected 1-7)"),
+            ));
+        }
+        Ok(())
+    }
+
+    /// 指定时间是否处于可用时段内；开始与结束相同表示全天可用
+    pub fn is_open_at(window: &AccessWindow, now: NaiveDateTime) -> Result<bool, AppError> {
+        let start = parse_time(&window.start)?;
+        let end = parse_time(&window.end)?;
+        let time = now.time();
+        let today = now.weekday().number_from_monday() as u8;
+        let yesterday = if today == 1 { 7 } else { today - 1 };
+        let day_allowed = |day: u8| window.days.is_empty() || window.days.contains(&day);
+
+        Ok(if start == end {
+            day_allowed(today)
+        } else if start < end {
+            day_allowed(today) && time >= start && time < end
+        } else {
+            (day_allowed(today) && time >= start) || (day_allowed(yesterday) && time < end)
+        })
+    }
+
+    /// 供应商当前是否可用；未设置时段视为始终可用，格式错误的时段不拦截
+    pub fn is_available(provider: &Provider) -> bool {
+        let Some(window) = window_of(provider) else {
+            return true;
+        };
+        Self::is_open_at(window, Local::now().naive_local()).unwrap_or_else(|e| {
+            log::warn!("供应商 {} 的可用时段无效，已忽略: {e}", provider.id);
+            true
+        })
+    }
+
+    /// 切换前检查可用时段，force 为 true 时放行
+    pub fn ensure_allowed(provider: &Provider, force: bool) -> Result<(), AppError> {
+        let Some(window) = window_of(provider) else {
+            return Ok(());
+        };
+        if force || Self::is_available(provider) {
+            return Ok(());
+        }
+        let window = describe(window);
+        Err(AppError::localized(
+            "provider.access_window_closed",
+            format!(
+                "供应商 {} 仅在 {window} 可用，如需切换请使用强制切换",
+                provider.name
+            ),
+            format!(
+                "Provider {} is only available during {window}; force the switch to override",
+                provider.name
+            ),
+        ))
+    }
+
+    /// 检查各应用的当前供应商，时段刚结束且配置了回退供应商时自动切换
+    ///
+    /// last_seen 记录上一轮各应用的当前供应商及其是否可用：时段外强制切换进来的供应商
+    /// 不会被立即切走；首轮（启动时）没有记录，已结束的时段同样会触发切换
+    pub fn enforce_closed_windows(
+        state: &AppState,
+        last_seen: &mut HashMap<String, (String, bool)>,
+        first_run: bool,
+    ) -> Result<Vec<WindowAutoSwitch>, AppError> {
+        let mut switched = Vec::new();
+        for app_type in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+            let Some(current_id) = state.storage.get_current_provider(app_type.as_str())? else {
+                continue;
+            };
+            let providers = state.storage.get_all_providers(app_type.as_str())?;
+            let Some(current) = providers.get(&current_id) else {
+                continue;
+            };
+            let open = Self::is_available(current);
+            let previous =
+                last_seen.insert(app_type.as_str().to_string(), (current_id.clone(), open));
+            let just_closed = match previous {
+                Some((id, was_open)) if id == current_id => was_open,
+                Some(_) => false,
+                None => first_run,
+            };
+            if open || !just_closed {
+                continue;
+            }
+            let Some(fallback_id) = window_of(current).and_then(|w| w.fallback_provider_id.clone())
+            else {
+                continue;
+            };
+            let Some(fallback) = providers.get(&fallback_id) else {
+                log::warn!(
+                    "供应商 {} 的回退供应商 {fallback_id} 不存在，跳过自动切换",
+                    current.id
+                );
+                continue;
+            };
+            if fallback_id == current_id || !Self::is_available(fallback) {
+                log::warn!("回退供应商 {fallback_id} 当前也不可用，跳过自动切换");
+                continue;
+            }
+
+            ProviderService::switch(state, app_type.clone(), &fallback_id)?;
+            log::info!(
+                "{} 的可用时段已结束，已从 {} 自动切换到 {}",
+                app_type.as_str(),
+                current.id,
+                fallback_id
+            );
+            switched.push(WindowAutoSwitch {
+                app: app_type.as_str().to_string(),
+                from_id: current.id.clone(),
+                from_name: current.name.clone(),
+                to_id: fallback.id.clone(),
+                to_name: fallback.name.clone(),
+            });
+        }
+        Ok(switched)
+    }
+
+    /// 启动后台调度器；自动切换后刷新托盘并通知前端
+    pub fn spawn_scheduler(app: AppHandle, state: AppState) {
+        tauri::async_runtime::spawn(async move {
+            let mut last_seen = HashMap::new();
+            let mut first_run = true;
+            loop {
+                let state = state.clone();
+                let result = tauri::async_runtime::spawn_blocking(move || {
+                    let switched = Self::enforce_closed_windows(&state, &mut last_seen, first_run);
+                    (last_seen, switched)
+                })
+                .await;
+
+                match result {
+                    Ok((seen, switched)) => {
+                        last_seen = seen;
+                        first_run = false;
+                        match switched {
+                            Ok(switched) if !switched.is_empty() => Self::announce(&app, &switched),
+                            Ok(_) => {}
+                            Err(err) => log::warn!("检查供应商可用时段失败: {err}"),
+                        }
+                    }
+                    Err(err) => {
+                        log::warn!("供应商可用时段检查任务异常: {err}");
+                        last_seen = HashMap::new();
+                    }
+                }
+
+                tokio::time::sleep(CHECK_INTERVAL).await;
+            }
+        });
+    }
+
+    fn announce(app: &AppHandle, switched: &[WindowAutoSwitch]) {
+        for item in switched {
+            if let Ok(app_type) = AppType::from_str(&item.app) {
+                crate::tray::mark_tray_current(app, &app_type, &item.to_id);
+            }
+            let payload = serde_json::json!({
+                "appType": item.app,
+                "providerId": item.to_id,
+            });
+            if let Err(e) = app.emit("provider-switched", payload) {
+                log::error!("发射供应商切换事件失败: {e}");
+            }
+            notify(
+                NotificationCategory::AccessWindow,
+                true,
+                NotificationText::new(
+                    format!(
+                        "{} 已超出可用时段，已切换到 {}",
+                        item.from_name, item.to_name
+                    ),
+                    format!(
+                        "{} is outside its access window; switched to {}",
+                        item.from_name, item.to_name
+                    ),
+                ),
+            );
+        }
+    }
+}
+
+fn window_of(provider: &Provider) -> Option<&AccessWindow> {
+    provider
+        .meta
+        .as_ref()
+        .and_then(|m| m.access_window.as_ref())
+}
+
+fn parse_time(raw: &str) -> Result<NaiveTime, AppError> {
+    NaiveTime::parse_from_str(raw.trim(), "%H:%M").map_err(|_| {
+        AppError::localized(
+            "provider.access_window_invalid_time",
+            format!("可用时段的时间格式无效: '{raw}'（应为 HH:MM）"),
+            format!("Invalid time in access window: '{raw}' (expected HH:MM)"),
+        )
+    })
+}
+
+fn describe(window: &AccessWindow) -> String {
+    let hours = format!("{}-{}", window.start.trim(), window.end.trim());
+    if window.days.is_empty() {
+        return hours;
+    }
+    let days: Vec<String> = window.days.iter().map(u8::to_string).collect();
+    format!("{hours} (days {})", days.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn window(days: &[u8], start: &str, end: &str) -> AccessWindow {
+        AccessWindow {
+            days: days.to_vec(),
+            start: start.into(),
+            end: end.into(),
+            fallback_provider_id: None,
+        }
+    }
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        // 2024-01-01 是周一
+        NaiveDate::from_ymd_opt(2024, 1, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn business_hours_and_overnight_windows() {
+        let business = window(&[1, 2, 3, 4, 5], "09:00", "18:00");
+        assert!(AccessWindowService::is_open_at(&business, at(1, 9, 0)).unwrap());
+        assert!(!AccessWindowService::is_open_at(&business, at(1, 18, 0)).unwrap());
+        assert!(!AccessWindowService::is_open_at(&business, at(6, 10, 0)).unwrap());
+
+        // 周五 22:00 开始的跨夜时段在周六凌晨仍然有效，周日凌晨则不属于任何允许的开始日
+        let overnight = window(&[5], "22:00", "06:00");
+        assert!(AccessWindowService::is_open_at(&overnight, at(5, 23, 30)).unwrap());
+        assert!(AccessWindowService::is_open_at(&overnight, at(6, 5, 59)).unwrap());
+        assert!(!AccessWindowService::is_open_at(&overnight, at(7, 1, 0)).unwrap());
+        assert!(!AccessWindowService::is_open_at(&overnight, at(5, 12, 0)).unwrap());
+
+        assert!(AccessWindowService::validate(&window(&[8], "09:00", "18:00")).is_err());
+        assert!(AccessWindowService::validate(&window(&[], "9am", "18:00")).is_err());
+    }
+}
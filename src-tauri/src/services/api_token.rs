@@ -0,0 +1,263 @@
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::OsRng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::str::FromStr;
+
+use crate::database::dao::ApiTokenEntry;
+use crate::error::AppError;
+use crate::store::AppState;
+
+/// 令牌明文前缀，便于在日志或剪贴板中识别
+const TOKEN_PREFIX: &str = "chk_";
+const SECRET_BYTES: usize = 24;
+/// 列表中展示的明文长度（含前缀）
+const DISPLAY_PREFIX_LEN: usize = 12;
+
+/// 令牌权限范围，按 ReadOnly < SwitchOnly < Full 逐级包含
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ApiTokenScope {
+    /// 仅查询
+    ReadOnly,
+    /// 查询与切换供应商（切换前通常需要先列出供应商）
+    SwitchOnly,
+    /// 所有命令
+    Full,
+}
+
+impl ApiTokenScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiTokenScope::ReadOnly => "readOnly",
+            ApiTokenScope::SwitchOnly => "switchOnly",
+            ApiTokenScope::Full => "full",
+        }
+    }
+
+    /// 调用指定命令所需的最低权限，查 [`COMMAND_SCOPES`]
+    pub fn required_for(command: &str) -> Self {
+        COMMAND_SCOPES
+            .iter()
+            .find(|(name, _)| *name == command)
+            .map(|(_, scope)| *scope)
+            .unwrap_or(ApiTokenScope::Full)
+    }
+
+    pub fn allows(&self, command: &str) -> bool {
+        *self >= Self::required_for(command)
+    }
+}
+
+/// 外部控制入口可调用的命令及所需最低权限
+///
+/// 逐条列出而非按名称前缀推断：`get_launcher_manifest`、`get_mcp_deeplink_secrets`
+/// 等同样以 `get_` 开头，却会返回已签名的操作链接或密钥。未列出的命令一律要求 Full。
+const COMMAND_SCOPES: &[(&str, ApiTokenScope)] = &[
+    ("get_providers", ApiTokenScope::ReadOnly),
+    ("get_current_provider", ApiTokenScope::ReadOnly),
+    ("get_provider_error_history", ApiTokenScope::ReadOnly),
+    ("get_config_status", ApiTokenScope::ReadOnly),
+    ("get_claude_config_status", ApiTokenScope::ReadOnly),
+    ("check_live_config", ApiTokenScope::ReadOnly),
+    ("get_status_summary", ApiTokenScope::ReadOnly),
+    ("get_management_paused", ApiTokenScope::ReadOnly),
+    ("get_mcp_servers", ApiTokenScope::ReadOnly),
+    ("get_prompt_summaries", ApiTokenScope::ReadOnly),
+    ("get_quick_actions", ApiTokenScope::ReadOnly),
+    ("get_endpoint_health", ApiTokenScope::ReadOnly),
+    ("get_tray_menu_stats", ApiTokenScope::ReadOnly),
+    ("list_stacks", ApiTokenScope::ReadOnly),
+    ("validate_switch", ApiTokenScope::ReadOnly),
+    ("preview_switch", ApiTokenScope::ReadOnly),
+    ("switch_provider", ApiTokenScope::SwitchOnly),
+    ("switch_provider_by_name", ApiTokenScope::SwitchOnly),
+];
+
+impl FromStr for ApiTokenScope {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "readOnly" => Ok(ApiTokenScope::ReadOnly),
+            "switchOnly" => Ok(ApiTokenScope::SwitchOnly),
+            "full" => Ok(ApiTokenScope::Full),
+            other => Err(AppError::localized(
+                "api_token.invalid_scope",
+                format!("无效的令牌权限范围: {other}"),
+                format!("Invalid token scope: {other}"),
+            )),
+        }
+    }
+}
+
+/// 新建令牌的结果，明文仅在此返回一次
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatedApiToken {
+    pub token: ApiTokenEntry,
+    pub secret: String,
+}
+
+/// 面向外部控制入口（本地 REST、CLI、脚本）的访问令牌管理与权限校验
+pub struct ApiTokenService;
+
+impl ApiTokenService {
+    pub fn create(
+        state: &AppState,
+        name: &str,
+        scope: ApiTokenScope,
+    ) -> Result<CreatedApiToken, AppError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(AppError::InvalidInput("令牌名称不能为空".to_string()));
+        }
+
+        let secret = generate_secret();
+        let entry = ApiTokenEntry {
+            id: token_id(&secret),
+            name: name.to_string(),
+            scope: scope.as_str().to_string(),
+            prefix: secret.chars().take(DISPLAY_PREFIX_LEN).collect(),
+            created_at: chrono::Utc::now().timestamp_millis(),
+            last_used_at: None,
+            revoked_at: None,
+        };
+        state
+            .storage
+            .insert_api_token(&entry, &hash_secret(&secret))?;
+        log::info!("已创建 API 令牌 {} ({})", entry.name, entry.scope);
+        Ok(CreatedApiToken {
+            token: entry,
+            secret,
+        })
+    }
+
+    pub fn list(state: &AppState) -> Result<Vec<ApiTokenEntry>, AppError> {
+        state.storage.get_api_tokens()
+    }
+
+    pub fn revoke(state: &AppState, id: &str) -> Result<bool, AppError> {
+        let revoked = state
+            .storage
+            .revoke_api_token(id, chrono::Utc::now().timestamp_millis())?;
+        if revoked {
+            log::info!("已吊销 API 令牌 {id}");
+        }
+        Ok(revoked)
+    }
+
+    /// 命令分发前调用：校验令牌有效且权限覆盖该命令，并记录最近使用时间
+    pub fn authorize(
+        state: &AppState,
+        secret: &str,
+        command: &str,
+    ) -> Result<ApiTokenEntry, AppError> {
+        let entry = state
+            .storage
+            .find_api_token_by_hash(&hash_secret(secret.trim()))?
+            .filter(|entry| entry.revoked_at.is_none())
+            .ok_or_else(|| {
+                AppError::localized(
+                    "api_token.invalid",
+                    "API 令牌无效或已被吊销",
+                    "The API token is invalid or has been revoked",
+                )
+            })?;
+
+        let scope = ApiTokenScope::from_str(&entry.scope)?;
+        if !scope.allows(command) {
+            let required = ApiTokenScope::required_for(command);
+            return Err(AppError::localized(
+                "api_token.scope_denied",
+                format!(
+                    "令牌 {} 的权限为 {}，调用 {command} 需要 {}",
+                    entry.name,
+                    scope.as_str(),
+                    required.as_str()
+                ),
+                format!(
+                    "Token {} has scope {}, but {command} requires {}",
+                    entry.name,
+                    scope.as_str(),
+                    required.as_str()
+                ),
+            ));
+        }
+
+        // 使用时间仅用于展示，写入失败不影响本次调用
+        if let Err(e) = state
+            .storage
+            .touch_api_token(&entry.id, chrono::Utc::now().timestamp_millis())
+        {
+            log::warn!("更新 API 令牌使用时间失败: {e}");
+        }
+        Ok(entry)
+    }
+}
+
+fn generate_secret() -> String {
+    let mut bytes = [0u8; SECRET_BYTES];
+    OsRng.fill_bytes(&mut bytes);
+    let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    format!("{TOKEN_PREFIX}{hex}")
+}
+
+fn hash_secret(secret: &str) -> String {
+    Sha256::new()
+        .chain_update(b"clihub-api-token:")
+        .chain_update(secret.as_bytes())
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// 由明文派生的记录 ID，不可反推明文
+fn token_id(secret: &str) -> String {
+    let digest = Sha256::new()
+        .chain_update(b"clihub-api-token-id:")
+        .chain_update(secret.as_bytes())
+        .finalize();
+    digest.iter().take(8).map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use std::sync::Arc;
+
+    #[test]
+    fn scopes_gate_commands_and_revoked_tokens_are_rejected() {
+        let state = AppState::new(Arc::new(Database::memory().unwrap()));
+        let reader = ApiTokenService::create(&state, "dashboard", ApiTokenScope::ReadOnly).unwrap();
+        let switcher =
+            ApiTokenService::create(&state, "hotkey", ApiTokenScope::SwitchOnly).unwrap();
+        assert!(reader.secret.starts_with(TOKEN_PREFIX));
+        assert_ne!(reader.secret, switcher.secret);
+
+        assert!(ApiTokenService::authorize(&state, &reader.secret, "get_providers").is_ok());
+        assert!(ApiTokenService::authorize(&state, &reader.secret, "switch_provider").is_err());
+        assert!(ApiTokenService::authorize(&state, &switcher.secret, "switch_provider").is_ok());
+        assert!(ApiTokenService::authorize(&state, &switcher.secret, "delete_provider").is_err());
+        assert!(ApiTokenService::authorize(&state, &switcher.secret, "list_api_tokens").is_err());
+        assert!(
+            ApiTokenService::authorize(&state, &reader.secret, "get_launcher_manifest").is_err()
+        );
+        assert!(ApiTokenService::authorize(&state, "chk_unknown", "get_providers").is_err());
+
+        let listed = ApiTokenService::list(&state).unwrap();
+        assert_eq!(listed.len(), 2);
+        assert!(listed
+            .iter()
+            .find(|t| t.id == reader.token.id)
+            .unwrap()
+            .last_used_at
+            .is_some());
+
+        assert!(ApiTokenService::revoke(&state, &reader.token.id).unwrap());
+        assert!(!ApiTokenService::revoke(&state, &reader.token.id).unwrap());
+        assert!(ApiTokenService::authorize(&state, &reader.secret, "get_providers").is_err());
+    }
+}
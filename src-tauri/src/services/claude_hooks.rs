@@ -0,0 +1,409 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::app_config::AppType;
+use crate::config::{file_exists, get_claude_settings_path, read_json_file, write_json_file};
+use crate::error::AppError;
+use crate::services::sync_pause::is_management_paused;
+use crate::services::WriteAccessService;
+use crate::store::AppState;
+
+/// Claude Code 支持的生命周期事件，按界面展示顺序排列
+pub const HOOK_EVENTS: [&str; 9] = [
+    "PreToolUse",
+    "PostToolUse",
+    "UserPromptSubmit",
+    "Notification",
+    "Stop",
+    "SubagentStop",
+    "PreCompact",
+    "SessionStart",
+    "SessionEnd",
+];
+
+/// 只有这些事件会按 matcher 过滤（工具名，或 PreCompact / SessionStart 的触发来源）
+const MATCHER_EVENTS: [&str; 4] = ["PreToolUse", "PostToolUse", "PreCompact", "SessionStart"];
+
+/// settings.json 中 `hooks` 的一条命令，已从「事件 → matcher 分组 → 命令」展开
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeHook {
+    pub event: String,
+    /// 正则表达式；为空或 `*` 表示匹配全部
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matcher: Option<String>,
+    pub command: String,
+    /// 超时秒数，未设置时使用 Claude Code 的默认值
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+}
+
+impl ClaudeHook {
+    fn matcher_key(&self) -> String {
+        self.matcher.clone().unwrap_or_default()
+    }
+}
+
+/// Claude 钩子管理
+///
+/// 钩子可以保存在单个供应商的 settingsConfig 中，也可以保存在 Claude 通用配置片段中
+/// 作为全局钩子（`provider_id` 为 None）。写入 live 时当前供应商的钩子与全局钩子合并后
+/// 写入 settings.json 的 `hooks`，与 env 等字段并列。
+pub struct ClaudeHookService;
+
+impl ClaudeHookService {
+    pub fn list(state: &AppState, provider_id: Option<&str>) -> Result<Vec<ClaudeHook>, AppError> {
+        match provider_id {
+            Some(id) => Ok(parse_hooks(provider_settings(state, id)?.get("hooks"))),
+            None => Ok(parse_hooks(global_snippet(state)?.get("hooks"))),
+        }
+    }
+
+    pub fn add(
+        state: &AppState,
+        provider_id: Option<&str>,
+        hook: ClaudeHook,
+    ) -> Result<Vec<ClaudeHook>, AppError> {
+        let hook = validate(hook)?;
+        Self::modify(state, provider_id, |hooks| {
+            hooks.push(hook);
+            Ok(())
+        })
+    }
+
+    pub fn update(
+        state: &AppState,
+        provider_id: Option<&str>,
+        index: usize,
+        hook: ClaudeHook,
+    ) -> Result<Vec<ClaudeHook>, AppError> {
+        let hook = validate(hook)?;
+        Self::modify(state, provider_id, |hooks| {
+            *hooks.get_mut(index).ok_or_else(|| not_found(index))? = hook;
+            Ok(())
+        })
+    }
+
+    pub fn remove(
+        state: &AppState,
+        provider_id: Option<&str>,
+        index: usize,
+    ) -> Result<Vec<ClaudeHook>, AppError> {
+        Self::modify(state, provider_id, |hooks| {
+            if index >= hooks.len() {
+                return Err(not_found(index));
+            }
+            hooks.remove(index);
+            Ok(())
+        })
+    }
+
+    fn modify(
+        state: &AppState,
+        provider_id: Option<&str>,
+        apply: impl FnOnce(&mut Vec<ClaudeHook>) -> Result<(), AppError>,
+    ) -> Result<Vec<ClaudeHook>, AppError> {
+        let mut hooks = Self::list(state, provider_id)?;
+        apply(&mut hooks)?;
+        update_scope(state, provider_id, |settings| set_hooks(settings, &hooks))?;
+        Self::sync_live(state)?;
+        Ok(hooks)
+    }
+
+    /// 把当前供应商与全局钩子合并写入 settings.json，内容未变化时不写文件
+    pub fn sync_live(state: &AppState) -> Result<(), AppError> {
+        let app = AppType::Claude.as_str();
+        let Some(current) = state.storage.get_current_provider(app)? else {
+            return Ok(());
+        };
+        Self::sync_live_for(state, &provider_settings(state, &current)?)
+    }
+
+    /// 同 [`Self::sync_live`]，但使用给定供应商的 settingsConfig（如切换预览中的目标供应商）
+    pub(crate) fn sync_live_for(state: &AppState, settings: &Value) -> Result<(), AppError> {
+        if is_management_paused() {
+            return Ok(());
+        }
+        let mut hooks = parse_hooks(settings.get("hooks"));
+        for hook in parse_hooks(global_snippet(state)?.get("hooks")) {
+            if !hooks.contains(&hook) {
+                hooks.push(hook);
+            }
+        }
+
+        let path = get_claude_settings_path();
+        if !file_exists(&path) && hooks.is_empty() {
+            return Ok(());
+        }
+        let mut live: Value = if file_exists(&path) {
+            read_json_file(&path)?
+        } else {
+            json!({})
+        };
+        let before = live.get("hooks").cloned();
+        set_hooks(&mut live, &hooks)?;
+        if live.get("hooks") == before.as_ref() {
+            return Ok(());
+        }
+        WriteAccessService::ensure_app_writable(&AppType::Claude)?;
+        write_json_file(&path, &live)
+    }
+}
+
+/// 改写供应商的 settingsConfig，或 provider_id 为 None 时改写 Claude 通用配置片段
+pub(crate) fn update_scope(
+    state: &AppState,
+    provider_id: Option<&str>,
+    update: impl FnOnce(&mut Value) -> Result<(), AppError>,
+) -> Result<(), AppError> {
+    let app = AppType::Claude.as_str();
+    let Some(id) = provider_id else {
+        let mut snippet = global_snippet(state)?;
+        update(&mut snippet)?;
+        let text = match snippet.as_object() {
+            Some(map) if map.is_empty() => None,
+            _ => Some(
+                serde_json::to_string_pretty(&snippet)
+                    .map_err(|e| AppError::JsonSerialize { source: e })?,
+            ),
+        };
+        return state.storage.set_config_snippet(app, text);
+    };
+
+    let mut provider = state
+        .storage
+        .get_all_providers(app)?
+        .shift_remove(id)
+        .ok_or_else(|| provider_not_found(id))?;
+    if let Some(org) = provider.meta.as_ref().and_then(|m| m.managed_by.as_deref()) {
+        return Err(AppError::provider_managed(app, id, org));
+    }
+    update(&mut provider.settings_config)?;
+    state.storage.save_provider(app, &provider)
+}
+
+pub(crate) fn provider_settings(state: &AppState, id: &str) -> Result<Value, AppError> {
+    state
+        .storage
+        .get_all_providers(AppType::Claude.as_str())?
+        .shift_remove(id)
+        .map(|p| p.settings_config)
+        .ok_or_else(|| provider_not_found(id))
+}
+
+/// Claude 通用配置片段；未设置时视为空对象
+pub(crate) fn global_snippet(state: &AppState) -> Result<Value, AppError> {
+    let Some(raw) = state.storage.get_config_snippet(AppType::Claude.as_str())? else {
+        return Ok(json!({}));
+    };
+    if raw.trim().is_empty() {
+        return Ok(json!({}));
+    }
+    let value: Value = serde_json::from_str(&raw).map_err(|e| {
+        AppError::localized(
+            "claude_hooks.invalid_snippet",
+            format!("Claude 通用配置片段不是有效的 JSON: {e}"),
+            format!("The Claude common config snippet is not valid JSON: {e}"),
+        )
+    })?;
+    if !value.is_object() {
+        return Err(AppError::localized(
+            "claude_hooks.invalid_snippet",
+            "Claude 通用配置片段必须是 JSON 对象",
+            "The Claude common config snippet must be a JSON object",
+        ));
+    }
+    Ok(value)
+}
+
+fn validate(mut hook: ClaudeHook) -> Result<ClaudeHook, AppError> {
+    hook.event = hook.event.trim().to_string();
+    hook.command = hook.command.trim().to_string();
+    hook.matcher = hook
+        .matcher
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty());
+
+    if !HOOK_EVENTS.contains(&hook.event.as_str()) {
+        return Err(AppError::localized(
+            "claude_hooks.unknown_event",
+            format!("不支持的钩子事件: {}", hook.event),
+            format!("Unsupported hook event: {}", hook.event),
+        ));
+    }
+    if hook.command.is_empty() {
+        return Err(AppError::localized(
+            "claude_hooks.command_required",
+            "钩子命令不能为空",
+            "The hook command cannot be empty",
+        ));
+    }
+    if hook.timeout == Some(0) {
+        return Err(AppError::localized(
+            "claude_hooks.invalid_timeout",
+            "超时时间必须大于 0 秒",
+            "The timeout must be greater than 0 seconds",
+        ));
+    }
+    if let Some(matcher) = hook.matcher.as_deref() {
+        if !MATCHER_EVENTS.contains(&hook.event.as_str()) {
+            return Err(AppError::localized(
+                "claude_hooks.matcher_unsupported",
+                format!("{} 事件不支持 matcher", hook.event),
+                format!("The {} event does not support a matcher", hook.event),
+            ));
+        }
+        if matcher != "*" {
+            regex::Regex::new(matcher).map_err(|e| {
+                AppError::localized(
+                    "claude_hooks.invalid_matcher",
+                    format!("matcher 不是有效的正则表达式: {e}"),
+                    format!("The matcher is not a valid regular expression: {e}"),
+                )
+            })?;
+        }
+    }
+    Ok(hook)
+}
+
+/// 展开 `{ 事件: [{ matcher, hooks: [{ type: "command", command, timeout }] }] }`
+///
+/// 只识别 command 类型，其他类型与格式不符的条目忽略
+fn parse_hooks(value: Option<&Value>) -> Vec<ClaudeHook> {
+    let Some(events) = value.and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    let mut hooks = Vec::new();
+    for (event, groups) in events {
+        for group in groups.as_array().into_iter().flatten() {
+            let matcher = group
+                .get("matcher")
+                .and_then(Value::as_str)
+                .filter(|m| !m.is_empty())
+                .map(str::to_string);
+            for entry in group
+                .get("hooks")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                if entry.get("type").and_then(Value::as_str) != Some("command") {
+                    continue;
+                }
+                let Some(command) = entry.get("command").and_then(Value::as_str) else {
+                    continue;
+                };
+                hooks.push(ClaudeHook {
+                    event: event.clone(),
+                    matcher: matcher.clone(),
+                    command: command.to_string(),
+                    timeout: entry.get("timeout").and_then(Value::as_u64),
+                });
+            }
+        }
+    }
+    hooks
+}
+
+/// 按事件、matcher 重新分组写回；列表为空时移除 `hooks`
+fn set_hooks(target: &mut Value, hooks: &[ClaudeHook]) -> Result<(), AppError> {
+    let obj = target
+        .as_object_mut()
+        .ok_or_else(|| AppError::Config("Claude 配置必须是 JSON 对象".to_string()))?;
+    if hooks.is_empty() {
+        obj.remove("hooks");
+        return Ok(());
+    }
+
+    let mut grouped: IndexMap<&str, IndexMap<String, Vec<Value>>> = IndexMap::new();
+    for hook in hooks {
+        let mut entry = Map::new();
+        entry.insert("type".to_string(), json!("command"));
+        entry.insert("command".to_string(), json!(hook.command));
+        if let Some(timeout) = hook.timeout {
+            entry.insert("timeout".to_string(), json!(timeout));
+        }
+        grouped
+            .entry(hook.event.as_str())
+            .or_default()
+            .entry(hook.matcher_key())
+            .or_default()
+            .push(Value::Object(entry));
+    }
+
+    let events: Map<String, Value> = grouped
+        .into_iter()
+        .map(|(event, groups)| {
+            let groups = groups
+                .into_iter()
+                .map(|(matcher, entries)| {
+                    if matcher.is_empty() {
+                        json!({ "hooks": entries })
+                    } else {
+                        json!({ "matcher": matcher, "hooks": entries })
+                    }
+                })
+                .collect();
+            (event.to_string(), Value::Array(groups))
+        })
+        .collect();
+    obj.insert("hooks".to_string(), Value::Object(events));
+    Ok(())
+}
+
+fn not_found(index: usize) -> AppError {
+    AppError::localized(
+        "claude_hooks.not_found",
+        format!("钩子不存在: #{index}"),
+        format!("Hook not found: #{index}"),
+    )
+}
+
+fn provider_not_found(id: &str) -> AppError {
+    AppError::localized(
+        "provider.not_found",
+        format!("供应商不存在: {id}"),
+        format!("Provider not found: {id}"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_grouped_hooks_and_validates_entries() {
+        let settings = json!({
+            "env": { "ANTHROPIC_BASE_URL": "https://api.example.com" },
+            "hooks": {
+                "PreToolUse": [
+                    { "matcher": "Bash", "hooks": [
+                        { "type": "command", "command": "audit.sh", "timeout": 30 },
+                        { "type": "command", "command": "lint.sh" },
+                    ] },
+                ],
+                "Stop": [{ "hooks": [{ "type": "command", "command": "notify.sh" }] }],
+            },
+        });
+        let hooks = parse_hooks(settings.get("hooks"));
+        assert_eq!(hooks.len(), 3);
+        assert_eq!(hooks[0].matcher.as_deref(), Some("Bash"));
+        assert_eq!(hooks[0].timeout, Some(30));
+
+        let mut rebuilt = json!({ "env": settings["env"].clone() });
+        set_hooks(&mut rebuilt, &hooks).unwrap();
+        assert_eq!(rebuilt, settings);
+
+        let hook = |event: &str, matcher: Option<&str>| ClaudeHook {
+            event: event.to_string(),
+            matcher: matcher.map(str::to_string),
+            command: "run.sh".to_string(),
+            timeout: None,
+        };
+        assert!(validate(hook("PostToolUse", Some("Edit|Write"))).is_ok());
+        assert!(validate(hook("PostToolUse", Some("("))).is_err());
+        assert!(validate(hook("Stop", Some("Bash"))).is_err());
+        assert!(validate(hook("OnSave", None)).is_err());
+    }
+}
@@ -0,0 +1,265 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::config::{
+    get_claude_local_settings_path, get_claude_managed_settings_path,
+    get_claude_user_settings_path, read_json_file,
+};
+use crate::settings::{get_claude_settings_layer, ClaudeSettingsLayer};
+
+/// 按优先级从低到高排列，后面的层级覆盖前面的同名键
+const LAYERS: [ClaudeSettingsLayer; 3] = [
+    ClaudeSettingsLayer::User,
+    ClaudeSettingsLayer::Local,
+    ClaudeSettingsLayer::Managed,
+];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeLayerInfo {
+    pub layer: ClaudeSettingsLayer,
+    pub path: String,
+    pub exists: bool,
+    /// CLI Hub 当前写入的层级
+    pub target: bool,
+    /// 文件存在但无法解析时的错误，此时该层不参与合并
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// CLI Hub 写入的键被更高优先级层级覆盖
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeSettingOverride {
+    /// 以 `.` 连接的键路径，如 `env.ANTHROPIC_BASE_URL`
+    pub key: String,
+    pub value: Value,
+    pub overridden_by: ClaudeSettingsLayer,
+    /// Claude Code 实际生效的值
+    pub effective: Value,
+}
+
+/// Claude Code 合并各层级后的实际配置
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeEffectiveSettings {
+    pub layers: Vec<ClaudeLayerInfo>,
+    pub merged: Value,
+    /// 每个叶子键最终来自哪个层级
+    pub sources: BTreeMap<String, ClaudeSettingsLayer>,
+    pub overrides: Vec<ClaudeSettingOverride>,
+}
+
+/// Claude Code 的 settings.json / settings.local.json / 托管策略分层处理
+pub struct ClaudeSettingsLayerService;
+
+impl ClaudeSettingsLayerService {
+    pub fn layer_path(layer: ClaudeSettingsLayer) -> PathBuf {
+        match layer {
+            ClaudeSettingsLayer::User => get_claude_user_settings_path(),
+            ClaudeSettingsLayer::Local => get_claude_local_settings_path(),
+            ClaudeSettingsLayer::Managed => get_claude_managed_settings_path(),
+        }
+    }
+
+    /// 读取各层级并给出合并结果，以及 CLI Hub 写入层中被覆盖的键
+    pub fn effective() -> ClaudeEffectiveSettings {
+        let target = get_claude_settings_layer();
+        let mut infos = Vec::new();
+        let mut loaded = Vec::new();
+        for layer in LAYERS {
+            let path = Self::layer_path(layer);
+            let exists = path.exists();
+            let mut error = None;
+            if exists {
+                match read_json_file::<Value>(&path) {
+                    Ok(value) if value.is_object() => loaded.push((layer, value)),
+                    Ok(_) => error = Some("根节点不是 JSON 对象".to_string()),
+                    Err(e) => error = Some(e.to_string()),
+                }
+            }
+            infos.push(ClaudeLayerInfo {
+                layer,
+                path: path.to_string_lossy().to_string(),
+                exists,
+                target: layer == target,
+                error,
+            });
+        }
+
+        let mut effective = resolve(&loaded, target);
+        effective.layers = infos;
+        effective
+    }
+
+    /// 写入目标层后，settings 中哪些键会被更高优先级的层级覆盖
+    pub fn shadowed(settings: &Value) -> Vec<ClaudeSettingOverride> {
+        let target = get_claude_settings_layer();
+        let mut loaded: Vec<(ClaudeSettingsLayer, Value)> = LAYERS
+            .into_iter()
+            .filter(|layer| rank(*layer) > rank(target))
+            .filter_map(|layer| {
+                let path = Self::layer_path(layer);
+                if !path.exists() {
+                    return None;
+                }
+                read_json_file::<Value>(&path).ok().map(|v| (layer, v))
+            })
+            .collect();
+        loaded.insert(0, (target, settings.clone()));
+        resolve(&loaded, target).overrides
+    }
+}
+
+fn rank(layer: ClaudeSettingsLayer) -> usize {
+    LAYERS.iter().position(|l| *l == layer).unwrap_or_default()
+}
+
+/// layers 须按优先级从低到高排列
+fn resolve(
+    layers: &[(ClaudeSettingsLayer, Value)],
+    target: ClaudeSettingsLayer,
+) -> ClaudeEffectiveSettings {
+    let mut merged = Map::new();
+    let mut sources = BTreeMap::new();
+    let mut leaves_by_layer = Vec::new();
+    for (layer, value) in layers {
+        if let Some(obj) = value.as_object() {
+            merge_into(&mut merged, &mut sources, obj, "", *layer);
+        }
+        let mut leaves = BTreeMap::new();
+        flatten(value, "", &mut leaves);
+        leaves_by_layer.push((*layer, leaves));
+    }
+
+    let mut overrides = Vec::new();
+    if let Some((_, written)) = leaves_by_layer.iter().find(|(l, _)| *l == target) {
+        for (key, value) in written {
+            let winner = leaves_by_layer
+                .iter()
+                .rev()
+                .find(|(l, leaves)| rank(*l) > rank(target) && leaves.contains_key(key));
+            if let Some((layer, leaves)) = winner {
+                if leaves[key] != *value {
+                    overrides.push(ClaudeSettingOverride {
+                        key: key.clone(),
+                        value: value.clone(),
+                        overridden_by: *layer,
+                        effective: leaves[key].clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    ClaudeEffectiveSettings {
+        layers: Vec::new(),
+        merged: Value::Object(merged),
+        sources,
+        overrides,
+    }
+}
+
+/// 对象逐层深度合并，其余类型（包括数组）整体替换
+fn merge_into(
+    merged: &mut Map<String, Value>,
+    sources: &mut BTreeMap<String, ClaudeSettingsLayer>,
+    incoming: &Map<String, Value>,
+    prefix: &str,
+    layer: ClaudeSettingsLayer,
+) {
+    for (key, value) in incoming {
+        let path = join_key(prefix, key);
+        match (merged.get_mut(key), value) {
+            (Some(Value::Object(existing)), Value::Object(obj)) => {
+                merge_into(existing, sources, obj, &path, layer);
+            }
+            (_, Value::Object(obj)) => {
+                sources.retain(|k, _| !is_within(k, &path));
+                let mut fresh = Map::new();
+                merge_into(&mut fresh, sources, obj, &path, layer);
+                merged.insert(key.clone(), Value::Object(fresh));
+            }
+            _ => {
+                sources.retain(|k, _| !is_within(k, &path));
+                sources.insert(path, layer);
+                merged.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+fn flatten(value: &Value, prefix: &str, out: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(obj) => {
+            for (key, child) in obj {
+                flatten(child, &join_key(prefix, key), out);
+            }
+        }
+        _ if !prefix.is_empty() => {
+            out.insert(prefix.to_string(), value.clone());
+        }
+        _ => {}
+    }
+}
+
+fn join_key(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{prefix}.{key}")
+    }
+}
+
+fn is_within(key: &str, path: &str) -> bool {
+    key == path || key.starts_with(&format!("{path}."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn merges_layers_and_reports_overridden_keys() {
+        let user = json!({
+            "env": { "ANTHROPIC_BASE_URL": "https://relay.example", "ANTHROPIC_AUTH_TOKEN": "sk-1" },
+            "model": "sonnet"
+        });
+        let local = json!({ "env": { "ANTHROPIC_BASE_URL": "http://localhost:8080" } });
+        let managed = json!({ "model": "sonnet", "permissions": { "deny": ["Bash"] } });
+        let layers = [
+            (ClaudeSettingsLayer::User, user),
+            (ClaudeSettingsLayer::Local, local),
+            (ClaudeSettingsLayer::Managed, managed),
+        ];
+
+        let effective = resolve(&layers, ClaudeSettingsLayer::User);
+        assert_eq!(
+            effective.merged["env"]["ANTHROPIC_BASE_URL"],
+            "http://localhost:8080"
+        );
+        assert_eq!(effective.merged["env"]["ANTHROPIC_AUTH_TOKEN"], "sk-1");
+        assert_eq!(
+            effective.sources["env.ANTHROPIC_BASE_URL"],
+            ClaudeSettingsLayer::Local
+        );
+        assert_eq!(
+            effective.sources["permissions.deny"],
+            ClaudeSettingsLayer::Managed
+        );
+
+        // 相同的值不算覆盖
+        assert_eq!(effective.overrides.len(), 1);
+        let base_url = &effective.overrides[0];
+        assert_eq!(base_url.key, "env.ANTHROPIC_BASE_URL");
+        assert_eq!(base_url.overridden_by, ClaudeSettingsLayer::Local);
+        assert_eq!(base_url.effective, "http://localhost:8080");
+
+        assert!(resolve(&layers, ClaudeSettingsLayer::Local)
+            .overrides
+            .is_empty());
+    }
+}
@@ -0,0 +1,416 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::app_config::AppType;
+use crate::config::{file_exists, get_claude_settings_path, read_json_file, write_json_file};
+use crate::error::AppError;
+use crate::services::claude_hooks::{global_snippet, provider_settings, update_scope};
+use crate::services::sync_pause::is_management_paused;
+use crate::services::WriteAccessService;
+use crate::store::AppState;
+
+/// `Tool`、`Tool(specifier)` 或 `mcp__server[__tool]`
+static RULE_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:mcp__[\w-]+(?:__[\w-]+)?|[A-Z][A-Za-z]*(?:\(.+\))?)$").expect("valid regex")
+});
+
+const DEFAULT_MODES: [&str; 4] = ["default", "acceptEdits", "plan", "bypassPermissions"];
+
+/// settings.json 中 `permissions` 的规则列表
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PermissionList {
+    /// 无需确认直接执行
+    Allow,
+    /// 始终拒绝
+    Deny,
+    /// 每次都询问
+    Ask,
+}
+
+impl PermissionList {
+    const ALL: [PermissionList; 3] = [Self::Allow, Self::Deny, Self::Ask];
+
+    fn key(self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Deny => "deny",
+            Self::Ask => "ask",
+        }
+    }
+}
+
+/// Claude 的工具权限：自动放行、拒绝、询问的规则与默认模式
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudePermissions {
+    #[serde(default)]
+    pub allow: Vec<String>,
+    #[serde(default)]
+    pub deny: Vec<String>,
+    #[serde(default)]
+    pub ask: Vec<String>,
+    /// default / acceptEdits / plan / bypassPermissions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_mode: Option<String>,
+}
+
+impl ClaudePermissions {
+    fn list(&self, list: PermissionList) -> &Vec<String> {
+        match list {
+            PermissionList::Allow => &self.allow,
+            PermissionList::Deny => &self.deny,
+            PermissionList::Ask => &self.ask,
+        }
+    }
+
+    fn list_mut(&mut self, list: PermissionList) -> &mut Vec<String> {
+        match list {
+            PermissionList::Allow => &mut self.allow,
+            PermissionList::Deny => &mut self.deny,
+            PermissionList::Ask => &mut self.ask,
+        }
+    }
+
+    /// 合并另一组权限：规则取并集，other 中设置的默认模式优先
+    fn merge(&mut self, other: &ClaudePermissions) {
+        for list in PermissionList::ALL {
+            for rule in other.list(list) {
+                if !self.list(list).contains(rule) {
+                    self.list_mut(list).push(rule.clone());
+                }
+            }
+        }
+        if other.default_mode.is_some() {
+            self.default_mode = other.default_mode.clone();
+        }
+    }
+}
+
+/// 内置的权限预设，应用时与现有规则合并
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionPreset {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub permissions: ClaudePermissions,
+}
+
+fn rules(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+/// Claude 工具权限管理，作用范围与钩子相同：单个供应商或通用配置片段（全局）
+pub struct ClaudePermissionService;
+
+impl ClaudePermissionService {
+    pub fn presets() -> Vec<PermissionPreset> {
+        vec![
+            PermissionPreset {
+                id: "readOnly",
+                name: "只读模式",
+                description: "放行读取与搜索，禁止修改文件和执行命令",
+                permissions: ClaudePermissions {
+                    allow: rules(&["Read", "Glob", "Grep", "LS"]),
+                    deny: rules(&["Edit", "MultiEdit", "Write", "NotebookEdit", "Bash"]),
+                    ..Default::default()
+                },
+            },
+            PermissionPreset {
+                id: "acceptEdits",
+                name: "自动接受编辑",
+                description: "文件修改无需确认，执行命令仍需询问",
+                permissions: ClaudePermissions {
+                    ask: rules(&["Bash"]),
+                    default_mode: Some("acceptEdits".to_string()),
+                    ..Default::default()
+                },
+            },
+            PermissionPreset {
+                id: "noNetwork",
+                name: "禁止联网",
+                description: "拒绝网页抓取、搜索以及 curl / wget 命令",
+                permissions: ClaudePermissions {
+                    deny: rules(&["WebFetch", "WebSearch", "Bash(curl:*)", "Bash(wget:*)"]),
+                    ..Default::default()
+                },
+            },
+        ]
+    }
+
+    /// 读取权限；provider_id 为 None 时读取通用配置片段中的全局权限
+    pub fn get(state: &AppState, provider_id: Option<&str>) -> Result<ClaudePermissions, AppError> {
+        let settings = match provider_id {
+            Some(id) => provider_settings(state, id)?,
+            None => global_snippet(state)?,
+        };
+        Ok(read_permissions(&settings))
+    }
+
+    /// 整体替换规则，保存前校验并去重
+    pub fn set(
+        state: &AppState,
+        provider_id: Option<&str>,
+        permissions: ClaudePermissions,
+    ) -> Result<ClaudePermissions, AppError> {
+        let permissions = validate(permissions)?;
+        update_scope(state, provider_id, |settings| {
+            write_permissions(settings, &permissions)
+        })?;
+        Self::sync_live(state)?;
+        Ok(permissions)
+    }
+
+    pub fn add_rule(
+        state: &AppState,
+        provider_id: Option<&str>,
+        list: PermissionList,
+        rule: &str,
+    ) -> Result<ClaudePermissions, AppError> {
+        let mut permissions = Self::get(state, provider_id)?;
+        permissions.list_mut(list).push(rule.to_string());
+        Self::set(state, provider_id, permissions)
+    }
+
+    pub fn remove_rule(
+        state: &AppState,
+        provider_id: Option<&str>,
+        list: PermissionList,
+        rule: &str,
+    ) -> Result<ClaudePermissions, AppError> {
+        let mut permissions = Self::get(state, provider_id)?;
+        let rules = permissions.list_mut(list);
+        let before = rules.len();
+        rules.retain(|r| r != rule.trim());
+        if rules.len() == before {
+            return Err(AppError::localized(
+                "claude_permissions.rule_not_found",
+                format!("规则不存在: {rule}"),
+                format!("Rule not found: {rule}"),
+            ));
+        }
+        Self::set(state, provider_id, permissions)
+    }
+
+    /// 把预设合并进现有规则
+    pub fn apply_preset(
+        state: &AppState,
+        provider_id: Option<&str>,
+        preset_id: &str,
+    ) -> Result<ClaudePermissions, AppError> {
+        let preset = Self::presets()
+            .into_iter()
+            .find(|p| p.id == preset_id)
+            .ok_or_else(|| {
+                AppError::localized(
+                    "claude_permissions.preset_not_found",
+                    format!("未找到权限预设: {preset_id}"),
+                    format!("Permission preset not found: {preset_id}"),
+                )
+            })?;
+        let mut permissions = Self::get(state, provider_id)?;
+        // 预设中的拒绝规则优先：先移出其他列表，避免与已有的放行规则冲突
+        for rule in &preset.permissions.deny {
+            permissions.allow.retain(|r| r != rule);
+            permissions.ask.retain(|r| r != rule);
+        }
+        permissions.merge(&preset.permissions);
+        Self::set(state, provider_id, permissions)
+    }
+
+    /// 把当前供应商与全局权限合并写入 settings.json，内容未变化时不写文件
+    ///
+    /// 只改动 allow / deny / ask / defaultMode，permissions 中的其他字段保持不变
+    pub fn sync_live(state: &AppState) -> Result<(), AppError> {
+        let Some(current) = state
+            .storage
+            .get_current_provider(AppType::Claude.as_str())?
+        else {
+            return Ok(());
+        };
+        Self::sync_live_for(state, &provider_settings(state, &current)?)
+    }
+
+    /// 以给定供应商的 settingsConfig 代替当前供应商合并权限
+    pub(crate) fn sync_live_for(state: &AppState, settings: &Value) -> Result<(), AppError> {
+        if is_management_paused() {
+            return Ok(());
+        }
+        let mut permissions = read_permissions(&global_snippet(state)?);
+        permissions.merge(&read_permissions(settings));
+
+        let path = get_claude_settings_path();
+        if !file_exists(&path) && permissions == ClaudePermissions::default() {
+            return Ok(());
+        }
+        let mut live: Value = if file_exists(&path) {
+            read_json_file(&path)?
+        } else {
+            json!({})
+        };
+        let before = live.get("permissions").cloned();
+        write_permissions(&mut live, &permissions)?;
+        if live.get("permissions") == before.as_ref() {
+            return Ok(());
+        }
+        WriteAccessService::ensure_app_writable(&AppType::Claude)?;
+        write_json_file(&path, &live)
+    }
+}
+
+fn read_permissions(settings: &Value) -> ClaudePermissions {
+    let Some(section) = settings.get("permissions") else {
+        return ClaudePermissions::default();
+    };
+    let strings = |key: &str| -> Vec<String> {
+        section
+            .get(key)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect()
+    };
+    ClaudePermissions {
+        allow: strings("allow"),
+        deny: strings("deny"),
+        ask: strings("ask"),
+        default_mode: section
+            .get("defaultMode")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+    }
+}
+
+/// 写回 `permissions`，空列表与未设置的默认模式会被移除；整个对象为空时移除该字段
+fn write_permissions(
+    settings: &mut Value,
+    permissions: &ClaudePermissions,
+) -> Result<(), AppError> {
+    let obj = settings
+        .as_object_mut()
+        .ok_or_else(|| AppError::Config("Claude 配置必须是 JSON 对象".to_string()))?;
+    let mut section = obj
+        .get("permissions")
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default();
+    for list in PermissionList::ALL {
+        let rules = permissions.list(list);
+        if rules.is_empty() {
+            section.remove(list.key());
+        } else {
+            section.insert(list.key().to_string(), json!(rules));
+        }
+    }
+    match &permissions.default_mode {
+        Some(mode) => section.insert("defaultMode".to_string(), json!(mode)),
+        None => section.remove("defaultMode"),
+    };
+
+    if section.is_empty() {
+        obj.remove("permissions");
+    } else {
+        obj.insert("permissions".to_string(), Value::Object(section));
+    }
+    Ok(())
+}
+
+fn validate(mut permissions: ClaudePermissions) -> Result<ClaudePermissions, AppError> {
+    for list in PermissionList::ALL {
+        let mut seen: Vec<String> = Vec::new();
+        for rule in permissions.list(list) {
+            let rule = rule.trim().to_string();
+            if !RULE_PATTERN.is_match(&rule) {
+                return Err(AppError::localized(
+                    "claude_permissions.invalid_rule",
+                    format!("无效的权限规则: {rule}（格式为 Tool 或 Tool(参数)）"),
+                    format!("Invalid permission rule: {rule} (expected Tool or Tool(specifier))"),
+                ));
+            }
+            if !seen.contains(&rule) {
+                seen.push(rule);
+            }
+        }
+        *permissions.list_mut(list) = seen;
+    }
+
+    if let Some(rule) = permissions
+        .allow
+        .iter()
+        .find(|rule| permissions.deny.contains(rule))
+    {
+        return Err(AppError::localized(
+            "claude_permissions.conflict",
+            format!("规则同时出现在放行与拒绝列表中: {rule}"),
+            format!("The rule is both allowed and denied: {rule}"),
+        ));
+    }
+
+    permissions.default_mode = permissions
+        .default_mode
+        .map(|mode| mode.trim().to_string())
+        .filter(|mode| !mode.is_empty());
+    if let Some(mode) = permissions.default_mode.as_deref() {
+        if !DEFAULT_MODES.contains(&mode) {
+            return Err(AppError::localized(
+                "claude_permissions.invalid_mode",
+                format!("不支持的默认权限模式: {mode}"),
+                format!("Unsupported default permission mode: {mode}"),
+            ));
+        }
+    }
+    Ok(permissions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_rules_and_keeps_unmanaged_permission_fields() {
+        let permissions = ClaudePermissions {
+            allow: rules(&[
+                " Bash(npm run test:*) ",
+                "Bash(npm run test:*)",
+                "mcp__github",
+            ]),
+            deny: rules(&["Read(./.env)"]),
+            ..Default::default()
+        };
+        let permissions = validate(permissions).unwrap();
+        assert_eq!(permissions.allow, ["Bash(npm run test:*)", "mcp__github"]);
+
+        assert!(validate(ClaudePermissions {
+            allow: rules(&["bash"]),
+            ..Default::default()
+        })
+        .is_err());
+        assert!(validate(ClaudePermissions {
+            allow: rules(&["Bash"]),
+            deny: rules(&["Bash"]),
+            ..Default::default()
+        })
+        .is_err());
+
+        let mut settings = json!({
+            "permissions": { "additionalDirectories": ["../docs"], "allow": ["Read"] },
+        });
+        write_permissions(&mut settings, &permissions).unwrap();
+        assert_eq!(
+            settings["permissions"]["additionalDirectories"][0],
+            "../docs"
+        );
+        assert_eq!(read_permissions(&settings), permissions);
+
+        write_permissions(&mut settings, &ClaudePermissions::default()).unwrap();
+        assert_eq!(
+            settings,
+            json!({ "permissions": { "additionalDirectories": ["../docs"] } })
+        );
+    }
+}
@@ -0,0 +1,256 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::app_config::AppType;
+use crate::config::write_text_file;
+use crate::error::AppError;
+use crate::prompt::Prompt;
+use crate::prompt_files::prompt_file_path;
+use crate::services::{is_management_paused, PromptService};
+use crate::store::AppState;
+
+const AGENTS_FILE: &str = "AGENTS.md";
+/// Codex 拼接全局指令与项目文档时使用的分隔行
+const PROJECT_DOC_SEPARATOR: &str = "\n\n--- project-doc ---\n\n";
+/// Codex 默认的 project_doc_max_bytes，只限制项目级文档的总长度
+const PROJECT_DOC_MAX_BYTES: usize = 32 * 1024;
+
+/// AGENTS.md 所在层级
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CodexAgentsLevel {
+    /// `~/.codex/AGENTS.md`，即 Codex 启用的提示词
+    Global,
+    /// 仓库根目录到工作目录之间各层的 `AGENTS.md`
+    Project,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexAgentsFile {
+    pub level: CodexAgentsLevel,
+    pub path: String,
+    pub exists: bool,
+    pub size: u64,
+}
+
+/// Codex 在指定工作目录下实际读到的指令
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexEffectiveAgents {
+    /// 按 Codex 读取顺序列出的各层文件，包括尚不存在的位置
+    pub files: Vec<CodexAgentsFile>,
+    pub merged: String,
+    /// 项目文档超过长度上限，末尾部分不会被 Codex 读取
+    pub truncated: bool,
+}
+
+/// Codex AGENTS.md 分层管理
+///
+/// Codex 先读全局 `~/.codex/AGENTS.md`，再从 Git 仓库根目录逐级读到工作目录，
+/// 项目文档按顺序拼接，整体附在全局指令之后。
+pub struct CodexAgentsService;
+
+impl CodexAgentsService {
+    /// 列出全局及项目各层的 AGENTS.md；未指定项目目录时只返回全局层
+    pub fn detect(project_dir: Option<&Path>) -> Result<Vec<CodexAgentsFile>, AppError> {
+        let mut files = vec![describe(
+            CodexAgentsLevel::Global,
+            prompt_file_path(&AppType::Codex)?,
+        )];
+        if let Some(dir) = project_dir {
+            files.extend(
+                project_dirs(&resolve_dir(dir)?)
+                    .into_iter()
+                    .map(|d| describe(CodexAgentsLevel::Project, d.join(AGENTS_FILE))),
+            );
+        }
+        Ok(files)
+    }
+
+    /// 写入指定层级并返回文件路径；项目层写入 `<project_dir>/AGENTS.md`
+    ///
+    /// 全局层与启用的提示词是同一个文件，下次启用提示词时会回填到提示词中。
+    pub fn write(
+        level: CodexAgentsLevel,
+        project_dir: Option<&Path>,
+        content: &str,
+    ) -> Result<String, AppError> {
+        if level == CodexAgentsLevel::Global && is_management_paused() {
+            return Err(AppError::localized(
+                "codex.agents.paused",
+                "已暂停管理配置文件，无法写入 AGENTS.md",
+                "Config management is paused; AGENTS.md was not written",
+            ));
+        }
+        let path = level_path(level, project_dir)?;
+        write_text_file(&path, content)?;
+        Ok(path.to_string_lossy().to_string())
+    }
+
+    /// 将某一层的 AGENTS.md 导入为 Codex 提示词（不启用），返回提示词 ID
+    pub fn import(
+        state: &AppState,
+        level: CodexAgentsLevel,
+        project_dir: Option<&Path>,
+    ) -> Result<String, AppError> {
+        let path = level_path(level, project_dir)?;
+        if !path.exists() {
+            return Err(AppError::localized(
+                "codex.agents.not_found",
+                format!("文件不存在: {}", path.display()),
+                format!("File not found: {}", path.display()),
+            ));
+        }
+        let content = std::fs::read_to_string(&path).map_err(|e| AppError::io(&path, e))?;
+        let timestamp = chrono::Utc::now().timestamp();
+        let id = format!("agents-{timestamp}");
+        let source = match level {
+            CodexAgentsLevel::Global => "~/.codex".to_string(),
+            CodexAgentsLevel::Project => path
+                .parent()
+                .and_then(|p| p.file_name())
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        };
+        let prompt = Prompt {
+            id: id.clone(),
+            name: format!("AGENTS.md ({source})"),
+            content,
+            description: Some(format!("导入自 {}", path.display())),
+            enabled: false,
+            created_at: Some(timestamp),
+            updated_at: Some(timestamp),
+            version: None,
+        };
+        PromptService::upsert_prompt(state, AppType::Codex, &id, prompt)?;
+        Ok(id)
+    }
+
+    /// 按 Codex 的读取顺序与长度上限合并各层内容
+    pub fn effective(project_dir: Option<&Path>) -> Result<CodexEffectiveAgents, AppError> {
+        let files = Self::detect(project_dir)?;
+        let mut global = None;
+        let mut docs = Vec::new();
+        for file in files.iter().filter(|f| f.exists) {
+            let path = Path::new(&file.path);
+            let content = std::fs::read_to_string(path).map_err(|e| AppError::io(path, e))?;
+            if content.trim().is_empty() {
+                continue;
+            }
+            match file.level {
+                CodexAgentsLevel::Global => global = Some(content),
+                CodexAgentsLevel::Project => docs.push(content),
+            }
+        }
+
+        let (merged, truncated) = merge(global.as_deref(), &docs);
+        Ok(CodexEffectiveAgents {
+            files,
+            merged,
+            truncated,
+        })
+    }
+}
+
+fn describe(level: CodexAgentsLevel, path: PathBuf) -> CodexAgentsFile {
+    let metadata = std::fs::metadata(&path).ok().filter(|m| m.is_file());
+    CodexAgentsFile {
+        level,
+        path: path.to_string_lossy().to_string(),
+        exists: metadata.is_some(),
+        size: metadata.map(|m| m.len()).unwrap_or_default(),
+    }
+}
+
+fn level_path(level: CodexAgentsLevel, project_dir: Option<&Path>) -> Result<PathBuf, AppError> {
+    match level {
+        CodexAgentsLevel::Global => prompt_file_path(&AppType::Codex),
+        CodexAgentsLevel::Project => {
+            let dir = project_dir.ok_or_else(|| {
+                AppError::localized(
+                    "codex.agents.project_required",
+                    "写入项目级 AGENTS.md 需要指定项目目录",
+                    "A project directory is required for project-level AGENTS.md",
+                )
+            })?;
+            Ok(resolve_dir(dir)?.join(AGENTS_FILE))
+        }
+    }
+}
+
+fn resolve_dir(dir: &Path) -> Result<PathBuf, AppError> {
+    if !dir.is_dir() {
+        return Err(AppError::localized(
+            "codex.agents.invalid_dir",
+            format!("项目目录不存在: {}", dir.display()),
+            format!("Project directory does not exist: {}", dir.display()),
+        ));
+    }
+    dir.canonicalize().map_err(|e| AppError::io(dir, e))
+}
+
+/// 从 Git 仓库根目录到 dir 的各级目录；不在仓库内时只有 dir 本身
+fn project_dirs(dir: &Path) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    for ancestor in dir.ancestors() {
+        dirs.push(ancestor.to_path_buf());
+        if ancestor.join(".git").exists() {
+            dirs.reverse();
+            return dirs;
+        }
+    }
+    vec![dir.to_path_buf()]
+}
+
+/// 项目文档按顺序以空行拼接并截断到上限，再附在全局指令之后
+fn merge(global: Option<&str>, docs: &[String]) -> (String, bool) {
+    let mut project = docs.join("\n\n");
+    let truncated = project.len() > PROJECT_DOC_MAX_BYTES;
+    if truncated {
+        let mut end = PROJECT_DOC_MAX_BYTES;
+        while !project.is_char_boundary(end) {
+            end -= 1;
+        }
+        project.truncate(end);
+    }
+
+    let merged = match (global, project.is_empty()) {
+        (Some(global), false) => format!("{global}{PROJECT_DOC_SEPARATOR}{project}"),
+        (Some(global), true) => global.to_string(),
+        (None, _) => project,
+    };
+    (merged, truncated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn walks_from_repo_root_and_merges_in_codex_order() {
+        let root = tempfile::tempdir().unwrap();
+        let nested = root.path().join("crates").join("core");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::create_dir(root.path().join(".git")).unwrap();
+
+        let dirs = project_dirs(&nested);
+        assert_eq!(
+            dirs,
+            [
+                root.path().to_path_buf(),
+                root.path().join("crates"),
+                nested.clone()
+            ]
+        );
+
+        let (merged, truncated) = merge(Some("global"), &["repo".to_string(), "crate".to_string()]);
+        assert_eq!(merged, "global\n\n--- project-doc ---\n\nrepo\n\ncrate");
+        assert!(!truncated);
+        assert_eq!(merge(Some("global"), &[]).0, "global");
+
+        let (merged, truncated) = merge(None, &["é".repeat(PROJECT_DOC_MAX_BYTES)]);
+        assert!(truncated);
+        assert_eq!(merged.len(), PROJECT_DOC_MAX_BYTES);
+    }
+}
@@ -1,4 +1,5 @@
 use super::provider::ProviderService;
+use super::sync_pause::is_management_paused;
 use crate::app_config::{AppType, MultiAppConfig};
 use crate::error::AppError;
 use crate::provider::Provider;
@@ -133,6 +134,9 @@ impl ConfigService {
 
     /// 同步当前供应商到对应的 live 配置。
     pub fn sync_current_providers_to_live(config: &mut MultiAppConfig) -> Result<(), AppError> {
+        if is_management_paused() {
+            return Ok(());
+        }
         Self::sync_current_provider_for_app(config, &AppType::Claude)?;
         Self::sync_current_provider_for_app(config, &AppType::Codex)?;
         Self::sync_current_provider_for_app(config, &AppType::Gemini)?;
@@ -223,6 +227,8 @@ impl ConfigService {
             fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
         }
 
+        let provider =
+            crate::services::provider::ClaudeFlavorEnv::with_flavor_env(&AppType::Claude, provider);
         write_json_file(&settings_path, &provider.settings_config)?;
 
         let live_after = read_json_file::<serde_json::Value>(&settings_path)?;
@@ -0,0 +1,291 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::app_config::AppType;
+use crate::deeplink::{build_provider_from_request, provider_to_request};
+use crate::mcp;
+use crate::provider::Provider;
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum McpConvertDirection {
+    JsonToToml,
+    TomlToJson,
+}
+
+/// 解析或转换失败的位置，行列从 1 开始
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversionError {
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+    /// 出错的条目，如 MCP 服务器 ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+/// 转换结果；内容有误时通过 error 返回位置供编辑器标注，而不是命令失败
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversionResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ConversionError>,
+}
+
+impl From<Result<String, ConversionError>> for ConversionResult {
+    fn from(result: Result<String, ConversionError>) -> Self {
+        match result {
+            Ok(content) => Self {
+                content: Some(content),
+                error: None,
+            },
+            Err(error) => Self {
+                content: None,
+                error: Some(error),
+            },
+        }
+    }
+}
+
+impl ConversionError {
+    fn message(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            line: None,
+            column: None,
+            path: None,
+        }
+    }
+
+    fn at_entry(path: &str, err: impl std::fmt::Display) -> Self {
+        Self {
+            path: Some(path.to_string()),
+            ..Self::message(err.to_string())
+        }
+    }
+
+    fn from_json(err: serde_json::Error) -> Self {
+        Self {
+            line: Some(err.line()),
+            column: Some(err.column()),
+            ..Self::message(format!("JSON 解析失败: {err}"))
+        }
+    }
+
+    fn from_toml(text: &str, err: toml::de::Error) -> Self {
+        let (line, column) = err.span().map(|span| line_column(text, span.start)).unzip();
+        Self {
+            line,
+            column,
+            ..Self::message(format!("TOML 解析失败: {}", err.message()))
+        }
+    }
+}
+
+pub struct ConfigConvertService;
+
+impl ConfigConvertService {
+    /// MCP 配置在 Claude/Gemini 的 JSON 与 Codex 的 TOML 之间转换
+    ///
+    /// JSON 可以是单个服务器定义、`{id: 定义}` 或 `{"mcpServers": {...}}`；
+    /// TOML 可以是单个条目表，或包含 `[mcp_servers.*]` 的完整片段
+    pub fn convert_mcp(direction: McpConvertDirection, content: &str) -> ConversionResult {
+        match direction {
+            McpConvertDirection::JsonToToml => mcp_json_to_toml(content),
+            McpConvertDirection::TomlToJson => mcp_toml_to_json(content),
+        }
+        .into()
+    }
+
+    /// 把一个应用的供应商配置转换为另一个应用的格式，沿用深链接导入的模板
+    ///
+    /// 输入输出均为 settings_config JSON；Codex 额外接受裸的 config.toml 文本。
+    /// 模型名称与应用相关，跨应用转换时不保留
+    pub fn convert_provider_settings(
+        from: &AppType,
+        to: &AppType,
+        content: &str,
+    ) -> ConversionResult {
+        provider_settings_to(from, to, content).into()
+    }
+}
+
+fn provider_settings_to(
+    from: &AppType,
+    to: &AppType,
+    content: &str,
+) -> Result<String, ConversionError> {
+    let settings = parse_provider_settings(from, content)?;
+    let provider = Provider::with_id(String::new(), "custom".to_string(), settings, None);
+    let mut request = provider_to_request(from, &provider)
+        .map_err(|e| ConversionError::message(e.to_string()))?;
+    // 转换结果只包含连接配置，不携带官网等页面信息
+    request.homepage = None;
+    if from != to {
+        request.model = None;
+        request.haiku_model = None;
+        request.sonnet_model = None;
+        request.opus_model = None;
+    }
+
+    let converted = build_provider_from_request(to, &request)
+        .map_err(|e| ConversionError::message(e.to_string()))?;
+    serde_json::to_string_pretty(&converted.settings_config)
+        .map_err(|e| ConversionError::message(e.to_string()))
+}
+
+fn parse_provider_settings(app: &AppType, content: &str) -> Result<Value, ConversionError> {
+    match serde_json::from_str::<Value>(content) {
+        Ok(value) if value.is_object() => Ok(value),
+        Ok(_) => Err(ConversionError::message("供应商配置必须是 JSON 对象")),
+        Err(e) if matches!(app, AppType::Codex) && !content.trim_start().starts_with('{') => {
+            // 不是 JSON 时按 config.toml 处理，先校验语法
+            toml::from_str::<toml::Table>(content)
+                .map_err(|te| ConversionError::from_toml(content, te))?;
+            log::debug!("按 Codex config.toml 解析供应商配置: {e}");
+            Ok(json!({ "auth": {}, "config": content }))
+        }
+        Err(e) => Err(ConversionError::from_json(e)),
+    }
+}
+
+fn mcp_json_to_toml(content: &str) -> Result<String, ConversionError> {
+    let value: Value = serde_json::from_str(content).map_err(ConversionError::from_json)?;
+    let obj = value
+        .as_object()
+        .ok_or_else(|| ConversionError::message("MCP 配置必须是 JSON 对象"))?;
+
+    if is_server_spec(obj) {
+        mcp::validate_server_spec(&value).map_err(|e| ConversionError::message(e.to_string()))?;
+        let table = mcp::json_server_to_toml_table(&value)
+            .map_err(|e| ConversionError::message(e.to_string()))?;
+        return Ok(table.to_string());
+    }
+
+    let servers = obj
+        .get("mcpServers")
+        .and_then(Value::as_object)
+        .unwrap_or(obj);
+    let mut doc = toml_edit::DocumentMut::new();
+    let mut root = toml_edit::Table::new();
+    root.set_implicit(true);
+    for (id, spec) in servers {
+        mcp::validate_server_spec(spec).map_err(|e| ConversionError::at_entry(id, e))?;
+        let table =
+            mcp::json_server_to_toml_table(spec).map_err(|e| ConversionError::at_entry(id, e))?;
+        root[id.as_str()] = toml_edit::Item::Table(table);
+    }
+    doc["mcp_servers"] = toml_edit::Item::Table(root);
+    Ok(doc.to_string())
+}
+
+fn mcp_toml_to_json(content: &str) -> Result<String, ConversionError> {
+    let root: toml::Table =
+        toml::from_str(content).map_err(|e| ConversionError::from_toml(content, e))?;
+
+    let servers = root
+        .get("mcp_servers")
+        .or_else(|| root.get("mcp").and_then(|m| m.get("servers")))
+        .and_then(toml::Value::as_table);
+
+    let value = match servers {
+        Some(servers) => {
+            let mut out = Map::new();
+            for (id, entry) in servers {
+                let table = entry
+                    .as_table()
+                    .ok_or_else(|| ConversionError::at_entry(id, "MCP 条目必须是 TOML 表"))?;
+                let spec = mcp::codex_entry_to_spec(table)
+                    .map_err(|e| ConversionError::at_entry(id, e))?;
+                out.insert(id.clone(), spec);
+            }
+            json!({ "mcpServers": out })
+        }
+        None => {
+            mcp::codex_entry_to_spec(&root).map_err(|e| ConversionError::message(e.to_string()))?
+        }
+    };
+
+    serde_json::to_string_pretty(&value).map_err(|e| ConversionError::message(e.to_string()))
+}
+
+fn is_server_spec(obj: &Map<String, Value>) -> bool {
+    ["type", "command", "url"]
+        .iter()
+        .any(|key| obj.get(*key).is_some_and(Value::is_string))
+}
+
+fn line_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .map_or(0, |last| last.chars().count())
+        + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mcp_round_trip_and_error_location() {
+        let json_text = r#"{"mcpServers": {"fs": {"type": "stdio", "command": "npx", "args": ["-y", "fs"], "env": {"ROOT": "/tmp"}}}}"#;
+        let toml_text =
+            ConfigConvertService::convert_mcp(McpConvertDirection::JsonToToml, json_text)
+                .content
+                .expect("json converts to toml");
+        assert!(toml_text.contains("[mcp_servers.fs]"));
+
+        let back = ConfigConvertService::convert_mcp(McpConvertDirection::TomlToJson, &toml_text)
+            .content
+            .expect("toml converts back");
+        let back: Value = serde_json::from_str(&back).unwrap();
+        assert_eq!(back["mcpServers"]["fs"]["command"], "npx");
+        assert_eq!(back["mcpServers"]["fs"]["env"]["ROOT"], "/tmp");
+
+        let broken = ConfigConvertService::convert_mcp(
+            McpConvertDirection::TomlToJson,
+            "[mcp_servers.fs]\ncommand = \"npx\"\nargs = [\n",
+        );
+        let error = broken.error.expect("broken toml reports an error");
+        assert!(error.line.is_some_and(|line| line >= 3));
+
+        let missing = ConfigConvertService::convert_mcp(
+            McpConvertDirection::JsonToToml,
+            r#"{"a": {"type": "stdio"}}"#,
+        );
+        assert_eq!(missing.error.unwrap().path.as_deref(), Some("a"));
+    }
+
+    #[test]
+    fn provider_settings_convert_between_apps() {
+        let claude = r#"{"env": {"ANTHROPIC_AUTH_TOKEN": "sk-1", "ANTHROPIC_BASE_URL": "https://relay.example/v1", "ANTHROPIC_MODEL": "claude-x"}}"#;
+        let result = ConfigConvertService::convert_provider_settings(
+            &AppType::Claude,
+            &AppType::Gemini,
+            claude,
+        );
+        let gemini: Value = serde_json::from_str(&result.content.unwrap()).unwrap();
+        assert_eq!(gemini["env"]["GEMINI_API_KEY"], "sk-1");
+        assert_eq!(
+            gemini["env"]["GOOGLE_GEMINI_BASE_URL"],
+            "https://relay.example/v1"
+        );
+        assert!(gemini["env"].get("GEMINI_MODEL").is_none());
+
+        let result = ConfigConvertService::convert_provider_settings(
+            &AppType::Claude,
+            &AppType::Codex,
+            "{\"env\": ",
+        );
+        assert_eq!(result.error.unwrap().line, Some(1));
+    }
+}
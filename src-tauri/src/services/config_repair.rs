@@ -0,0 +1,231 @@
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::app_config::AppType;
+use crate::codex_config::{get_codex_auth_path, get_codex_config_path};
+use crate::config::{get_claude_mcp_path, get_claude_settings_path};
+use crate::error::AppError;
+use crate::gemini_config::get_gemini_settings_path;
+use crate::services::mcp::McpService;
+use crate::services::provider::LiveConfigSync;
+use crate::services::sync_pause::is_management_paused;
+use crate::store::AppState;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileFormat {
+    Json,
+    Toml,
+}
+
+/// 检测到的损坏文件
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CorruptedLiveFile {
+    pub path: String,
+    pub error: String,
+    /// 修复时原文件的备份位置
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backup_path: Option<String>,
+}
+
+/// 一次修复的结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveConfigRepairReport {
+    pub app_type: String,
+    pub files: Vec<CorruptedLiveFile>,
+    /// 用于重建 live 配置的当前供应商
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider_id: Option<String>,
+    /// 是否已按数据库中的启用状态重新同步 MCP
+    pub mcp_synced: bool,
+}
+
+impl LiveConfigRepairReport {
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+/// 检测无法解析的 live 配置文件，备份后按数据库中的当前供应商与 MCP 状态重建
+pub struct ConfigRepairService;
+
+impl ConfigRepairService {
+    /// 列出指定应用中已损坏的 live 配置文件（不存在的文件不算损坏）
+    pub fn detect(app_type: &AppType) -> Vec<CorruptedLiveFile> {
+        live_files(app_type)
+            .into_iter()
+            .filter_map(|(path, format)| {
+                check_file(&path, format).map(|error| CorruptedLiveFile {
+                    path: path.to_string_lossy().to_string(),
+                    error,
+                    backup_path: None,
+                })
+            })
+            .collect()
+    }
+
+    /// 修复指定应用；没有损坏文件时不做任何写入
+    pub fn repair(state: &AppState, app_type: AppType) -> Result<LiveConfigRepairReport, AppError> {
+        let mut report = LiveConfigRepairReport {
+            app_type: app_type.as_str().to_string(),
+            files: Self::detect(&app_type),
+            provider_id: None,
+            mcp_synced: false,
+        };
+        if report.is_empty() {
+            return Ok(report);
+        }
+        if is_management_paused() {
+            return Err(AppError::localized(
+                "live_config.repair_paused",
+                "管理已暂停，请恢复管理后再修复 live 配置",
+                "Management is paused; resume it before repairing live config",
+            ));
+        }
+
+        for file in report.files.iter_mut() {
+            let backup = backup_corrupted(Path::new(&file.path))?;
+            log::warn!(
+                "live 配置文件损坏，已备份至 {}: {}",
+                backup.display(),
+                file.error
+            );
+            file.backup_path = Some(backup.to_string_lossy().to_string());
+        }
+
+        if let Some(current_id) = state.storage.get_current_provider(app_type.as_str())? {
+            let providers = state.storage.get_all_providers(app_type.as_str())?;
+            if let Some(provider) = providers.get(&current_id) {
+                LiveConfigSync::write_live_snapshot(&app_type, provider)?;
+                report.provider_id = Some(current_id);
+            }
+        }
+
+        McpService::sync_app(state, &app_type)?;
+        report.mcp_synced = true;
+
+        Ok(report)
+    }
+
+    /// 启动时检查全部应用，返回实际发生修复的报告；单个应用失败不影响其他应用
+    pub fn startup_check(state: &AppState) -> Vec<LiveConfigRepairReport> {
+        let mut reports = Vec::new();
+        if is_management_paused() {
+            return reports;
+        }
+        for app_type in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+            match Self::repair(state, app_type.clone()) {
+                Ok(report) if !report.is_empty() => {
+                    log::warn!(
+                        "已修复 {} 的 {} 个损坏的 live 配置文件",
+                        report.app_type,
+                        report.files.len()
+                    );
+                    reports.push(report);
+                }
+                Ok(_) => {}
+                Err(err) => log::error!("修复 {} 的 live 配置失败: {err}", app_type.as_str()),
+            }
+        }
+        reports
+    }
+}
+
+fn live_files(app_type: &AppType) -> Vec<(PathBuf, FileFormat)> {
+    match app_type {
+        AppType::Claude => vec![
+            (get_claude_settings_path(), FileFormat::Json),
+            (get_claude_mcp_path(), FileFormat::Json),
+        ],
+        AppType::Codex => vec![
+            (get_codex_auth_path(), FileFormat::Json),
+            (get_codex_config_path(), FileFormat::Toml),
+        ],
+        AppType::Gemini => vec![(get_gemini_settings_path(), FileFormat::Json)],
+    }
+}
+
+/// 返回解析错误；文件不存在或可正常解析时返回 None
+fn check_file(path: &Path, format: FileFormat) -> Option<String> {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return None,
+        Err(err) => return Some(err.to_string()),
+    };
+    let text = match String::from_utf8(bytes) {
+        Ok(text) => text,
+        Err(err) => return Some(err.to_string()),
+    };
+
+    match format {
+        // 崩溃后常见的空文件同样视为损坏
+        FileFormat::Json => serde_json::from_str::<serde_json::Value>(&text)
+            .err()
+            .map(|e| e.to_string()),
+        FileFormat::Toml => text
+            .parse::<toml_edit::DocumentMut>()
+            .err()
+            .map(|e| e.to_string()),
+    }
+}
+
+fn backup_corrupted(path: &Path) -> Result<PathBuf, AppError> {
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let backup = path.with_file_name(format!(
+        "{file_name}.corrupt-{}",
+        chrono::Local::now().format("%Y%m%d_%H%M%S")
+    ));
+    fs::rename(path, &backup).map_err(|e| AppError::io(path, e))?;
+    Ok(backup)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_file_flags_unparseable_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let json = dir.path().join("settings.json");
+        let toml = dir.path().join("config.toml");
+
+        assert!(
+            check_file(&json, FileFormat::Json).is_none(),
+            "missing file"
+        );
+
+        fs::write(&json, r#"{"env": {}}"#).unwrap();
+        fs::write(&toml, "model = \"gpt-5\"\n").unwrap();
+        assert!(check_file(&json, FileFormat::Json).is_none());
+        assert!(check_file(&toml, FileFormat::Toml).is_none());
+
+        fs::write(&json, r#"{"env": {"#).unwrap();
+        fs::write(&toml, "model = \n[broken").unwrap();
+        assert!(check_file(&json, FileFormat::Json).is_some());
+        assert!(check_file(&toml, FileFormat::Toml).is_some());
+
+        fs::write(&json, "").unwrap();
+        assert!(check_file(&json, FileFormat::Json).is_some(), "empty json");
+    }
+
+    #[test]
+    fn backup_corrupted_moves_file_aside() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("auth.json");
+        fs::write(&path, "{").unwrap();
+
+        let backup = backup_corrupted(&path).unwrap();
+        assert!(!path.exists());
+        assert_eq!(fs::read_to_string(&backup).unwrap(), "{");
+        assert!(backup
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with("auth.json.corrupt-"));
+    }
+}
@@ -0,0 +1,192 @@
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::OsRng;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+use crate::error::AppError;
+use crate::settings::{get_settings, ConfirmationLevel};
+
+/// 确认令牌有效期（秒）
+const TOKEN_TTL_SECS: i64 = 300;
+/// 确认令牌取自系统随机源的 128 位
+const TOKEN_BYTES: usize = 16;
+
+/// 受确认策略保护的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConfirmAction {
+    DeleteProvider,
+    DeleteMcpServer,
+    ImportSql,
+    RestoreBackup,
+}
+
+/// 下发给前端的确认挑战
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfirmationChallenge {
+    pub action: ConfirmAction,
+    pub resource: String,
+    pub level: ConfirmationLevel,
+    /// 策略为无需确认时为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// 需要用户输入的文本（仅 typeName 级别）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_text: Option<String>,
+    pub expires_at: i64,
+}
+
+/// 调用方随破坏性命令一起提交的确认信息
+#[derive(Debug, Clone, Default)]
+pub struct ConfirmationInput {
+    pub token: Option<String>,
+    pub text: Option<String>,
+    /// 自动化场景显式跳过确认
+    pub force: bool,
+}
+
+impl ConfirmationInput {
+    pub fn new(token: Option<String>, text: Option<String>, force: Option<bool>) -> Self {
+        Self {
+            token,
+            text,
+            force: force.unwrap_or(false),
+        }
+    }
+}
+
+struct PendingConfirmation {
+    action: ConfirmAction,
+    resource: String,
+    expected_text: Option<String>,
+    expires_at: i64,
+}
+
+static PENDING: Lazy<Mutex<HashMap<String, PendingConfirmation>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 破坏性操作的确认策略
+///
+/// 前端先调用 `request` 获取令牌，再把令牌（及 typeName 级别下用户输入的名称）
+/// 随破坏性命令提交，由命令层调用 `verify` 校验。令牌一次性有效。
+pub struct ConfirmationService;
+
+impl ConfirmationService {
+    pub fn level_for(action: ConfirmAction) -> ConfirmationLevel {
+        let policies = get_settings().confirmation_policies;
+        match action {
+            ConfirmAction::DeleteProvider => policies.delete_provider,
+            ConfirmAction::DeleteMcpServer => policies.delete_mcp_server,
+            ConfirmAction::ImportSql => policies.import_sql,
+            ConfirmAction::RestoreBackup => policies.restore_backup,
+        }
+    }
+
+    /// 为指定资源签发确认令牌
+    ///
+    /// `display_name` 为 typeName 级别下用户需要输入的名称。
+    pub fn request(
+        action: ConfirmAction,
+        resource: &str,
+        display_name: &str,
+    ) -> Result<ConfirmationChallenge, AppError> {
+        let level = Self::level_for(action);
+        let expires_at = chrono::Utc::now().timestamp() + TOKEN_TTL_SECS;
+        let expected_text =
+            (level == ConfirmationLevel::TypeName).then(|| display_name.to_string());
+
+        let token = if level == ConfirmationLevel::None {
+            None
+        } else {
+            let token = new_token();
+            let mut pending = PENDING.lock()?;
+            let now = chrono::Utc::now().timestamp();
+            pending.retain(|_, p| p.expires_at > now);
+            pending.insert(
+                token.clone(),
+                PendingConfirmation {
+                    action,
+                    resource: resource.to_string(),
+                    expected_text: expected_text.clone(),
+                    expires_at,
+                },
+            );
+            Some(token)
+        };
+
+        Ok(ConfirmationChallenge {
+            action,
+            resource: resource.to_string(),
+            level,
+            token,
+            expected_text,
+            expires_at,
+        })
+    }
+
+    /// 校验确认信息；策略为无需确认或显式 `force` 时直接通过
+    pub fn verify(
+        action: ConfirmAction,
+        resource: &str,
+        input: &ConfirmationInput,
+    ) -> Result<(), AppError> {
+        if Self::level_for(action) == ConfirmationLevel::None {
+            return Ok(());
+        }
+        if input.force {
+            log::info!("已显式跳过确认: {action:?} {resource}");
+            return Ok(());
+        }
+
+        let Some(token) = input.token.as_deref() else {
+            return Err(AppError::localized(
+                "confirmation.required",
+                "该操作需要确认，请先获取确认令牌",
+                "This action requires confirmation; request a confirmation token first",
+            ));
+        };
+
+        let pending = PENDING
+            .lock()?
+            .remove(token)
+            .filter(|p| p.action == action && p.resource == resource)
+            .filter(|p| p.expires_at > chrono::Utc::now().timestamp())
+            .ok_or_else(|| {
+                AppError::localized(
+                    "confirmation.invalid_token",
+                    "确认令牌无效或已过期",
+                    "Confirmation token is invalid or expired",
+                )
+            })?;
+
+        if let Some(expected) = pending.expected_text.as_deref() {
+            if input.text.as_deref().map(str::trim) != Some(expected) {
+                return Err(AppError::localized(
+                    "confirmation.text_mismatch",
+                    format!("请输入 \"{expected}\" 以确认"),
+                    format!("Type \"{expected}\" to confirm"),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// 备份文件路径或 ID 的显示名（去掉目录与扩展名）
+pub fn backup_display_name(resource: &str) -> String {
+    Path::new(resource)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| resource.to_string())
+}
+
+fn new_token() -> String {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
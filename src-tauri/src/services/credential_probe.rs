@@ -0,0 +1,142 @@
+use reqwest::{Client, RequestBuilder, StatusCode};
+use std::time::Duration;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::{CredentialStatus, Provider};
+use crate::services::provider::CredentialsExtractor;
+use crate::store::AppState;
+
+/// 探测请求超时；探测只是辅助手段，不能明显拖慢切换
+const PROBE_TIMEOUT_SECS: u64 = 5;
+
+/// 凭证探测结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProbeOutcome {
+    Valid,
+    /// 端点返回 401/403
+    Invalid(u16),
+    /// 无法判断（缺少凭证、网络错误或其他状态码）
+    Unknown(String),
+}
+
+/// 切换前使用即将写入的 Key 请求模型列表，识别已失效的凭证
+pub struct CredentialProbeService;
+
+impl CredentialProbeService {
+    pub async fn probe(app_type: &AppType, provider: &Provider) -> ProbeOutcome {
+        let meta = provider.meta.as_ref();
+        if meta.is_some_and(|m| m.local_model.is_some() || m.claude_flavor.is_some()) {
+            return ProbeOutcome::Unknown("供应商不使用 API Key".to_string());
+        }
+
+        let (api_key, base_url) =
+            match CredentialsExtractor::extract_credentials(provider, app_type) {
+                Ok(credentials) => credentials,
+                Err(err) => return ProbeOutcome::Unknown(err.to_string()),
+            };
+
+        let client = match Client::builder()
+            .timeout(Duration::from_secs(PROBE_TIMEOUT_SECS))
+            .build()
+        {
+            Ok(client) => client,
+            Err(err) => return ProbeOutcome::Unknown(err.to_string()),
+        };
+
+        let request = probe_request(&client, app_type, &api_key, &base_url);
+        match request.send().await {
+            Ok(resp) => classify_status(resp.status()),
+            Err(err) => ProbeOutcome::Unknown(err.to_string()),
+        }
+    }
+
+    /// 将探测结果写入供应商元数据；有效时清除此前的失效标记
+    ///
+    /// 返回是否发生了变更。
+    pub fn record(
+        state: &AppState,
+        app_type: &AppType,
+        provider: &Provider,
+        outcome: &ProbeOutcome,
+    ) -> Result<bool, AppError> {
+        let status = match outcome {
+            ProbeOutcome::Invalid(code) => Some(CredentialStatus {
+                valid: false,
+                checked_at: chrono::Utc::now().timestamp(),
+                http_status: Some(*code),
+            }),
+            ProbeOutcome::Valid => None,
+            ProbeOutcome::Unknown(_) => return Ok(false),
+        };
+
+        let previous = provider
+            .meta
+            .as_ref()
+            .and_then(|m| m.credential_status.as_ref());
+        if previous.is_none() && status.is_none() {
+            return Ok(false);
+        }
+
+        let mut updated = provider.clone();
+        updated
+            .meta
+            .get_or_insert_with(Default::default)
+            .credential_status = status;
+        state.storage.save_provider(app_type.as_str(), &updated)?;
+        Ok(true)
+    }
+}
+
+fn probe_request(
+    client: &Client,
+    app_type: &AppType,
+    api_key: &str,
+    base_url: &str,
+) -> RequestBuilder {
+    let base_url = base_url.trim().trim_end_matches('/');
+    match app_type {
+        AppType::Claude => client
+            .get(format!("{base_url}/v1/models"))
+            .header("x-api-key", api_key)
+            .bearer_auth(api_key)
+            .header("anthropic-version", "2023-06-01"),
+        AppType::Codex => client
+            .get(format!("{base_url}/models"))
+            .bearer_auth(api_key),
+        AppType::Gemini => client
+            .get(format!("{base_url}/v1beta/models"))
+            .header("x-goog-api-key", api_key),
+    }
+}
+
+/// 只有 401/403 视为凭证失效，其余状态码（如中转站不支持 models 接口）不下结论
+fn classify_status(status: StatusCode) -> ProbeOutcome {
+    match status {
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => ProbeOutcome::Invalid(status.as_u16()),
+        s if s.is_success() => ProbeOutcome::Valid,
+        s => ProbeOutcome::Unknown(format!("HTTP {}", s.as_u16())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_status_only_flags_auth_errors() {
+        assert_eq!(
+            classify_status(StatusCode::UNAUTHORIZED),
+            ProbeOutcome::Invalid(401)
+        );
+        assert_eq!(
+            classify_status(StatusCode::FORBIDDEN),
+            ProbeOutcome::Invalid(403)
+        );
+        assert_eq!(classify_status(StatusCode::OK), ProbeOutcome::Valid);
+        assert!(matches!(
+            classify_status(StatusCode::NOT_FOUND),
+            ProbeOutcome::Unknown(_)
+        ));
+    }
+}
@@ -0,0 +1,311 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::config::{get_app_config_dir, get_db_path, DB_FILE_NAME};
+use crate::error::AppError;
+use crate::store::AppState;
+
+/// 网络文件系统类型：SQLite 依赖的文件锁在这些文件系统上不可靠
+const NETWORK_FILESYSTEMS: &[&str] = &[
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smbfs",
+    "smb3",
+    "afpfs",
+    "webdav",
+    "davfs",
+    "fuse.sshfs",
+    "sshfs",
+    "9p",
+    "fuse.rclone",
+    "ceph",
+    "glusterfs",
+];
+
+/// 常见同步盘目录名
+const SYNCED_FOLDER_MARKERS: &[&str] = &[
+    "Dropbox",
+    "OneDrive",
+    "Google Drive",
+    "GoogleDrive",
+    "iCloud Drive",
+    "Mobile Documents",
+    "Nutstore",
+    "坚果云",
+];
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum DbLocationWarning {
+    /// 位于 NFS/SMB 等网络文件系统，多端同时打开可能损坏数据库
+    NetworkFilesystem,
+    /// 位于同步盘，同步客户端可能在写入中途上传文件或生成冲突副本
+    SyncedFolder,
+}
+
+/// 数据库位置及其风险提示
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DbLocationInfo {
+    pub path: String,
+    pub is_default: bool,
+    pub exists: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filesystem: Option<String>,
+    pub warnings: Vec<DbLocationWarning>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DbMoveResult {
+    pub from: String,
+    pub to: String,
+    /// 目标位置原有的文件被改名保留于此
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replaced_backup: Option<String>,
+    pub warnings: Vec<DbLocationWarning>,
+    /// 新位置重启后生效，旧文件保留作为回退；在此之前数据库只读
+    pub restart_required: bool,
+}
+
+pub struct DbLocationService;
+
+impl DbLocationService {
+    pub fn default_path() -> PathBuf {
+        get_app_config_dir().join(DB_FILE_NAME)
+    }
+
+    pub fn current() -> DbLocationInfo {
+        Self::inspect(&get_db_path())
+    }
+
+    pub fn inspect(path: &Path) -> DbLocationInfo {
+        let filesystem = filesystem_type(path);
+        let mut warnings = Vec::new();
+        if is_network_location(path, filesystem.as_deref()) {
+            warnings.push(DbLocationWarning::NetworkFilesystem);
+        }
+        if is_synced_folder(path) {
+            warnings.push(DbLocationWarning::SyncedFolder);
+        }
+
+        DbLocationInfo {
+            path: path.display().to_string(),
+            is_default: path == Self::default_path(),
+            exists: path.exists(),
+            filesystem,
+            warnings,
+        }
+    }
+
+    /// 解析用户输入的目标位置；指向已有目录时使用默认文件名
+    pub fn resolve_target(raw: &str) -> Result<PathBuf, AppError> {
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            return Err(AppError::localized(
+                "db_location.empty",
+                "数据库路径不能为空",
+                "Database path cannot be empty",
+            ));
+        }
+        let path = crate::app_store::resolve_path(trimmed);
+        if !path.is_absolute() {
+            return Err(AppError::localized(
+                "db_location.relative",
+                "数据库路径必须是绝对路径",
+                "Database path must be absolute",
+            ));
+        }
+        Ok(if path.is_dir() {
+            path.join(DB_FILE_NAME)
+        } else {
+            path
+        })
+    }
+
+    /// 把当前数据库复制到新位置并校验完整性，成功后写入 Store；target 为 None 表示迁回默认位置
+    ///
+    /// 复制前即把当前连接切为只读并保持到重启：否则重启前的修改（包括后台任务的写入）
+    /// 只会落在旧文件里，新位置生效后悄悄丢失。复制失败时恢复可写。
+    pub fn relocate(
+        app: &tauri::AppHandle,
+        state: &AppState,
+        target: Option<&str>,
+    ) -> Result<DbMoveResult, AppError> {
+        crate::services::demo::ensure_not_demo()?;
+
+        let from = get_db_path();
+        let to = match target {
+            Some(raw) => Self::resolve_target(raw)?,
+            None => Self::default_path(),
+        };
+        if from == to {
+            return Err(AppError::localized(
+                "db_location.unchanged",
+                "新位置与当前数据库位置相同",
+                "The new location is the same as the current database",
+            ));
+        }
+
+        // 目标处已有文件（如之前迁出后留下的旧库）时改名保留，不直接覆盖
+        let replaced_backup = if to.exists() {
+            let aside = to.with_extension(format!(
+                "db.{}.bak",
+                chrono::Utc::now().format("%Y%m%d_%H%M%S")
+            ));
+            std::fs::rename(&to, &aside).map_err(|e| AppError::io(&to, e))?;
+            Some(aside)
+        } else {
+            None
+        };
+
+        let copied = state.db.set_read_only(true).and_then(|_| {
+            state.db.copy_to_file(&to)?;
+            let store_value = (to != Self::default_path()).then_some(to.as_path());
+            crate::app_store::set_db_path_to_store(app, store_value)
+        });
+        if let Err(e) = copied {
+            let _ = std::fs::remove_file(&to);
+            if let Some(aside) = &replaced_backup {
+                let _ = std::fs::rename(aside, &to);
+            }
+            if let Err(unlock_err) = state.db.set_read_only(false) {
+                log::error!("恢复数据库可写失败: {unlock_err}");
+            }
+            return Err(e);
+        }
+        log::info!("数据库已复制到 {}，重启前保持只读", to.display());
+
+        Ok(DbMoveResult {
+            from: from.display().to_string(),
+            to: to.display().to_string(),
+            replaced_backup: replaced_backup.map(|p| p.display().to_string()),
+            warnings: Self::inspect(&to).warnings,
+            restart_required: true,
+        })
+    }
+}
+
+fn is_network_location(path: &Path, filesystem: Option<&str>) -> bool {
+    // Windows UNC 路径（\\server\share）
+    if path.to_string_lossy().starts_with(r"\\") {
+        return true;
+    }
+    filesystem.is_some_and(|fs| {
+        let fs = fs.to_ascii_lowercase();
+        NETWORK_FILESYSTEMS
+            .iter()
+            .any(|n| fs == *n || fs.starts_with(&format!("{n}.")))
+    })
+}
+
+fn is_synced_folder(path: &Path) -> bool {
+    path.components().any(|c| {
+        let name = c.as_os_str().to_string_lossy();
+        SYNCED_FOLDER_MARKERS
+            .iter()
+            .any(|marker| name == *marker || name.starts_with(&format!("{marker} -")))
+    })
+}
+
+/// 查找路径所在挂载点的文件系统类型
+fn filesystem_type(path: &Path) -> Option<String> {
+    let existing = path.ancestors().find(|p| p.exists())?;
+    let resolved = existing.canonicalize().ok()?;
+    mount_filesystem(&resolved, &read_mount_table())
+}
+
+/// 按最长前缀匹配挂载点
+fn mount_filesystem(path: &Path, mounts: &[(PathBuf, String)]) -> Option<String> {
+    mounts
+        .iter()
+        .filter(|(mount_point, _)| path.starts_with(mount_point))
+        .max_by_key(|(mount_point, _)| mount_point.components().count())
+        .map(|(_, fs)| fs.clone())
+}
+
+#[cfg(target_os = "linux")]
+fn read_mount_table() -> Vec<(PathBuf, String)> {
+    std::fs::read_to_string("/proc/mounts")
+        .map(|text| parse_proc_mounts(&text))
+        .unwrap_or_default()
+}
+
+#[cfg(target_os = "macos")]
+fn read_mount_table() -> Vec<(PathBuf, String)> {
+    std::process::Command::new("mount")
+        .output()
+        .map(|out| parse_bsd_mount(&String::from_utf8_lossy(&out.stdout)))
+        .unwrap_or_default()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn read_mount_table() -> Vec<(PathBuf, String)> {
+    Vec::new()
+}
+
+/// 解析 /proc/mounts：`设备 挂载点 类型 选项 ...`，挂载点中的空格转义为 \040
+#[cfg_attr(not(any(target_os = "linux", test)), allow(dead_code))]
+fn parse_proc_mounts(text: &str) -> Vec<(PathBuf, String)> {
+    text.lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let _device = fields.next()?;
+            let mount_point = fields.next()?.replace("\\040", " ");
+            let fs = fields.next()?;
+            Some((PathBuf::from(mount_point), fs.to_string()))
+        })
+        .collect()
+}
+
+/// 解析 macOS `mount` 输出：`设备 on 挂载点 (类型, 选项...)`
+#[cfg_attr(not(any(target_os = "macos", test)), allow(dead_code))]
+fn parse_bsd_mount(text: &str) -> Vec<(PathBuf, String)> {
+    text.lines()
+        .filter_map(|line| {
+            let (_, rest) = line.split_once(" on ")?;
+            let (mount_point, options) = rest.rsplit_once(" (")?;
+            let fs = options.split([',', ')']).next()?.trim();
+            Some((PathBuf::from(mount_point), fs.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn network_mounts_are_matched_by_longest_prefix() {
+        let linux = parse_proc_mounts(
+            "/dev/sda1 / ext4 rw 0 0\n\
+             server:/export /mnt/my\\040share nfs4 rw 0 0\n",
+        );
+        let fs = mount_filesystem(Path::new("/mnt/my share/cli-hub"), &linux);
+        assert_eq!(fs.as_deref(), Some("nfs4"));
+        assert!(is_network_location(Path::new("/mnt/x"), fs.as_deref()));
+        assert_eq!(
+            mount_filesystem(Path::new("/home/me"), &linux).as_deref(),
+            Some("ext4")
+        );
+
+        let mac = parse_bsd_mount(
+            "/dev/disk3s1 on / (apfs, local, journaled)\n\
+             //me@nas/home on /Volumes/home (smbfs, nodev, nosuid, mounted by me)\n",
+        );
+        let fs = mount_filesystem(Path::new("/Volumes/home/db"), &mac);
+        assert_eq!(fs.as_deref(), Some("smbfs"));
+        assert!(!is_network_location(Path::new("/"), Some("apfs")));
+    }
+
+    #[test]
+    fn synced_folders_are_flagged() {
+        assert!(is_synced_folder(Path::new("/Users/me/Dropbox/cli-hub.db")));
+        assert!(is_synced_folder(Path::new(
+            "/Users/me/Library/Mobile Documents/com~apple~CloudDocs/cli-hub.db"
+        )));
+        assert!(is_synced_folder(Path::new("/home/me/OneDrive - Corp/db")));
+        assert!(!is_synced_folder(Path::new("/home/me/.cli-hub/cli-hub.db")));
+    }
+}
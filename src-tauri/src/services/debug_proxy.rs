@@ -0,0 +1,685 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+use std::borrow::Cow;
+use std::collections::{HashMap, VecDeque};
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::oneshot;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::services::model_fidelity::{models_differ, ModelFidelityService};
+use crate::services::provider::{EndpointManager, LiveConfigSync};
+use crate::store::AppState;
+
+/// 日志环形缓冲区容量，超出后丢弃最早的记录
+const LOG_CAPACITY: usize = 500;
+const MAX_HEAD_BYTES: usize = 64 * 1024;
+const MAX_REQUEST_BODY: usize = 32 * 1024 * 1024;
+/// 记录正文及识别响应模型时最多保留的字节数
+const MAX_CAPTURED_BYTES: usize = 64 * 1024;
+const READ_TIMEOUT_SECS: u64 = 30;
+const CONNECT_TIMEOUT_SECS: u64 = 30;
+/// 不转发给上游的请求头；去掉 accept-encoding 让上游返回未压缩内容，便于识别模型
+const SKIPPED_REQUEST_HEADERS: &[&str] = &[
+    "host",
+    "connection",
+    "keep-alive",
+    "proxy-connection",
+    "proxy-authorization",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+    "content-length",
+    "accept-encoding",
+];
+/// 由代理重新生成的响应头
+const SKIPPED_RESPONSE_HEADERS: &[&str] = &[
+    "connection",
+    "keep-alive",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+    "content-length",
+];
+
+/// 运行中的代理，按应用区分；每个应用同一时间只代理当前供应商
+static PROXIES: Lazy<Mutex<HashMap<String, RunningProxy>>> = Lazy::new(Default::default);
+static LOG: Lazy<Mutex<VecDeque<ProxyLogEntry>>> = Lazy::new(Default::default);
+static NEXT_ENTRY_ID: AtomicU64 = AtomicU64::new(1);
+static MODEL_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#""model(?:Version)?"\s*:\s*"([^"]+)""#).expect("valid model regex"));
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ProxyRoute {
+    app: String,
+    provider_id: String,
+    upstream: String,
+    capture_bodies: bool,
+}
+
+struct RunningProxy {
+    route: ProxyRoute,
+    port: u16,
+    /// 丢弃发送端即通知代理退出
+    _shutdown: oneshot::Sender<()>,
+}
+
+/// 运行中的调试代理
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DebugProxyStatus {
+    pub app: String,
+    pub provider_id: String,
+    /// 真实端点
+    pub upstream: String,
+    /// 写入 live 配置的本机地址
+    pub proxy_url: String,
+    pub capture_bodies: bool,
+}
+
+/// 一次经过代理的请求
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyLogEntry {
+    pub id: u64,
+    pub app: String,
+    pub provider_id: String,
+    /// 请求开始时间（毫秒时间戳）
+    pub started_at: i64,
+    pub method: String,
+    pub path: String,
+    /// 上游返回的状态码；未连上上游时为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    pub duration_ms: u64,
+    pub request_bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_bytes: Option<u64>,
+    /// 请求中指定的模型（Gemini 取自路径）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_model: Option<String>,
+    /// 上游响应中声明的模型
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_model: Option<String>,
+    /// 响应模型与请求模型不一致（忽略日期后缀等版本差异）
+    pub model_mismatch: bool,
+    pub streaming: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_body: Option<String>,
+}
+
+/// 供应商级本地调试代理
+///
+/// 启用后 live 配置的 Base URL 改为 `http://127.0.0.1:<port>`，代理把请求原样转发到
+/// 真实端点，并把方法、路径、状态码、耗时、模型等元数据写入内存中的环形缓冲区。
+/// 默认不记录正文；请求头（含 API Key）始终不记录。
+pub struct DebugProxyService;
+
+impl DebugProxyService {
+    /// 写入 live 前调用：供应商启用调试代理时启动代理并返回指向代理的副本，
+    /// 否则停止该应用的代理并原样返回
+    ///
+    /// 切换预览（写入捕获模式）中不启停代理：已在运行时沿用其端口，否则以端口 0 占位
+    pub fn route_live<'a>(
+        app_type: &AppType,
+        provider: &'a Provider,
+    ) -> Result<Cow<'a, Provider>, AppError> {
+        let enabled = provider
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.debug_proxy.as_ref())
+            .filter(|config| config.enabled);
+        let previewing = crate::config::is_capturing_writes();
+        let Some(config) = enabled else {
+            if !previewing {
+                Self::stop(app_type)?;
+            }
+            return Ok(Cow::Borrowed(provider));
+        };
+        let Some(upstream) = EndpointManager::base_url(app_type, provider) else {
+            if !previewing {
+                log::warn!("供应商 {} 未配置 Base URL，调试代理未启用", provider.id);
+                Self::stop(app_type)?;
+            }
+            return Ok(Cow::Borrowed(provider));
+        };
+        if Self::is_proxy_url(&upstream)? {
+            return Err(AppError::localized(
+                "debug_proxy.loop",
+                "供应商的 Base URL 指向调试代理本身，请先改回真实端点",
+                "The provider's base URL points at the debug proxy itself; restore the real endpoint first",
+            ));
+        }
+
+        let port = if previewing {
+            PROXIES
+                .lock()
+                .ok()
+                .and_then(|proxies| proxies.get(app_type.as_str()).map(|p| p.port))
+                .unwrap_or(0)
+        } else {
+            Self::ensure_running(ProxyRoute {
+                app: app_type.as_str().to_string(),
+                provider_id: provider.id.clone(),
+                upstream,
+                capture_bodies: config.capture_bodies,
+            })?
+        };
+        let mut routed = provider.clone();
+        EndpointManager::set_base_url(app_type, &mut routed, &proxy_url(port));
+        Ok(Cow::Owned(routed))
+    }
+
+    /// live 中的 Base URL 指向调试代理时换回真实端点，避免把代理地址合并回供应商
+    pub fn restore_upstream(app_type: &AppType, live: &mut Value) {
+        let Ok(proxies) = PROXIES.lock() else {
+            return;
+        };
+        let Some(running) = proxies.get(app_type.as_str()) else {
+            return;
+        };
+        let mut wrapper = Provider::with_id(String::new(), String::new(), live.take(), None);
+        if EndpointManager::base_url(app_type, &wrapper).as_deref()
+            == Some(proxy_url(running.port).as_str())
+        {
+            EndpointManager::set_base_url(app_type, &mut wrapper, &running.route.upstream);
+        }
+        *live = wrapper.settings_config;
+    }
+
+    /// 启动时调用：代理端口每次启动都会变化，为启用了调试代理的当前供应商重写 live 配置
+    pub fn restore(state: &AppState) -> Result<(), AppError> {
+        for app_type in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+            let Some(current) = state.storage.get_current_provider(app_type.as_str())? else {
+                continue;
+            };
+            let providers = state.storage.get_all_providers(app_type.as_str())?;
+            let Some(provider) = providers.get(&current) else {
+                continue;
+            };
+            let enabled = provider
+                .meta
+                .as_ref()
+                .and_then(|meta| meta.debug_proxy.as_ref())
+                .is_some_and(|config| config.enabled);
+            if enabled {
+                LiveConfigSync::write_live_snapshot(&app_type, provider)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn status() -> Result<Vec<DebugProxyStatus>, AppError> {
+        let proxies = PROXIES.lock()?;
+        let mut list: Vec<_> = proxies
+            .values()
+            .map(|running| DebugProxyStatus {
+                app: running.route.app.clone(),
+                provider_id: running.route.provider_id.clone(),
+                upstream: running.route.upstream.clone(),
+                proxy_url: proxy_url(running.port),
+                capture_bodies: running.route.capture_bodies,
+            })
+            .collect();
+        list.sort_by(|a, b| a.app.cmp(&b.app));
+        Ok(list)
+    }
+
+    /// 按时间倒序返回日志，可按应用过滤
+    pub fn log(app: Option<&str>, limit: Option<usize>) -> Result<Vec<ProxyLogEntry>, AppError> {
+        let log = LOG.lock()?;
+        Ok(log
+            .iter()
+            .rev()
+            .filter(|entry| app.is_none_or(|app| entry.app == app))
+            .take(limit.unwrap_or(LOG_CAPACITY))
+            .cloned()
+            .collect())
+    }
+
+    pub fn clear_log() -> Result<(), AppError> {
+        LOG.lock()?.clear();
+        Ok(())
+    }
+
+    fn stop(app_type: &AppType) -> Result<(), AppError> {
+        if PROXIES.lock()?.remove(app_type.as_str()).is_some() {
+            log::info!("调试代理已停止: {}", app_type.as_str());
+        }
+        Ok(())
+    }
+
+    fn is_proxy_url(url: &str) -> Result<bool, AppError> {
+        Ok(PROXIES
+            .lock()?
+            .values()
+            .any(|running| proxy_url(running.port) == url))
+    }
+
+    /// 路由未变化时沿用已有代理，否则在新端口上启动并替换旧代理
+    fn ensure_running(route: ProxyRoute) -> Result<u16, AppError> {
+        let mut proxies = PROXIES.lock()?;
+        if let Some(running) = proxies.get(&route.app) {
+            if running.route == route {
+                return Ok(running.port);
+            }
+        }
+
+        let listener = std::net::TcpListener::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, 0)))
+            .and_then(|listener| listener.set_nonblocking(true).map(|_| listener))
+            .map_err(|e| AppError::Message(format!("启动调试代理失败: {e}")))?;
+        let port = listener
+            .local_addr()
+            .map_err(|e| AppError::Message(format!("读取调试代理端口失败: {e}")))?
+            .port();
+
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        log::info!(
+            "调试代理已启动: {} -> {} ({})",
+            proxy_url(port),
+            route.upstream,
+            route.app
+        );
+        let task_route = Arc::new(route.clone());
+        // 替换旧代理时丢弃其发送端，旧代理随即退出
+        proxies.insert(
+            route.app.clone(),
+            RunningProxy {
+                route,
+                port,
+                _shutdown: shutdown_tx,
+            },
+        );
+        tauri::async_runtime::spawn(async move {
+            match TcpListener::from_std(listener) {
+                Ok(listener) => serve(listener, task_route, shutdown_rx).await,
+                Err(e) => log::warn!("调试代理监听失败: {e}"),
+            }
+        });
+        Ok(port)
+    }
+}
+
+fn proxy_url(port: u16) -> String {
+    format!("http://127.0.0.1:{port}")
+}
+
+async fn serve(listener: TcpListener, route: Arc<ProxyRoute>, mut shutdown: oneshot::Receiver<()>) {
+    // 不设整体超时，流式响应可能持续数分钟
+    let client = match reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(CONNECT_TIMEOUT_SECS))
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            log::warn!("创建调试代理 HTTP 客户端失败: {e}");
+            return;
+        }
+    };
+
+    loop {
+        let stream = tokio::select! {
+            _ = &mut shutdown => return,
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    log::warn!("调试代理接受连接失败: {e}");
+                    continue;
+                }
+            },
+        };
+        let client = client.clone();
+        let route = route.clone();
+        tauri::async_runtime::spawn(async move {
+            handle(stream, &client, &route).await;
+        });
+    }
+}
+
+async fn handle(mut stream: TcpStream, client: &reqwest::Client, route: &ProxyRoute) {
+    let started = Instant::now();
+    let request = match read_request(&mut stream).await {
+        Ok(request) => request,
+        Err(e) => {
+            log::debug!("读取调试代理请求失败: {e}");
+            return;
+        }
+    };
+
+    let mut entry = ProxyLogEntry {
+        id: NEXT_ENTRY_ID.fetch_add(1, Ordering::Relaxed),
+        app: route.app.clone(),
+        provider_id: route.provider_id.clone(),
+        started_at: chrono::Utc::now().timestamp_millis(),
+        method: request.method.clone(),
+        path: request.path.clone(),
+        status: None,
+        duration_ms: 0,
+        request_bytes: request.body.len() as u64,
+        response_bytes: None,
+        request_model: sniff_model(&request.body).or_else(|| path_model(&request.path)),
+        response_model: None,
+        model_mismatch: false,
+        streaming: false,
+        error: None,
+        request_body: route.capture_bodies.then(|| capture(&request.body)),
+        response_body: None,
+    };
+
+    if let Err(e) = forward(&mut stream, client, route, request, &mut entry).await {
+        // 已开始转发响应时无法再改写状态码，只记录错误
+        if entry.status.is_none() {
+            let body = format!("debug proxy: {e}");
+            let head = format!(
+                "HTTP/1.1 502 Bad Gateway\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            );
+            let _ = stream.write_all(head.as_bytes()).await;
+            let _ = stream.write_all(body.as_bytes()).await;
+            let _ = stream.shutdown().await;
+        }
+        entry.error = Some(e);
+    }
+    entry.duration_ms = started.elapsed().as_millis() as u64;
+    entry.model_mismatch = models_differ(
+        entry.request_model.as_deref(),
+        entry.response_model.as_deref(),
+    );
+    if let (Some(requested), Some(served), Some(200..=299)) =
+        (&entry.request_model, &entry.response_model, entry.status)
+    {
+        ModelFidelityService::observe(&entry.app, &entry.provider_id, requested, served);
+    }
+    push_entry(entry);
+}
+
+async fn forward(
+    stream: &mut TcpStream,
+    client: &reqwest::Client,
+    route: &ProxyRoute,
+    request: ProxyRequest,
+    entry: &mut ProxyLogEntry,
+) -> Result<(), String> {
+    let method =
+        reqwest::Method::from_bytes(request.method.as_bytes()).map_err(|e| e.to_string())?;
+    let mut builder = client.request(
+        method.clone(),
+        format!("{}{}", route.upstream, request.path),
+    );
+    for (name, value) in &request.headers {
+        if !SKIPPED_REQUEST_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+    }
+    let mut response = builder
+        .body(request.body)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let status = response.status();
+    entry.status = Some(status.as_u16());
+    entry.streaming = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/event-stream"));
+
+    // HEAD、204、304 响应不能带正文
+    let bodyless =
+        method == reqwest::Method::HEAD || status.as_u16() == 204 || status.as_u16() == 304;
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\n",
+        status.as_u16(),
+        status.canonical_reason().unwrap_or_default()
+    );
+    for (name, value) in response.headers() {
+        if SKIPPED_RESPONSE_HEADERS.contains(&name.as_str()) {
+            continue;
+        }
+        if let Ok(value) = value.to_str() {
+            head.push_str(&format!("{name}: {value}\r\n"));
+        }
+    }
+    if !bodyless {
+        head.push_str("Transfer-Encoding: chunked\r\n");
+    }
+    head.push_str("Connection: close\r\n\r\n");
+    let io_err = |e: std::io::Error| format!("写回客户端失败: {e}");
+    stream.write_all(head.as_bytes()).await.map_err(io_err)?;
+
+    let mut prefix = Vec::new();
+    let mut total = 0u64;
+    let result = async {
+        if bodyless {
+            return Ok::<(), String>(());
+        }
+        while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+            if chunk.is_empty() {
+                continue;
+            }
+            total += chunk.len() as u64;
+            let room = MAX_CAPTURED_BYTES.saturating_sub(prefix.len());
+            prefix.extend_from_slice(&chunk[..room.min(chunk.len())]);
+            stream
+                .write_all(format!("{:x}\r\n", chunk.len()).as_bytes())
+                .await
+                .map_err(io_err)?;
+            stream.write_all(&chunk).await.map_err(io_err)?;
+            stream.write_all(b"\r\n").await.map_err(io_err)?;
+        }
+        stream.write_all(b"0\r\n\r\n").await.map_err(io_err)?;
+        let _ = stream.shutdown().await;
+        Ok::<(), String>(())
+    }
+    .await;
+
+    entry.response_bytes = Some(total);
+    entry.response_model = sniff_model(&prefix);
+    if route.capture_bodies {
+        entry.response_body = Some(capture(&prefix));
+    }
+    result
+}
+
+struct ProxyRequest {
+    method: String,
+    path: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl ProxyRequest {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// 读取一个完整请求，支持 Content-Length 与 chunked 两种正文
+async fn read_request(stream: &mut TcpStream) -> std::io::Result<ProxyRequest> {
+    let mut buf = Vec::with_capacity(8 * 1024);
+    let head_end = loop {
+        if let Some(pos) = find(&buf, b"\r\n\r\n") {
+            break pos + 4;
+        }
+        if buf.len() > MAX_HEAD_BYTES {
+            return Err(std::io::Error::other("request header too large"));
+        }
+        read_more(stream, &mut buf).await?;
+    };
+
+    let mut request = parse_head(&String::from_utf8_lossy(&buf[..head_end]))
+        .ok_or_else(|| std::io::Error::other("malformed request"))?;
+    let mut rest = buf.split_off(head_end);
+    let chunked = request
+        .header("transfer-encoding")
+        .is_some_and(|value| value.to_ascii_lowercase().contains("chunked"));
+    if chunked {
+        request.body = loop {
+            if let Some(body) = decode_chunked(&rest)? {
+                break body;
+            }
+            if rest.len() > MAX_REQUEST_BODY {
+                return Err(std::io::Error::other("request body too large"));
+            }
+            read_more(stream, &mut rest).await?;
+        };
+    } else {
+        let len = request
+            .header("content-length")
+            .and_then(|value| value.trim().parse::<usize>().ok())
+            .unwrap_or(0);
+        if len > MAX_REQUEST_BODY {
+            return Err(std::io::Error::other("request body too large"));
+        }
+        while rest.len() < len {
+            read_more(stream, &mut rest).await?;
+        }
+        rest.truncate(len);
+        request.body = rest;
+    }
+    Ok(request)
+}
+
+async fn read_more(stream: &mut TcpStream, buf: &mut Vec<u8>) -> std::io::Result<()> {
+    let mut chunk = [0u8; 16 * 1024];
+    let read = tokio::time::timeout(
+        Duration::from_secs(READ_TIMEOUT_SECS),
+        stream.read(&mut chunk),
+    )
+    .await
+    .map_err(|_| std::io::Error::other("request timed out"))??;
+    if read == 0 {
+        return Err(std::io::Error::other("connection closed"));
+    }
+    buf.extend_from_slice(&chunk[..read]);
+    Ok(())
+}
+
+fn parse_head(head: &str) -> Option<ProxyRequest> {
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let path = request_line.next().filter(|path| path.starts_with('/'))?;
+    let headers = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .collect();
+    Some(ProxyRequest {
+        method,
+        path: path.to_string(),
+        headers,
+        body: Vec::new(),
+    })
+}
+
+/// 正文未接收完整时返回 None
+fn decode_chunked(data: &[u8]) -> std::io::Result<Option<Vec<u8>>> {
+    let invalid = || std::io::Error::other("invalid chunk size");
+    let mut body = Vec::new();
+    let mut pos = 0;
+    loop {
+        let Some(line_len) = find(&data[pos..], b"\r\n") else {
+            return Ok(None);
+        };
+        let line = std::str::from_utf8(&data[pos..pos + line_len]).map_err(|_| invalid())?;
+        let size_text = line.split(';').next().unwrap_or_default().trim();
+        let size = usize::from_str_radix(size_text, 16).map_err(|_| invalid())?;
+        pos += line_len + 2;
+        if size == 0 {
+            return Ok(find(&data[pos..], b"\r\n").map(|_| body));
+        }
+        if data.len() < pos + size + 2 {
+            return Ok(None);
+        }
+        body.extend_from_slice(&data[pos..pos + size]);
+        pos += size + 2;
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+/// 取正文中第一个 `model` / `modelVersion` 字段；流式响应的首个事件即包含模型
+fn sniff_model(body: &[u8]) -> Option<String> {
+    let text = String::from_utf8_lossy(body);
+    MODEL_RE.captures(&text).map(|caps| caps[1].to_string())
+}
+
+/// Gemini 把模型放在路径中，如 `/v1beta/models/gemini-2.5-pro:generateContent`
+fn path_model(path: &str) -> Option<String> {
+    let rest = path.split("/models/").nth(1)?;
+    let model = rest.split([':', '?', '/']).next()?;
+    (!model.is_empty()).then(|| model.to_string())
+}
+
+fn capture(body: &[u8]) -> String {
+    String::from_utf8_lossy(&body[..body.len().min(MAX_CAPTURED_BYTES)]).to_string()
+}
+
+fn push_entry(entry: ProxyLogEntry) {
+    let mut log = LOG.lock().unwrap_or_else(|e| e.into_inner());
+    if log.len() >= LOG_CAPACITY {
+        log.pop_front();
+    }
+    log.push_back(entry);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_requests_and_detects_model_substitution() {
+        let request = parse_head(
+            "POST /v1/messages?beta=true HTTP/1.1\r\nHost: 127.0.0.1\r\nContent-Length: 12\r\n\r\n",
+        )
+        .unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/v1/messages?beta=true");
+        assert_eq!(request.header("content-length"), Some("12"));
+        assert!(parse_head("CONNECT example.com:443 HTTP/1.1\r\n\r\n").is_none());
+
+        let body = decode_chunked(b"5\r\nhello\r\n6;ext=1\r\n world\r\n0\r\n\r\n").unwrap();
+        assert_eq!(body.as_deref(), Some(&b"hello world"[..]));
+        assert_eq!(decode_chunked(b"5\r\nhel").unwrap(), None);
+
+        let model = sniff_model(br#"{"model": "claude-opus-4-1", "stream": true}"#);
+        assert_eq!(model.as_deref(), Some("claude-opus-4-1"));
+        let sse = b"event: message_start\ndata: {\"type\":\"message_start\",\"message\":{\"model\":\"claude-3-5-haiku\"}}";
+        assert_eq!(sniff_model(sse).as_deref(), Some("claude-3-5-haiku"));
+        assert_eq!(
+            path_model("/v1beta/models/gemini-2.5-pro:streamGenerateContent?alt=sse").as_deref(),
+            Some("gemini-2.5-pro")
+        );
+
+        assert!(!models_differ(
+            Some("claude-sonnet-4-5"),
+            Some("claude-sonnet-4-5-20250929")
+        ));
+        assert!(models_differ(
+            Some("claude-opus-4-1"),
+            Some("claude-3-5-haiku")
+        ));
+        assert!(!models_differ(Some("gpt-5"), None));
+    }
+}
@@ -0,0 +1,366 @@
+use serde_json::{json, Value};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::app_config::{AppType, McpApps, McpServer};
+use crate::error::AppError;
+use crate::prompt::Prompt;
+use crate::provider::{Provider, ProviderLatency, ProviderMeta};
+use crate::store::AppState;
+
+/// 设置为 1/true 时以演示模式启动
+pub const DEMO_ENV: &str = "CLI_HUB_DEMO";
+
+static DEMO: AtomicBool = AtomicBool::new(false);
+
+/// 是否处于演示模式：使用内存数据库，且不写入任何 live 配置
+pub fn is_demo_mode() -> bool {
+    DEMO.load(Ordering::Relaxed)
+}
+
+/// 演示模式与安全模式下拒绝修改 CLI 的配置文件
+pub fn ensure_live_write_allowed(path: &Path) -> Result<(), AppError> {
+    if crate::services::safe_mode::is_safe_mode() && is_live_config_path(path) {
+        return crate::services::safe_mode::ensure_not_safe_mode();
+    }
+    if !is_demo_mode() || !is_live_config_path(path) {
+        return Ok(());
+    }
+    Err(AppError::localized(
+        "demo.live_write_blocked",
+        format!("演示模式下不会修改 {}", path.display()),
+        format!("Demo mode does not modify {}", path.display()),
+    ))
+}
+
+/// 演示模式下拒绝执行会改动本机环境的操作（技能安装、环境变量清理等）
+pub fn ensure_not_demo() -> Result<(), AppError> {
+    if is_demo_mode() {
+        return Err(AppError::localized(
+            "demo.action_blocked",
+            "演示模式下不可执行此操作",
+            "This action is disabled in demo mode",
+        ));
+    }
+    Ok(())
+}
+
+fn is_live_config_path(path: &Path) -> bool {
+    let dirs = [
+        crate::config::get_claude_config_dir(),
+        crate::codex_config::get_codex_config_dir(),
+        crate::gemini_config::get_gemini_dir(),
+    ];
+    path == crate::config::get_claude_mcp_path() || dirs.iter().any(|dir| path.starts_with(dir))
+}
+
+pub struct DemoService;
+
+impl DemoService {
+    /// 环境变量是否要求演示模式
+    pub fn env_requested() -> bool {
+        std::env::var(DEMO_ENV)
+            .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+            .unwrap_or(false)
+    }
+
+    pub fn enable() {
+        DEMO.store(true, Ordering::Relaxed);
+        log::info!("演示模式已启用：使用内存数据库，不写入 live 配置");
+    }
+
+    /// 向（内存）数据库写入演示数据
+    pub fn seed(state: &AppState) -> Result<(), AppError> {
+        let now = chrono::Utc::now().timestamp();
+
+        for (app_type, providers) in [
+            (AppType::Claude, claude_providers()),
+            (AppType::Codex, codex_providers()),
+            (AppType::Gemini, gemini_providers()),
+        ] {
+            for (index, (provider, switches, latency_ms)) in providers.into_iter().enumerate() {
+                let mut provider = provider;
+                provider.sort_index = Some(index);
+                provider.created_at = Some((now - 86_400 * (30 - index as i64)) * 1000);
+                provider.meta = Some(ProviderMeta {
+                    latency: Some(ProviderLatency {
+                        latency_ms,
+                        tested_at: now - 3_600,
+                    }),
+                    ..Default::default()
+                });
+                state.storage.save_provider(app_type.as_str(), &provider)?;
+                for _ in 0..switches {
+                    state
+                        .storage
+                        .record_provider_switch(app_type.as_str(), &provider.id)?;
+                }
+                if index == 0 {
+                    state
+                        .storage
+                        .set_current_provider(app_type.as_str(), &provider.id)?;
+                }
+            }
+        }
+
+        for server in mcp_servers() {
+            state.storage.save_mcp_server(&server)?;
+        }
+
+        for (app_type, prompt) in prompts(now) {
+            state.storage.save_prompt(app_type.as_str(), &prompt)?;
+        }
+
+        Ok(())
+    }
+}
+
+type DemoProvider = (Provider, u32, u64);
+
+fn demo_provider(id: &str, name: &str, settings: Value, website: &str, category: &str) -> Provider {
+    let mut provider = Provider::with_id(
+        id.to_string(),
+        name.to_string(),
+        settings,
+        Some(website.to_string()),
+    );
+    provider.category = Some(category.to_string());
+    provider
+}
+
+fn claude_providers() -> Vec<DemoProvider> {
+    vec![
+        (
+            demo_provider(
+                "demo-claude-official",
+                "Claude Official",
+                json!({ "env": {} }),
+                "https://www.anthropic.com/claude-code",
+                "official",
+            ),
+            12,
+            0,
+        ),
+        (
+            demo_provider(
+                "demo-claude-relay",
+                "Acme Relay",
+                json!({
+                    "env": {
+                        "ANTHROPIC_AUTH_TOKEN": "sk-demo-acme-0000000000000000",
+                        "ANTHROPIC_BASE_URL": "https://relay.acme.example"
+                    }
+                }),
+                "https://relay.acme.example",
+                "third_party",
+            ),
+            5,
+            180,
+        ),
+        (
+            demo_provider(
+                "demo-claude-team",
+                "Team Gateway",
+                json!({
+                    "env": {
+                        "ANTHROPIC_AUTH_TOKEN": "sk-demo-team-0000000000000000",
+                        "ANTHROPIC_BASE_URL": "https://llm-gateway.example.com",
+                        "ANTHROPIC_MODEL": "claude-sonnet-4-5"
+                    }
+                }),
+                "https://llm-gateway.example.com",
+                "custom",
+            ),
+            2,
+            95,
+        ),
+    ]
+}
+
+fn codex_providers() -> Vec<DemoProvider> {
+    let config = |name: &str, base_url: &str| {
+        format!(
+            "model_provider = \"{name}\"\nmodel = \"gpt-5-codex\"\n\n[model_providers.{name}]\nname = \"{name}\"\nbase_url = \"{base_url}\"\nwire_api = \"responses\"\n"
+        )
+    };
+    vec![
+        (
+            demo_provider(
+                "demo-codex-official",
+                "OpenAI Official",
+                json!({ "auth": {}, "config": "" }),
+                "https://chatgpt.com/codex",
+                "official",
+            ),
+            8,
+            0,
+        ),
+        (
+            demo_provider(
+                "demo-codex-relay",
+                "Acme Relay",
+                json!({
+                    "auth": { "OPENAI_API_KEY": "sk-demo-acme-0000000000000000" },
+                    "config": config("acme", "https://relay.acme.example/v1")
+                }),
+                "https://relay.acme.example",
+                "third_party",
+            ),
+            3,
+            240,
+        ),
+    ]
+}
+
+fn gemini_providers() -> Vec<DemoProvider> {
+    vec![
+        (
+            demo_provider(
+                "demo-gemini-official",
+                "Google Official",
+                json!({ "env": {}, "config": {} }),
+                "https://ai.google.dev",
+                "official",
+            ),
+            4,
+            0,
+        ),
+        (
+            demo_provider(
+                "demo-gemini-relay",
+                "Acme Relay",
+                json!({
+                    "env": {
+                        "GEMINI_API_KEY": "sk-demo-acme-0000000000000000",
+                        "GOOGLE_GEMINI_BASE_URL": "https://relay.acme.example",
+                        "GEMINI_MODEL": "gemini-2.5-pro"
+                    },
+                    "config": {}
+                }),
+                "https://relay.acme.example",
+                "third_party",
+            ),
+            1,
+            310,
+        ),
+    ]
+}
+
+fn mcp_servers() -> Vec<McpServer> {
+    let server = |id: &str, name: &str, spec: Value, apps: McpApps, description: &str| McpServer {
+        id: id.to_string(),
+        name: name.to_string(),
+        server: spec,
+        apps,
+        description: Some(description.to_string()),
+        homepage: None,
+        docs: None,
+        tags: vec!["demo".to_string()],
+    };
+    vec![
+        server(
+            "fetch",
+            "Fetch",
+            json!({ "type": "stdio", "command": "uvx", "args": ["mcp-server-fetch"] }),
+            McpApps {
+                claude: true,
+                codex: true,
+                gemini: true,
+            },
+            "Fetch web pages as markdown",
+        ),
+        server(
+            "filesystem",
+            "Filesystem",
+            json!({
+                "type": "stdio",
+                "command": "npx",
+                "args": ["-y", "@modelcontextprotocol/server-filesystem", "~/projects"]
+            }),
+            McpApps {
+                claude: true,
+                codex: false,
+                gemini: false,
+            },
+            "Read and write files under ~/projects",
+        ),
+        server(
+            "docs",
+            "Team Docs",
+            json!({
+                "type": "http",
+                "url": "https://mcp.example.com/docs",
+                "headers": { "Authorization": "Bearer demo-token" }
+            }),
+            McpApps::default(),
+            "Internal documentation search",
+        ),
+    ]
+}
+
+fn prompts(now: i64) -> Vec<(AppType, Prompt)> {
+    let prompt = |id: &str, name: &str, content: &str, enabled: bool| Prompt {
+        id: id.to_string(),
+        name: name.to_string(),
+        content: content.to_string(),
+        description: None,
+        enabled,
+        created_at: Some(now),
+        updated_at: Some(now),
+        version: None,
+    };
+    vec![
+        (
+            AppType::Claude,
+            prompt(
+                "demo-conventions",
+                "Project conventions",
+                "# Conventions\n\n- Prefer small, focused commits\n- Run the test suite before pushing\n",
+                true,
+            ),
+        ),
+        (
+            AppType::Claude,
+            prompt(
+                "demo-reviewer",
+                "Strict reviewer",
+                "Review every change as a senior engineer would.\n",
+                false,
+            ),
+        ),
+        (
+            AppType::Codex,
+            prompt(
+                "demo-agents",
+                "AGENTS.md",
+                "# Agents\n\nKeep answers short and cite file paths.\n",
+                true,
+            ),
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use std::sync::Arc;
+
+    #[test]
+    fn seed_populates_every_app() {
+        let state = AppState::new(Arc::new(Database::memory().unwrap()));
+        DemoService::seed(&state).unwrap();
+
+        for app_type in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+            let providers = state.storage.get_all_providers(app_type.as_str()).unwrap();
+            assert!(providers.len() >= 2);
+            let current = state
+                .storage
+                .get_current_provider(app_type.as_str())
+                .unwrap();
+            assert!(current.is_some_and(|id| providers.contains_key(&id)));
+        }
+        assert_eq!(state.storage.get_all_mcp_servers().unwrap().len(), 3);
+        assert!(!state.db.is_empty_for_first_import().unwrap());
+    }
+}
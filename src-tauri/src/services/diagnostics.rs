@@ -0,0 +1,284 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use crate::error::AppError;
+use crate::log_sanitizer;
+use crate::services::{ExternalBackupService, WriteAccessService};
+use crate::store::AppState;
+
+const BUNDLE_FORMAT: &str = "cli-hub-diagnostics";
+const BUNDLE_VERSION: u32 = 1;
+/// 每个日志文件只保留末尾这么多行
+const MAX_LOG_LINES: usize = 1000;
+/// 最多收集最近修改的几个日志文件
+const MAX_LOG_FILES: usize = 3;
+const MAX_ERROR_LINES: usize = 50;
+/// `--version` 之类的命令超过该时间仍未返回时放弃
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 已写入的诊断包
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticsBundle {
+    pub path: String,
+    pub files: Vec<String>,
+    pub size: u64,
+}
+
+/// 生成用于提交 Issue 的诊断包
+///
+/// 只包含设置、表行数、版本信息、最近日志与错误；不含供应商配置、MCP 与提示词正文。
+/// 所有文本在写入前都经过脱敏，并把用户主目录替换为 `~`。
+pub struct DiagnosticsService;
+
+impl DiagnosticsService {
+    pub fn create_bundle(
+        state: &AppState,
+        log_dir: Option<&Path>,
+        target: &Path,
+    ) -> Result<DiagnosticsBundle, AppError> {
+        let logs = log_dir.map(collect_logs).unwrap_or_default();
+        let recent_errors: Vec<String> = logs
+            .iter()
+            .flat_map(|(_, content)| error_lines(content))
+            .collect();
+        let recent_errors =
+            recent_errors[recent_errors.len().saturating_sub(MAX_ERROR_LINES)..].to_vec();
+
+        let mut entries: Vec<(String, String)> = vec![
+            ("manifest.json".to_string(), to_json(&manifest())?),
+            ("settings.json".to_string(), to_json(&settings()?)?),
+            ("database.json".to_string(), to_json(&database(state)?)?),
+            ("environment.json".to_string(), to_json(&environment())?),
+            (
+                "errors.json".to_string(),
+                to_json(&errors(state, recent_errors))?,
+            ),
+        ];
+        entries.extend(
+            logs.into_iter()
+                .map(|(name, content)| (format!("logs/{name}"), content)),
+        );
+
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+        }
+        let file = std::fs::File::create(target).map_err(|e| AppError::io(target, e))?;
+        let mut zip = ZipWriter::new(file);
+        let options =
+            SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+        for (name, content) in &entries {
+            zip.start_file(name.as_str(), options).map_err(zip_error)?;
+            zip.write_all(scrub(content).as_bytes())
+                .map_err(|e| AppError::io(target, e))?;
+        }
+        zip.finish().map_err(zip_error)?;
+
+        let size = std::fs::metadata(target)
+            .map_err(|e| AppError::io(target, e))?
+            .len();
+        log::info!("已生成诊断包: {} ({size} 字节)", target.display());
+        Ok(DiagnosticsBundle {
+            path: target.display().to_string(),
+            files: entries.into_iter().map(|(name, _)| name).collect(),
+            size,
+        })
+    }
+}
+
+fn zip_error(e: zip::result::ZipError) -> AppError {
+    AppError::Message(format!("写入诊断包失败: {e}"))
+}
+
+fn to_json(value: &impl Serialize) -> Result<String, AppError> {
+    serde_json::to_string_pretty(value).map_err(|e| AppError::JsonSerialize { source: e })
+}
+
+/// 遮蔽密钥，并隐去路径中的用户名
+fn scrub(text: &str) -> String {
+    let redacted = log_sanitizer::redact(text);
+    match dirs::home_dir().map(|home| home.display().to_string()) {
+        Some(home) if home.len() > 1 => redacted.replace(&home, "~"),
+        _ => redacted.into_owned(),
+    }
+}
+
+fn manifest() -> Value {
+    json!({
+        "format": BUNDLE_FORMAT,
+        "version": BUNDLE_VERSION,
+        "createdAt": chrono::Utc::now().to_rfc3339(),
+        "appVersion": env!("CARGO_PKG_VERSION"),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "osVersion": os_version(),
+    })
+}
+
+/// 完整设置经脱敏后输出，GitHub Token、深链接密钥等字段只剩占位符
+fn settings() -> Result<Value, AppError> {
+    let settings = crate::settings::get_settings();
+    let raw =
+        serde_json::to_string(&settings).map_err(|e| AppError::JsonSerialize { source: e })?;
+    serde_json::from_str(&log_sanitizer::redact(&raw))
+        .map_err(|e| AppError::Message(format!("脱敏设置失败: {e}")))
+}
+
+fn database(state: &AppState) -> Result<Value, AppError> {
+    Ok(json!({
+        "schemaVersion": state.db.schema_version()?,
+        "tableCounts": state.db.table_row_counts()?,
+        "managementPaused": crate::services::sync_pause::is_management_paused(),
+    }))
+}
+
+fn environment() -> Value {
+    let cli: serde_json::Map<String, Value> = ["claude", "codex", "gemini"]
+        .into_iter()
+        .map(|cli| (cli.to_string(), json!(first_line_of(cli, &["--version"]))))
+        .collect();
+    json!({
+        "cliVersions": cli,
+        "writeCapabilities": WriteAccessService::detect(),
+    })
+}
+
+fn errors(state: &AppState, recent_log_errors: Vec<String>) -> Value {
+    let external_backup = ExternalBackupService::status(state)
+        .ok()
+        .and_then(|status| {
+            status
+                .last_error
+                .map(|error| (status.last_failure_at, error))
+        });
+    json!({
+        "initError": crate::init_status::get_init_error(),
+        "initWarnings": crate::init_status::get_init_warnings(),
+        "externalBackup": external_backup.map(|(at, error)| json!({ "at": at, "error": error })),
+        "recentLogErrors": recent_log_errors,
+    })
+}
+
+fn os_version() -> Option<String> {
+    if cfg!(target_os = "macos") {
+        first_line_of("sw_vers", &["-productVersion"])
+    } else if cfg!(target_os = "windows") {
+        first_line_of("cmd", &["/C", "ver"])
+    } else {
+        first_line_of("uname", &["-sr"])
+    }
+}
+
+/// 运行命令并返回输出的第一行非空内容；命令不存在、失败或超时时返回 None
+fn first_line_of(program: &str, args: &[&str]) -> Option<String> {
+    // Windows 上 npm 安装的 CLI 是 .cmd 脚本，需要经由 cmd 启动
+    let mut command = if cfg!(target_os = "windows") && program != "cmd" {
+        let mut command = Command::new("cmd");
+        command.arg("/C").arg(program);
+        command
+    } else {
+        Command::new(program)
+    };
+    let mut child = command
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    let deadline = Instant::now() + COMMAND_TIMEOUT;
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if Instant::now() < deadline => std::thread::sleep(Duration::from_millis(50)),
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    }
+
+    let output = child.wait_with_output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+/// 最近修改的几个日志文件的末尾部分
+fn collect_logs(dir: &Path) -> Vec<(String, String)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<(std::time::SystemTime, PathBuf)> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "log"))
+        .filter_map(|path| {
+            let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((modified, path))
+        })
+        .collect();
+    files.sort_by(|a, b| b.0.cmp(&a.0));
+
+    files
+        .into_iter()
+        .take(MAX_LOG_FILES)
+        .filter_map(|(_, path)| {
+            let name = path.file_name()?.to_string_lossy().into_owned();
+            let bytes = std::fs::read(&path).ok()?;
+            Some((
+                name,
+                tail_lines(&String::from_utf8_lossy(&bytes), MAX_LOG_LINES),
+            ))
+        })
+        .collect()
+}
+
+fn tail_lines(text: &str, max: usize) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    lines[lines.len().saturating_sub(max)..].join("\n")
+}
+
+/// 日志格式为 `[日期][时间][target][LEVEL] 消息`
+fn error_lines(log: &str) -> Vec<String> {
+    log.lines()
+        .filter(|line| line.contains("][ERROR]") || line.contains("][WARN]"))
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_log_tail_and_hides_secrets() {
+        let log = [
+            "[2026-01-01][10:00:00][cli_hub_lib][INFO] 启动",
+            "[2026-01-01][10:00:01][cli_hub_lib][WARN] 探测失败 key=sk-ant-abcdefgh12345678",
+            "[2026-01-01][10:00:02][cli_hub_lib][ERROR] 写入失败",
+        ]
+        .join("\n");
+
+        let tail = tail_lines(&log, 2);
+        assert!(!tail.contains("启动"));
+        assert_eq!(error_lines(&tail).len(), 2);
+
+        let scrubbed = scrub(&tail);
+        assert!(!scrubbed.contains("sk-ant-abcdefgh12345678"));
+        assert!(scrubbed.contains("写入失败"));
+    }
+}
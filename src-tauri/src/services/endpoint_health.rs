@@ -0,0 +1,259 @@
+use serde::Serialize;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::app_config::AppType;
+use crate::database::dao::EndpointHealth;
+use crate::error::AppError;
+use crate::notifications::{notify, NotificationCategory, NotificationText};
+use crate::services::provider::EndpointManager;
+use crate::services::{ProviderService, SpeedtestService};
+use crate::store::AppState;
+
+/// 探测间隔；只对配置了多个端点的供应商发请求
+const PROBE_INTERVAL: Duration = Duration::from_secs(5 * 60);
+const PROBE_TIMEOUT_SECS: u64 = 5;
+/// 当前端点分数低于该值视为已降级
+const DEGRADED_THRESHOLD: f64 = 0.5;
+/// 备用端点需达到该分数才会被切换过去，避免在两个都不稳定的端点间来回切换
+const HEALTHY_THRESHOLD: f64 = 0.8;
+
+/// 自动改写端点后发给前端的事件
+pub const ENDPOINT_FAILOVER_EVENT: &str = "endpoint-failover";
+
+/// 一次自动端点切换
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointFailover {
+    pub app: String,
+    pub provider_id: String,
+    pub provider_name: String,
+    pub from_url: String,
+    pub to_url: String,
+}
+
+struct ProbeTarget {
+    app_type: AppType,
+    provider_id: String,
+    urls: Vec<String>,
+}
+
+/// 多端点供应商的后台可用性探测与自动回退
+pub struct EndpointHealthService;
+
+impl EndpointHealthService {
+    pub fn get_health(
+        state: &AppState,
+        app_type: AppType,
+        provider_id: &str,
+    ) -> Result<Vec<EndpointHealth>, AppError> {
+        state
+            .storage
+            .get_endpoint_health(app_type.as_str(), provider_id)
+    }
+
+    /// 探测所有多端点供应商并更新分数；开启自动回退时改写降级的当前端点
+    pub async fn run_once(state: &AppState) -> Result<Vec<EndpointFailover>, AppError> {
+        for target in Self::probe_targets(state)? {
+            let app = target.app_type.as_str();
+            let results =
+                SpeedtestService::test_endpoints(target.urls.clone(), Some(PROBE_TIMEOUT_SECS))
+                    .await?;
+            for result in results {
+                // 5xx 说明网关可达但上游不可用，同样计为失败
+                let error = match (&result.error, result.status) {
+                    (Some(error), _) => Some(error.clone()),
+                    (None, Some(status)) if status >= 500 => Some(format!("HTTP {status}")),
+                    _ => None,
+                };
+                let latency = result
+                    .latency
+                    .map(|ms| u64::try_from(ms).unwrap_or(u64::MAX));
+                state.storage.record_endpoint_probe(
+                    app,
+                    &target.provider_id,
+                    &result.url,
+                    latency,
+                    error.as_deref(),
+                )?;
+            }
+            state
+                .storage
+                .prune_endpoint_health(app, &target.provider_id, &target.urls)?;
+        }
+
+        if !crate::settings::get_settings().endpoint_failover {
+            return Ok(Vec::new());
+        }
+        Self::failover_current(state)
+    }
+
+    /// 只切换各应用的当前供应商，其他供应商仅记录分数供切换时参考
+    fn failover_current(state: &AppState) -> Result<Vec<EndpointFailover>, AppError> {
+        let mut failovers = Vec::new();
+        for app_type in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+            let Some(current_id) = state.storage.get_current_provider(app_type.as_str())? else {
+                continue;
+            };
+            let Some(provider) = state
+                .storage
+                .get_all_providers(app_type.as_str())?
+                .shift_remove(&current_id)
+            else {
+                continue;
+            };
+            let Some(from_url) = EndpointManager::base_url(&app_type, &provider) else {
+                continue;
+            };
+            let health = state
+                .storage
+                .get_endpoint_health(app_type.as_str(), &current_id)?;
+            let Some(to_url) = pick_failover(&from_url, &health) else {
+                continue;
+            };
+
+            let mut updated = provider.clone();
+            if !EndpointManager::set_base_url(&app_type, &mut updated, &to_url) {
+                continue;
+            }
+            ProviderService::update(state, app_type.clone(), updated)?;
+            log::info!(
+                "{} 的端点 {from_url} 持续不可用，已改用 {to_url}",
+                provider.name
+            );
+            failovers.push(EndpointFailover {
+                app: app_type.as_str().to_string(),
+                provider_id: provider.id.clone(),
+                provider_name: provider.name.clone(),
+                from_url,
+                to_url,
+            });
+        }
+        Ok(failovers)
+    }
+
+    fn probe_targets(state: &AppState) -> Result<Vec<ProbeTarget>, AppError> {
+        let mut targets = Vec::new();
+        for app_type in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+            for (id, provider) in state.storage.get_all_providers(app_type.as_str())? {
+                let mut urls: Vec<String> = provider
+                    .meta
+                    .as_ref()
+                    .map(|m| m.custom_endpoints.keys().cloned().collect())
+                    .unwrap_or_default();
+                if let Some(base) = EndpointManager::base_url(&app_type, &provider) {
+                    if !urls.contains(&base) {
+                        urls.push(base);
+                    }
+                }
+                if urls.len() < 2 {
+                    continue;
+                }
+                urls.sort();
+                targets.push(ProbeTarget {
+                    app_type: app_type.clone(),
+                    provider_id: id,
+                    urls,
+                });
+            }
+        }
+        Ok(targets)
+    }
+
+    /// 启动后台探测；首次探测在一个间隔之后，避免拖慢启动
+    pub fn spawn_scheduler(app: AppHandle, state: AppState) {
+        tauri::async_runtime::spawn(async move {
+            loop {
+                tokio::time::sleep(PROBE_INTERVAL).await;
+                match Self::run_once(&state).await {
+                    Ok(failovers) if !failovers.is_empty() => Self::announce(&app, &failovers),
+                    Ok(_) => {}
+                    Err(err) => log::warn!("端点可用性探测失败: {err}"),
+                }
+            }
+        });
+    }
+
+    fn announce(app: &AppHandle, failovers: &[EndpointFailover]) {
+        if let Err(e) = app.emit(ENDPOINT_FAILOVER_EVENT, failovers) {
+            log::error!("发射端点切换事件失败: {e}");
+        }
+        for item in failovers {
+            notify(
+                NotificationCategory::EndpointFailover,
+                true,
+                NotificationText::new(
+                    format!(
+                        "{} 的端点 {} 不可用，已改用 {}",
+                        item.provider_name, item.from_url, item.to_url
+                    ),
+                    format!(
+                        "{}: {} is unavailable, now using {}",
+                        item.provider_name, item.from_url, item.to_url
+                    ),
+                ),
+            );
+        }
+    }
+}
+
+/// 当前端点已降级且存在足够健康的备用端点时，返回分数最高（同分取延迟最低）的一个
+fn pick_failover(current_url: &str, health: &[EndpointHealth]) -> Option<String> {
+    let current = health.iter().find(|h| h.url == current_url)?;
+    if current.score >= DEGRADED_THRESHOLD {
+        return None;
+    }
+    health
+        .iter()
+        .filter(|h| h.url != current_url && h.score >= HEALTHY_THRESHOLD)
+        .min_by(|a, b| {
+            b.score.total_cmp(&a.score).then(
+                a.latency_ms
+                    .unwrap_or(u64::MAX)
+                    .cmp(&b.latency_ms.unwrap_or(u64::MAX)),
+            )
+        })
+        .map(|h| h.url.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn health(url: &str, score: f64, latency_ms: Option<u64>) -> EndpointHealth {
+        EndpointHealth {
+            url: url.to_string(),
+            score,
+            latency_ms,
+            last_error: None,
+            checked_at: 0,
+        }
+    }
+
+    #[test]
+    fn fails_over_only_when_current_degraded_and_backup_healthy() {
+        let degraded = [
+            health("https://a", 0.3, None),
+            health("https://b", 0.9, Some(300)),
+            health("https://c", 0.9, Some(120)),
+            health("https://d", 0.6, Some(10)),
+        ];
+        assert_eq!(
+            pick_failover("https://a", &degraded).as_deref(),
+            Some("https://c")
+        );
+
+        let fine = [
+            health("https://a", 0.7, None),
+            health("https://b", 1.0, None),
+        ];
+        assert_eq!(pick_failover("https://a", &fine), None);
+
+        let no_backup = [
+            health("https://a", 0.1, None),
+            health("https://b", 0.5, None),
+        ];
+        assert_eq!(pick_failover("https://a", &no_backup), None);
+        assert_eq!(pick_failover("https://unknown", &degraded), None);
+    }
+}
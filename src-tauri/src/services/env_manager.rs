@@ -1,20 +1,80 @@
 use super::env_checker::EnvConflict;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[cfg(target_os = "windows")]
 use winreg::enums::*;
 #[cfg(target_os = "windows")]
 use winreg::RegKey;
 
+const BACKUP_PREFIX: &str = "env-backup-";
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BackupInfo {
+    /// 备份 ID（备份文件名去掉扩展名），旧备份读取时补齐
+    #[serde(default)]
+    pub id: String,
     pub backup_path: String,
     pub timestamp: String,
     pub conflicts: Vec<EnvConflict>,
+    /// 删除前的精确快照；旧版备份没有该字段
+    #[serde(default)]
+    pub entries: Vec<EnvBackupEntry>,
+}
+
+/// 单个变量在某个来源中的删除前状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvBackupEntry {
+    pub var_name: String,
+    pub var_value: String,
+    pub source_type: String,
+    pub source_path: String,
+    /// shell 文件中被删除的原始行
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub lines: Vec<ShellLine>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShellLine {
+    /// 从 1 开始的行号
+    pub line_number: usize,
+    pub content: String,
+}
+
+/// 修改 shell 配置文件前的差异预览
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShellFileDiff {
+    pub file_path: String,
+    pub removed: Vec<ShellLine>,
+    pub added: Vec<ShellLine>,
+}
+
+impl EnvBackupEntry {
+    fn from_conflict(conflict: &EnvConflict, lines: Vec<ShellLine>) -> Self {
+        Self {
+            var_name: conflict.var_name.clone(),
+            var_value: conflict.var_value.clone(),
+            source_type: conflict.source_type.clone(),
+            source_path: conflict.source_path.clone(),
+            lines,
+        }
+    }
+
+    fn to_conflict(&self) -> EnvConflict {
+        EnvConflict {
+            var_name: self.var_name.clone(),
+            var_value: self.var_value.clone(),
+            source_type: self.source_type.clone(),
+            source_path: self.source_path.clone(),
+        }
+    }
 }
 
 /// Delete environment variables with automatic backup
@@ -47,13 +107,16 @@ fn create_backup(conflicts: &[EnvConflict]) -> Result<BackupInfo, String> {
 
     // Generate backup file name with timestamp
     let timestamp = Utc::now().format("%Y%m%d_%H%M%S").to_string();
-    let backup_file = backup_dir.join(format!("env-backup-{timestamp}.json"));
+    let id = format!("{BACKUP_PREFIX}{timestamp}");
+    let backup_file = backup_dir.join(format!("{id}.json"));
 
     // Create backup data
     let backup_info = BackupInfo {
+        id,
         backup_path: backup_file.to_string_lossy().to_string(),
         timestamp: timestamp.clone(),
         conflicts: conflicts.to_vec(),
+        entries: snapshot_entries(conflicts)?,
     };
 
     // Write backup file
@@ -65,6 +128,260 @@ fn create_backup(conflicts: &[EnvConflict]) -> Result<BackupInfo, String> {
     Ok(backup_info)
 }
 
+/// 记录每个变量删除前的原始行，同一文件中的同名变量只记录一次
+fn snapshot_entries(conflicts: &[EnvConflict]) -> Result<Vec<EnvBackupEntry>, String> {
+    let mut entries: Vec<EnvBackupEntry> = Vec::new();
+
+    for conflict in conflicts {
+        if conflict.source_type != "file" {
+            entries.push(EnvBackupEntry::from_conflict(conflict, Vec::new()));
+            continue;
+        }
+
+        let (file_path, _) = split_source_path(&conflict.source_path);
+        let duplicate = entries.iter().any(|e| {
+            e.source_type == "file"
+                && e.var_name == conflict.var_name
+                && split_source_path(&e.source_path).0 == file_path
+        });
+        if duplicate {
+            continue;
+        }
+
+        let content =
+            fs::read_to_string(file_path).map_err(|e| format!("读取文件失败 {file_path}: {e}"))?;
+        let (_, removed) = remove_var_lines(&content, &[conflict.var_name.as_str()]);
+        entries.push(EnvBackupEntry::from_conflict(conflict, removed));
+    }
+
+    Ok(entries)
+}
+
+/// 列出所有环境变量备份（按时间倒序）
+pub fn list_env_backups() -> Result<Vec<BackupInfo>, String> {
+    let backup_dir = get_backup_dir()?;
+    let Ok(dir_entries) = fs::read_dir(&backup_dir) else {
+        return Ok(Vec::new());
+    };
+
+    let mut backups = Vec::new();
+    for entry in dir_entries.flatten() {
+        let path = entry.path();
+        let is_env_backup = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with(BACKUP_PREFIX) && n.ends_with(".json"));
+        if !is_env_backup {
+            continue;
+        }
+
+        match read_backup(&path) {
+            Ok(backup) => backups.push(backup),
+            Err(e) => log::warn!("跳过无法解析的环境变量备份 {}: {e}", path.display()),
+        }
+    }
+
+    backups.sort_by(|a, b| b.id.cmp(&a.id));
+    Ok(backups)
+}
+
+fn read_backup(path: &Path) -> Result<BackupInfo, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("读取备份文件失败: {e}"))?;
+    let mut backup: BackupInfo =
+        serde_json::from_str(&content).map_err(|e| format!("解析备份文件失败: {e}"))?;
+
+    if backup.id.is_empty() {
+        backup.id = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+    }
+    // 旧版备份只有 conflicts，退化为逐条追加 export 的恢复方式
+    if backup.entries.is_empty() {
+        backup.entries = backup
+            .conflicts
+            .iter()
+            .map(|c| EnvBackupEntry::from_conflict(c, Vec::new()))
+            .collect();
+    }
+    Ok(backup)
+}
+
+fn find_backup(backup_id: &str) -> Result<BackupInfo, String> {
+    if backup_id.is_empty() || backup_id.contains(['/', '\\']) || backup_id.contains("..") {
+        return Err(format!("无效的备份 ID: {backup_id}"));
+    }
+    let path = get_backup_dir()?.join(format!("{backup_id}.json"));
+    if !path.exists() {
+        return Err(format!("备份不存在: {backup_id}"));
+    }
+    read_backup(&path)
+}
+
+/// 预览删除变量对 shell 配置文件的修改
+pub fn preview_env_deletion(conflicts: &[EnvConflict]) -> Result<Vec<ShellFileDiff>, String> {
+    let mut vars_by_file: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for conflict in conflicts.iter().filter(|c| c.source_type == "file") {
+        let (file_path, _) = split_source_path(&conflict.source_path);
+        vars_by_file
+            .entry(file_path)
+            .or_default()
+            .push(conflict.var_name.as_str());
+    }
+
+    let mut diffs = Vec::new();
+    for (file_path, vars) in vars_by_file {
+        let content =
+            fs::read_to_string(file_path).map_err(|e| format!("读取文件失败 {file_path}: {e}"))?;
+        let (_, removed) = remove_var_lines(&content, &vars);
+        if !removed.is_empty() {
+            diffs.push(ShellFileDiff {
+                file_path: file_path.to_string(),
+                removed,
+                added: Vec::new(),
+            });
+        }
+    }
+
+    Ok(diffs)
+}
+
+/// 预览从备份恢复指定变量对 shell 配置文件的修改
+pub fn preview_env_restore(backup_id: &str, vars: &[String]) -> Result<Vec<ShellFileDiff>, String> {
+    let backup = find_backup(backup_id)?;
+
+    let mut diffs = Vec::new();
+    for (file_path, lines) in file_restore_plan(&backup, vars) {
+        let content = fs::read_to_string(&file_path).unwrap_or_default();
+        let (_, added) = insert_lines(&content, &lines);
+        if !added.is_empty() {
+            diffs.push(ShellFileDiff {
+                file_path,
+                removed: Vec::new(),
+                added,
+            });
+        }
+    }
+
+    Ok(diffs)
+}
+
+/// 仅恢复备份中选中的变量；`vars` 为空时恢复全部
+pub fn restore_env_vars(backup_id: &str, vars: &[String]) -> Result<(), String> {
+    let backup = find_backup(backup_id)?;
+    restore_backup(&backup, vars)
+}
+
+fn restore_backup(backup: &BackupInfo, vars: &[String]) -> Result<(), String> {
+    for (file_path, lines) in file_restore_plan(backup, vars) {
+        let content =
+            fs::read_to_string(&file_path).map_err(|e| format!("读取文件失败 {file_path}: {e}"))?;
+        let (new_content, _) = insert_lines(&content, &lines);
+        fs::write(&file_path, new_content).map_err(|e| format!("写入文件失败 {file_path}: {e}"))?;
+    }
+
+    for entry in selected_entries(backup, vars).filter(|e| e.source_type != "file") {
+        restore_single_env(&entry.to_conflict())?;
+    }
+
+    Ok(())
+}
+
+fn selected_entries<'a>(
+    backup: &'a BackupInfo,
+    vars: &'a [String],
+) -> impl Iterator<Item = &'a EnvBackupEntry> + 'a {
+    backup
+        .entries
+        .iter()
+        .filter(move |e| vars.is_empty() || vars.contains(&e.var_name))
+}
+
+/// 按文件汇总需要写回的行；旧备份没有原始行时追加 export 语句
+fn file_restore_plan(backup: &BackupInfo, vars: &[String]) -> BTreeMap<String, Vec<ShellLine>> {
+    let mut plan: BTreeMap<String, Vec<ShellLine>> = BTreeMap::new();
+    for entry in selected_entries(backup, vars).filter(|e| e.source_type == "file") {
+        let (file_path, line_number) = split_source_path(&entry.source_path);
+        let lines = if entry.lines.is_empty() {
+            vec![ShellLine {
+                line_number: line_number.unwrap_or(usize::MAX),
+                content: format!("export {}={}", entry.var_name, entry.var_value),
+            }]
+        } else {
+            entry.lines.clone()
+        };
+        plan.entry(file_path.to_string()).or_default().extend(lines);
+    }
+    plan
+}
+
+/// 将 `path:line` 形式的来源拆分为文件路径与行号
+fn split_source_path(source_path: &str) -> (&str, Option<usize>) {
+    match source_path.rsplit_once(':') {
+        Some((path, line)) if line.parse::<usize>().is_ok() => (path, line.parse().ok()),
+        _ => (source_path, None),
+    }
+}
+
+/// 判断 shell 行是否在给目标变量赋值（兼容 `export` 前缀）
+fn line_sets_var(line: &str, var_name: &str) -> bool {
+    let trimmed = line.trim();
+    let export_line = trimmed.strip_prefix("export ").unwrap_or(trimmed);
+    export_line
+        .find('=')
+        .is_some_and(|eq_pos| export_line[..eq_pos].trim() == var_name)
+}
+
+/// 删除给指定变量赋值的行，返回新内容与被删除的行（原始行号）
+fn remove_var_lines(content: &str, vars: &[&str]) -> (String, Vec<ShellLine>) {
+    let mut kept = Vec::new();
+    let mut removed = Vec::new();
+
+    for (idx, line) in content.lines().enumerate() {
+        if vars.iter().any(|var| line_sets_var(line, var)) {
+            removed.push(ShellLine {
+                line_number: idx + 1,
+                content: line.to_string(),
+            });
+        } else {
+            kept.push(line);
+        }
+    }
+
+    (join_lines(&kept, content), removed)
+}
+
+/// 按原行号插回备份的行，已存在的相同行会跳过；返回新内容与实际插入的行
+fn insert_lines(content: &str, lines: &[ShellLine]) -> (String, Vec<ShellLine>) {
+    let mut sorted = lines.to_vec();
+    sorted.sort_by_key(|l| l.line_number);
+    let mut out: Vec<&str> = content.lines().collect();
+
+    let mut inserted = Vec::new();
+    for line in &sorted {
+        if out.contains(&line.content.as_str()) {
+            continue;
+        }
+        let idx = line.line_number.saturating_sub(1).min(out.len());
+        out.insert(idx, line.content.as_str());
+        inserted.push(ShellLine {
+            line_number: idx + 1,
+            content: line.content.clone(),
+        });
+    }
+
+    (join_lines(&out, content), inserted)
+}
+
+/// 保留原文件末尾换行的习惯
+fn join_lines(lines: &[&str], original: &str) -> String {
+    let mut joined = lines.join("\n");
+    if original.ends_with('\n') && !joined.is_empty() {
+        joined.push('\n');
+    }
+    joined
+}
+
 /// Get backup directory path
 fn get_backup_dir() -> Result<PathBuf, String> {
     let home = dirs::home_dir().ok_or("无法获取用户主目录")?;
@@ -106,37 +423,20 @@ fn delete_single_env(conflict: &EnvConflict) -> Result<(), String> {
     match conflict.source_type.as_str() {
         "file" => {
             // Parse file path and line number from source_path (format: "path:line")
-            let parts: Vec<&str> = conflict.source_path.split(':').collect();
-            if parts.len() < 2 {
+            let (file_path, line_number) = split_source_path(&conflict.source_path);
+            if line_number.is_none() {
                 return Err("无效的文件路径格式".to_string());
             }
 
-            let file_path = parts[0];
-
             // Read file content
             let content = fs::read_to_string(file_path)
                 .map_err(|e| format!("读取文件失败 {file_path}: {e}"))?;
 
-            // Filter out the line containing the environment variable
-            let new_content: Vec<String> = content
-                .lines()
-                .filter(|line| {
-                    let trimmed = line.trim();
-                    let export_line = trimmed.strip_prefix("export ").unwrap_or(trimmed);
-
-                    // Check if this line sets the target variable
-                    if let Some(eq_pos) = export_line.find('=') {
-                        let var_name = export_line[..eq_pos].trim();
-                        var_name != conflict.var_name
-                    } else {
-                        true
-                    }
-                })
-                .map(|s| s.to_string())
-                .collect();
+            // Filter out the lines setting the environment variable
+            let (new_content, _) = remove_var_lines(&content, &[conflict.var_name.as_str()]);
 
             // Write back to file
-            fs::write(file_path, new_content.join("\n"))
+            fs::write(file_path, new_content)
                 .map_err(|e| format!("写入文件失败 {file_path}: {e}"))?;
 
             Ok(())
@@ -151,18 +451,10 @@ fn delete_single_env(conflict: &EnvConflict) -> Result<(), String> {
 
 /// Restore environment variables from backup
 pub fn restore_from_backup(backup_path: String) -> Result<(), String> {
-    // Read backup file
-    let content = fs::read_to_string(&backup_path).map_err(|e| format!("读取备份文件失败: {e}"))?;
-
-    let backup_info: BackupInfo =
-        serde_json::from_str(&content).map_err(|e| format!("解析备份文件失败: {e}"))?;
+    let backup_info = read_backup(Path::new(&backup_path))?;
 
-    // Restore each variable
-    for conflict in &backup_info.conflicts {
-        restore_single_env(conflict)?;
-    }
-
-    Ok(())
+    // Restore every variable in the backup
+    restore_backup(&backup_info, &[])
 }
 
 /// Restore a single environment variable
@@ -196,31 +488,12 @@ fn restore_single_env(conflict: &EnvConflict) -> Result<(), String> {
     }
 }
 
+/// shell 文件由 `restore_backup` 按原始行写回，这里只处理其他来源
 #[cfg(not(target_os = "windows"))]
 fn restore_single_env(conflict: &EnvConflict) -> Result<(), String> {
     match conflict.source_type.as_str() {
-        "file" => {
-            // Parse file path from source_path
-            let parts: Vec<&str> = conflict.source_path.split(':').collect();
-            if parts.is_empty() {
-                return Err("无效的文件路径格式".to_string());
-            }
-
-            let file_path = parts[0];
-
-            // Read file content
-            let mut content = fs::read_to_string(file_path)
-                .map_err(|e| format!("读取文件失败 {file_path}: {e}"))?;
-
-            // Append the environment variable line
-            let export_line = format!("\nexport {}={}", conflict.var_name, conflict.var_value);
-            content.push_str(&export_line);
-
-            // Write back to file
-            fs::write(file_path, content).map_err(|e| format!("写入文件失败 {file_path}: {e}"))?;
-
-            Ok(())
-        }
+        // 进程环境变量在删除时未做修改，无需恢复
+        "system" => Ok(()),
         _ => Err(format!(
             "无法恢复类型为 {} 的环境变量",
             conflict.source_type
@@ -237,4 +510,45 @@ mod tests {
         let backup_dir = get_backup_dir();
         assert!(backup_dir.is_ok());
     }
+
+    #[test]
+    fn test_remove_and_insert_lines_round_trip() {
+        let content = "# profile\nexport ANTHROPIC_API_KEY=sk-1\nalias ll='ls -l'\nANTHROPIC_BASE_URL=https://x\n";
+
+        let (removed_content, removed) =
+            remove_var_lines(content, &["ANTHROPIC_API_KEY", "ANTHROPIC_BASE_URL"]);
+        assert_eq!(removed_content, "# profile\nalias ll='ls -l'\n");
+        assert_eq!(
+            removed.iter().map(|l| l.line_number).collect::<Vec<_>>(),
+            vec![2, 4]
+        );
+
+        // 只恢复其中一个变量
+        let (partial, added) = insert_lines(&removed_content, &removed[..1]);
+        assert_eq!(
+            partial,
+            "# profile\nexport ANTHROPIC_API_KEY=sk-1\nalias ll='ls -l'\n"
+        );
+        assert_eq!(added.len(), 1);
+
+        let (restored, _) = insert_lines(&partial, &removed);
+        assert_eq!(restored, content);
+
+        // 已存在的行不会重复插入
+        let (again, added) = insert_lines(&restored, &removed);
+        assert_eq!(again, content);
+        assert!(added.is_empty());
+    }
+
+    #[test]
+    fn test_split_source_path() {
+        assert_eq!(
+            split_source_path("/home/u/.zshrc:12"),
+            ("/home/u/.zshrc", Some(12))
+        );
+        assert_eq!(
+            split_source_path("Process Environment"),
+            ("Process Environment", None)
+        );
+    }
 }
@@ -0,0 +1,205 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::error::AppError;
+use crate::notifications::{notify, NotificationCategory, NotificationText};
+use crate::settings::get_external_backup_settings;
+use crate::store::AppState;
+
+pub(crate) const STATUS_KEY: &str = "external_backup_status";
+const FILE_PREFIX: &str = "cli-hub-backup-";
+/// 调度器检查是否到期的间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// 最近一次外部备份的结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalBackupStatus {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_success_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_file: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_failure_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+/// 将完整 SQL 导出定期写入用户指定的外部目录，并按数量轮换
+pub struct ExternalBackupService;
+
+impl ExternalBackupService {
+    pub fn status(state: &AppState) -> Result<ExternalBackupStatus, AppError> {
+        Ok(state
+            .storage
+            .get_setting(STATUS_KEY)?
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default())
+    }
+
+    /// 立即导出一次；成功或失败都会记录到状态中
+    pub fn backup_now(state: &AppState) -> Result<ExternalBackupStatus, AppError> {
+        let (dir, settings) = get_external_backup_settings().ok_or_else(|| {
+            AppError::localized(
+                "external_backup.not_configured",
+                "尚未设置外部备份目录",
+                "External backup directory is not configured",
+            )
+        })?;
+
+        let mut status = Self::status(state)?;
+        let now = chrono::Utc::now();
+
+        match Self::write_backup(state, &dir, settings.retain) {
+            Ok(path) => {
+                log::info!("外部备份完成: {}", path.display());
+                status.last_success_at = Some(now.timestamp());
+                status.last_file = Some(path.to_string_lossy().to_string());
+                status.last_error = None;
+                Self::save_status(state, &status)?;
+                Ok(status)
+            }
+            Err(err) => {
+                log::warn!("外部备份失败: {err}");
+                status.last_failure_at = Some(now.timestamp());
+                status.last_error = Some(err.to_string());
+                Self::save_status(state, &status)?;
+                Err(err)
+            }
+        }
+    }
+
+    /// 是否已到下一次定期导出时间（以最近一次尝试为准，避免失败后频繁重试）
+    pub fn is_due(state: &AppState) -> Result<bool, AppError> {
+        let Some((_, settings)) = get_external_backup_settings() else {
+            return Ok(false);
+        };
+        let status = Self::status(state)?;
+        let last_attempt = status.last_success_at.max(status.last_failure_at);
+        let interval_secs = i64::from(settings.interval_hours.max(1)) * 3600;
+
+        Ok(last_attempt
+            .map(|ts| chrono::Utc::now().timestamp() - ts >= interval_secs)
+            .unwrap_or(true))
+    }
+
+    /// 启动后台调度器
+    pub fn spawn_scheduler(state: AppState) {
+        tauri::async_runtime::spawn(async move {
+            loop {
+                let state = state.clone();
+                let result = tauri::async_runtime::spawn_blocking(move || {
+                    if Self::is_due(&state)? {
+                        return Self::backup_now(&state).map(Some);
+                    }
+                    Ok::<_, AppError>(None)
+                })
+                .await;
+
+                match result {
+                    Ok(Ok(Some(status))) => {
+                        let file = status.last_file.unwrap_or_default();
+                        notify(
+                            NotificationCategory::Backup,
+                            true,
+                            NotificationText::new(
+                                format!("已导出到 {file}"),
+                                format!("Exported to {file}"),
+                            ),
+                        );
+                    }
+                    Ok(Err(err)) => {
+                        log::debug!("定期外部备份未完成: {err}");
+                        notify(
+                            NotificationCategory::Backup,
+                            false,
+                            NotificationText::new(err.to_string(), err.to_string()),
+                        );
+                    }
+                    _ => {}
+                }
+
+                tokio::time::sleep(CHECK_INTERVAL).await;
+            }
+        });
+    }
+
+    fn write_backup(state: &AppState, dir: &Path, retain: usize) -> Result<PathBuf, AppError> {
+        fs::create_dir_all(dir).map_err(|e| AppError::io(dir, e))?;
+
+        let file_name = format!(
+            "{FILE_PREFIX}{}.sql",
+            chrono::Local::now().format("%Y%m%d_%H%M%S")
+        );
+        let path = dir.join(file_name);
+        state.db.export_sql(&path)?;
+
+        rotate_backups(dir, retain.max(1))?;
+        Ok(path)
+    }
+
+    fn save_status(state: &AppState, status: &ExternalBackupStatus) -> Result<(), AppError> {
+        let json =
+            serde_json::to_string(status).map_err(|e| AppError::JsonSerialize { source: e })?;
+        state.storage.set_setting(STATUS_KEY, &json)
+    }
+}
+
+/// 只清理本应用生成的备份文件，文件名中的时间戳保证按名称排序即按时间排序
+fn rotate_backups(dir: &Path, retain: usize) -> Result<(), AppError> {
+    let mut backups: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|e| AppError::io(dir, e))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(FILE_PREFIX) && n.ends_with(".sql"))
+        })
+        .collect();
+
+    if backups.len() <= retain {
+        return Ok(());
+    }
+
+    backups.sort();
+    let remove_count = backups.len() - retain;
+    for path in backups.into_iter().take(remove_count) {
+        if let Err(err) = fs::remove_file(&path) {
+            log::warn!("删除旧的外部备份失败 {}: {err}", path.display());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotate_backups_keeps_newest_own_files() {
+        let dir = tempfile::tempdir().unwrap();
+        for stamp in ["20240101_000000", "20240102_000000", "20240103_000000"] {
+            fs::write(dir.path().join(format!("{FILE_PREFIX}{stamp}.sql")), "").unwrap();
+        }
+        fs::write(dir.path().join("notes.sql"), "").unwrap();
+
+        rotate_backups(dir.path(), 2).unwrap();
+
+        let mut names: Vec<String> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                format!("{FILE_PREFIX}20240102_000000.sql"),
+                format!("{FILE_PREFIX}20240103_000000.sql"),
+                "notes.sql".to_string(),
+            ]
+        );
+    }
+}
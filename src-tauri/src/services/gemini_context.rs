@@ -0,0 +1,192 @@
+use crate::config::{delete_file, write_text_file};
+use crate::error::AppError;
+use crate::gemini_config::{get_gemini_dir, read_context_file_names, write_context_file_names};
+use crate::prompt::GeminiContextFile;
+use crate::services::sync_pause::is_management_paused;
+use crate::store::AppState;
+
+/// 主提示词文件，由 PromptService 管理
+const PRIMARY_CONTEXT_FILE: &str = "GEMINI.md";
+
+/// Gemini 额外上下文文件管理
+///
+/// 内容保存在数据库中，启用时写入 `~/.gemini/<file_name>` 并加入 settings.json 的
+/// 上下文文件名列表；停用时回填磁盘内容后删除文件。
+pub struct GeminiContextService;
+
+impl GeminiContextService {
+    pub fn list(state: &AppState) -> Result<Vec<GeminiContextFile>, AppError> {
+        state.storage.get_gemini_context_files()
+    }
+
+    /// 新建或更新上下文文件；已启用的文件会同步写入磁盘
+    pub fn upsert(
+        state: &AppState,
+        file_name: &str,
+        content: String,
+    ) -> Result<GeminiContextFile, AppError> {
+        let file_name = validate_file_name(file_name)?;
+        let now = chrono::Utc::now().timestamp();
+
+        let existing = Self::find(state, &file_name)?;
+        let file = GeminiContextFile {
+            file_name: file_name.clone(),
+            content,
+            enabled: existing.as_ref().is_some_and(|f| f.enabled),
+            created_at: existing.and_then(|f| f.created_at).or(Some(now)),
+            updated_at: Some(now),
+        };
+
+        state.storage.save_gemini_context_file(&file)?;
+        if file.enabled && !is_management_paused() {
+            write_text_file(&get_gemini_dir().join(&file.file_name), &file.content)?;
+        }
+
+        Ok(file)
+    }
+
+    pub fn set_enabled(state: &AppState, file_name: &str, enabled: bool) -> Result<(), AppError> {
+        let mut file = Self::find(state, file_name)?.ok_or_else(|| not_found(file_name))?;
+        let path = get_gemini_dir().join(&file.file_name);
+
+        if is_management_paused() {
+            // 仅记录启用状态，磁盘文件在恢复管理时统一处理
+        } else if enabled {
+            write_text_file(&path, &file.content)?;
+        } else if path.exists() {
+            // 停用前回填用户可能在 Gemini 中直接修改过的内容
+            if let Ok(live) = std::fs::read_to_string(&path) {
+                if !live.trim().is_empty() && live != file.content {
+                    log::info!("回填 Gemini 上下文文件内容: {}", file.file_name);
+                    file.content = live;
+                    file.updated_at = Some(chrono::Utc::now().timestamp());
+                }
+            }
+            delete_file(&path)?;
+        }
+
+        file.enabled = enabled;
+        state.storage.save_gemini_context_file(&file)?;
+
+        Self::sync_settings(state)
+    }
+
+    pub fn delete(state: &AppState, file_name: &str) -> Result<(), AppError> {
+        if Self::find(state, file_name)?.is_some_and(|f| f.enabled) {
+            return Err(AppError::localized(
+                "gemini.context.delete_enabled",
+                "无法删除已启用的上下文文件",
+                "Cannot delete an enabled context file",
+            ));
+        }
+        state.storage.delete_gemini_context_file(file_name)
+    }
+
+    /// 使 settings.json 的上下文文件名与已启用的文件保持一致
+    ///
+    /// 用户手动添加、不受本应用管理的文件名会保留。
+    pub fn sync_settings(state: &AppState) -> Result<(), AppError> {
+        if is_management_paused() {
+            return Ok(());
+        }
+        let files = state.storage.get_gemini_context_files()?;
+        let is_managed =
+            |name: &str| name == PRIMARY_CONTEXT_FILE || files.iter().any(|f| f.file_name == name);
+
+        let mut names = vec![PRIMARY_CONTEXT_FILE.to_string()];
+        for name in read_context_file_names()? {
+            if !is_managed(&name) && !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names.extend(
+            files
+                .iter()
+                .filter(|f| f.enabled)
+                .map(|f| f.file_name.clone()),
+        );
+
+        write_context_file_names(&names)
+    }
+
+    /// 按数据库中的启用状态重写全部上下文文件，用于恢复管理后的统一同步
+    ///
+    /// 停用文件直接删除而不回填：暂停期间的数据库状态优先。
+    pub fn resync(state: &AppState) -> Result<(), AppError> {
+        let dir = get_gemini_dir();
+        for file in state.storage.get_gemini_context_files()? {
+            let path = dir.join(&file.file_name);
+            if file.enabled {
+                write_text_file(&path, &file.content)?;
+            } else if path.exists() {
+                delete_file(&path)?;
+            }
+        }
+        Self::sync_settings(state)
+    }
+
+    fn find(state: &AppState, file_name: &str) -> Result<Option<GeminiContextFile>, AppError> {
+        Ok(state
+            .storage
+            .get_gemini_context_files()?
+            .into_iter()
+            .find(|f| f.file_name == file_name))
+    }
+}
+
+/// 仅允许 `~/.gemini/` 下的 Markdown 文件名，且不能与主提示词文件冲突
+fn validate_file_name(file_name: &str) -> Result<String, AppError> {
+    let name = file_name.trim();
+    let invalid = name.is_empty()
+        || name.starts_with('.')
+        || name.contains(['/', '\\'])
+        || !name.to_ascii_lowercase().ends_with(".md");
+    if invalid {
+        return Err(AppError::localized(
+            "gemini.context.invalid_name",
+            format!("无效的上下文文件名: {file_name}（需为 .md 文件名，且不含路径）"),
+            format!(
+                "Invalid context file name: {file_name} (must be a .md file name without a path)"
+            ),
+        ));
+    }
+    if name.eq_ignore_ascii_case(PRIMARY_CONTEXT_FILE) {
+        return Err(AppError::localized(
+            "gemini.context.reserved_name",
+            "GEMINI.md 由提示词管理，请在提示词页面编辑",
+            "GEMINI.md is managed by prompts; edit it from the prompts page",
+        ));
+    }
+    Ok(name.to_string())
+}
+
+fn not_found(file_name: &str) -> AppError {
+    AppError::localized(
+        "gemini.context.not_found",
+        format!("上下文文件不存在: {file_name}"),
+        format!("Context file not found: {file_name}"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_file_name() {
+        assert_eq!(
+            validate_file_name(" CONVENTIONS.md ").unwrap(),
+            "CONVENTIONS.md"
+        );
+        for bad in [
+            "",
+            ".hidden.md",
+            "../x.md",
+            "dir/x.md",
+            "notes.txt",
+            "gemini.md",
+        ] {
+            assert!(validate_file_name(bad).is_err(), "{bad}");
+        }
+    }
+}
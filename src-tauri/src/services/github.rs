@@ -0,0 +1,213 @@
+use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
+use reqwest::header::{HeaderMap, AUTHORIZATION, RETRY_AFTER};
+use reqwest::{Client, Response, StatusCode};
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::error::format_skill_error;
+
+/// 限流时的错误码，前端据此与普通网络错误区分
+pub const RATE_LIMITED_CODE: &str = "GITHUB_RATE_LIMITED";
+const NETWORK_ERROR_CODE: &str = "NETWORK_ERROR";
+
+/// 含首次请求在内的最大尝试次数
+const MAX_ATTEMPTS: u32 = 3;
+const BASE_BACKOFF: Duration = Duration::from_secs(1);
+/// Retry-After 超过该值时不再原地等待，直接返回限流错误
+const MAX_RETRY_WAIT: Duration = Duration::from_secs(10);
+
+/// 所有 GitHub 请求共享的限流状态，取自最近一次响应头
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GitHubRateLimit {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remaining: Option<u32>,
+    /// 配额重置时间（Unix 秒）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reset_at: Option<i64>,
+    /// 是否携带了 GitHub Token
+    pub authenticated: bool,
+    pub updated_at: i64,
+}
+
+static RATE_LIMIT: Lazy<Mutex<Option<GitHubRateLimit>>> = Lazy::new(Default::default);
+
+/// 最近一次记录的限流状态；尚未请求过 GitHub 时为空
+pub fn rate_limit_status() -> Option<GitHubRateLimit> {
+    RATE_LIMIT.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// 设置中配置了 Token 时使用 API 的 zipball 地址（5000 次/小时且支持私有仓库），
+/// 否则沿用 github.com 的归档下载
+pub fn archive_url(owner: &str, name: &str, branch: &str) -> String {
+    if crate::settings::get_github_token().is_some() {
+        format!("https://api.github.com/repos/{owner}/{name}/zipball/{branch}")
+    } else {
+        format!("https://github.com/{owner}/{name}/archive/refs/heads/{branch}.zip")
+    }
+}
+
+/// 错误是否为 GitHub 限流（换分支、换仓库重试都没有意义）
+pub fn is_rate_limit_error(err: &anyhow::Error) -> bool {
+    err.to_string().contains(RATE_LIMITED_CODE)
+}
+
+/// 发送 GET 请求：自动附带 Token、记录限流头，网络错误和 5xx 按指数退避重试。
+/// 返回的响应可能仍是 4xx（如 404），由调用方处理
+pub async fn get_with_retry(client: &Client, url: &str) -> Result<Response> {
+    let token = crate::settings::get_github_token();
+    let authenticated = token.is_some();
+    if let Some(err) = exhausted_error(authenticated) {
+        return Err(err);
+    }
+
+    let mut attempt = 1;
+    loop {
+        let mut request = client.get(url);
+        if let Some(token) = &token {
+            request = request.header(AUTHORIZATION, format!("Bearer {token}"));
+        }
+
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) if attempt < MAX_ATTEMPTS => {
+                log::warn!("请求 GitHub 失败（第 {attempt} 次），稍后重试: {e}");
+                tokio::time::sleep(backoff(attempt)).await;
+                attempt += 1;
+                continue;
+            }
+            Err(e) => {
+                let reason = e.to_string();
+                return Err(anyhow!(format_skill_error(
+                    NETWORK_ERROR_CODE,
+                    &[("reason", &reason)],
+                    Some(if e.is_timeout() || e.is_connect() {
+                        "checkProxy"
+                    } else {
+                        "checkNetwork"
+                    }),
+                )));
+            }
+        };
+
+        let headers = response.headers();
+        record_headers(headers, authenticated);
+        let status = response.status();
+
+        if is_rate_limited(status, headers) {
+            let wait = retry_after(headers);
+            match wait {
+                Some(wait) if wait <= MAX_RETRY_WAIT && attempt < MAX_ATTEMPTS => {
+                    log::warn!("GitHub 限流，{} 秒后重试", wait.as_secs());
+                    tokio::time::sleep(wait).await;
+                    attempt += 1;
+                    continue;
+                }
+                _ => return Err(rate_limited_error(authenticated)),
+            }
+        }
+
+        if status.is_server_error() && attempt < MAX_ATTEMPTS {
+            log::warn!("GitHub 返回 {status}（第 {attempt} 次），稍后重试");
+            tokio::time::sleep(backoff(attempt)).await;
+            attempt += 1;
+            continue;
+        }
+
+        return Ok(response);
+    }
+}
+
+fn backoff(attempt: u32) -> Duration {
+    BASE_BACKOFF * 2u32.pow(attempt - 1)
+}
+
+fn header_value<T: std::str::FromStr>(headers: &HeaderMap, name: &str) -> Option<T> {
+    headers.get(name)?.to_str().ok()?.trim().parse().ok()
+}
+
+/// 429，或配额耗尽的 403；其余 403（无权限等）按普通错误处理
+fn is_rate_limited(status: StatusCode, headers: &HeaderMap) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || (status == StatusCode::FORBIDDEN
+            && (header_value::<u32>(headers, "x-ratelimit-remaining") == Some(0)
+                || headers.contains_key(RETRY_AFTER)))
+}
+
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    header_value::<u64>(headers, RETRY_AFTER.as_str()).map(Duration::from_secs)
+}
+
+fn record_headers(headers: &HeaderMap, authenticated: bool) {
+    let remaining = header_value::<u32>(headers, "x-ratelimit-remaining");
+    // github.com 的归档下载不带限流头，保留上一次 API 响应的记录
+    if remaining.is_none() {
+        return;
+    }
+    *RATE_LIMIT.lock().unwrap_or_else(|e| e.into_inner()) = Some(GitHubRateLimit {
+        limit: header_value(headers, "x-ratelimit-limit"),
+        remaining,
+        reset_at: header_value(headers, "x-ratelimit-reset"),
+        authenticated,
+        updated_at: chrono::Utc::now().timestamp(),
+    });
+}
+
+/// 已知配额耗尽且未到重置时间时直接失败，不再白白发请求；
+/// 若之后配置了 Token（身份变化）则重新尝试
+fn exhausted_error(authenticated: bool) -> Option<anyhow::Error> {
+    let status = rate_limit_status()?;
+    let now = chrono::Utc::now().timestamp();
+    let exhausted = status.remaining == Some(0)
+        && status.authenticated == authenticated
+        && status.reset_at.is_some_and(|reset| reset > now);
+    exhausted.then(|| rate_limited_error(authenticated))
+}
+
+fn rate_limited_error(authenticated: bool) -> anyhow::Error {
+    let reset_at = rate_limit_status()
+        .and_then(|s| s.reset_at)
+        .map(|reset| reset.to_string())
+        .unwrap_or_default();
+    anyhow!(format_skill_error(
+        RATE_LIMITED_CODE,
+        &[
+            ("resetAt", &reset_at),
+            (
+                "authenticated",
+                if authenticated { "true" } else { "false" }
+            ),
+        ],
+        Some(if authenticated {
+            "retryLater"
+        } else {
+            "addGithubToken"
+        }),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn only_quota_exhaustion_counts_as_rate_limit() {
+        let mut headers = HeaderMap::new();
+        assert!(is_rate_limited(StatusCode::TOO_MANY_REQUESTS, &headers));
+        assert!(!is_rate_limited(StatusCode::FORBIDDEN, &headers));
+
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+        assert!(is_rate_limited(StatusCode::FORBIDDEN, &headers));
+        assert!(!is_rate_limited(StatusCode::NOT_FOUND, &headers));
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("7"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(7)));
+        assert_eq!(backoff(1), Duration::from_secs(1));
+        assert_eq!(backoff(3), Duration::from_secs(4));
+    }
+}
@@ -0,0 +1,181 @@
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_opener::OpenerExt;
+
+use crate::deeplink::build_action_deeplink;
+use crate::error::AppError;
+use crate::services::{QuickAction, QuickActionKind, QuickActionService};
+use crate::store::AppState;
+
+/// 清单格式标识，扩展据此判断能否解析
+const MANIFEST_FORMAT: &str = "cli-hub-launcher";
+const MANIFEST_VERSION: u32 = 1;
+
+/// 通过深链接执行操作后发给前端的事件，载荷为 `QuickActionOutcome`
+pub const LAUNCHER_ACTION_EVENT: &str = "launcher-action";
+
+/// 清单中的一条操作
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LauncherAction {
+    /// 与命令面板相同的操作 ID，只要目标对象不被删除就保持不变
+    pub id: String,
+    pub kind: QuickActionKind,
+    pub app: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_id: Option<String>,
+    pub title: String,
+    /// 打开后直接执行操作的 clihub://v1/action 链接；配置了深链接密钥时附带按操作计算的签名
+    pub deeplink: String,
+    /// 在终端中触发同一操作的命令行参数（argv）
+    pub command: Vec<String>,
+}
+
+/// 供 Raycast / Alfred / Keypirinha 等启动器扩展生成命令的清单
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LauncherManifest {
+    pub format: String,
+    pub version: u32,
+    pub generated_at: i64,
+    pub actions: Vec<LauncherAction>,
+}
+
+pub struct LauncherService;
+
+impl LauncherService {
+    /// 生成包含全部供应商、提示词、MCP 与目录操作的清单，按 ID 排序保证输出稳定
+    pub fn manifest(state: &AppState) -> Result<LauncherManifest, AppError> {
+        let settings = crate::settings::get_effective_settings();
+        let english = settings.language.as_deref() == Some("en");
+        let secret = settings.security.and_then(|s| s.deeplink_secret);
+        let launcher = launch_prefix();
+
+        let mut actions = QuickActionService::all_actions(state, true)?
+            .into_iter()
+            .map(|action| {
+                let deeplink = build_action_deeplink(&action.id, secret.as_deref())?;
+                let command = if launcher.is_empty() {
+                    Vec::new()
+                } else {
+                    launcher
+                        .iter()
+                        .cloned()
+                        .chain(std::iter::once(deeplink.clone()))
+                        .collect()
+                };
+                Ok(LauncherAction {
+                    title: action_title(&action, english),
+                    id: action.id,
+                    kind: action.kind,
+                    app: action.app,
+                    target_id: action.target_id,
+                    deeplink,
+                    command,
+                })
+            })
+            .collect::<Result<Vec<_>, AppError>>()?;
+        actions.sort_by(|a, b| a.id.cmp(&b.id));
+
+        Ok(LauncherManifest {
+            format: MANIFEST_FORMAT.to_string(),
+            version: MANIFEST_VERSION,
+            generated_at: chrono::Utc::now().timestamp_millis(),
+            actions,
+        })
+    }
+
+    /// 执行 clihub://v1/action 链接对应的操作；在后台线程中完成，失败时通知前端
+    pub fn run_from_deeplink(app: &AppHandle, url: &str, action_id: String) {
+        let app = app.clone();
+        let url = url.to_string();
+        tauri::async_runtime::spawn_blocking(move || {
+            if let Err(e) = Self::run(&app, &action_id) {
+                log::error!("执行启动器操作 {action_id} 失败: {e}");
+                let payload = serde_json::json!({ "url": url, "error": e.to_string() });
+                if let Err(emit_err) = app.emit("deeplink-error", payload) {
+                    log::error!("发射深链接错误事件失败: {emit_err}");
+                }
+            }
+        });
+    }
+
+    fn run(app: &AppHandle, action_id: &str) -> Result<(), AppError> {
+        let state = app
+            .try_state::<AppState>()
+            .ok_or_else(|| AppError::Message("应用状态尚未初始化".to_string()))?;
+
+        // 切换供应商与托盘走同一路径，凭证探测、托盘勾选和前端刷新保持一致
+        let (kind, app_type, target_id) = QuickActionService::parse_action_id(action_id)?;
+        if let (QuickActionKind::SwitchProvider, Some(id)) = (kind, target_id) {
+            return crate::tray::switch_provider_internal(app, app_type, id);
+        }
+
+        let outcome = QuickActionService::execute(state.inner(), action_id)?;
+        if let Some(path) = outcome.open_path.as_deref() {
+            std::fs::create_dir_all(path).map_err(|e| AppError::io(path, e))?;
+            app.opener()
+                .open_path(path.to_string(), None::<String>)
+                .map_err(|e| AppError::Message(format!("打开文件夹失败: {e}")))?;
+        }
+        if let Err(e) = app.emit(LAUNCHER_ACTION_EVENT, &outcome) {
+            log::error!("发射启动器操作事件失败: {e}");
+        }
+        Ok(())
+    }
+}
+
+/// 触发深链接的命令前缀：macOS 交给 `open`，其他平台把链接作为参数传给本程序，
+/// 由单实例插件转发给正在运行的窗口
+fn launch_prefix() -> Vec<String> {
+    if cfg!(target_os = "macos") {
+        return vec!["open".to_string()];
+    }
+    match std::env::current_exe() {
+        Ok(exe) => vec![exe.to_string_lossy().to_string()],
+        Err(e) => {
+            log::warn!("获取可执行文件路径失败，清单将不包含命令行: {e}");
+            Vec::new()
+        }
+    }
+}
+
+fn action_title(action: &QuickAction, english: bool) -> String {
+    let app = match action.app.as_str() {
+        "claude" => "Claude",
+        "codex" => "Codex",
+        "gemini" => "Gemini",
+        other => other,
+    };
+    let label = &action.label;
+    match (action.kind, english) {
+        (QuickActionKind::SwitchProvider, true) => format!("Switch {app} to {label}"),
+        (QuickActionKind::SwitchProvider, false) => format!("切换 {app} 供应商：{label}"),
+        (QuickActionKind::ToggleMcp, true) => format!("Toggle MCP {label} for {app}"),
+        (QuickActionKind::ToggleMcp, false) => format!("切换 {app} 的 MCP：{label}"),
+        (QuickActionKind::EnablePrompt, true) => format!("Enable {app} prompt {label}"),
+        (QuickActionKind::EnablePrompt, false) => format!("启用 {app} 提示词：{label}"),
+        (QuickActionKind::OpenConfigFolder, true) => format!("Open {app} config folder"),
+        (QuickActionKind::OpenConfigFolder, false) => format!("打开 {app} 配置目录"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn titles_name_the_app_and_target() {
+        let action = QuickAction {
+            id: "mcp:codex:fetch".to_string(),
+            kind: QuickActionKind::ToggleMcp,
+            app: "codex".to_string(),
+            target_id: Some("fetch".to_string()),
+            label: "Fetch".to_string(),
+            enable: Some(true),
+            score: 0,
+        };
+        assert_eq!(action_title(&action, true), "Toggle MCP Fetch for Codex");
+        assert_eq!(action_title(&action, false), "切换 Codex 的 MCP：Fetch");
+    }
+}
@@ -0,0 +1,483 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::app_config::{AppType, MultiAppConfig};
+use crate::config::get_app_config_path;
+use crate::error::AppError;
+use crate::services::provider::ProviderValidator;
+use crate::store::AppState;
+
+const APPS: [AppType; 3] = [AppType::Claude, AppType::Codex, AppType::Gemini];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LegacyItemKind {
+    Provider,
+    McpServer,
+    Prompt,
+    Skill,
+    SkillRepo,
+    CommonConfig,
+}
+
+/// config.json 中一个可迁移条目的标识；app 仅用于供应商、提示词与通用配置片段
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LegacyItemRef {
+    pub kind: LegacyItemKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app: Option<String>,
+    pub id: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LegacyItem {
+    #[serde(flatten)]
+    pub item: LegacyItemRef,
+    pub name: String,
+    pub valid: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue: Option<String>,
+    /// 数据库中已有同一条目，迁移会覆盖它
+    pub in_database: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LegacyConfigReport {
+    pub path: String,
+    pub found: bool,
+    /// 已归档的 config.json，可通过恢复操作放回原处
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived_path: Option<String>,
+    pub items: Vec<LegacyItem>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LegacySkippedItem {
+    #[serde(flatten)]
+    pub item: LegacyItemRef,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LegacyMigrationResult {
+    pub migrated: Vec<LegacyItemRef>,
+    pub skipped: Vec<LegacySkippedItem>,
+    /// 所有有效条目均已迁移时 config.json 被重命名到此路径
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived_path: Option<String>,
+}
+
+/// 旧版 config.json 到数据库的交互式迁移：逐项分析、按选择迁移、完成后归档
+pub struct LegacyMigrationService;
+
+impl LegacyMigrationService {
+    /// 分析 config.json 中的条目及其有效性；文件不存在时返回空报告
+    pub fn analyze(state: &AppState) -> Result<LegacyConfigReport, AppError> {
+        let path = get_app_config_path();
+        let archived = archived_path(&path);
+        let archived_path = archived
+            .exists()
+            .then(|| archived.to_string_lossy().to_string());
+        let items = match read_legacy_config(&path)? {
+            Some(config) => Self::collect_items(state, &config)?,
+            None => Vec::new(),
+        };
+        Ok(LegacyConfigReport {
+            path: path.to_string_lossy().to_string(),
+            found: path.exists(),
+            archived_path,
+            items,
+        })
+    }
+
+    /// 仅迁移选中的条目；迁移后若已无待迁移的有效条目，则将 config.json 归档
+    pub fn migrate_selected(
+        state: &AppState,
+        selected: Vec<LegacyItemRef>,
+    ) -> Result<LegacyMigrationResult, AppError> {
+        let path = get_app_config_path();
+        let config = read_legacy_config(&path)?.ok_or_else(|| {
+            AppError::localized(
+                "migration.legacy_config_missing",
+                format!("未找到旧版配置文件: {}", path.display()),
+                format!("Legacy config file not found: {}", path.display()),
+            )
+        })?;
+
+        let items = Self::collect_items(state, &config)?;
+        let mut chosen = HashSet::new();
+        let mut skipped = Vec::new();
+        for item in selected {
+            match items.iter().find(|candidate| candidate.item == item) {
+                Some(found) if found.valid => {
+                    chosen.insert(item);
+                }
+                Some(found) => skipped.push(LegacySkippedItem {
+                    item,
+                    reason: found.issue.clone().unwrap_or_default(),
+                }),
+                None => skipped.push(LegacySkippedItem {
+                    item,
+                    reason: "config.json 中不存在该条目".to_string(),
+                }),
+            }
+        }
+
+        if !chosen.is_empty() {
+            let subset = Self::select(state, &config, &chosen)?;
+            state.db.migrate_from_json(&subset)?;
+            log::info!("已从 config.json 迁移 {} 个条目", chosen.len());
+        }
+
+        let remaining = Self::collect_items(state, &config)?
+            .into_iter()
+            .filter(|item| item.valid && !item.in_database)
+            .count();
+        let archived_path = if remaining == 0 {
+            let target = archive(&path)?;
+            log::info!("config.json 已全部迁移，归档到 {}", target.display());
+            Some(target.to_string_lossy().to_string())
+        } else {
+            None
+        };
+
+        let mut migrated: Vec<LegacyItemRef> = chosen.into_iter().collect();
+        migrated.sort_by(|a, b| (a.kind as u8, &a.app, &a.id).cmp(&(b.kind as u8, &b.app, &b.id)));
+        Ok(LegacyMigrationResult {
+            migrated,
+            skipped,
+            archived_path,
+        })
+    }
+
+    /// 将归档的 config.json 放回原处
+    pub fn restore_archived() -> Result<String, AppError> {
+        let path = get_app_config_path();
+        let archived = archived_path(&path);
+        if !archived.exists() {
+            return Err(AppError::localized(
+                "migration.archive_missing",
+                "没有可恢复的已归档 config.json",
+                "There is no archived config.json to restore",
+            ));
+        }
+        if path.exists() {
+            return Err(AppError::localized(
+                "migration.config_exists",
+                format!("{} 已存在，请先移走后再恢复", path.display()),
+                format!(
+                    "{} already exists; move it away before restoring",
+                    path.display()
+                ),
+            ));
+        }
+        std::fs::rename(&archived, &path).map_err(|e| AppError::io(&archived, e))?;
+        Ok(path.to_string_lossy().to_string())
+    }
+
+    fn collect_items(
+        state: &AppState,
+        config: &MultiAppConfig,
+    ) -> Result<Vec<LegacyItem>, AppError> {
+        let db = &state.db;
+        let mut items = Vec::new();
+
+        for app_type in APPS {
+            let app = app_type.as_str();
+            let Some(manager) = config.apps.get(app) else {
+                continue;
+            };
+            let existing = db.get_all_providers(app)?;
+            for (id, provider) in &manager.providers {
+                let issue = ProviderValidator::validate_provider_settings(&app_type, provider)
+                    .err()
+                    .map(|e| e.to_string());
+                items.push(item(
+                    LegacyItemKind::Provider,
+                    Some(app),
+                    id,
+                    &provider.name,
+                    issue,
+                    existing.contains_key(id),
+                ));
+            }
+        }
+
+        if let Some(servers) = &config.mcp.servers {
+            let existing = db.get_all_mcp_servers()?;
+            for (id, server) in servers {
+                let issue = crate::mcp::validate_server_spec(&server.server)
+                    .err()
+                    .map(|e| e.to_string());
+                items.push(item(
+                    LegacyItemKind::McpServer,
+                    None,
+                    id,
+                    &server.name,
+                    issue,
+                    existing.contains_key(id),
+                ));
+            }
+        }
+
+        for app_type in APPS {
+            let app = app_type.as_str();
+            let prompts = match app_type {
+                AppType::Claude => &config.prompts.claude.prompts,
+                AppType::Codex => &config.prompts.codex.prompts,
+                AppType::Gemini => &config.prompts.gemini.prompts,
+            };
+            let existing = db.get_prompts(app)?;
+            for (id, prompt) in prompts {
+                let issue = prompt
+                    .content
+                    .trim()
+                    .is_empty()
+                    .then(|| "提示词内容为空".to_string());
+                items.push(item(
+                    LegacyItemKind::Prompt,
+                    Some(app),
+                    id,
+                    &prompt.name,
+                    issue,
+                    existing.contains_key(id),
+                ));
+            }
+        }
+
+        let installed = db.get_skills()?;
+        for key in config.skills.skills.keys() {
+            items.push(item(
+                LegacyItemKind::Skill,
+                None,
+                key,
+                key,
+                None,
+                installed.contains_key(key),
+            ));
+        }
+
+        let repos = db.get_skill_repos()?;
+        for repo in &config.skills.repos {
+            let id = format!("{}/{}", repo.owner, repo.name);
+            let issue = (repo.owner.trim().is_empty() || repo.name.trim().is_empty())
+                .then(|| "仓库 owner 与 name 不能为空".to_string());
+            let exists = repos
+                .iter()
+                .any(|r| r.owner == repo.owner && r.name == repo.name);
+            items.push(item(
+                LegacyItemKind::SkillRepo,
+                None,
+                &id,
+                &id,
+                issue,
+                exists,
+            ));
+        }
+
+        for app_type in APPS {
+            let app = app_type.as_str();
+            let Some(snippet) = config.common_config_snippets.get(&app_type) else {
+                continue;
+            };
+            let issue = (app_type != AppType::Codex)
+                .then(|| serde_json::from_str::<serde_json::Value>(snippet).err())
+                .flatten()
+                .map(|e| format!("无效的 JSON 格式: {e}"));
+            items.push(item(
+                LegacyItemKind::CommonConfig,
+                Some(app),
+                app,
+                app,
+                issue,
+                db.get_config_snippet(app)?.is_some(),
+            ));
+        }
+
+        Ok(items)
+    }
+
+    /// 从完整配置中裁剪出选中的条目
+    ///
+    /// 数据库中已有当前供应商或已启用提示词时，迁移进来的条目不再标记为当前/启用，
+    /// 避免同一应用出现两个当前项
+    fn select(
+        state: &AppState,
+        config: &MultiAppConfig,
+        chosen: &HashSet<LegacyItemRef>,
+    ) -> Result<MultiAppConfig, AppError> {
+        let picked = |kind: LegacyItemKind, app: Option<&str>, id: &str| {
+            chosen.contains(&LegacyItemRef {
+                kind,
+                app: app.map(str::to_string),
+                id: id.to_string(),
+            })
+        };
+
+        let mut subset = config.clone();
+        for (app, manager) in subset.apps.iter_mut() {
+            manager
+                .providers
+                .retain(|id, _| picked(LegacyItemKind::Provider, Some(app.as_str()), id));
+            let keeps_current = manager.providers.contains_key(&manager.current)
+                && state.storage.get_current_provider(app)?.is_none();
+            if !keeps_current {
+                manager.current.clear();
+            }
+        }
+
+        if let Some(servers) = subset.mcp.servers.as_mut() {
+            servers.retain(|id, _| picked(LegacyItemKind::McpServer, None, id));
+        }
+
+        for (app, prompts) in [
+            ("claude", &mut subset.prompts.claude.prompts),
+            ("codex", &mut subset.prompts.codex.prompts),
+            ("gemini", &mut subset.prompts.gemini.prompts),
+        ] {
+            prompts.retain(|id, _| picked(LegacyItemKind::Prompt, Some(app), id));
+            if state.storage.get_enabled_prompt(app)?.is_some() {
+                for prompt in prompts.values_mut() {
+                    prompt.enabled = false;
+                }
+            }
+        }
+
+        subset
+            .skills
+            .skills
+            .retain(|key, _| picked(LegacyItemKind::Skill, None, key));
+        subset.skills.repos.retain(|repo| {
+            let id = format!("{}/{}", repo.owner, repo.name);
+            picked(LegacyItemKind::SkillRepo, None, &id)
+        });
+
+        let snippets = &mut subset.common_config_snippets;
+        for (app, snippet) in [
+            ("claude", &mut snippets.claude),
+            ("codex", &mut snippets.codex),
+            ("gemini", &mut snippets.gemini),
+        ] {
+            if !picked(LegacyItemKind::CommonConfig, Some(app), app) {
+                *snippet = None;
+            }
+        }
+        subset.claude_common_config_snippet = None;
+
+        Ok(subset)
+    }
+}
+
+fn item(
+    kind: LegacyItemKind,
+    app: Option<&str>,
+    id: &str,
+    name: &str,
+    issue: Option<String>,
+    in_database: bool,
+) -> LegacyItem {
+    LegacyItem {
+        item: LegacyItemRef {
+            kind,
+            app: app.map(str::to_string),
+            id: id.to_string(),
+        },
+        name: name.to_string(),
+        valid: issue.is_none(),
+        issue,
+        in_database,
+    }
+}
+
+/// 直接解析 config.json；不使用 MultiAppConfig::load，以免在文件缺失时创建新文件
+fn read_legacy_config(path: &Path) -> Result<Option<MultiAppConfig>, AppError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(path).map_err(|e| AppError::io(path, e))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| AppError::json(path, e))
+}
+
+fn archived_path(path: &Path) -> PathBuf {
+    path.with_extension("json.migrated")
+}
+
+/// 重命名为 config.json.migrated；已有同名归档时先将其加上时间戳保留
+fn archive(path: &Path) -> Result<PathBuf, AppError> {
+    let target = archived_path(path);
+    if target.exists() {
+        let stamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        let older = path.with_extension(format!("json.migrated.{stamp}"));
+        std::fs::rename(&target, &older).map_err(|e| AppError::io(&target, e))?;
+    }
+    std::fs::rename(path, &target).map_err(|e| AppError::io(path, e))?;
+    Ok(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    #[test]
+    fn reports_item_validity_and_migrates_only_selected() {
+        let config: MultiAppConfig = serde_json::from_value(json!({
+            "version": 2,
+            "claude": {
+                "current": "good",
+                "providers": {
+                    "good": { "id": "good", "name": "Good", "settingsConfig": { "env": {} } },
+                    "bad": { "id": "bad", "name": "Bad", "settingsConfig": "oops" }
+                }
+            },
+            "mcp": {
+                "servers": {
+                    "fetch": {
+                        "id": "fetch",
+                        "name": "Fetch",
+                        "server": { "type": "stdio", "command": "uvx" },
+                        "apps": { "claude": true, "codex": false, "gemini": false }
+                    }
+                }
+            }
+        }))
+        .unwrap();
+        let state = AppState::new(Arc::new(Database::memory().unwrap()));
+
+        let items = LegacyMigrationService::collect_items(&state, &config).unwrap();
+        // 默认技能仓库也会出现在报告中，这里只关心供应商与 MCP
+        let validity: Vec<(&str, bool)> = items
+            .iter()
+            .filter(|i| i.item.kind != LegacyItemKind::SkillRepo)
+            .map(|i| (i.item.id.as_str(), i.valid))
+            .collect();
+        assert_eq!(validity, [("good", true), ("bad", false), ("fetch", true)]);
+
+        let good = items[0].item.clone();
+        let subset =
+            LegacyMigrationService::select(&state, &config, &HashSet::from([good])).unwrap();
+        state.db.migrate_from_json(&subset).unwrap();
+
+        let providers = state.storage.get_all_providers("claude").unwrap();
+        assert_eq!(providers.keys().collect::<Vec<_>>(), ["good"]);
+        assert_eq!(
+            state
+                .storage
+                .get_current_provider("claude")
+                .unwrap()
+                .as_deref(),
+            Some("good")
+        );
+        assert!(state.storage.get_all_mcp_servers().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,273 @@
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::LocalModelConfig;
+
+pub const DEFAULT_OLLAMA_URL: &str = "http://localhost:11434";
+/// 写入 Codex config.toml 的模型供应商 ID
+const CODEX_PROVIDER_ID: &str = "ollama";
+/// 本地端点探测超时，避免未启动时长时间卡住切换
+const PROBE_TIMEOUT_SECS: u64 = 3;
+
+/// 本地 Ollama 实例的探测结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalModelStatus {
+    pub base_url: String,
+    pub running: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    pub models: Vec<LocalModel>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalModel {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    #[serde(
+        default,
+        rename(deserialize = "modified_at"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub modified_at: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct OllamaVersion {
+    version: String,
+}
+
+#[derive(Deserialize)]
+struct OllamaTags {
+    #[serde(default)]
+    models: Vec<LocalModel>,
+}
+
+/// 本地模型（Ollama）供应商：探测、生成配置与切换前健康检查
+pub struct LocalModelService;
+
+impl LocalModelService {
+    /// 探测本地 Ollama 是否运行并列出已下载的模型；未运行时不返回错误
+    pub async fn detect_ollama(base_url: Option<String>) -> Result<LocalModelStatus, AppError> {
+        let base_url = normalize_base_url(base_url.as_deref());
+        let client = build_client()?;
+
+        let version = match client.get(format!("{base_url}/api/version")).send().await {
+            Ok(resp) if resp.status().is_success() => {
+                resp.json::<OllamaVersion>().await.ok().map(|v| v.version)
+            }
+            Ok(resp) => {
+                return Ok(LocalModelStatus::stopped(
+                    base_url,
+                    format!("HTTP {}", resp.status().as_u16()),
+                ))
+            }
+            Err(err) => return Ok(LocalModelStatus::stopped(base_url, err.to_string())),
+        };
+
+        let models = Self::list_models(&client, &base_url).await?;
+        Ok(LocalModelStatus {
+            base_url,
+            running: true,
+            version,
+            models,
+            error: None,
+        })
+    }
+
+    /// 确认本地端点可访问且目标模型已下载
+    pub async fn check_health(config: &LocalModelConfig) -> Result<(), AppError> {
+        let base_url = normalize_base_url(Some(&config.base_url));
+        let client = build_client()?;
+
+        let models = Self::list_models(&client, &base_url).await.map_err(|_| {
+            AppError::localized(
+                "local_model.unreachable",
+                format!("无法连接本地模型服务 {base_url}，请确认 Ollama 已启动"),
+                format!("Cannot reach local model server {base_url}; make sure Ollama is running"),
+            )
+        })?;
+
+        if !models.iter().any(|m| model_matches(&m.name, &config.model)) {
+            return Err(AppError::localized(
+                "local_model.model_missing",
+                format!(
+                    "本地未找到模型 {}，请先执行 ollama pull {}",
+                    config.model, config.model
+                ),
+                format!(
+                    "Model {} is not available locally; run `ollama pull {}` first",
+                    config.model, config.model
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// 生成本地模型供应商的 settingsConfig
+    ///
+    /// Codex 直接使用 Ollama 的 OpenAI 兼容接口；Claude 需要一个 Anthropic 兼容代理。
+    pub fn build_settings(
+        app_type: &AppType,
+        config: &LocalModelConfig,
+    ) -> Result<Value, AppError> {
+        let model = config.model.trim();
+        if model.is_empty() {
+            return Err(AppError::InvalidInput("模型名称不能为空".to_string()));
+        }
+        let base_url = normalize_base_url(Some(&config.base_url));
+
+        match app_type {
+            AppType::Codex => Ok(json!({
+                "auth": {},
+                "config": codex_config_toml(&base_url, model),
+            })),
+            AppType::Claude => {
+                let proxy_url = config
+                    .proxy_url
+                    .as_deref()
+                    .map(|u| u.trim().trim_end_matches('/'))
+                    .filter(|u| !u.is_empty())
+                    .ok_or_else(|| {
+                        AppError::localized(
+                            "local_model.proxy_required",
+                            "Claude 使用本地模型需要配置 Anthropic 兼容代理地址",
+                            "Claude requires an Anthropic-compatible proxy URL for local models",
+                        )
+                    })?;
+                Ok(json!({
+                    "env": {
+                        "ANTHROPIC_BASE_URL": proxy_url,
+                        "ANTHROPIC_AUTH_TOKEN": "ollama",
+                        "ANTHROPIC_MODEL": model,
+                    }
+                }))
+            }
+            AppType::Gemini => Err(AppError::localized(
+                "local_model.unsupported_app",
+                "Gemini 暂不支持本地模型供应商",
+                "Local model providers are not supported for Gemini",
+            )),
+        }
+    }
+
+    async fn list_models(client: &Client, base_url: &str) -> Result<Vec<LocalModel>, AppError> {
+        let resp = client
+            .get(format!("{base_url}/api/tags"))
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| AppError::Message(format!("获取本地模型列表失败: {e}")))?;
+        let tags: OllamaTags = resp
+            .json()
+            .await
+            .map_err(|e| AppError::Message(format!("解析本地模型列表失败: {e}")))?;
+        Ok(tags.models)
+    }
+}
+
+impl LocalModelStatus {
+    fn stopped(base_url: String, error: String) -> Self {
+        Self {
+            base_url,
+            running: false,
+            version: None,
+            models: Vec::new(),
+            error: Some(error),
+        }
+    }
+}
+
+fn build_client() -> Result<Client, AppError> {
+    Client::builder()
+        .timeout(Duration::from_secs(PROBE_TIMEOUT_SECS))
+        .no_proxy()
+        .build()
+        .map_err(|e| {
+            AppError::localized(
+                "local_model.client_create_failed",
+                format!("创建 HTTP 客户端失败: {e}"),
+                format!("Failed to create HTTP client: {e}"),
+            )
+        })
+}
+
+/// 去掉结尾斜杠及 OpenAI 兼容路径 `/v1`，得到 Ollama 根地址
+fn normalize_base_url(base_url: Option<&str>) -> String {
+    let url = base_url
+        .map(str::trim)
+        .filter(|u| !u.is_empty())
+        .unwrap_or(DEFAULT_OLLAMA_URL)
+        .trim_end_matches('/');
+    url.strip_suffix("/v1").unwrap_or(url).to_string()
+}
+
+/// Ollama 会给未带标签的模型补上 `:latest`
+fn model_matches(available: &str, wanted: &str) -> bool {
+    let wanted = wanted.trim();
+    available == wanted || available.strip_suffix(":latest") == Some(wanted)
+}
+
+fn codex_config_toml(base_url: &str, model: &str) -> String {
+    let model = toml_edit::Value::from(model);
+    let base_url = toml_edit::Value::from(format!("{base_url}/v1"));
+    format!(
+        r#"model_provider = "{CODEX_PROVIDER_ID}"
+model = {model}
+
+[model_providers.{CODEX_PROVIDER_ID}]
+name = "Ollama"
+base_url = {base_url}
+wire_api = "chat"
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(model: &str) -> LocalModelConfig {
+        LocalModelConfig {
+            base_url: "http://127.0.0.1:11434/v1/".to_string(),
+            model: model.to_string(),
+            proxy_url: None,
+        }
+    }
+
+    #[test]
+    fn codex_settings_are_valid_toml() {
+        let settings =
+            LocalModelService::build_settings(&AppType::Codex, &config("qwen2.5-coder:7b"))
+                .expect("build codex settings");
+        let text = settings["config"].as_str().unwrap();
+        crate::codex_config::validate_config_toml(text).expect("valid toml");
+
+        let parsed: toml::Value = toml::from_str(text).unwrap();
+        assert_eq!(parsed["model"].as_str(), Some("qwen2.5-coder:7b"));
+        assert_eq!(
+            parsed["model_providers"]["ollama"]["base_url"].as_str(),
+            Some("http://127.0.0.1:11434/v1")
+        );
+    }
+
+    #[test]
+    fn claude_settings_require_proxy() {
+        assert!(LocalModelService::build_settings(&AppType::Claude, &config("llama3")).is_err());
+    }
+
+    #[test]
+    fn model_matches_latest_tag() {
+        assert!(model_matches("llama3:latest", "llama3"));
+        assert!(model_matches("llama3:8b", "llama3:8b"));
+        assert!(!model_matches("llama3:8b", "llama3"));
+    }
+}
@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::app_config::AppType;
+use crate::config::read_json_file;
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::services::provider::{LiveConfigSync, ProviderValidator};
+use crate::store::AppState;
+
+/// 管理员下发的供应商清单
+///
+/// 清单是该组织供应商的完整集合：重新导入时，清单中不再出现的供应商会解除管理，
+/// 变回普通的本地供应商（不会被删除）。导入空清单即可解除该组织的全部管理。
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManagedProviderManifest {
+    pub org: String,
+    #[serde(default)]
+    pub claude: Vec<Provider>,
+    #[serde(default)]
+    pub codex: Vec<Provider>,
+    #[serde(default)]
+    pub gemini: Vec<Provider>,
+}
+
+/// 导入结果，条目格式为 `app:id`
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManagedImportSummary {
+    pub org: String,
+    pub added: Vec<String>,
+    pub updated: Vec<String>,
+    pub released: Vec<String>,
+}
+
+/// 组织管理的供应商：只能通过清单导入设置或解除，本地只能切换
+pub struct ManagedProviderService;
+
+impl ManagedProviderService {
+    pub fn import_file(state: &AppState, path: &Path) -> Result<ManagedImportSummary, AppError> {
+        let manifest: ManagedProviderManifest = read_json_file(path)?;
+        Self::import(state, manifest)
+    }
+
+    pub fn import(
+        state: &AppState,
+        manifest: ManagedProviderManifest,
+    ) -> Result<ManagedImportSummary, AppError> {
+        let org = manifest.org.trim().to_string();
+        if org.is_empty() {
+            return Err(AppError::localized(
+                "managed.org_required",
+                "清单缺少组织名称 (org)",
+                "The manifest is missing the organization name (org)",
+            ));
+        }
+        let sets = [
+            (AppType::Claude, manifest.claude),
+            (AppType::Codex, manifest.codex),
+            (AppType::Gemini, manifest.gemini),
+        ];
+        // 先整体校验，避免只导入一部分
+        for (app_type, providers) in &sets {
+            for provider in providers {
+                ProviderValidator::validate_provider_settings(app_type, provider)?;
+            }
+        }
+
+        let mut summary = ManagedImportSummary {
+            org: org.clone(),
+            ..Default::default()
+        };
+        for (app_type, providers) in sets {
+            let app = app_type.as_str();
+            let mut existing = state.storage.get_all_providers(app)?;
+            let current = state.storage.get_current_provider(app)?;
+            let incoming: HashSet<String> = providers.iter().map(|p| p.id.clone()).collect();
+
+            for mut provider in providers {
+                let key = format!("{app}:{}", provider.id);
+                match existing.shift_remove(&provider.id) {
+                    Some(local) => {
+                        keep_local_state(&mut provider, &local);
+                        summary.updated.push(key);
+                    }
+                    None => summary.added.push(key),
+                }
+                provider.version = None;
+                provider
+                    .meta
+                    .get_or_insert_with(Default::default)
+                    .managed_by = Some(org.clone());
+                state.storage.save_provider(app, &provider)?;
+                if current.as_deref() == Some(provider.id.as_str()) {
+                    LiveConfigSync::write_live_snapshot(&app_type, &provider)?;
+                }
+            }
+
+            for (id, mut provider) in existing {
+                let Some(meta) = provider.meta.as_mut() else {
+                    continue;
+                };
+                if meta.managed_by.as_deref() != Some(org.as_str()) || incoming.contains(&id) {
+                    continue;
+                }
+                meta.managed_by = None;
+                state.storage.save_provider(app, &provider)?;
+                summary.released.push(format!("{app}:{id}"));
+            }
+        }
+
+        log::info!(
+            "已导入 {org} 管理的供应商: 新增 {}，更新 {}，解除 {}",
+            summary.added.len(),
+            summary.updated.len(),
+            summary.released.len()
+        );
+        Ok(summary)
+    }
+}
+
+/// 保留排序、切换统计等仅与本机相关的字段
+fn keep_local_state(provider: &mut Provider, local: &Provider) {
+    provider.sort_index = local.sort_index;
+    provider.created_at = local.created_at.or(provider.created_at);
+    provider.last_switched_at = local.last_switched_at;
+    provider.switch_count = local.switch_count;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use crate::services::ProviderService;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    fn provider(id: &str) -> Provider {
+        Provider::with_id(
+            id.to_string(),
+            id.to_string(),
+            json!({ "env": {
+                "ANTHROPIC_BASE_URL": "https://gateway.example.com",
+                "ANTHROPIC_AUTH_TOKEN": "sk-org-0000000000",
+            } }),
+            None,
+        )
+    }
+
+    fn manifest(ids: &[&str]) -> ManagedProviderManifest {
+        ManagedProviderManifest {
+            org: "Acme".to_string(),
+            claude: ids.iter().map(|id| provider(id)).collect(),
+            codex: Vec::new(),
+            gemini: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn managed_providers_reject_local_edits_until_released() {
+        let state = AppState::new(Arc::new(Database::memory().unwrap()));
+        let first = ManagedProviderService::import(&state, manifest(&["a", "b"])).unwrap();
+        assert_eq!(first.added, ["claude:a", "claude:b"]);
+
+        let err = ProviderService::delete(&state, AppType::Claude, "b").unwrap_err();
+        assert!(err.to_string().contains("PROVIDER_MANAGED"));
+        assert!(ProviderService::update(&state, AppType::Claude, provider("a")).is_err());
+
+        let second = ManagedProviderService::import(&state, manifest(&["a"])).unwrap();
+        assert_eq!(second.updated, ["claude:a"]);
+        assert_eq!(second.released, ["claude:b"]);
+        ProviderService::delete(&state, AppType::Claude, "b").unwrap();
+    }
+}
@@ -1,39 +1,79 @@
 use indexmap::IndexMap;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::str::FromStr;
 
-use crate::app_config::{AppType, McpServer};
+use crate::app_config::{AppType, McpApps, McpServer, McpSortUpdate, QuarantinedMcpEntry};
 use crate::error::AppError;
-use crate::mcp;
+use crate::log_sanitizer::mask_secret;
+use crate::mcp::{self, RejectedMcpEntry};
+use crate::notifications::{notify, NotificationCategory, NotificationText};
+use crate::services::sync_pause::is_management_paused;
+use crate::services::WriteAccessService;
 use crate::store::AppState;
 
+/// 待写入 live 配置的 (服务器, 应用) 集合
+///
+/// 每次变更只标记受影响的条目，flush 时每个应用的配置文件最多读写一次
+#[derive(Debug, Default)]
+pub(crate) struct McpDirtySet {
+    by_app: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl McpDirtySet {
+    pub(crate) fn mark(&mut self, server_id: &str, app: &AppType) {
+        self.by_app
+            .entry(app.as_str().to_string())
+            .or_default()
+            .insert(server_id.to_string());
+    }
+
+    /// 标记服务器当前启用的全部应用
+    pub(crate) fn mark_enabled(&mut self, server: &McpServer) {
+        for app in server.apps.enabled_apps() {
+            self.mark(&server.id, &app);
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.by_app.is_empty()
+    }
+}
+
 /// MCP 相关业务逻辑（v3.7.0 统一结构）
 pub struct McpService;
 
 impl McpService {
     /// 获取所有 MCP 服务器（统一结构）
     pub fn get_all_servers(state: &AppState) -> Result<IndexMap<String, McpServer>, AppError> {
-        state.db.get_all_mcp_servers()
+        state.storage.get_all_mcp_servers()
     }
 
     /// 添加或更新 MCP 服务器
-    pub fn upsert_server(state: &AppState, server: McpServer) -> Result<(), AppError> {
-        state.db.save_mcp_server(&server)?;
-
-        // 同步到各个启用的应用
-        Self::sync_server_to_apps(state, &server)?;
+    ///
+    /// 前端回传遮蔽后的 env 值时沿用数据库中的原值；取消启用的应用会从其 live 配置中移除
+    pub fn upsert_server(state: &AppState, mut server: McpServer) -> Result<(), AppError> {
+        let mut dirty = McpDirtySet::default();
+        if let Some(existing) = state.storage.get_all_mcp_servers()?.get(&server.id) {
+            restore_masked_env(&mut server.server, &existing.server);
+            dirty.mark_enabled(existing);
+        }
+        state.storage.save_mcp_server(&server)?;
+        dirty.mark_enabled(&server);
 
-        Ok(())
+        Self::flush(state, dirty)
     }
 
     /// 删除 MCP 服务器
     pub fn delete_server(state: &AppState, id: &str) -> Result<bool, AppError> {
-        let server = state.db.get_all_mcp_servers()?.shift_remove(id);
+        let server = state.storage.get_all_mcp_servers()?.shift_remove(id);
 
         if let Some(server) = server {
-            state.db.delete_mcp_server(id)?;
+            state.storage.delete_mcp_server(id)?;
 
-            // 从所有应用的 live 配置中移除
-            Self::remove_server_from_all_apps(state, id, &server)?;
+            // 数据库中已不存在，flush 时会从曾启用的应用中移除
+            let mut dirty = McpDirtySet::default();
+            dirty.mark_enabled(&server);
+            Self::flush(state, dirty)?;
             Ok(true)
         } else {
             Ok(false)
@@ -47,71 +87,216 @@ impl McpService {
         app: AppType,
         enabled: bool,
     ) -> Result<(), AppError> {
-        let mut servers = state.db.get_all_mcp_servers()?;
+        let mut servers = state.storage.get_all_mcp_servers()?;
 
         if let Some(server) = servers.get_mut(server_id) {
             server.apps.set_enabled_for(&app, enabled);
-            state.db.save_mcp_server(server)?;
+            state.storage.save_mcp_server(server)?;
 
-            // 同步到对应应用
-            if enabled {
-                Self::sync_server_to_app(state, server, &app)?;
-            } else {
-                Self::remove_server_from_app(state, server_id, &app)?;
-            }
+            // 只同步对应应用
+            let mut dirty = McpDirtySet::default();
+            dirty.mark(server_id, &app);
+            Self::flush(state, dirty)?;
         }
 
         Ok(())
     }
 
-    /// 将 MCP 服务器同步到所有启用的应用
-    fn sync_server_to_apps(_state: &AppState, server: &McpServer) -> Result<(), AppError> {
-        for app in server.apps.enabled_apps() {
-            Self::sync_server_to_app_no_config(server, &app)?;
+    /// 复制 MCP 服务器为新 ID（配置与各应用启用状态一并复制）
+    pub fn duplicate_server(
+        state: &AppState,
+        id: &str,
+        new_id: &str,
+    ) -> Result<McpServer, AppError> {
+        let new_id = new_id.trim();
+        if new_id.is_empty() {
+            return Err(AppError::InvalidInput(
+                "新的 MCP 服务器 ID 不能为空".to_string(),
+            ));
         }
 
-        Ok(())
+        let servers = state.storage.get_all_mcp_servers()?;
+        if servers.contains_key(new_id) {
+            return Err(AppError::localized(
+                "mcp.duplicate_id_exists",
+                format!("MCP 服务器 ID '{new_id}' 已存在"),
+                format!("MCP server id '{new_id}' already exists"),
+            ));
+        }
+
+        let source = servers.get(id).ok_or_else(|| {
+            AppError::localized(
+                "mcp.not_found",
+                format!("未找到 MCP 服务器: {id}"),
+                format!("MCP server not found: {id}"),
+            )
+        })?;
+
+        let mut server = source.clone();
+        server.id = new_id.to_string();
+        // 名称与原 ID 相同时跟随新 ID，否则保留原名称并附加新 ID 便于区分
+        server.name = if source.name == source.id {
+            new_id.to_string()
+        } else {
+            format!("{} ({new_id})", source.name)
+        };
+
+        Self::upsert_server(state, server.clone())?;
+        Ok(server)
     }
 
-    /// 将 MCP 服务器同步到指定应用
-    fn sync_server_to_app(
-        _state: &AppState,
-        server: &McpServer,
-        app: &AppType,
-    ) -> Result<(), AppError> {
-        Self::sync_server_to_app_no_config(server, app)
+    /// 设置单个环境变量，其余配置保持不变
+    pub fn set_env_var(
+        state: &AppState,
+        id: &str,
+        key: &str,
+        value: &str,
+    ) -> Result<McpServer, AppError> {
+        let key = key.trim();
+        if key.is_empty() || key.contains('=') || key.chars().any(char::is_whitespace) {
+            return Err(AppError::localized(
+                "mcp.env_key_invalid",
+                format!("无效的环境变量名: '{key}'"),
+                format!("Invalid environment variable name: '{key}'"),
+            ));
+        }
+
+        let mut server = Self::get_server(state, id)?;
+        let spec = server
+            .server
+            .as_object_mut()
+            .ok_or_else(|| AppError::McpValidation(format!("MCP 服务器 {id} 的配置必须是对象")))?;
+        let env = spec
+            .entry("env")
+            .or_insert_with(|| serde_json::json!({}))
+            .as_object_mut()
+            .ok_or_else(|| AppError::McpValidation(format!("MCP 服务器 {id} 的 env 必须是对象")))?;
+        env.insert(
+            key.to_string(),
+            serde_json::Value::String(value.to_string()),
+        );
+
+        Self::save_and_sync(state, &server)?;
+        Ok(server)
     }
 
-    fn sync_server_to_app_no_config(server: &McpServer, app: &AppType) -> Result<(), AppError> {
-        match app {
-            AppType::Claude => {
-                mcp::sync_single_server_to_claude(&Default::default(), &server.id, &server.server)?;
-            }
-            AppType::Codex => {
-                // Codex uses TOML format, must use the correct function
-                mcp::sync_single_server_to_codex(&Default::default(), &server.id, &server.server)?;
+    /// 删除单个环境变量；env 为空时一并移除该字段
+    pub fn remove_env_var(state: &AppState, id: &str, key: &str) -> Result<McpServer, AppError> {
+        let mut server = Self::get_server(state, id)?;
+        let Some(spec) = server.server.as_object_mut() else {
+            return Ok(server);
+        };
+        let Some(env) = spec.get_mut("env").and_then(|v| v.as_object_mut()) else {
+            return Ok(server);
+        };
+        if env.remove(key).is_none() {
+            return Ok(server);
+        }
+        if env.is_empty() {
+            spec.remove("env");
+        }
+
+        Self::save_and_sync(state, &server)?;
+        Ok(server)
+    }
+
+    /// 返回 env 值已遮蔽的副本，仅用于展示
+    pub fn masked(server: &McpServer) -> McpServer {
+        let mut server = server.clone();
+        if let Some(env) = server.server.get_mut("env").and_then(|v| v.as_object_mut()) {
+            for value in env.values_mut() {
+                if let Some(s) = value.as_str() {
+                    *value = serde_json::Value::String(mask_secret(s));
+                }
             }
-            AppType::Gemini => {
-                mcp::sync_single_server_to_gemini(&Default::default(), &server.id, &server.server)?;
+        }
+        server
+    }
+
+    fn get_server(state: &AppState, id: &str) -> Result<McpServer, AppError> {
+        state
+            .storage
+            .get_all_mcp_servers()?
+            .shift_remove(id)
+            .ok_or_else(|| {
+                AppError::localized(
+                    "mcp.not_found",
+                    format!("未找到 MCP 服务器: {id}"),
+                    format!("MCP server not found: {id}"),
+                )
+            })
+    }
+
+    fn save_and_sync(state: &AppState, server: &McpServer) -> Result<(), AppError> {
+        state.storage.save_mcp_server(server)?;
+        let mut dirty = McpDirtySet::default();
+        dirty.mark_enabled(server);
+        Self::flush(state, dirty)
+    }
+
+    /// 按数据库中的最新状态写入标记过的条目：仍在该应用启用的写入，其余移除
+    pub(crate) fn flush(state: &AppState, dirty: McpDirtySet) -> Result<(), AppError> {
+        // 暂停管理期间仅更新数据库，恢复时统一同步
+        if dirty.is_empty() || is_management_paused() {
+            return Ok(());
+        }
+        let servers = state.storage.get_all_mcp_servers()?;
+        let order: Vec<String> = servers.keys().cloned().collect();
+        for (app, ids) in dirty.by_app {
+            let app = AppType::from_str(&app)?;
+            let mut upserts = HashMap::new();
+            let mut removals = Vec::new();
+            for id in ids {
+                match servers.get(&id) {
+                    Some(server) if server.apps.is_enabled_for(&app) => {
+                        upserts.insert(id, server.server.clone());
+                    }
+                    _ => removals.push(id),
+                }
             }
+            Self::sync_server_set(&app, &upserts, &removals, &order)?;
         }
         Ok(())
     }
 
-    /// 从所有曾启用过该服务器的应用中移除
-    fn remove_server_from_all_apps(
-        state: &AppState,
-        id: &str,
-        server: &McpServer,
+    /// 一次性写入某应用的多项增删（批量启停时避免逐个读写配置文件）
+    ///
+    /// `order` 为数据库中的服务器顺序，写入后 live 配置中的条目按此排列
+    pub(crate) fn sync_server_set(
+        app: &AppType,
+        upserts: &HashMap<String, serde_json::Value>,
+        removals: &[String],
+        order: &[String],
     ) -> Result<(), AppError> {
-        // 从所有曾启用的应用中移除
-        for app in server.apps.enabled_apps() {
-            Self::remove_server_from_app(state, id, &app)?;
+        if is_management_paused() {
+            return Ok(());
+        }
+        WriteAccessService::ensure_app_writable(app)?;
+        match app {
+            AppType::Claude => mcp::apply_servers_to_claude(upserts, removals, order),
+            AppType::Codex => mcp::apply_servers_to_codex(upserts, removals, order),
+            AppType::Gemini => mcp::apply_servers_to_gemini(upserts, removals, order),
         }
-        Ok(())
     }
 
-    fn remove_server_from_app(_state: &AppState, id: &str, app: &AppType) -> Result<(), AppError> {
+    /// 更新 MCP 服务器排序，并按新顺序重写各应用的 live 配置
+    pub fn update_sort_order(
+        state: &AppState,
+        updates: Vec<McpSortUpdate>,
+    ) -> Result<bool, AppError> {
+        state.storage.update_mcp_sort_order(&updates)?;
+        Self::sync_all_enabled(state)?;
+        Ok(true)
+    }
+
+    pub(crate) fn remove_server_from_app(
+        _state: &AppState,
+        id: &str,
+        app: &AppType,
+    ) -> Result<(), AppError> {
+        if is_management_paused() {
+            return Ok(());
+        }
         match app {
             AppType::Claude => mcp::remove_server_from_claude(id)?,
             AppType::Codex => mcp::remove_server_from_codex(id)?,
@@ -121,14 +306,25 @@ impl McpService {
     }
 
     /// 手动同步所有启用的 MCP 服务器到对应的应用
+    ///
+    /// 每个应用合并为一次写入，内容与 live 配置一致的条目和文件不会被重写
     pub fn sync_all_enabled(state: &AppState) -> Result<(), AppError> {
-        let servers = Self::get_all_servers(state)?;
-
-        for server in servers.values() {
-            Self::sync_server_to_apps(state, server)?;
+        let mut dirty = McpDirtySet::default();
+        for server in Self::get_all_servers(state)?.values() {
+            dirty.mark_enabled(server);
         }
+        Self::flush(state, dirty)
+    }
 
-        Ok(())
+    /// 仅将在指定应用中启用的 MCP 服务器同步到该应用
+    pub fn sync_app(state: &AppState, app: &AppType) -> Result<(), AppError> {
+        let mut dirty = McpDirtySet::default();
+        for server in Self::get_all_servers(state)?.values() {
+            if server.apps.is_enabled_for(app) {
+                dirty.mark(&server.id, app);
+            }
+        }
+        Self::flush(state, dirty)
     }
 
     // ========================================================================
@@ -168,15 +364,7 @@ impl McpService {
     /// [已废弃] 同步启用的 MCP 到指定应用（兼容旧 API）
     #[deprecated(since = "3.7.0", note = "Use sync_all_enabled instead")]
     pub fn sync_enabled(state: &AppState, app: AppType) -> Result<(), AppError> {
-        let servers = Self::get_all_servers(state)?;
-
-        for server in servers.values() {
-            if server.apps.is_enabled_for(&app) {
-                Self::sync_server_to_app(state, server, &app)?;
-            }
-        }
-
-        Ok(())
+        Self::sync_app(state, &app)
     }
 
     /// 从 Claude 导入 MCP（v3.7.0 已更新为统一结构）
@@ -185,16 +373,23 @@ impl McpService {
         let mut temp_config = crate::app_config::MultiAppConfig::default();
 
         // 调用原有的导入逻辑（从 mcp.rs）
-        let count = crate::mcp::import_from_claude(&mut temp_config)?;
+        let mut rejects = Vec::new();
+        let count = crate::mcp::import_from_claude_with_rejects(&mut temp_config, &mut rejects)?;
+        Self::quarantine_rejects(state, &AppType::Claude, rejects)?;
 
         // 如果有导入的服务器，保存到数据库
         if count > 0 {
             if let Some(servers) = &temp_config.mcp.servers {
+                let mut dirty = McpDirtySet::default();
                 for server in servers.values() {
-                    state.db.save_mcp_server(server)?;
-                    // 同步到 Claude live 配置
-                    Self::sync_server_to_apps(state, server)?;
+                    state.storage.save_mcp_server(server)?;
+                    state
+                        .storage
+                        .delete_quarantined_mcp(&quarantine_id(&AppType::Claude, &server.id))?;
+                    dirty.mark_enabled(server);
                 }
+                // 统一同步到启用的应用
+                Self::flush(state, dirty)?;
             }
         }
 
@@ -207,16 +402,23 @@ impl McpService {
         let mut temp_config = crate::app_config::MultiAppConfig::default();
 
         // 调用原有的导入逻辑（从 mcp.rs）
-        let count = crate::mcp::import_from_codex(&mut temp_config)?;
+        let mut rejects = Vec::new();
+        let count = crate::mcp::import_from_codex_with_rejects(&mut temp_config, &mut rejects)?;
+        Self::quarantine_rejects(state, &AppType::Codex, rejects)?;
 
         // 如果有导入的服务器，保存到数据库
         if count > 0 {
             if let Some(servers) = &temp_config.mcp.servers {
+                let mut dirty = McpDirtySet::default();
                 for server in servers.values() {
-                    state.db.save_mcp_server(server)?;
-                    // 同步到 Codex live 配置
-                    Self::sync_server_to_apps(state, server)?;
+                    state.storage.save_mcp_server(server)?;
+                    state
+                        .storage
+                        .delete_quarantined_mcp(&quarantine_id(&AppType::Codex, &server.id))?;
+                    dirty.mark_enabled(server);
                 }
+                // 统一同步到启用的应用
+                Self::flush(state, dirty)?;
             }
         }
 
@@ -229,19 +431,191 @@ impl McpService {
         let mut temp_config = crate::app_config::MultiAppConfig::default();
 
         // 调用原有的导入逻辑（从 mcp.rs）
-        let count = crate::mcp::import_from_gemini(&mut temp_config)?;
+        let mut rejects = Vec::new();
+        let count = crate::mcp::import_from_gemini_with_rejects(&mut temp_config, &mut rejects)?;
+        Self::quarantine_rejects(state, &AppType::Gemini, rejects)?;
 
         // 如果有导入的服务器，保存到数据库
         if count > 0 {
             if let Some(servers) = &temp_config.mcp.servers {
+                let mut dirty = McpDirtySet::default();
                 for server in servers.values() {
-                    state.db.save_mcp_server(server)?;
-                    // 同步到 Gemini live 配置
-                    Self::sync_server_to_apps(state, server)?;
+                    state.storage.save_mcp_server(server)?;
+                    state
+                        .storage
+                        .delete_quarantined_mcp(&quarantine_id(&AppType::Gemini, &server.id))?;
+                    dirty.mark_enabled(server);
                 }
+                // 统一同步到启用的应用
+                Self::flush(state, dirty)?;
             }
         }
 
         Ok(count)
     }
+
+    /// 列出导入时被隔离的 MCP 条目
+    pub fn list_quarantined(state: &AppState) -> Result<Vec<QuarantinedMcpEntry>, AppError> {
+        state.storage.get_quarantined_mcp()
+    }
+
+    /// 使用（可选的）修正后内容重新导入隔离条目
+    ///
+    /// 成功时写入数据库、同步到对应应用并移出隔离区；失败时更新隔离区中的内容与错误信息。
+    pub fn retry_quarantined(
+        state: &AppState,
+        id: &str,
+        raw: Option<String>,
+    ) -> Result<McpServer, AppError> {
+        let mut entry = Self::find_quarantined(state, id)?;
+        if let Some(raw) = raw {
+            entry.raw = raw;
+        }
+        let app = AppType::from_str(&entry.app_type)?;
+
+        let spec = match parse_quarantined_spec(&app, &entry) {
+            Ok(spec) => spec,
+            Err(err) => {
+                entry.error = err.to_string();
+                state.storage.save_quarantined_mcp(&entry)?;
+                return Err(err);
+            }
+        };
+
+        // 与导入逻辑一致：已存在的服务器只启用对应应用，不覆盖其配置
+        let mut server = match state
+            .storage
+            .get_all_mcp_servers()?
+            .shift_remove(&entry.server_id)
+        {
+            Some(existing) => existing,
+            None => McpServer {
+                id: entry.server_id.clone(),
+                name: entry.server_id.clone(),
+                server: spec,
+                apps: McpApps::default(),
+                description: None,
+                homepage: None,
+                docs: None,
+                tags: Vec::new(),
+            },
+        };
+        server.apps.set_enabled_for(&app, true);
+
+        Self::save_and_sync(state, &server)?;
+        state.storage.delete_quarantined_mcp(&entry.id)?;
+        log::info!("隔离的 MCP 条目 '{}' 已重新导入", entry.id);
+        Ok(server)
+    }
+
+    /// 放弃隔离条目
+    pub fn discard_quarantined(state: &AppState, id: &str) -> Result<(), AppError> {
+        Self::find_quarantined(state, id)?;
+        state.storage.delete_quarantined_mcp(id)
+    }
+
+    fn find_quarantined(state: &AppState, id: &str) -> Result<QuarantinedMcpEntry, AppError> {
+        state
+            .storage
+            .get_quarantined_mcp()?
+            .into_iter()
+            .find(|entry| entry.id == id)
+            .ok_or_else(|| {
+                AppError::localized(
+                    "mcp.quarantine_not_found",
+                    format!("未找到隔离的 MCP 条目: {id}"),
+                    format!("Quarantined MCP entry not found: {id}"),
+                )
+            })
+    }
+
+    fn quarantine_rejects(
+        state: &AppState,
+        app: &AppType,
+        rejects: Vec<RejectedMcpEntry>,
+    ) -> Result<(), AppError> {
+        if !rejects.is_empty() {
+            let ids = rejects
+                .iter()
+                .map(|r| r.server_id.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            notify(
+                NotificationCategory::McpSync,
+                false,
+                NotificationText::new(
+                    format!(
+                        "{} 中有 {} 个 MCP 服务器被隔离: {ids}",
+                        app.as_str(),
+                        rejects.len()
+                    ),
+                    format!(
+                        "{} MCP server(s) from {} were quarantined: {ids}",
+                        rejects.len(),
+                        app.as_str()
+                    ),
+                ),
+            );
+        }
+        let now = chrono::Utc::now().timestamp();
+        for reject in rejects {
+            state.storage.save_quarantined_mcp(&QuarantinedMcpEntry {
+                id: quarantine_id(app, &reject.server_id),
+                app_type: app.as_str().to_string(),
+                server_id: reject.server_id,
+                raw: reject.raw,
+                error: reject.error,
+                created_at: now,
+            })?;
+        }
+        Ok(())
+    }
+}
+
+fn quarantine_id(app: &AppType, server_id: &str) -> String {
+    format!("{}:{server_id}", app.as_str())
+}
+
+/// Codex 条目为 TOML 片段（允许带或不带 `id = {...}` 外层），其余应用为 JSON
+fn parse_quarantined_spec(
+    app: &AppType,
+    entry: &QuarantinedMcpEntry,
+) -> Result<serde_json::Value, AppError> {
+    match app {
+        AppType::Codex => {
+            let table: toml::value::Table = toml::from_str(&entry.raw)
+                .map_err(|e| AppError::McpValidation(format!("解析 TOML 失败: {e}")))?;
+            let inner = match table.get(&entry.server_id) {
+                Some(toml::Value::Table(inner)) if table.len() == 1 => inner,
+                _ => &table,
+            };
+            mcp::codex_entry_to_spec(inner)
+        }
+        AppType::Claude | AppType::Gemini => {
+            let spec: serde_json::Value = serde_json::from_str(&entry.raw)
+                .map_err(|e| AppError::McpValidation(format!("解析 JSON 失败: {e}")))?;
+            mcp::validate_server_spec(&spec)?;
+            Ok(spec)
+        }
+    }
+}
+
+/// 新值等于原值遮蔽后的结果时视为未修改，恢复原值
+fn restore_masked_env(incoming: &mut serde_json::Value, existing: &serde_json::Value) {
+    let Some(old_env) = existing.get("env").and_then(|v| v.as_object()) else {
+        return;
+    };
+    let Some(new_env) = incoming.get_mut("env").and_then(|v| v.as_object_mut()) else {
+        return;
+    };
+    for (key, value) in new_env.iter_mut() {
+        let Some(original) = old_env.get(key).and_then(|v| v.as_str()) else {
+            continue;
+        };
+        if value.as_str() == Some(mask_secret(original).as_str())
+            && value.as_str() != Some(original)
+        {
+            *value = serde_json::Value::String(original.to_string());
+        }
+    }
 }
@@ -0,0 +1,236 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::app_config::{AppType, McpServer};
+use crate::error::AppError;
+use crate::services::mcp::McpService;
+use crate::store::AppState;
+
+/// MCP 方案在 settings 表中的键
+pub(crate) const PROFILES_KEY: &str = "mcp_profiles";
+
+/// MCP 方案：某个应用下一组启用的服务器（如“前端开发”“数据分析”）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct McpProfile {
+    pub name: String,
+    pub app: String,
+    /// 启用的服务器 ID，未列出的服务器在应用方案时停用
+    pub servers: Vec<String>,
+    pub updated_at: i64,
+}
+
+pub struct McpProfileService;
+
+/// 应用方案时的数据库与 live 变更
+struct ProfilePlan {
+    enabled: Vec<String>,
+    upserts: HashMap<String, serde_json::Value>,
+    removals: Vec<String>,
+}
+
+impl ProfilePlan {
+    fn new(profile: &McpProfile, servers: &IndexMap<String, McpServer>, app: &AppType) -> Self {
+        // 方案中已被删除的服务器直接忽略
+        let enabled: Vec<String> = profile
+            .servers
+            .iter()
+            .filter(|id| servers.contains_key(*id))
+            .cloned()
+            .collect();
+        let removals = servers
+            .values()
+            .filter(|s| s.apps.is_enabled_for(app) && !enabled.contains(&s.id))
+            .map(|s| s.id.clone())
+            .collect();
+        let upserts = enabled
+            .iter()
+            .map(|id| (id.clone(), servers[id].server.clone()))
+            .collect();
+        Self {
+            enabled,
+            upserts,
+            removals,
+        }
+    }
+}
+
+impl McpProfileService {
+    /// 列出方案，指定应用时只返回该应用的方案
+    pub fn list(state: &AppState, app: Option<&AppType>) -> Result<Vec<McpProfile>, AppError> {
+        let profiles = Self::load(state)?;
+        Ok(match app {
+            Some(app) => profiles
+                .into_iter()
+                .filter(|p| p.app == app.as_str())
+                .collect(),
+            None => profiles,
+        })
+    }
+
+    /// 把应用当前的启用状态保存为方案（同名方案覆盖）
+    pub fn save_current(
+        state: &AppState,
+        name: &str,
+        app: &AppType,
+    ) -> Result<McpProfile, AppError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(AppError::localized(
+                "mcp.profile.name_empty",
+                "方案名称不能为空",
+                "Profile name cannot be empty",
+            ));
+        }
+
+        let servers = state
+            .storage
+            .get_all_mcp_servers()?
+            .into_values()
+            .filter(|s| s.apps.is_enabled_for(app))
+            .map(|s| s.id)
+            .collect();
+        let profile = McpProfile {
+            name: name.to_string(),
+            app: app.as_str().to_string(),
+            servers,
+            updated_at: chrono::Utc::now().timestamp(),
+        };
+
+        let mut profiles = Self::load(state)?;
+        profiles.retain(|p| !(p.name == profile.name && p.app == profile.app));
+        profiles.push(profile.clone());
+        Self::store(state, &profiles)?;
+        Ok(profile)
+    }
+
+    /// 应用方案：一个事务内切换全部启用状态，再对该应用做一次同步
+    pub fn apply(
+        state: &AppState,
+        name: &str,
+        app: &AppType,
+    ) -> Result<IndexMap<String, McpServer>, AppError> {
+        let profile = Self::load(state)?
+            .into_iter()
+            .find(|p| p.name == name && p.app == app.as_str())
+            .ok_or_else(|| {
+                AppError::localized(
+                    "mcp.profile.not_found",
+                    format!("MCP 方案不存在: {name}"),
+                    format!("MCP profile not found: {name}"),
+                )
+            })?;
+
+        let servers = state.storage.get_all_mcp_servers()?;
+        let plan = ProfilePlan::new(&profile, &servers, app);
+
+        state.storage.set_mcp_enabled_set(app, &plan.enabled)?;
+        let order: Vec<String> = servers.keys().cloned().collect();
+        McpService::sync_server_set(app, &plan.upserts, &plan.removals, &order)?;
+
+        state.storage.get_all_mcp_servers()
+    }
+
+    pub fn delete(state: &AppState, name: &str, app: &AppType) -> Result<bool, AppError> {
+        let mut profiles = Self::load(state)?;
+        let before = profiles.len();
+        profiles.retain(|p| !(p.name == name && p.app == app.as_str()));
+        if profiles.len() == before {
+            return Ok(false);
+        }
+        Self::store(state, &profiles)?;
+        Ok(true)
+    }
+
+    fn load(state: &AppState) -> Result<Vec<McpProfile>, AppError> {
+        let Some(raw) = state.storage.get_setting(PROFILES_KEY)? else {
+            return Ok(Vec::new());
+        };
+        serde_json::from_str(&raw).map_err(|e| AppError::Config(format!("解析 MCP 方案失败: {e}")))
+    }
+
+    fn store(state: &AppState, profiles: &[McpProfile]) -> Result<(), AppError> {
+        let json =
+            serde_json::to_string(profiles).map_err(|e| AppError::JsonSerialize { source: e })?;
+        state.storage.set_setting(PROFILES_KEY, &json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_config::McpApps;
+    use crate::database::Database;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    fn server(id: &str, claude: bool) -> McpServer {
+        McpServer {
+            id: id.to_string(),
+            name: id.to_string(),
+            server: json!({ "type": "stdio", "command": "echo" }),
+            apps: McpApps {
+                claude,
+                codex: false,
+                gemini: false,
+            },
+            description: None,
+            homepage: None,
+            docs: None,
+            tags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn saved_profile_restores_enable_flags() {
+        let state = AppState::new(Arc::new(Database::memory().unwrap()));
+        for s in [
+            server("fetch", true),
+            server("github", true),
+            server("db", false),
+        ] {
+            state.storage.save_mcp_server(&s).unwrap();
+        }
+
+        let saved = McpProfileService::save_current(&state, " web ", &AppType::Claude).unwrap();
+        assert_eq!(saved.name, "web");
+        assert_eq!(saved.servers.len(), 2);
+
+        state
+            .storage
+            .set_mcp_enabled_set(&AppType::Claude, &["db".to_string()])
+            .unwrap();
+        let enabled = |state: &AppState| {
+            let mut ids: Vec<String> = state
+                .storage
+                .get_all_mcp_servers()
+                .unwrap()
+                .into_values()
+                .filter(|s| s.apps.claude)
+                .map(|s| s.id)
+                .collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(enabled(&state), ["db"]);
+
+        let mut profile = McpProfileService::list(&state, Some(&AppType::Claude))
+            .unwrap()
+            .remove(0);
+        profile.servers.push("deleted".to_string());
+        let servers = state.storage.get_all_mcp_servers().unwrap();
+        let plan = ProfilePlan::new(&profile, &servers, &AppType::Claude);
+        assert_eq!(plan.removals, ["db"]);
+        assert_eq!(plan.upserts.len(), 2);
+
+        state
+            .storage
+            .set_mcp_enabled_set(&AppType::Claude, &plan.enabled)
+            .unwrap();
+        assert_eq!(enabled(&state), ["fetch", "github"]);
+
+        assert!(McpProfileService::delete(&state, "web", &AppType::Claude).unwrap());
+        assert!(McpProfileService::list(&state, None).unwrap().is_empty());
+    }
+}
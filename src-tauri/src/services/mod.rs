@@ -1,15 +1,115 @@
+pub mod access_window;
+pub mod api_token;
+pub mod claude_hooks;
+pub mod claude_layers;
+pub mod claude_permissions;
+pub mod codex_agents;
 pub mod config;
+pub mod config_convert;
+pub mod config_repair;
+pub mod confirmation;
+pub mod credential_probe;
+pub mod db_location;
+pub mod debug_proxy;
+pub mod demo;
+pub mod diagnostics;
+pub mod endpoint_health;
 pub mod env_checker;
 pub mod env_manager;
+pub mod external_backup;
+pub mod gemini_context;
+pub mod github;
+pub mod launcher;
+pub mod legacy_migration;
+pub mod local_model;
+pub mod managed_providers;
 pub mod mcp;
+pub mod mcp_profile;
+pub mod model_fidelity;
 pub mod prompt;
+pub mod prompt_crypto;
+pub mod prompt_schedule;
+pub mod prompt_tokens;
 pub mod provider;
+pub mod provider_csv;
+pub mod provider_drafts;
+pub mod provider_errors;
+pub mod provider_export;
+pub mod provider_presets;
+pub mod provider_template;
+pub mod provider_upgrade;
+pub mod quick_actions;
+pub mod relay_directory;
+pub mod safe_mode;
+pub mod security_review;
+pub mod session_log;
+pub mod share_page;
 pub mod skill;
+pub mod slash_commands;
 pub mod speedtest;
+pub mod stack;
+pub mod status_summary;
+pub mod sync_pause;
+pub mod transfer;
+pub mod vcs_export;
+pub mod write_access;
 
+pub use access_window::{AccessWindowService, WindowAutoSwitch};
+pub use api_token::{ApiTokenScope, ApiTokenService, CreatedApiToken};
+pub use claude_hooks::{ClaudeHook, ClaudeHookService};
+pub use claude_layers::{ClaudeEffectiveSettings, ClaudeSettingsLayerService};
+pub use claude_permissions::{
+    ClaudePermissionService, ClaudePermissions, PermissionList, PermissionPreset,
+};
+pub use codex_agents::{
+    CodexAgentsFile, CodexAgentsLevel, CodexAgentsService, CodexEffectiveAgents,
+};
 pub use config::ConfigService;
+pub use config_convert::{ConfigConvertService, ConversionResult, McpConvertDirection};
+pub use config_repair::{ConfigRepairService, CorruptedLiveFile, LiveConfigRepairReport};
+pub use confirmation::{ConfirmAction, ConfirmationInput, ConfirmationService};
+pub use credential_probe::{CredentialProbeService, ProbeOutcome};
+pub use db_location::{DbLocationInfo, DbLocationService, DbMoveResult};
+pub use debug_proxy::{DebugProxyService, DebugProxyStatus, ProxyLogEntry};
+pub use demo::{is_demo_mode, DemoService};
+pub use diagnostics::{DiagnosticsBundle, DiagnosticsService};
+pub use endpoint_health::{EndpointFailover, EndpointHealthService};
+pub use external_backup::{ExternalBackupService, ExternalBackupStatus};
+pub use gemini_context::GeminiContextService;
+pub use github::GitHubRateLimit;
+pub use launcher::{LauncherManifest, LauncherService};
+pub use legacy_migration::{
+    LegacyConfigReport, LegacyItemRef, LegacyMigrationResult, LegacyMigrationService,
+};
+pub use local_model::{LocalModelService, LocalModelStatus};
+pub use managed_providers::{ManagedImportSummary, ManagedProviderService};
 pub use mcp::McpService;
+pub use mcp_profile::{McpProfile, McpProfileService};
+pub use model_fidelity::ModelFidelityService;
 pub use prompt::PromptService;
-pub use provider::{ProviderService, ProviderSortUpdate};
+pub use prompt_crypto::{PromptCryptoService, PromptKeySource};
+pub use prompt_schedule::{PromptSchedule, PromptScheduleService};
+pub use prompt_tokens::{PromptTokenEstimate, PromptTokenService};
+pub use provider::{EndpointImportResult, ProviderService, ProviderSortUpdate};
+pub use provider_csv::{CsvColumnMapping, ProviderCsvImportService};
+pub use provider_drafts::{ProviderDraftResult, ProviderDraftService};
+pub use provider_errors::ProviderErrorService;
+pub use provider_export::{ProviderExportService, ProviderImportConflict, ProviderImportResult};
+pub use provider_presets::{PresetRefreshResult, ProviderPresetService};
+pub use provider_template::ProviderTemplateService;
+pub use provider_upgrade::{ProviderRewrite, ProviderUpgradeReport, ProviderUpgradeService};
+pub use quick_actions::{QuickAction, QuickActionKind, QuickActionOutcome, QuickActionService};
+pub use relay_directory::{RelayDirectoryService, RelayEntry};
+pub use safe_mode::{is_safe_mode, SafeModeService};
+pub use security_review::{SecurityFinding, SecurityReviewService};
+pub use session_log::{CliSessionPage, CliSessionSummary, SessionLogService};
+pub use share_page::{SharePageResult, SharePageService};
 pub use skill::{Skill, SkillRepo, SkillService};
-pub use speedtest::{EndpointLatency, SpeedtestService};
+pub use slash_commands::{SlashCommandExportResult, SlashCommandImportResult, SlashCommandService};
+pub use speedtest::{EndpointLatency, InferenceLatency, SpeedtestService};
+pub use stack::{StackApplyReport, StackService};
+pub use status_summary::{StatusSummary, StatusSummaryService};
+pub use sync_pause::{is_management_paused, ResumeSyncPreview, SyncPauseService};
+pub use transfer::{TransferService, TransferSession};
+pub use vcs_export::{VcsExportService, VcsExportSummary};
+pub use write_access::{AppWriteCapability, WriteAccessService};
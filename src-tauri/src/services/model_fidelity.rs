@@ -0,0 +1,176 @@
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::app_config::AppType;
+use crate::database::Storage;
+use crate::error::AppError;
+use crate::provider::{ModelFidelity, ModelFidelitySource, Provider};
+use crate::services::SpeedtestService;
+use crate::store::AppState;
+
+/// 供调试代理在后台写入观测结果，启动时绑定
+static STORAGE: OnceLock<Arc<dyn Storage>> = OnceLock::new();
+/// 代理最近一次观测到的 (请求模型, 响应模型)，键为 `app:id`，相同时跳过数据库读写
+static LAST_OBSERVED: Lazy<Mutex<HashMap<String, (String, String)>>> = Lazy::new(Default::default);
+
+/// 检测中转是否以其他模型（通常是更便宜的模型）响应请求
+///
+/// 结果写入供应商元数据的 `modelFidelity`，随供应商列表返回，并显示在托盘提示中。
+pub struct ModelFidelityService;
+
+impl ModelFidelityService {
+    pub fn bind(storage: Arc<dyn Storage>) {
+        let _ = STORAGE.set(storage);
+    }
+
+    /// 以供应商配置的模型发送一次测试请求，比对响应中的模型并记录
+    pub async fn recheck(
+        state: &AppState,
+        app_type: &AppType,
+        provider_id: &str,
+    ) -> Result<ModelFidelity, AppError> {
+        let provider = state
+            .storage
+            .get_all_providers(app_type.as_str())?
+            .shift_remove(provider_id)
+            .ok_or_else(|| AppError::Message(format!("供应商 {provider_id} 不存在")))?;
+
+        let result = SpeedtestService::test_inference(app_type, &provider, None).await?;
+        let Some(served) = result.served_model else {
+            let reason = result.error.map(|e| format!(": {e}")).unwrap_or_default();
+            return Err(AppError::localized(
+                "model_fidelity.unknown",
+                format!("无法确认实际模型，响应中没有模型字段{reason}"),
+                format!("Could not verify the served model; the response did not name one{reason}"),
+            ));
+        };
+
+        let fidelity = observation(&result.model, &served, ModelFidelitySource::Probe);
+        save(&*state.storage, app_type, provider, fidelity.clone())?;
+        Ok(fidelity)
+    }
+
+    /// 调试代理收到带模型的成功响应时调用；出错只记日志，不影响转发
+    pub fn observe(app: &str, provider_id: &str, requested: &str, served: &str) {
+        let Some(storage) = STORAGE.get() else {
+            return;
+        };
+        let key = format!("{app}:{provider_id}");
+        let pair = (requested.to_string(), served.to_string());
+        {
+            let mut last = LAST_OBSERVED.lock().unwrap_or_else(|e| e.into_inner());
+            if last.get(&key) == Some(&pair) {
+                return;
+            }
+            last.insert(key, pair);
+        }
+
+        let result = AppType::from_str(app).and_then(|app_type| {
+            let Some(provider) = storage.get_all_providers(app)?.shift_remove(provider_id) else {
+                return Ok(());
+            };
+            let next = observation(requested, served, ModelFidelitySource::Proxy);
+            let previous = provider
+                .meta
+                .as_ref()
+                .and_then(|meta| meta.model_fidelity.as_ref());
+            if supersedes(previous, &next) {
+                save(&**storage, &app_type, provider, next)?;
+            }
+            Ok(())
+        });
+        if let Err(e) = result {
+            log::warn!("记录模型一致性失败: {e}");
+        }
+    }
+}
+
+/// 带日期等后缀的版本（如 `claude-sonnet-4-5-20250929`）视为同一模型
+pub(crate) fn models_differ(requested: Option<&str>, served: Option<&str>) -> bool {
+    match (requested, served) {
+        (Some(requested), Some(served)) => {
+            let requested = requested.trim_start_matches("models/");
+            let served = served.trim_start_matches("models/");
+            !served.starts_with(requested) && !requested.starts_with(served)
+        }
+        _ => false,
+    }
+}
+
+fn observation(requested: &str, served: &str, source: ModelFidelitySource) -> ModelFidelity {
+    ModelFidelity {
+        requested_model: requested.to_string(),
+        served_model: served.to_string(),
+        mismatch: models_differ(Some(requested), Some(served)),
+        checked_at: chrono::Utc::now().timestamp(),
+        source,
+    }
+}
+
+/// CLI 会混用多个模型（如 Claude Code 的 haiku 后台任务），某个模型正常不能抵消
+/// 另一个模型的不一致：只有同一请求模型恢复正常时才清除告警
+fn supersedes(previous: Option<&ModelFidelity>, next: &ModelFidelity) -> bool {
+    match previous {
+        None => true,
+        Some(previous) if previous.mismatch && !next.mismatch => {
+            previous.requested_model == next.requested_model
+        }
+        Some(previous) => {
+            previous.mismatch != next.mismatch
+                || previous.requested_model != next.requested_model
+                || previous.served_model != next.served_model
+        }
+    }
+}
+
+fn save(
+    storage: &dyn Storage,
+    app_type: &AppType,
+    mut provider: Provider,
+    fidelity: ModelFidelity,
+) -> Result<(), AppError> {
+    if fidelity.mismatch {
+        log::warn!(
+            "供应商 {} 请求 {} 实际返回 {}",
+            provider.id,
+            fidelity.requested_model,
+            fidelity.served_model
+        );
+    }
+    provider
+        .meta
+        .get_or_insert_with(Default::default)
+        .model_fidelity = Some(fidelity);
+    storage.save_provider(app_type.as_str(), &provider)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mismatch_clears_only_when_the_same_model_recovers() {
+        let downgraded = observation(
+            "claude-opus-4-1",
+            "claude-3-5-haiku",
+            ModelFidelitySource::Proxy,
+        );
+        assert!(downgraded.mismatch);
+        let other_ok = observation(
+            "claude-3-5-haiku",
+            "claude-3-5-haiku",
+            ModelFidelitySource::Proxy,
+        );
+        assert!(!supersedes(Some(&downgraded), &other_ok));
+        let recovered = observation(
+            "claude-opus-4-1",
+            "claude-opus-4-1",
+            ModelFidelitySource::Probe,
+        );
+        assert!(supersedes(Some(&downgraded), &recovered));
+        assert!(supersedes(None, &other_ok));
+        assert!(!supersedes(Some(&other_ok), &other_ok));
+    }
+}
@@ -1,10 +1,13 @@
 use indexmap::IndexMap;
+use std::path::Path;
 
 use crate::app_config::AppType;
 use crate::config::write_text_file;
 use crate::error::AppError;
-use crate::prompt::Prompt;
+use crate::prompt::{Prompt, PromptSummary};
 use crate::prompt_files::prompt_file_path;
+use crate::services::prompt_crypto::PromptCryptoService;
+use crate::services::sync_pause::is_management_paused;
 use crate::store::AppState;
 
 /// 安全地获取当前 Unix 时间戳
@@ -22,112 +25,206 @@ impl PromptService {
         state: &AppState,
         app: AppType,
     ) -> Result<IndexMap<String, Prompt>, AppError> {
-        state.db.get_prompts(app.as_str())
+        state.storage.get_prompts(app.as_str())
+    }
+
+    /// 分页获取不含正文的提示词摘要
+    pub fn get_prompt_summaries(
+        state: &AppState,
+        app: AppType,
+        limit: Option<usize>,
+        offset: Option<usize>,
+    ) -> Result<Vec<PromptSummary>, AppError> {
+        state
+            .storage
+            .get_prompt_summaries(app.as_str(), limit, offset.unwrap_or(0))
+    }
+
+    pub fn get_prompt_content(
+        state: &AppState,
+        app: AppType,
+        id: &str,
+    ) -> Result<String, AppError> {
+        state
+            .storage
+            .get_prompt_content(app.as_str(), id)?
+            .ok_or_else(|| AppError::InvalidInput(format!("提示词 {id} 不存在")))
     }
 
     pub fn upsert_prompt(
         state: &AppState,
         app: AppType,
         _id: &str,
-        prompt: Prompt,
+        mut prompt: Prompt,
     ) -> Result<(), AppError> {
         // 检查是否为已启用的提示词
         let is_enabled = prompt.enabled;
 
-        state.db.save_prompt(app.as_str(), &prompt)?;
+        // 已加密的提示词编辑后带回明文，按原方式重新加密后再保存
+        let plaintext = prompt.content.clone();
+        if !PromptCryptoService::is_encrypted(&prompt.content) {
+            if let Some(stored) = state.storage.get_prompt(app.as_str(), &prompt.id)? {
+                prompt.content = PromptCryptoService::reseal(&app, &stored, &plaintext)?;
+            }
+        }
 
-        // 如果是已启用的提示词，同步更新到对应的文件
-        if is_enabled {
+        // 前端编辑会带回读取时的版本号；内部调用不带版本则直接覆盖
+        state.storage.save_prompt_checked(app.as_str(), &prompt)?;
+
+        // 如果是已启用的提示词，同步更新到对应的文件（暂停管理时推迟到恢复）
+        if is_enabled && !is_management_paused() {
             let target_path = prompt_file_path(&app)?;
-            write_text_file(&target_path, &prompt.content)?;
+            let live = PromptCryptoService::plaintext(&app, &prompt.id, &plaintext)?;
+            write_text_file(&target_path, &live)?;
         }
 
         Ok(())
     }
 
     pub fn delete_prompt(state: &AppState, app: AppType, id: &str) -> Result<(), AppError> {
-        let prompts = state.db.get_prompts(app.as_str())?;
-
-        if let Some(prompt) = prompts.get(id) {
+        if let Some(prompt) = state.storage.get_prompt(app.as_str(), id)? {
             if prompt.enabled {
                 return Err(AppError::InvalidInput("无法删除已启用的提示词".to_string()));
             }
         }
 
-        state.db.delete_prompt(app.as_str(), id)?;
+        state.storage.delete_prompt(app.as_str(), id)?;
         Ok(())
     }
 
     pub fn enable_prompt(state: &AppState, app: AppType, id: &str) -> Result<(), AppError> {
         // 回填当前 live 文件内容到已启用的提示词，或创建备份
+        // 暂停管理期间 live 文件可能已落后于数据库，不能回填
+        let paused = is_management_paused();
         let target_path = prompt_file_path(&app)?;
-        if target_path.exists() {
-            if let Ok(live_content) = std::fs::read_to_string(&target_path) {
-                if !live_content.trim().is_empty() {
-                    let mut prompts = state.db.get_prompts(app.as_str())?;
-
-                    // 尝试回填到当前已启用的提示词
-                    if let Some((enabled_id, enabled_prompt)) = prompts
-                        .iter_mut()
-                        .find(|(_, p)| p.enabled)
-                        .map(|(id, p)| (id.clone(), p))
-                    {
-                        let timestamp = get_unix_timestamp()?;
-                        enabled_prompt.content = live_content.clone();
-                        enabled_prompt.updated_at = Some(timestamp);
-                        log::info!("回填 live 提示词内容到已启用项: {enabled_id}");
-                        state.db.save_prompt(app.as_str(), enabled_prompt)?;
-                    } else {
-                        // 没有已启用的提示词，则创建一次备份（避免重复备份）
-                        let content_exists = prompts
-                            .values()
-                            .any(|p| p.content.trim() == live_content.trim());
-                        if !content_exists {
-                            let timestamp = std::time::SystemTime::now()
-                                .duration_since(std::time::UNIX_EPOCH)
-                                .unwrap()
-                                .as_secs() as i64;
-                            let backup_id = format!("backup-{timestamp}");
-                            let backup_prompt = Prompt {
-                                id: backup_id.clone(),
-                                name: format!(
-                                    "原始提示词 {}",
-                                    chrono::Local::now().format("%Y-%m-%d %H:%M")
-                                ),
-                                content: live_content,
-                                description: Some("自动备份的原始提示词".to_string()),
-                                enabled: false,
-                                created_at: Some(timestamp),
-                                updated_at: Some(timestamp),
-                            };
-                            log::info!("回填 live 提示词内容，创建备份: {backup_id}");
-                            state.db.save_prompt(app.as_str(), &backup_prompt)?;
-                        }
-                    }
-                }
-            }
+        if !paused && target_path.exists() {
+            Self::backfill_live(state, &app, &target_path)?;
         }
 
         // 启用目标提示词并写入文件
-        let mut prompts = state.db.get_prompts(app.as_str())?;
+        let prompt = state
+            .storage
+            .get_prompt(app.as_str(), id)?
+            .ok_or_else(|| AppError::InvalidInput(format!("提示词 {id} 不存在")))?;
+        if !paused {
+            let content = PromptCryptoService::plaintext(&app, id, &prompt.content)?;
+            write_text_file(&target_path, &content)?; // 原子写入
+        }
+
+        // 单条 UPDATE 同时停用其他提示词
+        state.storage.set_enabled_prompt(app.as_str(), id)?;
+
+        Ok(())
+    }
 
-        for prompt in prompts.values_mut() {
-            prompt.enabled = false;
+    /// 批量启用/停用提示词：数据库在同一事务中更新，之后只写一次 live 文件
+    ///
+    /// 每个应用同一时间只启用一个提示词，因此最多一项可设为启用，其余提示词随之停用；
+    /// 只停用时不改动 live 文件。返回更新后已启用的提示词 ID
+    pub fn set_prompts_enabled(
+        state: &AppState,
+        app: AppType,
+        changes: &[(String, bool)],
+    ) -> Result<Option<String>, AppError> {
+        let mut seen = std::collections::HashSet::new();
+        if let Some((id, _)) = changes.iter().find(|(id, _)| !seen.insert(id)) {
+            return Err(AppError::InvalidInput(format!("提示词 {id} 重复出现")));
+        }
+        let enabling: Vec<&str> = changes
+            .iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(id, _)| id.as_str())
+            .collect();
+        if enabling.len() > 1 {
+            return Err(AppError::localized(
+                "prompt.bulk_multiple_enabled",
+                "每个应用只能启用一个提示词，批量操作中最多一项可设为启用",
+                "Only one prompt per app can be enabled; at most one change may enable a prompt",
+            ));
         }
 
-        if let Some(prompt) = prompts.get_mut(id) {
-            prompt.enabled = true;
-            write_text_file(&target_path, &prompt.content)?; // 原子写入
-            state.db.save_prompt(app.as_str(), prompt)?;
-        } else {
-            return Err(AppError::InvalidInput(format!("提示词 {id} 不存在")));
+        let previous = state.storage.get_enabled_prompt(app.as_str())?;
+        let target = match enabling.first() {
+            Some(id) => Some(
+                state
+                    .storage
+                    .get_prompt(app.as_str(), id)?
+                    .ok_or_else(|| AppError::InvalidInput(format!("提示词 {id} 不存在")))?,
+            ),
+            None => None,
+        };
+        let switching = target
+            .as_ref()
+            .is_some_and(|t| previous.as_ref().map(|p| p.id.as_str()) != Some(t.id.as_str()));
+
+        let paused = is_management_paused();
+        let target_path = prompt_file_path(&app)?;
+        if switching && !paused && target_path.exists() {
+            Self::backfill_live(state, &app, &target_path)?;
         }
 
-        // Save all prompts to disable others
-        for (_, prompt) in prompts.iter() {
-            state.db.save_prompt(app.as_str(), prompt)?;
+        // 任一 ID 不存在时整体回滚，live 文件保持不变
+        state.storage.set_prompts_enabled(app.as_str(), changes)?;
+
+        if let (Some(prompt), false) = (target.as_ref(), paused) {
+            let content = PromptCryptoService::plaintext(&app, &prompt.id, &prompt.content)?;
+            write_text_file(&target_path, &content)?;
         }
 
+        Ok(state
+            .storage
+            .get_enabled_prompt(app.as_str())?
+            .map(|p| p.id))
+    }
+
+    /// 切换启用项前，把 live 文件内容回填到当前已启用的提示词；没有已启用项时创建一次备份
+    fn backfill_live(state: &AppState, app: &AppType, target_path: &Path) -> Result<(), AppError> {
+        if let Ok(live_content) = std::fs::read_to_string(target_path) {
+            if !live_content.trim().is_empty() {
+                // 尝试回填到当前已启用的提示词
+                if let Some(mut enabled_prompt) = state.storage.get_enabled_prompt(app.as_str())? {
+                    // 加密的提示词无法重新加密时（口令未解锁）跳过回填，避免明文落库
+                    match PromptCryptoService::reseal(app, &enabled_prompt, &live_content) {
+                        Ok(content) => {
+                            enabled_prompt.content = content;
+                            enabled_prompt.updated_at = Some(get_unix_timestamp()?);
+                            log::info!("回填 live 提示词内容到已启用项: {}", enabled_prompt.id);
+                            state.storage.save_prompt(app.as_str(), &enabled_prompt)?;
+                        }
+                        Err(e) => {
+                            log::warn!("跳过回填加密提示词 {}: {e}", enabled_prompt.id);
+                        }
+                    }
+                } else {
+                    // 没有已启用的提示词，则创建一次备份（避免重复备份）
+                    let content_exists = state
+                        .storage
+                        .prompt_content_exists(app.as_str(), &live_content)?;
+                    if !content_exists {
+                        let timestamp = std::time::SystemTime::now()
+                            .duration_since(std::time::UNIX_EPOCH)
+                            .unwrap()
+                            .as_secs() as i64;
+                        let backup_id = format!("backup-{timestamp}");
+                        let backup_prompt = Prompt {
+                            id: backup_id.clone(),
+                            name: format!(
+                                "原始提示词 {}",
+                                chrono::Local::now().format("%Y-%m-%d %H:%M")
+                            ),
+                            content: live_content,
+                            description: Some("自动备份的原始提示词".to_string()),
+                            enabled: false,
+                            created_at: Some(timestamp),
+                            updated_at: Some(timestamp),
+                            version: None,
+                        };
+                        log::info!("回填 live 提示词内容，创建备份: {backup_id}");
+                        state.storage.save_prompt(app.as_str(), &backup_prompt)?;
+                    }
+                }
+            }
+        }
         Ok(())
     }
 
@@ -154,6 +251,7 @@ impl PromptService {
             enabled: false,
             created_at: Some(timestamp),
             updated_at: Some(timestamp),
+            version: None,
         };
 
         Self::upsert_prompt(state, app, &id, prompt)?;
@@ -213,10 +311,11 @@ impl PromptService {
             enabled: true, // 首次导入时自动启用
             created_at: Some(timestamp),
             updated_at: Some(timestamp),
+            version: None,
         };
 
         // 保存到数据库
-        state.db.save_prompt(app.as_str(), &prompt)?;
+        state.storage.save_prompt(app.as_str(), &prompt)?;
 
         log::info!("自动导入完成: {}", app.as_str());
         Ok(1)
@@ -0,0 +1,502 @@
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::prelude::*;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::prompt::Prompt;
+use crate::store::AppState;
+
+/// 加密后的正文格式：`clihub-enc:v1:<pass|device>:<派生方式>:<base64(salt | nonce | 密文)>`
+///
+/// 派生方式见 [`Kdf`]，写在头中使以后调整参数时旧密文仍能解开。
+const ENCRYPTED_PREFIX: &str = "clihub-enc:v1:";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const MIN_PASSPHRASE_LEN: usize = 8;
+/// 新加密时使用的 Argon2id 参数，慢速派生使离线猜测口令代价高昂
+#[cfg(not(test))]
+const KDF_MEMORY_KIB: u32 = 64 * 1024;
+#[cfg(test)]
+const KDF_MEMORY_KIB: u32 = 1024;
+const KDF_ITERATIONS: u32 = 3;
+const KDF_LANES: u32 = 1;
+/// 解析头中参数时的上限，避免导入的密文以极端参数耗尽内存或 CPU
+const MAX_KDF_MEMORY_KIB: u32 = 1024 * 1024;
+const MAX_KDF_ITERATIONS: u32 = 64;
+/// 本机密钥在系统钥匙串（Keychain / 凭据管理器 / Secret Service）中的条目
+const KEYRING_SERVICE: &str = "cli-hub";
+const KEYRING_ACCOUNT: &str = "prompt-device-key";
+/// 旧版本放在配置目录下的明文密钥文件，首次使用时迁入钥匙串后删除
+const LEGACY_DEVICE_KEY_FILE: &str = "prompt.key";
+
+/// 本次运行中已解锁的口令，键为 `app:id`，只保存在内存中
+static UNLOCKED: Lazy<Mutex<HashMap<String, String>>> = Lazy::new(Default::default);
+
+/// 提示词的加密方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PromptKeySource {
+    /// 用户口令，每次启动后首次使用前需要解锁
+    Passphrase,
+    /// 系统钥匙串中的本机密钥，无需输入；换机后无法解密
+    Device,
+}
+
+impl PromptKeySource {
+    fn tag(self) -> &'static str {
+        match self {
+            Self::Passphrase => "pass",
+            Self::Device => "device",
+        }
+    }
+}
+
+/// 由口令或本机密钥得到加密密钥的方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kdf {
+    /// 口令：Argon2id，头中记为 `argon2id,m=<KiB>,t=<迭代>,p=<并行度>`
+    Argon2id {
+        memory_kib: u32,
+        iterations: u32,
+        lanes: u32,
+    },
+    /// 本机密钥本身是随机的，只需按盐做 SHA-256 区分
+    Sha256,
+}
+
+impl Kdf {
+    fn for_source(source: PromptKeySource) -> Self {
+        match source {
+            PromptKeySource::Passphrase => Self::Argon2id {
+                memory_kib: KDF_MEMORY_KIB,
+                iterations: KDF_ITERATIONS,
+                lanes: KDF_LANES,
+            },
+            PromptKeySource::Device => Self::Sha256,
+        }
+    }
+
+    fn encode(self) -> String {
+        match self {
+            Self::Argon2id {
+                memory_kib,
+                iterations,
+                lanes,
+            } => format!("argon2id,m={memory_kib},t={iterations},p={lanes}"),
+            Self::Sha256 => "sha256".to_string(),
+        }
+    }
+
+    fn parse(spec: &str) -> Option<Self> {
+        let mut parts = spec.split(',');
+        match parts.next()? {
+            "sha256" => parts.next().is_none().then_some(Self::Sha256),
+            "argon2id" => {
+                let (mut memory_kib, mut iterations, mut lanes) = (None, None, None);
+                for part in parts {
+                    let (name, value) = part.split_once('=')?;
+                    let value = value.parse::<u32>().ok()?;
+                    match name {
+                        "m" => memory_kib = Some(value),
+                        "t" => iterations = Some(value),
+                        "p" => lanes = Some(value),
+                        _ => return None,
+                    }
+                }
+                let (memory_kib, iterations) = (memory_kib?, iterations?);
+                if memory_kib > MAX_KDF_MEMORY_KIB || iterations > MAX_KDF_ITERATIONS {
+                    return None;
+                }
+                Some(Self::Argon2id {
+                    memory_kib,
+                    iterations,
+                    lanes: lanes?,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// 提示词正文加密
+///
+/// 数据库与导出文件中只保存密文，只有写入 live 提示词文件（或导出斜杠命令）时才解密。
+pub struct PromptCryptoService;
+
+impl PromptCryptoService {
+    pub fn is_encrypted(content: &str) -> bool {
+        content.starts_with(ENCRYPTED_PREFIX)
+    }
+
+    pub fn key_source(content: &str) -> Option<PromptKeySource> {
+        let rest = content.strip_prefix(ENCRYPTED_PREFIX)?;
+        match rest.split_once(':')?.0 {
+            "pass" => Some(PromptKeySource::Passphrase),
+            "device" => Some(PromptKeySource::Device),
+            _ => None,
+        }
+    }
+
+    /// 加密已保存的提示词；不传口令时使用本机密钥。已写入的 live 文件保持不变
+    pub fn encrypt_prompt(
+        state: &AppState,
+        app: &AppType,
+        id: &str,
+        passphrase: Option<&str>,
+    ) -> Result<Prompt, AppError> {
+        let mut prompt = load(state, app, id)?;
+        if Self::is_encrypted(&prompt.content) {
+            return Err(AppError::localized(
+                "prompt.crypto.already_encrypted",
+                "提示词已加密",
+                "The prompt is already encrypted",
+            ));
+        }
+        let source = match passphrase {
+            Some(passphrase) if passphrase.chars().count() < MIN_PASSPHRASE_LEN => {
+                return Err(AppError::localized(
+                    "prompt.crypto.passphrase_too_short",
+                    format!("口令至少需要 {MIN_PASSPHRASE_LEN} 个字符"),
+                    format!("The passphrase must be at least {MIN_PASSPHRASE_LEN} characters"),
+                ));
+            }
+            Some(_) => PromptKeySource::Passphrase,
+            None => PromptKeySource::Device,
+        };
+
+        prompt.content = seal(&prompt.content, source, passphrase)?;
+        prompt.updated_at = Some(chrono::Utc::now().timestamp());
+        state.storage.save_prompt(app.as_str(), &prompt)?;
+        if let Some(passphrase) = passphrase {
+            remember(app, id, passphrase);
+        }
+        Ok(prompt)
+    }
+
+    /// 永久解除加密，恢复为明文保存
+    pub fn decrypt_prompt(
+        state: &AppState,
+        app: &AppType,
+        id: &str,
+        passphrase: Option<&str>,
+    ) -> Result<Prompt, AppError> {
+        let mut prompt = load(state, app, id)?;
+        if !Self::is_encrypted(&prompt.content) {
+            return Ok(prompt);
+        }
+        prompt.content = open_with(app, id, &prompt.content, passphrase)?;
+        prompt.updated_at = Some(chrono::Utc::now().timestamp());
+        state.storage.save_prompt(app.as_str(), &prompt)?;
+        UNLOCKED
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&unlock_key(app, id));
+        Ok(prompt)
+    }
+
+    /// 校验口令并在本次运行中记住，返回明文供查看或编辑
+    pub fn unlock_prompt(
+        state: &AppState,
+        app: &AppType,
+        id: &str,
+        passphrase: &str,
+    ) -> Result<String, AppError> {
+        let prompt = load(state, app, id)?;
+        if !Self::is_encrypted(&prompt.content) {
+            return Ok(prompt.content);
+        }
+        let plaintext = open(&prompt.content, Some(passphrase))?;
+        if Self::key_source(&prompt.content) == Some(PromptKeySource::Passphrase) {
+            remember(app, id, passphrase);
+        }
+        Ok(plaintext)
+    }
+
+    /// 写入 live 文件等需要明文的场景；口令加密且未解锁时返回 PROMPT_LOCKED
+    pub fn plaintext(app: &AppType, id: &str, content: &str) -> Result<String, AppError> {
+        if !Self::is_encrypted(content) {
+            return Ok(content.to_string());
+        }
+        open_with(app, id, content, None)
+    }
+
+    /// 按已保存提示词的加密方式加密新正文；原本未加密时原样返回
+    pub fn reseal(app: &AppType, stored: &Prompt, plaintext: &str) -> Result<String, AppError> {
+        match Self::key_source(&stored.content) {
+            None => Ok(plaintext.to_string()),
+            Some(PromptKeySource::Device) => seal(plaintext, PromptKeySource::Device, None),
+            Some(PromptKeySource::Passphrase) => {
+                let passphrase = remembered(app, &stored.id)
+                    .ok_or_else(|| AppError::prompt_locked(app.as_str(), &stored.id))?;
+                seal(plaintext, PromptKeySource::Passphrase, Some(&passphrase))
+            }
+        }
+    }
+}
+
+fn load(state: &AppState, app: &AppType, id: &str) -> Result<Prompt, AppError> {
+    state
+        .storage
+        .get_prompt(app.as_str(), id)?
+        .ok_or_else(|| AppError::InvalidInput(format!("提示词 {id} 不存在")))
+}
+
+fn unlock_key(app: &AppType, id: &str) -> String {
+    format!("{}:{id}", app.as_str())
+}
+
+fn remember(app: &AppType, id: &str, passphrase: &str) {
+    UNLOCKED
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(unlock_key(app, id), passphrase.to_string());
+}
+
+fn remembered(app: &AppType, id: &str) -> Option<String> {
+    UNLOCKED
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&unlock_key(app, id))
+        .cloned()
+}
+
+/// 未传口令时使用本次运行中已解锁的口令
+fn open_with(
+    app: &AppType,
+    id: &str,
+    content: &str,
+    passphrase: Option<&str>,
+) -> Result<String, AppError> {
+    let passphrase = passphrase
+        .map(str::to_string)
+        .or_else(|| remembered(app, id));
+    if passphrase.is_none()
+        && PromptCryptoService::key_source(content) == Some(PromptKeySource::Passphrase)
+    {
+        return Err(AppError::prompt_locked(app.as_str(), id));
+    }
+    open(content, passphrase.as_deref())
+}
+
+fn seal(
+    plaintext: &str,
+    source: PromptKeySource,
+    passphrase: Option<&str>,
+) -> Result<String, AppError> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let kdf = Kdf::for_source(source);
+    let ciphertext = ChaCha20Poly1305::new(&key_for(source, kdf, &salt, passphrase)?)
+        .encrypt(&nonce, plaintext.as_bytes())
+        .map_err(|e| AppError::Message(format!("加密提示词失败: {e}")))?;
+
+    let mut payload = Vec::with_capacity(SALT_LEN + NONCE_LEN + ciphertext.len());
+    payload.extend_from_slice(&salt);
+    payload.extend_from_slice(&nonce);
+    payload.extend_from_slice(&ciphertext);
+    Ok(format!(
+        "{ENCRYPTED_PREFIX}{}:{}:{}",
+        source.tag(),
+        kdf.encode(),
+        BASE64_STANDARD.encode(payload)
+    ))
+}
+
+fn open(content: &str, passphrase: Option<&str>) -> Result<String, AppError> {
+    let invalid = || {
+        AppError::localized(
+            "prompt.crypto.invalid",
+            "加密的提示词内容已损坏",
+            "The encrypted prompt content is corrupted",
+        )
+    };
+    let source = PromptCryptoService::key_source(content).ok_or_else(invalid)?;
+    let mut fields = content[ENCRYPTED_PREFIX.len()..].splitn(3, ':');
+    let (_, kdf, encoded) = (fields.next(), fields.next(), fields.next());
+    let kdf = kdf.and_then(Kdf::parse).ok_or_else(invalid)?;
+    let encoded = encoded.ok_or_else(invalid)?;
+    let payload = BASE64_STANDARD
+        .decode(encoded.trim())
+        .map_err(|_| invalid())?;
+    if payload.len() < SALT_LEN + NONCE_LEN {
+        return Err(invalid());
+    }
+    let (salt, rest) = payload.split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+    let plaintext = ChaCha20Poly1305::new(&key_for(source, kdf, salt, passphrase)?)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| match source {
+            PromptKeySource::Passphrase => AppError::localized(
+                "prompt.crypto.wrong_passphrase",
+                "口令错误",
+                "Incorrect passphrase",
+            ),
+            PromptKeySource::Device => AppError::localized(
+                "prompt.crypto.wrong_device",
+                "无法用本机密钥解密，提示词可能是在其他设备上加密的",
+                "Cannot decrypt with this device's key; the prompt may have been encrypted on another device",
+            ),
+        })?;
+    String::from_utf8(plaintext).map_err(|_| invalid())
+}
+
+fn key_for(
+    source: PromptKeySource,
+    kdf: Kdf,
+    salt: &[u8],
+    passphrase: Option<&str>,
+) -> Result<Key, AppError> {
+    match (source, kdf) {
+        (
+            PromptKeySource::Passphrase,
+            Kdf::Argon2id {
+                memory_kib,
+                iterations,
+                lanes,
+            },
+        ) => {
+            let passphrase = passphrase.ok_or_else(|| {
+                AppError::localized(
+                    "prompt.crypto.passphrase_required",
+                    "需要输入口令",
+                    "A passphrase is required",
+                )
+            })?;
+            let params = Params::new(memory_kib, iterations, lanes, Some(32))
+                .map_err(|e| AppError::Message(format!("派生提示词密钥失败: {e}")))?;
+            let mut key = Key::default();
+            Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+                .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+                .map_err(|e| AppError::Message(format!("派生提示词密钥失败: {e}")))?;
+            Ok(key)
+        }
+        (PromptKeySource::Device, Kdf::Sha256) => {
+            let digest = Sha256::new()
+                .chain_update(b"clihub-prompt-device:")
+                .chain_update(salt)
+                .chain_update(device_key()?)
+                .finalize();
+            Ok(Key::clone_from_slice(&digest))
+        }
+        _ => Err(AppError::localized(
+            "prompt.crypto.invalid",
+            "加密的提示词内容已损坏",
+            "The encrypted prompt content is corrupted",
+        )),
+    }
+}
+
+/// 首次使用时生成并存入系统钥匙串；删除该条目后以本机密钥加密的提示词无法恢复
+fn device_key() -> Result<[u8; 32], AppError> {
+    let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_ACCOUNT).map_err(keychain_error)?;
+    match entry.get_secret() {
+        Ok(bytes) => return device_key_from(&bytes),
+        Err(keyring::Error::NoEntry) => {}
+        Err(e) => return Err(keychain_error(e)),
+    }
+
+    let legacy = crate::config::get_app_config_dir().join(LEGACY_DEVICE_KEY_FILE);
+    let key = match std::fs::read(&legacy) {
+        Ok(bytes) => device_key_from(&bytes)?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            let mut key = [0u8; 32];
+            OsRng.fill_bytes(&mut key);
+            key
+        }
+        Err(e) => return Err(AppError::io(&legacy, e)),
+    };
+    entry.set_secret(&key).map_err(keychain_error)?;
+
+    if legacy.exists() {
+        match std::fs::remove_file(&legacy) {
+            Ok(()) => log::info!("已将本机提示词密钥迁入系统钥匙串"),
+            Err(e) => log::warn!("删除旧的本机密钥文件失败 {}: {e}", legacy.display()),
+        }
+    }
+    Ok(key)
+}
+
+fn device_key_from(bytes: &[u8]) -> Result<[u8; 32], AppError> {
+    <[u8; 32]>::try_from(bytes).map_err(|_| {
+        AppError::localized(
+            "prompt.crypto.device_key_invalid",
+            "本机密钥已损坏",
+            "The device key is corrupted",
+        )
+    })
+}
+
+fn keychain_error(e: keyring::Error) -> AppError {
+    AppError::localized(
+        "prompt.crypto.keychain_unavailable",
+        format!("无法访问系统钥匙串: {e}"),
+        format!("Cannot access the system keychain: {e}"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn passphrase_sealed_content_opens_only_with_the_same_passphrase() {
+        let sealed = seal(
+            "内部上下文 internal context",
+            PromptKeySource::Passphrase,
+            Some("correct horse"),
+        )
+        .unwrap();
+        assert!(PromptCryptoService::is_encrypted(&sealed));
+        assert_eq!(
+            PromptCryptoService::key_source(&sealed),
+            Some(PromptKeySource::Passphrase)
+        );
+        assert!(!sealed.contains("internal context"));
+        assert!(sealed.starts_with(&format!(
+            "clihub-enc:v1:pass:argon2id,m={KDF_MEMORY_KIB},t={KDF_ITERATIONS},p=1:"
+        )));
+
+        assert_eq!(
+            open(&sealed, Some("correct horse")).unwrap(),
+            "内部上下文 internal context"
+        );
+        assert!(open(&sealed, Some("wrong horse")).is_err());
+        assert!(matches!(
+            open_with(&AppType::Claude, "never-unlocked", &sealed, None),
+            Err(AppError::Message(message)) if message.contains("PROMPT_LOCKED")
+        ));
+        assert_eq!(
+            PromptCryptoService::plaintext(&AppType::Claude, "p", "plain").unwrap(),
+            "plain"
+        );
+
+        // 口令密文必须在头中写明派生方式
+        let unversioned = sealed.replacen(
+            &format!("{}:", Kdf::for_source(PromptKeySource::Passphrase).encode()),
+            "",
+            1,
+        );
+        assert!(open(&unversioned, Some("correct horse")).is_err());
+        assert_eq!(
+            Kdf::parse("argon2id,m=1024,t=3,p=1"),
+            Some(Kdf::Argon2id {
+                memory_kib: 1024,
+                iterations: 3,
+                lanes: 1
+            })
+        );
+        assert_eq!(Kdf::parse("argon2id,m=1024"), None);
+        assert_eq!(Kdf::parse("argon2id,m=4294967295,t=3,p=1"), None);
+        assert_eq!(Kdf::parse("pbkdf2"), None);
+    }
+}
@@ -0,0 +1,292 @@
+use chrono::{Local, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::notifications::{notify, NotificationCategory, NotificationText};
+use crate::provider::AccessWindow;
+use crate::services::{AccessWindowService, PromptService};
+use crate::store::AppState;
+
+/// 提示词定时方案在 settings 表中的键
+pub(crate) const SCHEDULES_KEY: &str = "prompt_schedules";
+
+/// 时段按分钟配置，与供应商可用时段共用检查频率
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 某个应用的提示词定时方案，例如工作日 9–18 点启用“工作”提示词，其余时间启用“个人项目”
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptSchedule {
+    pub app: String,
+    /// 1 为周一、7 为周日；为空表示每天
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub days: Vec<u8>,
+    /// 格式 HH:MM；结束早于开始表示跨越午夜
+    pub start: String,
+    pub end: String,
+    /// 时段内启用的提示词
+    pub prompt_id: String,
+    /// 时段外启用的提示词；未设置时时段外保持不变
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub otherwise_prompt_id: Option<String>,
+    /// 调度器最近一次应用的阶段（true 为时段内）
+    ///
+    /// 手动启用其他提示词不会改变该值，因此手动选择会一直保留到下一个时段边界
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub applied_phase: Option<bool>,
+}
+
+impl PromptSchedule {
+    fn window(&self) -> AccessWindow {
+        AccessWindow {
+            days: self.days.clone(),
+            start: self.start.clone(),
+            end: self.end.clone(),
+            fallback_provider_id: None,
+        }
+    }
+
+    /// 进入新阶段时返回该阶段（true 为时段内）；仍处于已应用的阶段时返回 None
+    fn due_phase(&self, now: NaiveDateTime) -> Result<Option<bool>, AppError> {
+        let in_window = AccessWindowService::is_open_at(&self.window(), now)?;
+        Ok((self.applied_phase != Some(in_window)).then_some(in_window))
+    }
+
+    fn target(&self, in_window: bool) -> Option<&str> {
+        if in_window {
+            Some(self.prompt_id.as_str())
+        } else {
+            self.otherwise_prompt_id.as_deref()
+        }
+    }
+}
+
+/// 调度器在时段边界执行的一次提示词切换
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptScheduleSwitch {
+    pub app: String,
+    pub prompt_id: String,
+    pub prompt_name: String,
+    pub in_window: bool,
+}
+
+/// 按时段自动切换各应用启用的提示词
+pub struct PromptScheduleService;
+
+impl PromptScheduleService {
+    /// 列出定时方案，指定应用时只返回该应用的方案
+    pub fn list(state: &AppState, app: Option<&AppType>) -> Result<Vec<PromptSchedule>, AppError> {
+        let schedules = Self::load(state)?;
+        Ok(match app {
+            Some(app) => schedules
+                .into_iter()
+                .filter(|s| s.app == app.as_str())
+                .collect(),
+            None => schedules,
+        })
+    }
+
+    /// 保存应用的定时方案（每个应用一个，已有方案被替换），下一次检查时立即生效
+    pub fn save(
+        state: &AppState,
+        mut schedule: PromptSchedule,
+    ) -> Result<PromptSchedule, AppError> {
+        let app = AppType::from_str(&schedule.app)?;
+        AccessWindowService::validate(&schedule.window())?;
+        let prompts = state.storage.get_prompts(app.as_str())?;
+        let targets = std::iter::once(&schedule.prompt_id).chain(&schedule.otherwise_prompt_id);
+        for id in targets {
+            if !prompts.contains_key(id) {
+                return Err(AppError::localized(
+                    "prompt_schedule.prompt_not_found",
+                    format!("定时方案引用的提示词不存在: {id}"),
+                    format!("The schedule refers to a prompt that does not exist: {id}"),
+                ));
+            }
+        }
+
+        schedule.applied_phase = None;
+        let mut schedules = Self::load(state)?;
+        schedules.retain(|s| s.app != schedule.app);
+        schedules.push(schedule.clone());
+        Self::store(state, &schedules)?;
+        Ok(schedule)
+    }
+
+    /// 删除应用的定时方案，当前启用的提示词保持不变
+    pub fn delete(state: &AppState, app: &AppType) -> Result<bool, AppError> {
+        let mut schedules = Self::load(state)?;
+        let before = schedules.len();
+        schedules.retain(|s| s.app != app.as_str());
+        if schedules.len() == before {
+            return Ok(false);
+        }
+        Self::store(state, &schedules)?;
+        Ok(true)
+    }
+
+    /// 检查各方案是否跨过时段边界，是则启用对应提示词并同步 live 文件
+    ///
+    /// 单个方案失败（提示词已删除、加密提示词未解锁等）只记录日志，不影响其他应用
+    pub fn apply_due(
+        state: &AppState,
+        now: NaiveDateTime,
+    ) -> Result<Vec<PromptScheduleSwitch>, AppError> {
+        let mut schedules = Self::load(state)?;
+        let mut switched = Vec::new();
+        let mut changed = false;
+
+        for schedule in schedules.iter_mut() {
+            let in_window = match schedule.due_phase(now) {
+                Ok(Some(in_window)) => in_window,
+                Ok(None) => continue,
+                Err(e) => {
+                    log::warn!("{} 的提示词定时方案无效，已跳过: {e}", schedule.app);
+                    continue;
+                }
+            };
+            schedule.applied_phase = Some(in_window);
+            changed = true;
+
+            let Some(target) = schedule.target(in_window).map(str::to_string) else {
+                continue;
+            };
+            match Self::switch_to(state, &schedule.app, &target) {
+                Ok(Some(name)) => {
+                    log::info!("{} 已按定时方案启用提示词 {target}", schedule.app);
+                    switched.push(PromptScheduleSwitch {
+                        app: schedule.app.clone(),
+                        prompt_id: target,
+                        prompt_name: name,
+                        in_window,
+                    });
+                }
+                Ok(None) => {}
+                Err(e) => log::warn!("{} 按定时方案启用提示词 {target} 失败: {e}", schedule.app),
+            }
+        }
+
+        if changed {
+            Self::store(state, &schedules)?;
+        }
+        Ok(switched)
+    }
+
+    /// 启用目标提示词并返回其名称；已处于启用状态时返回 None
+    fn switch_to(state: &AppState, app: &str, id: &str) -> Result<Option<String>, AppError> {
+        let app_type = AppType::from_str(app)?;
+        let enabled = state.storage.get_enabled_prompt(app)?;
+        if enabled.as_ref().is_some_and(|p| p.id == id) {
+            return Ok(None);
+        }
+        PromptService::enable_prompt(state, app_type, id)?;
+        let name = state
+            .storage
+            .get_prompt(app, id)?
+            .map(|p| p.name)
+            .unwrap_or_else(|| id.to_string());
+        Ok(Some(name))
+    }
+
+    /// 启动后台调度器；切换后通知前端刷新提示词列表
+    pub fn spawn_scheduler(app: AppHandle, state: AppState) {
+        tauri::async_runtime::spawn(async move {
+            loop {
+                let state = state.clone();
+                let result = tauri::async_runtime::spawn_blocking(move || {
+                    Self::apply_due(&state, Local::now().naive_local())
+                })
+                .await;
+
+                match result {
+                    Ok(Ok(switched)) => Self::announce(&app, &switched),
+                    Ok(Err(err)) => log::warn!("检查提示词定时方案失败: {err}"),
+                    Err(err) => log::warn!("提示词定时方案检查任务异常: {err}"),
+                }
+
+                tokio::time::sleep(CHECK_INTERVAL).await;
+            }
+        });
+    }
+
+    fn announce(app: &AppHandle, switched: &[PromptScheduleSwitch]) {
+        for item in switched {
+            let payload = serde_json::json!({
+                "appType": item.app,
+                "promptId": item.prompt_id,
+            });
+            if let Err(e) = app.emit("prompt-schedule-applied", payload) {
+                log::error!("发射提示词定时切换事件失败: {e}");
+            }
+            notify(
+                NotificationCategory::PromptSchedule,
+                true,
+                NotificationText::new(
+                    format!("{} 已按计划启用提示词 {}", item.app, item.prompt_name),
+                    format!(
+                        "{} switched to prompt {} on schedule",
+                        item.app, item.prompt_name
+                    ),
+                ),
+            );
+        }
+    }
+
+    fn load(state: &AppState) -> Result<Vec<PromptSchedule>, AppError> {
+        let Some(raw) = state.storage.get_setting(SCHEDULES_KEY)? else {
+            return Ok(Vec::new());
+        };
+        serde_json::from_str(&raw)
+            .map_err(|e| AppError::Config(format!("解析提示词定时方案失败: {e}")))
+    }
+
+    fn store(state: &AppState, schedules: &[PromptSchedule]) -> Result<(), AppError> {
+        let json =
+            serde_json::to_string(schedules).map_err(|e| AppError::JsonSerialize { source: e })?;
+        state.storage.set_setting(SCHEDULES_KEY, &json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        // 2024-01-01 是周一
+        NaiveDate::from_ymd_opt(2024, 1, day)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn switches_only_at_boundaries_so_manual_choice_survives() {
+        let mut schedule = PromptSchedule {
+            app: "claude".into(),
+            days: vec![1, 2, 3, 4, 5],
+            start: "09:00".into(),
+            end: "18:00".into(),
+            prompt_id: "work".into(),
+            otherwise_prompt_id: Some("side".into()),
+            applied_phase: None,
+        };
+
+        // 新方案立即应用当前阶段
+        assert_eq!(schedule.due_phase(at(1, 10)).unwrap(), Some(true));
+        assert_eq!(schedule.target(true), Some("work"));
+        schedule.applied_phase = Some(true);
+
+        // 同一阶段内不再切换，手动启用的提示词得以保留
+        assert_eq!(schedule.due_phase(at(1, 15)).unwrap(), None);
+        assert_eq!(schedule.due_phase(at(1, 18)).unwrap(), Some(false));
+        schedule.applied_phase = Some(false);
+        assert_eq!(schedule.due_phase(at(6, 10)).unwrap(), None);
+        assert_eq!(schedule.target(false), Some("side"));
+    }
+}
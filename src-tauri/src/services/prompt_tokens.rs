@@ -0,0 +1,241 @@
+use serde::Serialize;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::services::prompt_crypto::PromptCryptoService;
+use crate::store::AppState;
+
+/// 分词器家族；各家 BPE 词表不同，同一段文本的 token 数会有差异
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TokenizerFamily {
+    Claude,
+    OpenAi,
+    Gemini,
+}
+
+/// 各家族的近似参数，取自对常见英文/中文/代码文本的抽样比对
+struct FamilyProfile {
+    /// 一个拉丁字母单词平均每个 token 覆盖的字符数
+    word_chars_per_token: f64,
+    /// 连续数字每个 token 覆盖的位数（OpenAI 按三位切分，SentencePiece 逐位切分）
+    digits_per_token: f64,
+    /// 每个 CJK 字符平均消耗的 token
+    tokens_per_cjk: f64,
+    /// 名义上下文窗口，仅用于换算占比
+    context_window: u64,
+}
+
+impl TokenizerFamily {
+    /// 优先按模型名判断，无法识别时使用应用默认的家族
+    pub fn resolve(app: &AppType, model: Option<&str>) -> Self {
+        let model = model
+            .map(|m| m.trim().to_ascii_lowercase())
+            .unwrap_or_default();
+        if model.contains("claude") {
+            Self::Claude
+        } else if model.contains("gemini") {
+            Self::Gemini
+        } else if model.starts_with("gpt")
+            || model.starts_with("o1")
+            || model.starts_with("o3")
+            || model.starts_with("o4")
+            || model.contains("codex")
+        {
+            Self::OpenAi
+        } else {
+            match app {
+                AppType::Claude => Self::Claude,
+                AppType::Codex => Self::OpenAi,
+                AppType::Gemini => Self::Gemini,
+            }
+        }
+    }
+
+    fn profile(self) -> FamilyProfile {
+        match self {
+            Self::Claude => FamilyProfile {
+                word_chars_per_token: 4.5,
+                digits_per_token: 3.0,
+                tokens_per_cjk: 1.3,
+                context_window: 200_000,
+            },
+            Self::OpenAi => FamilyProfile {
+                word_chars_per_token: 5.0,
+                digits_per_token: 3.0,
+                tokens_per_cjk: 1.0,
+                context_window: 400_000,
+            },
+            Self::Gemini => FamilyProfile {
+                word_chars_per_token: 5.0,
+                digits_per_token: 1.0,
+                tokens_per_cjk: 0.8,
+                context_window: 1_048_576,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptTokenEstimate {
+    /// 命中已保存提示词时为其 ID，否则视为直接传入的正文
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt_id: Option<String>,
+    pub family: TokenizerFamily,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    pub characters: usize,
+    pub tokens: u64,
+    pub context_window: u64,
+    /// 占名义上下文窗口的百分比（0-100，保留两位小数）
+    pub context_percent: f64,
+}
+
+pub struct PromptTokenService;
+
+impl PromptTokenService {
+    /// 估算提示词（按 ID 查找，找不到则当作正文）占用的 token 数
+    pub fn estimate(
+        state: &AppState,
+        app: AppType,
+        prompt_id_or_content: &str,
+        model: Option<&str>,
+    ) -> Result<PromptTokenEstimate, AppError> {
+        let (prompt_id, content) = match state
+            .storage
+            .get_prompt_content(app.as_str(), prompt_id_or_content)?
+        {
+            Some(content) => {
+                let content = PromptCryptoService::plaintext(&app, prompt_id_or_content, &content)?;
+                (Some(prompt_id_or_content.to_string()), content)
+            }
+            None => (None, prompt_id_or_content.to_string()),
+        };
+
+        let family = TokenizerFamily::resolve(&app, model);
+        let tokens = estimate_tokens(&content, family);
+        let context_window = family.profile().context_window;
+        let percent = tokens as f64 / context_window as f64 * 100.0;
+        Ok(PromptTokenEstimate {
+            prompt_id,
+            family,
+            model: model
+                .map(str::trim)
+                .filter(|m| !m.is_empty())
+                .map(str::to_string),
+            characters: content.chars().count(),
+            tokens,
+            context_window,
+            context_percent: (percent * 100.0).round() / 100.0,
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Word,
+    Digit,
+    Space,
+    Newline,
+    Cjk,
+    Punct,
+    Other,
+}
+
+fn classify(c: char) -> CharClass {
+    match c {
+        '\n' | '\r' => CharClass::Newline,
+        c if c.is_whitespace() => CharClass::Space,
+        c if c.is_ascii_digit() => CharClass::Digit,
+        c if c.is_ascii_alphabetic() || c == '_' => CharClass::Word,
+        c if c.is_ascii_punctuation() => CharClass::Punct,
+        c if is_cjk(c) => CharClass::Cjk,
+        c if c.is_alphabetic() => CharClass::Word,
+        _ => CharClass::Other,
+    }
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c as u32,
+        0x3040..=0x30FF     // 平假名、片假名
+        | 0x3400..=0x4DBF   // CJK 扩展 A
+        | 0x4E00..=0x9FFF   // CJK 统一表意文字
+        | 0xAC00..=0xD7AF   // 韩文音节
+        | 0xF900..=0xFAFF   // CJK 兼容表意文字
+        | 0x3000..=0x303F   // CJK 标点
+        | 0xFF00..=0xFFEF   // 全角字符
+    )
+}
+
+/// 模拟 BPE 的预切分：按字符类别切成片段，再按各家族的平均压缩率折算。
+/// 单个空格会与后一个单词合并，因此不单独计数
+pub fn estimate_tokens(text: &str, family: TokenizerFamily) -> u64 {
+    let profile = family.profile();
+    let mut total = 0.0_f64;
+    let mut chars = text.chars().peekable();
+
+    while let Some(first) = chars.next() {
+        let class = classify(first);
+        let mut run = vec![first];
+        while let Some(&next) = chars.peek() {
+            if classify(next) != class {
+                break;
+            }
+            run.push(next);
+            chars.next();
+        }
+        let len = run.len() as f64;
+
+        total += match class {
+            CharClass::Word => {
+                // 非 ASCII 字母（重音、西里尔等）在字节级 BPE 中更碎
+                let non_ascii = run.iter().filter(|c| !c.is_ascii()).count() as f64;
+                let effective = len + non_ascii;
+                (effective / profile.word_chars_per_token).ceil().max(1.0)
+            }
+            CharClass::Digit => (len / profile.digits_per_token).ceil(),
+            CharClass::Space if len > 1.0 => 1.0,
+            CharClass::Space => 0.0,
+            CharClass::Newline => 1.0,
+            CharClass::Cjk => len * profile.tokens_per_cjk,
+            // Markdown 中的 `**`、`##`、`---` 等通常两两合并
+            CharClass::Punct => (len / 2.0).ceil(),
+            // emoji 等符号常被拆成多个字节 token
+            CharClass::Other => len * 2.0,
+        };
+    }
+
+    total.ceil() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimates_follow_family_and_script() {
+        assert_eq!(estimate_tokens("", TokenizerFamily::OpenAi), 0);
+        assert_eq!(estimate_tokens("hello world", TokenizerFamily::OpenAi), 2);
+        assert_eq!(estimate_tokens("## Rules\n", TokenizerFamily::OpenAi), 3);
+
+        // 数字切分方式不同：OpenAI 三位一组，Gemini 逐位
+        assert_eq!(estimate_tokens("123456", TokenizerFamily::OpenAi), 2);
+        assert_eq!(estimate_tokens("123456", TokenizerFamily::Gemini), 6);
+
+        let chinese = "请始终使用中文回答";
+        assert!(
+            estimate_tokens(chinese, TokenizerFamily::Claude)
+                > estimate_tokens(chinese, TokenizerFamily::Gemini)
+        );
+
+        assert_eq!(
+            TokenizerFamily::resolve(&AppType::Claude, Some("gpt-5-codex")),
+            TokenizerFamily::OpenAi
+        );
+        assert_eq!(
+            TokenizerFamily::resolve(&AppType::Gemini, None),
+            TokenizerFamily::Gemini
+        );
+    }
+}
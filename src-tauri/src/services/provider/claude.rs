@@ -1,7 +1,9 @@
-use serde_json::Value;
+use serde_json::{Map, Value};
+use std::borrow::Cow;
 
 use crate::app_config::AppType;
-use crate::provider::Provider;
+use crate::error::AppError;
+use crate::provider::{ClaudeFlavor, ClaudeFlavorConfig, Provider};
 
 pub struct ClaudeModelNormalizer;
 
@@ -77,13 +79,171 @@ impl ClaudeModelNormalizer {
 
         changed
     }
+}
+
+const USE_BEDROCK: &str = "CLAUDE_CODE_USE_BEDROCK";
+const USE_VERTEX: &str = "CLAUDE_CODE_USE_VERTEX";
+const AWS_REGION: &str = "AWS_REGION";
+const AWS_PROFILE: &str = "AWS_PROFILE";
+const VERTEX_REGION: &str = "CLOUD_ML_REGION";
+const VERTEX_PROJECT_ID: &str = "ANTHROPIC_VERTEX_PROJECT_ID";
+
+/// 根据供应商的 Claude 接入方式生成 Bedrock / Vertex 所需的 env
+pub struct ClaudeFlavorEnv;
+
+impl ClaudeFlavorEnv {
+    /// 返回写入 live 时应使用的供应商；未设置接入方式时原样返回
+    pub fn with_flavor_env<'a>(app_type: &AppType, provider: &'a Provider) -> Cow<'a, Provider> {
+        let Some(config) = Self::flavor_config(app_type, provider) else {
+            return Cow::Borrowed(provider);
+        };
+
+        let mut flavored = provider.clone();
+        Self::apply(&mut flavored.settings_config, config);
+        Cow::Owned(flavored)
+    }
+
+    /// 校验接入方式所需的区域与项目（允许直接写在 env 中）
+    pub fn validate(app_type: &AppType, provider: &Provider) -> Result<(), AppError> {
+        let Some(config) = Self::flavor_config(app_type, provider) else {
+            return Ok(());
+        };
+
+        let env = provider
+            .settings_config
+            .get("env")
+            .and_then(|v| v.as_object());
+        let has = |value: &Option<String>, env_key: &str| {
+            value.as_deref().is_some_and(|v| !v.trim().is_empty())
+                || env
+                    .and_then(|e| e.get(env_key))
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|v| !v.trim().is_empty())
+        };
+
+        match config.flavor {
+            ClaudeFlavor::Direct => Ok(()),
+            ClaudeFlavor::Bedrock if !has(&config.region, AWS_REGION) => Err(AppError::localized(
+                "provider.claude.bedrock.region_missing",
+                "Bedrock 供应商需要设置 AWS 区域",
+                "Bedrock provider requires an AWS region",
+            )),
+            ClaudeFlavor::Vertex if !has(&config.region, VERTEX_REGION) => {
+                Err(AppError::localized(
+                    "provider.claude.vertex.region_missing",
+                    "Vertex 供应商需要设置区域",
+                    "Vertex provider requires a region",
+                ))
+            }
+            ClaudeFlavor::Vertex if !has(&config.project_id, VERTEX_PROJECT_ID) => {
+                Err(AppError::localized(
+                    "provider.claude.vertex.project_missing",
+                    "Vertex 供应商需要设置 GCP 项目 ID",
+                    "Vertex provider requires a GCP project ID",
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn flavor_config<'a>(
+        app_type: &AppType,
+        provider: &'a Provider,
+    ) -> Option<&'a ClaudeFlavorConfig> {
+        if !matches!(app_type, AppType::Claude) {
+            return None;
+        }
+        provider.meta.as_ref()?.claude_flavor.as_ref()
+    }
 
-    pub fn normalize_provider_if_claude(app_type: &AppType, provider: &mut Provider) {
-        if matches!(app_type, AppType::Claude) {
-            let mut v = provider.settings_config.clone();
-            if Self::normalize_claude_models_in_value(&mut v) {
-                provider.settings_config = v;
+    /// 写入当前接入方式的开关与区域，并移除另一种云接入方式的开关
+    fn apply(settings: &mut Value, config: &ClaudeFlavorConfig) {
+        let Some(root) = settings.as_object_mut() else {
+            return;
+        };
+        let env = root
+            .entry("env")
+            .or_insert_with(|| Value::Object(Map::new()));
+        let Some(env) = env.as_object_mut() else {
+            return;
+        };
+
+        let mut set = |key: &str, value: Option<&str>| {
+            if let Some(v) = value.map(str::trim).filter(|v| !v.is_empty()) {
+                env.insert(key.to_string(), Value::String(v.to_string()));
+            }
+        };
+
+        match config.flavor {
+            ClaudeFlavor::Direct => {}
+            ClaudeFlavor::Bedrock => {
+                set(USE_BEDROCK, Some("1"));
+                set(AWS_REGION, config.region.as_deref());
+                set(AWS_PROFILE, config.aws_profile.as_deref());
+            }
+            ClaudeFlavor::Vertex => {
+                set(USE_VERTEX, Some("1"));
+                set(VERTEX_REGION, config.region.as_deref());
+                set(VERTEX_PROJECT_ID, config.project_id.as_deref());
             }
         }
+
+        if config.flavor != ClaudeFlavor::Bedrock {
+            env.remove(USE_BEDROCK);
+        }
+        if config.flavor != ClaudeFlavor::Vertex {
+            env.remove(USE_VERTEX);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn provider_with_flavor(config: ClaudeFlavorConfig) -> Provider {
+        let mut provider = Provider::with_id(
+            "bedrock".into(),
+            "Bedrock".into(),
+            json!({ "env": { "CLAUDE_CODE_USE_VERTEX": "1" } }),
+            None,
+        );
+        provider.meta = Some(crate::provider::ProviderMeta {
+            claude_flavor: Some(config),
+            ..Default::default()
+        });
+        provider
+    }
+
+    #[test]
+    fn bedrock_flavor_generates_env_and_clears_vertex_flag() {
+        let provider = provider_with_flavor(ClaudeFlavorConfig {
+            flavor: ClaudeFlavor::Bedrock,
+            region: Some("us-east-1".into()),
+            aws_profile: Some("work".into()),
+            ..Default::default()
+        });
+        ClaudeFlavorEnv::validate(&AppType::Claude, &provider).unwrap();
+
+        let flavored = ClaudeFlavorEnv::with_flavor_env(&AppType::Claude, &provider);
+        assert_eq!(
+            flavored.settings_config["env"],
+            json!({
+                "CLAUDE_CODE_USE_BEDROCK": "1",
+                "AWS_REGION": "us-east-1",
+                "AWS_PROFILE": "work"
+            })
+        );
+    }
+
+    #[test]
+    fn vertex_flavor_requires_project() {
+        let provider = provider_with_flavor(ClaudeFlavorConfig {
+            flavor: ClaudeFlavor::Vertex,
+            region: Some("us-east5".into()),
+            ..Default::default()
+        });
+        assert!(ClaudeFlavorEnv::validate(&AppType::Claude, &provider).is_err());
     }
 }
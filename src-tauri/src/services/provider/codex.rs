@@ -0,0 +1,320 @@
+use base64::prelude::*;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::app_config::AppType;
+use crate::codex_config::get_codex_auth_path;
+use crate::config::read_json_file;
+use crate::error::AppError;
+use crate::provider::{CodexAuthMode, Provider};
+use crate::services::sync_pause::is_management_paused;
+use crate::store::AppState;
+
+const API_KEY: &str = "OPENAI_API_KEY";
+const TOKENS: &str = "tokens";
+
+/// ChatGPT 登录凭证的过期信息（不含任何令牌内容）
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexLoginStatus {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// id_token 的过期时间（Unix 秒）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<i64>,
+    /// auth.json 中记录的最近刷新时间
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_refresh: Option<String>,
+    pub has_refresh_token: bool,
+    pub expired: bool,
+}
+
+/// Codex ChatGPT 登录账号：auth.json 作为不透明凭证整体保存与还原
+pub struct CodexLoginAuth;
+
+impl CodexLoginAuth {
+    /// 供应商的认证方式：显式设置优先，否则按 auth 内容推断
+    pub fn mode(provider: &Provider) -> CodexAuthMode {
+        if let Some(mode) = provider.meta.as_ref().and_then(|m| m.codex_auth_mode) {
+            return mode;
+        }
+        match provider.settings_config.get("auth") {
+            Some(auth) => Self::detect(auth),
+            None => CodexAuthMode::ApiKey,
+        }
+    }
+
+    /// auth.json 含 tokens 且没有 API Key 时视为 ChatGPT 登录
+    pub fn detect(auth: &Value) -> CodexAuthMode {
+        let has_key = auth
+            .get(API_KEY)
+            .and_then(Value::as_str)
+            .is_some_and(|k| !k.trim().is_empty());
+        if !has_key && auth.get(TOKENS).is_some_and(Value::is_object) {
+            CodexAuthMode::ChatgptLogin
+        } else {
+            CodexAuthMode::ApiKey
+        }
+    }
+
+    /// 未显式设置认证方式时，按 auth 内容补上 chatgpt-login 标记
+    pub fn normalize(app_type: &AppType, provider: &mut Provider) {
+        if !matches!(app_type, AppType::Codex) {
+            return;
+        }
+        let explicit = provider
+            .meta
+            .as_ref()
+            .is_some_and(|m| m.codex_auth_mode.is_some());
+        if explicit || Self::mode(provider) != CodexAuthMode::ChatgptLogin {
+            return;
+        }
+        provider
+            .meta
+            .get_or_insert_with(Default::default)
+            .codex_auth_mode = Some(CodexAuthMode::ChatgptLogin);
+    }
+
+    /// 校验显式设置的认证方式与 auth 内容一致
+    pub fn validate(app_type: &AppType, provider: &Provider) -> Result<(), AppError> {
+        if !matches!(app_type, AppType::Codex) {
+            return Ok(());
+        }
+        let Some(mode) = provider.meta.as_ref().and_then(|m| m.codex_auth_mode) else {
+            return Ok(());
+        };
+        let auth = provider.settings_config.get("auth");
+
+        match mode {
+            CodexAuthMode::ApiKey => {
+                let has_key = auth
+                    .and_then(|a| a.get(API_KEY))
+                    .and_then(Value::as_str)
+                    .is_some_and(|k| !k.trim().is_empty());
+                if !has_key {
+                    return Err(AppError::localized(
+                        "provider.codex.api_key.missing",
+                        "API Key 模式需要填写 auth.OPENAI_API_KEY",
+                        "API key mode requires auth.OPENAI_API_KEY",
+                    ));
+                }
+            }
+            CodexAuthMode::ChatgptLogin => {
+                let tokens = auth.and_then(|a| a.get(TOKENS)).and_then(Value::as_object);
+                let usable = tokens.is_some_and(|t| {
+                    ["id_token", "refresh_token"]
+                        .iter()
+                        .any(|k| t.get(*k).and_then(Value::as_str).is_some())
+                });
+                if !usable {
+                    return Err(AppError::localized(
+                        "provider.codex.login.tokens_missing",
+                        "ChatGPT 登录模式需要完整的 auth.json（含 tokens），请先在 Codex 中登录后捕获",
+                        "ChatGPT login mode requires a full auth.json with tokens; log in with Codex and capture it first",
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 读取登录凭证的账号与过期信息；非登录模式返回 None
+    pub fn status(provider: &Provider) -> Option<CodexLoginStatus> {
+        if Self::mode(provider) != CodexAuthMode::ChatgptLogin {
+            return None;
+        }
+        let auth = provider.settings_config.get("auth")?;
+        let tokens = auth.get(TOKENS).and_then(Value::as_object);
+        let token = |key: &str| tokens.and_then(|t| t.get(key)).and_then(Value::as_str);
+
+        let claims = token("id_token").and_then(jwt_claims).unwrap_or_default();
+        let expires_at = claims.get("exp").and_then(Value::as_i64);
+        let account_id = token("account_id").map(str::to_string).or_else(|| {
+            claims
+                .get("https://api.openai.com/auth")
+                .and_then(|a| a.get("chatgpt_account_id"))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        });
+
+        Some(CodexLoginStatus {
+            account_id,
+            email: claims
+                .get("email")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            expires_at,
+            last_refresh: auth
+                .get("last_refresh")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            has_refresh_token: token("refresh_token").is_some_and(|t| !t.is_empty()),
+            expired: expires_at.is_some_and(|exp| exp <= chrono::Utc::now().timestamp()),
+        })
+    }
+
+    /// 把当前 live auth.json 整体存入供应商，并标记为 ChatGPT 登录
+    pub fn capture_live(state: &AppState, id: &str) -> Result<Provider, AppError> {
+        let app_type = AppType::Codex;
+        let mut provider = state
+            .storage
+            .get_all_providers(app_type.as_str())?
+            .shift_remove(id)
+            .ok_or_else(|| AppError::Message(format!("供应商 {id} 不存在")))?;
+
+        let auth = Self::read_live_auth()?.ok_or_else(|| {
+            AppError::localized(
+                "codex.auth.missing",
+                "Codex 配置文件不存在：缺少 auth.json",
+                "Codex configuration missing: auth.json not found",
+            )
+        })?;
+        if Self::detect(&auth) != CodexAuthMode::ChatgptLogin {
+            return Err(AppError::localized(
+                "provider.codex.login.not_logged_in",
+                "当前 auth.json 不是 ChatGPT 登录凭证，请先运行 codex login",
+                "The current auth.json is not a ChatGPT login; run `codex login` first",
+            ));
+        }
+
+        Self::store_auth(&mut provider, auth);
+        provider
+            .meta
+            .get_or_insert_with(Default::default)
+            .codex_auth_mode = Some(CodexAuthMode::ChatgptLogin);
+        state.storage.save_provider(app_type.as_str(), &provider)?;
+        Ok(provider)
+    }
+
+    /// 切换前回填：Codex 会刷新令牌并改写 auth.json，需把最新内容写回当前登录供应商
+    pub fn backfill_current(state: &AppState) -> Result<(), AppError> {
+        if is_management_paused() {
+            return Ok(());
+        }
+        let app_type = AppType::Codex;
+        let Some(current) = state.storage.get_current_provider(app_type.as_str())? else {
+            return Ok(());
+        };
+        let Some(mut provider) = state
+            .storage
+            .get_all_providers(app_type.as_str())?
+            .shift_remove(&current)
+        else {
+            return Ok(());
+        };
+        if Self::mode(&provider) != CodexAuthMode::ChatgptLogin {
+            return Ok(());
+        }
+        let Some(auth) = Self::read_live_auth()? else {
+            return Ok(());
+        };
+        // 用户可能已在外部登出或换成 API Key，此时不覆盖已保存的登录凭证
+        if Self::detect(&auth) != CodexAuthMode::ChatgptLogin
+            || provider.settings_config.get("auth") == Some(&auth)
+        {
+            return Ok(());
+        }
+
+        Self::store_auth(&mut provider, auth);
+        state.storage.save_provider(app_type.as_str(), &provider)
+    }
+
+    fn read_live_auth() -> Result<Option<Value>, AppError> {
+        let path = get_codex_auth_path();
+        if !path.exists() {
+            return Ok(None);
+        }
+        read_json_file(&path).map(Some)
+    }
+
+    fn store_auth(provider: &mut Provider, auth: Value) {
+        if !provider.settings_config.is_object() {
+            provider.settings_config = Value::Object(Map::new());
+        }
+        if let Some(settings) = provider.settings_config.as_object_mut() {
+            settings.insert("auth".to_string(), auth);
+        }
+    }
+}
+
+/// 解析 JWT 的 payload（不校验签名，仅用于读取过期时间等展示信息）
+fn jwt_claims(token: &str) -> Option<Map<String, Value>> {
+    let payload = token.split('.').nth(1)?;
+    let bytes = BASE64_URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .ok()?;
+    match serde_json::from_slice(&bytes).ok()? {
+        Value::Object(map) => Some(map),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn jwt(claims: Value) -> String {
+        let payload = BASE64_URL_SAFE_NO_PAD.encode(claims.to_string());
+        format!("eyJhbGciOiJub25lIn0.{payload}.sig")
+    }
+
+    fn login_provider(exp: i64) -> Provider {
+        Provider::with_id(
+            "login".into(),
+            "ChatGPT".into(),
+            json!({
+                "auth": {
+                    "OPENAI_API_KEY": null,
+                    "tokens": {
+                        "id_token": jwt(json!({ "exp": exp, "email": "me@example.com" })),
+                        "access_token": "at",
+                        "refresh_token": "rt",
+                        "account_id": "acc-1"
+                    },
+                    "last_refresh": "2025-01-01T00:00:00Z"
+                },
+                "config": ""
+            }),
+            None,
+        )
+    }
+
+    #[test]
+    fn login_auth_is_detected_and_expiry_surfaced() {
+        let mut provider = login_provider(1);
+        CodexLoginAuth::normalize(&AppType::Codex, &mut provider);
+        assert_eq!(
+            provider.meta.as_ref().and_then(|m| m.codex_auth_mode),
+            Some(CodexAuthMode::ChatgptLogin)
+        );
+        CodexLoginAuth::validate(&AppType::Codex, &provider).unwrap();
+
+        let status = CodexLoginAuth::status(&provider).unwrap();
+        assert_eq!(status.account_id.as_deref(), Some("acc-1"));
+        assert_eq!(status.email.as_deref(), Some("me@example.com"));
+        assert_eq!(status.expires_at, Some(1));
+        assert!(status.expired);
+        assert!(status.has_refresh_token);
+    }
+
+    #[test]
+    fn explicit_mode_must_match_auth_contents() {
+        let mut provider = Provider::with_id(
+            "key".into(),
+            "Key".into(),
+            json!({ "auth": { "OPENAI_API_KEY": "sk-1" }, "config": "" }),
+            None,
+        );
+        assert_eq!(CodexLoginAuth::mode(&provider), CodexAuthMode::ApiKey);
+        assert!(CodexLoginAuth::status(&provider).is_none());
+
+        provider.meta = Some(crate::provider::ProviderMeta {
+            codex_auth_mode: Some(CodexAuthMode::ChatgptLogin),
+            ..Default::default()
+        });
+        assert!(CodexLoginAuth::validate(&AppType::Codex, &provider).is_err());
+    }
+}
@@ -54,6 +54,16 @@ impl CredentialsExtractor {
                 Ok((api_key, base_url))
             }
             AppType::Codex => {
+                if super::codex::CodexLoginAuth::mode(provider)
+                    == crate::provider::CodexAuthMode::ChatgptLogin
+                {
+                    return Err(AppError::localized(
+                        "provider.codex.login.no_api_key",
+                        "ChatGPT 登录账号没有 API Key，无法用于用量查询或测速",
+                        "ChatGPT login accounts have no API key for usage queries or speed tests",
+                    ));
+                }
+
                 let auth = provider
                     .settings_config
                     .get("auth")
@@ -1,10 +1,43 @@
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use url::Url;
+
 use crate::app_config::AppType;
 use crate::error::AppError;
+use crate::provider::Provider;
 use crate::settings::CustomEndpoint;
 use crate::store::AppState;
 
+/// Format tag of an exported endpoint list
+const ENDPOINT_SNIPPET_FORMAT: &str = "cli-hub-endpoints";
+const ENDPOINT_SNIPPET_VERSION: u32 = 1;
+/// Guard against pasting something that is clearly not an endpoint list
+const MAX_IMPORTED_ENDPOINTS: usize = 64;
+
+/// Portable endpoint list, small enough to paste into chat or a note
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointSnippet {
+    pub format: String,
+    pub version: u32,
+    pub app: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider_name: Option<String>,
+    pub endpoints: Vec<String>,
+}
+
+/// Outcome of merging a pasted endpoint list into a provider
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointImportResult {
+    pub added: Vec<String>,
+    /// Already present on the provider
+    pub skipped: Vec<String>,
+}
+
 pub struct EndpointManager;
 
 impl EndpointManager {
@@ -14,7 +47,7 @@ impl EndpointManager {
         app_type: AppType,
         provider_id: &str,
     ) -> Result<Vec<CustomEndpoint>, AppError> {
-        let providers = state.db.get_all_providers(app_type.as_str())?;
+        let providers = state.storage.get_all_providers(app_type.as_str())?;
         let Some(provider) = providers.get(provider_id) else {
             return Ok(vec![]);
         };
@@ -66,6 +99,67 @@ impl EndpointManager {
         Ok(())
     }
 
+    /// Serialize the provider's custom endpoints as a compact JSON snippet
+    pub fn export_custom_endpoints(
+        state: &AppState,
+        app_type: AppType,
+        provider_id: &str,
+    ) -> Result<String, AppError> {
+        let providers = state.storage.get_all_providers(app_type.as_str())?;
+        let provider = providers.get(provider_id).ok_or_else(|| {
+            AppError::localized(
+                "provider.not_found",
+                format!("供应商不存在: {provider_id}"),
+                format!("Provider not found: {provider_id}"),
+            )
+        })?;
+
+        let mut endpoints: Vec<_> =
+            Self::get_custom_endpoints(state, app_type.clone(), provider_id)?
+                .into_iter()
+                .map(|e| e.url)
+                .collect();
+        // Oldest first, so mirrors keep the order they were added in
+        endpoints.reverse();
+
+        let snippet = EndpointSnippet {
+            format: ENDPOINT_SNIPPET_FORMAT.to_string(),
+            version: ENDPOINT_SNIPPET_VERSION,
+            app: app_type.as_str().to_string(),
+            provider_name: Some(provider.name.clone()),
+            endpoints,
+        };
+        serde_json::to_string(&snippet).map_err(|e| AppError::JsonSerialize { source: e })
+    }
+
+    /// Merge endpoints from a snippet, a bare JSON array or a provider deep link
+    pub fn import_custom_endpoints(
+        state: &AppState,
+        app_type: AppType,
+        provider_id: &str,
+        input: &str,
+    ) -> Result<EndpointImportResult, AppError> {
+        let urls = parse_endpoint_input(input)?;
+        let existing: Vec<String> =
+            Self::get_custom_endpoints(state, app_type.clone(), provider_id)?
+                .into_iter()
+                .map(|e| e.url)
+                .collect();
+
+        let mut result = EndpointImportResult::default();
+        for url in urls {
+            if existing.contains(&url) || result.added.contains(&url) {
+                result.skipped.push(url);
+                continue;
+            }
+            state
+                .db
+                .add_custom_endpoint(app_type.as_str(), provider_id, &url)?;
+            result.added.push(url);
+        }
+        Ok(result)
+    }
+
     /// Update endpoint last used timestamp
     pub fn update_endpoint_last_used(
         state: &AppState,
@@ -75,18 +169,62 @@ impl EndpointManager {
     ) -> Result<(), AppError> {
         let normalized = url.trim().trim_end_matches('/').to_string();
 
-        let mut providers = state.db.get_all_providers(app_type.as_str())?;
+        let mut providers = state.storage.get_all_providers(app_type.as_str())?;
         if let Some(provider) = providers.get_mut(provider_id) {
             if let Some(meta) = provider.meta.as_mut() {
                 if let Some(endpoint) = meta.custom_endpoints.get_mut(&normalized) {
                     endpoint.last_used = Some(Self::now_millis());
-                    state.db.save_provider(app_type.as_str(), provider)?;
+                    state.storage.save_provider(app_type.as_str(), provider)?;
                 }
             }
         }
         Ok(())
     }
 
+    /// Base URL the provider currently writes to the live config
+    pub fn base_url(app_type: &AppType, provider: &Provider) -> Option<String> {
+        let settings = &provider.settings_config;
+        let url = match app_type {
+            AppType::Claude => settings.pointer("/env/ANTHROPIC_BASE_URL")?.as_str()?,
+            AppType::Gemini => settings.pointer("/env/GOOGLE_GEMINI_BASE_URL")?.as_str()?,
+            AppType::Codex => {
+                let config = settings.get("config")?.as_str()?;
+                codex_base_url_re().captures(config)?.get(2)?.as_str()
+            }
+        };
+        Some(url.trim().trim_end_matches('/').to_string()).filter(|u| !u.is_empty())
+    }
+
+    /// Point the provider at another base URL; returns false when the config has no base URL
+    pub fn set_base_url(app_type: &AppType, provider: &mut Provider, url: &str) -> bool {
+        let settings = &mut provider.settings_config;
+        let key = match app_type {
+            AppType::Claude => "ANTHROPIC_BASE_URL",
+            AppType::Gemini => "GOOGLE_GEMINI_BASE_URL",
+            AppType::Codex => {
+                let Some(config) = settings.get("config").and_then(Value::as_str) else {
+                    return false;
+                };
+                let re = codex_base_url_re();
+                if !re.is_match(config) {
+                    return false;
+                }
+                let updated = re.replace(config, |caps: &regex::Captures| {
+                    format!("{}{url}{}", &caps[1], &caps[3])
+                });
+                settings["config"] = Value::String(updated.into_owned());
+                return true;
+            }
+        };
+        match settings.get_mut("env").and_then(Value::as_object_mut) {
+            Some(env) if env.contains_key(key) => {
+                env.insert(key.to_string(), Value::String(url.to_string()));
+                true
+            }
+            _ => false,
+        }
+    }
+
     fn now_millis() -> i64 {
         SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -94,3 +232,105 @@ impl EndpointManager {
             .as_millis() as i64
     }
 }
+
+fn codex_base_url_re() -> Regex {
+    Regex::new(r#"(base_url\s*=\s*["'])([^"']+)(["'])"#).expect("valid base_url regex")
+}
+
+/// Accepts the exported snippet, a JSON array of URLs, or a clihub:// provider link
+/// carrying an `endpoints` parameter; returns normalized, de-duplicated http(s) URLs
+fn parse_endpoint_input(input: &str) -> Result<Vec<String>, AppError> {
+    let input = input.trim();
+    let raw: Vec<String> = if input.starts_with("clihub://") {
+        let url = Url::parse(input)
+            .map_err(|e| AppError::InvalidInput(format!("Invalid deep link URL: {e}")))?;
+        url.query_pairs()
+            .find(|(key, _)| key == "endpoints")
+            .map(|(_, value)| value.split(',').map(str::to_string).collect())
+            .unwrap_or_default()
+    } else if input.starts_with('[') {
+        serde_json::from_str(input)
+            .map_err(|e| AppError::InvalidInput(format!("Invalid endpoint list: {e}")))?
+    } else {
+        let snippet: EndpointSnippet = serde_json::from_str(input)
+            .map_err(|e| AppError::InvalidInput(format!("Invalid endpoint snippet: {e}")))?;
+        if snippet.format != ENDPOINT_SNIPPET_FORMAT {
+            return Err(AppError::InvalidInput(format!(
+                "Unsupported endpoint snippet format: {}",
+                snippet.format
+            )));
+        }
+        if snippet.version > ENDPOINT_SNIPPET_VERSION {
+            return Err(AppError::InvalidInput(format!(
+                "Endpoint snippet version {} is newer than supported",
+                snippet.version
+            )));
+        }
+        snippet.endpoints
+    };
+
+    let mut urls: Vec<String> = Vec::new();
+    for candidate in raw {
+        let normalized = candidate.trim().trim_end_matches('/').to_string();
+        if normalized.is_empty() || urls.contains(&normalized) {
+            continue;
+        }
+        let parsed = Url::parse(&normalized).map_err(|e| {
+            AppError::InvalidInput(format!("Invalid endpoint URL '{normalized}': {e}"))
+        })?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(AppError::InvalidInput(format!(
+                "Endpoint must use http or https: {normalized}"
+            )));
+        }
+        urls.push(normalized);
+    }
+
+    if urls.is_empty() {
+        return Err(AppError::localized(
+            "provider.endpoint.import_empty",
+            "未找到可导入的端点",
+            "No endpoints found to import",
+        ));
+    }
+    if urls.len() > MAX_IMPORTED_ENDPOINTS {
+        return Err(AppError::InvalidInput(format!(
+            "Too many endpoints: {} (max {MAX_IMPORTED_ENDPOINTS})",
+            urls.len()
+        )));
+    }
+    Ok(urls)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn endpoint_input_accepts_snippet_array_and_deeplink() {
+        let snippet = r#"{"format":"cli-hub-endpoints","version":1,"app":"claude",
+            "endpoints":["https://a.relay.com/","https://b.relay.com","https://a.relay.com"]}"#;
+        assert_eq!(
+            parse_endpoint_input(snippet).unwrap(),
+            ["https://a.relay.com", "https://b.relay.com"]
+        );
+
+        assert_eq!(
+            parse_endpoint_input(r#"["http://10.0.0.2:8080"]"#).unwrap(),
+            ["http://10.0.0.2:8080"]
+        );
+
+        let link = "clihub://v1/import?resource=provider&endpoints=https%3A%2F%2Fhk.relay.com%2Chttps%3A%2F%2Fus.relay.com";
+        assert_eq!(
+            parse_endpoint_input(link).unwrap(),
+            ["https://hk.relay.com", "https://us.relay.com"]
+        );
+
+        assert!(parse_endpoint_input(r#"["ftp://x.com"]"#).is_err());
+        assert!(parse_endpoint_input(
+            r#"{"format":"other","version":1,"app":"claude","endpoints":[]}"#
+        )
+        .is_err());
+        assert!(parse_endpoint_input("[]").is_err());
+    }
+}
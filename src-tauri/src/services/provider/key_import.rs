@@ -0,0 +1,168 @@
+use serde::Serialize;
+use serde_json::json;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::Provider;
+
+use super::ProviderValidator;
+
+/// 可从 API Key 前缀识别的官方厂商
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ApiKeyVendor {
+    Anthropic,
+    OpenAi,
+    Google,
+}
+
+impl ApiKeyVendor {
+    /// 按前缀识别；`sk-ant-` 须先于通用的 `sk-` 判断
+    pub fn detect(key: &str) -> Option<Self> {
+        if key.starts_with("sk-ant-") {
+            Some(Self::Anthropic)
+        } else if key.starts_with("AIza") && key.len() == 39 {
+            Some(Self::Google)
+        } else if key.starts_with("sk-") {
+            Some(Self::OpenAi)
+        } else {
+            None
+        }
+    }
+
+    pub fn app_type(self) -> AppType {
+        match self {
+            Self::Anthropic => AppType::Claude,
+            Self::OpenAi => AppType::Codex,
+            Self::Google => AppType::Gemini,
+        }
+    }
+
+    fn default_name(self) -> &'static str {
+        match self {
+            Self::Anthropic => "Anthropic API",
+            Self::OpenAi => "OpenAI API",
+            Self::Google => "Gemini API",
+        }
+    }
+
+    fn console_url(self) -> &'static str {
+        match self {
+            Self::Anthropic => "https://console.anthropic.com",
+            Self::OpenAi => "https://platform.openai.com",
+            Self::Google => "https://aistudio.google.com",
+        }
+    }
+}
+
+/// 由 API Key 生成、尚未保存的供应商，前端确认后再调用添加
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyProviderDraft {
+    pub app: String,
+    pub vendor: ApiKeyVendor,
+    pub provider: Provider,
+}
+
+pub struct KeyProviderImporter;
+
+impl KeyProviderImporter {
+    /// 识别粘贴的 API Key 并生成指向官方端点的供应商草稿
+    pub fn draft(raw_key: &str, name: Option<&str>) -> Result<KeyProviderDraft, AppError> {
+        let key = normalize_key(raw_key);
+        if key.is_empty() || key.chars().any(char::is_whitespace) {
+            return Err(AppError::localized(
+                "provider.key_import.invalid",
+                "API Key 为空或包含空白字符",
+                "The API key is empty or contains whitespace",
+            ));
+        }
+        let vendor = ApiKeyVendor::detect(key).ok_or_else(|| {
+            AppError::localized(
+                "provider.key_import.unknown_vendor",
+                "无法识别该 API Key 所属厂商（支持 sk-ant-、sk-、AIza 开头的密钥）",
+                "Unrecognized API key (supported prefixes: sk-ant-, sk-, AIza)",
+            )
+        })?;
+
+        let settings_config = match vendor {
+            ApiKeyVendor::Anthropic => json!({
+                "env": {
+                    "ANTHROPIC_API_KEY": key,
+                    "ANTHROPIC_BASE_URL": "https://api.anthropic.com",
+                }
+            }),
+            // 空 config.toml 即使用 Codex 内置的 OpenAI 端点
+            ApiKeyVendor::OpenAi => json!({
+                "auth": { "OPENAI_API_KEY": key },
+                "config": "",
+            }),
+            ApiKeyVendor::Google => json!({
+                "env": { "GEMINI_API_KEY": key }
+            }),
+        };
+
+        let name = name
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+            .unwrap_or(vendor.default_name());
+        let timestamp = chrono::Utc::now().timestamp_millis();
+        let slug: String = name
+            .chars()
+            .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+            .collect::<String>()
+            .to_lowercase();
+        let provider = Provider::with_id(
+            format!("{slug}-{timestamp}"),
+            name.to_string(),
+            settings_config,
+            Some(vendor.console_url().to_string()),
+        );
+
+        let app_type = vendor.app_type();
+        ProviderValidator::validate_provider_settings(&app_type, &provider)?;
+        Ok(KeyProviderDraft {
+            app: app_type.as_str().to_string(),
+            vendor,
+            provider,
+        })
+    }
+}
+
+/// 去掉粘贴时常带上的引号与 `Bearer ` 前缀
+fn normalize_key(raw: &str) -> &str {
+    let key = raw.trim().trim_matches(|c| c == '"' || c == '\'').trim();
+    key.strip_prefix("Bearer ").unwrap_or(key).trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_vendor_and_builds_official_provider() {
+        let claude = KeyProviderImporter::draft(" \"sk-ant-api03-abc\" ", None).unwrap();
+        assert_eq!(claude.vendor, ApiKeyVendor::Anthropic);
+        assert_eq!(claude.app, "claude");
+        assert_eq!(
+            claude.provider.settings_config["env"]["ANTHROPIC_API_KEY"],
+            "sk-ant-api03-abc"
+        );
+
+        let codex = KeyProviderImporter::draft("Bearer sk-proj-xyz", Some("Work")).unwrap();
+        assert_eq!(codex.app, "codex");
+        assert_eq!(codex.provider.name, "Work");
+        assert_eq!(
+            codex.provider.settings_config["auth"]["OPENAI_API_KEY"],
+            "sk-proj-xyz"
+        );
+
+        let gemini_key = format!("AIza{}", "x".repeat(35));
+        let gemini = KeyProviderImporter::draft(&gemini_key, None).unwrap();
+        assert_eq!(gemini.vendor, ApiKeyVendor::Google);
+        assert_eq!(gemini.provider.name, "Gemini API");
+
+        assert!(KeyProviderImporter::draft("AIzaShort", None).is_err());
+        assert!(KeyProviderImporter::draft("sk-ant- abc", None).is_err());
+    }
+}
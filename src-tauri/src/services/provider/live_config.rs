@@ -1,21 +1,63 @@
+use serde::Serialize;
 use serde_json::{json, Value};
 
 use crate::app_config::AppType;
 use crate::codex_config::{get_codex_auth_path, get_codex_config_path, write_codex_live_atomic};
 use crate::config::{get_claude_settings_path, read_json_file, write_json_file};
 use crate::error::AppError;
-use crate::provider::Provider;
+use crate::provider::{Provider, SyncScopeMode};
 use crate::services::mcp::McpService;
+use crate::services::sync_pause::is_management_paused;
+use crate::services::{DebugProxyService, WriteAccessService};
 use crate::store::AppState;
 
-use super::claude::ClaudeModelNormalizer;
+use super::claude::{ClaudeFlavorEnv, ClaudeModelNormalizer};
 use super::gemini::GeminiAuthDetector;
 use super::types::GeminiAuthType;
 
 pub struct LiveConfigSync;
 
+/// live 回填时单个字段的变化（None 表示字段不存在）
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveFieldChange {
+    pub path: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+/// 把 live 字段合并回供应商的预览结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveMergePreview {
+    pub changes: Vec<LiveFieldChange>,
+    /// 合并后的 settings_config
+    pub settings_config: Value,
+    /// 是否已写入数据库（预览或无变化时为 false）
+    pub applied: bool,
+}
+
 impl LiveConfigSync {
     pub fn write_live_snapshot(app_type: &AppType, provider: &Provider) -> Result<(), AppError> {
+        if is_management_paused() {
+            log::info!(
+                "Management paused, deferring live write for {} ({})",
+                provider.id,
+                app_type.as_str()
+            );
+            return Ok(());
+        }
+        // 多个文件写入前统一检查，避免只写入一部分
+        WriteAccessService::ensure_app_writable(app_type)?;
+
+        let provider = ClaudeFlavorEnv::with_flavor_env(app_type, provider);
+        let routed = DebugProxyService::route_live(app_type, provider.as_ref())?;
+        let provider = routed.as_ref();
+
+        if let Some(keys) = Self::scoped_keys(app_type, provider) {
+            return Self::write_scoped_snapshot(app_type, provider, &keys);
+        }
+
         match app_type {
             AppType::Claude => {
                 let path = get_claude_settings_path();
@@ -35,8 +77,7 @@ impl LiveConfigSync {
                 let auth_path = get_codex_auth_path();
                 write_json_file(&auth_path, auth)?;
                 let config_path = get_codex_config_path();
-                std::fs::write(&config_path, config_str)
-                    .map_err(|e| AppError::io(&config_path, e))?;
+                crate::config::write_text_file(&config_path, config_str)?;
             }
             AppType::Gemini => {
                 use crate::gemini_config::{
@@ -60,14 +101,187 @@ impl LiveConfigSync {
         Ok(())
     }
 
+    /// 凭证范围对应的字段（相对 settingsConfig 的点分路径）
+    pub(crate) fn credential_keys(app_type: &AppType) -> &'static [&'static str] {
+        match app_type {
+            AppType::Claude => &[
+                "env.ANTHROPIC_AUTH_TOKEN",
+                "env.ANTHROPIC_API_KEY",
+                "env.ANTHROPIC_BASE_URL",
+                "env.CLAUDE_CODE_USE_BEDROCK",
+                "env.CLAUDE_CODE_USE_VERTEX",
+                "env.AWS_REGION",
+                "env.AWS_PROFILE",
+                "env.CLOUD_ML_REGION",
+                "env.ANTHROPIC_VERTEX_PROJECT_ID",
+            ],
+            AppType::Codex => &["auth", "config.model_provider", "config.model_providers"],
+            AppType::Gemini => &[
+                "env.GEMINI_API_KEY",
+                "env.GOOGLE_API_KEY",
+                "env.GOOGLE_GEMINI_BASE_URL",
+            ],
+        }
+    }
+
+    /// 解析供应商的同步范围；返回 None 表示完整覆盖
+    fn scoped_keys(app_type: &AppType, provider: &Provider) -> Option<Vec<String>> {
+        let scope = provider.meta.as_ref()?.sync_scope.as_ref()?;
+        match scope.mode {
+            SyncScopeMode::Full => None,
+            SyncScopeMode::Credentials => Some(
+                Self::credential_keys(app_type)
+                    .iter()
+                    .map(|k| k.to_string())
+                    .collect(),
+            ),
+            SyncScopeMode::Custom => Some(
+                scope
+                    .keys
+                    .iter()
+                    .map(|k| k.trim().to_string())
+                    .filter(|k| !k.is_empty())
+                    .collect(),
+            ),
+        }
+    }
+
+    /// 只把指定字段合并进现有 live 配置，其余内容保持不变
+    ///
+    /// 供应商中不存在的字段会从 live 中移除，避免残留上一个供应商的凭证
+    fn write_scoped_snapshot(
+        app_type: &AppType,
+        provider: &Provider,
+        keys: &[String],
+    ) -> Result<(), AppError> {
+        match app_type {
+            AppType::Claude => {
+                let path = get_claude_settings_path();
+                let mut live: Value = if path.exists() {
+                    read_json_file(&path)?
+                } else {
+                    json!({})
+                };
+                for key in keys {
+                    let segments: Vec<&str> = key.split('.').collect();
+                    copy_json_path(&provider.settings_config, &mut live, &segments);
+                }
+                write_json_file(&path, &live)?;
+            }
+            AppType::Codex => {
+                let (auth_keys, config_keys) = split_sections(keys, "auth", "config");
+
+                if !auth_keys.is_empty() {
+                    let source = provider
+                        .settings_config
+                        .get("auth")
+                        .cloned()
+                        .unwrap_or_else(|| json!({}));
+                    let auth_path = get_codex_auth_path();
+                    let mut live: Value = if auth_path.exists() {
+                        read_json_file(&auth_path)?
+                    } else {
+                        json!({})
+                    };
+                    for segments in &auth_keys {
+                        copy_json_path(&source, &mut live, segments);
+                    }
+                    write_json_file(&auth_path, &live)?;
+                }
+
+                if !config_keys.is_empty() {
+                    let source_text = provider
+                        .settings_config
+                        .get("config")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default();
+                    let source = parse_toml_doc(source_text)?;
+                    let config_path = get_codex_config_path();
+                    let live_text = if config_path.exists() {
+                        std::fs::read_to_string(&config_path)
+                            .map_err(|e| AppError::io(&config_path, e))?
+                    } else {
+                        String::new()
+                    };
+                    let mut live = parse_toml_doc(&live_text)?;
+                    for segments in &config_keys {
+                        copy_toml_path(source.as_table(), live.as_table_mut(), segments);
+                    }
+                    crate::config::write_text_file(&config_path, &live.to_string())?;
+                }
+            }
+            AppType::Gemini => {
+                use crate::gemini_config::{
+                    get_gemini_settings_path, json_to_env, read_gemini_env, write_gemini_env_atomic,
+                };
+
+                let (env_keys, config_keys) = split_sections(keys, "env", "config");
+
+                if !env_keys.is_empty() {
+                    let source = json_to_env(&provider.settings_config)?;
+                    let mut live = read_gemini_env()?;
+                    for segments in &env_keys {
+                        match segments.as_slice() {
+                            [] => live = source.clone(),
+                            [name] => match source.get(*name) {
+                                Some(value) => {
+                                    live.insert(name.to_string(), value.clone());
+                                }
+                                None => {
+                                    live.remove(*name);
+                                }
+                            },
+                            _ => {
+                                log::warn!("忽略无效的 Gemini env 同步字段: {}", segments.join("."))
+                            }
+                        }
+                    }
+                    write_gemini_env_atomic(&live)?;
+                }
+
+                if !config_keys.is_empty() {
+                    let source = provider
+                        .settings_config
+                        .get("config")
+                        .cloned()
+                        .unwrap_or_else(|| json!({}));
+                    let settings_path = get_gemini_settings_path();
+                    let mut live: Value = if settings_path.exists() {
+                        read_json_file(&settings_path)?
+                    } else {
+                        json!({})
+                    };
+                    for segments in &config_keys {
+                        copy_json_path(&source, &mut live, segments);
+                    }
+                    write_json_file(&settings_path, &live)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 把 live 中选定字段复制进 settings_config，返回合并结果与有变化的字段
+    ///
+    /// 路径格式与同步范围一致；Codex 的 `config.*` 按 TOML 键合并，保留原有格式与注释
+    pub fn merge_live_paths(
+        app_type: &AppType,
+        settings: &Value,
+        paths: &[String],
+    ) -> Result<(Value, Vec<LiveFieldChange>), AppError> {
+        let mut live = Self::read_live_settings(app_type.clone())?;
+        DebugProxyService::restore_upstream(app_type, &mut live);
+        merge_paths(app_type, &live, settings, paths)
+    }
+
     /// Sync current provider from database to live config
     pub fn sync_current_from_db(state: &AppState) -> Result<(), AppError> {
         for app_type in [AppType::Claude, AppType::Codex, AppType::Gemini] {
-            let current_id = match state.db.get_current_provider(app_type.as_str())? {
+            let current_id = match state.storage.get_current_provider(app_type.as_str())? {
                 Some(id) => id,
                 None => continue,
             };
-            let providers = state.db.get_all_providers(app_type.as_str())?;
+            let providers = state.storage.get_all_providers(app_type.as_str())?;
             if let Some(provider) = providers.get(&current_id) {
                 Self::write_live_snapshot(&app_type, provider)?;
             } else {
@@ -80,6 +294,7 @@ impl LiveConfigSync {
         }
 
         McpService::sync_all_enabled(state)?;
+        super::ProviderService::sync_claude_overlays(state, &AppType::Claude);
         Ok(())
     }
 
@@ -179,6 +394,10 @@ impl LiveConfigSync {
             write_gemini_env_atomic,
         };
 
+        if is_management_paused() {
+            return Ok(());
+        }
+
         let auth_type = GeminiAuthDetector::detect_gemini_auth_type(provider);
 
         let mut env_map = json_to_env(&provider.settings_config)?;
@@ -240,3 +459,310 @@ impl LiveConfigSync {
         Ok(())
     }
 }
+
+/// 按首段拆分字段路径，例如 `auth.OPENAI_API_KEY` -> (auth, [OPENAI_API_KEY])
+fn split_sections<'a>(
+    keys: &'a [String],
+    first: &str,
+    second: &str,
+) -> (Vec<Vec<&'a str>>, Vec<Vec<&'a str>>) {
+    let mut first_keys = Vec::new();
+    let mut second_keys = Vec::new();
+    for key in keys {
+        let mut segments = key.split('.');
+        let section = segments.next().unwrap_or_default();
+        let rest: Vec<&str> = segments.collect();
+        if section == first {
+            first_keys.push(rest);
+        } else if section == second {
+            second_keys.push(rest);
+        } else {
+            log::warn!("忽略无效的同步字段: {key}");
+        }
+    }
+    (first_keys, second_keys)
+}
+
+/// 把 source 中的路径复制到 target；source 中不存在则从 target 删除，空路径表示整体替换
+fn copy_json_path(source: &Value, target: &mut Value, segments: &[&str]) {
+    let value = segments
+        .iter()
+        .try_fold(source, |current, segment| current.get(*segment))
+        .cloned();
+
+    let Some((last, parents)) = segments.split_last() else {
+        if let Some(value) = value {
+            *target = value;
+        }
+        return;
+    };
+
+    let mut cursor = target;
+    for segment in parents {
+        if value.is_none() && cursor.get(*segment).is_none() {
+            return;
+        }
+        if !cursor.is_object() {
+            *cursor = json!({});
+        }
+        let Some(obj) = cursor.as_object_mut() else {
+            return;
+        };
+        cursor = obj.entry(segment.to_string()).or_insert_with(|| json!({}));
+    }
+
+    if !cursor.is_object() {
+        if value.is_none() {
+            return;
+        }
+        *cursor = json!({});
+    }
+    if let Some(obj) = cursor.as_object_mut() {
+        match value {
+            Some(value) => {
+                obj.insert(last.to_string(), value);
+            }
+            None => {
+                obj.remove(*last);
+            }
+        }
+    }
+}
+
+fn merge_paths(
+    app_type: &AppType,
+    live: &Value,
+    settings: &Value,
+    paths: &[String],
+) -> Result<(Value, Vec<LiveFieldChange>), AppError> {
+    let paths: Vec<String> = paths
+        .iter()
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect();
+    if paths.is_empty() {
+        return Err(AppError::localized(
+            "provider.live_merge.empty",
+            "请至少选择一个要回填的字段",
+            "Select at least one field to pull from the live config",
+        ));
+    }
+    let sections: &[&str] = match app_type {
+        AppType::Claude => &[],
+        AppType::Codex => &["auth", "config"],
+        AppType::Gemini => &["env", "config"],
+    };
+    if let Some(path) = paths.iter().find(|p| {
+        !sections.is_empty() && !sections.contains(&p.split('.').next().unwrap_or_default())
+    }) {
+        return Err(AppError::localized(
+            "provider.live_merge.invalid_path",
+            format!("字段 {path} 必须以 {} 开头", sections.join(" / ")),
+            format!("Field {path} must start with {}", sections.join(" / ")),
+        ));
+    }
+
+    let mut merged = if settings.is_object() {
+        settings.clone()
+    } else {
+        json!({})
+    };
+    match app_type {
+        AppType::Codex => {
+            let (auth_keys, config_keys) = split_sections(&paths, "auth", "config");
+            let live_auth = live.get("auth").cloned().unwrap_or_else(|| json!({}));
+            for segments in &auth_keys {
+                let target = merged
+                    .as_object_mut()
+                    .map(|obj| obj.entry("auth").or_insert_with(|| json!({})));
+                if let Some(target) = target {
+                    copy_json_path(&live_auth, target, segments);
+                }
+            }
+            if !config_keys.is_empty() {
+                let source = parse_toml_doc(
+                    live.get("config")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default(),
+                )?;
+                let mut target = parse_toml_doc(
+                    merged
+                        .get("config")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default(),
+                )?;
+                for segments in &config_keys {
+                    copy_toml_path(source.as_table(), target.as_table_mut(), segments);
+                }
+                merged["config"] = Value::String(target.to_string());
+            }
+        }
+        AppType::Claude | AppType::Gemini => {
+            for path in &paths {
+                let segments: Vec<&str> = path.split('.').collect();
+                copy_json_path(live, &mut merged, &segments);
+            }
+        }
+    }
+
+    let changes = paths
+        .iter()
+        .filter_map(|path| {
+            let before = field_value(app_type, settings, path);
+            let after = field_value(app_type, &merged, path);
+            (before != after).then(|| LiveFieldChange {
+                path: path.clone(),
+                before,
+                after,
+            })
+        })
+        .collect();
+    Ok((merged, changes))
+}
+
+/// 读取字段当前值用于差异展示，Codex 的 config 先按 TOML 解析
+fn field_value(app_type: &AppType, settings: &Value, path: &str) -> Option<Value> {
+    let mut segments = path.split('.');
+    if matches!(app_type, AppType::Codex) && path.split('.').next() == Some("config") {
+        segments.next();
+        let text = settings.get("config")?.as_str()?;
+        let table = text.parse::<toml::Table>().ok()?;
+        let config = serde_json::to_value(table).ok()?;
+        return segments
+            .try_fold(&config, |current, segment| current.get(segment))
+            .cloned();
+    }
+    segments
+        .try_fold(settings, |current, segment| current.get(segment))
+        .cloned()
+}
+
+fn parse_toml_doc(text: &str) -> Result<toml_edit::DocumentMut, AppError> {
+    if text.trim().is_empty() {
+        return Ok(toml_edit::DocumentMut::new());
+    }
+    text.parse::<toml_edit::DocumentMut>()
+        .map_err(|e| AppError::Config(format!("解析 Codex config.toml 失败: {e}")))
+}
+
+/// TOML 版本的 copy_json_path（仅支持标准表嵌套）
+fn copy_toml_path(source: &toml_edit::Table, target: &mut toml_edit::Table, segments: &[&str]) {
+    let Some((first, rest)) = segments.split_first() else {
+        *target = source.clone();
+        return;
+    };
+
+    if rest.is_empty() {
+        match source.get(first) {
+            Some(item) => {
+                target.insert(first, item.clone());
+            }
+            None => {
+                target.remove(first);
+            }
+        }
+        return;
+    }
+
+    let empty = toml_edit::Table::new();
+    let child_source = source
+        .get(first)
+        .and_then(|item| item.as_table())
+        .unwrap_or(&empty);
+    if child_source.is_empty() && !target.contains_key(first) {
+        return;
+    }
+    let child_target = target
+        .entry(first)
+        .or_insert_with(toml_edit::table)
+        .as_table_mut();
+    if let Some(child_target) = child_target {
+        copy_toml_path(child_source, child_target, rest);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copy_json_path_sets_and_removes_nested_keys() {
+        let source = json!({ "env": { "ANTHROPIC_AUTH_TOKEN": "new" } });
+        let mut target = json!({
+            "env": { "ANTHROPIC_API_KEY": "old", "ANTHROPIC_MODEL": "keep" },
+            "permissions": { "allow": [] }
+        });
+
+        copy_json_path(&source, &mut target, &["env", "ANTHROPIC_AUTH_TOKEN"]);
+        copy_json_path(&source, &mut target, &["env", "ANTHROPIC_API_KEY"]);
+        copy_json_path(&source, &mut target, &["missing", "KEY"]);
+
+        assert_eq!(
+            target,
+            json!({
+                "env": { "ANTHROPIC_AUTH_TOKEN": "new", "ANTHROPIC_MODEL": "keep" },
+                "permissions": { "allow": [] }
+            })
+        );
+    }
+
+    #[test]
+    fn merge_paths_pulls_selected_live_fields_only() {
+        let settings = json!({
+            "auth": { "OPENAI_API_KEY": "sk-1" },
+            "config": "model = \"gpt-5\"\nmodel_reasoning_effort = \"low\"\n"
+        });
+        let live = json!({
+            "auth": { "OPENAI_API_KEY": "sk-2" },
+            "config": "model = \"o3\"\nmodel_reasoning_effort = \"high\"\n"
+        });
+
+        let (merged, changes) = merge_paths(
+            &AppType::Codex,
+            &live,
+            &settings,
+            &["config.model_reasoning_effort".to_string()],
+        )
+        .unwrap();
+
+        assert_eq!(merged["auth"]["OPENAI_API_KEY"], "sk-1");
+        let config = merged["config"].as_str().unwrap();
+        assert!(config.contains("model = \"gpt-5\""));
+        assert!(config.contains("model_reasoning_effort = \"high\""));
+        assert_eq!(
+            changes,
+            vec![LiveFieldChange {
+                path: "config.model_reasoning_effort".into(),
+                before: Some(json!("low")),
+                after: Some(json!("high")),
+            }]
+        );
+
+        assert!(merge_paths(&AppType::Codex, &live, &settings, &["env.X".into()]).is_err());
+    }
+
+    #[test]
+    fn copy_toml_path_keeps_unrelated_keys() {
+        let source = parse_toml_doc(
+            "model_provider = \"relay\"\nmodel = \"gpt-5\"\n\n[model_providers.relay]\nbase_url = \"https://relay.example\"\n",
+        )
+        .unwrap();
+        let mut target = parse_toml_doc(
+            "model_provider = \"old\"\nmodel = \"o3\"\n\n[mcp_servers.fetch]\ncommand = \"uvx\"\n",
+        )
+        .unwrap();
+
+        for key in LiveConfigSync::credential_keys(&AppType::Codex) {
+            let segments: Vec<&str> = key.split('.').skip(1).collect();
+            if key.starts_with("config") {
+                copy_toml_path(source.as_table(), target.as_table_mut(), &segments);
+            }
+        }
+
+        let text = target.to_string();
+        assert!(text.contains("model_provider = \"relay\""));
+        assert!(text.contains("model = \"o3\""));
+        assert!(text.contains("base_url = \"https://relay.example\""));
+        assert!(text.contains("[mcp_servers.fetch]"));
+    }
+}
@@ -0,0 +1,105 @@
+use indexmap::IndexMap;
+use serde::Serialize;
+
+use crate::error::AppError;
+use crate::provider::Provider;
+
+/// 幂等切换的结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwitchOutcome {
+    pub app: String,
+    pub provider_id: String,
+    pub provider_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_id: Option<String>,
+    /// 目标已是当前供应商时为 false，此时不会重写任何配置
+    pub changed: bool,
+}
+
+pub struct ProviderLookup;
+
+impl ProviderLookup {
+    /// 按 ID 或名称查找供应商
+    ///
+    /// 依次尝试：ID 精确匹配、名称精确匹配、名称忽略大小写匹配、名称唯一前缀匹配（忽略大小写）。
+    /// 多个候选时报错并列出候选名称
+    pub fn resolve<'a>(
+        providers: &'a IndexMap<String, Provider>,
+        query: &str,
+    ) -> Result<&'a Provider, AppError> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Err(AppError::InvalidInput("供应商名称不能为空".to_string()));
+        }
+        if let Some(provider) = providers.get(query) {
+            return Ok(provider);
+        }
+
+        let lower = query.to_lowercase();
+        let passes: [&dyn Fn(&Provider) -> bool; 3] = [
+            &|p: &Provider| p.name == query,
+            &|p: &Provider| p.name.to_lowercase() == lower,
+            &|p: &Provider| p.name.to_lowercase().starts_with(&lower),
+        ];
+        for matches in passes {
+            let candidates: Vec<&Provider> = providers.values().filter(|p| matches(p)).collect();
+            match candidates.as_slice() {
+                [] => continue,
+                [single] => return Ok(single),
+                many => {
+                    let names: Vec<&str> = many.iter().map(|p| p.name.as_str()).collect();
+                    return Err(AppError::localized(
+                        "provider.lookup_ambiguous",
+                        format!("名称 '{query}' 匹配到多个供应商: {}", names.join(", ")),
+                        format!(
+                            "'{query}' matches more than one provider: {}",
+                            names.join(", ")
+                        ),
+                    ));
+                }
+            }
+        }
+
+        Err(AppError::localized(
+            "provider.lookup_not_found",
+            format!("未找到名称为 '{query}' 的供应商"),
+            format!("No provider named '{query}'"),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn providers(names: &[(&str, &str)]) -> IndexMap<String, Provider> {
+        names
+            .iter()
+            .map(|(id, name)| {
+                (
+                    id.to_string(),
+                    Provider::with_id(id.to_string(), name.to_string(), json!({}), None),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn resolves_by_id_name_and_unique_prefix() {
+        let all = providers(&[
+            ("a1", "OpenRouter"),
+            ("a2", "Open Relay"),
+            ("a3", "Kimi"),
+            ("a4", "kimi"),
+        ]);
+
+        assert_eq!(ProviderLookup::resolve(&all, "a3").unwrap().id, "a3");
+        assert_eq!(ProviderLookup::resolve(&all, "kimi").unwrap().id, "a4");
+        assert_eq!(ProviderLookup::resolve(&all, "openr").unwrap().id, "a1");
+        assert!(ProviderLookup::resolve(&all, "KIMI").is_err());
+        assert!(ProviderLookup::resolve(&all, "open").is_err());
+        assert!(ProviderLookup::resolve(&all, "missing").is_err());
+    }
+}
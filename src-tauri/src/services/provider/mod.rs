@@ -6,15 +6,34 @@ mod endpoints;
 mod usage;
 mod validation;
 mod credentials;
+mod sort;
+mod schema;
+mod codex;
+mod switch_checks;
+mod lookup;
+mod key_import;
+mod switch_preview;
 
 pub use types::ProviderSortUpdate;
 pub use gemini::GeminiAuthDetector;
-pub use claude::ClaudeModelNormalizer;
-pub use live_config::LiveConfigSync;
-pub use endpoints::EndpointManager;
+pub use claude::{ClaudeFlavorEnv, ClaudeModelNormalizer};
+pub use live_config::{LiveConfigSync, LiveFieldChange, LiveMergePreview};
+pub use endpoints::{EndpointImportResult, EndpointManager};
 pub use usage::UsageQueryExecutor;
 pub use validation::ProviderValidator;
 pub use credentials::CredentialsExtractor;
+pub use sort::ProviderSorter;
+pub use schema::{ProviderSchema, ProviderSchemaDescriber};
+pub use codex::{CodexLoginAuth, CodexLoginStatus};
+pub use lookup::{ProviderLookup, SwitchOutcome};
+pub use key_import::{ApiKeyVendor, KeyProviderDraft, KeyProviderImporter};
+pub use switch_preview::{
+    DiffHunk, DiffLine, DiffLineKind, FileChangeStatus, FileDiff, SwitchPreview,
+};
+pub use switch_checks::{
+    probe_health, SwitchCheck, SwitchCheckKind, SwitchCheckResult, SwitchCheckStatus,
+    SwitchContext, SwitchPipeline, SwitchValidation,
+};
 
 use indexmap::IndexMap;
 use serde_json::{json, Value};
@@ -23,9 +42,17 @@ use crate::app_config::AppType;
 use crate::codex_config::get_codex_auth_path;
 use crate::config::{get_claude_settings_path, read_json_file};
 use crate::error::AppError;
-use crate::provider::{Provider, UsageResult};
+use crate::gemini_config::{apply_model_routing, read_model_routing, GeminiModelRouting};
+use crate::provider::{Provider, ProviderLatency, ProviderOperation, UsageResult};
+use crate::services::access_window::AccessWindowService;
+use crate::services::claude_hooks::ClaudeHookService;
+use crate::services::claude_permissions::ClaudePermissionService;
 use crate::services::mcp::McpService;
-use crate::settings::CustomEndpoint;
+use crate::services::provider_errors::ProviderErrorService;
+use crate::services::provider_upgrade::ProviderUpgradeService;
+use crate::services::relay_directory::RelayDirectoryService;
+use crate::services::speedtest::EndpointLatency;
+use crate::settings::{get_provider_sort_mode, CustomEndpoint};
 use crate::store::AppState;
 
 pub struct ProviderService;
@@ -39,32 +66,105 @@ impl ProviderService {
         state: &AppState,
         app_type: AppType,
     ) -> Result<IndexMap<String, Provider>, AppError> {
-        state.db.get_all_providers(app_type.as_str())
+        let providers = state.storage.get_all_providers(app_type.as_str())?;
+        Ok(ProviderSorter::sort(providers, get_provider_sort_mode(&app_type)))
     }
 
     pub fn current(state: &AppState, app_type: AppType) -> Result<String, AppError> {
         state
-            .db
+            .storage
             .get_current_provider(app_type.as_str())
             .map(|opt| opt.unwrap_or_default())
     }
 
     pub fn add(state: &AppState, app_type: AppType, provider: Provider) -> Result<bool, AppError> {
         let mut provider = provider;
-        ClaudeModelNormalizer::normalize_provider_if_claude(&app_type, &mut provider);
-        ProviderValidator::validate_provider_settings(&app_type, &provider)?;
+        Self::prepare_new(state, &app_type, &mut provider)?;
 
-        state.db.save_provider(app_type.as_str(), &provider)?;
+        state.storage.save_provider(app_type.as_str(), &provider)?;
+        Self::adopt_if_no_current(state, &app_type, &provider)?;
 
-        let current = state.db.get_current_provider(app_type.as_str())?;
-        if current.is_none() {
-            state
-                .db
-                .set_current_provider(app_type.as_str(), &provider.id)?;
-            LiveConfigSync::write_live_snapshot(&app_type, &provider)?;
+        Ok(true)
+    }
+
+    /// 批量新增：逐个规范化，再在同一事务中写入
+    ///
+    /// 返回与输入顺序一致的逐项结果；校验失败的项不写入，事务失败时全部标记为失败。
+    /// 某个应用此前没有当前供应商时，与 [`Self::add`] 一样以本批次第一个作为当前供应商。
+    pub fn add_batch(
+        state: &AppState,
+        providers: Vec<(AppType, Provider)>,
+    ) -> Vec<Result<(), AppError>> {
+        let mut results = Vec::with_capacity(providers.len());
+        let mut prepared = Vec::new();
+        let mut prepared_idx = Vec::new();
+        for (idx, (app_type, mut provider)) in providers.into_iter().enumerate() {
+            match Self::prepare_new(state, &app_type, &mut provider) {
+                Ok(()) => {
+                    prepared.push((app_type, provider));
+                    prepared_idx.push(idx);
+                    results.push(Ok(()));
+                }
+                Err(e) => results.push(Err(e)),
+            }
+        }
+        if prepared.is_empty() {
+            return results;
         }
 
-        Ok(true)
+        let rows: Vec<(String, Provider)> = prepared
+            .iter()
+            .map(|(app_type, provider)| (app_type.as_str().to_string(), provider.clone()))
+            .collect();
+        if let Err(e) = state.storage.save_providers(&rows) {
+            let message = e.to_string();
+            for idx in prepared_idx {
+                results[idx] = Err(AppError::Message(message.clone()));
+            }
+            return results;
+        }
+
+        for (app_type, provider) in &prepared {
+            if let Err(e) = Self::adopt_if_no_current(state, app_type, provider) {
+                // 供应商已写入，仅当前供应商未能设置，记录日志即可
+                log::warn!("设置 {} 的当前供应商失败: {e}", app_type.as_str());
+            }
+        }
+        results
+    }
+
+    /// 应用还没有当前供应商时，将新增的供应商设为当前并写入 live 配置
+    fn adopt_if_no_current(
+        state: &AppState,
+        app_type: &AppType,
+        provider: &Provider,
+    ) -> Result<(), AppError> {
+        if state.storage.get_current_provider(app_type.as_str())?.is_some() {
+            return Ok(());
+        }
+        state
+            .storage
+            .set_current_provider(app_type.as_str(), &provider.id)?;
+        LiveConfigSync::write_live_snapshot(app_type, provider)
+    }
+
+    /// 新增前的统一处理：升级旧字段、清除管理标记、补全目录信息并校验配置
+    fn prepare_new(
+        state: &AppState,
+        app_type: &AppType,
+        provider: &mut Provider,
+    ) -> Result<(), AppError> {
+        ProviderUpgradeService::apply_latest(app_type, provider);
+        CodexLoginAuth::normalize(app_type, provider);
+        // 组织管理标记只能通过管理员导入设置
+        if let Some(meta) = provider.meta.as_mut() {
+            meta.managed_by = None;
+        }
+        // 按端点域名补全官网、图标等缺失信息，匹配失败不影响添加
+        if let Err(e) = RelayDirectoryService::enrich(state, app_type, provider) {
+            log::debug!("匹配中转目录失败: {e}");
+        }
+        ProviderValidator::validate_provider_settings(app_type, provider)
     }
 
     pub fn update(
@@ -73,25 +173,90 @@ impl ProviderService {
         provider: Provider,
     ) -> Result<bool, AppError> {
         let mut provider = provider;
-        ClaudeModelNormalizer::normalize_provider_if_claude(&app_type, &mut provider);
+        Self::ensure_not_managed(state, &app_type, &provider.id)?;
+        if let Some(meta) = provider.meta.as_mut() {
+            meta.managed_by = None;
+        }
+        ProviderUpgradeService::apply_latest(&app_type, &mut provider);
+        CodexLoginAuth::normalize(&app_type, &mut provider);
         ProviderValidator::validate_provider_settings(&app_type, &provider)?;
 
-        let current_id = state.db.get_current_provider(app_type.as_str())?;
+        let current_id = state.storage.get_current_provider(app_type.as_str())?;
         let is_current = current_id.as_deref() == Some(provider.id.as_str());
 
-        state.db.save_provider(app_type.as_str(), &provider)?;
+        // 携带版本号时校验是否已被其他窗口修改，冲突则整体放弃本次保存
+        state.storage.save_provider_checked(app_type.as_str(), &provider)?;
 
         if is_current {
             LiveConfigSync::write_live_snapshot(&app_type, &provider)?;
             McpService::sync_all_enabled(state)?;
+            Self::sync_claude_overlays(state, &app_type);
         }
 
         Ok(true)
     }
 
+    /// 读取 Gemini 供应商的模型路由
+    pub fn get_gemini_model_routing(
+        state: &AppState,
+        provider_id: &str,
+    ) -> Result<GeminiModelRouting, AppError> {
+        let provider = Self::gemini_provider(state, provider_id)?;
+        match provider.settings_config.get("config") {
+            Some(config) => read_model_routing(config),
+            None => Ok(GeminiModelRouting::default()),
+        }
+    }
+
+    /// 设置 Gemini 供应商的默认/回退/按任务模型；当前供应商会立即写入 settings.json
+    ///
+    /// 供应商尚无 config 时以现有 settings.json 为基础，避免切换后丢失用户的其他设置
+    pub fn set_gemini_model_routing(
+        state: &AppState,
+        provider_id: &str,
+        routing: &GeminiModelRouting,
+    ) -> Result<Provider, AppError> {
+        Self::ensure_not_managed(state, &AppType::Gemini, provider_id)?;
+        let mut provider = Self::gemini_provider(state, provider_id)?;
+        let Some(settings) = provider.settings_config.as_object_mut() else {
+            return Err(AppError::localized(
+                "gemini.validation.invalid_settings",
+                "Gemini 供应商配置必须是 JSON 对象",
+                "Gemini provider settings must be a JSON object",
+            ));
+        };
+        let config = settings.entry("config").or_insert(Value::Null);
+        if config.is_null() {
+            let live_path = crate::gemini_config::get_gemini_settings_path();
+            *config = if live_path.exists() {
+                read_json_file(&live_path)?
+            } else {
+                json!({})
+            };
+        }
+        apply_model_routing(config, routing)?;
+
+        Self::update(state, AppType::Gemini, provider.clone())?;
+        Ok(provider)
+    }
+
+    fn gemini_provider(state: &AppState, provider_id: &str) -> Result<Provider, AppError> {
+        state
+            .storage
+            .get_all_providers(AppType::Gemini.as_str())?
+            .shift_remove(provider_id)
+            .ok_or_else(|| {
+                AppError::localized(
+                    "provider.not_found",
+                    format!("供应商不存在: {provider_id}"),
+                    format!("Provider not found: {provider_id}"),
+                )
+            })
+    }
+
     pub fn import_default_config(state: &AppState, app_type: AppType) -> Result<(), AppError> {
         {
-            let providers = state.db.get_all_providers(app_type.as_str())?;
+            let providers = state.storage.get_all_providers(app_type.as_str())?;
             if !providers.is_empty() {
                 return Ok(());
             }
@@ -164,9 +329,9 @@ impl ProviderService {
         );
         provider.category = Some("custom".to_string());
 
-        state.db.save_provider(app_type.as_str(), &provider)?;
+        state.storage.save_provider(app_type.as_str(), &provider)?;
         state
-            .db
+            .storage
             .set_current_provider(app_type.as_str(), &provider.id)?;
 
         Ok(())
@@ -202,6 +367,23 @@ impl ProviderService {
         EndpointManager::remove_custom_endpoint(state, app_type, provider_id, url)
     }
 
+    pub fn export_custom_endpoints(
+        state: &AppState,
+        app_type: AppType,
+        provider_id: &str,
+    ) -> Result<String, AppError> {
+        EndpointManager::export_custom_endpoints(state, app_type, provider_id)
+    }
+
+    pub fn import_custom_endpoints(
+        state: &AppState,
+        app_type: AppType,
+        provider_id: &str,
+        input: &str,
+    ) -> Result<EndpointImportResult, AppError> {
+        EndpointManager::import_custom_endpoints(state, app_type, provider_id, input)
+    }
+
     pub fn update_endpoint_last_used(
         state: &AppState,
         app_type: AppType,
@@ -216,24 +398,72 @@ impl ProviderService {
         app_type: AppType,
         updates: Vec<ProviderSortUpdate>,
     ) -> Result<bool, AppError> {
-        let mut providers = state.db.get_all_providers(app_type.as_str())?;
+        let mut providers = state.storage.get_all_providers(app_type.as_str())?;
 
         for update in updates {
             if let Some(provider) = providers.get_mut(&update.id) {
                 provider.sort_index = Some(update.sort_index);
-                state.db.save_provider(app_type.as_str(), provider)?;
+                state.storage.save_provider(app_type.as_str(), provider)?;
             }
         }
 
         Ok(true)
     }
 
+    /// Record the fastest reachable endpoint from a speed test on the provider
+    pub fn record_latency(
+        state: &AppState,
+        app_type: AppType,
+        provider_id: &str,
+        results: &[EndpointLatency],
+    ) -> Result<(), AppError> {
+        let Some(best) = results
+            .iter()
+            .filter(|r| r.error.is_none())
+            .filter_map(|r| r.latency)
+            .min()
+        else {
+            return Ok(());
+        };
+
+        let providers = state.storage.get_all_providers(app_type.as_str())?;
+        let Some(provider) = providers.get(provider_id) else {
+            return Ok(());
+        };
+        let mut updated = provider.clone();
+        updated.meta.get_or_insert_with(Default::default).latency = Some(ProviderLatency {
+            latency_ms: u64::try_from(best).unwrap_or(u64::MAX),
+            tested_at: chrono::Utc::now().timestamp(),
+        });
+        state.storage.save_provider(app_type.as_str(), &updated)
+    }
+
     pub async fn query_usage(
         state: &AppState,
         app_type: AppType,
         provider_id: &str,
     ) -> Result<UsageResult, AppError> {
-        UsageQueryExecutor::query_usage(state, app_type, provider_id).await
+        let result = UsageQueryExecutor::query_usage(state, app_type.clone(), provider_id).await;
+        let failure = match &result {
+            Ok(usage) if !usage.success => usage.error.clone(),
+            Ok(_) => None,
+            Err(e) => Some(e.to_string()),
+        };
+        if let Some(message) = failure {
+            ProviderErrorService::record(
+                state,
+                &app_type,
+                provider_id,
+                ProviderOperation::Usage,
+                &message,
+            );
+        }
+        result
+    }
+
+    /// 取消供应商正在进行的用量查询
+    pub fn cancel_usage_query(provider_id: &str) -> bool {
+        UsageQueryExecutor::cancel(provider_id)
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -290,29 +520,179 @@ impl ProviderService {
     }
 
     pub fn delete(state: &AppState, app_type: AppType, id: &str) -> Result<(), AppError> {
-        let current = state.db.get_current_provider(app_type.as_str())?;
+        Self::ensure_not_managed(state, &app_type, id)?;
+        let current = state.storage.get_current_provider(app_type.as_str())?;
         if current.as_deref() == Some(id) {
             return Err(AppError::Message(
                 "无法删除当前正在使用的供应商".to_string(),
             ));
         }
-        state.db.delete_provider(app_type.as_str(), id)
+        state.storage.delete_provider(app_type.as_str(), id)
+    }
+
+    /// 组织管理的供应商只能切换，编辑和删除都返回 PROVIDER_MANAGED
+    fn ensure_not_managed(state: &AppState, app_type: &AppType, id: &str) -> Result<(), AppError> {
+        let providers = state.storage.get_all_providers(app_type.as_str())?;
+        let org = providers
+            .get(id)
+            .and_then(|p| p.meta.as_ref())
+            .and_then(|m| m.managed_by.as_deref());
+        match org {
+            Some(org) => Err(AppError::provider_managed(app_type.as_str(), id, org)),
+            None => Ok(()),
+        }
+    }
+
+    /// 将 live 中选定字段合并回已保存的供应商，dry_run 时只返回差异
+    pub fn merge_live_into_provider(
+        state: &AppState,
+        app_type: AppType,
+        id: &str,
+        paths: &[String],
+        dry_run: bool,
+    ) -> Result<LiveMergePreview, AppError> {
+        let mut provider = state
+            .storage
+            .get_all_providers(app_type.as_str())?
+            .shift_remove(id)
+            .ok_or_else(|| AppError::Message(format!("供应商 {id} 不存在")))?;
+
+        let (merged, changes) =
+            LiveConfigSync::merge_live_paths(&app_type, &provider.settings_config, paths)?;
+        let applied = !dry_run && !changes.is_empty();
+        if applied {
+            if let Some(org) = provider.meta.as_ref().and_then(|m| m.managed_by.as_deref()) {
+                return Err(AppError::provider_managed(app_type.as_str(), id, org));
+            }
+            provider.settings_config = merged.clone();
+            ProviderValidator::validate_provider_settings(&app_type, &provider)?;
+            state.storage.save_provider(app_type.as_str(), &provider)?;
+        }
+
+        Ok(LiveMergePreview {
+            changes,
+            settings_config: merged,
+            applied,
+        })
     }
 
     pub fn switch(state: &AppState, app_type: AppType, id: &str) -> Result<(), AppError> {
-        let providers = state.db.get_all_providers(app_type.as_str())?;
+        Self::switch_with(state, app_type, id, false)
+    }
+
+    /// 切换供应商；force 为 true 时忽略供应商的可用时段。失败会记入供应商的错误历史
+    pub fn switch_with(
+        state: &AppState,
+        app_type: AppType,
+        id: &str,
+        force: bool,
+    ) -> Result<(), AppError> {
+        let result = Self::switch_inner(state, &app_type, id, force);
+        if let Err(e) = &result {
+            ProviderErrorService::record(
+                state,
+                &app_type,
+                id,
+                ProviderOperation::Switch,
+                &e.to_string(),
+            );
+        }
+        result
+    }
+
+    fn switch_inner(
+        state: &AppState,
+        app_type: &AppType,
+        id: &str,
+        force: bool,
+    ) -> Result<(), AppError> {
+        SwitchPipeline::for_switch().enforce(state, app_type, id)?;
+
+        let providers = state.storage.get_all_providers(app_type.as_str())?;
         let provider = providers
             .get(id)
             .ok_or_else(|| AppError::Message(format!("供应商 {id} 不存在")))?;
+        AccessWindowService::ensure_allowed(provider, force)?;
 
-        state.db.set_current_provider(app_type.as_str(), id)?;
+        // 切走 ChatGPT 登录账号前保存 Codex 刷新后的令牌
+        let current = state.storage.get_current_provider(app_type.as_str())?;
+        if matches!(app_type, AppType::Codex) && current.as_deref() != Some(id) {
+            CodexLoginAuth::backfill_current(state)?;
+        }
+
+        state.storage.set_current_provider(app_type.as_str(), id)?;
+        state.storage.record_provider_switch(app_type.as_str(), id)?;
+        crate::services::StatusSummaryService::invalidate();
 
-        LiveConfigSync::write_live_snapshot(&app_type, provider)?;
+        LiveConfigSync::write_live_snapshot(app_type, provider)?;
 
         McpService::sync_all_enabled(state)?;
+        Self::sync_claude_overlays(state, app_type);
 
         Ok(())
     }
+
+    /// 供应商配置写入 settings.json 后补上全局钩子与权限规则；失败不影响本次切换或保存
+    pub(crate) fn sync_claude_overlays(state: &AppState, app_type: &AppType) {
+        if !matches!(app_type, AppType::Claude) {
+            return;
+        }
+        if let Err(e) = ClaudeHookService::sync_live(state) {
+            log::warn!("同步 Claude 全局钩子失败: {e}");
+        }
+        if let Err(e) = ClaudePermissionService::sync_live(state) {
+            log::warn!("同步 Claude 权限规则失败: {e}");
+        }
+    }
+
+    /// 同 [`Self::sync_claude_overlays`]，按给定供应商而非数据库中的当前供应商合并
+    pub(crate) fn sync_claude_overlays_for(
+        state: &AppState,
+        app_type: &AppType,
+        provider: &Provider,
+    ) {
+        if !matches!(app_type, AppType::Claude) {
+            return;
+        }
+        if let Err(e) = ClaudeHookService::sync_live_for(state, &provider.settings_config) {
+            log::warn!("同步 Claude 全局钩子失败: {e}");
+        }
+        if let Err(e) = ClaudePermissionService::sync_live_for(state, &provider.settings_config)
+        {
+            log::warn!("同步 Claude 权限规则失败: {e}");
+        }
+    }
+
+    /// 按 ID 或名称查找供应商，规则见 [`ProviderLookup::resolve`]
+    pub fn find(state: &AppState, app_type: &AppType, query: &str) -> Result<Provider, AppError> {
+        let providers = state.storage.get_all_providers(app_type.as_str())?;
+        ProviderLookup::resolve(&providers, query).cloned()
+    }
+
+    /// 幂等切换：目标已是当前供应商时直接返回，不重写 live 配置
+    pub fn switch_idempotent(
+        state: &AppState,
+        app_type: AppType,
+        id: &str,
+    ) -> Result<SwitchOutcome, AppError> {
+        let providers = state.storage.get_all_providers(app_type.as_str())?;
+        let provider = providers
+            .get(id)
+            .ok_or_else(|| AppError::Message(format!("供应商 {id} 不存在")))?;
+        let previous_id = state.storage.get_current_provider(app_type.as_str())?;
+        let changed = previous_id.as_deref() != Some(id);
+        if changed {
+            Self::switch(state, app_type.clone(), id)?;
+        }
+
+        Ok(SwitchOutcome {
+            app: app_type.as_str().to_string(),
+            provider_id: id.to_string(),
+            provider_name: provider.name.clone(),
+            previous_id,
+            changed,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,323 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::app_config::AppType;
+
+/// Value kind of a settings_config field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SchemaFieldType {
+    Object,
+    String,
+    /// String holding a TOML document (validated by parsing)
+    Toml,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaField {
+    /// Dotted path inside settings_config, e.g. `env.ANTHROPIC_BASE_URL`
+    pub path: &'static str,
+    #[serde(rename = "type")]
+    pub field_type: SchemaFieldType,
+    pub required: bool,
+    /// Condition under which an optional field becomes mandatory
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required_when: Option<&'static str>,
+    pub description: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example: Option<Value>,
+}
+
+/// Machine-readable description of the settings_config an app expects
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderSchema {
+    pub app_type: String,
+    /// settings_config itself must always be a JSON object
+    pub root_type: SchemaFieldType,
+    pub fields: Vec<SchemaField>,
+    /// Top-level sections accepted by a custom sync scope (empty = any key)
+    pub sync_scope_sections: Vec<&'static str>,
+    /// A complete settings_config that passes validation
+    pub example: Value,
+}
+
+pub struct ProviderSchemaDescriber;
+
+impl ProviderSchemaDescriber {
+    /// Describe settings_config for an app. Kept in lockstep with
+    /// `ProviderValidator` and `CredentialsExtractor` by the tests below.
+    pub fn describe(app_type: &AppType) -> ProviderSchema {
+        let (fields, sync_scope_sections, example) = match app_type {
+            AppType::Claude => (
+                Self::claude_fields(),
+                Vec::new(),
+                json!({
+                    "env": {
+                        "ANTHROPIC_AUTH_TOKEN": "sk-xxx",
+                        "ANTHROPIC_BASE_URL": "https://api.example.com"
+                    }
+                }),
+            ),
+            AppType::Codex => (
+                Self::codex_fields(),
+                vec!["auth", "config"],
+                json!({
+                    "auth": { "OPENAI_API_KEY": "sk-xxx" },
+                    "config": "model_provider = \"custom\"\nmodel = \"gpt-5-codex\"\n\n[model_providers.custom]\nname = \"custom\"\nbase_url = \"https://api.example.com/v1\"\nwire_api = \"responses\"\n"
+                }),
+            ),
+            AppType::Gemini => (
+                Self::gemini_fields(),
+                vec!["env", "config"],
+                json!({
+                    "env": {
+                        "GEMINI_API_KEY": "sk-xxx",
+                        "GOOGLE_GEMINI_BASE_URL": "https://api.example.com",
+                        "GEMINI_MODEL": "gemini-2.5-pro"
+                    },
+                    "config": {}
+                }),
+            ),
+        };
+
+        ProviderSchema {
+            app_type: app_type.as_str().to_string(),
+            root_type: SchemaFieldType::Object,
+            fields,
+            sync_scope_sections,
+            example,
+        }
+    }
+
+    fn field(
+        path: &'static str,
+        field_type: SchemaFieldType,
+        required: bool,
+        description: &'static str,
+        example: Option<Value>,
+    ) -> SchemaField {
+        SchemaField {
+            path,
+            field_type,
+            required,
+            required_when: None,
+            description,
+            example,
+        }
+    }
+
+    fn claude_fields() -> Vec<SchemaField> {
+        let mut fields = vec![
+            Self::field(
+                "env",
+                SchemaFieldType::Object,
+                false,
+                "Environment variables written to ~/.claude/settings.json",
+                None,
+            ),
+            Self::field(
+                "env.ANTHROPIC_AUTH_TOKEN",
+                SchemaFieldType::String,
+                false,
+                "API key (preferred over ANTHROPIC_API_KEY)",
+                Some(json!("sk-xxx")),
+            ),
+            Self::field(
+                "env.ANTHROPIC_API_KEY",
+                SchemaFieldType::String,
+                false,
+                "API key used when ANTHROPIC_AUTH_TOKEN is absent",
+                Some(json!("sk-xxx")),
+            ),
+            Self::field(
+                "env.ANTHROPIC_BASE_URL",
+                SchemaFieldType::String,
+                false,
+                "API endpoint; required for usage queries and speed tests",
+                Some(json!("https://api.example.com")),
+            ),
+            Self::field(
+                "env.ANTHROPIC_MODEL",
+                SchemaFieldType::String,
+                false,
+                "Default model",
+                Some(json!("claude-sonnet-4-5")),
+            ),
+            Self::field(
+                "env.ANTHROPIC_DEFAULT_HAIKU_MODEL",
+                SchemaFieldType::String,
+                false,
+                "Haiku-tier model (replaces the legacy ANTHROPIC_SMALL_FAST_MODEL)",
+                None,
+            ),
+            Self::field(
+                "env.ANTHROPIC_DEFAULT_SONNET_MODEL",
+                SchemaFieldType::String,
+                false,
+                "Sonnet-tier model",
+                None,
+            ),
+            Self::field(
+                "env.ANTHROPIC_DEFAULT_OPUS_MODEL",
+                SchemaFieldType::String,
+                false,
+                "Opus-tier model",
+                None,
+            ),
+        ];
+
+        // Bedrock / Vertex access modes may take these from meta or env
+        fields.push(SchemaField {
+            required_when: Some("meta.claudeFlavor.flavor = bedrock"),
+            ..Self::field(
+                "env.AWS_REGION",
+                SchemaFieldType::String,
+                false,
+                "AWS region for Bedrock",
+                Some(json!("us-east-1")),
+            )
+        });
+        fields.push(SchemaField {
+            required_when: Some("meta.claudeFlavor.flavor = vertex"),
+            ..Self::field(
+                "env.CLOUD_ML_REGION",
+                SchemaFieldType::String,
+                false,
+                "Google Cloud region for Vertex AI",
+                Some(json!("us-east5")),
+            )
+        });
+        fields.push(SchemaField {
+            required_when: Some("meta.claudeFlavor.flavor = vertex"),
+            ..Self::field(
+                "env.ANTHROPIC_VERTEX_PROJECT_ID",
+                SchemaFieldType::String,
+                false,
+                "Google Cloud project for Vertex AI",
+                None,
+            )
+        });
+        fields
+    }
+
+    fn codex_fields() -> Vec<SchemaField> {
+        vec![
+            Self::field(
+                "auth",
+                SchemaFieldType::Object,
+                true,
+                "Contents of ~/.codex/auth.json",
+                Some(json!({ "OPENAI_API_KEY": "sk-xxx" })),
+            ),
+            Self::field(
+                "auth.OPENAI_API_KEY",
+                SchemaFieldType::String,
+                false,
+                "API key used for usage queries and speed tests",
+                Some(json!("sk-xxx")),
+            ),
+            Self::field(
+                "config",
+                SchemaFieldType::Toml,
+                false,
+                "Contents of ~/.codex/config.toml; must parse as TOML, base_url is read from it",
+                Some(json!("model = \"gpt-5-codex\"\n")),
+            ),
+        ]
+    }
+
+    fn gemini_fields() -> Vec<SchemaField> {
+        vec![
+            Self::field(
+                "env",
+                SchemaFieldType::Object,
+                false,
+                "Variables written to ~/.gemini/.env; empty means Google OAuth",
+                None,
+            ),
+            SchemaField {
+                required_when: Some("env is not empty (checked on switch)"),
+                ..Self::field(
+                    "env.GEMINI_API_KEY",
+                    SchemaFieldType::String,
+                    false,
+                    "API key",
+                    Some(json!("sk-xxx")),
+                )
+            },
+            Self::field(
+                "env.GOOGLE_GEMINI_BASE_URL",
+                SchemaFieldType::String,
+                false,
+                "API endpoint",
+                Some(json!("https://api.example.com")),
+            ),
+            Self::field(
+                "env.GEMINI_MODEL",
+                SchemaFieldType::String,
+                false,
+                "Default model",
+                Some(json!("gemini-2.5-pro")),
+            ),
+            Self::field(
+                "config",
+                SchemaFieldType::Object,
+                false,
+                "Merged into ~/.gemini/settings.json (object or null)",
+                None,
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::Provider;
+    use crate::services::provider::{CredentialsExtractor, ProviderValidator};
+
+    fn provider(settings: Value) -> Provider {
+        Provider::with_id("p".into(), "P".into(), settings, None)
+    }
+
+    #[test]
+    fn examples_pass_validation_and_expose_credentials() {
+        for app_type in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+            let schema = ProviderSchemaDescriber::describe(&app_type);
+            let p = provider(schema.example.clone());
+            ProviderValidator::validate_provider_settings(&app_type, &p)
+                .unwrap_or_else(|e| panic!("{} example invalid: {e}", schema.app_type));
+            let (api_key, _) = CredentialsExtractor::extract_credentials(&p, &app_type)
+                .unwrap_or_else(|e| panic!("{} example lacks credentials: {e}", schema.app_type));
+            assert_eq!(api_key, "sk-xxx");
+        }
+    }
+
+    #[test]
+    fn removing_required_top_level_fields_fails_validation() {
+        for app_type in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+            let schema = ProviderSchemaDescriber::describe(&app_type);
+            for field in schema.fields.iter().filter(|f| f.required) {
+                let mut settings = schema.example.clone();
+                settings.as_object_mut().unwrap().remove(field.path);
+                assert!(
+                    ProviderValidator::validate_provider_settings(&app_type, &provider(settings))
+                        .is_err(),
+                    "{} should be required",
+                    field.path
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn wrong_field_types_fail_validation() {
+        let codex = provider(json!({ "auth": {}, "config": 1 }));
+        assert!(ProviderValidator::validate_provider_settings(&AppType::Codex, &codex).is_err());
+
+        let gemini = provider(json!({ "env": "x" }));
+        assert!(ProviderValidator::validate_provider_settings(&AppType::Gemini, &gemini).is_err());
+    }
+}
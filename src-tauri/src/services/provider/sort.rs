@@ -0,0 +1,132 @@
+use indexmap::IndexMap;
+use std::cmp::Ordering;
+
+use crate::provider::Provider;
+use crate::settings::ProviderSortMode;
+
+pub struct ProviderSorter;
+
+impl ProviderSorter {
+    /// Reorder providers according to the selected mode.
+    ///
+    /// Input is expected in manual order (as returned by the DB); the sort is
+    /// stable so ties keep their manual position.
+    pub fn sort(
+        mut providers: IndexMap<String, Provider>,
+        mode: ProviderSortMode,
+    ) -> IndexMap<String, Provider> {
+        match mode {
+            ProviderSortMode::Manual => {}
+            ProviderSortMode::RecentlyUsed => providers.sort_by(|_, a, _, b| {
+                descending_some_first(a.last_switched_at, b.last_switched_at)
+            }),
+            ProviderSortMode::Latency => {
+                providers.sort_by(|_, a, _, b| ascending_some_first(latency(a), latency(b)))
+            }
+            ProviderSortMode::Alphabetical => {
+                providers.sort_by(|_, a, _, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+            }
+            ProviderSortMode::Category => providers.sort_by(|_, a, _, b| {
+                ascending_some_first(category(a).as_ref(), category(b).as_ref())
+            }),
+        }
+        providers
+    }
+}
+
+fn latency(provider: &Provider) -> Option<u64> {
+    provider
+        .meta
+        .as_ref()
+        .and_then(|m| m.latency.as_ref())
+        .map(|l| l.latency_ms)
+}
+
+fn category(provider: &Provider) -> Option<String> {
+    provider
+        .category
+        .as_deref()
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// Providers without a value go last
+fn ascending_some_first<T: Ord>(a: Option<T>, b: Option<T>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+fn descending_some_first<T: Ord>(a: Option<T>, b: Option<T>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => b.cmp(&a),
+        (a, b) => ascending_some_first(a, b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::{ProviderLatency, ProviderMeta};
+    use serde_json::json;
+
+    fn provider(
+        id: &str,
+        name: &str,
+        category: Option<&str>,
+        latency_ms: Option<u64>,
+        last_switched_at: Option<i64>,
+    ) -> Provider {
+        let mut provider = Provider::with_id(id.into(), name.into(), json!({}), None);
+        provider.category = category.map(str::to_string);
+        provider.last_switched_at = last_switched_at;
+        provider.meta = Some(ProviderMeta {
+            latency: latency_ms.map(|latency_ms| ProviderLatency {
+                latency_ms,
+                tested_at: 0,
+            }),
+            ..Default::default()
+        });
+        provider
+    }
+
+    fn manual_order() -> IndexMap<String, Provider> {
+        [
+            provider("a", "zeta", Some("third_party"), None, None),
+            provider("b", "Alpha", None, Some(300), Some(20)),
+            provider("c", "beta", Some("official"), Some(120), Some(10)),
+        ]
+        .into_iter()
+        .map(|p| (p.id.clone(), p))
+        .collect()
+    }
+
+    fn ids(providers: &IndexMap<String, Provider>) -> Vec<&str> {
+        providers.keys().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn manual_mode_keeps_db_order() {
+        let sorted = ProviderSorter::sort(manual_order(), ProviderSortMode::Manual);
+        assert_eq!(ids(&sorted), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn automatic_modes_put_missing_values_last() {
+        let sorted = ProviderSorter::sort(manual_order(), ProviderSortMode::RecentlyUsed);
+        assert_eq!(ids(&sorted), ["b", "c", "a"]);
+
+        let sorted = ProviderSorter::sort(manual_order(), ProviderSortMode::Latency);
+        assert_eq!(ids(&sorted), ["c", "b", "a"]);
+
+        let sorted = ProviderSorter::sort(manual_order(), ProviderSortMode::Alphabetical);
+        assert_eq!(ids(&sorted), ["b", "c", "a"]);
+
+        let sorted = ProviderSorter::sort(manual_order(), ProviderSortMode::Category);
+        assert_eq!(ids(&sorted), ["c", "a", "b"]);
+    }
+}
@@ -0,0 +1,460 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::{CodexAuthMode, Provider};
+use crate::services::access_window::AccessWindowService;
+use crate::services::claude_layers::ClaudeSettingsLayerService;
+use crate::services::credential_probe::{CredentialProbeService, ProbeOutcome};
+use crate::services::local_model::LocalModelService;
+use crate::services::session_log::SessionLogService;
+use crate::store::AppState;
+
+use super::codex::CodexLoginAuth;
+use super::gemini::GeminiAuthDetector;
+use super::types::GeminiAuthType;
+
+/// 最近写入过的会话视为仍在运行
+const ACTIVE_SESSION_WINDOW: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SwitchCheckKind {
+    ProviderExists,
+    Credentials,
+    HealthProbe,
+    SessionGuard,
+    DiskWritable,
+    AccessWindow,
+    SettingsOverride,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SwitchCheckStatus {
+    Passed,
+    /// 可以切换，但需要提示用户
+    Warning,
+    /// 切换会失败或切换后不可用
+    Failed,
+    /// 不适用于该供应商
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwitchCheckResult {
+    pub kind: SwitchCheckKind,
+    pub status: SwitchCheckStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl SwitchCheckResult {
+    fn new(kind: SwitchCheckKind, status: SwitchCheckStatus, message: Option<String>) -> Self {
+        Self {
+            kind,
+            status,
+            message,
+        }
+    }
+
+    fn passed(kind: SwitchCheckKind) -> Self {
+        Self::new(kind, SwitchCheckStatus::Passed, None)
+    }
+}
+
+/// 切换前检查的汇总结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwitchValidation {
+    pub app: String,
+    pub provider_id: String,
+    pub checks: Vec<SwitchCheckResult>,
+    /// 没有任何 Failed 项
+    pub can_switch: bool,
+}
+
+/// 单项检查的上下文；供应商不存在时 provider 为 None
+pub struct SwitchContext<'a> {
+    pub app_type: &'a AppType,
+    pub id: &'a str,
+    pub provider: Option<&'a Provider>,
+}
+
+/// 切换前检查项
+pub trait SwitchCheck: Send + Sync {
+    fn kind(&self) -> SwitchCheckKind;
+    fn run(&self, ctx: &SwitchContext<'_>) -> SwitchCheckResult;
+}
+
+/// 按顺序执行的切换前检查
+pub struct SwitchPipeline {
+    checks: Vec<Box<dyn SwitchCheck>>,
+}
+
+impl SwitchPipeline {
+    /// 供 validate_switch 使用的完整检查
+    pub fn standard() -> Self {
+        Self {
+            checks: vec![
+                Box::new(ProviderExists),
+                Box::new(CredentialsPresent),
+                Box::new(SessionGuard),
+                Box::new(DiskWritable),
+                Box::new(AccessWindowOpen),
+                Box::new(ClaudeLayerOverride),
+            ],
+        }
+    }
+
+    /// 真正切换时强制执行的检查；凭证格式由 live 配置写入时给出更具体的错误
+    pub fn for_switch() -> Self {
+        Self {
+            checks: vec![Box::new(ProviderExists), Box::new(DiskWritable)],
+        }
+    }
+
+    pub fn run(
+        &self,
+        state: &AppState,
+        app_type: &AppType,
+        id: &str,
+    ) -> Result<SwitchValidation, AppError> {
+        let providers = state.storage.get_all_providers(app_type.as_str())?;
+        let ctx = SwitchContext {
+            app_type,
+            id,
+            provider: providers.get(id),
+        };
+        let checks: Vec<SwitchCheckResult> = self.checks.iter().map(|c| c.run(&ctx)).collect();
+        Ok(SwitchValidation {
+            app: app_type.as_str().to_string(),
+            provider_id: id.to_string(),
+            can_switch: !checks.iter().any(|c| c.status == SwitchCheckStatus::Failed),
+            checks,
+        })
+    }
+
+    /// 执行检查，遇到 Failed 项时返回对应错误
+    pub fn enforce(&self, state: &AppState, app_type: &AppType, id: &str) -> Result<(), AppError> {
+        let validation = self.run(state, app_type, id)?;
+        match validation
+            .checks
+            .into_iter()
+            .find(|c| c.status == SwitchCheckStatus::Failed)
+        {
+            Some(failed) => {
+                Err(AppError::Message(failed.message.unwrap_or_else(|| {
+                    format!("切换前检查未通过: {:?}", failed.kind)
+                })))
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// 可选的联网健康探测：本地模型检查服务可用，其余供应商用 Key 请求模型列表
+pub async fn probe_health(app_type: &AppType, provider: &Provider) -> SwitchCheckResult {
+    let kind = SwitchCheckKind::HealthProbe;
+    if let Some(local_model) = provider.meta.as_ref().and_then(|m| m.local_model.as_ref()) {
+        return match LocalModelService::check_health(local_model).await {
+            Ok(()) => SwitchCheckResult::passed(kind),
+            Err(e) => SwitchCheckResult::new(kind, SwitchCheckStatus::Failed, Some(e.to_string())),
+        };
+    }
+
+    match CredentialProbeService::probe(app_type, provider).await {
+        ProbeOutcome::Valid => SwitchCheckResult::passed(kind),
+        ProbeOutcome::Invalid(status) => SwitchCheckResult::new(
+            kind,
+            SwitchCheckStatus::Failed,
+            Some(format!("API Key 无效或已过期 (HTTP {status})")),
+        ),
+        ProbeOutcome::Unknown(reason) => {
+            SwitchCheckResult::new(kind, SwitchCheckStatus::Skipped, Some(reason))
+        }
+    }
+}
+
+struct ProviderExists;
+
+impl SwitchCheck for ProviderExists {
+    fn kind(&self) -> SwitchCheckKind {
+        SwitchCheckKind::ProviderExists
+    }
+
+    fn run(&self, ctx: &SwitchContext<'_>) -> SwitchCheckResult {
+        match ctx.provider {
+            Some(_) => SwitchCheckResult::passed(self.kind()),
+            None => SwitchCheckResult::new(
+                self.kind(),
+                SwitchCheckStatus::Failed,
+                Some(format!("供应商 {} 不存在", ctx.id)),
+            ),
+        }
+    }
+}
+
+/// 只检查凭证是否填写，不联网
+struct CredentialsPresent;
+
+impl SwitchCheck for CredentialsPresent {
+    fn kind(&self) -> SwitchCheckKind {
+        SwitchCheckKind::Credentials
+    }
+
+    fn run(&self, ctx: &SwitchContext<'_>) -> SwitchCheckResult {
+        let kind = self.kind();
+        let Some(provider) = ctx.provider else {
+            return SwitchCheckResult::new(kind, SwitchCheckStatus::Skipped, None);
+        };
+        let meta = provider.meta.as_ref();
+        if meta.is_some_and(|m| m.local_model.is_some() || m.claude_flavor.is_some()) {
+            return SwitchCheckResult::new(kind, SwitchCheckStatus::Skipped, None);
+        }
+
+        let non_empty = |pointer: &str| {
+            provider
+                .settings_config
+                .pointer(pointer)
+                .and_then(Value::as_str)
+                .is_some_and(|v| !v.trim().is_empty())
+        };
+
+        match ctx.app_type {
+            AppType::Claude => {
+                if non_empty("/env/ANTHROPIC_AUTH_TOKEN") || non_empty("/env/ANTHROPIC_API_KEY") {
+                    SwitchCheckResult::passed(kind)
+                } else {
+                    SwitchCheckResult::new(
+                        kind,
+                        SwitchCheckStatus::Warning,
+                        Some("未配置 API Key，将沿用 Claude Code 自身的登录状态".to_string()),
+                    )
+                }
+            }
+            AppType::Codex => {
+                if CodexLoginAuth::mode(provider) == CodexAuthMode::ChatgptLogin {
+                    return match CodexLoginAuth::status(provider) {
+                        Some(status) if status.expired && !status.has_refresh_token => {
+                            SwitchCheckResult::new(
+                                kind,
+                                SwitchCheckStatus::Failed,
+                                Some(
+                                    "ChatGPT 登录凭证已过期且无法刷新，请重新登录后捕获"
+                                        .to_string(),
+                                ),
+                            )
+                        }
+                        Some(status) if status.expired => SwitchCheckResult::new(
+                            kind,
+                            SwitchCheckStatus::Warning,
+                            Some("ChatGPT 登录令牌已过期，Codex 将尝试自动刷新".to_string()),
+                        ),
+                        _ => SwitchCheckResult::passed(kind),
+                    };
+                }
+                if non_empty("/auth/OPENAI_API_KEY") {
+                    SwitchCheckResult::passed(kind)
+                } else {
+                    SwitchCheckResult::new(
+                        kind,
+                        SwitchCheckStatus::Failed,
+                        Some("缺少 auth.OPENAI_API_KEY".to_string()),
+                    )
+                }
+            }
+            AppType::Gemini => {
+                if GeminiAuthDetector::detect_gemini_auth_type(provider)
+                    == GeminiAuthType::GoogleOfficial
+                {
+                    SwitchCheckResult::new(kind, SwitchCheckStatus::Skipped, None)
+                } else if non_empty("/env/GEMINI_API_KEY") {
+                    SwitchCheckResult::passed(kind)
+                } else {
+                    SwitchCheckResult::new(
+                        kind,
+                        SwitchCheckStatus::Failed,
+                        Some("缺少 GEMINI_API_KEY".to_string()),
+                    )
+                }
+            }
+        }
+    }
+}
+
+/// 正在运行的 CLI 会话不会重新读取配置，切换后需要重启才生效
+struct SessionGuard;
+
+impl SwitchCheck for SessionGuard {
+    fn kind(&self) -> SwitchCheckKind {
+        SwitchCheckKind::SessionGuard
+    }
+
+    fn run(&self, ctx: &SwitchContext<'_>) -> SwitchCheckResult {
+        if !matches!(ctx.app_type, AppType::Claude | AppType::Codex) {
+            return SwitchCheckResult::new(self.kind(), SwitchCheckStatus::Skipped, None);
+        }
+        match SessionLogService::recently_active(ctx.app_type, ACTIVE_SESSION_WINDOW) {
+            0 => SwitchCheckResult::passed(self.kind()),
+            count => SwitchCheckResult::new(
+                self.kind(),
+                SwitchCheckStatus::Warning,
+                Some(format!(
+                    "{count} 个会话在最近 10 分钟内仍有活动，切换后需重启对应 CLI 才会使用新供应商"
+                )),
+            ),
+        }
+    }
+}
+
+/// live 配置所在目录必须可写
+struct DiskWritable;
+
+impl SwitchCheck for DiskWritable {
+    fn kind(&self) -> SwitchCheckKind {
+        SwitchCheckKind::DiskWritable
+    }
+
+    fn run(&self, ctx: &SwitchContext<'_>) -> SwitchCheckResult {
+        let dir = live_config_dir(ctx.app_type);
+        match check_writable(&dir) {
+            Ok(()) => SwitchCheckResult::passed(self.kind()),
+            Err(reason) => SwitchCheckResult::new(
+                self.kind(),
+                SwitchCheckStatus::Failed,
+                Some(format!("配置目录不可写 {}: {reason}", dir.display())),
+            ),
+        }
+    }
+}
+
+/// 时段外仍可强制切换，因此只给出警告
+struct AccessWindowOpen;
+
+impl SwitchCheck for AccessWindowOpen {
+    fn kind(&self) -> SwitchCheckKind {
+        SwitchCheckKind::AccessWindow
+    }
+
+    fn run(&self, ctx: &SwitchContext<'_>) -> SwitchCheckResult {
+        let Some(provider) = ctx.provider else {
+            return SwitchCheckResult::new(self.kind(), SwitchCheckStatus::Skipped, None);
+        };
+        if provider
+            .meta
+            .as_ref()
+            .and_then(|m| m.access_window.as_ref())
+            .is_none()
+        {
+            return SwitchCheckResult::new(self.kind(), SwitchCheckStatus::Skipped, None);
+        }
+        match AccessWindowService::ensure_allowed(provider, false) {
+            Ok(()) => SwitchCheckResult::passed(self.kind()),
+            Err(e) => {
+                SwitchCheckResult::new(self.kind(), SwitchCheckStatus::Warning, Some(e.to_string()))
+            }
+        }
+    }
+}
+
+/// Claude 的 settings.local.json 或托管策略会覆盖 CLI Hub 写入的同名键
+struct ClaudeLayerOverride;
+
+impl SwitchCheck for ClaudeLayerOverride {
+    fn kind(&self) -> SwitchCheckKind {
+        SwitchCheckKind::SettingsOverride
+    }
+
+    fn run(&self, ctx: &SwitchContext<'_>) -> SwitchCheckResult {
+        let (AppType::Claude, Some(provider)) = (ctx.app_type, ctx.provider) else {
+            return SwitchCheckResult::new(self.kind(), SwitchCheckStatus::Skipped, None);
+        };
+        let overrides = ClaudeSettingsLayerService::shadowed(&provider.settings_config);
+        if overrides.is_empty() {
+            return SwitchCheckResult::passed(self.kind());
+        }
+        let detail: Vec<String> = overrides
+            .iter()
+            .map(|o| format!("{} ({:?})", o.key, o.overridden_by))
+            .collect();
+        SwitchCheckResult::new(
+            self.kind(),
+            SwitchCheckStatus::Warning,
+            Some(format!(
+                "以下配置会被更高优先级的层级覆盖: {}",
+                detail.join(", ")
+            )),
+        )
+    }
+}
+
+fn live_config_dir(app_type: &AppType) -> PathBuf {
+    match app_type {
+        AppType::Claude => crate::config::get_claude_config_dir(),
+        AppType::Codex => crate::codex_config::get_codex_config_dir(),
+        AppType::Gemini => crate::gemini_config::get_gemini_dir(),
+    }
+}
+
+/// 目录存在时实际创建临时文件验证；尚未创建时检查最近的已有上级目录权限
+fn check_writable(dir: &Path) -> Result<(), String> {
+    if dir.is_dir() {
+        return tempfile::NamedTempFile::new_in(dir)
+            .map(|_| ())
+            .map_err(|e| e.to_string());
+    }
+    let Some(existing) = dir.ancestors().find(|p| p.is_dir()) else {
+        return Ok(());
+    };
+    match std::fs::metadata(existing) {
+        Ok(meta) if meta.permissions().readonly() => Err(format!("{} 为只读", existing.display())),
+        Ok(_) => Ok(()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn context<'a>(app_type: &'a AppType, provider: Option<&'a Provider>) -> SwitchContext<'a> {
+        SwitchContext {
+            app_type,
+            id: "p1",
+            provider,
+        }
+    }
+
+    #[test]
+    fn missing_provider_and_credentials_fail() {
+        let result = ProviderExists.run(&context(&AppType::Codex, None));
+        assert_eq!(result.status, SwitchCheckStatus::Failed);
+        assert!(result.message.unwrap().contains("p1"));
+
+        let provider = Provider::with_id(
+            "p1".into(),
+            "Codex".into(),
+            json!({ "auth": {}, "config": "" }),
+            None,
+        );
+        let result = CredentialsPresent.run(&context(&AppType::Codex, Some(&provider)));
+        assert_eq!(result.status, SwitchCheckStatus::Failed);
+
+        let official = Provider::with_id("p1".into(), "Claude".into(), json!({ "env": {} }), None);
+        let result = CredentialsPresent.run(&context(&AppType::Claude, Some(&official)));
+        assert_eq!(result.status, SwitchCheckStatus::Warning);
+    }
+
+    #[test]
+    fn writable_check_accepts_temp_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(check_writable(dir.path()).is_ok());
+        assert!(check_writable(&dir.path().join("not-yet-created")).is_ok());
+    }
+}
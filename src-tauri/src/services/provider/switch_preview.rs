@@ -0,0 +1,303 @@
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+use crate::app_config::AppType;
+use crate::config::{capture_writes, CapturedWrites};
+use crate::error::AppError;
+use crate::log_sanitizer::redact;
+use crate::services::mcp::McpService;
+use crate::store::AppState;
+
+use super::{LiveConfigSync, ProviderService};
+
+/// 每处改动前后保留的上下文行数
+const CONTEXT_LINES: usize = 3;
+/// 去掉首尾相同的行后，两侧行数之积超过该值时不再逐行对齐，整段视为替换
+const MAX_ALIGN_CELLS: usize = 4_000_000;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum FileChangeStatus {
+    Created,
+    Modified,
+    Deleted,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum DiffLineKind {
+    Context,
+    Added,
+    Removed,
+}
+
+/// 差异中的一行，密钥已遮蔽
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    pub text: String,
+}
+
+/// 一段连续改动及其上下文；行号从 1 开始
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffHunk {
+    pub old_start: usize,
+    pub new_start: usize,
+    pub lines: Vec<DiffLine>,
+}
+
+/// 切换会改动的单个文件
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileDiff {
+    pub path: String,
+    pub status: FileChangeStatus,
+    pub added: usize,
+    pub removed: usize,
+    pub hunks: Vec<DiffHunk>,
+}
+
+/// 切换预览：按写入顺序列出内容会变化的文件
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwitchPreview {
+    pub app: String,
+    pub provider_id: String,
+    pub provider_name: String,
+    pub files: Vec<FileDiff>,
+}
+
+impl ProviderService {
+    /// 预览切换到指定供应商会写入的内容，不改动任何文件、数据库或调试代理
+    ///
+    /// 与切换走同一套 live 同步逻辑（供应商配置、MCP 与 Claude 钩子/权限），
+    /// 只是写入被捕获后与磁盘上的现有内容逐行比较。切换前检查不在此处执行，见 `validate_switch`
+    pub fn preview_switch(
+        state: &AppState,
+        app_type: AppType,
+        id: &str,
+    ) -> Result<SwitchPreview, AppError> {
+        let provider = state
+            .storage
+            .get_all_providers(app_type.as_str())?
+            .shift_remove(id)
+            .ok_or_else(|| AppError::Message(format!("供应商 {id} 不存在")))?;
+
+        let ((), captured) = capture_writes(|| {
+            LiveConfigSync::write_live_snapshot(&app_type, &provider)?;
+            McpService::sync_all_enabled(state)?;
+            Self::sync_claude_overlays_for(state, &app_type, &provider);
+            Ok(())
+        })?;
+
+        Ok(SwitchPreview {
+            app: app_type.as_str().to_string(),
+            provider_id: provider.id,
+            provider_name: provider.name,
+            files: file_diffs(captured)?,
+        })
+    }
+}
+
+fn file_diffs(captured: CapturedWrites) -> Result<Vec<FileDiff>, AppError> {
+    let mut files = Vec::new();
+    for (path, content) in captured {
+        let before = read_existing(&path)?;
+        let after = content.map(|data| String::from_utf8_lossy(&data).into_owned());
+        let status = match (&before, &after) {
+            (None, None) => continue,
+            (Some(old), Some(new)) if old == new => continue,
+            (None, Some(_)) => FileChangeStatus::Created,
+            (Some(_), None) => FileChangeStatus::Deleted,
+            (Some(_), Some(_)) => FileChangeStatus::Modified,
+        };
+        let hunks = diff_lines(
+            before.as_deref().unwrap_or_default(),
+            after.as_deref().unwrap_or_default(),
+        );
+        let count = |kind: DiffLineKind| {
+            hunks
+                .iter()
+                .flat_map(|hunk| &hunk.lines)
+                .filter(|line| line.kind == kind)
+                .count()
+        };
+        files.push(FileDiff {
+            path: path.display().to_string(),
+            status,
+            added: count(DiffLineKind::Added),
+            removed: count(DiffLineKind::Removed),
+            hunks,
+        });
+    }
+    Ok(files)
+}
+
+fn read_existing(path: &Path) -> Result<Option<String>, AppError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let data = fs::read(path).map_err(|e| AppError::io(path, e))?;
+    Ok(Some(String::from_utf8_lossy(&data).into_owned()))
+}
+
+/// 逐行比较，按统一差异格式分块
+fn diff_lines(old: &str, new: &str) -> Vec<DiffHunk> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let ops = align(&old, &new);
+
+    // 每个操作发生时两侧的行下标及该行文本
+    let mut rows = Vec::with_capacity(ops.len());
+    let (mut i, mut j) = (0, 0);
+    for kind in ops {
+        let text = if kind == DiffLineKind::Added {
+            new[j]
+        } else {
+            old[i]
+        };
+        rows.push((kind, i, j, text));
+        match kind {
+            DiffLineKind::Context => {
+                i += 1;
+                j += 1;
+            }
+            DiffLineKind::Removed => i += 1,
+            DiffLineKind::Added => j += 1,
+        }
+    }
+
+    let changed: Vec<usize> = rows
+        .iter()
+        .enumerate()
+        .filter(|(_, row)| row.0 != DiffLineKind::Context)
+        .map(|(index, _)| index)
+        .collect();
+    let mut hunks = Vec::new();
+    let mut k = 0;
+    while k < changed.len() {
+        let start = changed[k].saturating_sub(CONTEXT_LINES);
+        let mut end = changed[k];
+        // 两处改动之间的上下文不超过两倍时合并为一块
+        while k + 1 < changed.len() && changed[k + 1] <= end + 2 * CONTEXT_LINES + 1 {
+            k += 1;
+            end = changed[k];
+        }
+        k += 1;
+        let stop = (end + CONTEXT_LINES + 1).min(rows.len());
+        let (_, old_index, new_index, _) = rows[start];
+        hunks.push(DiffHunk {
+            old_start: old_index + 1,
+            new_start: new_index + 1,
+            lines: rows[start..stop]
+                .iter()
+                .map(|&(kind, _, _, text)| DiffLine {
+                    kind,
+                    text: redact(text).into_owned(),
+                })
+                .collect(),
+        });
+    }
+    hunks
+}
+
+/// 基于最长公共子序列对齐两侧的行；同一位置先列删除再列新增
+fn align(old: &[&str], new: &[&str]) -> Vec<DiffLineKind> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let a = &old[prefix..old.len() - suffix];
+    let b = &new[prefix..new.len() - suffix];
+
+    let mut ops = vec![DiffLineKind::Context; prefix];
+    if a.len() * b.len() > MAX_ALIGN_CELLS {
+        ops.extend(std::iter::repeat_n(DiffLineKind::Removed, a.len()));
+        ops.extend(std::iter::repeat_n(DiffLineKind::Added, b.len()));
+    } else {
+        // lcs[i * width + j]：a[i..] 与 b[j..] 的最长公共子序列长度
+        let width = b.len() + 1;
+        let mut lcs = vec![0u32; (a.len() + 1) * width];
+        for i in (0..a.len()).rev() {
+            for j in (0..b.len()).rev() {
+                lcs[i * width + j] = if a[i] == b[j] {
+                    lcs[(i + 1) * width + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() || j < b.len() {
+            if i < a.len() && j < b.len() && a[i] == b[j] {
+                ops.push(DiffLineKind::Context);
+                i += 1;
+                j += 1;
+            } else if i < a.len()
+                && (j == b.len() || lcs[(i + 1) * width + j] >= lcs[i * width + j + 1])
+            {
+                ops.push(DiffLineKind::Removed);
+                i += 1;
+            } else {
+                ops.push(DiffLineKind::Added);
+                j += 1;
+            }
+        }
+    }
+    ops.extend(std::iter::repeat_n(DiffLineKind::Context, suffix));
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::write_text_file;
+
+    #[test]
+    fn captured_writes_become_hunks_without_touching_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let existing = dir.path().join("config.toml");
+        let created = dir.path().join(".env");
+        let old: Vec<String> = (1..=12).map(|n| n.to_string()).collect();
+        fs::write(&existing, old.join("\n")).unwrap();
+
+        let mut new = old.clone();
+        new[1] = "two".to_string();
+        new[10] = "eleven".to_string();
+        let ((), captured) = capture_writes(|| {
+            write_text_file(&existing, &new.join("\n"))?;
+            write_text_file(&created, "API_KEY=sk-abcdefghijklmnopqrstuvwx")
+        })
+        .unwrap();
+        assert_eq!(fs::read_to_string(&existing).unwrap(), old.join("\n"));
+        assert!(!created.exists());
+
+        let files = file_diffs(captured).unwrap();
+        assert_eq!(files.len(), 2);
+        let modified = &files[0];
+        assert_eq!(modified.status, FileChangeStatus::Modified);
+        assert_eq!((modified.added, modified.removed), (2, 2));
+        // 两处改动相隔超过两倍上下文，分成两块
+        assert_eq!(modified.hunks.len(), 2);
+        assert_eq!(modified.hunks[0].old_start, 1);
+        assert_eq!(modified.hunks[0].lines.len(), 6);
+        assert_eq!(modified.hunks[1].old_start, 8);
+        assert_eq!(
+            modified.hunks[1].lines[3],
+            DiffLine {
+                kind: DiffLineKind::Removed,
+                text: "11".to_string(),
+            }
+        );
+
+        let env = &files[1];
+        assert_eq!(env.status, FileChangeStatus::Created);
+        assert!(!env.hunks[0].lines[0].text.contains("abcdefghijklmnop"));
+    }
+}
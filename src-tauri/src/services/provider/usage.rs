@@ -1,15 +1,159 @@
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use tokio::runtime::Runtime;
+use tokio::sync::Semaphore;
+use tokio::task::AbortHandle;
+
 use crate::error::AppError;
 use crate::provider::{UsageData, UsageResult};
-use crate::settings;
 use crate::store::AppState;
+use crate::usage_format::{current_language, localize_usage};
 use crate::usage_script;
 use crate::app_config::AppType;
 
+/// Threads of the runtime reserved for usage scripts
+const USAGE_WORKER_THREADS: usize = 2;
+/// Scripts running at once; later queries wait for a free slot
+const MAX_CONCURRENT_SCRIPTS: usize = 4;
+
+/// Usage scripts run their JS synchronously and may wait on slow relays, so they get a
+/// runtime of their own instead of sharing the one that serves provider switches
+static USAGE_RUNTIME: Lazy<Option<Runtime>> = Lazy::new(|| {
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(USAGE_WORKER_THREADS)
+        .thread_name("usage-script")
+        .enable_all()
+        .build()
+        .map_err(|e| log::error!("Failed to start usage script runtime: {e}"))
+        .ok()
+});
+
+static SCRIPT_SLOTS: Lazy<Semaphore> = Lazy::new(|| Semaphore::new(MAX_CONCURRENT_SCRIPTS));
+
+/// In-flight query per provider; a newer query for the same provider aborts the older one
+static RUNNING: Lazy<Mutex<HashMap<String, RunningQuery>>> = Lazy::new(Default::default);
+static NEXT_QUERY_ID: AtomicU64 = AtomicU64::new(0);
+
+struct RunningQuery {
+    id: u64,
+    handle: AbortHandle,
+}
+
+/// Latest result of a saved-script query per provider, read by the status summary
+static LAST_RESULTS: Lazy<Mutex<HashMap<String, (i64, UsageResult)>>> = Lazy::new(Default::default);
+
+fn running() -> MutexGuard<'static, HashMap<String, RunningQuery>> {
+    RUNNING.lock().unwrap_or_else(|e| e.into_inner())
+}
+
 pub struct UsageQueryExecutor;
 
 impl UsageQueryExecutor {
+    /// Last saved-script query result of a provider and when it finished (Unix seconds)
+    pub fn last_result(provider_id: &str) -> Option<(i64, UsageResult)> {
+        LAST_RESULTS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(provider_id)
+            .cloned()
+    }
+
+    /// Abort the in-flight usage query of a provider; returns false if none was running
+    pub fn cancel(provider_id: &str) -> bool {
+        match running().remove(provider_id) {
+            Some(query) => {
+                query.handle.abort();
+                log::info!("Cancelled usage query for provider {provider_id}");
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Run the script on the usage runtime, superseding any earlier query of the provider
+    ///
+    /// Cancellation takes effect at the next await point (the HTTP request), so a script
+    /// stuck in its synchronous JS part still finishes that part before stopping.
+    async fn run_isolated(
+        provider_id: &str,
+        script_code: &str,
+        api_key: &str,
+        base_url: &str,
+        timeout: u64,
+        access_token: Option<&str>,
+        user_id: Option<&str>,
+    ) -> Result<Vec<UsageData>, AppError> {
+        let Some(runtime) = USAGE_RUNTIME.as_ref() else {
+            return usage_script::execute_usage_script(
+                script_code,
+                api_key,
+                base_url,
+                timeout,
+                access_token,
+                user_id,
+            )
+            .await;
+        };
+
+        let (script_code, api_key, base_url) = (
+            script_code.to_string(),
+            api_key.to_string(),
+            base_url.to_string(),
+        );
+        let access_token = access_token.map(str::to_string);
+        let user_id = user_id.map(str::to_string);
+        let task = runtime.spawn(async move {
+            let _slot = SCRIPT_SLOTS.acquire().await.map_err(|e| {
+                AppError::Message(format!("Usage script worker unavailable: {e}"))
+            })?;
+            usage_script::execute_usage_script(
+                &script_code,
+                &api_key,
+                &base_url,
+                timeout,
+                access_token.as_deref(),
+                user_id.as_deref(),
+            )
+            .await
+        });
+
+        let id = NEXT_QUERY_ID.fetch_add(1, Ordering::Relaxed);
+        let superseded = running().insert(
+            provider_id.to_string(),
+            RunningQuery {
+                id,
+                handle: task.abort_handle(),
+            },
+        );
+        if let Some(previous) = superseded {
+            previous.handle.abort();
+            log::debug!("Superseded usage query for provider {provider_id}");
+        }
+
+        let result = task.await;
+        {
+            let mut running = running();
+            if running.get(provider_id).is_some_and(|q| q.id == id) {
+                running.remove(provider_id);
+            }
+        }
+
+        match result {
+            Ok(result) => result,
+            Err(e) if e.is_cancelled() => Err(AppError::localized(
+                "usage_script.cancelled",
+                "用量查询已取消",
+                "Usage query was cancelled",
+            )),
+            Err(e) => Err(AppError::Message(format!("Usage script task failed: {e}"))),
+        }
+    }
+
     /// Execute usage script and format result
     async fn execute_and_format_usage_result(
+        provider_id: &str,
         script_code: &str,
         api_key: &str,
         base_url: &str,
@@ -17,7 +161,8 @@ impl UsageQueryExecutor {
         access_token: Option<&str>,
         user_id: Option<&str>,
     ) -> Result<UsageResult, AppError> {
-        match usage_script::execute_usage_script(
+        match Self::run_isolated(
+            provider_id,
             script_code,
             api_key,
             base_url,
@@ -27,26 +172,8 @@ impl UsageQueryExecutor {
         )
         .await
         {
-            Ok(data) => {
-                let usage_list: Vec<UsageData> = if data.is_array() {
-                    serde_json::from_value(data).map_err(|e| {
-                        AppError::localized(
-                            "usage_script.data_format_error",
-                            format!("数据格式错误: {e}"),
-                            format!("Data format error: {e}"),
-                        )
-                    })?
-                } else {
-                    let single: UsageData = serde_json::from_value(data).map_err(|e| {
-                        AppError::localized(
-                            "usage_script.data_format_error",
-                            format!("数据格式错误: {e}"),
-                            format!("Data format error: {e}"),
-                        )
-                    })?;
-                    vec![single]
-                };
-
+            Ok(mut usage_list) => {
+                localize_usage(&mut usage_list, &current_language());
                 Ok(UsageResult {
                     success: true,
                     data: Some(usage_list),
@@ -54,9 +181,7 @@ impl UsageQueryExecutor {
                 })
             }
             Err(err) => {
-                let lang = settings::get_settings()
-                    .language
-                    .unwrap_or_else(|| "zh".to_string());
+                let lang = current_language();
 
                 let msg = match err {
                     AppError::Localized { zh, en, .. } => {
@@ -68,6 +193,8 @@ impl UsageQueryExecutor {
                     }
                     other => other.to_string(),
                 };
+                let msg = crate::log_sanitizer::redact(&msg).into_owned();
+                log::warn!("用量查询脚本执行失败: {msg}");
 
                 Ok(UsageResult {
                     success: false,
@@ -85,7 +212,7 @@ impl UsageQueryExecutor {
         provider_id: &str,
     ) -> Result<UsageResult, AppError> {
         let (script_code, timeout, api_key, base_url, access_token, user_id) = {
-            let providers = state.db.get_all_providers(app_type.as_str())?;
+            let providers = state.storage.get_all_providers(app_type.as_str())?;
             let provider = providers.get(provider_id).ok_or_else(|| {
                 AppError::localized(
                     "provider.not_found",
@@ -123,7 +250,8 @@ impl UsageQueryExecutor {
             )
         };
 
-        Self::execute_and_format_usage_result(
+        let result = Self::execute_and_format_usage_result(
+            provider_id,
             &script_code,
             &api_key,
             &base_url,
@@ -131,7 +259,13 @@ impl UsageQueryExecutor {
             access_token.as_deref(),
             user_id.as_deref(),
         )
-        .await
+        .await?;
+
+        LAST_RESULTS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(provider_id.to_string(), (chrono::Utc::now().timestamp(), result.clone()));
+        Ok(result)
     }
 
     /// Test usage script (using temporary script content, not saved)
@@ -139,7 +273,7 @@ impl UsageQueryExecutor {
     pub async fn test_usage_script(
         _state: &AppState,
         _app_type: AppType,
-        _provider_id: &str,
+        provider_id: &str,
         script_code: &str,
         timeout: u64,
         api_key: Option<&str>,
@@ -148,6 +282,7 @@ impl UsageQueryExecutor {
         user_id: Option<&str>,
     ) -> Result<UsageResult, AppError> {
         Self::execute_and_format_usage_result(
+            provider_id,
             script_code,
             api_key.unwrap_or(""),
             base_url.unwrap_or(""),
@@ -15,6 +15,7 @@ impl ProviderValidator {
                         "Claude configuration must be a JSON object",
                     ));
                 }
+                super::claude::ClaudeFlavorEnv::validate(app_type, provider)?;
             }
             AppType::Codex => {
                 let settings = provider.settings_config.as_object().ok_or_else(|| {
@@ -43,6 +44,8 @@ impl ProviderValidator {
                     ));
                 }
 
+                super::codex::CodexLoginAuth::validate(app_type, provider)?;
+
                 if let Some(config_value) = settings.get("config") {
                     if !(config_value.is_string() || config_value.is_null()) {
                         return Err(AppError::localized(
@@ -66,6 +69,50 @@ impl ProviderValidator {
             if let Some(usage_script) = &meta.usage_script {
                 Self::validate_usage_script(usage_script)?;
             }
+            if let Some(scope) = &meta.sync_scope {
+                Self::validate_sync_scope(app_type, scope)?;
+            }
+            if let Some(window) = &meta.access_window {
+                crate::services::AccessWindowService::validate(window)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validate live sync scope (custom mode needs keys under known sections)
+    fn validate_sync_scope(
+        app_type: &AppType,
+        scope: &crate::provider::SyncScope,
+    ) -> Result<(), AppError> {
+        use crate::provider::SyncScopeMode;
+
+        if scope.mode != SyncScopeMode::Custom {
+            return Ok(());
+        }
+
+        if scope.keys.iter().all(|k| k.trim().is_empty()) {
+            return Err(AppError::localized(
+                "provider.sync_scope.empty",
+                "自定义同步范围至少需要一个字段",
+                "Custom sync scope requires at least one key",
+            ));
+        }
+
+        let sections: &[&str] = match app_type {
+            AppType::Claude => return Ok(()),
+            AppType::Codex => &["auth", "config"],
+            AppType::Gemini => &["env", "config"],
+        };
+        for key in scope.keys.iter().map(|k| k.trim()).filter(|k| !k.is_empty()) {
+            let section = key.split('.').next().unwrap_or_default();
+            if !sections.contains(&section) {
+                return Err(AppError::localized(
+                    "provider.sync_scope.invalid_key",
+                    format!("同步字段 {key} 必须以 {} 开头", sections.join(" / ")),
+                    format!("Sync key {key} must start with {}", sections.join(" / ")),
+                ));
+            }
         }
 
         Ok(())
@@ -0,0 +1,736 @@
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::Duration;
+use url::Url;
+
+use crate::app_config::AppType;
+use crate::deeplink::{
+    build_provider_from_request, parse_provider_deeplink, DeepLinkImportRequest,
+};
+use crate::error::AppError;
+use crate::log_sanitizer::mask_secret as mask_key;
+use crate::services::ProviderService;
+use crate::store::AppState;
+
+const DOWNLOAD_TIMEOUT_SECS: u64 = 20;
+const MAX_CSV_BYTES: usize = 5 * 1024 * 1024;
+
+/// CSV 列映射：值为表头名称（忽略大小写与首尾空白）
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvColumnMapping {
+    pub name: String,
+    pub endpoint: String,
+    pub api_key: String,
+    /// 应用列（claude / codex / gemini），缺省时使用 `default_app`
+    #[serde(default)]
+    pub app: Option<String>,
+    #[serde(default)]
+    pub default_app: Option<String>,
+    #[serde(default)]
+    pub homepage: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+/// 预览中的单行结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvProviderRow {
+    /// 表格中的行号（表头为第 1 行）
+    pub row: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app: Option<String>,
+    pub name: String,
+    pub endpoint: String,
+    /// 仅展示掩码后的密钥
+    pub api_key_masked: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvImportedProvider {
+    pub row: usize,
+    pub app: String,
+    pub id: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvRowError {
+    pub row: usize,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvImportResult {
+    pub imported: Vec<CsvImportedProvider>,
+    pub failed: Vec<CsvRowError>,
+}
+
+/// 从共享表格（Google Sheet 公开链接或任意 CSV 链接）批量导入供应商
+pub struct ProviderCsvImportService;
+
+impl ProviderCsvImportService {
+    /// 下载 CSV 文本；Google Sheets 编辑链接会自动转换为 CSV 导出链接
+    pub async fn download(url: &str) -> Result<String, AppError> {
+        let url = normalize_sheet_url(url)?;
+
+        let client = Client::builder()
+            .timeout(Duration::from_secs(DOWNLOAD_TIMEOUT_SECS))
+            .redirect(reqwest::redirect::Policy::limited(5))
+            .user_agent("cli-hub")
+            .build()
+            .map_err(|e| AppError::Message(format!("Failed to create HTTP client: {e}")))?;
+
+        let mut response = client
+            .get(url.as_str())
+            .send()
+            .await
+            .map_err(|e| AppError::Message(format!("Failed to download CSV: {e}")))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Message(format!(
+                "Failed to download CSV: HTTP {}",
+                response.status()
+            )));
+        }
+
+        // 先看声明的长度，再边读边计数，避免超大或不断开的响应占满内存
+        let too_large =
+            || AppError::InvalidInput(format!("CSV is larger than the {MAX_CSV_BYTES} byte limit"));
+        if response
+            .content_length()
+            .is_some_and(|length| length > MAX_CSV_BYTES as u64)
+        {
+            return Err(too_large());
+        }
+
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| AppError::Message(format!("Failed to read CSV response: {e}")))?
+        {
+            if bytes.len() + chunk.len() > MAX_CSV_BYTES {
+                return Err(too_large());
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+
+        String::from_utf8(bytes)
+            .map(|text| text.trim_start_matches('\u{feff}').to_string())
+            .map_err(|e| AppError::InvalidInput(format!("CSV is not valid UTF-8: {e}")))
+    }
+
+    /// 解析并校验每一行，不写入数据库
+    pub fn preview(
+        state: &AppState,
+        csv: &str,
+        mapping: &CsvColumnMapping,
+    ) -> Result<Vec<CsvProviderRow>, AppError> {
+        let rows = MappedRow::parse_all(csv, mapping)?;
+        let mut existing = ExistingNames::default();
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let error = row
+                    .to_request()
+                    .and_then(|request| existing.check(state, &request))
+                    .err()
+                    .map(|e| e.to_string());
+                CsvProviderRow {
+                    row: row.row,
+                    app: row.app.clone(),
+                    name: row.name.clone(),
+                    endpoint: row.endpoint.clone(),
+                    api_key_masked: mask_key(&row.api_key),
+                    error,
+                }
+            })
+            .collect())
+    }
+
+    /// 逐行导入；失败的行记录原因，不影响其他行
+    pub fn import(
+        state: &AppState,
+        csv: &str,
+        mapping: &CsvColumnMapping,
+    ) -> Result<CsvImportResult, AppError> {
+        let rows = MappedRow::parse_all(csv, mapping)?;
+        let mut existing = ExistingNames::default();
+        let mut result = CsvImportResult {
+            imported: Vec::new(),
+            failed: Vec::new(),
+        };
+
+        let timestamp = chrono::Utc::now().timestamp_millis();
+        for row in rows {
+            let outcome = row.to_request().and_then(|request| {
+                let app_type = existing.check(state, &request)?;
+                let mut provider = build_provider_from_request(&app_type, &request)?;
+                provider.id = row.provider_id(timestamp);
+                let id = provider.id.clone();
+                ProviderService::add(state, app_type.clone(), provider)?;
+                Ok((app_type, id))
+            });
+
+            match outcome {
+                Ok((app_type, id)) => result.imported.push(CsvImportedProvider {
+                    row: row.row,
+                    app: app_type.as_str().to_string(),
+                    id,
+                }),
+                Err(err) => {
+                    log::warn!("CSV 第 {} 行导入失败: {err}", row.row);
+                    result.failed.push(CsvRowError {
+                        row: row.row,
+                        error: err.to_string(),
+                    });
+                }
+            }
+        }
+
+        log::info!(
+            "CSV 导入供应商完成: 成功 {} 行, 失败 {} 行",
+            result.imported.len(),
+            result.failed.len()
+        );
+        Ok(result)
+    }
+
+    /// 从 JSON 数组或 CSV 文本批量导入供应商
+    ///
+    /// 每行先经深链接参数与 `ProviderValidator` 校验，通过的行在同一事务中写入；
+    /// 返回逐行结果，失败行附带原因。
+    pub fn import_bulk(state: &AppState, payload: &str) -> Result<CsvImportResult, AppError> {
+        let rows = MappedRow::parse_bulk(payload)?;
+        let mut existing = ExistingNames::default();
+        let mut failed = Vec::new();
+        let mut pending = Vec::new();
+
+        let timestamp = chrono::Utc::now().timestamp_millis();
+        for row in rows {
+            let row = match row {
+                Ok(row) => row,
+                Err(row_error) => {
+                    failed.push(row_error);
+                    continue;
+                }
+            };
+            let outcome = row.to_request().and_then(|request| {
+                let app_type = existing.check(state, &request)?;
+                let mut provider = build_provider_from_request(&app_type, &request)?;
+                provider.id = row.provider_id(timestamp);
+                Ok((app_type, provider))
+            });
+            match outcome {
+                Ok(entry) => pending.push((row.row, entry)),
+                Err(err) => failed.push(CsvRowError {
+                    row: row.row,
+                    error: err.to_string(),
+                }),
+            }
+        }
+
+        let (slots, providers): (Vec<_>, Vec<_>) = pending
+            .into_iter()
+            .map(|(row, (app_type, provider))| {
+                let slot = CsvImportedProvider {
+                    row,
+                    app: app_type.as_str().to_string(),
+                    id: provider.id.clone(),
+                };
+                (slot, (app_type, provider))
+            })
+            .unzip();
+
+        let mut result = CsvImportResult {
+            imported: Vec::new(),
+            failed,
+        };
+        for (slot, outcome) in slots
+            .into_iter()
+            .zip(ProviderService::add_batch(state, providers))
+        {
+            match outcome {
+                Ok(()) => result.imported.push(slot),
+                Err(err) => result.failed.push(CsvRowError {
+                    row: slot.row,
+                    error: err.to_string(),
+                }),
+            }
+        }
+        result.failed.sort_by_key(|f| f.row);
+        for row_error in &result.failed {
+            log::warn!("批量导入第 {} 行失败: {}", row_error.row, row_error.error);
+        }
+
+        log::info!(
+            "批量导入供应商完成: 成功 {} 行, 失败 {} 行",
+            result.imported.len(),
+            result.failed.len()
+        );
+        Ok(result)
+    }
+}
+
+/// 批量导入的一行（JSON 数组元素）；CSV 载荷使用同名表头
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BulkProviderEntry {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    endpoint: String,
+    #[serde(default)]
+    api_key: String,
+    #[serde(default)]
+    app: Option<String>,
+    #[serde(default)]
+    model: Option<String>,
+}
+
+/// 按映射取出的一行数据
+struct MappedRow {
+    row: usize,
+    app: Option<String>,
+    name: String,
+    endpoint: String,
+    api_key: String,
+    homepage: Option<String>,
+    model: Option<String>,
+    notes: Option<String>,
+}
+
+impl MappedRow {
+    fn parse_all(csv: &str, mapping: &CsvColumnMapping) -> Result<Vec<Self>, AppError> {
+        let mut records = parse_csv(csv)?.into_iter();
+        let (_, header) = records
+            .next()
+            .ok_or_else(|| AppError::InvalidInput("CSV is empty".to_string()))?;
+
+        let find = |column: &str| -> Result<usize, AppError> {
+            let wanted = column.trim().to_lowercase();
+            header
+                .iter()
+                .position(|h| h.trim().to_lowercase() == wanted)
+                .ok_or_else(|| AppError::InvalidInput(format!("CSV column not found: {column}")))
+        };
+        let find_opt = |column: &Option<String>| -> Result<Option<usize>, AppError> {
+            match column.as_deref().map(str::trim).filter(|c| !c.is_empty()) {
+                Some(column) => find(column).map(Some),
+                None => Ok(None),
+            }
+        };
+
+        let name_idx = find(&mapping.name)?;
+        let endpoint_idx = find(&mapping.endpoint)?;
+        let key_idx = find(&mapping.api_key)?;
+        let app_idx = find_opt(&mapping.app)?;
+        let homepage_idx = find_opt(&mapping.homepage)?;
+        let model_idx = find_opt(&mapping.model)?;
+        let notes_idx = find_opt(&mapping.notes)?;
+
+        if app_idx.is_none() && mapping.default_app.is_none() {
+            return Err(AppError::InvalidInput(
+                "Either an app column or a default app is required".to_string(),
+            ));
+        }
+
+        let rows = records
+            .filter(|(_, record)| record.iter().any(|cell| !cell.trim().is_empty()))
+            .map(|(line, record)| {
+                let cell = |i: usize| {
+                    record
+                        .get(i)
+                        .map(|v| v.trim().to_string())
+                        .unwrap_or_default()
+                };
+                let cell_opt = |i: Option<usize>| i.map(cell).filter(|v| !v.is_empty());
+
+                Self {
+                    row: line,
+                    app: cell_opt(app_idx)
+                        .or_else(|| mapping.default_app.clone())
+                        .map(|app| app.trim().to_lowercase()),
+                    name: cell(name_idx),
+                    endpoint: cell(endpoint_idx),
+                    api_key: cell(key_idx),
+                    homepage: cell_opt(homepage_idx),
+                    model: cell_opt(model_idx),
+                    notes: cell_opt(notes_idx),
+                }
+            })
+            .collect();
+
+        Ok(rows)
+    }
+
+    /// 解析批量导入载荷：以 `[` 开头按 JSON 数组处理，否则按带表头的 CSV 处理
+    ///
+    /// JSON 的行号从 1 开始；无法解析的元素单独记为失败行，不影响其他行。
+    fn parse_bulk(payload: &str) -> Result<Vec<Result<Self, CsvRowError>>, AppError> {
+        let payload = payload.trim_start_matches('\u{feff}').trim();
+        if payload.is_empty() {
+            return Err(AppError::InvalidInput(
+                "Import payload is empty".to_string(),
+            ));
+        }
+        if payload.len() > MAX_CSV_BYTES {
+            return Err(AppError::InvalidInput(format!(
+                "Import payload is too large ({} bytes, limit {MAX_CSV_BYTES})",
+                payload.len()
+            )));
+        }
+
+        if !payload.starts_with('[') {
+            let has_model = parse_csv(payload)?.first().is_some_and(|(_, header)| {
+                header
+                    .iter()
+                    .any(|h| h.trim().eq_ignore_ascii_case("model"))
+            });
+            let mapping = CsvColumnMapping {
+                name: "name".to_string(),
+                endpoint: "endpoint".to_string(),
+                api_key: "apiKey".to_string(),
+                app: Some("app".to_string()),
+                default_app: None,
+                homepage: None,
+                model: has_model.then(|| "model".to_string()),
+                notes: None,
+            };
+            return Ok(Self::parse_all(payload, &mapping)?
+                .into_iter()
+                .map(Ok)
+                .collect());
+        }
+
+        let entries: Vec<serde_json::Value> = serde_json::from_str(payload)
+            .map_err(|e| AppError::InvalidInput(format!("Invalid JSON array: {e}")))?;
+        Ok(entries
+            .into_iter()
+            .enumerate()
+            .map(|(idx, value)| {
+                let row = idx + 1;
+                let entry: BulkProviderEntry =
+                    serde_json::from_value(value).map_err(|e| CsvRowError {
+                        row,
+                        error: format!("Invalid row: {e}"),
+                    })?;
+                let trimmed =
+                    |v: Option<String>| v.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+                Ok(Self {
+                    row,
+                    app: trimmed(entry.app).map(|app| app.to_lowercase()),
+                    name: entry.name.trim().to_string(),
+                    endpoint: entry.endpoint.trim().to_string(),
+                    api_key: entry.api_key.trim().to_string(),
+                    homepage: None,
+                    model: trimmed(entry.model),
+                    notes: None,
+                })
+            })
+            .collect())
+    }
+
+    /// 同一批次时间戳相同，追加行号避免 ID 冲突
+    fn provider_id(&self, timestamp: i64) -> String {
+        let sanitized_name = self
+            .name
+            .chars()
+            .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+            .collect::<String>()
+            .to_lowercase();
+        format!("{sanitized_name}-{timestamp}-{}", self.row)
+    }
+
+    /// 复用深链接的参数校验，生成供应商导入请求
+    fn to_request(&self) -> Result<DeepLinkImportRequest, AppError> {
+        if self.name.is_empty() {
+            return Err(AppError::InvalidInput("Name cannot be empty".to_string()));
+        }
+        if self.endpoint.is_empty() {
+            return Err(AppError::InvalidInput(
+                "Endpoint cannot be empty".to_string(),
+            ));
+        }
+        if self.api_key.is_empty() {
+            return Err(AppError::InvalidInput(
+                "API key cannot be empty".to_string(),
+            ));
+        }
+
+        let mut params = HashMap::new();
+        if let Some(app) = &self.app {
+            params.insert("app".to_string(), app.clone());
+        }
+        params.insert("name".to_string(), self.name.clone());
+        params.insert("endpoint".to_string(), self.endpoint.clone());
+        params.insert("apiKey".to_string(), self.api_key.clone());
+        // 未提供主页时使用端点的站点根地址
+        let homepage = self.homepage.clone().or_else(|| {
+            Url::parse(&self.endpoint)
+                .ok()
+                .map(|url| url.origin().ascii_serialization())
+        });
+        if let Some(homepage) = homepage {
+            params.insert("homepage".to_string(), homepage);
+        }
+        if let Some(model) = &self.model {
+            params.insert("model".to_string(), model.clone());
+        }
+        if let Some(notes) = &self.notes {
+            params.insert("notes".to_string(), notes.clone());
+        }
+
+        parse_provider_deeplink(&params, "v1".to_string(), "provider".to_string())
+    }
+}
+
+/// 已存在的供应商名称（按应用分组），用于跳过重复行
+#[derive(Default)]
+struct ExistingNames {
+    by_app: HashMap<String, Vec<String>>,
+}
+
+impl ExistingNames {
+    fn check(
+        &mut self,
+        state: &AppState,
+        request: &DeepLinkImportRequest,
+    ) -> Result<AppType, AppError> {
+        let app = request.app.as_deref().unwrap_or_default();
+        let app_type = AppType::from_str(app)?;
+        let name = request.name.as_deref().unwrap_or_default().to_lowercase();
+
+        if !self.by_app.contains_key(app) {
+            let names = ProviderService::list(state, app_type.clone())?
+                .values()
+                .map(|p| p.name.to_lowercase())
+                .collect();
+            self.by_app.insert(app.to_string(), names);
+        }
+
+        let names = self.by_app.get_mut(app).expect("names loaded above");
+        if names.contains(&name) {
+            return Err(AppError::InvalidInput(format!(
+                "Provider '{}' already exists for {app}",
+                request.name.as_deref().unwrap_or_default()
+            )));
+        }
+        // 记录本批次已处理的名称，以便标出表格内部的重复行
+        names.push(name);
+        Ok(app_type)
+    }
+}
+
+/// Google Sheets 编辑/分享链接转换为 CSV 导出链接，其余链接原样返回
+fn normalize_sheet_url(raw: &str) -> Result<Url, AppError> {
+    let url = Url::parse(raw.trim())
+        .map_err(|e| AppError::InvalidInput(format!("Invalid CSV URL: {e}")))?;
+    if url.scheme() != "https" && url.scheme() != "http" {
+        return Err(AppError::InvalidInput(
+            "CSV URL must be http or https".to_string(),
+        ));
+    }
+
+    if url.host_str() != Some("docs.google.com") {
+        return Ok(url);
+    }
+
+    let segments: Vec<&str> = url.path_segments().map(|s| s.collect()).unwrap_or_default();
+    let sheet_id = match segments.as_slice() {
+        ["spreadsheets", "d", id, ..] => id.to_string(),
+        _ => return Ok(url),
+    };
+    if segments.get(3) == Some(&"export") {
+        return Ok(url);
+    }
+
+    // gid 可能出现在查询参数或 #gid= 片段中
+    let gid = url
+        .query_pairs()
+        .find(|(k, _)| k == "gid")
+        .map(|(_, v)| v.into_owned())
+        .or_else(|| {
+            url.fragment()
+                .and_then(|f| f.strip_prefix("gid="))
+                .map(String::from)
+        });
+
+    let mut export = format!("https://docs.google.com/spreadsheets/d/{sheet_id}/export?format=csv");
+    if let Some(gid) = gid {
+        export.push_str(&format!("&gid={gid}"));
+    }
+    Url::parse(&export).map_err(|e| AppError::InvalidInput(format!("Invalid CSV URL: {e}")))
+}
+
+/// 解析 RFC 4180 风格的 CSV，每条记录附带其起始行号（从 1 开始）
+///
+/// 引号内的换行会让一条记录跨越多行，因此行号取自读取器的位置而非记录序号。
+fn parse_csv(text: &str) -> Result<Vec<(usize, Vec<String>)>, AppError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+
+    reader
+        .records()
+        .map(|record| {
+            let record = record.map_err(|e| AppError::InvalidInput(format!("Invalid CSV: {e}")))?;
+            let line = record.position().map_or(0, |p| p.line() as usize);
+            Ok((line, record.iter().map(str::to_string).collect()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use crate::provider::Provider;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_parse_csv_handles_quotes_and_newlines() {
+        let records =
+            parse_csv("name,notes\r\n\"Relay, Inc\",\"line1\nsaid \"\"hi\"\"\"\nlast,\n").unwrap();
+        assert_eq!(
+            records,
+            vec![
+                (1, vec!["name".to_string(), "notes".to_string()]),
+                (
+                    2,
+                    vec!["Relay, Inc".to_string(), "line1\nsaid \"hi\"".to_string()]
+                ),
+                (4, vec!["last".to_string(), String::new()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_normalize_sheet_url() {
+        let url = normalize_sheet_url(
+            "https://docs.google.com/spreadsheets/d/abc123/edit?usp=sharing#gid=42",
+        )
+        .unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://docs.google.com/spreadsheets/d/abc123/export?format=csv&gid=42"
+        );
+
+        let plain = normalize_sheet_url("https://example.com/relays.csv").unwrap();
+        assert_eq!(plain.as_str(), "https://example.com/relays.csv");
+
+        assert!(normalize_sheet_url("file:///etc/passwd").is_err());
+    }
+
+    #[test]
+    fn test_mapped_rows_validate_per_row() {
+        let mapping = CsvColumnMapping {
+            name: "Name".to_string(),
+            endpoint: "URL".to_string(),
+            api_key: "Key".to_string(),
+            app: Some("App".to_string()),
+            default_app: Some("claude".to_string()),
+            homepage: None,
+            model: None,
+            notes: None,
+        };
+        let csv = "name,url,key,app\nA,https://a.example.com,sk-a,\nB,ftp://b,sk-b,codex\n,,,\nC,https://c.example.com,,gemini\n\"D\nmultiline\",https://d.example.com,sk-d,\nE,https://e.example.com,sk-e,\n";
+
+        let rows = MappedRow::parse_all(csv, &mapping).unwrap();
+        assert_eq!(rows.len(), 5);
+        assert_eq!(rows[0].app.as_deref(), Some("claude"));
+        assert_eq!(rows[2].row, 5);
+        // 引号内换行后的行号仍指向表格中的实际行
+        assert_eq!(rows[3].row, 6);
+        assert_eq!(rows[4].row, 8);
+
+        let request = rows[0].to_request().unwrap();
+        assert_eq!(request.homepage.as_deref(), Some("https://a.example.com"));
+        assert!(rows[1].to_request().is_err());
+        assert!(rows[2].to_request().is_err());
+    }
+
+    #[test]
+    fn test_import_bulk_reports_each_row() {
+        let state = AppState::new(Arc::new(Database::memory().unwrap()));
+        let relay = Provider::with_id(
+            "relay".to_string(),
+            "Relay".to_string(),
+            json!({ "env": {
+                "ANTHROPIC_BASE_URL": "https://relay.example.com",
+                "ANTHROPIC_AUTH_TOKEN": "sk-relay-0000000000",
+            } }),
+            None,
+        );
+        state.storage.save_provider("claude", &relay).unwrap();
+        state
+            .storage
+            .set_current_provider("claude", "relay")
+            .unwrap();
+
+        let payload = json!([
+            {
+                "name": "Packy",
+                "endpoint": "https://api.packy.example.com",
+                "apiKey": "sk-packy",
+                "app": "claude",
+                "model": "claude-sonnet-4"
+            },
+            {
+                "name": "relay",
+                "endpoint": "https://b.example.com",
+                "apiKey": "sk-b",
+                "app": "claude"
+            },
+            { "name": "NoKey", "endpoint": "https://c.example.com", "app": "claude" },
+            42,
+            {
+                "name": "Cursor",
+                "endpoint": "https://d.example.com",
+                "apiKey": "sk-d",
+                "app": "cursor"
+            },
+        ])
+        .to_string();
+        let result = ProviderCsvImportService::import_bulk(&state, &payload).unwrap();
+        assert_eq!(result.imported.len(), 1);
+        assert_eq!(result.imported[0].row, 1);
+        let failed_rows: Vec<usize> = result.failed.iter().map(|f| f.row).collect();
+        assert_eq!(failed_rows, vec![2, 3, 4, 5]);
+
+        let csv = "Name,Endpoint,ApiKey,App\nAihub,https://aihub.example.com,sk-aihub,claude\n";
+        let result = ProviderCsvImportService::import_bulk(&state, csv).unwrap();
+        assert_eq!(result.imported.len(), 1);
+        assert_eq!(result.imported[0].row, 2);
+
+        assert_eq!(state.storage.get_all_providers("claude").unwrap().len(), 3);
+        assert_eq!(
+            state
+                .storage
+                .get_current_provider("claude")
+                .unwrap()
+                .as_deref(),
+            Some("relay")
+        );
+        assert!(ProviderCsvImportService::import_bulk(&state, "  ").is_err());
+    }
+
+    #[test]
+    fn test_mask_key() {
+        assert_eq!(mask_key("sk-1234567890"), "sk-1…7890");
+        assert_eq!(mask_key("short"), "*****");
+    }
+}
@@ -0,0 +1,406 @@
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::{DraftSource, Provider};
+use crate::services::env_checker::check_env_conflicts;
+use crate::services::provider::{ApiKeyVendor, ProviderValidator};
+use crate::settings::FirstImportSources;
+use crate::store::AppState;
+
+/// op 可能等待桌面端解锁，超时后放弃该条目
+const OP_TIMEOUT: Duration = Duration::from_secs(20);
+/// 未指定模型时 Codex 草稿使用的模型
+const DEFAULT_CODEX_MODEL: &str = "gpt-5-codex";
+
+const CUSTOM_CODEX_CONFIG: &str = r#"model_provider = "custom"
+model = "${MODEL}"
+
+[model_providers.custom]
+name = "custom"
+base_url = "${BASE_URL}"
+wire_api = "responses"
+requires_openai_auth = true
+"#;
+
+/// 生成的一个草稿供应商
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderDraftResult {
+    pub app: String,
+    pub id: String,
+    pub name: String,
+    pub source: DraftSource,
+}
+
+struct DraftCandidate {
+    app_type: AppType,
+    source: DraftSource,
+    provider: Provider,
+}
+
+/// 首次导入时，live 配置缺失的应用从环境变量或 1Password 生成草稿供应商
+///
+/// 草稿带有 `pendingReview` 标记，只保存到数据库、不写入 live 配置，
+/// 用户确认或编辑后再切换生效，而不是因为找不到配置文件而什么都不导入。
+pub struct ProviderDraftService;
+
+impl ProviderDraftService {
+    /// 为仍没有任何供应商的应用生成草稿；没有可用来源时返回空列表
+    pub fn import_missing(
+        state: &AppState,
+        sources: &FirstImportSources,
+    ) -> Result<Vec<ProviderDraftResult>, AppError> {
+        let mut missing = Vec::new();
+        for app_type in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+            if state
+                .storage
+                .get_all_providers(app_type.as_str())?
+                .is_empty()
+            {
+                missing.push(app_type);
+            }
+        }
+        if missing.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut candidates = Vec::new();
+        if !sources.skip_env {
+            candidates.extend(Self::env_candidates());
+        }
+        for item in &sources.one_password_items {
+            match read_op_item(item) {
+                Ok(value) => match candidate_from_op_item(item, &value) {
+                    Some(candidate) => candidates.push(candidate),
+                    None => log::info!("1Password 条目 {item} 中没有可识别的 API Key"),
+                },
+                Err(e) => log::warn!("读取 1Password 条目 {item} 失败: {e}"),
+            }
+        }
+
+        let mut results = Vec::new();
+        for DraftCandidate {
+            app_type,
+            source,
+            mut provider,
+        } in candidates
+        {
+            if !missing.contains(&app_type) {
+                continue;
+            }
+            let app = app_type.as_str();
+            if let Err(e) = ProviderValidator::validate_provider_settings(&app_type, &provider) {
+                log::warn!("跳过无效的草稿供应商 {}: {e}", provider.name);
+                continue;
+            }
+            let existing = state.storage.get_all_providers(app)?;
+            let base = provider.id.clone();
+            let mut n = 2;
+            while existing.contains_key(&provider.id) {
+                provider.id = format!("{base}-{n}");
+                n += 1;
+            }
+            provider.created_at = Some(chrono::Utc::now().timestamp_millis());
+            state.storage.save_provider(app, &provider)?;
+            // 草稿作为当前供应商，但在确认前不改写 live 配置
+            if state.storage.get_current_provider(app)?.is_none() {
+                state.storage.set_current_provider(app, &provider.id)?;
+            }
+            results.push(ProviderDraftResult {
+                app: app.to_string(),
+                id: provider.id.clone(),
+                name: provider.name.clone(),
+                source,
+            });
+        }
+        Ok(results)
+    }
+
+    /// 用户已确认草稿，去掉待确认标记
+    pub fn mark_reviewed(state: &AppState, app_type: AppType, id: &str) -> Result<(), AppError> {
+        let app = app_type.as_str();
+        let mut provider = state
+            .storage
+            .get_all_providers(app)?
+            .shift_remove(id)
+            .ok_or_else(|| {
+                AppError::localized(
+                    "provider.not_found",
+                    format!("供应商不存在: {id}"),
+                    format!("Provider not found: {id}"),
+                )
+            })?;
+        let Some(meta) = provider.meta.as_mut() else {
+            return Ok(());
+        };
+        if meta.pending_review.take().is_none() {
+            return Ok(());
+        }
+        state.storage.save_provider(app, &provider)
+    }
+
+    fn env_candidates() -> Vec<DraftCandidate> {
+        let mut vars = Vec::new();
+        for app in ["claude", "codex"] {
+            match check_env_conflicts(app) {
+                Ok(found) => vars.extend(
+                    found
+                        .into_iter()
+                        .map(|conflict| (conflict.var_name, conflict.var_value)),
+                ),
+                Err(e) => log::debug!("读取 {app} 相关环境变量失败: {e}"),
+            }
+        }
+        candidates_from_env(vars)
+    }
+}
+
+/// 按变量名去重（先出现的优先，即进程环境先于 shell 配置文件），
+/// 引用其他变量的值无法在此展开，直接忽略
+fn candidates_from_env(vars: Vec<(String, String)>) -> Vec<DraftCandidate> {
+    let mut env = Map::new();
+    for (name, value) in vars {
+        let value = value.trim();
+        if value.is_empty() || value.contains('$') || env.contains_key(&name) {
+            continue;
+        }
+        env.insert(name, Value::String(value.to_string()));
+    }
+    let get = |key: &str| env.get(key).and_then(Value::as_str);
+
+    let mut candidates = Vec::new();
+    let claude_env: Map<String, Value> = env
+        .iter()
+        .filter(|(name, _)| name.starts_with("ANTHROPIC_"))
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect();
+    if get("ANTHROPIC_AUTH_TOKEN").is_some() || get("ANTHROPIC_API_KEY").is_some() {
+        candidates.push(draft(
+            AppType::Claude,
+            "env-claude",
+            "Anthropic (env)",
+            json!({ "env": claude_env }),
+            DraftSource::Env,
+        ));
+    }
+    if let Some(key) = get("OPENAI_API_KEY") {
+        candidates.push(draft(
+            AppType::Codex,
+            "env-codex",
+            "OpenAI (env)",
+            codex_settings(key, get("OPENAI_BASE_URL"), get("OPENAI_MODEL")),
+            DraftSource::Env,
+        ));
+    }
+    candidates
+}
+
+/// 从 `op item get --format json` 的输出中找出可识别前缀的 API Key，
+/// 以及标签为 base_url / endpoint 的字段
+fn candidate_from_op_item(item_name: &str, item: &Value) -> Option<DraftCandidate> {
+    let fields = item.get("fields")?.as_array()?;
+    let (vendor, key) = fields
+        .iter()
+        .filter_map(field_value)
+        .find_map(|value| ApiKeyVendor::detect(value).map(|vendor| (vendor, value)))?;
+    let base_url = fields
+        .iter()
+        .find(|field| {
+            field
+                .get("label")
+                .and_then(Value::as_str)
+                .is_some_and(is_base_url_label)
+        })
+        .and_then(field_value);
+
+    let app_type = vendor.app_type();
+    let settings_config = match app_type {
+        AppType::Claude => {
+            let mut env = Map::new();
+            match base_url {
+                Some(url) => {
+                    env.insert("ANTHROPIC_AUTH_TOKEN".into(), json!(key));
+                    env.insert("ANTHROPIC_BASE_URL".into(), json!(url));
+                }
+                None => {
+                    env.insert("ANTHROPIC_API_KEY".into(), json!(key));
+                }
+            }
+            json!({ "env": env })
+        }
+        AppType::Codex => codex_settings(key, base_url, None),
+        AppType::Gemini => {
+            let mut env = Map::new();
+            env.insert("GEMINI_API_KEY".into(), json!(key));
+            if let Some(url) = base_url {
+                env.insert("GOOGLE_GEMINI_BASE_URL".into(), json!(url));
+            }
+            json!({ "env": env })
+        }
+    };
+    let slug: String = item_name
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+        .collect::<String>()
+        .to_lowercase();
+    Some(draft(
+        app_type,
+        &format!("op-{slug}"),
+        item_name,
+        settings_config,
+        DraftSource::OnePassword,
+    ))
+}
+
+fn field_value(field: &Value) -> Option<&str> {
+    field
+        .get("value")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+}
+
+fn is_base_url_label(label: &str) -> bool {
+    let label = label.trim().to_ascii_lowercase().replace([' ', '-'], "_");
+    matches!(
+        label.as_str(),
+        "base_url" | "baseurl" | "api_base" | "endpoint"
+    )
+}
+
+/// 有 Base URL 时生成自定义 model_provider，否则使用 Codex 内置的 OpenAI 端点
+fn codex_settings(key: &str, base_url: Option<&str>, model: Option<&str>) -> Value {
+    let config = match base_url {
+        Some(url) => CUSTOM_CODEX_CONFIG
+            .replace("${MODEL}", model.unwrap_or(DEFAULT_CODEX_MODEL))
+            .replace("${BASE_URL}", url),
+        None => model
+            .map(|m| format!("model = \"{m}\"\n"))
+            .unwrap_or_default(),
+    };
+    json!({
+        "auth": { "OPENAI_API_KEY": key },
+        "config": config,
+    })
+}
+
+fn draft(
+    app_type: AppType,
+    id: &str,
+    name: &str,
+    settings_config: Value,
+    source: DraftSource,
+) -> DraftCandidate {
+    let mut provider = Provider::with_id(id.to_string(), name.to_string(), settings_config, None);
+    provider
+        .meta
+        .get_or_insert_with(Default::default)
+        .pending_review = Some(source);
+    DraftCandidate {
+        app_type,
+        source,
+        provider,
+    }
+}
+
+fn read_op_item(item: &str) -> Result<Value, AppError> {
+    let mut child = Command::new("op")
+        .args(["item", "get", item, "--format", "json", "--reveal"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| {
+            AppError::localized(
+                "provider_drafts.op_unavailable",
+                format!("无法运行 1Password CLI (op): {e}"),
+                format!("Failed to run the 1Password CLI (op): {e}"),
+            )
+        })?;
+
+    let deadline = Instant::now() + OP_TIMEOUT;
+    while child
+        .try_wait()
+        .map_err(|e| AppError::Message(e.to_string()))?
+        .is_none()
+    {
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(AppError::localized(
+                "provider_drafts.op_timeout",
+                "1Password CLI 响应超时，请确认已登录或已解锁",
+                "The 1Password CLI timed out; make sure it is signed in and unlocked",
+            ));
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| AppError::Message(e.to_string()))?;
+    if !output.status.success() {
+        return Err(AppError::Message(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    serde_json::from_slice(&output.stdout)
+        .map_err(|e| AppError::Message(format!("无法解析 op 的输出: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_drafts_from_env_and_op_items() {
+        let vars = vec![
+            (
+                "ANTHROPIC_AUTH_TOKEN".to_string(),
+                "sk-relay-123".to_string(),
+            ),
+            (
+                "ANTHROPIC_BASE_URL".to_string(),
+                "https://relay.example.com".to_string(),
+            ),
+            // shell 配置文件中的同名变量晚于进程环境，被忽略
+            ("ANTHROPIC_AUTH_TOKEN".to_string(), "sk-stale".to_string()),
+            ("OPENAI_API_KEY".to_string(), "$OTHER_KEY".to_string()),
+        ];
+        let candidates = candidates_from_env(vars);
+        assert_eq!(candidates.len(), 1);
+        let claude = &candidates[0].provider;
+        assert_eq!(
+            claude.settings_config["env"]["ANTHROPIC_AUTH_TOKEN"],
+            "sk-relay-123"
+        );
+        assert_eq!(
+            claude.meta.as_ref().unwrap().pending_review,
+            Some(DraftSource::Env)
+        );
+
+        let item = json!({
+            "title": "OpenAI Relay",
+            "fields": [
+                { "label": "username", "value": "me@example.com" },
+                { "label": "credential", "type": "CONCEALED", "value": "sk-proj-abc" },
+                { "label": "Base URL", "value": "https://openai.example.com/v1" },
+            ]
+        });
+        let candidate = candidate_from_op_item("OpenAI Relay", &item).unwrap();
+        assert_eq!(candidate.app_type, AppType::Codex);
+        assert_eq!(candidate.provider.id, "op-openairelay");
+        let config = candidate.provider.settings_config["config"]
+            .as_str()
+            .unwrap();
+        assert!(config.contains("base_url = \"https://openai.example.com/v1\""));
+        ProviderValidator::validate_provider_settings(&AppType::Codex, &candidate.provider)
+            .unwrap();
+
+        assert!(candidate_from_op_item("Notes", &json!({ "fields": [] })).is_none());
+    }
+}
@@ -0,0 +1,127 @@
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::log_sanitizer::redact;
+use crate::provider::{ProviderErrorNote, ProviderOperation};
+use crate::store::AppState;
+
+/// 供应商失败记录
+///
+/// 切换、健康检查或用量查询失败时，错误写入 `provider_errors` 历史，
+/// 同时更新供应商 meta 中的 lastError，供列表直接展示。
+pub struct ProviderErrorService;
+
+impl ProviderErrorService {
+    /// 记录一次失败；记录本身出错只写日志，不影响调用方返回原始错误
+    pub fn record(
+        state: &AppState,
+        app_type: &AppType,
+        provider_id: &str,
+        operation: ProviderOperation,
+        message: &str,
+    ) {
+        if let Err(e) = Self::try_record(state, app_type, provider_id, operation, message) {
+            log::warn!("记录供应商 {provider_id} 的失败信息失败: {e}");
+        }
+    }
+
+    /// 失败历史，按时间倒序
+    pub fn history(
+        state: &AppState,
+        app_type: &AppType,
+        provider_id: &str,
+    ) -> Result<Vec<ProviderErrorNote>, AppError> {
+        state
+            .storage
+            .get_provider_errors(app_type.as_str(), provider_id)
+    }
+
+    fn try_record(
+        state: &AppState,
+        app_type: &AppType,
+        provider_id: &str,
+        operation: ProviderOperation,
+        message: &str,
+    ) -> Result<(), AppError> {
+        let app = app_type.as_str();
+        // 供应商不存在（如切换到错误的 ID）时没有可以挂靠的记录
+        let Some(mut provider) = state
+            .storage
+            .get_all_providers(app)?
+            .shift_remove(provider_id)
+        else {
+            return Ok(());
+        };
+
+        let note = ProviderErrorNote {
+            operation,
+            // 错误信息可能带有请求 URL 或响应片段，先遮蔽其中的密钥
+            message: redact(message).into_owned(),
+            occurred_at: chrono::Utc::now().timestamp_millis(),
+        };
+        state
+            .storage
+            .record_provider_error(app, provider_id, &note)?;
+        provider
+            .meta
+            .get_or_insert_with(Default::default)
+            .last_error = Some(note);
+        state.storage.save_provider(app, &provider)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use crate::provider::Provider;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    #[test]
+    fn failures_update_last_error_and_history() {
+        let state = AppState::new(Arc::new(Database::memory().unwrap()));
+        let provider = Provider::with_id(
+            "relay".to_string(),
+            "Relay".to_string(),
+            json!({ "env": { "ANTHROPIC_AUTH_TOKEN": "sk-relay-0000000000" } }),
+            None,
+        );
+        state.storage.save_provider("claude", &provider).unwrap();
+
+        ProviderErrorService::record(
+            &state,
+            &AppType::Claude,
+            "relay",
+            ProviderOperation::Switch,
+            "写入 settings.json 失败",
+        );
+        ProviderErrorService::record(
+            &state,
+            &AppType::Claude,
+            "relay",
+            ProviderOperation::Usage,
+            "HTTP 502 Bad Gateway",
+        );
+        // 不存在的供应商不留记录
+        ProviderErrorService::record(
+            &state,
+            &AppType::Claude,
+            "missing",
+            ProviderOperation::Switch,
+            "供应商 missing 不存在",
+        );
+
+        let stored = state.storage.get_all_providers("claude").unwrap();
+        let last = stored["relay"].meta.as_ref().unwrap().last_error.as_ref();
+        assert_eq!(last.map(|n| n.operation), Some(ProviderOperation::Usage));
+
+        let history = ProviderErrorService::history(&state, &AppType::Claude, "relay").unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].message, "HTTP 502 Bad Gateway");
+        assert!(
+            ProviderErrorService::history(&state, &AppType::Claude, "missing")
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
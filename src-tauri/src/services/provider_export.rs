@@ -0,0 +1,335 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::str::FromStr;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::services::vcs_export::redact_value;
+use crate::services::ProviderService;
+use crate::store::AppState;
+
+const DOCUMENT_FORMAT: &str = "cli-hub-provider";
+const DOCUMENT_VERSION: u32 = 1;
+/// 未包含密钥的文档中敏感字段的占位值，与 Git 导出一致
+const REDACTED: &str = "<redacted>";
+
+/// 单个供应商的分享文档：配置、元数据、自定义端点、用量脚本与图标
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderDocument {
+    pub format: String,
+    pub version: u32,
+    pub app: String,
+    pub exported_at: i64,
+    pub secrets_included: bool,
+    pub provider: Provider,
+}
+
+/// 导入时 id 已存在的处理方式
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ProviderImportConflict {
+    /// 以新的 id 另存一份
+    #[default]
+    Rename,
+    /// 覆盖本地同 id 的供应商，保留排序与切换统计
+    Replace,
+    /// 保留本地供应商，不导入
+    Skip,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderImportResult {
+    pub app: String,
+    /// 导入后的 id；跳过时为本地已有供应商的 id
+    pub id: String,
+    pub renamed: bool,
+    pub replaced: bool,
+    pub skipped: bool,
+    /// 文档不含密钥，导入后需要补填 API Key
+    pub needs_secrets: bool,
+}
+
+pub struct ProviderExportService;
+
+impl ProviderExportService {
+    /// 导出单个供应商为自包含的 JSON 文档
+    ///
+    /// 切换统计、测速、凭证探测等仅与本机有关的状态不会导出；
+    /// 不包含密钥时，配置与用量脚本中的敏感字段替换为占位值。
+    pub fn export(
+        state: &AppState,
+        app_type: AppType,
+        id: &str,
+        include_secrets: bool,
+    ) -> Result<String, AppError> {
+        let mut provider = state
+            .storage
+            .get_all_providers(app_type.as_str())?
+            .shift_remove(id)
+            .ok_or_else(|| {
+                AppError::localized(
+                    "provider.not_found",
+                    format!("供应商不存在: {id}"),
+                    format!("Provider not found: {id}"),
+                )
+            })?;
+        strip_local_state(&mut provider);
+        if !include_secrets {
+            redact_secrets(&mut provider);
+        }
+
+        let document = ProviderDocument {
+            format: DOCUMENT_FORMAT.to_string(),
+            version: DOCUMENT_VERSION,
+            app: app_type.as_str().to_string(),
+            exported_at: chrono::Utc::now().timestamp_millis(),
+            secrets_included: include_secrets,
+            provider,
+        };
+        serde_json::to_string_pretty(&document).map_err(|e| AppError::JsonSerialize { source: e })
+    }
+
+    /// 导入 [`Self::export`] 生成的文档
+    pub fn import(
+        state: &AppState,
+        document: &str,
+        on_conflict: ProviderImportConflict,
+    ) -> Result<ProviderImportResult, AppError> {
+        let document: ProviderDocument = serde_json::from_str(document).map_err(|e| {
+            AppError::localized(
+                "provider_export.invalid_document",
+                format!("无法解析供应商文档: {e}"),
+                format!("Failed to parse the provider document: {e}"),
+            )
+        })?;
+        if document.format != DOCUMENT_FORMAT || document.version > DOCUMENT_VERSION {
+            return Err(AppError::localized(
+                "provider_export.unsupported_document",
+                format!(
+                    "不支持的供应商文档: {} v{}",
+                    document.format, document.version
+                ),
+                format!(
+                    "Unsupported provider document: {} v{}",
+                    document.format, document.version
+                ),
+            ));
+        }
+        let app_type = AppType::from_str(&document.app)?;
+        let app = app_type.as_str();
+        let mut provider = document.provider;
+        strip_local_state(&mut provider);
+
+        let existing = state.storage.get_all_providers(app)?;
+        let mut result = ProviderImportResult {
+            app: app.to_string(),
+            id: provider.id.clone(),
+            renamed: false,
+            replaced: false,
+            skipped: false,
+            needs_secrets: false,
+        };
+
+        let Some(local) = existing.get(&provider.id) else {
+            result.needs_secrets = needs_secrets(&provider)?;
+            provider.created_at = Some(chrono::Utc::now().timestamp_millis());
+            ProviderService::add(state, app_type, provider)?;
+            return Ok(result);
+        };
+        match on_conflict {
+            ProviderImportConflict::Skip => {
+                result.skipped = true;
+            }
+            ProviderImportConflict::Replace => {
+                // 覆盖时沿用本地已有的密钥，避免占位值替换掉可用的凭证
+                restore_secrets(&mut provider.settings_config, &local.settings_config);
+                restore_script_secrets(&mut provider, local);
+                result.needs_secrets = needs_secrets(&provider)?;
+                provider.sort_index = local.sort_index;
+                provider.created_at = local.created_at;
+                ProviderService::update(state, app_type, provider)?;
+                result.replaced = true;
+            }
+            ProviderImportConflict::Rename => {
+                let id = unique_id(&provider.id, |candidate| existing.contains_key(candidate));
+                provider.name = unique_name(&provider.name, |candidate| {
+                    existing.values().any(|p| p.name == candidate)
+                });
+                provider.id = id.clone();
+                provider.created_at = Some(chrono::Utc::now().timestamp_millis());
+                result.needs_secrets = needs_secrets(&provider)?;
+                ProviderService::add(state, app_type, provider)?;
+                result.id = id;
+                result.renamed = true;
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// 去掉排序、切换统计、测速与探测结果等本机状态
+fn strip_local_state(provider: &mut Provider) {
+    provider.sort_index = None;
+    provider.created_at = None;
+    provider.last_switched_at = None;
+    provider.switch_count = 0;
+    provider.version = None;
+    if let Some(meta) = provider.meta.as_mut() {
+        meta.credential_status = None;
+        meta.latency = None;
+        meta.debug_proxy = None;
+        meta.model_fidelity = None;
+        meta.managed_by = None;
+        meta.pending_review = None;
+        meta.last_error = None;
+    }
+}
+
+fn redact_secrets(provider: &mut Provider) {
+    let settings = std::mem::take(&mut provider.settings_config);
+    provider.settings_config = redact_value(settings);
+    if let Some(script) = provider
+        .meta
+        .as_mut()
+        .and_then(|meta| meta.usage_script.as_mut())
+    {
+        for secret in [&mut script.api_key, &mut script.access_token] {
+            if secret.as_deref().is_some_and(|s| !s.is_empty()) {
+                *secret = Some(REDACTED.to_string());
+            }
+        }
+    }
+}
+
+fn needs_secrets(provider: &Provider) -> Result<bool, AppError> {
+    let value =
+        serde_json::to_value(provider).map_err(|e| AppError::JsonSerialize { source: e })?;
+    Ok(contains_redacted(&value))
+}
+
+fn contains_redacted(value: &Value) -> bool {
+    match value {
+        Value::String(s) => s.contains(REDACTED),
+        Value::Array(items) => items.iter().any(contains_redacted),
+        Value::Object(map) => map.values().any(contains_redacted),
+        _ => false,
+    }
+}
+
+/// 把导入配置中值为占位符的字段换回本地同一路径上的值
+fn restore_secrets(imported: &mut Value, local: &Value) {
+    match imported {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                let Some(local_value) = local.get(key) else {
+                    continue;
+                };
+                if value.as_str() == Some(REDACTED) {
+                    *value = local_value.clone();
+                } else {
+                    restore_secrets(value, local_value);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                if let Some(local_item) = local.get(index) {
+                    restore_secrets(item, local_item);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn restore_script_secrets(provider: &mut Provider, local: &Provider) {
+    let local_script = local.meta.as_ref().and_then(|m| m.usage_script.as_ref());
+    let (Some(script), Some(local_script)) = (
+        provider.meta.as_mut().and_then(|m| m.usage_script.as_mut()),
+        local_script,
+    ) else {
+        return;
+    };
+    if script.api_key.as_deref() == Some(REDACTED) {
+        script.api_key = local_script.api_key.clone();
+    }
+    if script.access_token.as_deref() == Some(REDACTED) {
+        script.access_token = local_script.access_token.clone();
+    }
+}
+
+fn unique_id(base: &str, taken: impl Fn(&str) -> bool) -> String {
+    (2..)
+        .map(|n| format!("{base}-{n}"))
+        .find(|candidate| !taken(candidate.as_str()))
+        .expect("unbounded candidates")
+}
+
+fn unique_name(base: &str, taken: impl Fn(&str) -> bool) -> String {
+    if !taken(base) {
+        return base.to_string();
+    }
+    (2..)
+        .map(|n| format!("{base} {n}"))
+        .find(|candidate| !taken(candidate.as_str()))
+        .expect("unbounded candidates")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    #[test]
+    fn round_trip_renames_on_collision_and_flags_missing_secrets() {
+        let state = AppState::new(Arc::new(Database::memory().unwrap()));
+        let provider = Provider::with_id(
+            "relay".to_string(),
+            "Relay".to_string(),
+            json!({ "env": {
+                "ANTHROPIC_BASE_URL": "https://relay.example.com",
+                "ANTHROPIC_AUTH_TOKEN": "sk-relay-0000000000",
+            } }),
+            None,
+        );
+        state.storage.save_provider("claude", &provider).unwrap();
+        state
+            .storage
+            .set_current_provider("claude", "relay")
+            .unwrap();
+
+        let document =
+            ProviderExportService::export(&state, AppType::Claude, "relay", false).unwrap();
+        assert!(!document.contains("sk-relay-0000000000"));
+        assert!(document.contains("https://relay.example.com"));
+
+        let skipped =
+            ProviderExportService::import(&state, &document, ProviderImportConflict::Skip).unwrap();
+        assert!(skipped.skipped);
+
+        let renamed =
+            ProviderExportService::import(&state, &document, ProviderImportConflict::Rename)
+                .unwrap();
+        assert_eq!(renamed.id, "relay-2");
+        assert!(renamed.needs_secrets);
+        let providers = state.storage.get_all_providers("claude").unwrap();
+        assert_eq!(providers.len(), 2);
+        assert_eq!(providers["relay-2"].name, "Relay 2");
+
+        // 覆盖时占位值换回本地密钥
+        let mut imported: ProviderDocument = serde_json::from_str(&document).unwrap();
+        restore_secrets(
+            &mut imported.provider.settings_config,
+            &providers["relay"].settings_config,
+        );
+        assert_eq!(
+            imported.provider.settings_config["env"]["ANTHROPIC_AUTH_TOKEN"],
+            "sk-relay-0000000000"
+        );
+    }
+}
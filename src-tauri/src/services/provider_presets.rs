@@ -0,0 +1,247 @@
+use minisign_verify::{PublicKey, Signature};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::app_config::AppType;
+use crate::database::Storage;
+use crate::error::AppError;
+use crate::provider_templates::{PresetSource, ProviderTemplate, PROVIDER_TEMPLATES};
+use crate::store::AppState;
+
+/// 远程预设索引地址，签名为同名 `.minisig` 文件
+const INDEX_URL: &str =
+    "https://raw.githubusercontent.com/ziye0180/cli-hub/main/presets/provider-presets.json";
+/// 与应用更新包相同的 minisign 公钥，索引由发布流程签名
+const INDEX_PUBLIC_KEY: &str = "RWTjKDlXmowCyC9Q/dOAftdyN/oC70kgS2Zbl5CRd63EFO5NZwtHjEVQ";
+/// 当前支持的索引格式版本
+const INDEX_VERSION: u32 = 1;
+
+/// 远程预设缓存在 settings 表中的键
+pub(crate) const CACHE_KEY: &str = "provider_presets_cache";
+/// 缓存有效期，过期前后台检查不会重新拉取
+const CACHE_TTL_SECS: i64 = 24 * 60 * 60;
+const FETCH_TIMEOUT_SECS: u64 = 15;
+/// 启动后首次检查的延迟，避免与启动流程争抢网络
+const STARTUP_DELAY: Duration = Duration::from_secs(30);
+const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+#[derive(Deserialize)]
+struct PresetIndex {
+    version: u32,
+    templates: Vec<ProviderTemplate>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PresetCache {
+    fetched_at: i64,
+    templates: Vec<ProviderTemplate>,
+}
+
+/// 刷新远程预设的结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetRefreshResult {
+    /// 是否实际拉取了索引；缓存未过期且未强制刷新时为 false
+    pub refreshed: bool,
+    pub remote_count: usize,
+    /// 缓存的拉取时间（Unix 秒），从未成功拉取时为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fetched_at: Option<i64>,
+}
+
+/// 远程可更新的供应商预设：新中转无需发版即可出现在模板列表中
+pub struct ProviderPresetService;
+
+impl ProviderPresetService {
+    /// 当前生效的模板：远程预设覆盖同 ID 的内置模板，新增的排在内置之后
+    pub fn templates(state: &AppState) -> Result<Vec<ProviderTemplate>, AppError> {
+        let remote = cached(state.storage.as_ref())?
+            .map(|cache| cache.templates)
+            .unwrap_or_default();
+        Ok(merge(PROVIDER_TEMPLATES.clone(), remote))
+    }
+
+    /// 拉取并校验远程索引；缓存未过期且未强制刷新时直接返回缓存状态
+    pub async fn refresh(state: &AppState, force: bool) -> Result<PresetRefreshResult, AppError> {
+        let now = chrono::Utc::now().timestamp();
+        if !force {
+            if let Some(cache) = cached(state.storage.as_ref())? {
+                if now - cache.fetched_at < CACHE_TTL_SECS {
+                    return Ok(PresetRefreshResult {
+                        refreshed: false,
+                        remote_count: cache.templates.len(),
+                        fetched_at: Some(cache.fetched_at),
+                    });
+                }
+            }
+        }
+
+        let client = Client::builder()
+            .timeout(Duration::from_secs(FETCH_TIMEOUT_SECS))
+            .build()
+            .map_err(|e| AppError::Message(format!("创建 HTTP 客户端失败: {e}")))?;
+        let index = fetch_text(&client, INDEX_URL).await?;
+        let signature = fetch_text(&client, &format!("{INDEX_URL}.minisig")).await?;
+        verify_index(&index, &signature)?;
+        let templates = parse_index(&index)?;
+
+        let cache = PresetCache {
+            fetched_at: now,
+            templates,
+        };
+        let json =
+            serde_json::to_string(&cache).map_err(|e| AppError::JsonSerialize { source: e })?;
+        state.storage.set_setting(CACHE_KEY, &json)?;
+        log::info!("已更新远程供应商预设，共 {} 项", cache.templates.len());
+
+        Ok(PresetRefreshResult {
+            refreshed: true,
+            remote_count: cache.templates.len(),
+            fetched_at: Some(now),
+        })
+    }
+
+    /// 启动后台检查：启动稍后拉取一次，之后定期检查缓存是否过期
+    pub fn spawn_scheduler(state: AppState) {
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(STARTUP_DELAY).await;
+            loop {
+                if let Err(e) = Self::refresh(&state, false).await {
+                    log::warn!("更新远程供应商预设失败，继续使用缓存: {e}");
+                }
+                tokio::time::sleep(CHECK_INTERVAL).await;
+            }
+        });
+    }
+}
+
+fn cached(storage: &dyn Storage) -> Result<Option<PresetCache>, AppError> {
+    let Some(raw) = storage.get_setting(CACHE_KEY)? else {
+        return Ok(None);
+    };
+    Ok(serde_json::from_str(&raw)
+        .map_err(|e| log::warn!("远程预设缓存已损坏，忽略: {e}"))
+        .ok())
+}
+
+async fn fetch_text(client: &Client, url: &str) -> Result<String, AppError> {
+    let fetch_failed = |e: reqwest::Error| {
+        AppError::localized(
+            "provider_presets.fetch_failed",
+            format!("获取远程供应商预设失败: {e}"),
+            format!("Failed to fetch remote provider presets: {e}"),
+        )
+    };
+    client
+        .get(url)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(fetch_failed)?
+        .text()
+        .await
+        .map_err(fetch_failed)
+}
+
+/// 校验索引的 minisign 签名，未通过时不写入缓存
+fn verify_index(index: &str, signature: &str) -> Result<(), AppError> {
+    let bad_signature = |e: minisign_verify::Error| {
+        AppError::localized(
+            "provider_presets.bad_signature",
+            format!("远程供应商预设签名校验失败: {e}"),
+            format!("Remote provider preset signature check failed: {e}"),
+        )
+    };
+    let key = PublicKey::from_base64(INDEX_PUBLIC_KEY).map_err(bad_signature)?;
+    let signature = Signature::decode(signature.trim()).map_err(bad_signature)?;
+    key.verify(index.as_bytes(), &signature, false)
+        .map_err(bad_signature)
+}
+
+/// 解析索引并标记来源；缺少 ID 或目标应用无效的条目被跳过
+fn parse_index(text: &str) -> Result<Vec<ProviderTemplate>, AppError> {
+    let index: PresetIndex = serde_json::from_str(text).map_err(|e| {
+        AppError::localized(
+            "provider_presets.invalid",
+            format!("远程供应商预设格式错误: {e}"),
+            format!("Invalid remote provider presets: {e}"),
+        )
+    })?;
+    if index.version > INDEX_VERSION {
+        return Err(AppError::localized(
+            "provider_presets.unsupported_version",
+            format!("远程供应商预设版本 {} 过新，请升级应用", index.version),
+            format!(
+                "Remote provider presets version {} is newer than supported, please upgrade",
+                index.version
+            ),
+        ));
+    }
+
+    Ok(index
+        .templates
+        .into_iter()
+        .filter(|t| {
+            let valid = !t.id.trim().is_empty()
+                && AppType::from_str(&t.app).is_ok()
+                && t.placeholders.iter().all(|p| !p.key.trim().is_empty());
+            if !valid {
+                log::warn!("跳过无效的远程供应商预设: '{}'", t.id);
+            }
+            valid
+        })
+        .map(|mut t| {
+            t.source = PresetSource::Remote;
+            t
+        })
+        .collect())
+}
+
+fn merge(
+    mut templates: Vec<ProviderTemplate>,
+    remote: Vec<ProviderTemplate>,
+) -> Vec<ProviderTemplate> {
+    for preset in remote {
+        match templates.iter_mut().find(|t| t.id == preset.id) {
+            Some(existing) => *existing = preset,
+            None => templates.push(preset),
+        }
+    }
+    templates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remote_presets_override_builtins_and_are_flagged() {
+        let index = r#"{"version":1,"templates":[
+            {"id":"claude-custom-relay","app":"claude","name":"Custom relay v2",
+             "settingsConfig":{"env":{"ANTHROPIC_BASE_URL":"${BASE_URL}"}},
+             "placeholders":[{"key":"BASE_URL","label":"Base URL"}]},
+            {"id":"new-relay","app":"codex","name":"New relay","settingsConfig":{}},
+            {"id":"broken","app":"cursor","name":"Unknown app","settingsConfig":{}}
+        ]}"#;
+        let remote = parse_index(index).unwrap();
+        assert_eq!(remote.len(), 2, "entries for unknown apps are skipped");
+        assert!(remote.iter().all(|t| t.source == PresetSource::Remote));
+
+        let merged = merge(PROVIDER_TEMPLATES.clone(), remote);
+        assert_eq!(merged.len(), PROVIDER_TEMPLATES.len() + 1);
+        let relay = merged
+            .iter()
+            .find(|t| t.id == "claude-custom-relay")
+            .unwrap();
+        assert_eq!(relay.name, "Custom relay v2");
+        assert_eq!(relay.source, PresetSource::Remote);
+        assert_eq!(merged.last().unwrap().id, "new-relay");
+        assert_eq!(merged[0].source, PresetSource::Builtin);
+
+        assert!(parse_index(r#"{"version":2,"templates":[]}"#).is_err());
+        assert!(verify_index(index, "not a signature").is_err());
+    }
+}
@@ -0,0 +1,201 @@
+use serde_json::Value;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::provider_templates::{ProviderTemplate, TemplatePlaceholder};
+use crate::services::{ProviderPresetService, ProviderService};
+use crate::store::AppState;
+
+pub struct ProviderTemplateService;
+
+impl ProviderTemplateService {
+    /// 列出内置与远程预设模板；指定应用时只返回该应用的模板
+    pub fn list(
+        state: &AppState,
+        app_type: Option<&AppType>,
+    ) -> Result<Vec<ProviderTemplate>, AppError> {
+        Ok(ProviderPresetService::templates(state)?
+            .into_iter()
+            .filter(|t| app_type.is_none_or(|app| app.as_str() == t.app))
+            .collect())
+    }
+
+    /// 返回尚未填写且没有默认值的占位符，空列表表示可以直接创建
+    pub fn missing_placeholders(
+        state: &AppState,
+        template_id: &str,
+        values: &HashMap<String, String>,
+    ) -> Result<Vec<TemplatePlaceholder>, AppError> {
+        Ok(missing(&find(state, template_id)?, values))
+    }
+
+    /// 用填写的值替换占位符并创建供应商，返回新供应商
+    ///
+    /// 任一必填占位符为空时不创建，错误中附带缺失项列表。
+    pub fn instantiate(
+        state: &AppState,
+        template_id: &str,
+        values: &HashMap<String, String>,
+        name: Option<&str>,
+    ) -> Result<Provider, AppError> {
+        let template = find(state, template_id)?;
+        let missing = missing(&template, values);
+        if !missing.is_empty() {
+            let missing = serde_json::to_value(&missing)
+                .map_err(|e| AppError::JsonSerialize { source: e })?;
+            return Err(AppError::missing_placeholders(template_id, missing));
+        }
+
+        let resolved: HashMap<&str, String> = template
+            .placeholders
+            .iter()
+            .filter_map(|p| {
+                let value = filled(values, &p.key)
+                    .map(str::to_string)
+                    .or_else(|| p.default_value.clone())?;
+                Some((p.key.as_str(), value))
+            })
+            .collect();
+
+        let name = name
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+            .unwrap_or(template.name.as_str())
+            .to_string();
+        let sanitized: String = name
+            .chars()
+            .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+            .collect::<String>()
+            .to_lowercase();
+        let id = format!(
+            "{}-{}",
+            if sanitized.is_empty() {
+                template.id.as_str()
+            } else {
+                sanitized.as_str()
+            },
+            chrono::Utc::now().timestamp_millis()
+        );
+
+        let mut provider = Provider::with_id(
+            id,
+            name,
+            substitute(&template.settings_config, &resolved),
+            Some(template.website_url.clone()).filter(|url| !url.is_empty()),
+        );
+        provider.icon = template.icon.clone();
+        provider.created_at = Some(chrono::Utc::now().timestamp_millis());
+
+        let app_type = AppType::from_str(&template.app)?;
+        ProviderService::add(state, app_type, provider.clone())?;
+        Ok(provider)
+    }
+}
+
+fn find(state: &AppState, template_id: &str) -> Result<ProviderTemplate, AppError> {
+    ProviderPresetService::templates(state)?
+        .into_iter()
+        .find(|t| t.id == template_id)
+        .ok_or_else(|| {
+            AppError::localized(
+                "provider_template.not_found",
+                format!("未找到供应商模板: {template_id}"),
+                format!("Provider template not found: {template_id}"),
+            )
+        })
+}
+
+fn filled<'a>(values: &'a HashMap<String, String>, key: &str) -> Option<&'a str> {
+    values.get(key).map(|v| v.trim()).filter(|v| !v.is_empty())
+}
+
+fn missing(
+    template: &ProviderTemplate,
+    values: &HashMap<String, String>,
+) -> Vec<TemplatePlaceholder> {
+    template
+        .placeholders
+        .iter()
+        .filter(|p| p.default_value.is_none() && filled(values, &p.key).is_none())
+        .cloned()
+        .collect()
+}
+
+/// 递归替换所有字符串中的 `${KEY}`
+fn substitute(value: &Value, values: &HashMap<&str, String>) -> Value {
+    match value {
+        Value::String(text) => {
+            let mut text = text.clone();
+            for (key, replacement) in values {
+                text = text.replace(&format!("${{{key}}}"), replacement);
+            }
+            Value::String(text)
+        }
+        Value::Array(items) => Value::Array(items.iter().map(|v| substitute(v, values)).collect()),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), substitute(v, values)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider_templates::PROVIDER_TEMPLATES;
+
+    #[test]
+    fn placeholders_must_be_filled_before_substitution() {
+        let template = PROVIDER_TEMPLATES
+            .iter()
+            .find(|t| t.id == "codex-azure-openai")
+            .unwrap();
+        let mut values = HashMap::from([("API_KEY".to_string(), "  ".to_string())]);
+        let missing_keys = missing(template, &values);
+        let keys: Vec<_> = missing_keys.iter().map(|p| p.key.as_str()).collect();
+        // DEPLOYMENT 有默认值，不算缺失；空白视为未填写
+        assert_eq!(keys, ["RESOURCE_NAME", "API_KEY"]);
+
+        values.insert("API_KEY".to_string(), "sk-test".to_string());
+        values.insert("RESOURCE_NAME".to_string(), "acme".to_string());
+        assert!(missing(template, &values).is_empty());
+
+        let resolved = HashMap::from([
+            ("RESOURCE_NAME", "acme".to_string()),
+            ("API_KEY", "sk-test".to_string()),
+        ]);
+        let config = substitute(&template.settings_config, &resolved);
+        assert_eq!(config["auth"]["OPENAI_API_KEY"], "sk-test");
+        assert!(config["config"]
+            .as_str()
+            .unwrap()
+            .contains("https://acme.openai.azure.com/openai"));
+    }
+
+    #[test]
+    fn builtin_templates_declare_every_placeholder_they_use() {
+        let mut ids = std::collections::HashSet::new();
+        for template in PROVIDER_TEMPLATES.iter() {
+            assert!(
+                ids.insert(template.id.as_str()),
+                "重复的模板 id: {}",
+                template.id
+            );
+            assert!(template.id.starts_with(&format!("{}-", template.app)));
+
+            // 用全部占位符替换后不应残留 `${...}`
+            let resolved: HashMap<&str, String> = template
+                .placeholders
+                .iter()
+                .map(|p| (p.key.as_str(), "value".to_string()))
+                .collect();
+            let config = substitute(&template.settings_config, &resolved).to_string();
+            assert!(!config.contains("${"), "{} 有未声明的占位符", template.id);
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::services::provider::ClaudeModelNormalizer;
+use crate::store::AppState;
+
+/// 各应用已应用的配置迁移版本与最近一次升级报告在 settings 表中的键
+pub(crate) const UPGRADE_KEY: &str = "provider_settings_upgrade";
+
+/// 供应商配置的一步迁移：改写 settings_config，有改动时返回 true
+///
+/// 迁移必须幂等，新增或编辑供应商时也会整体再跑一遍
+struct SettingsMigration {
+    app: AppType,
+    version: u32,
+    name: &'static str,
+    apply: fn(&mut Value) -> bool,
+}
+
+/// 按应用、版本递增排列；新增迁移只能追加更大的版本号
+const MIGRATIONS: &[SettingsMigration] = &[SettingsMigration {
+    app: AppType::Claude,
+    version: 1,
+    name: "claude-default-model-keys",
+    apply: ClaudeModelNormalizer::normalize_claude_models_in_value,
+}];
+
+/// 一个被改写的供应商及其应用的迁移
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderRewrite {
+    pub app: String,
+    pub provider_id: String,
+    pub provider_name: String,
+    pub migrations: Vec<String>,
+}
+
+/// 升级后启动时的一次迁移结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderUpgradeReport {
+    pub app_version: String,
+    pub upgraded_at: i64,
+    /// 应用 -> (原版本, 新版本)
+    pub versions: IndexMap<String, (u32, u32)>,
+    pub rewritten: Vec<ProviderRewrite>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpgradeState {
+    #[serde(default)]
+    versions: IndexMap<String, u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_report: Option<ProviderUpgradeReport>,
+}
+
+/// 应用升级后把数据库中所有供应商的配置迁移到当前格式
+pub struct ProviderUpgradeService;
+
+impl ProviderUpgradeService {
+    /// 对单个供应商应用该应用的全部迁移，返回生效的迁移名称
+    pub fn apply_latest(app_type: &AppType, provider: &mut Provider) -> Vec<&'static str> {
+        Self::apply_from(app_type, 0, provider)
+    }
+
+    fn apply_from(app_type: &AppType, from: u32, provider: &mut Provider) -> Vec<&'static str> {
+        MIGRATIONS
+            .iter()
+            .filter(|m| &m.app == app_type && m.version > from)
+            .filter(|m| (m.apply)(&mut provider.settings_config))
+            .map(|m| m.name)
+            .collect()
+    }
+
+    fn latest_version(app_type: &AppType) -> u32 {
+        MIGRATIONS
+            .iter()
+            .filter(|m| &m.app == app_type)
+            .map(|m| m.version)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// 执行尚未应用的迁移；没有待执行的迁移时返回 None
+    ///
+    /// 只改写数据库中的配置，live 文件在下次切换时按新格式写入。
+    /// 单个供应商保存失败只记录日志，该应用的版本号不前移，下次启动重试
+    pub fn run(state: &AppState) -> Result<Option<ProviderUpgradeReport>, AppError> {
+        let mut upgrade = Self::load(state)?;
+        let mut report = ProviderUpgradeReport {
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            upgraded_at: chrono::Utc::now().timestamp_millis(),
+            ..Default::default()
+        };
+
+        for app_type in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+            let app = app_type.as_str();
+            let from = upgrade.versions.get(app).copied().unwrap_or(0);
+            let to = Self::latest_version(&app_type);
+            if to <= from {
+                continue;
+            }
+
+            let mut failed = false;
+            for (id, mut provider) in state.storage.get_all_providers(app)? {
+                let applied = Self::apply_from(&app_type, from, &mut provider);
+                if applied.is_empty() {
+                    continue;
+                }
+                if let Err(e) = state.storage.save_provider(app, &provider) {
+                    log::warn!("迁移 {app} 供应商 {id} 的配置失败: {e}");
+                    failed = true;
+                    continue;
+                }
+                report.rewritten.push(ProviderRewrite {
+                    app: app.to_string(),
+                    provider_id: id,
+                    provider_name: provider.name,
+                    migrations: applied.into_iter().map(str::to_string).collect(),
+                });
+            }
+            if !failed {
+                upgrade.versions.insert(app.to_string(), to);
+                report.versions.insert(app.to_string(), (from, to));
+            }
+        }
+
+        if report.versions.is_empty() {
+            return Ok(None);
+        }
+        log::info!(
+            "供应商配置已迁移到当前版本，改写 {} 个供应商",
+            report.rewritten.len()
+        );
+        upgrade.last_report = Some(report.clone());
+        Self::store(state, &upgrade)?;
+        Ok(Some(report))
+    }
+
+    /// 最近一次升级迁移的报告
+    pub fn last_report(state: &AppState) -> Result<Option<ProviderUpgradeReport>, AppError> {
+        Ok(Self::load(state)?.last_report)
+    }
+
+    fn load(state: &AppState) -> Result<UpgradeState, AppError> {
+        let Some(raw) = state.storage.get_setting(UPGRADE_KEY)? else {
+            return Ok(UpgradeState::default());
+        };
+        serde_json::from_str(&raw)
+            .map_err(|e| AppError::Config(format!("解析供应商配置迁移状态失败: {e}")))
+    }
+
+    fn store(state: &AppState, upgrade: &UpgradeState) -> Result<(), AppError> {
+        let json =
+            serde_json::to_string(upgrade).map_err(|e| AppError::JsonSerialize { source: e })?;
+        state.storage.set_setting(UPGRADE_KEY, &json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    #[test]
+    fn rewrites_legacy_providers_once_and_reports_them() {
+        let state = AppState::new(Arc::new(Database::memory().unwrap()));
+        let legacy = Provider::with_id(
+            "old".to_string(),
+            "Old".to_string(),
+            json!({ "env": { "ANTHROPIC_SMALL_FAST_MODEL": "claude-haiku" } }),
+            None,
+        );
+        state.storage.save_provider("claude", &legacy).unwrap();
+
+        let report = ProviderUpgradeService::run(&state).unwrap().unwrap();
+        assert_eq!(report.versions.get("claude"), Some(&(0, 1)));
+        assert_eq!(report.rewritten.len(), 1);
+        assert_eq!(
+            report.rewritten[0].migrations,
+            ["claude-default-model-keys"]
+        );
+
+        let saved = state.storage.get_all_providers("claude").unwrap();
+        let env = &saved["old"].settings_config["env"];
+        assert!(env.get("ANTHROPIC_SMALL_FAST_MODEL").is_none());
+        assert_eq!(env["ANTHROPIC_DEFAULT_HAIKU_MODEL"], "claude-haiku");
+
+        // 版本已记录，再次启动不会重复执行
+        assert!(ProviderUpgradeService::run(&state).unwrap().is_none());
+        let last = ProviderUpgradeService::last_report(&state)
+            .unwrap()
+            .unwrap();
+        assert_eq!(last.rewritten, report.rewritten);
+    }
+}
@@ -0,0 +1,351 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::services::{McpService, PromptService, ProviderService};
+use crate::store::AppState;
+
+const DEFAULT_LIMIT: usize = 50;
+const ALL_APPS: [AppType; 3] = [AppType::Claude, AppType::Codex, AppType::Gemini];
+
+/// 快捷操作类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum QuickActionKind {
+    /// 切换到指定供应商
+    SwitchProvider,
+    /// 为指定应用启用/停用 MCP 服务器
+    ToggleMcp,
+    /// 启用提示词
+    EnablePrompt,
+    /// 打开应用配置目录
+    OpenConfigFolder,
+}
+
+impl QuickActionKind {
+    fn prefix(&self) -> &'static str {
+        match self {
+            QuickActionKind::SwitchProvider => "provider",
+            QuickActionKind::ToggleMcp => "mcp",
+            QuickActionKind::EnablePrompt => "prompt",
+            QuickActionKind::OpenConfigFolder => "folder",
+        }
+    }
+
+    /// 参与模糊匹配的动词，方便输入 "switch"、"enable" 等检索
+    fn keywords(&self) -> &'static str {
+        match self {
+            QuickActionKind::SwitchProvider => "switch provider",
+            QuickActionKind::ToggleMcp => "mcp enable disable toggle",
+            QuickActionKind::EnablePrompt => "prompt enable",
+            QuickActionKind::OpenConfigFolder => "open config folder",
+        }
+    }
+
+    /// 等价的 Tauri 命令名，供 API 令牌按命令校验权限
+    pub fn command(&self) -> &'static str {
+        match self {
+            QuickActionKind::SwitchProvider => "switch_provider",
+            QuickActionKind::ToggleMcp => "toggle_mcp_app",
+            QuickActionKind::EnablePrompt => "enable_prompt",
+            QuickActionKind::OpenConfigFolder => "open_config_folder",
+        }
+    }
+}
+
+/// 命令面板中的一条可执行操作
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickAction {
+    /// 形如 `provider:claude:<id>`，原样传给 `execute_quick_action`
+    pub id: String,
+    pub kind: QuickActionKind,
+    pub app: String,
+    /// 目标对象 ID（打开目录时为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_id: Option<String>,
+    /// 目标对象显示名
+    pub label: String,
+    /// 执行后的启用状态（仅 MCP 切换）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enable: Option<bool>,
+    pub score: i64,
+}
+
+/// 执行结果，供前端刷新对应视图
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickActionOutcome {
+    pub kind: QuickActionKind,
+    pub app: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_id: Option<String>,
+    /// 需要由调用方打开的目录
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub open_path: Option<String>,
+}
+
+pub struct QuickActionService;
+
+impl QuickActionService {
+    /// 按查询字符串模糊匹配所有可执行操作，按得分降序返回
+    pub fn search(
+        state: &AppState,
+        query: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<QuickAction>, AppError> {
+        let query = query.trim().to_lowercase();
+        let actions = Self::all_actions(state, false)?;
+
+        let mut matched: Vec<QuickAction> = actions
+            .into_iter()
+            .filter_map(|mut action| {
+                action.score = Self::score_action(&action, &query)?;
+                Some(action)
+            })
+            .collect();
+
+        matched.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| a.label.to_lowercase().cmp(&b.label.to_lowercase()))
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        matched.truncate(limit.unwrap_or(DEFAULT_LIMIT));
+
+        Ok(matched)
+    }
+
+    /// 列出全部操作；`include_active` 为 false 时跳过当前供应商和已启用的提示词
+    pub(crate) fn all_actions(
+        state: &AppState,
+        include_active: bool,
+    ) -> Result<Vec<QuickAction>, AppError> {
+        let mut actions = Vec::new();
+
+        for app in ALL_APPS {
+            let current = ProviderService::current(state, app.clone())?;
+            for (id, provider) in ProviderService::list(state, app.clone())? {
+                if id == current && !include_active {
+                    continue;
+                }
+                actions.push(Self::candidate(
+                    QuickActionKind::SwitchProvider,
+                    &app,
+                    Some(id),
+                    provider.name,
+                    None,
+                ));
+            }
+
+            for prompt in PromptService::get_prompt_summaries(state, app.clone(), None, None)? {
+                if prompt.enabled && !include_active {
+                    continue;
+                }
+                actions.push(Self::candidate(
+                    QuickActionKind::EnablePrompt,
+                    &app,
+                    Some(prompt.id),
+                    prompt.name,
+                    None,
+                ));
+            }
+
+            actions.push(Self::candidate(
+                QuickActionKind::OpenConfigFolder,
+                &app,
+                None,
+                app.as_str().to_string(),
+                None,
+            ));
+        }
+
+        for (id, server) in McpService::get_all_servers(state)? {
+            for app in ALL_APPS {
+                let enable = !server.apps.is_enabled_for(&app);
+                actions.push(Self::candidate(
+                    QuickActionKind::ToggleMcp,
+                    &app,
+                    Some(id.clone()),
+                    server.name.clone(),
+                    Some(enable),
+                ));
+            }
+        }
+
+        Ok(actions)
+    }
+
+    /// 按 ID 执行操作；打开目录需要窗口句柄，由调用方根据 `open_path` 处理
+    pub fn execute(state: &AppState, action_id: &str) -> Result<QuickActionOutcome, AppError> {
+        let (kind, app, target_id) = Self::parse_action_id(action_id)?;
+
+        let mut outcome = QuickActionOutcome {
+            kind,
+            app: app.as_str().to_string(),
+            target_id: target_id.clone(),
+            open_path: None,
+        };
+
+        match (kind, target_id.as_deref()) {
+            (QuickActionKind::SwitchProvider, Some(id)) => {
+                ProviderService::switch(state, app, id)?;
+            }
+            (QuickActionKind::EnablePrompt, Some(id)) => {
+                PromptService::enable_prompt(state, app, id)?;
+            }
+            (QuickActionKind::ToggleMcp, Some(id)) => {
+                let server = McpService::get_all_servers(state)?
+                    .shift_remove(id)
+                    .ok_or_else(|| AppError::InvalidInput(format!("MCP server not found: {id}")))?;
+                let enable = !server.apps.is_enabled_for(&app);
+                McpService::toggle_app(state, id, app, enable)?;
+            }
+            (QuickActionKind::OpenConfigFolder, _) => {
+                outcome.open_path = Some(config_dir(&app).to_string_lossy().to_string());
+            }
+            (_, None) => {
+                return Err(AppError::InvalidInput(format!(
+                    "Invalid quick action id: {action_id}"
+                )))
+            }
+        }
+
+        Ok(outcome)
+    }
+
+    fn candidate(
+        kind: QuickActionKind,
+        app: &AppType,
+        target_id: Option<String>,
+        label: String,
+        enable: Option<bool>,
+    ) -> QuickAction {
+        let id = match target_id.as_deref() {
+            Some(target) => format!("{}:{}:{}", kind.prefix(), app.as_str(), target),
+            None => format!("{}:{}", kind.prefix(), app.as_str()),
+        };
+        QuickAction {
+            id,
+            kind,
+            app: app.as_str().to_string(),
+            target_id,
+            label,
+            enable,
+            score: 0,
+        }
+    }
+
+    /// 显示名命中权重更高，其次才是 "动词 + 应用 + 名称" 的整体文本
+    fn score_action(action: &QuickAction, query: &str) -> Option<i64> {
+        if query.is_empty() {
+            return Some(0);
+        }
+
+        let label_score = fuzzy_score(&action.label, query).map(|s| s * 2);
+        let haystack = format!("{} {} {}", action.kind.keywords(), action.app, action.label);
+        let full_score = fuzzy_score(&haystack, query);
+
+        label_score.max(full_score)
+    }
+
+    pub(crate) fn parse_action_id(
+        action_id: &str,
+    ) -> Result<(QuickActionKind, AppType, Option<String>), AppError> {
+        let invalid = || AppError::InvalidInput(format!("Invalid quick action id: {action_id}"));
+
+        let mut parts = action_id.splitn(3, ':');
+        let kind = match parts.next().ok_or_else(invalid)? {
+            "provider" => QuickActionKind::SwitchProvider,
+            "mcp" => QuickActionKind::ToggleMcp,
+            "prompt" => QuickActionKind::EnablePrompt,
+            "folder" => QuickActionKind::OpenConfigFolder,
+            _ => return Err(invalid()),
+        };
+        let app = AppType::from_str(parts.next().ok_or_else(invalid)?)?;
+        let target_id = parts.next().filter(|id| !id.is_empty()).map(String::from);
+
+        Ok((kind, app, target_id))
+    }
+}
+
+fn config_dir(app: &AppType) -> PathBuf {
+    match app {
+        AppType::Claude => crate::config::get_claude_config_dir(),
+        AppType::Codex => crate::codex_config::get_codex_config_dir(),
+        AppType::Gemini => crate::gemini_config::get_gemini_dir(),
+    }
+}
+
+/// 子序列模糊匹配：所有查询字符需按顺序出现
+///
+/// 连续命中与单词开头命中加分，跨越的字符扣分；不匹配返回 None。
+fn fuzzy_score(text: &str, query: &str) -> Option<i64> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0i64;
+    let mut pos = 0usize;
+    let mut prev_match: Option<usize> = None;
+
+    for qc in query.chars().filter(|c| !c.is_whitespace()) {
+        let idx = pos + text[pos..].iter().position(|&c| c == qc)?;
+
+        score += 1;
+        if prev_match.is_some_and(|prev| prev + 1 == idx) {
+            score += 5;
+        }
+        if idx == 0 || !text[idx - 1].is_alphanumeric() {
+            score += 3;
+        }
+        if let Some(prev) = prev_match {
+            score -= (idx - prev - 1).min(3) as i64;
+        }
+
+        prev_match = Some(idx);
+        pos = idx + 1;
+    }
+
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_score_prefers_contiguous_matches() {
+        assert!(fuzzy_score("OpenRouter", "xyz").is_none());
+        assert!(fuzzy_score("OpenRouter", "rto").is_none());
+
+        let contiguous = fuzzy_score("OpenRouter", "open").unwrap();
+        let scattered = fuzzy_score("Other Provider Engine", "open").unwrap();
+        assert!(contiguous > scattered);
+
+        let word_start = fuzzy_score("open router", "or").unwrap();
+        let mid_word = fuzzy_score("color", "or").unwrap();
+        assert!(word_start > mid_word);
+    }
+
+    #[test]
+    fn test_parse_action_id_round_trip() {
+        let action = QuickActionService::candidate(
+            QuickActionKind::SwitchProvider,
+            &AppType::Codex,
+            Some("id:with:colons".to_string()),
+            "Test".to_string(),
+            None,
+        );
+        let (kind, app, target) = QuickActionService::parse_action_id(&action.id).unwrap();
+        assert_eq!(kind, QuickActionKind::SwitchProvider);
+        assert_eq!(app, AppType::Codex);
+        assert_eq!(target.as_deref(), Some("id:with:colons"));
+
+        let (kind, _, target) = QuickActionService::parse_action_id("folder:gemini").unwrap();
+        assert_eq!(kind, QuickActionKind::OpenConfigFolder);
+        assert!(target.is_none());
+
+        assert!(QuickActionService::parse_action_id("unknown:claude:x").is_err());
+        assert!(QuickActionService::parse_action_id("provider:vim:x").is_err());
+    }
+}
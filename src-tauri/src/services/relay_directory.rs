@@ -0,0 +1,456 @@
+use regex::Regex;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use url::Url;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::provider_defaults::DEFAULT_PROVIDER_ICONS;
+use crate::store::AppState;
+
+/// 远程目录缓存在 settings 表中的键
+pub(crate) const CACHE_KEY: &str = "relay_directory_cache";
+const FETCH_TIMEOUT_SECS: u64 = 15;
+
+/// 已知中转 / 服务商条目，按端点域名匹配
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RelayEntry {
+    /// 匹配的域名（同时匹配其子域名）
+    pub domains: Vec<String>,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub homepage: Option<String>,
+    /// 图标名称，颜色缺省时取内置图标颜色
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon_color: Option<String>,
+    /// 用量查询模板（与前端模板键一致，如 `general`、`newapi`）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage_preset: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docs_url: Option<String>,
+}
+
+/// 远程目录格式：直接为数组，或 `{ "entries": [...] }`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RemoteDirectory {
+    List(Vec<RelayEntry>),
+    Wrapped { entries: Vec<RelayEntry> },
+}
+
+fn entry(
+    domains: &[&str],
+    name: &str,
+    homepage: &str,
+    icon: Option<&str>,
+    usage_preset: Option<&str>,
+    docs_url: Option<&str>,
+) -> RelayEntry {
+    RelayEntry {
+        domains: domains.iter().map(|d| d.to_string()).collect(),
+        name: name.to_string(),
+        homepage: Some(homepage.to_string()),
+        icon: icon.map(str::to_string),
+        icon_color: None,
+        usage_preset: usage_preset.map(str::to_string),
+        docs_url: docs_url.map(str::to_string),
+    }
+}
+
+/// 内置目录（与前端供应商预设保持一致）
+fn builtin_entries() -> Vec<RelayEntry> {
+    vec![
+        entry(
+            &["anthropic.com"],
+            "Anthropic",
+            "https://www.anthropic.com/claude-code",
+            Some("anthropic"),
+            None,
+            Some("https://docs.anthropic.com/en/docs/claude-code/overview"),
+        ),
+        entry(
+            &["openai.com"],
+            "OpenAI",
+            "https://chatgpt.com/codex",
+            Some("openai"),
+            None,
+            Some("https://developers.openai.com/codex"),
+        ),
+        entry(
+            &["googleapis.com"],
+            "Google AI",
+            "https://ai.google.dev",
+            Some("gemini"),
+            None,
+            Some("https://ai.google.dev/gemini-api/docs"),
+        ),
+        entry(
+            &["deepseek.com"],
+            "DeepSeek",
+            "https://platform.deepseek.com",
+            Some("deepseek"),
+            None,
+            Some("https://api-docs.deepseek.com"),
+        ),
+        entry(
+            &["bigmodel.cn"],
+            "Zhipu GLM",
+            "https://open.bigmodel.cn",
+            Some("zhipu"),
+            None,
+            None,
+        ),
+        entry(
+            &["z.ai"],
+            "Z.ai GLM",
+            "https://z.ai",
+            Some("zhipu"),
+            None,
+            None,
+        ),
+        entry(
+            &["aliyuncs.com"],
+            "Qwen Coder",
+            "https://bailian.console.aliyun.com",
+            Some("alibaba"),
+            None,
+            None,
+        ),
+        entry(
+            &["moonshot.cn"],
+            "Kimi k2",
+            "https://platform.moonshot.cn/console",
+            Some("moonshot"),
+            None,
+            None,
+        ),
+        entry(
+            &["kimi.com"],
+            "Kimi For Coding",
+            "https://www.kimi.com/coding/docs/",
+            Some("kimi"),
+            None,
+            Some("https://www.kimi.com/coding/docs/"),
+        ),
+        entry(
+            &["modelscope.cn"],
+            "ModelScope",
+            "https://modelscope.cn",
+            None,
+            None,
+            None,
+        ),
+        entry(
+            &["streamlake.ai"],
+            "KAT-Coder",
+            "https://console.streamlake.ai",
+            None,
+            None,
+            None,
+        ),
+        entry(
+            &["longcat.chat"],
+            "Longcat",
+            "https://longcat.chat/platform",
+            None,
+            None,
+            None,
+        ),
+        entry(
+            &["minimaxi.com", "minimax.io"],
+            "MiniMax",
+            "https://platform.minimaxi.com",
+            Some("minimax"),
+            None,
+            None,
+        ),
+        entry(
+            &["volces.com"],
+            "DouBaoSeed",
+            "https://www.volcengine.com/product/doubao",
+            None,
+            None,
+            None,
+        ),
+        entry(
+            &["aihubmix.com"],
+            "AiHubMix",
+            "https://aihubmix.com",
+            None,
+            None,
+            None,
+        ),
+        entry(
+            &["dmxapi.cn"],
+            "DMXAPI",
+            "https://www.dmxapi.cn",
+            None,
+            None,
+            None,
+        ),
+        entry(
+            &["packyapi.com", "packycode.com"],
+            "PackyCode",
+            "https://www.packyapi.com",
+            None,
+            Some("newapi"),
+            None,
+        ),
+    ]
+}
+
+/// 根据端点域名补全供应商元数据（官网、图标、用量模板、文档链接）
+pub struct RelayDirectoryService;
+
+impl RelayDirectoryService {
+    /// 当前生效的目录：远程缓存覆盖内置条目中相同域名的部分
+    pub fn entries(state: &AppState) -> Result<Vec<RelayEntry>, AppError> {
+        let mut entries = Self::cached(state)?;
+        let remote_domains: Vec<String> = entries
+            .iter()
+            .flat_map(|e| e.domains.iter().cloned())
+            .collect();
+        entries.extend(
+            builtin_entries()
+                .into_iter()
+                .filter(|e| !e.domains.iter().any(|d| remote_domains.contains(d))),
+        );
+        Ok(entries)
+    }
+
+    /// 从远程地址拉取目录并缓存，返回条目数量
+    pub async fn update_from_url(state: &AppState, url: &str) -> Result<usize, AppError> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(FETCH_TIMEOUT_SECS))
+            .build()
+            .map_err(|e| AppError::Message(format!("创建 HTTP 客户端失败: {e}")))?;
+        let text = client
+            .get(url)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| {
+                AppError::localized(
+                    "relay_directory.fetch_failed",
+                    format!("获取中转目录失败: {e}"),
+                    format!("Failed to fetch relay directory: {e}"),
+                )
+            })?
+            .text()
+            .await
+            .map_err(|e| AppError::Message(format!("读取中转目录失败: {e}")))?;
+
+        let entries = parse_directory(&text)?;
+        let json =
+            serde_json::to_string(&entries).map_err(|e| AppError::JsonSerialize { source: e })?;
+        state.storage.set_setting(CACHE_KEY, &json)?;
+        Ok(entries.len())
+    }
+
+    /// 查找与供应商端点匹配的条目
+    pub fn lookup(
+        state: &AppState,
+        app_type: &AppType,
+        provider: &Provider,
+    ) -> Result<Option<RelayEntry>, AppError> {
+        let Some(host) = provider_endpoint(app_type, provider).and_then(|e| endpoint_host(&e))
+        else {
+            return Ok(None);
+        };
+        Ok(Self::entries(state)?
+            .into_iter()
+            .find(|entry| entry.domains.iter().any(|d| domain_matches(&host, d))))
+    }
+
+    /// 仅补全缺失字段，不覆盖用户已填写的内容；返回是否有改动
+    pub fn enrich(
+        state: &AppState,
+        app_type: &AppType,
+        provider: &mut Provider,
+    ) -> Result<bool, AppError> {
+        let Some(entry) = Self::lookup(state, app_type, provider)? else {
+            return Ok(false);
+        };
+        Ok(apply_entry(provider, &entry))
+    }
+
+    /// 重新匹配并补全已保存供应商的元数据
+    pub fn refresh_provider(
+        state: &AppState,
+        app_type: AppType,
+        id: &str,
+    ) -> Result<Provider, AppError> {
+        let mut provider = state
+            .storage
+            .get_all_providers(app_type.as_str())?
+            .shift_remove(id)
+            .ok_or_else(|| {
+                AppError::localized(
+                    "provider.not_found",
+                    format!("供应商不存在: {id}"),
+                    format!("Provider not found: {id}"),
+                )
+            })?;
+        if Self::enrich(state, &app_type, &mut provider)? {
+            state.storage.save_provider(app_type.as_str(), &provider)?;
+        }
+        Ok(provider)
+    }
+
+    fn cached(state: &AppState) -> Result<Vec<RelayEntry>, AppError> {
+        let Some(raw) = state.storage.get_setting(CACHE_KEY)? else {
+            return Ok(Vec::new());
+        };
+        Ok(serde_json::from_str(&raw).unwrap_or_else(|e| {
+            log::warn!("中转目录缓存已损坏，忽略: {e}");
+            Vec::new()
+        }))
+    }
+}
+
+fn parse_directory(text: &str) -> Result<Vec<RelayEntry>, AppError> {
+    let directory: RemoteDirectory = serde_json::from_str(text).map_err(|e| {
+        AppError::localized(
+            "relay_directory.invalid",
+            format!("中转目录格式错误: {e}"),
+            format!("Invalid relay directory: {e}"),
+        )
+    })?;
+    let entries = match directory {
+        RemoteDirectory::List(entries) | RemoteDirectory::Wrapped { entries } => entries,
+    };
+    Ok(entries
+        .into_iter()
+        .map(|mut e| {
+            e.domains = e
+                .domains
+                .iter()
+                .map(|d| d.trim().trim_start_matches("www.").to_ascii_lowercase())
+                .filter(|d| !d.is_empty())
+                .collect();
+            e
+        })
+        .filter(|e| !e.domains.is_empty())
+        .collect())
+}
+
+fn apply_entry(provider: &mut Provider, entry: &RelayEntry) -> bool {
+    let mut changed = false;
+    let is_blank = |v: &Option<String>| !v.as_deref().is_some_and(|s| !s.trim().is_empty());
+
+    if is_blank(&provider.website_url) && entry.homepage.is_some() {
+        provider.website_url = entry.homepage.clone();
+        changed = true;
+    }
+    if is_blank(&provider.icon) {
+        if let Some(icon) = &entry.icon {
+            provider.icon = Some(icon.clone());
+            provider.icon_color = entry.icon_color.clone().or_else(|| {
+                DEFAULT_PROVIDER_ICONS
+                    .get(icon.as_str())
+                    .map(|i| i.color.to_string())
+            });
+            changed = true;
+        }
+    }
+
+    let meta = provider.meta.get_or_insert_with(Default::default);
+    if meta.docs_url.is_none() && entry.docs_url.is_some() {
+        meta.docs_url = entry.docs_url.clone();
+        changed = true;
+    }
+    // 已配置用量脚本时无需再提示模板
+    if meta.usage_preset.is_none() && meta.usage_script.is_none() && entry.usage_preset.is_some() {
+        meta.usage_preset = entry.usage_preset.clone();
+        changed = true;
+    }
+    changed
+}
+
+/// 读取供应商配置中的 API 端点
+fn provider_endpoint(app_type: &AppType, provider: &Provider) -> Option<String> {
+    let settings = &provider.settings_config;
+    let value = match app_type {
+        AppType::Claude => settings
+            .pointer("/env/ANTHROPIC_BASE_URL")?
+            .as_str()?
+            .to_string(),
+        AppType::Gemini => settings
+            .pointer("/env/GOOGLE_GEMINI_BASE_URL")?
+            .as_str()?
+            .to_string(),
+        AppType::Codex => {
+            let config = settings.get("config")?.as_str()?;
+            let re = Regex::new(r#"base_url\s*=\s*["']([^"']+)["']"#).ok()?;
+            re.captures(config)?.get(1)?.as_str().to_string()
+        }
+    };
+    Some(value)
+}
+
+fn endpoint_host(endpoint: &str) -> Option<String> {
+    let url = Url::parse(endpoint.trim()).ok()?;
+    let host = url.host_str()?.to_ascii_lowercase();
+    Some(host.trim_start_matches("www.").to_string())
+}
+
+fn domain_matches(host: &str, domain: &str) -> bool {
+    host == domain
+        || host
+            .strip_suffix(domain)
+            .is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn domain_matching_covers_subdomains_only() {
+        assert!(domain_matches("api.deepseek.com", "deepseek.com"));
+        assert!(domain_matches("deepseek.com", "deepseek.com"));
+        assert!(!domain_matches("notdeepseek.com", "deepseek.com"));
+    }
+
+    #[test]
+    fn apply_entry_fills_only_missing_fields() {
+        let entry = builtin_entries()
+            .into_iter()
+            .find(|e| e.name == "DeepSeek")
+            .unwrap();
+        let mut provider = Provider::with_id(
+            "ds".into(),
+            "DS".into(),
+            json!({ "env": { "ANTHROPIC_BASE_URL": "https://api.deepseek.com/anthropic" } }),
+            Some("https://my.example".into()),
+        );
+        assert_eq!(
+            provider_endpoint(&AppType::Claude, &provider).and_then(|e| endpoint_host(&e)),
+            Some("api.deepseek.com".to_string())
+        );
+
+        assert!(apply_entry(&mut provider, &entry));
+        assert_eq!(provider.website_url.as_deref(), Some("https://my.example"));
+        assert_eq!(provider.icon.as_deref(), Some("deepseek"));
+        assert_eq!(provider.icon_color.as_deref(), Some("#1E88E5"));
+        assert!(provider.meta.unwrap().docs_url.is_some());
+    }
+
+    #[test]
+    fn remote_directory_accepts_wrapped_and_plain_lists() {
+        let plain = r#"[{ "domains": ["WWW.Relay.Example"], "name": "Relay" }]"#;
+        let wrapped = r#"{ "version": 2, "entries": [{ "domains": [" "], "name": "Empty" }] }"#;
+        assert_eq!(
+            parse_directory(plain).unwrap()[0].domains,
+            ["relay.example"]
+        );
+        assert!(parse_directory(wrapped).unwrap().is_empty());
+        assert!(parse_directory("{}").is_err());
+    }
+}
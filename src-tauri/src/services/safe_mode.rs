@@ -0,0 +1,81 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::error::AppError;
+
+/// 设置为 1/true 时以安全模式启动
+pub const SAFE_MODE_ENV: &str = "CLI_HUB_SAFE_MODE";
+/// 等价的命令行参数
+pub const SAFE_MODE_ARG: &str = "--safe-mode";
+
+static SAFE_MODE: AtomicBool = AtomicBool::new(false);
+
+/// 是否处于安全模式：正常读写数据库，但不启动后台任务、不做首次导入，也不写入任何 live 配置
+pub fn is_safe_mode() -> bool {
+    SAFE_MODE.load(Ordering::Relaxed)
+}
+
+/// 安全模式下拒绝写入 live 配置的操作（如恢复管理、修复 live 配置）
+pub fn ensure_not_safe_mode() -> Result<(), AppError> {
+    if is_safe_mode() {
+        return Err(AppError::localized(
+            "safe_mode.action_blocked",
+            "安全模式下不会修改 CLI 配置文件，请修复数据后正常重启应用",
+            "Safe mode does not modify CLI config files; fix the data and restart the app normally",
+        ));
+    }
+    Ok(())
+}
+
+/// 配置损坏时的排障入口：打开界面查看、修正供应商与数据库，而不会在启动时立即重写配置
+pub struct SafeModeService;
+
+impl SafeModeService {
+    /// 环境变量或命令行参数是否要求安全模式
+    pub fn requested() -> bool {
+        Self::requested_by(
+            std::env::var(SAFE_MODE_ENV).ok().as_deref(),
+            std::env::args(),
+        )
+    }
+
+    fn requested_by(env: Option<&str>, mut args: impl Iterator<Item = String>) -> bool {
+        let env_requested = env.is_some_and(|v| {
+            matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes")
+        });
+        env_requested || args.any(|arg| arg == SAFE_MODE_ARG)
+    }
+
+    pub fn enable() {
+        SAFE_MODE.store(true, Ordering::Relaxed);
+        log::warn!("安全模式已启用：跳过后台任务与首次导入，不写入 live 配置");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args<'a>(list: &'a [&str]) -> impl Iterator<Item = String> + 'a {
+        list.iter().map(|s| s.to_string())
+    }
+
+    #[test]
+    fn detects_env_and_cli_flag() {
+        assert!(SafeModeService::requested_by(
+            Some(" TRUE "),
+            args(&["cli-hub"])
+        ));
+        assert!(SafeModeService::requested_by(
+            None,
+            args(&["cli-hub", "--safe-mode"])
+        ));
+        assert!(!SafeModeService::requested_by(
+            Some("0"),
+            args(&["cli-hub"])
+        ));
+        assert!(!SafeModeService::requested_by(
+            None,
+            args(&["clihub://v1/import"])
+        ));
+    }
+}
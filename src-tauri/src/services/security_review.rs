@@ -0,0 +1,349 @@
+use reqwest::Client;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::time::Duration;
+use url::Url;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::services::provider::EndpointManager;
+use crate::store::AppState;
+
+const TLS_PROBE_TIMEOUT_SECS: u64 = 8;
+/// 短于该长度的值视为占位符，不参与密钥复用检查
+const MIN_KEY_LEN: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SecuritySeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SecurityFindingKind {
+    /// 端点使用 http://，密钥以明文传输
+    InsecureEndpoint,
+    /// 同一个 API Key 出现在多个供应商中
+    ReusedKey,
+    /// 备注中疑似写有密钥
+    KeyInNotes,
+    /// 端点的 TLS 证书校验失败
+    InvalidCertificate,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecurityFinding {
+    pub kind: SecurityFindingKind,
+    pub severity: SecuritySeverity,
+    pub app: String,
+    pub provider_id: String,
+    pub provider_name: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// 使用同一密钥的其他供应商，格式为 `app:id`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub related: Vec<String>,
+}
+
+struct Target<'a> {
+    app: &'a AppType,
+    provider: &'a Provider,
+}
+
+impl Target<'_> {
+    fn finding(
+        &self,
+        kind: SecurityFindingKind,
+        severity: SecuritySeverity,
+        message: String,
+    ) -> SecurityFinding {
+        SecurityFinding {
+            kind,
+            severity,
+            app: self.app.as_str().to_string(),
+            provider_id: self.provider.id.clone(),
+            provider_name: self.provider.name.clone(),
+            message,
+            url: None,
+            related: Vec::new(),
+        }
+    }
+}
+
+/// 供应商安全检查：明文端点、密钥复用、备注中的密钥，以及可选的证书探测
+pub struct SecurityReviewService;
+
+impl SecurityReviewService {
+    /// 返回全部发现，按严重程度从高到低排列；probe_tls 为 true 时会请求各 HTTPS 端点
+    pub async fn findings(
+        state: &AppState,
+        probe_tls: bool,
+    ) -> Result<Vec<SecurityFinding>, AppError> {
+        let english = crate::settings::get_effective_settings()
+            .language
+            .as_deref()
+            == Some("en");
+        let mut providers = Vec::new();
+        for app in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+            let all = state.storage.get_all_providers(app.as_str())?;
+            providers.push((app, all.into_values().collect::<Vec<_>>()));
+        }
+        let targets: Vec<Target> = providers
+            .iter()
+            .flat_map(|(app, list)| list.iter().map(move |provider| Target { app, provider }))
+            .collect();
+
+        let mut findings = Vec::new();
+        let mut https_endpoints = Vec::new();
+        for target in &targets {
+            for url in endpoints(target.app, target.provider) {
+                match Url::parse(&url) {
+                    Ok(parsed) if parsed.scheme() == "http" => {
+                        findings.push(insecure_endpoint(target, &parsed, &url, english));
+                    }
+                    Ok(parsed) if parsed.scheme() == "https" => {
+                        https_endpoints.push((target, url));
+                    }
+                    _ => {}
+                }
+            }
+            if let Some(finding) = key_in_notes(target, english) {
+                findings.push(finding);
+            }
+        }
+        findings.extend(reused_keys(&targets, english));
+
+        if probe_tls {
+            let client = Client::builder()
+                .timeout(Duration::from_secs(TLS_PROBE_TIMEOUT_SECS))
+                .build()
+                .map_err(|e| AppError::Message(format!("创建 HTTP 客户端失败: {e}")))?;
+            let lenient = Client::builder()
+                .timeout(Duration::from_secs(TLS_PROBE_TIMEOUT_SECS))
+                .danger_accept_invalid_certs(true)
+                .build()
+                .map_err(|e| AppError::Message(format!("创建 HTTP 客户端失败: {e}")))?;
+            let mut probed: BTreeMap<String, bool> = BTreeMap::new();
+            for (target, url) in https_endpoints {
+                let invalid = match probed.get(&url) {
+                    Some(invalid) => *invalid,
+                    None => {
+                        let invalid = certificate_rejected(&client, &lenient, &url).await;
+                        probed.insert(url.clone(), invalid);
+                        invalid
+                    }
+                };
+                if invalid {
+                    let message = if english {
+                        "TLS certificate validation failed; the connection may be intercepted"
+                    } else {
+                        "TLS 证书校验失败，连接可能被劫持"
+                    };
+                    let mut finding = target.finding(
+                        SecurityFindingKind::InvalidCertificate,
+                        SecuritySeverity::Critical,
+                        message.to_string(),
+                    );
+                    finding.url = Some(url);
+                    findings.push(finding);
+                }
+            }
+        }
+
+        findings.sort_by(|a, b| b.severity.cmp(&a.severity));
+        Ok(findings)
+    }
+}
+
+/// 当前 Base URL 与自定义端点，已去重
+fn endpoints(app: &AppType, provider: &Provider) -> Vec<String> {
+    let mut urls: Vec<String> = EndpointManager::base_url(app, provider)
+        .into_iter()
+        .collect();
+    if let Some(meta) = provider.meta.as_ref() {
+        for url in meta.custom_endpoints.keys() {
+            if !urls.contains(url) {
+                urls.push(url.clone());
+            }
+        }
+    }
+    urls
+}
+
+/// 本机地址（如 Ollama）走 http 很常见，只作提示
+fn insecure_endpoint(target: &Target, parsed: &Url, url: &str, english: bool) -> SecurityFinding {
+    let local = match parsed.host() {
+        Some(url::Host::Domain(domain)) => domain.eq_ignore_ascii_case("localhost"),
+        Some(url::Host::Ipv4(ip)) => ip.is_loopback(),
+        Some(url::Host::Ipv6(ip)) => ip.is_loopback(),
+        None => false,
+    };
+    let (severity, message) = match (local, english) {
+        (true, true) => (SecuritySeverity::Info, "Local endpoint uses plain HTTP"),
+        (true, false) => (SecuritySeverity::Info, "本机端点使用明文 HTTP"),
+        (false, true) => (
+            SecuritySeverity::Critical,
+            "Endpoint uses plain HTTP; the API key is sent unencrypted",
+        ),
+        (false, false) => (
+            SecuritySeverity::Critical,
+            "端点使用明文 HTTP，API Key 会以未加密形式发送",
+        ),
+    };
+    let mut finding = target.finding(
+        SecurityFindingKind::InsecureEndpoint,
+        severity,
+        message.to_string(),
+    );
+    finding.url = Some(url.to_string());
+    finding
+}
+
+fn key_in_notes(target: &Target, english: bool) -> Option<SecurityFinding> {
+    let notes = target.provider.notes.as_deref()?;
+    let own_key = api_key(target.app, target.provider);
+    let leaked = matches!(crate::log_sanitizer::redact(notes), Cow::Owned(_))
+        || own_key.is_some_and(|key| notes.contains(key));
+    leaked.then(|| {
+        let message = if english {
+            "Notes appear to contain an API key; notes are stored and exported in plain text"
+        } else {
+            "备注中疑似包含 API Key，备注会以明文保存和导出"
+        };
+        target.finding(
+            SecurityFindingKind::KeyInNotes,
+            SecuritySeverity::Warning,
+            message.to_string(),
+        )
+    })
+}
+
+/// 每个复用密钥的供应商各生成一条发现，related 中列出其余供应商
+fn reused_keys(targets: &[Target], english: bool) -> Vec<SecurityFinding> {
+    let mut by_key: BTreeMap<&str, Vec<&Target>> = BTreeMap::new();
+    for target in targets {
+        if let Some(key) = api_key(target.app, target.provider) {
+            by_key.entry(key).or_default().push(target);
+        }
+    }
+
+    let mut findings = Vec::new();
+    for group in by_key.values().filter(|g| g.len() > 1) {
+        for target in group {
+            let related: Vec<String> = group
+                .iter()
+                .filter(|other| !std::ptr::eq(**other, *target))
+                .map(|other| format!("{}:{}", other.app.as_str(), other.provider.id))
+                .collect();
+            let message = if english {
+                format!(
+                    "API key is shared with {} other provider(s); revoking it affects all of them",
+                    related.len()
+                )
+            } else {
+                format!(
+                    "API Key 与其他 {} 个供应商共用，吊销时会一并失效",
+                    related.len()
+                )
+            };
+            let mut finding = target.finding(
+                SecurityFindingKind::ReusedKey,
+                SecuritySeverity::Warning,
+                message,
+            );
+            finding.related = related;
+            findings.push(finding);
+        }
+    }
+    findings
+}
+
+fn api_key<'a>(app: &AppType, provider: &'a Provider) -> Option<&'a str> {
+    let settings = &provider.settings_config;
+    let key = match app {
+        AppType::Claude => settings
+            .pointer("/env/ANTHROPIC_AUTH_TOKEN")
+            .or_else(|| settings.pointer("/env/ANTHROPIC_API_KEY")),
+        AppType::Codex => settings.pointer("/auth/OPENAI_API_KEY"),
+        AppType::Gemini => settings.pointer("/env/GEMINI_API_KEY"),
+    }?;
+    key.as_str()
+        .map(str::trim)
+        .filter(|k| k.len() >= MIN_KEY_LEN)
+}
+
+/// 正常校验证书时连接失败、忽略证书后能拿到响应，即判定证书无效
+async fn certificate_rejected(client: &Client, lenient: &Client, url: &str) -> bool {
+    match client.head(url).send().await {
+        Ok(_) => false,
+        Err(e) if e.is_connect() => lenient.head(url).send().await.is_ok(),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn provider(id: &str, base_url: &str, key: &str, notes: Option<&str>) -> Provider {
+        let mut provider = Provider::with_id(
+            id.to_string(),
+            id.to_string(),
+            json!({ "env": { "ANTHROPIC_BASE_URL": base_url, "ANTHROPIC_AUTH_TOKEN": key } }),
+            None,
+        );
+        provider.notes = notes.map(str::to_string);
+        provider
+    }
+
+    #[test]
+    fn flags_plain_http_shared_keys_and_keys_in_notes() {
+        let claude = AppType::Claude;
+        let providers = [
+            provider(
+                "relay",
+                "http://relay.example.com",
+                "sk-shared-123456",
+                None,
+            ),
+            provider("local", "http://127.0.0.1:11434", "sk-shared-123456", None),
+            provider(
+                "noted",
+                "https://api.example.com",
+                "sk-other-654321",
+                Some("backup key sk-backup-abcdef123"),
+            ),
+        ];
+        let targets: Vec<Target> = providers
+            .iter()
+            .map(|provider| Target {
+                app: &claude,
+                provider,
+            })
+            .collect();
+
+        let parse = |t: &Target| Url::parse(&endpoints(t.app, t.provider)[0]).unwrap();
+        let remote = insecure_endpoint(&targets[0], &parse(&targets[0]), "", true);
+        assert_eq!(remote.severity, SecuritySeverity::Critical);
+        let local = insecure_endpoint(&targets[1], &parse(&targets[1]), "", true);
+        assert_eq!(local.severity, SecuritySeverity::Info);
+
+        let reused = reused_keys(&targets, true);
+        assert_eq!(reused.len(), 2);
+        assert_eq!(reused[0].related, ["claude:local"]);
+
+        assert!(key_in_notes(&targets[0], true).is_none());
+        assert_eq!(
+            key_in_notes(&targets[2], false).unwrap().kind,
+            SecurityFindingKind::KeyInNotes
+        );
+    }
+}
@@ -0,0 +1,401 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Component, Path, PathBuf};
+
+use crate::app_config::AppType;
+use crate::codex_config::get_codex_config_dir;
+use crate::config::get_claude_config_dir;
+use crate::database::dao::ProviderSwitchEntry;
+use crate::error::AppError;
+use crate::store::AppState;
+
+/// 每页消息数
+pub const SESSION_PAGE_SIZE: usize = 50;
+/// 列表摘要只读取文件开头若干行，避免逐个解析大文件
+const SUMMARY_SCAN_LINES: usize = 200;
+const PREVIEW_CHARS: usize = 200;
+
+/// 会话列表项
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliSessionSummary {
+    /// 相对会话根目录的文件路径，作为 get_cli_session 的参数
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// 首条记录的时间（Unix 毫秒）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<i64>,
+    /// 文件最后修改时间（Unix 毫秒）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_prompt: Option<String>,
+    /// 会话开始时生效的供应商（按切换记录推断）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<ProviderSwitchEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CliSessionMessage {
+    pub role: String,
+    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider_id: Option<String>,
+}
+
+/// 会话内容的一页（页码从 0 开始）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliSessionPage {
+    pub id: String,
+    pub page: usize,
+    pub page_size: usize,
+    pub total_messages: usize,
+    pub total_pages: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<ProviderSwitchEntry>,
+    pub messages: Vec<CliSessionMessage>,
+}
+
+/// 只读浏览 Claude / Codex 的本地会话记录（JSONL）
+pub struct SessionLogService;
+
+impl SessionLogService {
+    pub fn list(state: &AppState, app_type: AppType) -> Result<Vec<CliSessionSummary>, AppError> {
+        let root = Self::root(&app_type)?;
+        let switches = state.storage.get_provider_switch_log(app_type.as_str())?;
+
+        let mut files = Vec::new();
+        collect_jsonl(&root, &mut files);
+
+        let mut sessions: Vec<CliSessionSummary> = files
+            .into_iter()
+            .filter_map(|path| {
+                let id = relative_id(&root, &path)?;
+                let updated_at = std::fs::metadata(&path)
+                    .and_then(|m| m.modified())
+                    .ok()
+                    .map(|t| chrono::DateTime::<chrono::Utc>::from(t).timestamp_millis());
+                let mut summary = summarize(&app_type, &path)?;
+                summary.id = id;
+                summary.updated_at = updated_at;
+                summary.provider = summary
+                    .started_at
+                    .and_then(|ts| provider_at(&switches, ts))
+                    .cloned();
+                Some(summary)
+            })
+            .collect();
+
+        sessions.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+        Ok(sessions)
+    }
+
+    pub fn get(
+        state: &AppState,
+        app_type: AppType,
+        id: &str,
+        page: usize,
+    ) -> Result<CliSessionPage, AppError> {
+        let root = Self::root(&app_type)?;
+        let path = resolve_id(&root, id)?;
+        let switches = state.storage.get_provider_switch_log(app_type.as_str())?;
+
+        let file = File::open(&path).map_err(|e| AppError::io(&path, e))?;
+        let mut messages: Vec<CliSessionMessage> = BufReader::new(file)
+            .lines()
+            .map_while(Result::ok)
+            .filter_map(|line| serde_json::from_str::<Value>(&line).ok())
+            .filter_map(|value| parse_message(&app_type, &value))
+            .collect();
+        for message in &mut messages {
+            message.provider_id = message
+                .timestamp
+                .and_then(|ts| provider_at(&switches, ts))
+                .map(|entry| entry.provider_id.clone());
+        }
+
+        let total_messages = messages.len();
+        let total_pages = total_messages.div_ceil(SESSION_PAGE_SIZE);
+        let provider = messages
+            .iter()
+            .find_map(|m| m.timestamp)
+            .and_then(|ts| provider_at(&switches, ts))
+            .cloned();
+
+        Ok(CliSessionPage {
+            id: id.to_string(),
+            page,
+            page_size: SESSION_PAGE_SIZE,
+            total_messages,
+            total_pages,
+            provider,
+            messages: messages
+                .into_iter()
+                .skip(page.saturating_mul(SESSION_PAGE_SIZE))
+                .take(SESSION_PAGE_SIZE)
+                .collect(),
+        })
+    }
+
+    /// 最近一段时间内仍在写入的会话数，用于提示切换后需要重启正在运行的 CLI
+    pub(crate) fn recently_active(app_type: &AppType, within: std::time::Duration) -> usize {
+        let Ok(root) = Self::root(app_type) else {
+            return 0;
+        };
+        let mut files = Vec::new();
+        collect_jsonl(&root, &mut files);
+        files
+            .iter()
+            .filter_map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+            .filter(|modified| modified.elapsed().is_ok_and(|age| age <= within))
+            .count()
+    }
+
+    fn root(app_type: &AppType) -> Result<PathBuf, AppError> {
+        match app_type {
+            AppType::Claude => Ok(get_claude_config_dir().join("projects")),
+            AppType::Codex => Ok(get_codex_config_dir().join("sessions")),
+            _ => Err(AppError::localized(
+                "session_log.unsupported_app",
+                "仅支持查看 Claude 与 Codex 的会话记录",
+                "Session logs are only available for Claude and Codex",
+            )),
+        }
+    }
+}
+
+fn collect_jsonl(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_jsonl(&path, out);
+        } else if path.extension().is_some_and(|ext| ext == "jsonl") {
+            out.push(path);
+        }
+    }
+}
+
+fn relative_id(root: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(root).ok()?;
+    Some(
+        relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+    )
+}
+
+/// 会话 ID 只能是会话根目录下的相对 .jsonl 路径
+fn resolve_id(root: &Path, id: &str) -> Result<PathBuf, AppError> {
+    let relative = Path::new(id);
+    let safe = relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_)))
+        && relative.extension().is_some_and(|ext| ext == "jsonl");
+    let path = root.join(relative);
+    if !safe || !path.is_file() {
+        return Err(AppError::localized(
+            "session_log.not_found",
+            "会话记录不存在",
+            "Session log not found",
+        ));
+    }
+    Ok(path)
+}
+
+fn summarize(app_type: &AppType, path: &Path) -> Option<CliSessionSummary> {
+    let file = File::open(path).ok()?;
+    let mut summary = CliSessionSummary {
+        id: String::new(),
+        project: None,
+        started_at: None,
+        updated_at: None,
+        first_prompt: None,
+        provider: None,
+    };
+
+    for line in BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .take(SUMMARY_SCAN_LINES)
+    {
+        let Ok(value) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        if summary.started_at.is_none() {
+            summary.started_at = timestamp_of(&value);
+        }
+        if summary.project.is_none() {
+            summary.project = project_of(&value);
+        }
+        if summary.first_prompt.is_none() {
+            summary.first_prompt = parse_message(app_type, &value)
+                .filter(|m| m.role == "user")
+                .map(|m| m.text.chars().take(PREVIEW_CHARS).collect());
+        }
+        if summary.first_prompt.is_some() && summary.project.is_some() {
+            break;
+        }
+    }
+    Some(summary)
+}
+
+fn timestamp_of(value: &Value) -> Option<i64> {
+    let raw = value
+        .get("timestamp")
+        .or_else(|| value.pointer("/payload/timestamp"))?
+        .as_str()?;
+    chrono::DateTime::parse_from_rfc3339(raw)
+        .ok()
+        .map(|dt| dt.timestamp_millis())
+}
+
+fn project_of(value: &Value) -> Option<String> {
+    value
+        .get("cwd")
+        .or_else(|| value.pointer("/payload/cwd"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+/// 提取用户/助手的文本消息，工具调用与系统注入内容忽略
+fn parse_message(app_type: &AppType, value: &Value) -> Option<CliSessionMessage> {
+    let (role, content) = match app_type {
+        AppType::Claude => {
+            let kind = value.get("type").and_then(Value::as_str)?;
+            if !matches!(kind, "user" | "assistant")
+                || value.get("isMeta").and_then(Value::as_bool) == Some(true)
+            {
+                return None;
+            }
+            let message = value.get("message")?;
+            (message.get("role")?.as_str()?, message.get("content")?)
+        }
+        _ => {
+            // 新版为 {type: "response_item", payload: {...}}，旧版直接是消息对象
+            let item = match value.get("type").and_then(Value::as_str) {
+                Some("response_item") => value.get("payload")?,
+                _ => value,
+            };
+            if item.get("type").and_then(Value::as_str) != Some("message") {
+                return None;
+            }
+            (item.get("role")?.as_str()?, item.get("content")?)
+        }
+    };
+    if !matches!(role, "user" | "assistant") {
+        return None;
+    }
+
+    let text = match content {
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items
+            .iter()
+            .filter(|item| {
+                matches!(
+                    item.get("type").and_then(Value::as_str),
+                    Some("text" | "input_text" | "output_text")
+                )
+            })
+            .filter_map(|item| item.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => return None,
+    };
+    let trimmed = text.trim();
+    if trimmed.is_empty()
+        || trimmed.starts_with("<environment_context>")
+        || trimmed.starts_with("<user_instructions>")
+    {
+        return None;
+    }
+
+    Some(CliSessionMessage {
+        role: role.to_string(),
+        text: trimmed.to_string(),
+        timestamp: timestamp_of(value),
+        provider_id: None,
+    })
+}
+
+/// 取时间点之前最近一次切换到的供应商
+fn provider_at(switches: &[ProviderSwitchEntry], timestamp: i64) -> Option<&ProviderSwitchEntry> {
+    switches
+        .iter()
+        .take_while(|entry| entry.switched_at <= timestamp)
+        .last()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn transcript_lines_are_parsed_for_both_clis() {
+        let claude = json!({
+            "type": "user",
+            "timestamp": "2025-01-02T03:04:05.000Z",
+            "cwd": "/work/app",
+            "message": { "role": "user", "content": [{ "type": "text", "text": "hi" }] }
+        });
+        let message = parse_message(&AppType::Claude, &claude).unwrap();
+        assert_eq!(message.text, "hi");
+        assert_eq!(message.timestamp, Some(1_735_787_045_000));
+        assert_eq!(project_of(&claude).as_deref(), Some("/work/app"));
+
+        let tool_result = json!({
+            "type": "user",
+            "message": { "role": "user", "content": [{ "type": "tool_result", "content": "x" }] }
+        });
+        assert!(parse_message(&AppType::Claude, &tool_result).is_none());
+
+        let codex = json!({
+            "timestamp": "2025-01-02T03:04:05Z",
+            "type": "response_item",
+            "payload": {
+                "type": "message",
+                "role": "assistant",
+                "content": [{ "type": "output_text", "text": "done" }]
+            }
+        });
+        assert_eq!(
+            parse_message(&AppType::Codex, &codex).unwrap().role,
+            "assistant"
+        );
+        let context = json!({
+            "type": "message",
+            "role": "user",
+            "content": [{ "type": "input_text", "text": "<environment_context>cwd</environment_context>" }]
+        });
+        assert!(parse_message(&AppType::Codex, &context).is_none());
+    }
+
+    #[test]
+    fn provider_is_the_latest_switch_before_timestamp() {
+        let entry = |id: &str, at: i64| ProviderSwitchEntry {
+            provider_id: id.to_string(),
+            provider_name: id.to_uppercase(),
+            switched_at: at,
+        };
+        let switches = vec![entry("a", 100), entry("b", 200)];
+        assert!(provider_at(&switches, 50).is_none());
+        assert_eq!(provider_at(&switches, 150).unwrap().provider_id, "a");
+        assert_eq!(provider_at(&switches, 200).unwrap().provider_id, "b");
+
+        let root = Path::new("/sessions");
+        assert!(resolve_id(root, "../secret.jsonl").is_err());
+        assert!(resolve_id(root, "/etc/passwd").is_err());
+    }
+}
@@ -0,0 +1,326 @@
+use qrcode::render::svg;
+use qrcode::QrCode;
+use serde::Serialize;
+use std::path::Path;
+
+use crate::app_config::AppType;
+use crate::deeplink::{build_provider_deeplink, provider_to_request, API_KEY_PLACEHOLDER};
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::store::AppState;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharePageResult {
+    pub path: String,
+    pub link: String,
+    /// 链接过长无法编码为二维码时为 false
+    pub qr_included: bool,
+    pub secret_included: bool,
+}
+
+/// 页面文案；跟随应用语言，非英文时使用中文
+struct Labels {
+    lang: &'static str,
+    subtitle: &'static str,
+    import_title: &'static str,
+    import_hint: &'static str,
+    import_button: &'static str,
+    qr_hint: &'static str,
+    manual_title: &'static str,
+    endpoint: &'static str,
+    api_key: &'static str,
+    model: &'static str,
+    homepage: &'static str,
+    placeholder_hint: &'static str,
+    footer: &'static str,
+}
+
+const LABELS_ZH: Labels = Labels {
+    lang: "zh-CN",
+    subtitle: "供应商接入说明",
+    import_title: "一键导入 CLI Hub",
+    import_hint: "已安装 CLI Hub 时，点击下方按钮或扫描二维码即可导入该供应商。",
+    import_button: "导入到 CLI Hub",
+    qr_hint: "使用已安装 CLI Hub 的设备扫描",
+    manual_title: "手动配置",
+    endpoint: "接口地址",
+    api_key: "API Key",
+    model: "模型",
+    homepage: "官网",
+    placeholder_hint: "页面未包含 API Key，导入后请将 YOUR_API_KEY 替换为你获得的密钥。",
+    footer: "由 CLI Hub 生成",
+};
+
+const LABELS_EN: Labels = Labels {
+    lang: "en",
+    subtitle: "Provider connection guide",
+    import_title: "Import into CLI Hub",
+    import_hint: "With CLI Hub installed, click the button below or scan the QR code to add this provider.",
+    import_button: "Import into CLI Hub",
+    qr_hint: "Scan on a device with CLI Hub installed",
+    manual_title: "Manual setup",
+    endpoint: "Endpoint",
+    api_key: "API key",
+    model: "Model",
+    homepage: "Website",
+    placeholder_hint: "This page does not include the API key. After importing, replace YOUR_API_KEY with the key you were given.",
+    footer: "Generated by CLI Hub",
+};
+
+pub struct SharePageService;
+
+impl SharePageService {
+    /// 生成供应商分享页（单个静态 HTML），写入指定路径
+    pub fn generate(
+        state: &AppState,
+        app_type: AppType,
+        id: &str,
+        path: &Path,
+        include_secret: bool,
+    ) -> Result<SharePageResult, AppError> {
+        let providers = state.storage.get_all_providers(app_type.as_str())?;
+        let provider = providers
+            .get(id)
+            .ok_or_else(|| AppError::Message(format!("供应商 {id} 不存在")))?;
+
+        let link = build_provider_deeplink(&app_type, provider, include_secret)?;
+        let qr = qr_svg(&link);
+        let labels = match crate::settings::get_effective_settings()
+            .language
+            .as_deref()
+        {
+            Some("en") => &LABELS_EN,
+            _ => &LABELS_ZH,
+        };
+        let html = render(
+            &app_type,
+            provider,
+            &link,
+            qr.as_deref(),
+            include_secret,
+            labels,
+        )?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+        }
+        crate::config::atomic_write(path, html.as_bytes())?;
+        log::info!("已生成供应商分享页: {}", path.display());
+
+        Ok(SharePageResult {
+            path: path.display().to_string(),
+            link,
+            qr_included: qr.is_some(),
+            secret_included: include_secret,
+        })
+    }
+}
+
+/// 二维码容量有限（约 2.9KB），超出时页面只保留链接
+fn qr_svg(link: &str) -> Option<String> {
+    let code = QrCode::new(link.as_bytes()).ok()?;
+    let image = code.render::<svg::Color>().min_dimensions(240, 240).build();
+    // 去掉 XML 声明以便内嵌到 HTML
+    let start = image.find("<svg")?;
+    Some(image[start..].to_string())
+}
+
+fn render(
+    app_type: &AppType,
+    provider: &Provider,
+    link: &str,
+    qr: Option<&str>,
+    include_secret: bool,
+    labels: &Labels,
+) -> Result<String, AppError> {
+    let request = provider_to_request(app_type, provider)?;
+    let api_key = match request.api_key.as_deref() {
+        Some(key) if include_secret && !key.is_empty() => key,
+        _ => API_KEY_PLACEHOLDER,
+    };
+
+    let mut rows = String::new();
+    let mut row = |label: &str, value: Option<&str>| {
+        if let Some(value) = value.filter(|v| !v.is_empty()) {
+            rows.push_str(&format!(
+                "<tr><th>{}</th><td><code>{}</code></td></tr>\n",
+                escape(label),
+                escape(value)
+            ));
+        }
+    };
+    row(labels.endpoint, request.endpoint.as_deref());
+    row(labels.api_key, Some(api_key));
+    row(labels.model, request.model.as_deref());
+    row(labels.homepage, request.homepage.as_deref());
+
+    let snippet = escape(&manual_snippet(app_type, provider, &request, api_key));
+    let placeholder_hint = if include_secret {
+        String::new()
+    } else {
+        format!(
+            "<p class=\"hint\">{}</p>\n",
+            escape(labels.placeholder_hint)
+        )
+    };
+    let qr_block = qr
+        .map(|svg| {
+            format!(
+                "<figure class=\"qr\">{svg}<figcaption>{}</figcaption></figure>\n",
+                escape(labels.qr_hint)
+            )
+        })
+        .unwrap_or_default();
+    let notes = provider
+        .notes
+        .as_deref()
+        .filter(|n| !n.is_empty())
+        .map(|n| format!("<p class=\"notes\">{}</p>\n", escape(n)))
+        .unwrap_or_default();
+
+    Ok(format!(
+        r#"<!DOCTYPE html>
+<html lang="{lang}">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<meta name="referrer" content="no-referrer">
+<title>{name}</title>
+<style>
+body {{ font-family: -apple-system, "Segoe UI", "PingFang SC", sans-serif; max-width: 640px; margin: 40px auto; padding: 0 20px; color: #1f2328; }}
+h1 {{ margin-bottom: 4px; }}
+.subtitle, figcaption, footer {{ color: #656d76; font-size: 14px; }}
+.button {{ display: inline-block; padding: 10px 18px; border-radius: 8px; background: #2563eb; color: #fff; text-decoration: none; }}
+.qr svg {{ width: 240px; height: 240px; }}
+.qr {{ margin: 20px 0; }}
+table {{ border-collapse: collapse; width: 100%; }}
+th, td {{ text-align: left; padding: 6px 8px; border-bottom: 1px solid #d0d7de; }}
+code, pre {{ font-family: ui-monospace, Menlo, Consolas, monospace; word-break: break-all; }}
+pre {{ background: #f6f8fa; padding: 12px; border-radius: 8px; white-space: pre-wrap; }}
+.hint {{ background: #fff8c5; padding: 8px 12px; border-radius: 6px; }}
+</style>
+</head>
+<body>
+<h1>{name}</h1>
+<p class="subtitle">{subtitle} · {app}</p>
+{notes}<h2>{import_title}</h2>
+<p>{import_hint}</p>
+<p><a class="button" href="{link}">{import_button}</a></p>
+{qr_block}<pre>{link}</pre>
+<h2>{manual_title}</h2>
+{placeholder_hint}<table>
+{rows}</table>
+<pre>{snippet}</pre>
+<footer>{footer}</footer>
+</body>
+</html>
+"#,
+        lang = labels.lang,
+        name = escape(&provider.name),
+        subtitle = escape(labels.subtitle),
+        app = app_type.as_str(),
+        import_title = escape(labels.import_title),
+        import_hint = escape(labels.import_hint),
+        import_button = escape(labels.import_button),
+        link = escape(link),
+        manual_title = escape(labels.manual_title),
+        footer = escape(labels.footer),
+    ))
+}
+
+/// 手动配置片段：Claude/Gemini 为环境变量，Codex 为 config.toml 加 OPENAI_API_KEY
+fn manual_snippet(
+    app_type: &AppType,
+    provider: &Provider,
+    request: &crate::deeplink::DeepLinkImportRequest,
+    api_key: &str,
+) -> String {
+    let endpoint = request.endpoint.as_deref().unwrap_or_default();
+    let model = request.model.as_deref().filter(|m| !m.is_empty());
+    let mut lines = Vec::new();
+    match app_type {
+        AppType::Claude => {
+            lines.push(format!("export ANTHROPIC_BASE_URL=\"{endpoint}\""));
+            lines.push(format!("export ANTHROPIC_AUTH_TOKEN=\"{api_key}\""));
+            if let Some(model) = model {
+                lines.push(format!("export ANTHROPIC_MODEL=\"{model}\""));
+            }
+        }
+        AppType::Codex => {
+            if let Some(config) = provider
+                .settings_config
+                .get("config")
+                .and_then(|v| v.as_str())
+                .filter(|c| !c.trim().is_empty())
+            {
+                lines.push("# ~/.codex/config.toml".to_string());
+                lines.push(config.trim_end().to_string());
+                lines.push(String::new());
+            }
+            lines.push(format!("export OPENAI_API_KEY=\"{api_key}\""));
+        }
+        AppType::Gemini => {
+            lines.push(format!("export GOOGLE_GEMINI_BASE_URL=\"{endpoint}\""));
+            lines.push(format!("export GEMINI_API_KEY=\"{api_key}\""));
+            if let Some(model) = model {
+                lines.push(format!("export GEMINI_MODEL=\"{model}\""));
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+fn escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn page_escapes_fields_and_hides_secret() {
+        let provider = Provider::with_id(
+            "p1".into(),
+            "<script>Relay</script>".into(),
+            json!({
+                "env": {
+                    "GEMINI_API_KEY": "gm-secret",
+                    "GOOGLE_GEMINI_BASE_URL": "https://gemini.relay.example"
+                }
+            }),
+            None,
+        );
+        let link = build_provider_deeplink(&AppType::Gemini, &provider, false).unwrap();
+        let qr = qr_svg(&link);
+        assert!(qr.as_deref().is_some_and(|s| s.starts_with("<svg")));
+
+        let html = render(
+            &AppType::Gemini,
+            &provider,
+            &link,
+            qr.as_deref(),
+            false,
+            &LABELS_EN,
+        )
+        .unwrap();
+        assert!(!html.contains("gm-secret"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;script&gt;Relay"));
+        assert!(html.contains("export GEMINI_API_KEY=&quot;YOUR_API_KEY&quot;"));
+        assert!(html.contains(LABELS_EN.placeholder_hint));
+    }
+}
@@ -1,5 +1,6 @@
 use anyhow::{anyhow, Context, Result};
 use chrono::{DateTime, Utc};
+use indexmap::IndexMap;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -8,6 +9,7 @@ use std::path::{Path, PathBuf};
 use tokio::time::timeout;
 
 use crate::error::format_skill_error;
+use crate::services::github;
 
 /// 技能对象
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,6 +65,23 @@ pub struct SkillState {
     /// 安装时间
     #[serde(rename = "installedAt")]
     pub installed_at: DateTime<Utc>,
+    /// 来源: "repo:owner/name" 或 "unmanaged"，None 表示由本应用安装
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+/// 未被数据库管理的技能来源标记
+pub const UNMANAGED_SKILL_SOURCE: &str = "unmanaged";
+
+/// 扫描安装目录时发现的技能
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScannedSkill {
+    pub directory: String,
+    pub name: String,
+    pub description: String,
+    /// "repo:owner/name" 或 "unmanaged"
+    pub source: String,
 }
 
 /// 持久化存储结构
@@ -159,13 +178,26 @@ impl SkillService {
 
         let results: Vec<Result<Vec<Skill>>> = futures::future::join_all(fetch_tasks).await;
 
+        let repo_count = enabled_repos.len();
+        let mut rate_limited = Vec::new();
         for (repo, result) in enabled_repos.into_iter().zip(results.into_iter()) {
             match result {
                 Ok(repo_skills) => skills.extend(repo_skills),
+                Err(e) if github::is_rate_limit_error(&e) => rate_limited.push(e),
                 Err(e) => log::warn!("获取仓库 {}/{} 技能失败: {}", repo.owner, repo.name, e),
             }
         }
 
+        // 所有仓库都因限流失败时返回结构化错误，避免界面显示为空列表却没有提示
+        if repo_count > 0 && rate_limited.len() == repo_count {
+            if let Some(err) = rate_limited.pop() {
+                return Err(err);
+            }
+        }
+        for err in rate_limited {
+            log::warn!("获取技能仓库被 GitHub 限流: {err}");
+        }
+
         // 合并本地技能
         self.merge_local_skills(&mut skills)?;
 
@@ -352,6 +384,70 @@ impl SkillService {
         Ok(())
     }
 
+    /// 扫描安装目录中存在但数据库未记录（或记录为未安装）的技能
+    ///
+    /// 目录若是 git 仓库，则用 remote 地址匹配已配置的技能仓库；
+    /// 匹配不到的标记为 unmanaged。
+    pub fn scan_installed_skills(
+        &self,
+        known: &IndexMap<String, SkillState>,
+        repos: &[SkillRepo],
+    ) -> Result<Vec<ScannedSkill>> {
+        if !self.install_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut scanned = Vec::new();
+        for entry in fs::read_dir(&self.install_dir)? {
+            let path = entry?.path();
+            let Some(directory) = path.file_name().map(|n| n.to_string_lossy().to_string()) else {
+                continue;
+            };
+            if !path.is_dir() || directory.starts_with('.') {
+                continue;
+            }
+
+            let skill_md = path.join("SKILL.md");
+            if !skill_md.exists() {
+                continue;
+            }
+
+            let is_known = known
+                .iter()
+                .any(|(key, state)| state.installed && key.eq_ignore_ascii_case(&directory));
+            if is_known {
+                continue;
+            }
+
+            let meta = self
+                .parse_skill_metadata(&skill_md)
+                .unwrap_or(SkillMetadata {
+                    name: None,
+                    description: None,
+                });
+            let source = read_git_remote_url(&path)
+                .and_then(|url| parse_github_repo(&url))
+                .and_then(|(owner, name)| {
+                    repos.iter().find(|repo| {
+                        repo.owner.eq_ignore_ascii_case(&owner)
+                            && repo.name.eq_ignore_ascii_case(&name)
+                    })
+                })
+                .map(|repo| format!("repo:{}/{}", repo.owner, repo.name))
+                .unwrap_or_else(|| UNMANAGED_SKILL_SOURCE.to_string());
+
+            scanned.push(ScannedSkill {
+                name: meta.name.unwrap_or_else(|| directory.clone()),
+                description: meta.description.unwrap_or_default(),
+                directory,
+                source,
+            });
+        }
+
+        scanned.sort_by(|a, b| a.directory.cmp(&b.directory));
+        Ok(scanned)
+    }
+
     /// 去重技能列表
     fn deduplicate_skills(skills: &mut Vec<Skill>) {
         let mut seen = HashMap::new();
@@ -381,15 +477,17 @@ impl SkillService {
 
         let mut last_error = None;
         for branch in branches {
-            let url = format!(
-                "https://github.com/{}/{}/archive/refs/heads/{}.zip",
-                repo.owner, repo.name, branch
-            );
+            let url = github::archive_url(&repo.owner, &repo.name, branch);
 
             match self.download_and_extract(&url, &temp_path).await {
                 Ok(_) => {
                     return Ok(temp_path);
                 }
+                // 限流时换分支也会失败，直接返回
+                Err(e) if github::is_rate_limit_error(&e) => {
+                    let _ = fs::remove_dir_all(&temp_path);
+                    return Err(e);
+                }
                 Err(e) => {
                     last_error = Some(e);
                     continue;
@@ -403,7 +501,7 @@ impl SkillService {
     /// 下载并解压 ZIP
     async fn download_and_extract(&self, url: &str, dest: &Path) -> Result<()> {
         // 下载 ZIP
-        let response = self.http_client.get(url).send().await?;
+        let response = github::get_with_retry(&self.http_client, url).await?;
         if !response.status().is_success() {
             let status = response.status().as_u16().to_string();
             return Err(anyhow::anyhow!(format_skill_error(
@@ -591,3 +689,94 @@ impl SkillService {
         Ok(())
     }
 }
+
+/// 读取技能目录的 git remote 地址（优先 origin）
+fn read_git_remote_url(dir: &Path) -> Option<String> {
+    let config = fs::read_to_string(dir.join(".git").join("config")).ok()?;
+    parse_git_remote_url(&config)
+}
+
+fn parse_git_remote_url(config: &str) -> Option<String> {
+    let mut section = String::new();
+    let mut first = None;
+
+    for line in config.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            section = line.to_string();
+            continue;
+        }
+        if !section.starts_with("[remote ") {
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        if key.trim() != "url" {
+            continue;
+        }
+
+        let url = value.trim().to_string();
+        if section == "[remote \"origin\"]" {
+            return Some(url);
+        }
+        first.get_or_insert(url);
+    }
+
+    first
+}
+
+/// 从 GitHub 地址中解析 owner/name，支持 https、ssh 与 git@ 形式
+fn parse_github_repo(url: &str) -> Option<(String, String)> {
+    let idx = url.find("github.com")?;
+    let rest = url[idx + "github.com".len()..]
+        .trim_start_matches([':', '/'])
+        .trim_end_matches('/');
+    let rest = rest.strip_suffix(".git").unwrap_or(rest);
+
+    let mut parts = rest.split('/');
+    let owner = parts.next().filter(|s| !s.is_empty())?;
+    let name = parts.next().filter(|s| !s.is_empty())?;
+    Some((owner.to_string(), name.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_git_remote_prefers_origin() {
+        let config = r#"[core]
+	bare = false
+[remote "upstream"]
+	url = https://github.com/other/skills.git
+[remote "origin"]
+	url = git@github.com:Anthropics/Skills.git
+	fetch = +refs/heads/*:refs/remotes/origin/*
+"#;
+        let url = parse_git_remote_url(config).unwrap();
+        assert_eq!(url, "git@github.com:Anthropics/Skills.git");
+        assert_eq!(
+            parse_github_repo(&url),
+            Some(("Anthropics".to_string(), "Skills".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_github_repo_variants() {
+        for url in [
+            "https://github.com/anthropics/skills",
+            "https://github.com/anthropics/skills.git/",
+            "ssh://git@github.com/anthropics/skills.git",
+        ] {
+            assert_eq!(
+                parse_github_repo(url),
+                Some(("anthropics".to_string(), "skills".to_string())),
+                "{url}"
+            );
+        }
+        assert!(parse_github_repo("https://gitlab.com/a/b").is_none());
+        assert!(parse_github_repo("https://github.com/only-owner").is_none());
+    }
+}
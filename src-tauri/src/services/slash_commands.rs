@@ -0,0 +1,280 @@
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::app_config::AppType;
+use crate::config::{get_claude_config_dir, write_text_file};
+use crate::error::AppError;
+use crate::prompt::Prompt;
+use crate::services::prompt_crypto::PromptCryptoService;
+use crate::store::AppState;
+
+/// Claude Code 会把命令参数替换到这个占位符
+const ARGUMENTS_PLACEHOLDER: &str = "$ARGUMENTS";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlashCommandFile {
+    pub prompt_id: String,
+    /// 不含前导 `/` 的命令名
+    pub command: String,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlashCommandConflict {
+    pub prompt_id: String,
+    pub command: String,
+    pub path: String,
+    /// true 表示与本次导出的另一条提示词重名，false 表示目标文件已存在
+    pub duplicate_in_batch: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlashCommandExportResult {
+    pub written: Vec<SlashCommandFile>,
+    /// 未写入的冲突项，overwrite 为 true 时只包含批内重名
+    pub conflicts: Vec<SlashCommandConflict>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlashCommandImportResult {
+    pub imported_ids: Vec<String>,
+    /// 内容已存在于提示词库而跳过的命令
+    pub skipped: Vec<String>,
+}
+
+pub struct SlashCommandService;
+
+impl SlashCommandService {
+    pub fn commands_dir() -> PathBuf {
+        get_claude_config_dir().join("commands")
+    }
+
+    /// 把选中的提示词写成 `~/.claude/commands/<name>.md`
+    pub fn export(
+        state: &AppState,
+        app: AppType,
+        ids: &[String],
+        overwrite: bool,
+    ) -> Result<SlashCommandExportResult, AppError> {
+        let dir = Self::commands_dir();
+        let mut result = SlashCommandExportResult::default();
+        let mut taken = HashSet::new();
+
+        for id in ids {
+            let mut prompt = state
+                .storage
+                .get_prompt(app.as_str(), id)?
+                .ok_or_else(|| AppError::InvalidInput(format!("提示词 {id} 不存在")))?;
+            // 命令文件供 CLI 直接读取，只能写明文
+            prompt.content = PromptCryptoService::plaintext(&app, id, &prompt.content)?;
+            let command = command_name(&prompt);
+            let path = dir.join(format!("{command}.md"));
+
+            let duplicate_in_batch = !taken.insert(command.clone());
+            if duplicate_in_batch || (path.exists() && !overwrite) {
+                result.conflicts.push(SlashCommandConflict {
+                    prompt_id: prompt.id.clone(),
+                    command,
+                    path: path.display().to_string(),
+                    duplicate_in_batch,
+                });
+                continue;
+            }
+
+            write_text_file(&path, &render_command(&prompt))?;
+            result.written.push(SlashCommandFile {
+                prompt_id: prompt.id.clone(),
+                command,
+                path: path.display().to_string(),
+            });
+        }
+
+        log::info!(
+            "已导出 {} 个斜杠命令，{} 个冲突",
+            result.written.len(),
+            result.conflicts.len()
+        );
+        Ok(result)
+    }
+
+    /// 把 `~/.claude/commands` 下的命令导入提示词库；子目录中的命令名为 `目录:名称`
+    pub fn import(state: &AppState, app: AppType) -> Result<SlashCommandImportResult, AppError> {
+        let dir = Self::commands_dir();
+        let mut files = Vec::new();
+        collect_markdown(&dir, &mut files);
+        files.sort();
+
+        let timestamp = chrono::Utc::now().timestamp();
+        let mut result = SlashCommandImportResult::default();
+        for path in files {
+            let raw = std::fs::read_to_string(&path).map_err(|e| AppError::io(&path, e))?;
+            let command = command_from_path(&dir, &path);
+            let (description, content) = parse_command(&raw);
+            if content.trim().is_empty()
+                || state
+                    .storage
+                    .prompt_content_exists(app.as_str(), &content)?
+            {
+                result.skipped.push(command);
+                continue;
+            }
+
+            let id = format!("slash-{}-{timestamp}", command.replace(':', "-"));
+            state.storage.save_prompt(
+                app.as_str(),
+                &Prompt {
+                    id: id.clone(),
+                    name: command,
+                    content,
+                    description,
+                    enabled: false,
+                    created_at: Some(timestamp),
+                    updated_at: Some(timestamp),
+                    version: None,
+                },
+            )?;
+            result.imported_ids.push(id);
+        }
+
+        Ok(result)
+    }
+}
+
+/// 由提示词名称生成命令名：小写、空白转为 `-`，只保留字母数字与 `-`/`_`
+fn command_name(prompt: &Prompt) -> String {
+    let mut name = String::new();
+    for c in prompt.name.trim().chars() {
+        if c.is_alphanumeric() || c == '_' {
+            name.extend(c.to_lowercase());
+        } else if (c.is_whitespace() || c == '-') && !name.ends_with('-') && !name.is_empty() {
+            name.push('-');
+        }
+    }
+    let name = name.trim_end_matches('-').to_string();
+    if name.is_empty() {
+        prompt.id.clone()
+    } else {
+        name
+    }
+}
+
+/// 正文没有参数占位符时在末尾追加 `$ARGUMENTS`，命令后的输入才会传给模型
+fn render_command(prompt: &Prompt) -> String {
+    let mut out = String::new();
+    if let Some(description) = prompt.description.as_deref().filter(|d| !d.is_empty()) {
+        // JSON 字符串同时是合法的 YAML 双引号字符串
+        let quoted = serde_json::to_string(description).unwrap_or_default();
+        out.push_str(&format!("---\ndescription: {quoted}\n---\n\n"));
+    }
+    out.push_str(prompt.content.trim_end());
+    if !has_placeholder(&prompt.content) {
+        out.push_str(&format!("\n\n{ARGUMENTS_PLACEHOLDER}"));
+    }
+    out.push('\n');
+    out
+}
+
+fn has_placeholder(content: &str) -> bool {
+    content.contains(ARGUMENTS_PLACEHOLDER)
+        || content
+            .match_indices('$')
+            .any(|(i, _)| content[i + 1..].starts_with(|c: char| c.is_ascii_digit()))
+}
+
+/// 返回 front matter 中的 description 与去掉导出时追加的占位符后的正文
+fn parse_command(raw: &str) -> (Option<String>, String) {
+    let raw = raw.trim_start_matches('\u{feff}');
+    let (description, body) = match raw
+        .strip_prefix("---")
+        .and_then(|rest| rest.split_once("\n---"))
+    {
+        Some((front_matter, body)) => {
+            let description = serde_yaml::from_str::<serde_yaml::Value>(front_matter)
+                .ok()
+                .and_then(|v| v.get("description")?.as_str().map(str::to_string));
+            (description, body.trim_start_matches('-'))
+        }
+        None => (None, raw),
+    };
+
+    let body = body.trim();
+    let body = body
+        .strip_suffix(ARGUMENTS_PLACEHOLDER)
+        .map(str::trim_end)
+        .filter(|rest| !has_placeholder(rest))
+        .unwrap_or(body);
+    (description, body.to_string())
+}
+
+fn command_from_path(root: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(root).unwrap_or(path).with_extension("");
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+fn collect_markdown(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_markdown(&path, out);
+        } else if path.extension().is_some_and(|ext| ext == "md") {
+            out.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prompt(name: &str, content: &str, description: Option<&str>) -> Prompt {
+        Prompt {
+            id: "p1".into(),
+            name: name.into(),
+            content: content.into(),
+            description: description.map(str::to_string),
+            enabled: false,
+            created_at: None,
+            updated_at: None,
+            version: None,
+        }
+    }
+
+    #[test]
+    fn export_and_import_round_trip() {
+        let p = prompt(
+            "Code Review: Rust",
+            "Review this code.",
+            Some("Review: \"strict\""),
+        );
+        assert_eq!(command_name(&p), "code-review-rust");
+
+        let rendered = render_command(&p);
+        assert!(rendered.ends_with("Review this code.\n\n$ARGUMENTS\n"));
+        let (description, content) = parse_command(&rendered);
+        assert_eq!(description.as_deref(), Some("Review: \"strict\""));
+        assert_eq!(content, "Review this code.");
+
+        let positional = prompt("fix", "Fix issue #$1 in $2", None);
+        let rendered = render_command(&positional);
+        assert_eq!(rendered, "Fix issue #$1 in $2\n");
+        assert_eq!(parse_command(&rendered).1, "Fix issue #$1 in $2");
+
+        assert_eq!(command_name(&prompt("!!!", "x", None)), "p1");
+        assert_eq!(
+            command_from_path(Path::new("/c"), Path::new("/c/frontend/component.md")),
+            "frontend:component"
+        );
+    }
+}
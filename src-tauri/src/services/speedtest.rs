@@ -1,14 +1,27 @@
 use futures::future::join_all;
-use reqwest::{Client, Url};
+use reqwest::{Client, RequestBuilder, Url};
 use serde::Serialize;
+use serde_json::{json, Value};
 use std::time::{Duration, Instant};
 
+use crate::app_config::AppType;
 use crate::error::AppError;
+use crate::provider::Provider;
+use crate::services::provider::CredentialsExtractor;
 
 const DEFAULT_TIMEOUT_SECS: u64 = 8;
 const MAX_TIMEOUT_SECS: u64 = 30;
 const MIN_TIMEOUT_SECS: u64 = 2;
 
+/// 推理测速需要等待模型生成，超时上限单独放宽
+const INFERENCE_DEFAULT_TIMEOUT_SECS: u64 = 30;
+const INFERENCE_MAX_TIMEOUT_SECS: u64 = 120;
+/// 限制输出长度，测速本身只消耗极少量 token
+const INFERENCE_MAX_TOKENS: u32 = 64;
+const INFERENCE_PROMPT: &str = "Count from 1 to 20, separated by spaces.";
+/// 错误响应体只保留开头部分
+const ERROR_BODY_LIMIT: usize = 300;
+
 /// 端点测速结果
 #[derive(Debug, Clone, Serialize)]
 pub struct EndpointLatency {
@@ -18,6 +31,32 @@ pub struct EndpointLatency {
     pub error: Option<String>,
 }
 
+/// 推理测速结果：首 token 延迟与生成速度
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InferenceLatency {
+    pub url: String,
+    pub model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// 从发出请求到收到第一个文本片段的毫秒数
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_token_ms: Option<u128>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_ms: Option<u128>,
+    /// 优先取响应中的 usage，缺失时按文本片段数估算
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_tokens: Option<u64>,
+    /// 首 token 之后的生成速度
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tokens_per_sec: Option<f64>,
+    /// 响应中声明的模型，与 model 不同说明中转换成了其他模型
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub served_model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 /// 网络测速相关业务
 pub struct SpeedtestService;
 
@@ -95,6 +134,75 @@ impl SpeedtestService {
         Ok(join_all(tasks).await)
     }
 
+    /// 以供应商实际使用的模型发送一次流式补全，测量首 token 延迟与生成速度
+    ///
+    /// 请求格式按应用区分：Claude 使用 Anthropic messages，Codex 使用 OpenAI responses，
+    /// Gemini 使用 streamGenerateContent（SSE）。
+    pub async fn test_inference(
+        app_type: &AppType,
+        provider: &Provider,
+        timeout_secs: Option<u64>,
+    ) -> Result<InferenceLatency, AppError> {
+        let (api_key, base_url) = CredentialsExtractor::extract_credentials(provider, app_type)?;
+        let model = inference_model(app_type, provider);
+        let timeout = timeout_secs
+            .unwrap_or(INFERENCE_DEFAULT_TIMEOUT_SECS)
+            .clamp(MIN_TIMEOUT_SECS, INFERENCE_MAX_TIMEOUT_SECS);
+        let client = Self::build_client(timeout)?;
+        let (url, request) = inference_request(&client, app_type, &api_key, &base_url, &model);
+
+        let mut result = InferenceLatency {
+            url,
+            model,
+            ..Default::default()
+        };
+        let start = Instant::now();
+        let mut resp = match request.send().await {
+            Ok(resp) => resp,
+            Err(err) => {
+                result.error = Some(describe_error(&err));
+                return Ok(result);
+            }
+        };
+        result.status = Some(resp.status().as_u16());
+        if !resp.status().is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            result.error = Some(body.chars().take(ERROR_BODY_LIMIT).collect());
+            return Ok(result);
+        }
+
+        let mut tally = StreamTally::new(app_type.clone());
+        let mut pending = String::new();
+        loop {
+            match resp.chunk().await {
+                Ok(Some(bytes)) => {
+                    pending.push_str(&String::from_utf8_lossy(&bytes));
+                    while let Some(pos) = pending.find('\n') {
+                        let line: String = pending.drain(..=pos).collect();
+                        tally.feed_line(line.trim_end(), start.elapsed().as_millis());
+                    }
+                }
+                Ok(None) => break,
+                Err(err) => {
+                    result.error = Some(describe_error(&err));
+                    break;
+                }
+            }
+        }
+        tally.feed_line(pending.trim_end(), start.elapsed().as_millis());
+
+        let total = start.elapsed().as_millis();
+        result.total_ms = Some(total);
+        result.first_token_ms = tally.first_token_ms;
+        result.output_tokens = tally.output_tokens();
+        result.tokens_per_sec = tally.tokens_per_sec(total);
+        result.served_model = tally.served_model.clone();
+        if result.error.is_none() && tally.first_token_ms.is_none() {
+            result.error = Some("响应中没有生成任何文本".to_string());
+        }
+        Ok(result)
+    }
+
     fn build_client(timeout_secs: u64) -> Result<Client, AppError> {
         Client::builder()
             .timeout(Duration::from_secs(timeout_secs))
@@ -116,6 +224,183 @@ impl SpeedtestService {
     }
 }
 
+fn describe_error(err: &reqwest::Error) -> String {
+    if err.is_timeout() {
+        "请求超时".to_string()
+    } else if err.is_connect() {
+        "连接失败".to_string()
+    } else {
+        err.to_string()
+    }
+}
+
+/// 供应商配置的模型；未配置时使用各 CLI 的常见默认模型
+fn inference_model(app_type: &AppType, provider: &Provider) -> String {
+    let settings = &provider.settings_config;
+    let env_model = |key: &str| {
+        settings
+            .get("env")
+            .and_then(|env| env.get(key))
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|m| !m.is_empty())
+            .map(str::to_string)
+    };
+    match app_type {
+        AppType::Claude => env_model("ANTHROPIC_MODEL")
+            .or_else(|| env_model("ANTHROPIC_DEFAULT_SONNET_MODEL"))
+            .unwrap_or_else(|| "claude-sonnet-4-5".to_string()),
+        AppType::Codex => settings
+            .get("config")
+            .and_then(Value::as_str)
+            .and_then(|text| text.parse::<toml::Table>().ok())
+            .and_then(|table| table.get("model")?.as_str().map(str::to_string))
+            .unwrap_or_else(|| "gpt-5".to_string()),
+        AppType::Gemini => {
+            env_model("GEMINI_MODEL").unwrap_or_else(|| "gemini-2.5-flash".to_string())
+        }
+    }
+}
+
+fn inference_request(
+    client: &Client,
+    app_type: &AppType,
+    api_key: &str,
+    base_url: &str,
+    model: &str,
+) -> (String, RequestBuilder) {
+    let base_url = base_url.trim().trim_end_matches('/');
+    match app_type {
+        AppType::Claude => {
+            let url = format!("{base_url}/v1/messages");
+            let body = json!({
+                "model": model,
+                "max_tokens": INFERENCE_MAX_TOKENS,
+                "stream": true,
+                "messages": [{ "role": "user", "content": INFERENCE_PROMPT }],
+            });
+            let request = client
+                .post(&url)
+                .header("x-api-key", api_key)
+                .bearer_auth(api_key)
+                .header("anthropic-version", "2023-06-01")
+                .json(&body);
+            (url, request)
+        }
+        AppType::Codex => {
+            let url = format!("{base_url}/responses");
+            let body = json!({
+                "model": model,
+                "input": INFERENCE_PROMPT,
+                "max_output_tokens": INFERENCE_MAX_TOKENS,
+                "stream": true,
+            });
+            (
+                url.clone(),
+                client.post(&url).bearer_auth(api_key).json(&body),
+            )
+        }
+        AppType::Gemini => {
+            let url = format!("{base_url}/v1beta/models/{model}:streamGenerateContent?alt=sse");
+            let body = json!({
+                "contents": [{ "role": "user", "parts": [{ "text": INFERENCE_PROMPT }] }],
+                "generationConfig": { "maxOutputTokens": INFERENCE_MAX_TOKENS },
+            });
+            let request = client
+                .post(&url)
+                .header("x-goog-api-key", api_key)
+                .json(&body);
+            (url, request)
+        }
+    }
+}
+
+/// 逐行解析 SSE，记录首个文本片段的时间与输出 token 数
+struct StreamTally {
+    app_type: AppType,
+    first_token_ms: Option<u128>,
+    /// 收到的非空文本片段数，usage 缺失时作为 token 数的近似
+    text_events: u64,
+    reported_tokens: Option<u64>,
+    served_model: Option<String>,
+}
+
+impl StreamTally {
+    fn new(app_type: AppType) -> Self {
+        Self {
+            app_type,
+            first_token_ms: None,
+            text_events: 0,
+            reported_tokens: None,
+            served_model: None,
+        }
+    }
+
+    fn feed_line(&mut self, line: &str, elapsed_ms: u128) {
+        let Some(data) = line.strip_prefix("data:").map(str::trim) else {
+            return;
+        };
+        let Ok(event) = serde_json::from_str::<Value>(data) else {
+            return;
+        };
+
+        if self.served_model.is_none() {
+            let model = match self.app_type {
+                AppType::Claude => event.pointer("/message/model"),
+                AppType::Codex => event.pointer("/response/model"),
+                AppType::Gemini => event.get("modelVersion"),
+            };
+            self.served_model = model.and_then(Value::as_str).map(str::to_string);
+        }
+
+        let (text, tokens) = match self.app_type {
+            AppType::Claude => (
+                event
+                    .pointer("/delta/text")
+                    .filter(|_| event["type"] == "content_block_delta"),
+                event.pointer("/usage/output_tokens"),
+            ),
+            AppType::Codex => (
+                event
+                    .get("delta")
+                    .filter(|_| event["type"] == "response.output_text.delta"),
+                event.pointer("/response/usage/output_tokens"),
+            ),
+            AppType::Gemini => (
+                event.pointer("/candidates/0/content/parts/0/text"),
+                event.pointer("/usageMetadata/candidatesTokenCount"),
+            ),
+        };
+
+        if text
+            .and_then(Value::as_str)
+            .is_some_and(|text| !text.is_empty())
+        {
+            self.text_events += 1;
+            self.first_token_ms.get_or_insert(elapsed_ms);
+        }
+        if let Some(tokens) = tokens.and_then(Value::as_u64) {
+            self.reported_tokens = Some(tokens);
+        }
+    }
+
+    fn output_tokens(&self) -> Option<u64> {
+        self.reported_tokens
+            .or((self.text_events > 0).then_some(self.text_events))
+    }
+
+    fn tokens_per_sec(&self, total_ms: u128) -> Option<f64> {
+        let first = self.first_token_ms?;
+        let tokens = self.output_tokens()?;
+        let generating_ms = total_ms.saturating_sub(first);
+        if tokens < 2 || generating_ms == 0 {
+            return None;
+        }
+        // 首 token 已计入首字延迟，速度只统计其后的 token
+        Some((tokens - 1) as f64 * 1000.0 / generating_ms as f64)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -140,6 +425,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn stream_tally_reads_each_app_format() {
+        let mut claude = StreamTally::new(AppType::Claude);
+        claude.feed_line(
+            r#"data: {"type":"message_start","message":{"model":"claude-sonnet-4-5-20250929"}}"#,
+            4,
+        );
+        claude.feed_line("event: content_block_delta", 5);
+        claude.feed_line(
+            r#"data: {"type":"content_block_delta","delta":{"type":"text_delta","text":"1 2"}}"#,
+            120,
+        );
+        claude.feed_line(
+            r#"data: {"type":"content_block_delta","delta":{"type":"text_delta","text":" 3"}}"#,
+            150,
+        );
+        claude.feed_line(
+            r#"data: {"type":"message_delta","usage":{"output_tokens":11}}"#,
+            220,
+        );
+        assert_eq!(claude.first_token_ms, Some(120));
+        assert_eq!(claude.output_tokens(), Some(11));
+        assert_eq!(claude.tokens_per_sec(1120), Some(10.0));
+        assert_eq!(
+            claude.served_model.as_deref(),
+            Some("claude-sonnet-4-5-20250929")
+        );
+
+        let mut codex = StreamTally::new(AppType::Codex);
+        codex.feed_line(r#"data: {"type":"response.created"}"#, 30);
+        codex.feed_line(
+            r#"data: {"type":"response.output_text.delta","delta":"1"}"#,
+            90,
+        );
+        codex.feed_line(
+            r#"data: {"type":"response.output_text.delta","delta":" 2"}"#,
+            100,
+        );
+        assert_eq!(codex.first_token_ms, Some(90));
+        assert_eq!(codex.output_tokens(), Some(2));
+
+        let mut gemini = StreamTally::new(AppType::Gemini);
+        gemini.feed_line(
+            r#"data: {"candidates":[{"content":{"parts":[{"text":"1 2 3"}]}}],"usageMetadata":{"candidatesTokenCount":5}}"#,
+            300,
+        );
+        assert_eq!(gemini.first_token_ms, Some(300));
+        assert_eq!(gemini.output_tokens(), Some(5));
+        assert_eq!(gemini.tokens_per_sec(300), None);
+    }
+
     #[test]
     fn test_endpoints_handles_empty_list() {
         let result =
@@ -0,0 +1,271 @@
+use chrono::Utc;
+use serde::Serialize;
+
+use crate::app_config::{AppType, McpApps, McpServer};
+use crate::error::AppError;
+use crate::prompt::Prompt;
+use crate::services::skill::{SkillRepo, SkillService, SkillState};
+use crate::services::{McpService, PromptService};
+use crate::stacks::{StackDefinition, BUILTIN_STACKS};
+use crate::store::AppState;
+
+/// 套件条目类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StackItemKind {
+    Mcp,
+    Prompt,
+    Skill,
+}
+
+/// 单个条目的处理结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StackItemReport {
+    pub kind: StackItemKind,
+    pub id: String,
+    pub apps: Vec<String>,
+    /// 跳过原因（alreadyEnabled / alreadyInstalled / claudeOnly）或失败信息
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// 应用套件的汇总结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StackApplyReport {
+    pub stack: String,
+    pub added: Vec<StackItemReport>,
+    pub skipped: Vec<StackItemReport>,
+    pub failed: Vec<StackItemReport>,
+}
+
+impl StackApplyReport {
+    fn push_added(&mut self, kind: StackItemKind, id: &str, apps: &[AppType]) {
+        self.added.push(StackItemReport {
+            kind,
+            id: id.to_string(),
+            apps: app_names(apps),
+            reason: None,
+        });
+    }
+
+    fn push_skipped(&mut self, kind: StackItemKind, id: &str, apps: &[AppType], reason: &str) {
+        self.skipped.push(StackItemReport {
+            kind,
+            id: id.to_string(),
+            apps: app_names(apps),
+            reason: Some(reason.to_string()),
+        });
+    }
+
+    fn push_failed(&mut self, kind: StackItemKind, id: &str, apps: &[AppType], err: String) {
+        log::warn!("应用套件 {} 时处理 {id} 失败: {err}", self.stack);
+        self.failed.push(StackItemReport {
+            kind,
+            id: id.to_string(),
+            apps: app_names(apps),
+            reason: Some(err),
+        });
+    }
+}
+
+pub struct StackService;
+
+impl StackService {
+    /// 列出内置套件
+    pub fn list() -> Vec<StackDefinition> {
+        BUILTIN_STACKS.clone()
+    }
+
+    /// 安装并启用套件中的全部内容（MCP、提示词、技能）
+    ///
+    /// 单个条目失败不会中断其余条目，结果记录在 `failed` 中。
+    pub async fn apply(
+        state: &AppState,
+        skill_service: &SkillService,
+        name: &str,
+        apps: &[AppType],
+    ) -> Result<StackApplyReport, AppError> {
+        let stack = Self::resolve(name)?;
+        let mut report = Self::apply_mcp_and_prompt(state, stack, apps)?;
+
+        for skill in &stack.skills {
+            let claude = [AppType::Claude];
+            if !apps.contains(&AppType::Claude) {
+                report.push_skipped(StackItemKind::Skill, skill.directory, &[], "claudeOnly");
+                continue;
+            }
+
+            let installed = state
+                .db
+                .get_skills()?
+                .get(skill.directory)
+                .is_some_and(|s| s.installed);
+            if installed {
+                report.push_skipped(
+                    StackItemKind::Skill,
+                    skill.directory,
+                    &claude,
+                    "alreadyInstalled",
+                );
+                continue;
+            }
+
+            let repo = SkillRepo {
+                owner: skill.repo_owner.to_string(),
+                name: skill.repo_name.to_string(),
+                branch: skill.repo_branch.to_string(),
+                enabled: true,
+                skills_path: None,
+            };
+            let result = skill_service
+                .install_skill(skill.directory.to_string(), repo)
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|_| {
+                    state
+                        .db
+                        .update_skill_state(
+                            skill.directory,
+                            &SkillState {
+                                installed: true,
+                                installed_at: Utc::now(),
+                                source: None,
+                            },
+                        )
+                        .map_err(|e| e.to_string())
+                });
+
+            match result {
+                Ok(()) => report.push_added(StackItemKind::Skill, skill.directory, &claude),
+                Err(err) => report.push_failed(StackItemKind::Skill, skill.directory, &claude, err),
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// 仅处理 MCP 与提示词（不涉及网络下载）
+    pub fn apply_mcp_and_prompt(
+        state: &AppState,
+        stack: &StackDefinition,
+        apps: &[AppType],
+    ) -> Result<StackApplyReport, AppError> {
+        if apps.is_empty() {
+            return Err(AppError::InvalidInput(
+                "At least one app is required to apply a stack".to_string(),
+            ));
+        }
+
+        let mut report = StackApplyReport {
+            stack: stack.id.to_string(),
+            added: Vec::new(),
+            skipped: Vec::new(),
+            failed: Vec::new(),
+        };
+
+        let existing = McpService::get_all_servers(state)?;
+        for spec in &stack.mcp_servers {
+            // 已存在的同名服务器保留用户自己的定义，只补齐未启用的应用
+            let (result, targets) = match existing.get(spec.id) {
+                Some(server) => {
+                    let missing: Vec<AppType> = apps
+                        .iter()
+                        .filter(|app| !server.apps.is_enabled_for(app))
+                        .cloned()
+                        .collect();
+                    if missing.is_empty() {
+                        report.push_skipped(StackItemKind::Mcp, spec.id, apps, "alreadyEnabled");
+                        continue;
+                    }
+                    let result = missing.iter().try_for_each(|app| {
+                        McpService::toggle_app(state, spec.id, app.clone(), true)
+                    });
+                    (result, missing)
+                }
+                None => {
+                    let mut server_apps = McpApps::default();
+                    for app in apps {
+                        server_apps.set_enabled_for(app, true);
+                    }
+                    let server = McpServer {
+                        id: spec.id.to_string(),
+                        name: spec.name.to_string(),
+                        server: spec.server.clone(),
+                        apps: server_apps,
+                        description: None,
+                        homepage: Some(spec.homepage.to_string()),
+                        docs: None,
+                        tags: vec![format!("stack:{}", stack.id)],
+                    };
+                    (McpService::upsert_server(state, server), apps.to_vec())
+                }
+            };
+
+            match result {
+                Ok(()) => report.push_added(StackItemKind::Mcp, spec.id, &targets),
+                Err(err) => {
+                    report.push_failed(StackItemKind::Mcp, spec.id, &targets, err.to_string())
+                }
+            }
+        }
+
+        if let Some(spec) = &stack.prompt {
+            for app in apps {
+                let target = [app.clone()];
+                let existing = state.storage.get_prompt(app.as_str(), spec.id)?;
+                if existing.as_ref().is_some_and(|p| p.enabled) {
+                    report.push_skipped(StackItemKind::Prompt, spec.id, &target, "alreadyEnabled");
+                    continue;
+                }
+
+                let result = if existing.is_some() {
+                    PromptService::enable_prompt(state, app.clone(), spec.id)
+                } else {
+                    let now = Utc::now().timestamp();
+                    let prompt = Prompt {
+                        id: spec.id.to_string(),
+                        name: spec.name.to_string(),
+                        content: spec.content.to_string(),
+                        description: Some(stack.description.to_string()),
+                        enabled: false,
+                        created_at: Some(now),
+                        updated_at: Some(now),
+                        version: None,
+                    };
+                    PromptService::upsert_prompt(state, app.clone(), spec.id, prompt)
+                        .and_then(|_| PromptService::enable_prompt(state, app.clone(), spec.id))
+                };
+
+                match result {
+                    Ok(()) => report.push_added(StackItemKind::Prompt, spec.id, &target),
+                    Err(err) => {
+                        report.push_failed(StackItemKind::Prompt, spec.id, &target, err.to_string())
+                    }
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// 按 ID 或显示名（忽略大小写）查找套件
+    pub fn resolve(name: &str) -> Result<&'static StackDefinition, AppError> {
+        let name = name.trim();
+        BUILTIN_STACKS
+            .iter()
+            .find(|stack| stack.id == name || stack.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                AppError::localized(
+                    "stack.not_found",
+                    format!("未找到套件: {name}"),
+                    format!("Stack not found: {name}"),
+                )
+            })
+    }
+}
+
+fn app_names(apps: &[AppType]) -> Vec<String> {
+    apps.iter().map(|app| app.as_str().to_string()).collect()
+}
@@ -0,0 +1,204 @@
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::{Provider, UsageResult};
+use crate::services::provider::UsageQueryExecutor;
+use crate::services::{is_management_paused, AccessWindowService};
+use crate::store::AppState;
+use crate::usage_format::format_percent;
+
+/// 小组件通常每隔几秒轮询一次，缓存期内直接返回上次结果
+const CACHE_TTL: Duration = Duration::from_secs(2);
+
+static CACHE: Lazy<Mutex<Option<(Instant, StatusSummary)>>> = Lazy::new(Default::default);
+
+/// 当前供应商的健康标记，均来自已记录的结果，不会发起网络请求
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusHealth {
+    /// 最近一次凭证探测结果；未探测过为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credentials_valid: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    /// 是否处于可用时段内；未设置时段视为可用
+    pub in_access_window: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppStatus {
+    pub app: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider_name: Option<String>,
+    /// 最近一次用量查询中占用比例最高的额度（0-100）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quota_used_percent: Option<f64>,
+    /// 格式化后的占用比例，如 `62.5%`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quota_used_text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage_checked_at: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health: Option<StatusHealth>,
+}
+
+/// 供菜单栏扩展、桌面小组件等小界面使用的精简状态
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusSummary {
+    pub generated_at: i64,
+    pub management_paused: bool,
+    pub apps: Vec<AppStatus>,
+}
+
+pub struct StatusSummaryService;
+
+impl StatusSummaryService {
+    /// 返回当前状态摘要，缓存期内不重复读取数据库
+    pub fn get(state: &AppState) -> Result<StatusSummary, AppError> {
+        let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((at, summary)) = cache.as_ref() {
+            if at.elapsed() < CACHE_TTL {
+                return Ok(summary.clone());
+            }
+        }
+        let summary = Self::build(state)?;
+        *cache = Some((Instant::now(), summary.clone()));
+        Ok(summary)
+    }
+
+    /// 切换供应商后丢弃缓存，让下一次轮询立即反映变化
+    pub fn invalidate() {
+        *CACHE.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    fn build(state: &AppState) -> Result<StatusSummary, AppError> {
+        let mut apps = Vec::new();
+        for app_type in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+            let current = state.storage.get_current_provider(app_type.as_str())?;
+            let provider = match &current {
+                Some(id) => state
+                    .storage
+                    .get_all_providers(app_type.as_str())?
+                    .shift_remove(id),
+                None => None,
+            };
+            apps.push(match provider {
+                Some(provider) => app_status(app_type.as_str(), &provider),
+                None => AppStatus {
+                    app: app_type.as_str().to_string(),
+                    provider_id: current,
+                    provider_name: None,
+                    quota_used_percent: None,
+                    quota_used_text: None,
+                    usage_checked_at: None,
+                    health: None,
+                },
+            });
+        }
+
+        Ok(StatusSummary {
+            generated_at: chrono::Utc::now().timestamp(),
+            management_paused: is_management_paused(),
+            apps,
+        })
+    }
+}
+
+fn app_status(app: &str, provider: &Provider) -> AppStatus {
+    let meta = provider.meta.as_ref();
+    let last_usage = UsageQueryExecutor::last_result(&provider.id);
+    let usage_error = last_usage
+        .as_ref()
+        .filter(|(_, result)| !result.success)
+        .and_then(|(_, result)| result.error.clone());
+
+    let quota_used = last_usage.as_ref().and_then(|(_, r)| quota_used_percent(r));
+
+    AppStatus {
+        app: app.to_string(),
+        provider_id: Some(provider.id.clone()),
+        provider_name: Some(provider.name.clone()),
+        quota_used_percent: quota_used,
+        quota_used_text: quota_used.map(format_percent),
+        usage_checked_at: last_usage.as_ref().map(|(at, _)| *at),
+        health: Some(StatusHealth {
+            credentials_valid: meta.and_then(|m| m.credential_status.as_ref().map(|s| s.valid)),
+            latency_ms: meta.and_then(|m| m.latency.as_ref().map(|l| l.latency_ms)),
+            in_access_window: AccessWindowService::is_available(provider),
+            usage_error,
+        }),
+    }
+}
+
+/// 所有套餐及分桶中已用比例的最大值，只看能算出比例的条目
+fn quota_used_percent(result: &UsageResult) -> Option<f64> {
+    let ratio = |total: Option<f64>, used: Option<f64>, remaining: Option<f64>| {
+        let total = total.filter(|t| *t > 0.0)?;
+        let used = used.or_else(|| remaining.map(|r| total - r))?;
+        Some((used / total * 100.0).clamp(0.0, 100.0))
+    };
+
+    result
+        .data
+        .iter()
+        .flatten()
+        .flat_map(|data| {
+            std::iter::once(ratio(data.total, data.used, data.remaining)).chain(
+                data.buckets
+                    .iter()
+                    .map(|b| ratio(b.total, b.used, b.remaining)),
+            )
+        })
+        .flatten()
+        .reduce(f64::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::{UsageBucket, UsageData};
+
+    #[test]
+    fn quota_percent_takes_the_fullest_plan_or_bucket() {
+        let result = UsageResult {
+            success: true,
+            data: Some(vec![
+                UsageData {
+                    total: Some(100.0),
+                    remaining: Some(75.0),
+                    ..Default::default()
+                },
+                UsageData {
+                    buckets: vec![UsageBucket {
+                        total: Some(10.0),
+                        used: Some(6.0),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            ]),
+            error: None,
+        };
+        assert_eq!(quota_used_percent(&result), Some(60.0));
+
+        let unknown = UsageResult {
+            success: true,
+            data: Some(vec![UsageData {
+                remaining: Some(5.0),
+                ..Default::default()
+            }]),
+            error: None,
+        };
+        assert_eq!(quota_used_percent(&unknown), None);
+    }
+}
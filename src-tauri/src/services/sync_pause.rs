@@ -0,0 +1,207 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::app_config::AppType;
+use crate::codex_config::get_codex_config_path;
+use crate::error::AppError;
+use crate::prompt_files::prompt_file_path;
+use crate::services::demo::{ensure_not_demo, is_demo_mode};
+use crate::services::gemini_context::GeminiContextService;
+use crate::services::mcp::McpService;
+use crate::services::prompt_crypto::PromptCryptoService;
+use crate::services::provider::{ClaudeFlavorEnv, LiveConfigSync};
+use crate::services::safe_mode::{ensure_not_safe_mode, is_safe_mode};
+use crate::store::AppState;
+
+pub(crate) const PAUSED_KEY: &str = "management_paused";
+
+/// 进程内缓存，避免每次写入 live 配置前都查询数据库
+static PAUSED: AtomicBool = AtomicBool::new(false);
+
+/// 管理是否已暂停；暂停期间（以及演示模式、安全模式下）不写入任何 live 配置
+pub fn is_management_paused() -> bool {
+    PAUSED.load(Ordering::Relaxed) || is_demo_mode() || is_safe_mode()
+}
+
+/// 恢复管理前各应用待同步的差异
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumeSyncPreview {
+    pub app_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider_id: Option<String>,
+    pub provider_changed: bool,
+    /// 当前 live 配置（读取失败时为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub live_settings: Option<Value>,
+    /// 恢复后将写入的供应商配置
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_settings: Option<Value>,
+    /// 已启用但 live 配置中缺失的 MCP 服务器
+    pub mcp_to_add: Vec<String>,
+    /// 已停用但仍存在于 live 配置中的 MCP 服务器
+    pub mcp_to_remove: Vec<String>,
+    pub prompt_changed: bool,
+}
+
+impl ResumeSyncPreview {
+    pub fn has_changes(&self) -> bool {
+        self.provider_changed
+            || self.prompt_changed
+            || !self.mcp_to_add.is_empty()
+            || !self.mcp_to_remove.is_empty()
+    }
+}
+
+/// 暂停/恢复对 live 配置的全部托管写入
+pub struct SyncPauseService;
+
+impl SyncPauseService {
+    /// 启动时从数据库恢复暂停状态
+    pub fn load(state: &AppState) -> Result<bool, AppError> {
+        let paused = state.storage.get_setting(PAUSED_KEY)?.as_deref() == Some("true");
+        PAUSED.store(paused, Ordering::Relaxed);
+        if paused {
+            log::info!("管理处于暂停状态，不会写入 live 配置");
+        }
+        Ok(paused)
+    }
+
+    pub fn pause(state: &AppState) -> Result<(), AppError> {
+        Self::set_paused(state, true)?;
+        log::info!("已暂停管理，后续变更仅写入数据库");
+        Ok(())
+    }
+
+    /// 计算恢复管理时将执行的同步（不写入任何文件）
+    pub fn preview(state: &AppState) -> Result<Vec<ResumeSyncPreview>, AppError> {
+        [AppType::Claude, AppType::Codex, AppType::Gemini]
+            .into_iter()
+            .map(|app_type| Self::preview_app(state, app_type))
+            .collect()
+    }
+
+    /// 恢复管理并一次性同步暂停期间积累的变更，返回实际同步前的差异
+    pub fn resume(state: &AppState) -> Result<Vec<ResumeSyncPreview>, AppError> {
+        ensure_not_demo()?;
+        ensure_not_safe_mode()?;
+        let previews = Self::preview(state)?;
+        Self::set_paused(state, false)?;
+
+        for preview in previews.iter().filter(|p| p.has_changes()) {
+            let app_type = AppType::from_str(&preview.app_type)?;
+
+            if let (true, Some(id)) = (preview.provider_changed, preview.provider_id.as_ref()) {
+                let providers = state.storage.get_all_providers(app_type.as_str())?;
+                if let Some(provider) = providers.get(id) {
+                    LiveConfigSync::write_live_snapshot(&app_type, provider)?;
+                }
+            }
+
+            McpService::sync_app(state, &app_type)?;
+            for id in &preview.mcp_to_remove {
+                McpService::remove_server_from_app(state, id, &app_type)?;
+            }
+
+            if preview.prompt_changed {
+                if let Some(prompt) = state.storage.get_enabled_prompt(app_type.as_str())? {
+                    // 口令加密且未解锁的提示词保持 live 文件不变，解锁后启用即可写入
+                    match PromptCryptoService::plaintext(&app_type, &prompt.id, &prompt.content) {
+                        Ok(content) => {
+                            crate::config::write_text_file(&prompt_file_path(&app_type)?, &content)?
+                        }
+                        Err(e) => log::warn!("跳过写入提示词 {}: {e}", prompt.id),
+                    }
+                }
+            }
+        }
+
+        GeminiContextService::resync(state)?;
+        log::info!("已恢复管理并完成同步");
+        Ok(previews)
+    }
+
+    fn set_paused(state: &AppState, paused: bool) -> Result<(), AppError> {
+        state
+            .storage
+            .set_setting(PAUSED_KEY, if paused { "true" } else { "false" })?;
+        PAUSED.store(paused, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn preview_app(state: &AppState, app_type: AppType) -> Result<ResumeSyncPreview, AppError> {
+        let provider_id = state.storage.get_current_provider(app_type.as_str())?;
+        let target_settings = match provider_id.as_ref() {
+            Some(id) => state
+                .storage
+                .get_all_providers(app_type.as_str())?
+                .get(id)
+                .map(|p| {
+                    ClaudeFlavorEnv::with_flavor_env(&app_type, p)
+                        .settings_config
+                        .clone()
+                }),
+            None => None,
+        };
+        let live_settings = LiveConfigSync::read_live_settings(app_type.clone()).ok();
+        let provider_changed = target_settings.is_some() && target_settings != live_settings;
+
+        let live_ids = live_mcp_ids(&app_type).unwrap_or_default();
+        let mut mcp_to_add = Vec::new();
+        let mut mcp_to_remove = Vec::new();
+        for (id, server) in state.storage.get_all_mcp_servers()? {
+            let enabled = server.apps.is_enabled_for(&app_type);
+            if enabled && !live_ids.contains(&id) {
+                mcp_to_add.push(id);
+            } else if !enabled && live_ids.contains(&id) {
+                mcp_to_remove.push(id);
+            }
+        }
+
+        let prompt_changed = match state.storage.get_enabled_prompt(app_type.as_str())? {
+            Some(prompt) => {
+                let live = std::fs::read_to_string(prompt_file_path(&app_type)?).ok();
+                let target = PromptCryptoService::plaintext(&app_type, &prompt.id, &prompt.content);
+                target.is_ok_and(|target| live.as_deref() != Some(target.as_str()))
+            }
+            None => false,
+        };
+
+        Ok(ResumeSyncPreview {
+            app_type: app_type.as_str().to_string(),
+            provider_id,
+            provider_changed,
+            live_settings,
+            target_settings,
+            mcp_to_add,
+            mcp_to_remove,
+            prompt_changed,
+        })
+    }
+}
+
+fn live_mcp_ids(app_type: &AppType) -> Result<HashSet<String>, AppError> {
+    match app_type {
+        AppType::Claude => Ok(crate::claude_mcp::read_mcp_servers_map()?
+            .into_keys()
+            .collect()),
+        AppType::Gemini => Ok(crate::gemini_mcp::read_mcp_servers_map()?
+            .into_keys()
+            .collect()),
+        AppType::Codex => {
+            let path = get_codex_config_path();
+            let Ok(text) = std::fs::read_to_string(&path) else {
+                return Ok(HashSet::new());
+            };
+            let root: toml::Table = toml::from_str(&text).map_err(|e| AppError::toml(&path, e))?;
+            Ok(root
+                .get("mcp_servers")
+                .and_then(|v| v.as_table())
+                .map(|tbl| tbl.keys().cloned().collect())
+                .unwrap_or_default())
+        }
+    }
+}
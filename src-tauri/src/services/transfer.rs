@@ -0,0 +1,539 @@
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use subtle::ConstantTimeEq;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::oneshot;
+
+use crate::database::Database;
+use crate::error::AppError;
+
+/// 传输服务的有效期
+const TRANSFER_TTL: Duration = Duration::from_secs(10 * 60);
+/// 口令错误次数上限，超过后关闭服务以防暴力猜测
+const MAX_FAILED_ATTEMPTS: u32 = 5;
+const TRANSFER_PATH: &str = "/transfer";
+const CHALLENGE_PATH: &str = "/transfer/challenge";
+const CHALLENGE_HEADER: &str = "x-transfer-challenge";
+const RESPONSE_HEADER: &str = "x-transfer-response";
+const PAYLOAD_MAGIC: &[u8] = b"CLIHUBX2";
+const SALT_LEN: usize = 16;
+const CHALLENGE_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+/// 同时有效的挑战数量上限，旧的挑战先失效
+const MAX_OPEN_CHALLENGES: usize = 8;
+/// Argon2id 参数：口令只有约 50 位熵，慢速派生使截获流量后的离线猜测不可行
+#[cfg(not(test))]
+const KDF_MEMORY_KIB: u32 = 64 * 1024;
+#[cfg(test)]
+const KDF_MEMORY_KIB: u32 = 1024;
+const KDF_ITERATIONS: u32 = 3;
+/// 去掉易混淆字符（0/O、1/I）的口令字符集
+const CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+const CODE_LEN: usize = 10;
+
+/// 当前运行中的传输服务（编号与关闭信号），同一时间只允许一个
+static ACTIVE: Lazy<Mutex<Option<(u64, oneshot::Sender<()>)>>> = Lazy::new(|| Mutex::new(None));
+static NEXT_SERVER_ID: AtomicU64 = AtomicU64::new(1);
+
+/// 已启动的传输服务信息，供另一台设备连接
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferSession {
+    /// 局域网地址，形如 `192.168.1.10:53817`
+    pub address: String,
+    /// 一次性口令，接收端据此完成挑战应答并解密导出内容；口令本身不会在网络上传输
+    pub code: String,
+    pub expires_at: i64,
+}
+
+/// 通过局域网在设备间传输完整的 SQL 导出
+///
+/// 发送端导出数据库，用由一次性口令经 Argon2id 派生的密钥加密；接收端先领取随机挑战，
+/// 以派生出的校验密钥应答，通过后才拿到密文。成功传输一次或过期后服务自动关闭；
+/// 接收端解密后按 SQL 导入流程写入（导入前自动备份）。
+pub struct TransferService;
+
+impl TransferService {
+    pub async fn start_server(db: Arc<Database>) -> Result<TransferSession, AppError> {
+        // 只监听局域网网卡，且仅在传输期间监听
+        let ip = lan_ip().ok_or_else(|| {
+            AppError::localized(
+                "transfer.no_lan",
+                "未找到可用的局域网网络，无法启动传输服务",
+                "No LAN network interface found; cannot start the transfer server",
+            )
+        })?;
+
+        let code = generate_code();
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let key_code = code.clone();
+        let (payload, keys) = tauri::async_runtime::spawn_blocking(move || {
+            let keys = derive_keys(&salt, &key_code)?;
+            let payload = encrypt(&export_sql_bytes(&db)?, &keys.cipher)?;
+            Ok::<_, AppError>((payload, keys))
+        })
+        .await
+        .map_err(|e| AppError::Message(format!("导出数据库失败: {e}")))??;
+
+        let listener = TcpListener::bind(SocketAddr::new(ip, 0))
+            .await
+            .map_err(|e| AppError::Message(format!("启动传输服务失败: {e}")))?;
+        let port = listener
+            .local_addr()
+            .map_err(|e| AppError::Message(format!("读取传输服务端口失败: {e}")))?
+            .port();
+
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        let server_id = NEXT_SERVER_ID.fetch_add(1, Ordering::Relaxed);
+        // 替换旧服务时丢弃其发送端，旧服务随即退出
+        *ACTIVE.lock()? = Some((server_id, shutdown_tx));
+
+        tauri::async_runtime::spawn(async move {
+            serve(listener, payload, salt, keys.auth, shutdown_rx).await;
+            if let Ok(mut active) = ACTIVE.lock() {
+                if active.as_ref().is_some_and(|(id, _)| *id == server_id) {
+                    *active = None;
+                }
+            }
+        });
+
+        let session = TransferSession {
+            address: SocketAddr::new(ip, port).to_string(),
+            code: format_code(&code),
+            expires_at: (chrono::Utc::now() + TRANSFER_TTL).timestamp(),
+        };
+        log::info!("传输服务已启动: {}", session.address);
+        Ok(session)
+    }
+
+    pub fn stop_server() -> Result<bool, AppError> {
+        Ok(ACTIVE
+            .lock()?
+            .take()
+            .is_some_and(|(_, tx)| tx.send(()).is_ok()))
+    }
+
+    /// 从另一台设备拉取并导入完整导出，返回导入前自动创建的备份 ID
+    pub async fn receive(db: Arc<Database>, address: &str, code: &str) -> Result<String, AppError> {
+        let code = normalize_code(code);
+        if code.len() != CODE_LEN {
+            return Err(invalid_code());
+        }
+
+        let address = address.trim().trim_start_matches("http://").to_string();
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(60))
+            .build()
+            .map_err(|e| AppError::Message(format!("创建 HTTP 客户端失败: {e}")))?;
+
+        let challenge = fetch(
+            &address,
+            client.get(format!("http://{address}{CHALLENGE_PATH}")),
+        )
+        .await?;
+        if challenge.len() != SALT_LEN + CHALLENGE_LEN {
+            return Err(invalid_payload());
+        }
+        let (salt, nonce) = challenge.split_at(SALT_LEN);
+
+        let salt = salt.to_vec();
+        let keys = tauri::async_runtime::spawn_blocking(move || derive_keys(&salt, &code))
+            .await
+            .map_err(|e| AppError::Message(format!("派生传输密钥失败: {e}")))??;
+
+        let payload = fetch(
+            &address,
+            client
+                .get(format!("http://{address}{TRANSFER_PATH}"))
+                .header(CHALLENGE_HEADER, to_hex(nonce))
+                .header(
+                    RESPONSE_HEADER,
+                    to_hex(&challenge_response(&keys.auth, nonce)),
+                ),
+        )
+        .await?;
+        let sql = decrypt(&payload, &keys.cipher)?;
+
+        tauri::async_runtime::spawn_blocking(move || {
+            let file = tempfile::Builder::new()
+                .prefix("cli-hub-transfer-")
+                .suffix(".sql")
+                .tempfile()
+                .map_err(|e| AppError::Message(format!("创建临时文件失败: {e}")))?;
+            std::fs::write(file.path(), &sql).map_err(|e| AppError::io(file.path(), e))?;
+            db.import_sql(file.path())
+        })
+        .await
+        .map_err(|e| AppError::Message(format!("导入传输内容失败: {e}")))?
+    }
+}
+
+/// 发送请求并读取响应体；403 表示挑战应答未通过，即口令错误
+async fn fetch(address: &str, request: reqwest::RequestBuilder) -> Result<Vec<u8>, AppError> {
+    let response = request.send().await.map_err(|e| {
+        AppError::localized(
+            "transfer.connect_failed",
+            format!("无法连接到传输服务 {address}: {e}"),
+            format!("Failed to connect to transfer server {address}: {e}"),
+        )
+    })?;
+
+    match response.status().as_u16() {
+        200 => {}
+        403 => return Err(invalid_code()),
+        status => return Err(AppError::Message(format!("传输服务返回异常状态: {status}"))),
+    }
+
+    response
+        .bytes()
+        .await
+        .map(|bytes| bytes.to_vec())
+        .map_err(|e| AppError::Message(format!("接收导出内容失败: {e}")))
+}
+
+/// 传输服务收到的请求
+enum TransferRequest {
+    Challenge,
+    /// 携带挑战与应答（均为十六进制）
+    Payload {
+        challenge: String,
+        response: String,
+    },
+    Other,
+}
+
+async fn serve(
+    listener: TcpListener,
+    payload: Vec<u8>,
+    salt: [u8; SALT_LEN],
+    auth_key: [u8; 32],
+    mut shutdown: oneshot::Receiver<()>,
+) {
+    let deadline = tokio::time::sleep(TRANSFER_TTL);
+    tokio::pin!(deadline);
+    let mut failed_attempts = 0;
+    let mut challenges: Vec<[u8; CHALLENGE_LEN]> = Vec::new();
+
+    loop {
+        let mut stream = tokio::select! {
+            _ = &mut shutdown => {
+                log::info!("传输服务已关闭");
+                return;
+            }
+            _ = &mut deadline => {
+                log::info!("传输服务已过期");
+                return;
+            }
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    log::warn!("传输服务接受连接失败: {e}");
+                    continue;
+                }
+            },
+        };
+
+        let authorized = match read_request(&mut stream).await {
+            Ok(TransferRequest::Challenge) => {
+                let mut challenge = [0u8; CHALLENGE_LEN];
+                OsRng.fill_bytes(&mut challenge);
+                if challenges.len() >= MAX_OPEN_CHALLENGES {
+                    challenges.remove(0);
+                }
+                challenges.push(challenge);
+                let body = [salt.as_slice(), challenge.as_slice()].concat();
+                let _ = write_response(&mut stream, "200 OK", &body).await;
+                continue;
+            }
+            Ok(TransferRequest::Payload {
+                challenge,
+                response,
+            }) => {
+                // 挑战只能使用一次，无论应答是否正确
+                let issued = from_hex(&challenge)
+                    .and_then(|c| challenges.iter().position(|open| open[..] == c[..]))
+                    .map(|idx| challenges.remove(idx));
+                match (issued, from_hex(&response)) {
+                    (Some(challenge), Some(response)) => {
+                        let expected = challenge_response(&auth_key, &challenge);
+                        bool::from(expected.as_slice().ct_eq(&response))
+                    }
+                    _ => false,
+                }
+            }
+            Ok(TransferRequest::Other) => {
+                let _ = write_response(&mut stream, "404 Not Found", &[]).await;
+                continue;
+            }
+            Err(e) => {
+                log::debug!("读取传输请求失败: {e}");
+                continue;
+            }
+        };
+
+        if !authorized {
+            failed_attempts += 1;
+            let _ = write_response(&mut stream, "403 Forbidden", &[]).await;
+            if failed_attempts >= MAX_FAILED_ATTEMPTS {
+                log::warn!("传输口令错误次数过多，已关闭传输服务");
+                break;
+            }
+            continue;
+        }
+
+        match write_response(&mut stream, "200 OK", &payload).await {
+            Ok(()) => {
+                // 一次性口令：成功传输后立即关闭
+                log::info!("已完成一次数据库传输");
+                break;
+            }
+            Err(e) => log::warn!("发送导出内容失败，等待重试: {e}"),
+        }
+    }
+}
+
+/// 读取请求头，按路径区分领取挑战与下载密文
+async fn read_request(stream: &mut TcpStream) -> std::io::Result<TransferRequest> {
+    let mut buf = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 1024];
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+        if buf.len() > 8 * 1024 {
+            return Err(std::io::Error::other("request header too large"));
+        }
+        let read = tokio::time::timeout(Duration::from_secs(10), stream.read(&mut chunk))
+            .await
+            .map_err(|_| std::io::Error::other("request timed out"))??;
+        if read == 0 {
+            return Err(std::io::Error::other("connection closed"));
+        }
+        buf.extend_from_slice(&chunk[..read]);
+    }
+
+    let head = String::from_utf8_lossy(&buf);
+    let mut lines = head.split("\r\n");
+    let path = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .unwrap_or_default();
+    match path {
+        CHALLENGE_PATH => return Ok(TransferRequest::Challenge),
+        TRANSFER_PATH => {}
+        _ => return Ok(TransferRequest::Other),
+    }
+
+    let headers: Vec<(&str, &str)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim(), value.trim()))
+        .collect();
+    let header = |wanted: &str| {
+        headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(wanted))
+            .map(|(_, value)| value.to_string())
+            .unwrap_or_default()
+    };
+    Ok(TransferRequest::Payload {
+        challenge: header(CHALLENGE_HEADER),
+        response: header(RESPONSE_HEADER),
+    })
+}
+
+async fn write_response(stream: &mut TcpStream, status: &str, body: &[u8]) -> std::io::Result<()> {
+    let head = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: application/octet-stream\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body).await?;
+    stream.shutdown().await
+}
+
+fn export_sql_bytes(db: &Database) -> Result<Vec<u8>, AppError> {
+    let file = tempfile::Builder::new()
+        .prefix("cli-hub-transfer-")
+        .suffix(".sql")
+        .tempfile()
+        .map_err(|e| AppError::Message(format!("创建临时文件失败: {e}")))?;
+    db.export_sql(file.path())?;
+    std::fs::read(file.path()).map_err(|e| AppError::io(file.path(), e))
+}
+
+/// 本机在局域网中的地址；没有可用网卡时返回 None
+fn lan_ip() -> Option<IpAddr> {
+    // UDP connect 不会真正发包，仅用于让系统选择出口网卡
+    UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+        .and_then(|socket| {
+            socket.connect((Ipv4Addr::new(10, 255, 255, 255), 1))?;
+            socket.local_addr()
+        })
+        .ok()
+        .map(|addr| addr.ip())
+        .filter(|ip| !ip.is_loopback() && !ip.is_unspecified())
+}
+
+fn generate_code() -> String {
+    let mut bytes = [0u8; CODE_LEN];
+    OsRng.fill_bytes(&mut bytes);
+    bytes
+        .iter()
+        .map(|b| CODE_ALPHABET[*b as usize % CODE_ALPHABET.len()] as char)
+        .collect()
+}
+
+/// 以 `XXXXX-XXXXX` 形式展示口令，便于口述与输入
+fn format_code(code: &str) -> String {
+    let (head, tail) = code.split_at(CODE_LEN / 2);
+    format!("{head}-{tail}")
+}
+
+fn normalize_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// 由口令派生的两把密钥：加密导出内容与应答挑战互不相同
+struct TransferKeys {
+    cipher: Key,
+    auth: [u8; 32],
+}
+
+/// Argon2id(口令, 随机盐)，输出前半作加密密钥、后半作校验密钥
+fn derive_keys(salt: &[u8], code: &str) -> Result<TransferKeys, AppError> {
+    let params = Params::new(KDF_MEMORY_KIB, KDF_ITERATIONS, 1, Some(64))
+        .map_err(|e| AppError::Message(format!("派生传输密钥失败: {e}")))?;
+    let mut output = [0u8; 64];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(code.as_bytes(), salt, &mut output)
+        .map_err(|e| AppError::Message(format!("派生传输密钥失败: {e}")))?;
+
+    let (cipher, auth) = output.split_at(32);
+    let mut auth_key = [0u8; 32];
+    auth_key.copy_from_slice(auth);
+    Ok(TransferKeys {
+        cipher: Key::clone_from_slice(cipher),
+        auth: auth_key,
+    })
+}
+
+fn challenge_response(auth_key: &[u8; 32], challenge: &[u8]) -> Vec<u8> {
+    Sha256::new()
+        .chain_update(b"clihub-transfer-auth:")
+        .chain_update(auth_key)
+        .chain_update(challenge)
+        .finalize()
+        .to_vec()
+}
+
+fn encrypt(plaintext: &[u8], key: &Key) -> Result<Vec<u8>, AppError> {
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = ChaCha20Poly1305::new(key)
+        .encrypt(&nonce, plaintext)
+        .map_err(|e| AppError::Message(format!("加密导出内容失败: {e}")))?;
+
+    let mut out = Vec::with_capacity(PAYLOAD_MAGIC.len() + NONCE_LEN + ciphertext.len());
+    out.extend_from_slice(PAYLOAD_MAGIC);
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+fn decrypt(payload: &[u8], key: &Key) -> Result<Vec<u8>, AppError> {
+    if payload.len() < PAYLOAD_MAGIC.len() + NONCE_LEN || !payload.starts_with(PAYLOAD_MAGIC) {
+        return Err(invalid_payload());
+    }
+    let (nonce, ciphertext) = payload[PAYLOAD_MAGIC.len()..].split_at(NONCE_LEN);
+
+    ChaCha20Poly1305::new(key)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| invalid_code())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn from_hex(text: &str) -> Option<Vec<u8>> {
+    if text.len() % 2 != 0 || !text.is_ascii() {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).ok())
+        .collect()
+}
+
+fn invalid_payload() -> AppError {
+    AppError::localized(
+        "transfer.invalid_payload",
+        "传输内容格式无效",
+        "Invalid transfer payload",
+    )
+}
+
+fn invalid_code() -> AppError {
+    AppError::localized(
+        "transfer.invalid_code",
+        "传输口令无效或已过期",
+        "The transfer code is invalid or has expired",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypted_payload_round_trips_only_with_matching_code() {
+        let code = generate_code();
+        let salt = [7u8; SALT_LEN];
+        let keys = derive_keys(&salt, &code).unwrap();
+        let payload = encrypt(b"INSERT INTO providers VALUES (1);", &keys.cipher).unwrap();
+        assert!(payload.starts_with(PAYLOAD_MAGIC));
+
+        assert_eq!(
+            decrypt(&payload, &keys.cipher).unwrap(),
+            b"INSERT INTO providers VALUES (1);"
+        );
+        let wrong = derive_keys(&salt, "WRONGCODE2").unwrap();
+        assert!(decrypt(&payload, &wrong.cipher).is_err());
+        assert!(decrypt(&payload[..10], &keys.cipher).is_err());
+        // 加密密钥与校验密钥互不相同，应答不会泄露加密密钥
+        assert_ne!(keys.cipher.as_slice(), keys.auth.as_slice());
+    }
+
+    #[test]
+    fn challenge_responses_depend_on_code_and_challenge() {
+        let salt = [1u8; SALT_LEN];
+        let keys = derive_keys(&salt, &generate_code()).unwrap();
+        let other = derive_keys(&salt, &generate_code()).unwrap();
+
+        let response = challenge_response(&keys.auth, b"challenge-one");
+        assert_eq!(response, challenge_response(&keys.auth, b"challenge-one"));
+        assert_ne!(response, challenge_response(&keys.auth, b"challenge-two"));
+        assert_ne!(response, challenge_response(&other.auth, b"challenge-one"));
+        assert_eq!(from_hex(&to_hex(&response)).unwrap(), response);
+        assert!(from_hex("zz").is_none());
+    }
+
+    #[test]
+    fn code_formatting_round_trips() {
+        let code = generate_code();
+        assert_eq!(code.len(), CODE_LEN);
+        let formatted = format_code(&code);
+        assert_eq!(formatted.len(), CODE_LEN + 1);
+        assert_eq!(normalize_code(&formatted.to_lowercase()), code);
+    }
+}
@@ -0,0 +1,317 @@
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::app_config::AppType;
+use crate::config::{atomic_write, sanitize_provider_name};
+use crate::error::AppError;
+use crate::prompt::Prompt;
+use crate::store::AppState;
+
+const REDACTED: &str = "<redacted>";
+const ALL_APPS: [AppType; 3] = [AppType::Claude, AppType::Codex, AppType::Gemini];
+
+/// 字段名包含这些片段时视为敏感信息（不区分大小写）
+const SECRET_MARKERS: [&str; 6] = [
+    "key",
+    "token",
+    "secret",
+    "password",
+    "credential",
+    "authorization",
+];
+
+/// Codex config.toml 中形如 `xxx_key = "..."` 的敏感行
+static TOML_SECRET_LINE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?mi)^(\s*([A-Za-z0-9_.\-]*(?:key|token|secret|password)[A-Za-z0-9_\-]*)\s*=\s*)"[^"\n]*""#)
+        .expect("valid regex")
+});
+
+/// 一次导出的统计信息
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VcsExportSummary {
+    pub dir: String,
+    pub providers: usize,
+    pub mcp_servers: usize,
+    pub prompts: usize,
+    /// 内容发生变化（新建或更新）的文件
+    pub written: Vec<String>,
+    /// 数据库中已不存在、因此被删除的文件
+    pub removed: Vec<String>,
+}
+
+/// 面向 Git 的确定性导出
+///
+/// 目录结构：`providers/<app>/<id>.json`、`mcp/<id>.json`、`prompts/<app>/<id>.md`。
+/// JSON 键按字母排序并美化输出，敏感字段替换为 `<redacted>`，
+/// 内容未变的文件不会重写，便于保持最小 diff。
+pub struct VcsExportService;
+
+impl VcsExportService {
+    pub fn write(state: &AppState, dir: &Path) -> Result<VcsExportSummary, AppError> {
+        let mut files: Vec<(PathBuf, String)> = Vec::new();
+        let mut summary = VcsExportSummary {
+            dir: dir.to_string_lossy().to_string(),
+            providers: 0,
+            mcp_servers: 0,
+            prompts: 0,
+            written: Vec::new(),
+            removed: Vec::new(),
+        };
+
+        for app in ALL_APPS {
+            for (id, provider) in state.storage.get_all_providers(app.as_str())? {
+                let value = serde_json::to_value(&provider)
+                    .map_err(|e| AppError::JsonSerialize { source: e })?;
+                let path = Path::new("providers")
+                    .join(app.as_str())
+                    .join(format!("{}.json", sanitize_provider_name(&id)));
+                files.push((path, to_pretty_json(redact_value(value))?));
+                summary.providers += 1;
+            }
+
+            for (id, prompt) in state.storage.get_prompts(app.as_str())? {
+                let path = Path::new("prompts")
+                    .join(app.as_str())
+                    .join(format!("{}.md", sanitize_provider_name(&id)));
+                files.push((path, render_prompt(&prompt)));
+                summary.prompts += 1;
+            }
+        }
+
+        for (id, server) in state.storage.get_all_mcp_servers()? {
+            let value =
+                serde_json::to_value(&server).map_err(|e| AppError::JsonSerialize { source: e })?;
+            let path = Path::new("mcp").join(format!("{}.json", sanitize_provider_name(&id)));
+            files.push((path, to_pretty_json(redact_value(value))?));
+            summary.mcp_servers += 1;
+        }
+
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let expected: BTreeSet<PathBuf> = files.iter().map(|(path, _)| path.clone()).collect();
+        for (rel, content) in &files {
+            let target = dir.join(rel);
+            let unchanged = fs::read_to_string(&target)
+                .map(|existing| existing == *content)
+                .unwrap_or(false);
+            if !unchanged {
+                atomic_write(&target, content.as_bytes())?;
+                summary.written.push(display_path(rel));
+            }
+        }
+
+        summary.removed = remove_stale_files(dir, &expected)?;
+
+        log::info!(
+            "VCS 导出完成: {} 个供应商, {} 个 MCP, {} 个提示词, 写入 {} 个文件, 删除 {} 个文件",
+            summary.providers,
+            summary.mcp_servers,
+            summary.prompts,
+            summary.written.len(),
+            summary.removed.len()
+        );
+
+        Ok(summary)
+    }
+}
+
+/// 删除受管目录中已不对应任何数据的导出文件
+fn remove_stale_files(dir: &Path, expected: &BTreeSet<PathBuf>) -> Result<Vec<String>, AppError> {
+    let mut managed: Vec<PathBuf> = vec![PathBuf::from("mcp")];
+    for app in ALL_APPS {
+        managed.push(Path::new("providers").join(app.as_str()));
+        managed.push(Path::new("prompts").join(app.as_str()));
+    }
+
+    let mut removed = Vec::new();
+    for rel_dir in managed {
+        let entries = match fs::read_dir(dir.join(&rel_dir)) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+
+        let mut stale: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_file())
+            .map(|entry| rel_dir.join(entry.file_name()))
+            .filter(|rel| {
+                matches!(
+                    rel.extension().and_then(|ext| ext.to_str()),
+                    Some("json") | Some("md")
+                ) && !expected.contains(rel)
+            })
+            .collect();
+        stale.sort();
+
+        for rel in stale {
+            let path = dir.join(&rel);
+            fs::remove_file(&path).map_err(|e| AppError::io(&path, e))?;
+            removed.push(display_path(&rel));
+        }
+    }
+
+    Ok(removed)
+}
+
+fn display_path(rel: &Path) -> String {
+    rel.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn to_pretty_json(value: Value) -> Result<String, AppError> {
+    let mut text = serde_json::to_string_pretty(&sort_keys(value))
+        .map_err(|e| AppError::JsonSerialize { source: e })?;
+    text.push('\n');
+    Ok(text)
+}
+
+/// 递归按键排序，不依赖 serde_json 是否启用 preserve_order
+fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = map.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            let mut sorted = Map::new();
+            for (key, val) in entries {
+                sorted.insert(key, sort_keys(val));
+            }
+            Value::Object(sorted)
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(sort_keys).collect()),
+        other => other,
+    }
+}
+
+fn is_secret_key(key: &str) -> bool {
+    let lower = key.to_ascii_lowercase();
+    // env_key 仅保存环境变量名，不是密钥本身
+    if lower == "env_key" {
+        return false;
+    }
+    SECRET_MARKERS.iter().any(|marker| lower.contains(marker))
+}
+
+pub(crate) fn redact_value(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, val)| {
+                    let val = match val {
+                        Value::String(s) if is_secret_key(&key) && !s.is_empty() => {
+                            Value::String(REDACTED.to_string())
+                        }
+                        // Codex 的 config 字段是 TOML 文本
+                        Value::String(s) if key == "config" => Value::String(redact_toml(&s)),
+                        other => redact_value(other),
+                    };
+                    (key, val)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(redact_value).collect()),
+        other => other,
+    }
+}
+
+fn redact_toml(text: &str) -> String {
+    TOML_SECRET_LINE
+        .replace_all(text, |caps: &Captures| {
+            let key = caps[2].rsplit('.').next().unwrap_or_default();
+            if is_secret_key(key) {
+                format!("{}\"{REDACTED}\"", &caps[1])
+            } else {
+                caps[0].to_string()
+            }
+        })
+        .into_owned()
+}
+
+fn render_prompt(prompt: &Prompt) -> String {
+    let mut out = String::from("---\n");
+    out.push_str(&format!("id: {}\n", prompt.id));
+    out.push_str(&format!("name: {}\n", prompt.name));
+    if let Some(description) = prompt.description.as_deref() {
+        out.push_str(&format!("description: {description}\n"));
+    }
+    out.push_str(&format!("enabled: {}\n", prompt.enabled));
+    out.push_str("---\n\n");
+    out.push_str(prompt.content.trim_end());
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use crate::provider::Provider;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_redact_value_masks_secrets_and_toml() {
+        let value = json!({
+            "env": {
+                "ANTHROPIC_AUTH_TOKEN": "sk-secret",
+                "ANTHROPIC_BASE_URL": "https://api.example.com"
+            },
+            "auth": { "OPENAI_API_KEY": "sk-2" },
+            "config": "model_provider = \"x\"\n[model_providers.x]\nenv_key = \"OPENAI_API_KEY\"\nexperimental_bearer_token = \"abc\"\n",
+            "max_tokens": 1024
+        });
+
+        let redacted = redact_value(value);
+        assert_eq!(redacted["env"]["ANTHROPIC_AUTH_TOKEN"], REDACTED);
+        assert_eq!(
+            redacted["env"]["ANTHROPIC_BASE_URL"],
+            "https://api.example.com"
+        );
+        assert_eq!(redacted["auth"]["OPENAI_API_KEY"], REDACTED);
+        assert_eq!(redacted["max_tokens"], 1024);
+
+        let config = redacted["config"].as_str().unwrap();
+        assert!(config.contains("env_key = \"OPENAI_API_KEY\""));
+        assert!(config.contains("experimental_bearer_token = \"<redacted>\""));
+        assert!(!config.contains("abc"));
+    }
+
+    #[test]
+    fn test_write_is_deterministic_and_prunes_stale_files() {
+        let db = Arc::new(Database::memory().expect("create memory db"));
+        let state = AppState::new(db);
+        let provider = Provider::with_id(
+            "p1".to_string(),
+            "Provider One".to_string(),
+            json!({ "env": { "ANTHROPIC_AUTH_TOKEN": "sk-1", "B": 1, "A": 2 } }),
+            None,
+        );
+        state.storage.save_provider("claude", &provider).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("mcp")).unwrap();
+        fs::write(dir.path().join("mcp").join("gone.json"), "{}").unwrap();
+
+        let first = VcsExportService::write(&state, dir.path()).unwrap();
+        assert_eq!(first.providers, 1);
+        assert_eq!(first.written, vec!["providers/claude/p1.json".to_string()]);
+        assert_eq!(first.removed, vec!["mcp/gone.json".to_string()]);
+
+        let content =
+            fs::read_to_string(dir.path().join("providers").join("claude").join("p1.json"))
+                .unwrap();
+        assert!(!content.contains("sk-1"));
+        assert!(content.find("\"A\"").unwrap() < content.find("\"B\"").unwrap());
+
+        let second = VcsExportService::write(&state, dir.path()).unwrap();
+        assert!(second.written.is_empty());
+        assert!(second.removed.is_empty());
+    }
+}
@@ -0,0 +1,221 @@
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+
+/// 检测时发现不可写的目录；写入这些目录前会重新检测一次
+static READ_ONLY_DIRS: Lazy<Mutex<HashSet<PathBuf>>> = Lazy::new(Default::default);
+
+/// 单个写入目标的检测结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WriteTarget {
+    pub path: String,
+    pub exists: bool,
+    pub writable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 某个应用的配置目录是否可写
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppWriteCapability {
+    pub app: String,
+    /// 全部目标可写时为 true；否则切换供应商、同步 MCP 等操作会被拒绝
+    pub writable: bool,
+    pub targets: Vec<WriteTarget>,
+    /// 不可写时的处理建议
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hints: Vec<String>,
+}
+
+/// 检测各应用配置目录的写权限，避免在企业锁定环境中写到一半才失败
+pub struct WriteAccessService;
+
+impl WriteAccessService {
+    /// 检测全部应用并刷新只读目录缓存
+    pub fn detect() -> Vec<AppWriteCapability> {
+        let capabilities: Vec<_> = [AppType::Claude, AppType::Codex, AppType::Gemini]
+            .iter()
+            .map(Self::detect_app)
+            .collect();
+
+        let mut read_only = READ_ONLY_DIRS.lock().unwrap_or_else(|e| e.into_inner());
+        read_only.clear();
+        for capability in &capabilities {
+            for target in capability.targets.iter().filter(|t| !t.writable) {
+                log::warn!("{} 配置目录不可写: {}", capability.app, target.path);
+                read_only.insert(PathBuf::from(&target.path));
+            }
+        }
+        capabilities
+    }
+
+    pub fn detect_app(app: &AppType) -> AppWriteCapability {
+        let targets: Vec<WriteTarget> = target_dirs(app)
+            .iter()
+            .map(PathBuf::as_path)
+            .map(probe)
+            .collect();
+        let writable = targets.iter().all(|t| t.writable);
+        let hints = if writable {
+            Vec::new()
+        } else {
+            hints(app, &targets)
+        };
+        AppWriteCapability {
+            app: app.as_str().to_string(),
+            writable,
+            targets,
+            hints,
+        }
+    }
+
+    /// 写入 live 配置前调用：目标目录不可写时直接返回 READ_ONLY_TARGET 错误，
+    /// 不会先写入一部分文件
+    pub fn ensure_app_writable(app: &AppType) -> Result<(), AppError> {
+        if !Self::flagged(app) {
+            return Ok(());
+        }
+        let capability = Self::detect_app(app);
+        Self::update_cache(&capability);
+        if capability.writable {
+            return Ok(());
+        }
+        Err(read_only_error(&capability))
+    }
+
+    /// 单个文件写入前的检查；只针对已标记为只读的应用目录，其余路径照常写入
+    pub fn ensure_path_writable(path: &Path) -> Result<(), AppError> {
+        if READ_ONLY_DIRS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_empty()
+        {
+            return Ok(());
+        }
+        let app = if path == crate::config::get_claude_mcp_path() {
+            Some(AppType::Claude)
+        } else {
+            [AppType::Claude, AppType::Codex, AppType::Gemini]
+                .into_iter()
+                .find(|app| path.starts_with(&target_dirs(app)[0]))
+        };
+        match app {
+            Some(app) => Self::ensure_app_writable(&app),
+            None => Ok(()),
+        }
+    }
+
+    fn flagged(app: &AppType) -> bool {
+        let read_only = READ_ONLY_DIRS.lock().unwrap_or_else(|e| e.into_inner());
+        target_dirs(app).iter().any(|dir| read_only.contains(dir))
+    }
+
+    fn update_cache(capability: &AppWriteCapability) {
+        let mut read_only = READ_ONLY_DIRS.lock().unwrap_or_else(|e| e.into_inner());
+        for target in &capability.targets {
+            let path = PathBuf::from(&target.path);
+            if target.writable {
+                read_only.remove(&path);
+            } else {
+                read_only.insert(path);
+            }
+        }
+    }
+}
+
+/// 应用会写入的目录，第一项为配置目录；Claude 的 MCP 配置 `~/.claude.json` 位于配置目录之外
+fn target_dirs(app: &AppType) -> Vec<PathBuf> {
+    match app {
+        AppType::Claude => {
+            let mut dirs = vec![crate::config::get_claude_config_dir()];
+            if let Some(parent) = crate::config::get_claude_mcp_path().parent() {
+                if !dirs.iter().any(|d| d == parent) {
+                    dirs.push(parent.to_path_buf());
+                }
+            }
+            dirs
+        }
+        AppType::Codex => vec![crate::codex_config::get_codex_config_dir()],
+        AppType::Gemini => vec![crate::gemini_config::get_gemini_dir()],
+    }
+}
+
+/// 在目录（尚不存在时为最近的已存在上级）中创建并删除一个临时文件，不会创建目录本身
+fn probe(dir: &Path) -> WriteTarget {
+    let exists = dir.is_dir();
+    let base = dir.ancestors().find(|d| d.is_dir()).unwrap_or(dir);
+    let test_file = base.join(format!(".cli-hub-write-test-{}", std::process::id()));
+    let result = std::fs::write(&test_file, b"").and_then(|_| std::fs::remove_file(&test_file));
+    WriteTarget {
+        path: dir.to_string_lossy().to_string(),
+        exists,
+        writable: result.is_ok(),
+        error: result.err().map(|e| e.to_string()),
+    }
+}
+
+fn hints(app: &AppType, targets: &[WriteTarget]) -> Vec<String> {
+    let english = crate::settings::get_effective_settings()
+        .language
+        .as_deref()
+        == Some("en");
+    let mut hints: Vec<String> = targets
+        .iter()
+        .filter(|t| !t.writable)
+        .map(|t| match (cfg!(unix), english) {
+            (true, true) => format!("Grant write access: chmod -R u+w \"{}\"", t.path),
+            (true, false) => format!("授予写权限：chmod -R u+w \"{}\"", t.path),
+            (false, true) => format!(
+                "Clear the read-only attribute on \"{}\" or ask your administrator for write access",
+                t.path
+            ),
+            (false, false) => format!("取消 \"{}\" 的只读属性，或联系管理员开放写权限", t.path),
+        })
+        .collect();
+    hints.push(if english {
+        format!(
+            "Or point the {} config directory to a writable location in Settings",
+            app.as_str()
+        )
+    } else {
+        format!("或在设置中把 {} 配置目录改到可写的位置", app.as_str())
+    });
+    hints
+}
+
+fn read_only_error(capability: &AppWriteCapability) -> AppError {
+    let paths: Vec<&str> = capability
+        .targets
+        .iter()
+        .filter(|t| !t.writable)
+        .map(|t| t.path.as_str())
+        .collect();
+    AppError::read_only_target(&capability.app, &paths, &capability.hints)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn probe_checks_missing_dirs_through_their_nearest_parent() {
+        let root = tempfile::tempdir().unwrap();
+        let missing = root.path().join("nested").join(".claude");
+        let target = probe(&missing);
+        assert!(!target.exists);
+        assert!(target.writable);
+        assert!(target.error.is_none());
+        assert!(
+            !root.path().join("nested").exists(),
+            "probing must not create directories"
+        );
+        assert_eq!(std::fs::read_dir(root.path()).unwrap().count(), 0);
+    }
+}
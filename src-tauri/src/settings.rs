@@ -1,9 +1,10 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, OnceLock, RwLock};
 
+use crate::app_config::AppType;
 use crate::database::Database;
 use crate::error::AppError;
 
@@ -29,6 +30,252 @@ pub struct SecurityAuthSettings {
 pub struct SecuritySettings {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub auth: Option<SecurityAuthSettings>,
+    /// 深链接共享密钥，设置后 clihub:// 链接必须携带以该密钥计算的 HMAC-SHA256 签名（sig 参数）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deeplink_secret: Option<String>,
+}
+
+/// 定期导出到外部目录（如 Dropbox/OneDrive/iCloud 同步文件夹）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalBackupSettings {
+    /// 备份目录，未设置时不执行定期导出
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dir: Option<String>,
+    /// 导出间隔（小时）
+    #[serde(default = "default_external_backup_interval_hours")]
+    pub interval_hours: u32,
+    /// 保留的备份文件数量
+    #[serde(default = "default_external_backup_retain")]
+    pub retain: usize,
+}
+
+fn default_external_backup_interval_hours() -> u32 {
+    24
+}
+
+fn default_external_backup_retain() -> usize {
+    7
+}
+
+impl Default for ExternalBackupSettings {
+    fn default() -> Self {
+        Self {
+            dir: None,
+            interval_hours: default_external_backup_interval_hours(),
+            retain: default_external_backup_retain(),
+        }
+    }
+}
+
+/// 切换供应商时的凭证探测策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum CredentialProbeMode {
+    /// 不探测（默认）
+    #[default]
+    Off,
+    /// 探测失败时标记供应商并提示，仍然完成切换
+    Warn,
+    /// 探测到 401/403 时中止切换
+    Abort,
+}
+
+impl CredentialProbeMode {
+    fn is_off(&self) -> bool {
+        *self == Self::Off
+    }
+}
+
+/// CLI Hub 写入 Claude 配置的层级，优先级从低到高依次为 User、Local、Managed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum ClaudeSettingsLayer {
+    /// ~/.claude/settings.json（默认）
+    #[default]
+    User,
+    /// ~/.claude/settings.local.json，覆盖 settings.json 中的同名键
+    Local,
+    /// 系统级托管策略（managed-settings.json），只读，不能作为写入层级
+    Managed,
+}
+
+impl ClaudeSettingsLayer {
+    fn is_user(&self) -> bool {
+        *self == Self::User
+    }
+}
+
+/// 供应商列表排序方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum ProviderSortMode {
+    /// 按手动拖拽的 sortIndex 排序（默认）
+    #[default]
+    Manual,
+    /// 最近切换过的在前
+    RecentlyUsed,
+    /// 最近一次测速延迟低的在前
+    Latency,
+    /// 按名称字母顺序
+    Alphabetical,
+    /// 按分类分组
+    Category,
+}
+
+/// 各应用的供应商排序方式
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderSortModes {
+    #[serde(default)]
+    pub claude: ProviderSortMode,
+    #[serde(default)]
+    pub codex: ProviderSortMode,
+    #[serde(default)]
+    pub gemini: ProviderSortMode,
+}
+
+impl ProviderSortModes {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn for_app(&self, app_type: &AppType) -> ProviderSortMode {
+        match app_type {
+            AppType::Claude => self.claude,
+            AppType::Codex => self.codex,
+            AppType::Gemini => self.gemini,
+        }
+    }
+}
+
+/// 破坏性操作的确认级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum ConfirmationLevel {
+    /// 无需确认（默认，保持旧版行为）
+    #[default]
+    None,
+    /// 需要一次确认
+    Confirm,
+    /// 需要输入资源名称
+    TypeName,
+}
+
+/// 各破坏性操作的确认策略
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfirmationPolicies {
+    #[serde(default)]
+    pub delete_provider: ConfirmationLevel,
+    #[serde(default)]
+    pub delete_mcp_server: ConfirmationLevel,
+    #[serde(default)]
+    pub import_sql: ConfirmationLevel,
+    #[serde(default)]
+    pub restore_backup: ConfirmationLevel,
+}
+
+impl ConfirmationPolicies {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// 托盘菜单的内容密度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum TrayDensity {
+    /// 列出全部供应商（默认）
+    #[default]
+    Comfortable,
+    /// 每个应用只显示当前供应商
+    Compact,
+}
+
+impl TrayDensity {
+    fn is_comfortable(&self) -> bool {
+        *self == Self::Comfortable
+    }
+}
+
+/// 托盘图标样式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum TrayIconStyle {
+    /// macOS 使用单色模板图标（由系统适配深浅色菜单栏），其他平台使用彩色应用图标
+    #[default]
+    Auto,
+    /// 始终使用彩色应用图标
+    Colored,
+    /// 单色图标；Windows/Linux 按系统主题选择深色或浅色版本
+    Monochrome,
+}
+
+impl TrayIconStyle {
+    fn is_auto(&self) -> bool {
+        *self == Self::Auto
+    }
+}
+
+/// 后台任务系统通知的分类
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NotificationCategory {
+    /// 首次启动导入现有配置
+    FirstImport,
+    /// config.json → SQLite 迁移
+    Migration,
+    /// 定期外部备份
+    Backup,
+    /// MCP 同步 / 导入失败
+    McpSync,
+    /// 可用时段结束后的自动切换
+    AccessWindow,
+    /// 端点不可用时自动改用备用端点
+    EndpointFailover,
+    /// 提示词定时方案在时段边界的切换
+    PromptSchedule,
+}
+
+/// 系统通知设置
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationSettings {
+    /// 总开关（默认关闭，仅记录日志）
+    #[serde(default)]
+    pub enabled: bool,
+    /// 单独关闭的分类
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disabled_categories: Vec<NotificationCategory>,
+}
+
+impl NotificationSettings {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn allows(&self, category: NotificationCategory) -> bool {
+        self.enabled && !self.disabled_categories.contains(&category)
+    }
+}
+
+/// 首次导入时，live 配置缺失的应用从哪些来源生成草稿供应商
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FirstImportSources {
+    /// 不读取 shell 中的 ANTHROPIC_* / OPENAI_* 环境变量
+    #[serde(default)]
+    pub skip_env: bool,
+    /// 通过 1Password CLI（op）读取的条目名称；为空时不调用 op
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub one_password_items: Vec<String>,
+}
+
+impl FirstImportSources {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 /// 应用设置结构，允许覆盖默认配置目录
@@ -61,6 +308,39 @@ pub struct AppSettings {
     /// Codex 自定义端点列表
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub custom_endpoints_codex: HashMap<String, CustomEndpoint>,
+    /// 定期导出到外部目录
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_backup: Option<ExternalBackupSettings>,
+    /// 破坏性操作的确认策略
+    #[serde(default, skip_serializing_if = "ConfirmationPolicies::is_default")]
+    pub confirmation_policies: ConfirmationPolicies,
+    /// 切换供应商前是否探测 API Key 有效性
+    #[serde(default, skip_serializing_if = "CredentialProbeMode::is_off")]
+    pub credential_probe: CredentialProbeMode,
+    /// 供应商列表排序方式（按应用）
+    #[serde(default, skip_serializing_if = "ProviderSortModes::is_default")]
+    pub provider_sort: ProviderSortModes,
+    /// 后台任务结果的系统通知
+    #[serde(default, skip_serializing_if = "NotificationSettings::is_default")]
+    pub notifications: NotificationSettings,
+    /// Claude 配置写入的层级
+    #[serde(default, skip_serializing_if = "ClaudeSettingsLayer::is_user")]
+    pub claude_settings_layer: ClaudeSettingsLayer,
+    /// 当前端点持续不可用时，自动改写为探测结果最好的备用端点
+    #[serde(default)]
+    pub endpoint_failover: bool,
+    /// 托盘菜单的内容密度
+    #[serde(default, skip_serializing_if = "TrayDensity::is_comfortable")]
+    pub tray_density: TrayDensity,
+    /// 托盘图标样式
+    #[serde(default, skip_serializing_if = "TrayIconStyle::is_auto")]
+    pub tray_icon_style: TrayIconStyle,
+    /// 技能下载使用的 GitHub Token，可提高 API 配额并访问私有仓库
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub github_token: Option<String>,
+    /// 首次导入的额外来源
+    #[serde(default, skip_serializing_if = "FirstImportSources::is_default")]
+    pub first_import_sources: FirstImportSources,
 }
 
 fn default_show_in_tray() -> bool {
@@ -85,6 +365,17 @@ impl Default for AppSettings {
             security: None,
             custom_endpoints_claude: HashMap::new(),
             custom_endpoints_codex: HashMap::new(),
+            external_backup: None,
+            confirmation_policies: ConfirmationPolicies::default(),
+            credential_probe: CredentialProbeMode::default(),
+            provider_sort: ProviderSortModes::default(),
+            notifications: NotificationSettings::default(),
+            claude_settings_layer: ClaudeSettingsLayer::default(),
+            endpoint_failover: false,
+            tray_density: TrayDensity::default(),
+            tray_icon_style: TrayIconStyle::default(),
+            github_token: None,
+            first_import_sources: FirstImportSources::default(),
         }
     }
 }
@@ -120,12 +411,40 @@ impl AppSettings {
             .filter(|s| !s.is_empty())
             .map(|s| s.to_string());
 
+        if let Some(backup) = self.external_backup.as_mut() {
+            backup.dir = backup
+                .dir
+                .as_ref()
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string());
+        }
+
+        // 托管策略由管理员维护，CLI Hub 不写入
+        if self.claude_settings_layer == ClaudeSettingsLayer::Managed {
+            self.claude_settings_layer = ClaudeSettingsLayer::User;
+        }
+
         self.language = self
             .language
             .as_ref()
             .map(|s| s.trim())
             .filter(|s| matches!(*s, "en" | "zh"))
             .map(|s| s.to_string());
+
+        self.github_token = self
+            .github_token
+            .as_ref()
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string());
+
+        self.first_import_sources.one_password_items =
+            std::mem::take(&mut self.first_import_sources.one_password_items)
+                .into_iter()
+                .map(|item| item.trim().to_string())
+                .filter(|item| !item.is_empty())
+                .collect();
     }
 
     fn load_from_file() -> Self {
@@ -151,6 +470,104 @@ impl AppSettings {
     }
 }
 
+/// 设置导出文件的格式标识
+const SETTINGS_EXPORT_FORMAT: &str = "cli-hub-settings";
+const SETTINGS_EXPORT_VERSION: u32 = 1;
+
+/// 可在机器间迁移的偏好设置（不含供应商 / MCP 等数据）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppSettingsExport {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: i64,
+    pub settings: AppSettings,
+}
+
+impl AppSettings {
+    /// 去掉本机相关字段：配置目录、备份目录、开机自启、深链接密钥、GitHub Token 与旧版端点数据
+    fn portable(&self) -> Self {
+        let mut portable = self.clone();
+        portable.claude_config_dir = None;
+        portable.codex_config_dir = None;
+        portable.gemini_config_dir = None;
+        portable.launch_on_startup = false;
+        portable.custom_endpoints_claude.clear();
+        portable.custom_endpoints_codex.clear();
+        if let Some(backup) = portable.external_backup.as_mut() {
+            backup.dir = None;
+        }
+        if let Some(security) = portable.security.as_mut() {
+            security.deeplink_secret = None;
+        }
+        portable.github_token = None;
+        portable
+    }
+
+    /// 以导入的偏好为准，保留本机相关字段
+    fn with_local_fields(mut self, local: &AppSettings) -> Self {
+        self.claude_config_dir = local.claude_config_dir.clone();
+        self.codex_config_dir = local.codex_config_dir.clone();
+        self.gemini_config_dir = local.gemini_config_dir.clone();
+        self.launch_on_startup = local.launch_on_startup;
+        self.github_token = local.github_token.clone();
+        self.custom_endpoints_claude = local.custom_endpoints_claude.clone();
+        self.custom_endpoints_codex = local.custom_endpoints_codex.clone();
+        let local_backup_dir = local.external_backup.as_ref().and_then(|b| b.dir.clone());
+        match self.external_backup.as_mut() {
+            Some(backup) => backup.dir = local_backup_dir,
+            None if local_backup_dir.is_some() => {
+                self.external_backup = local.external_backup.clone()
+            }
+            None => {}
+        }
+        let local_secret = local
+            .security
+            .as_ref()
+            .and_then(|s| s.deeplink_secret.clone());
+        if local_secret.is_some() {
+            self.security
+                .get_or_insert_with(Default::default)
+                .deeplink_secret = local_secret;
+        }
+        self
+    }
+}
+
+/// 导出当前偏好设置到 JSON 文件
+pub fn export_app_settings(path: &Path) -> Result<(), AppError> {
+    let export = AppSettingsExport {
+        format: SETTINGS_EXPORT_FORMAT.to_string(),
+        version: SETTINGS_EXPORT_VERSION,
+        exported_at: chrono::Utc::now().timestamp_millis(),
+        settings: get_settings().portable(),
+    };
+    crate::config::write_json_file(path, &export)
+}
+
+/// 从 JSON 文件导入偏好设置，本机相关字段保持不变；返回生效后的设置
+pub fn import_app_settings(path: &Path) -> Result<AppSettings, AppError> {
+    let export: AppSettingsExport = crate::config::read_json_file(path)?;
+    if export.format != SETTINGS_EXPORT_FORMAT {
+        return Err(AppError::localized(
+            "settings.import.invalid_format",
+            "不是 CLI Hub 设置导出文件",
+            "Not a CLI Hub settings export file",
+        ));
+    }
+    if export.version > SETTINGS_EXPORT_VERSION {
+        return Err(AppError::localized(
+            "settings.import.too_new",
+            "设置文件来自更新版本的 CLI Hub，请先升级",
+            "The settings file comes from a newer CLI Hub; please upgrade first",
+        ));
+    }
+
+    let merged = export.settings.with_local_fields(&get_settings());
+    update_settings(merged.clone())?;
+    Ok(merged)
+}
+
 fn save_settings_file(settings: &AppSettings) -> Result<(), AppError> {
     let mut normalized = settings.clone();
     normalized.normalize_paths();
@@ -166,6 +583,138 @@ fn save_settings_file(settings: &AppSettings) -> Result<(), AppError> {
     Ok(())
 }
 
+/// 按配置档（profile）覆盖的设置项，未设置的字段沿用全局设置
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsOverrides {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minimize_to_tray_on_close: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tray_density: Option<TrayDensity>,
+}
+
+impl SettingsOverrides {
+    fn normalize(&mut self) {
+        self.language = self
+            .language
+            .as_ref()
+            .map(|s| s.trim())
+            .filter(|s| matches!(*s, "en" | "zh"))
+            .map(|s| s.to_string());
+    }
+
+    /// 解析顺序：配置档 → 全局
+    fn apply(&self, settings: &mut AppSettings) {
+        if let Some(value) = self.minimize_to_tray_on_close {
+            settings.minimize_to_tray_on_close = value;
+        }
+        if let Some(language) = &self.language {
+            settings.language = Some(language.clone());
+        }
+        if let Some(density) = self.tray_density {
+            settings.tray_density = density;
+        }
+    }
+}
+
+/// 当前配置档及其覆盖项
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsProfile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    pub overrides: SettingsOverrides,
+}
+
+/// 指定配置档的环境变量；未设置时演示模式使用 demo 配置档
+const PROFILE_ENV: &str = "CLI_HUB_PROFILE";
+const DEMO_PROFILE: &str = "demo";
+
+/// 当前生效的配置档名称；日常使用（无配置档）时返回 None
+pub fn active_profile() -> Option<String> {
+    std::env::var(PROFILE_ENV)
+        .ok()
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .or_else(|| crate::services::is_demo_mode().then(|| DEMO_PROFILE.to_string()))
+}
+
+fn profile_settings_key(profile: &str) -> String {
+    format!("{APP_SETTINGS_KEY}@{profile}")
+}
+
+/// 是否为某个配置档案的覆盖项键（`app_settings@<profile>`），供清理孤立设置时保留
+pub(crate) fn is_profile_settings_key(key: &str) -> bool {
+    key.strip_prefix(APP_SETTINGS_KEY)
+        .and_then(|rest| rest.strip_prefix('@'))
+        .is_some_and(|profile| !profile.is_empty())
+}
+
+static PROFILE_OVERRIDES: OnceLock<RwLock<SettingsOverrides>> = OnceLock::new();
+
+fn profile_overrides_store() -> &'static RwLock<SettingsOverrides> {
+    PROFILE_OVERRIDES.get_or_init(|| RwLock::new(load_active_overrides()))
+}
+
+fn load_active_overrides() -> SettingsOverrides {
+    match (active_profile(), SETTINGS_DB.get()) {
+        (Some(profile), Some(db)) => load_overrides(db, &profile).unwrap_or_else(|e| {
+            log::warn!("读取配置档 {profile} 的设置覆盖失败: {e}");
+            SettingsOverrides::default()
+        }),
+        _ => SettingsOverrides::default(),
+    }
+}
+
+fn load_overrides(db: &Database, profile: &str) -> Result<SettingsOverrides, AppError> {
+    let Some(raw) = db.get_setting(&profile_settings_key(profile))? else {
+        return Ok(SettingsOverrides::default());
+    };
+    let mut overrides: SettingsOverrides = serde_json::from_str(&raw)
+        .map_err(|e| AppError::Message(format!("解析配置档 {profile} 的设置失败: {e}")))?;
+    overrides.normalize();
+    Ok(overrides)
+}
+
+/// 读取配置档的覆盖项；profile 为空时使用当前配置档
+pub fn get_settings_profile(profile: Option<&str>) -> Result<SettingsProfile, AppError> {
+    let profile = profile.map(str::to_string).or_else(active_profile);
+    let overrides = match (&profile, SETTINGS_DB.get()) {
+        (Some(name), Some(db)) => load_overrides(db, name)?,
+        _ => SettingsOverrides::default(),
+    };
+    Ok(SettingsProfile { profile, overrides })
+}
+
+/// 保存配置档的覆盖项；保存的是当前配置档时立即生效
+pub fn update_profile_overrides(
+    profile: &str,
+    mut overrides: SettingsOverrides,
+) -> Result<(), AppError> {
+    let profile = profile.trim();
+    if profile.is_empty() {
+        return Err(AppError::localized(
+            "settings.profile.empty",
+            "配置档名称不能为空",
+            "Profile name must not be empty",
+        ));
+    }
+    let Some(db) = SETTINGS_DB.get() else {
+        return Err(AppError::Message("数据库尚未初始化".to_string()));
+    };
+    overrides.normalize();
+    let json =
+        serde_json::to_string(&overrides).map_err(|e| AppError::JsonSerialize { source: e })?;
+    db.set_setting(&profile_settings_key(profile), &json)?;
+
+    if active_profile().as_deref() == Some(profile) {
+        *profile_overrides_store().write().expect("写入设置锁失败") = overrides;
+    }
+    Ok(())
+}
+
 static SETTINGS_STORE: OnceLock<RwLock<AppSettings>> = OnceLock::new();
 
 fn settings_store() -> &'static RwLock<AppSettings> {
@@ -173,7 +722,7 @@ fn settings_store() -> &'static RwLock<AppSettings> {
 }
 
 static SETTINGS_DB: OnceLock<Arc<Database>> = OnceLock::new();
-const APP_SETTINGS_KEY: &str = "app_settings";
+pub(crate) const APP_SETTINGS_KEY: &str = "app_settings";
 
 pub fn bind_db(db: Arc<Database>) {
     if SETTINGS_DB.set(db).is_err() {
@@ -184,6 +733,9 @@ pub fn bind_db(db: Arc<Database>) {
         let mut guard = store.write().expect("写入设置锁失败");
         *guard = load_initial_settings();
     }
+    if let Some(store) = PROFILE_OVERRIDES.get() {
+        *store.write().expect("写入设置锁失败") = load_active_overrides();
+    }
 }
 
 fn load_initial_settings() -> AppSettings {
@@ -243,10 +795,21 @@ fn resolve_override_path(raw: &str) -> PathBuf {
     PathBuf::from(raw)
 }
 
+/// 全局设置（不含配置档覆盖），用于设置页编辑与持久化
 pub fn get_settings() -> AppSettings {
     settings_store().read().expect("读取设置锁失败").clone()
 }
 
+/// 叠加当前配置档覆盖后的设置，运行时读取语言、托盘行为等应使用此函数
+pub fn get_effective_settings() -> AppSettings {
+    let mut settings = get_settings();
+    profile_overrides_store()
+        .read()
+        .expect("读取设置锁失败")
+        .apply(&mut settings);
+    settings
+}
+
 pub fn update_settings(mut new_settings: AppSettings) -> Result<(), AppError> {
     new_settings.normalize_paths();
     if let Some(db) = SETTINGS_DB.get() {
@@ -266,6 +829,7 @@ pub fn reload_settings() -> Result<(), AppError> {
     let fresh_settings = load_initial_settings();
     let mut guard = settings_store().write().expect("写入设置锁失败");
     *guard = fresh_settings;
+    *profile_overrides_store().write().expect("写入设置锁失败") = load_active_overrides();
     Ok(())
 }
 
@@ -298,6 +862,14 @@ pub fn get_claude_override_dir() -> Option<PathBuf> {
         .map(|p| resolve_override_path(p))
 }
 
+/// CLI Hub 当前写入 Claude 配置的层级
+pub fn get_claude_settings_layer() -> ClaudeSettingsLayer {
+    settings_store()
+        .read()
+        .map(|s| s.claude_settings_layer)
+        .unwrap_or_default()
+}
+
 pub fn get_codex_override_dir() -> Option<PathBuf> {
     let settings = settings_store().read().ok()?;
     settings
@@ -313,3 +885,98 @@ pub fn get_gemini_override_dir() -> Option<PathBuf> {
         .as_ref()
         .map(|p| resolve_override_path(p))
 }
+
+pub fn get_provider_sort_mode(app_type: &AppType) -> ProviderSortMode {
+    settings_store()
+        .read()
+        .map(|settings| settings.provider_sort.for_app(app_type))
+        .unwrap_or_default()
+}
+
+/// 指定分类的后台结果是否需要发送系统通知
+pub fn notification_allowed(category: NotificationCategory) -> bool {
+    settings_store()
+        .read()
+        .map(|settings| settings.notifications.allows(category))
+        .unwrap_or(false)
+}
+
+/// 访问 GitHub 使用的 Token；未配置时匿名访问
+pub fn get_github_token() -> Option<String> {
+    settings_store().read().ok()?.github_token.clone()
+}
+
+/// 外部备份设置；未配置目录时返回 None
+pub fn get_external_backup_settings() -> Option<(PathBuf, ExternalBackupSettings)> {
+    let settings = settings_store().read().ok()?;
+    let backup = settings.external_backup.clone()?;
+    let dir = resolve_override_path(backup.dir.as_deref()?);
+    Some((dir, backup))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn portable_settings_keep_local_paths_on_import() {
+        let source = AppSettings {
+            language: Some("zh".to_string()),
+            claude_config_dir: Some("/remote/.claude".to_string()),
+            external_backup: Some(ExternalBackupSettings {
+                dir: Some("/remote/backups".to_string()),
+                interval_hours: 6,
+                retain: 3,
+            }),
+            ..Default::default()
+        };
+        let exported = source.portable();
+        assert!(exported.claude_config_dir.is_none());
+        assert!(exported.external_backup.as_ref().unwrap().dir.is_none());
+
+        let local = AppSettings {
+            claude_config_dir: Some("/local/.claude".to_string()),
+            external_backup: Some(ExternalBackupSettings {
+                dir: Some("/local/backups".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let merged = exported.with_local_fields(&local);
+        assert_eq!(merged.language.as_deref(), Some("zh"));
+        assert_eq!(merged.claude_config_dir.as_deref(), Some("/local/.claude"));
+        let backup = merged.external_backup.unwrap();
+        assert_eq!(backup.dir.as_deref(), Some("/local/backups"));
+        assert_eq!(backup.interval_hours, 6);
+    }
+
+    #[test]
+    fn profile_overrides_win_over_global_settings() {
+        let mut settings = AppSettings {
+            language: Some("zh".to_string()),
+            ..Default::default()
+        };
+        let mut overrides = SettingsOverrides {
+            minimize_to_tray_on_close: Some(false),
+            language: Some(" fr ".to_string()),
+            tray_density: Some(TrayDensity::Compact),
+        };
+        overrides.normalize();
+        assert_eq!(overrides.language, None);
+
+        overrides.apply(&mut settings);
+        assert!(!settings.minimize_to_tray_on_close);
+        assert_eq!(settings.language.as_deref(), Some("zh"));
+        assert_eq!(settings.tray_density, TrayDensity::Compact);
+
+        let db = Database::memory().expect("create memory db");
+        let raw = r#"{"language":"en"}"#;
+        db.set_setting(&profile_settings_key("demo"), raw).unwrap();
+        let loaded = load_overrides(&db, "demo").unwrap();
+        assert_eq!(loaded.language.as_deref(), Some("en"));
+        assert_eq!(
+            load_overrides(&db, "work").unwrap(),
+            SettingsOverrides::default()
+        );
+    }
+}
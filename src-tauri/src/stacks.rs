@@ -0,0 +1,175 @@
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// 工作流套件：一组适合某类开发场景的 MCP 服务器、提示词与技能
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StackDefinition {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub mcp_servers: Vec<StackMcpServer>,
+    /// 每个应用至多启用一个提示词，因此套件只携带一个
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<StackPrompt>,
+    pub skills: Vec<StackSkill>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StackMcpServer {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub server: Value,
+    pub homepage: &'static str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StackPrompt {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub content: &'static str,
+}
+
+/// 技能仅支持 Claude（安装到 ~/.claude/skills）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StackSkill {
+    pub directory: &'static str,
+    pub repo_owner: &'static str,
+    pub repo_name: &'static str,
+    pub repo_branch: &'static str,
+}
+
+const PYTHON_PROMPT: &str = "\
+# Python development
+
+- Target Python 3.10+ and add type hints to public functions.
+- Prefer the standard library; ask before adding new dependencies.
+- Use `pytest` for tests and keep them next to the code they cover.
+- Format with `ruff format` and fix `ruff check` findings before finishing.
+";
+
+const WEB_PROMPT: &str = "\
+# Web development
+
+- Use TypeScript with strict mode; avoid `any`.
+- Keep components small and colocate styles and tests with them.
+- Check accessibility (labels, roles, keyboard navigation) for every UI change.
+- Verify changes in a real browser before reporting them as done.
+";
+
+fn stdio(command: &str, args: &[&str]) -> Value {
+    json!({ "type": "stdio", "command": command, "args": args })
+}
+
+/// 内置套件列表
+pub static BUILTIN_STACKS: Lazy<Vec<StackDefinition>> = Lazy::new(|| {
+    vec![
+        StackDefinition {
+            id: "python-dev",
+            name: "Python dev",
+            description: "Web fetch, git history and persistent memory for Python projects",
+            mcp_servers: vec![
+                StackMcpServer {
+                    id: "fetch",
+                    name: "mcp-server-fetch",
+                    server: stdio("uvx", &["mcp-server-fetch"]),
+                    homepage: "https://github.com/modelcontextprotocol/servers",
+                },
+                StackMcpServer {
+                    id: "git",
+                    name: "mcp-server-git",
+                    server: stdio("uvx", &["mcp-server-git"]),
+                    homepage: "https://github.com/modelcontextprotocol/servers",
+                },
+                StackMcpServer {
+                    id: "memory",
+                    name: "@modelcontextprotocol/server-memory",
+                    server: stdio("npx", &["-y", "@modelcontextprotocol/server-memory"]),
+                    homepage: "https://github.com/modelcontextprotocol/servers",
+                },
+            ],
+            prompt: Some(StackPrompt {
+                id: "stack-python-dev",
+                name: "Python dev",
+                content: PYTHON_PROMPT,
+            }),
+            skills: vec![StackSkill {
+                directory: "mcp-builder",
+                repo_owner: "anthropics",
+                repo_name: "skills",
+                repo_branch: "main",
+            }],
+        },
+        StackDefinition {
+            id: "web-dev",
+            name: "Web dev",
+            description:
+                "Up-to-date library docs, browser automation and web fetch for frontend work",
+            mcp_servers: vec![
+                StackMcpServer {
+                    id: "context7",
+                    name: "@upstash/context7-mcp",
+                    server: stdio("npx", &["-y", "@upstash/context7-mcp"]),
+                    homepage: "https://context7.com",
+                },
+                StackMcpServer {
+                    id: "playwright",
+                    name: "@playwright/mcp",
+                    server: stdio("npx", &["-y", "@playwright/mcp@latest"]),
+                    homepage: "https://github.com/microsoft/playwright-mcp",
+                },
+                StackMcpServer {
+                    id: "fetch",
+                    name: "mcp-server-fetch",
+                    server: stdio("uvx", &["mcp-server-fetch"]),
+                    homepage: "https://github.com/modelcontextprotocol/servers",
+                },
+            ],
+            prompt: Some(StackPrompt {
+                id: "stack-web-dev",
+                name: "Web dev",
+                content: WEB_PROMPT,
+            }),
+            skills: vec![StackSkill {
+                directory: "webapp-testing",
+                repo_owner: "anthropics",
+                repo_name: "skills",
+                repo_branch: "main",
+            }],
+        },
+        StackDefinition {
+            id: "research",
+            name: "Research",
+            description: "Structured reasoning, web fetch and documentation lookup",
+            mcp_servers: vec![
+                StackMcpServer {
+                    id: "sequential-thinking",
+                    name: "@modelcontextprotocol/server-sequential-thinking",
+                    server: stdio(
+                        "npx",
+                        &["-y", "@modelcontextprotocol/server-sequential-thinking"],
+                    ),
+                    homepage: "https://github.com/modelcontextprotocol/servers",
+                },
+                StackMcpServer {
+                    id: "fetch",
+                    name: "mcp-server-fetch",
+                    server: stdio("uvx", &["mcp-server-fetch"]),
+                    homepage: "https://github.com/modelcontextprotocol/servers",
+                },
+                StackMcpServer {
+                    id: "context7",
+                    name: "@upstash/context7-mcp",
+                    server: stdio("npx", &["-y", "@upstash/context7-mcp"]),
+                    homepage: "https://context7.com",
+                },
+            ],
+            prompt: None,
+            skills: Vec::new(),
+        },
+    ]
+});
@@ -0,0 +1,317 @@
+use serde::Serialize;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::watch;
+
+use crate::app_config::AppType;
+use crate::commands::skill::SkillServiceState;
+use crate::database::Database;
+use crate::notifications::{notify, NotificationCategory, NotificationText};
+use crate::services::mcp::McpService;
+use crate::services::prompt::PromptService;
+use crate::services::provider::ProviderService;
+use crate::services::{ConfigRepairService, ProviderDraftService, ProviderUpgradeService};
+use crate::store::AppState;
+
+/// 启动进度事件名
+pub const STARTUP_PROGRESS_EVENT: &str = "startup-progress";
+/// 升级后的配置迁移改写了供应商时发送，载荷为迁移报告
+pub const PROVIDER_UPGRADE_EVENT: &str = "provider-settings-upgraded";
+/// 供应商数据完整性检查发现问题时发送
+pub const INIT_WARNINGS_EVENT: &str = "init-warnings";
+
+/// 后台初始化阶段
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum StartupStage {
+    /// 数据库为空时从现有配置文件导入
+    FirstImport,
+    /// 应用升级后把已保存的供应商配置迁移到当前格式
+    ProviderUpgrade,
+    /// 检查数据库中的供应商数据，修复当前供应商标记
+    IntegrityCheck,
+    /// 检查并修复损坏的 live 配置
+    ConfigRepair,
+    SkillService,
+    Ready,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupProgress {
+    pub stage: StartupStage,
+    /// 该阶段是否已结束
+    pub done: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// 启动就绪状态：窗口先显示，依赖完整数据的命令可等待后台初始化结束
+pub struct StartupState {
+    ready: watch::Sender<bool>,
+}
+
+impl Default for StartupState {
+    fn default() -> Self {
+        let (ready, _) = watch::channel(false);
+        Self { ready }
+    }
+}
+
+impl StartupState {
+    pub fn is_ready(&self) -> bool {
+        *self.ready.borrow()
+    }
+
+    pub async fn wait_ready(&self) {
+        let mut rx = self.ready.subscribe();
+        // 发送端与状态同生命周期，不会提前关闭
+        let _ = rx.wait_for(|ready| *ready).await;
+    }
+
+    fn mark_ready(&self) {
+        self.ready.send_replace(true);
+    }
+}
+
+/// 在后台执行首次导入、配置修复与技能服务初始化，并逐阶段发送进度事件
+pub fn spawn_background_init(app: AppHandle, db: Arc<Database>) {
+    tauri::async_runtime::spawn(async move {
+        let state = AppState::new(db);
+        let blocking_app = app.clone();
+        let result = tauri::async_runtime::spawn_blocking(move || {
+            run_blocking_stages(&blocking_app, &state);
+        })
+        .await;
+        if let Err(e) = result {
+            log::error!("后台初始化任务异常退出: {e}");
+        }
+
+        emit(&app, StartupStage::SkillService, false, None);
+        if let Some(skills) = app.try_state::<SkillServiceState>() {
+            if let Err(e) = skills.get().await {
+                log::warn!("{e}");
+            }
+        }
+        emit(&app, StartupStage::SkillService, true, None);
+
+        if let Some(startup) = app.try_state::<StartupState>() {
+            startup.mark_ready();
+        }
+        emit(&app, StartupStage::Ready, true, None);
+
+        // 首次导入可能新增了供应商，刷新托盘
+        crate::tray::schedule_tray_rebuild(&app);
+    });
+}
+
+fn run_blocking_stages(app: &AppHandle, state: &AppState) {
+    // 安全模式下数据保持原样，首次导入、完整性自动修复与 live 配置修复都留给用户手动处理
+    if crate::services::is_safe_mode() {
+        log::info!("安全模式：跳过首次导入、配置迁移、完整性检查与 live 配置修复");
+        return;
+    }
+
+    // 检查是否需要首次导入（数据库为空）
+    let need_first_import = state.db.is_empty_for_first_import().unwrap_or_else(|e| {
+        log::warn!("Failed to check if database is empty: {e}");
+        false
+    });
+    if need_first_import {
+        emit(app, StartupStage::FirstImport, false, None);
+        let summary = first_import(state);
+        emit(app, StartupStage::FirstImport, true, Some(summary));
+    }
+
+    emit(app, StartupStage::ProviderUpgrade, false, None);
+    match ProviderUpgradeService::run(state) {
+        Ok(Some(report)) if !report.rewritten.is_empty() => {
+            if let Err(e) = app.emit(PROVIDER_UPGRADE_EVENT, &report) {
+                log::debug!("发送供应商配置迁移事件失败: {e}");
+            }
+        }
+        Ok(_) => {}
+        Err(e) => log::warn!("迁移供应商配置失败: {e}"),
+    }
+    emit(app, StartupStage::ProviderUpgrade, true, None);
+
+    // 须在 live 配置修复之前完成：修复依赖唯一的当前供应商
+    emit(app, StartupStage::IntegrityCheck, false, None);
+    match state.db.check_provider_integrity() {
+        Ok(issues) => {
+            if !issues.is_empty() {
+                if let Err(e) = app.emit(INIT_WARNINGS_EVENT, &issues) {
+                    log::debug!("发送启动检查警告事件失败: {e}");
+                }
+            }
+            crate::init_status::set_init_warnings(issues);
+        }
+        Err(e) => log::warn!("供应商数据完整性检查失败: {e}"),
+    }
+    emit(app, StartupStage::IntegrityCheck, true, None);
+
+    // 检查 live 配置是否损坏（如崩溃后写入中断），损坏时备份并从数据库重建
+    emit(app, StartupStage::ConfigRepair, false, None);
+    for report in ConfigRepairService::startup_check(state) {
+        if let Err(e) = app.emit("live-config-repaired", &report) {
+            log::debug!("发送 live 配置修复事件失败: {e}");
+        }
+    }
+    emit(app, StartupStage::ConfigRepair, true, None);
+}
+
+fn emit(app: &AppHandle, stage: StartupStage, done: bool, message: Option<String>) {
+    let progress = StartupProgress {
+        stage,
+        done,
+        message,
+    };
+    if let Err(e) = app.emit(STARTUP_PROGRESS_EVENT, &progress) {
+        log::debug!("发送启动进度事件失败: {e}");
+    }
+}
+
+/// 数据库为空时，从用户现有的配置文件导入数据并初始化默认配置，返回导入摘要
+fn first_import(state: &AppState) -> String {
+    // 数据库为空，尝试从用户现有的配置文件导入数据并初始化默认配置
+    log::info!(
+        "Empty database detected, importing existing configurations and initializing defaults..."
+    );
+    let mut imported_providers = 0usize;
+    let mut draft_providers = 0usize;
+    let mut imported_mcp = 0usize;
+    let mut imported_prompts = 0usize;
+    let mut import_failures = 0usize;
+
+    // 1. 初始化默认 Skills 仓库（3个）
+    match state.db.init_default_skill_repos() {
+        Ok(count) if count > 0 => {
+            log::info!("✓ Initialized {count} default skill repositories");
+        }
+        Ok(_) => log::debug!("No default skill repositories to initialize"),
+        Err(e) => log::warn!("✗ Failed to initialize default skill repos: {e}"),
+    }
+
+    // 2. 导入供应商配置（从 live 配置文件）
+    for app in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+        match ProviderService::import_default_config(state, app.clone()) {
+            Ok(_) => {
+                imported_providers += 1;
+                log::info!("✓ Imported default provider for {}", app.as_str());
+            }
+            Err(e) => {
+                log::debug!(
+                    "○ No default provider to import for {}: {}",
+                    app.as_str(),
+                    e
+                );
+            }
+        }
+    }
+
+    // live 配置缺失的应用改从环境变量 / 1Password 生成待确认的草稿
+    let sources = crate::settings::get_settings().first_import_sources;
+    match ProviderDraftService::import_missing(state, &sources) {
+        Ok(drafts) => {
+            for draft in &drafts {
+                log::info!("✓ Created draft provider {} for {}", draft.id, draft.app);
+            }
+            draft_providers = drafts.len();
+        }
+        Err(e) => {
+            import_failures += 1;
+            log::warn!("✗ Failed to create draft providers: {e}");
+        }
+    }
+
+    // 3. 导入 MCP 服务器配置
+    match McpService::import_from_claude(state) {
+        Ok(count) if count > 0 => {
+            imported_mcp += count;
+            log::info!("✓ Imported {count} MCP server(s) from Claude");
+        }
+        Ok(_) => log::debug!("○ No Claude MCP servers found to import"),
+        Err(e) => {
+            import_failures += 1;
+            log::warn!("✗ Failed to import Claude MCP: {e}");
+        }
+    }
+
+    match McpService::import_from_codex(state) {
+        Ok(count) if count > 0 => {
+            imported_mcp += count;
+            log::info!("✓ Imported {count} MCP server(s) from Codex");
+        }
+        Ok(_) => log::debug!("○ No Codex MCP servers found to import"),
+        Err(e) => {
+            import_failures += 1;
+            log::warn!("✗ Failed to import Codex MCP: {e}");
+        }
+    }
+
+    match McpService::import_from_gemini(state) {
+        Ok(count) if count > 0 => {
+            imported_mcp += count;
+            log::info!("✓ Imported {count} MCP server(s) from Gemini");
+        }
+        Ok(_) => log::debug!("○ No Gemini MCP servers found to import"),
+        Err(e) => {
+            import_failures += 1;
+            log::warn!("✗ Failed to import Gemini MCP: {e}");
+        }
+    }
+
+    // 4. 导入提示词文件
+    match PromptService::import_from_file_on_first_launch(state, AppType::Claude) {
+        Ok(count) if count > 0 => {
+            imported_prompts += count;
+            log::info!("✓ Imported {count} prompt(s) from Claude");
+        }
+        Ok(_) => log::debug!("○ No Claude prompt file found to import"),
+        Err(e) => {
+            import_failures += 1;
+            log::warn!("✗ Failed to import Claude prompt: {e}");
+        }
+    }
+
+    match PromptService::import_from_file_on_first_launch(state, AppType::Codex) {
+        Ok(count) if count > 0 => {
+            imported_prompts += count;
+            log::info!("✓ Imported {count} prompt(s) from Codex");
+        }
+        Ok(_) => log::debug!("○ No Codex prompt file found to import"),
+        Err(e) => {
+            import_failures += 1;
+            log::warn!("✗ Failed to import Codex prompt: {e}");
+        }
+    }
+
+    match PromptService::import_from_file_on_first_launch(state, AppType::Gemini) {
+        Ok(count) if count > 0 => {
+            imported_prompts += count;
+            log::info!("✓ Imported {count} prompt(s) from Gemini");
+        }
+        Ok(_) => log::debug!("○ No Gemini prompt file found to import"),
+        Err(e) => {
+            import_failures += 1;
+            log::warn!("✗ Failed to import Gemini prompt: {e}");
+        }
+    }
+
+    notify(
+        NotificationCategory::FirstImport,
+        import_failures == 0,
+        NotificationText::new(
+            format!(
+                "导入 {imported_providers} 个供应商（另有 {draft_providers} 个待确认的草稿）、{imported_mcp} 个 MCP 服务器、{imported_prompts} 条提示词，失败 {import_failures} 项"
+            ),
+            format!(
+                "Imported {imported_providers} provider(s) ({draft_providers} draft(s) to review), {imported_mcp} MCP server(s), {imported_prompts} prompt(s); {import_failures} failed"
+            ),
+        ),
+    );
+    log::info!("First-time import completed");
+    format!(
+        "providers={imported_providers} drafts={draft_providers} mcp={imported_mcp} prompts={imported_prompts} failed={import_failures}"
+    )
+}
@@ -1,14 +1,29 @@
-use crate::database::Database;
+use crate::database::{Database, Storage};
 use std::sync::Arc;
 
 /// 全局应用状态
+///
+/// 克隆得到的是同一数据库与存储的共享句柄，后台调度器持有克隆而不是另建状态
+#[derive(Clone)]
 pub struct AppState {
+    /// SQLite 数据库，备份、完整性检查等仍需直接访问
     pub db: Arc<Database>,
+    /// 供应商、MCP、提示词与设置的存储接口，服务层通过它读写数据
+    pub storage: Arc<dyn Storage>,
 }
 
 impl AppState {
-    /// 创建新的应用状态
+    /// 创建新的应用状态，存储接口默认由同一个 SQLite 数据库提供
     pub fn new(db: Arc<Database>) -> Self {
-        Self { db }
+        Self {
+            storage: db.clone(),
+            db,
+        }
+    }
+
+    /// 使用其他存储实现（如测试替身或可同步的后端）
+    #[allow(dead_code)]
+    pub fn with_storage(db: Arc<Database>, storage: Arc<dyn Storage>) -> Self {
+        Self { db, storage }
     }
 }
@@ -0,0 +1,126 @@
+//! 测试夹具：基于内存数据库构建 `AppState`，并按需预置供应商、MCP 与提示词
+//!
+//! 仅在启用 `test-support` feature 时编译，供本仓库的集成测试及下游分支复用。
+
+use serde_json::Value;
+use std::sync::Arc;
+
+use crate::app_config::{AppType, McpApps, McpServer};
+use crate::database::Database;
+use crate::prompt::Prompt;
+use crate::provider::Provider;
+use crate::settings::{update_settings, AppSettings};
+use crate::store::AppState;
+
+/// `AppState` 夹具构建器
+///
+/// ```ignore
+/// let state = AppStateFixture::new()
+///     .seed_current_provider(AppType::Claude, fixture_provider("p1", json!({ "env": {} })))
+///     .seed_mcp(fixture_mcp("fetch", &[AppType::Claude]))
+///     .build();
+/// ```
+///
+/// 预置数据写入失败时直接 panic，便于在测试中定位问题。
+pub struct AppStateFixture {
+    db: Arc<Database>,
+    settings: Option<AppSettings>,
+}
+
+impl AppStateFixture {
+    pub fn new() -> Self {
+        let db = Database::memory().expect("create in-memory database");
+        Self {
+            db: Arc::new(db),
+            settings: None,
+        }
+    }
+
+    pub fn seed_provider(self, app_type: AppType, provider: Provider) -> Self {
+        self.db
+            .save_provider(app_type.as_str(), &provider)
+            .unwrap_or_else(|e| panic!("seed provider {}: {e}", provider.id));
+        self
+    }
+
+    /// 预置供应商并设为当前供应商
+    pub fn seed_current_provider(self, app_type: AppType, provider: Provider) -> Self {
+        let id = provider.id.clone();
+        let fixture = self.seed_provider(app_type.clone(), provider);
+        fixture
+            .db
+            .set_current_provider(app_type.as_str(), &id)
+            .unwrap_or_else(|e| panic!("set current provider {id}: {e}"));
+        fixture
+    }
+
+    pub fn seed_mcp(self, server: McpServer) -> Self {
+        self.db
+            .save_mcp_server(&server)
+            .unwrap_or_else(|e| panic!("seed mcp server {}: {e}", server.id));
+        self
+    }
+
+    pub fn seed_prompt(self, app_type: AppType, prompt: Prompt) -> Self {
+        self.db
+            .save_prompt(app_type.as_str(), &prompt)
+            .unwrap_or_else(|e| panic!("seed prompt {}: {e}", prompt.id));
+        self
+    }
+
+    /// 构建时写入全局设置（设置为进程级全局状态，需配合测试互斥锁使用）
+    pub fn with_settings(mut self, settings: AppSettings) -> Self {
+        self.settings = Some(settings);
+        self
+    }
+
+    pub fn build(self) -> AppState {
+        if let Some(settings) = self.settings {
+            update_settings(settings).expect("apply fixture settings");
+        }
+        AppState::new(self.db)
+    }
+}
+
+impl Default for AppStateFixture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 以 id 作为名称的供应商
+pub fn fixture_provider(id: &str, settings_config: Value) -> Provider {
+    Provider::with_id(id.to_string(), id.to_string(), settings_config, None)
+}
+
+/// 在指定应用中启用的 stdio MCP 服务器
+pub fn fixture_mcp(id: &str, apps: &[AppType]) -> McpServer {
+    let mut enabled = McpApps::default();
+    for app in apps {
+        enabled.set_enabled_for(app, true);
+    }
+    McpServer {
+        id: id.to_string(),
+        name: id.to_string(),
+        server: serde_json::json!({ "type": "stdio", "command": "echo" }),
+        apps: enabled,
+        description: None,
+        homepage: None,
+        docs: None,
+        tags: Vec::new(),
+    }
+}
+
+/// 未启用的提示词
+pub fn fixture_prompt(id: &str, content: &str) -> Prompt {
+    Prompt {
+        id: id.to_string(),
+        name: id.to_string(),
+        content: content.to_string(),
+        description: None,
+        enabled: false,
+        created_at: None,
+        updated_at: None,
+        version: None,
+    }
+}
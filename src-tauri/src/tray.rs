@@ -1,15 +1,60 @@
 use crate::app_config::AppType;
 use crate::error::AppError;
+use crate::settings::TrayIconStyle;
 use crate::store::AppState;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use tauri::{
+    image::Image,
     menu::{CheckMenuItem, Menu, MenuBuilder, MenuItem},
     Emitter, Manager,
 };
 
+/// macOS 菜单栏模板图标：只有黑色与透明度，由系统按深浅色菜单栏着色
+const TEMPLATE_ICON: &[u8] = include_bytes!("../icons/tray/macos/statusTemplate@2x.png");
+/// 浅色任务栏/面板使用的深色单色图标
+const MONOCHROME_LIGHT_ICON: &[u8] = include_bytes!("../icons/tray/tray-light.png");
+/// 深色任务栏/面板使用的白色单色图标
+const MONOCHROME_DARK_ICON: &[u8] = include_bytes!("../icons/tray/tray-dark.png");
+
+/// 合并窗口：窗口内的多次重建请求只执行一次完整重建
+const REBUILD_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// 已有一次重建在窗口内等待执行
+static REBUILD_PENDING: AtomicBool = AtomicBool::new(false);
+/// 最近一次设置到托盘上的菜单，勾选变化时直接在其上修改
+static TRAY_MENU: Lazy<Mutex<Option<Menu<tauri::Wry>>>> = Lazy::new(Default::default);
+static STATS: Lazy<Mutex<TrayMenuStats>> = Lazy::new(Default::default);
+
+/// 托盘菜单刷新统计，用于观察合并与局部更新的效果
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrayMenuStats {
+    /// 收到的完整重建请求
+    pub requested: u64,
+    /// 合并进已在等待的重建、未单独执行的请求
+    pub coalesced: u64,
+    pub rebuilds: u64,
+    /// 只移动勾选、未重建菜单的次数
+    pub partial_updates: u64,
+    /// 最近一次完整重建耗时（毫秒）
+    pub last_rebuild_ms: u64,
+}
+
+fn with_stats(update: impl FnOnce(&mut TrayMenuStats)) {
+    if let Ok(mut stats) = STATS.lock() {
+        update(&mut stats);
+    }
+}
+
 #[derive(Clone, Copy)]
 pub struct TrayTexts {
     show_main: &'static str,
     no_provider_hint: &'static str,
+    pause_management: &'static str,
     quit: &'static str,
 }
 
@@ -19,11 +64,13 @@ impl TrayTexts {
             "en" => Self {
                 show_main: "Open main window",
                 no_provider_hint: "  (No providers yet, please add them from the main window)",
+                pause_management: "Pause management",
                 quit: "Quit",
             },
             _ => Self {
                 show_main: "打开主界面",
                 no_provider_hint: "  (无供应商，请在主界面添加)",
+                pause_management: "暂停管理",
                 quit: "退出",
             },
         }
@@ -135,6 +182,112 @@ pub fn append_provider_section<'a>(
     Ok(menu_builder)
 }
 
+/// 托盘提示文本：当前供应商被检测到模型不一致时逐条列出
+pub fn tray_tooltip(state: &AppState) -> String {
+    let english = crate::settings::get_effective_settings()
+        .language
+        .as_deref()
+        == Some("en");
+    let mut lines = vec!["CLI Hub".to_string()];
+    for section in TRAY_SECTIONS.iter() {
+        let app = section.app_type.as_str();
+        let Ok(Some(current)) = state.storage.get_current_provider(app) else {
+            continue;
+        };
+        let Ok(providers) = state.storage.get_all_providers(app) else {
+            continue;
+        };
+        let Some(provider) = providers.get(&current) else {
+            continue;
+        };
+        let Some(fidelity) = provider
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.model_fidelity.as_ref())
+            .filter(|fidelity| fidelity.mismatch)
+        else {
+            continue;
+        };
+        lines.push(if english {
+            format!(
+                "⚠ {} · {}: requested {}, served {}",
+                section.log_name, provider.name, fidelity.requested_model, fidelity.served_model
+            )
+        } else {
+            format!(
+                "⚠ {} · {}：请求 {}，实际返回 {}",
+                section.log_name, provider.name, fidelity.requested_model, fidelity.served_model
+            )
+        });
+    }
+    lines.join("\n")
+}
+
+/// 按图标样式与系统主题选择托盘图标，返回图标及是否应作为模板图标显示
+pub fn tray_icon(app: &tauri::AppHandle) -> Option<(Image<'static>, bool)> {
+    let monochrome = match crate::settings::get_settings().tray_icon_style {
+        TrayIconStyle::Auto => cfg!(target_os = "macos"),
+        TrayIconStyle::Colored => false,
+        TrayIconStyle::Monochrome => true,
+    };
+    if monochrome {
+        if cfg!(target_os = "macos") {
+            if let Some(icon) = decode_icon(TEMPLATE_ICON) {
+                return Some((icon, true));
+            }
+        } else {
+            let bytes = if system_is_dark(app) {
+                MONOCHROME_DARK_ICON
+            } else {
+                MONOCHROME_LIGHT_ICON
+            };
+            if let Some(icon) = decode_icon(bytes) {
+                return Some((icon, false));
+            }
+        }
+    }
+    app.default_window_icon()
+        .map(|icon| (icon.clone().to_owned(), false))
+}
+
+fn decode_icon(bytes: &[u8]) -> Option<Image<'static>> {
+    Image::from_bytes(bytes)
+        .map(Image::to_owned)
+        .map_err(|e| log::warn!("解析托盘图标失败，改用应用图标: {e}"))
+        .ok()
+}
+
+/// Linux 部分桌面环境无法获取主题，此时按浅色处理
+fn system_is_dark(app: &tauri::AppHandle) -> bool {
+    app.get_webview_window("main")
+        .and_then(|window| window.theme().ok())
+        .is_some_and(|theme| matches!(theme, tauri::Theme::Dark))
+}
+
+/// 图标样式或系统主题变化后重新设置托盘图标
+pub fn refresh_tray_icon(app: &tauri::AppHandle) {
+    let Some(tray) = app.tray_by_id("main") else {
+        return;
+    };
+    let Some((icon, template)) = tray_icon(app) else {
+        return;
+    };
+    if let Err(e) = tray.set_icon(Some(icon)) {
+        log::warn!("更新托盘图标失败: {e}");
+    }
+    if let Err(e) = tray.set_icon_as_template(template) {
+        log::warn!("设置托盘模板图标失败: {e}");
+    }
+}
+
+pub fn refresh_tray_tooltip(app: &tauri::AppHandle, state: &AppState) {
+    if let Some(tray) = app.tray_by_id("main") {
+        if let Err(e) = tray.set_tooltip(Some(tray_tooltip(state))) {
+            log::warn!("更新托盘提示失败: {e}");
+        }
+    }
+}
+
 pub fn handle_provider_tray_event(app: &tauri::AppHandle, event_id: &str) -> bool {
     for section in TRAY_SECTIONS.iter() {
         if let Some(provider_id) = event_id.strip_prefix(section.prefix) {
@@ -145,6 +298,8 @@ pub fn handle_provider_tray_event(app: &tauri::AppHandle, event_id: &str) -> boo
             tauri::async_runtime::spawn_blocking(move || {
                 if let Err(e) = switch_provider_internal(&app_handle, app_type, provider_id) {
                     log::error!("切换{}供应商失败: {e}", section.log_name);
+                    // 点击已翻转了菜单项的勾选，切换失败时按实际状态恢复
+                    schedule_tray_rebuild(&app_handle);
                 }
             });
             return true;
@@ -158,8 +313,9 @@ pub fn create_tray_menu(
     app: &tauri::AppHandle,
     app_state: &AppState,
 ) -> Result<Menu<tauri::Wry>, AppError> {
-    let app_settings = crate::settings::get_settings();
+    let app_settings = crate::settings::get_effective_settings();
     let tray_texts = TrayTexts::from_language(app_settings.language.as_deref().unwrap_or("zh"));
+    let compact = app_settings.tray_density == crate::settings::TrayDensity::Compact;
 
     let mut menu_builder = MenuBuilder::new(app);
 
@@ -172,11 +328,15 @@ pub fn create_tray_menu(
     // 直接添加所有供应商到主菜单（扁平化结构，更简单可靠）
     for section in TRAY_SECTIONS.iter() {
         let app_type_str = section.app_type.as_str();
-        let providers = app_state.db.get_all_providers(app_type_str)?;
+        let mut providers = app_state.storage.get_all_providers(app_type_str)?;
         let current_id = app_state
-            .db
+            .storage
             .get_current_provider(app_type_str)?
             .unwrap_or_default();
+        // 紧凑模式只保留当前供应商，切换请回到主界面
+        if compact {
+            providers.retain(|id, _| *id == current_id);
+        }
 
         let manager = crate::provider::ProviderManager {
             providers,
@@ -187,17 +347,126 @@ pub fn create_tray_menu(
             append_provider_section(app, menu_builder, Some(&manager), section, &tray_texts)?;
     }
 
+    // 暂停管理：勾选期间不写入任何 live 配置
+    let pause_item = CheckMenuItem::with_id(
+        app,
+        "pause_management",
+        tray_texts.pause_management,
+        true,
+        crate::services::is_management_paused(),
+        None::<&str>,
+    )
+    .map_err(|e| AppError::Message(format!("创建暂停管理菜单失败: {e}")))?;
+
     // 分隔符和退出菜单
     let quit_item = MenuItem::with_id(app, "quit", tray_texts.quit, true, None::<&str>)
         .map_err(|e| AppError::Message(format!("创建退出菜单失败: {e}")))?;
 
-    menu_builder = menu_builder.separator().item(&quit_item);
+    menu_builder = menu_builder
+        .separator()
+        .item(&pause_item)
+        .separator()
+        .item(&quit_item);
 
     menu_builder
         .build()
         .map_err(|e| AppError::Message(format!("构建菜单失败: {e}")))
 }
 
+/// 记录托盘当前使用的菜单，供 [`mark_tray_current`] 局部更新
+pub fn remember_tray_menu(menu: &Menu<tauri::Wry>) {
+    if let Ok(mut slot) = TRAY_MENU.lock() {
+        *slot = Some(menu.clone());
+    }
+}
+
+/// 立即重建托盘菜单并刷新提示
+fn rebuild_tray_menu(app: &tauri::AppHandle, state: &AppState) -> Result<(), AppError> {
+    let Some(tray) = app.tray_by_id("main") else {
+        return Ok(());
+    };
+    let started = Instant::now();
+    let menu = create_tray_menu(app, state)?;
+    tray.set_menu(Some(menu.clone()))
+        .map_err(|e| AppError::Message(format!("更新托盘菜单失败: {e}")))?;
+    remember_tray_menu(&menu);
+    refresh_tray_tooltip(app, state);
+
+    let elapsed = started.elapsed().as_millis() as u64;
+    with_stats(|stats| {
+        stats.rebuilds += 1;
+        stats.last_rebuild_ms = elapsed;
+    });
+    log::debug!("托盘菜单已重建，耗时 {elapsed}ms");
+    Ok(())
+}
+
+/// 请求重建托盘菜单
+///
+/// 请求在 [`REBUILD_DEBOUNCE`] 窗口内合并，窗口结束时按最新数据重建一次，
+/// 批量导入或连续编辑时不会反复重建导致菜单闪烁。
+pub fn schedule_tray_rebuild(app: &tauri::AppHandle) {
+    with_stats(|stats| stats.requested += 1);
+    if REBUILD_PENDING.swap(true, Ordering::AcqRel) {
+        with_stats(|stats| stats.coalesced += 1);
+        return;
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(REBUILD_DEBOUNCE).await;
+        // 先清除标记再读取数据，重建期间的新请求会另起一个窗口
+        REBUILD_PENDING.store(false, Ordering::Release);
+        let Some(state) = app.try_state::<AppState>() else {
+            return;
+        };
+        if let Err(e) = rebuild_tray_menu(&app, state.inner()) {
+            log::error!("重建托盘菜单失败: {e}");
+        }
+    });
+}
+
+/// 当前供应商变化时只移动勾选
+///
+/// 菜单中找不到目标项（紧凑模式只列出当前供应商，或菜单尚未记录）时改为请求完整重建。
+pub fn mark_tray_current(app: &tauri::AppHandle, app_type: &AppType, provider_id: &str) {
+    let Some(section) = TRAY_SECTIONS.iter().find(|s| s.app_type == *app_type) else {
+        return;
+    };
+    let target = format!("{}{provider_id}", section.prefix);
+    let moved = TRAY_MENU
+        .lock()
+        .ok()
+        .and_then(|slot| slot.clone())
+        .filter(|menu| menu.get(target.as_str()).is_some())
+        .is_some_and(|menu| move_check(&menu, section.prefix, &target));
+
+    if !moved {
+        schedule_tray_rebuild(app);
+        return;
+    }
+    with_stats(|stats| stats.partial_updates += 1);
+    if let Some(state) = app.try_state::<AppState>() {
+        refresh_tray_tooltip(app, state.inner());
+    }
+}
+
+/// 勾选 `target`，取消同一分组内其余供应商的勾选；任一项失败返回 false
+fn move_check(menu: &Menu<tauri::Wry>, prefix: &str, target: &str) -> bool {
+    let Ok(items) = menu.items() else {
+        return false;
+    };
+    items
+        .iter()
+        .filter_map(|item| item.as_check_menuitem())
+        .filter(|item| item.id().0.starts_with(prefix))
+        .all(|item| item.set_checked(item.id().0 == target).is_ok())
+}
+
+pub fn tray_menu_stats() -> TrayMenuStats {
+    STATS.lock().map(|stats| *stats).unwrap_or_default()
+}
+
 #[cfg(target_os = "macos")]
 pub fn apply_tray_policy(app: &tauri::AppHandle, dock_visible: bool) {
     use tauri::ActivationPolicy;
@@ -237,6 +506,15 @@ pub fn handle_tray_menu_event(app: &tauri::AppHandle, event_id: &str) {
                 }
             }
         }
+        "pause_management" => {
+            let app_handle = app.clone();
+            tauri::async_runtime::spawn_blocking(move || {
+                let paused = !crate::services::is_management_paused();
+                if let Err(e) = set_management_paused_internal(&app_handle, paused) {
+                    log::error!("切换暂停管理状态失败: {e}");
+                }
+            });
+        }
         "quit" => {
             log::info!("退出应用");
             app.exit(0);
@@ -261,17 +539,16 @@ pub fn switch_provider_internal(
         let app_type_str = app_type.as_str().to_string();
         let provider_id_clone = provider_id.clone();
 
-        crate::commands::switch_provider(app_state.clone(), app_type_str.clone(), provider_id)
-            .map_err(AppError::Message)?;
-
-        // 切换成功后重新创建托盘菜单
-        if let Ok(new_menu) = create_tray_menu(app, app_state.inner()) {
-            if let Some(tray) = app.tray_by_id("main") {
-                if let Err(e) = tray.set_menu(Some(new_menu)) {
-                    log::error!("更新托盘菜单失败: {e}");
-                }
-            }
-        }
+        // 托盘事件在 spawn_blocking 线程中处理，可以直接等待异步命令完成
+        tauri::async_runtime::block_on(crate::commands::switch_provider(
+            app.clone(),
+            app_state.clone(),
+            app_type_str.clone(),
+            provider_id,
+            None,
+        ))
+        .map_err(AppError::Message)?;
+        // 托盘勾选已由 switch_provider 命令更新
 
         // 发射事件到前端，通知供应商已切换
         let event_data = serde_json::json!({
@@ -285,24 +562,45 @@ pub fn switch_provider_internal(
     Ok(())
 }
 
+/// 暂停或恢复管理；恢复时执行统一同步并返回同步前的差异
+pub fn set_management_paused_internal(
+    app: &tauri::AppHandle,
+    paused: bool,
+) -> Result<Vec<crate::services::ResumeSyncPreview>, AppError> {
+    let Some(app_state) = app.try_state::<AppState>() else {
+        return Ok(Vec::new());
+    };
+
+    let applied = if paused {
+        crate::services::SyncPauseService::pause(app_state.inner())?;
+        Vec::new()
+    } else {
+        crate::services::SyncPauseService::resume(app_state.inner())?
+    };
+
+    // 菜单项的勾选状态由点击自动翻转，重建菜单以确保与实际状态一致
+    schedule_tray_rebuild(app);
+
+    if let Err(e) = app.emit("management-paused-changed", paused) {
+        log::error!("发射暂停管理事件失败: {e}");
+    }
+    Ok(applied)
+}
+
 /// 更新托盘菜单的Tauri命令
+///
+/// 重建在合并窗口结束后异步执行；返回值表示托盘是否存在
 #[tauri::command]
-pub async fn update_tray_menu(
-    app: tauri::AppHandle,
-    state: tauri::State<'_, AppState>,
-) -> Result<bool, String> {
-    match create_tray_menu(&app, state.inner()) {
-        Ok(new_menu) => {
-            if let Some(tray) = app.tray_by_id("main") {
-                tray.set_menu(Some(new_menu))
-                    .map_err(|e| format!("更新托盘菜单失败: {e}"))?;
-                return Ok(true);
-            }
-            Ok(false)
-        }
-        Err(err) => {
-            log::error!("创建托盘菜单失败: {err}");
-            Ok(false)
-        }
+pub async fn update_tray_menu(app: tauri::AppHandle) -> Result<bool, String> {
+    if app.tray_by_id("main").is_none() {
+        return Ok(false);
     }
+    schedule_tray_rebuild(&app);
+    Ok(true)
+}
+
+/// 托盘菜单刷新统计
+#[tauri::command]
+pub fn get_tray_menu_stats() -> Result<TrayMenuStats, String> {
+    Ok(tray_menu_stats())
 }
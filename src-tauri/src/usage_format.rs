@@ -0,0 +1,167 @@
+use chrono::{DateTime, Local, TimeZone};
+use serde::{Deserialize, Serialize};
+
+use crate::provider::UsageData;
+
+/// 按界面语言格式化好的用量文本，与原始数值一同返回，各界面直接展示即可
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageDisplay {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub used: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remaining: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub used_percent: Option<String>,
+    /// 本地时区的重置时间
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reset_at: Option<String>,
+}
+
+/// 当前界面语言，未设置时与其他用户可见文本一样按中文处理
+pub fn current_language() -> String {
+    crate::settings::get_effective_settings()
+        .language
+        .unwrap_or_else(|| "zh".to_string())
+}
+
+/// 为每个套餐及其分桶填充格式化文本；分桶沿用所属套餐的货币
+pub fn localize_usage(usage_list: &mut [UsageData], lang: &str) {
+    for usage in usage_list {
+        let currency = usage.currency.clone();
+        let amount = |value: Option<f64>, unit: Option<&str>| {
+            value.map(|v| format_amount(v, currency.as_deref(), unit, lang))
+        };
+
+        usage.display = Some(UsageDisplay {
+            total: amount(usage.total, usage.unit.as_deref()),
+            used: amount(usage.used, usage.unit.as_deref()),
+            remaining: amount(usage.remaining, usage.unit.as_deref()),
+            used_percent: used_ratio(usage.total, usage.used).map(format_percent),
+            reset_at: usage.reset_at.and_then(|at| format_timestamp(at, lang)),
+        });
+        for bucket in usage.buckets.iter_mut() {
+            bucket.display = Some(UsageDisplay {
+                total: amount(bucket.total, bucket.unit.as_deref()),
+                used: amount(bucket.used, bucket.unit.as_deref()),
+                remaining: amount(bucket.remaining, bucket.unit.as_deref()),
+                used_percent: used_ratio(bucket.total, bucket.used).map(format_percent),
+                reset_at: bucket.reset_at.and_then(|at| format_timestamp(at, lang)),
+            });
+        }
+    }
+}
+
+/// 百分比保留一位小数，整数时省略小数部分
+pub fn format_percent(percent: f64) -> String {
+    let text = group_digits(&format!("{percent:.1}"));
+    format!("{}%", text.strip_suffix(".0").unwrap_or(&text))
+}
+
+fn used_ratio(total: Option<f64>, used: Option<f64>) -> Option<f64> {
+    let total = total.filter(|t| *t > 0.0)?;
+    Some((used? / total * 100.0).clamp(0.0, 100.0))
+}
+
+/// 有货币时按金额格式（两位小数加货币符号），否则保留至多两位小数并附上单位
+fn format_amount(value: f64, currency: Option<&str>, unit: Option<&str>, lang: &str) -> String {
+    if let Some(code) = currency {
+        let sign = if value < 0.0 { "-" } else { "" };
+        let number = group_digits(&format!("{:.2}", value.abs()));
+        return format!("{sign}{}{number}", currency_prefix(code, lang));
+    }
+
+    let number = group_digits(&format!("{value:.2}"));
+    let number = number.trim_end_matches('0').trim_end_matches('.');
+    match unit.map(str::trim).filter(|u| !u.is_empty()) {
+        Some(unit) => format!("{number} {unit}"),
+        None => number.to_string(),
+    }
+}
+
+/// 本地货币使用简写符号，外币加地区前缀以免混淆（如中文界面下的 US$）
+fn currency_prefix(code: &str, lang: &str) -> String {
+    let english = lang == "en";
+    match code {
+        "USD" if english => "$".to_string(),
+        "USD" => "US$".to_string(),
+        "CNY" if english => "CN¥".to_string(),
+        "CNY" => "¥".to_string(),
+        "EUR" => "€".to_string(),
+        "GBP" => "£".to_string(),
+        "JPY" => "JP¥".to_string(),
+        other => format!("{other} "),
+    }
+}
+
+/// 整数部分每三位插入逗号，中英文界面一致
+fn group_digits(number: &str) -> String {
+    let (sign, digits) = match number.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", number),
+    };
+    let (int_part, frac_part) = match digits.split_once('.') {
+        Some((int_part, frac)) => (int_part, Some(frac)),
+        None => (digits, None),
+    };
+
+    let mut grouped = String::with_capacity(int_part.len() + int_part.len() / 3);
+    for (i, ch) in int_part.chars().enumerate() {
+        if i > 0 && (int_part.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(ch);
+    }
+    match frac_part {
+        Some(frac) => format!("{sign}{grouped}.{frac}"),
+        None => format!("{sign}{grouped}"),
+    }
+}
+
+fn format_timestamp(seconds: i64, lang: &str) -> Option<String> {
+    let at = Local.timestamp_opt(seconds, 0).single()?;
+    Some(format_datetime(&at, lang))
+}
+
+fn format_datetime<Tz: TimeZone>(at: &DateTime<Tz>, lang: &str) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    if lang == "en" {
+        at.format("%b %-d, %Y %H:%M").to_string()
+    } else {
+        at.format("%Y年%-m月%-d日 %H:%M").to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn formats_amounts_percentages_and_dates_per_language() {
+        assert_eq!(format_amount(1234.5, Some("USD"), None, "en"), "$1,234.50");
+        assert_eq!(
+            format_amount(1234.5, Some("USD"), None, "zh"),
+            "US$1,234.50"
+        );
+        assert_eq!(format_amount(-3.0, Some("CNY"), None, "zh"), "-¥3.00");
+        assert_eq!(format_amount(12.0, Some("HKD"), None, "en"), "HKD 12.00");
+        assert_eq!(
+            format_amount(1500000.0, None, Some("tokens"), "en"),
+            "1,500,000 tokens"
+        );
+        assert_eq!(format_amount(0.25, None, None, "zh"), "0.25");
+
+        assert_eq!(format_percent(60.0), "60%");
+        assert_eq!(format_percent(12.345), "12.3%");
+        assert_eq!(used_ratio(Some(0.0), Some(1.0)), None);
+
+        let at = Utc.with_ymd_and_hms(2026, 1, 5, 14, 30, 0).unwrap();
+        assert_eq!(format_datetime(&at, "en"), "Jan 5, 2026 14:30");
+        assert_eq!(format_datetime(&at, "zh"), "2026年1月5日 14:30");
+    }
+}
@@ -1,10 +1,12 @@
 use reqwest::Client;
 use rquickjs::{Context, Function, Runtime};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::time::Duration;
 
 use crate::error::AppError;
+use crate::provider::{UsageBucket, UsageData, USAGE_SCHEMA_VERSION};
 
 /// 执行用量查询脚本
 pub async fn execute_usage_script(
@@ -14,7 +16,20 @@ pub async fn execute_usage_script(
     timeout_secs: u64,
     access_token: Option<&str>,
     user_id: Option<&str>,
-) -> Result<Value, AppError> {
+) -> Result<Vec<UsageData>, AppError> {
+    let (script, request) = prepare_request(script_code, api_key, base_url, access_token, user_id)?;
+    let response_data = send_http_request(&request, timeout_secs).await?;
+    extract_usage(&script, &response_data)
+}
+
+/// 替换模板变量并执行脚本，取出 request 配置；返回替换后的脚本供 extractor 复用
+fn prepare_request(
+    script_code: &str,
+    api_key: &str,
+    base_url: &str,
+    access_token: Option<&str>,
+    user_id: Option<&str>,
+) -> Result<(String, RequestConfig), AppError> {
     // 1. 替换变量
     let mut replaced = script_code
         .replace("{{apiKey}}", api_key)
@@ -103,10 +118,12 @@ pub async fn execute_usage_script(
         )
     })?;
 
-    // 4. 发送 HTTP 请求
-    let response_data = send_http_request(&request, timeout_secs).await?;
+    Ok((replaced, request))
+}
 
-    // 5. 在独立作用域中执行 extractor（确保 Runtime/Context 在函数结束前释放）
+/// 在独立的 JS 运行时中以响应数据调用 extractor，并规范化返回值
+fn extract_usage(script: &str, response_data: &str) -> Result<Vec<UsageData>, AppError> {
+    // 在独立作用域中执行 extractor（确保 Runtime/Context 在函数结束前释放）
     let result: Value = {
         let runtime = Runtime::new().map_err(|e| {
             AppError::localized(
@@ -125,7 +142,7 @@ pub async fn execute_usage_script(
 
         context.with(|ctx| {
             // 重新 eval 获取配置对象
-            let config: rquickjs::Object = ctx.eval(replaced.clone()).map_err(|e| {
+            let config: rquickjs::Object = ctx.eval(script).map_err(|e| {
                 AppError::localized(
                     "usage_script.config_reparse_failed",
                     format!("重新解析配置失败: {e}"),
@@ -143,14 +160,13 @@ pub async fn execute_usage_script(
             })?;
 
             // 将响应数据转换为 JS 值
-            let response_js: rquickjs::Value =
-                ctx.json_parse(response_data.as_str()).map_err(|e| {
-                    AppError::localized(
-                        "usage_script.response_parse_failed",
-                        format!("解析响应 JSON 失败: {e}"),
-                        format!("Failed to parse response JSON: {e}"),
-                    )
-                })?;
+            let response_js: rquickjs::Value = ctx.json_parse(response_data).map_err(|e| {
+                AppError::localized(
+                    "usage_script.response_parse_failed",
+                    format!("解析响应 JSON 失败: {e}"),
+                    format!("Failed to parse response JSON: {e}"),
+                )
+            })?;
 
             // 调用 extractor(response)
             let result_js: rquickjs::Value = extractor.call((response_js,)).map_err(|e| {
@@ -198,10 +214,8 @@ pub async fn execute_usage_script(
         })?
     }; // Runtime 和 Context 在这里被 drop
 
-    // 6. 验证返回值格式
-    validate_result(&result)?;
-
-    Ok(result)
+    // 验证并规范化为当前版本的用量结构
+    normalize_result(result)
 }
 
 /// 请求配置结构
@@ -270,21 +284,310 @@ async fn send_http_request(config: &RequestConfig, timeout_secs: u64) -> Result<
     })?;
 
     if !status.is_success() {
-        let preview = if text.len() > 200 {
-            format!("{}...", &text[..200])
-        } else {
-            text.clone()
-        };
-        return Err(AppError::localized(
-            "usage_script.http_error",
-            format!("HTTP {status} : {preview}"),
-            format!("HTTP {status} : {preview}"),
-        ));
+        return Err(http_status_error(status, &text));
     }
 
     Ok(text)
 }
 
+fn http_status_error(status: impl std::fmt::Display, text: &str) -> AppError {
+    let preview = if text.len() > 200 {
+        format!("{}...", &text[..200])
+    } else {
+        text.to_string()
+    };
+    // 响应体可能回显请求中的 Key
+    let preview = crate::log_sanitizer::redact(&preview);
+    AppError::localized(
+        "usage_script.http_error",
+        format!("HTTP {status} : {preview}"),
+        format!("HTTP {status} : {preview}"),
+    )
+}
+
+/// 试运行时代替真实接口返回的响应
+#[derive(Debug, Clone, Deserialize)]
+pub struct MockHttpResponse {
+    /// 按子串匹配请求 URL；为空时匹配任意请求
+    #[serde(default)]
+    pub url: Option<String>,
+    /// 为空时匹配任意 HTTP 方法
+    #[serde(default)]
+    pub method: Option<String>,
+    #[serde(default = "default_mock_status")]
+    pub status: u16,
+    /// 字符串原样作为响应体，其余 JSON 值序列化后返回
+    #[serde(default)]
+    pub body: Value,
+}
+
+fn default_mock_status() -> u16 {
+    200
+}
+
+/// 脚本构造出的请求（请求头已脱敏）
+#[derive(Debug, Clone, Serialize)]
+pub struct DryRunRequest {
+    pub url: String,
+    pub method: String,
+    pub headers: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+/// 试运行结果；extractor 出错时仍返回请求信息便于排查
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageDryRunResult {
+    pub request: DryRunRequest,
+    /// 命中的模拟响应下标
+    pub matched_mock: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Vec<UsageData>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 以模拟响应试运行用量脚本，不发出任何网络请求
+///
+/// 未提供的凭证以占位值代替，脚本作者无需填写真实 Key。
+pub fn dry_run_usage_script(
+    script_code: &str,
+    mock_responses: &[MockHttpResponse],
+    api_key: Option<&str>,
+    base_url: Option<&str>,
+    access_token: Option<&str>,
+    user_id: Option<&str>,
+) -> Result<UsageDryRunResult, AppError> {
+    let (script, request) = prepare_request(
+        script_code,
+        api_key.unwrap_or("dry-run-api-key"),
+        base_url.unwrap_or(""),
+        Some(access_token.unwrap_or("dry-run-access-token")),
+        Some(user_id.unwrap_or("dry-run-user")),
+    )?;
+
+    let (matched_mock, mock) = mock_responses
+        .iter()
+        .enumerate()
+        .find(|(_, mock)| mock_matches(mock, &request))
+        .ok_or_else(|| {
+            AppError::localized(
+                "usage_script.mock_not_found",
+                format!("没有匹配请求的模拟响应: {} {}", request.method, request.url),
+                format!(
+                    "No mock response matches the request: {} {}",
+                    request.method, request.url
+                ),
+            )
+        })?;
+
+    let body = match &mock.body {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    };
+    let outcome = if (200..300).contains(&mock.status) {
+        extract_usage(&script, &body)
+    } else {
+        Err(http_status_error(mock.status, &body))
+    };
+
+    let (data, error) = match outcome {
+        Ok(data) => (Some(data), None),
+        Err(err) => (None, Some(err.to_string())),
+    };
+
+    Ok(UsageDryRunResult {
+        request: DryRunRequest {
+            url: request.url,
+            method: request.method,
+            headers: request
+                .headers
+                .into_iter()
+                .map(|(k, v)| {
+                    let v = crate::log_sanitizer::redact(&v).into_owned();
+                    (k, v)
+                })
+                .collect(),
+            body: request.body,
+        },
+        matched_mock,
+        data,
+        error,
+    })
+}
+
+fn mock_matches(mock: &MockHttpResponse, request: &RequestConfig) -> bool {
+    let url_matches = mock
+        .url
+        .as_deref()
+        .is_none_or(|pattern| request.url.contains(pattern));
+    let method_matches = mock
+        .method
+        .as_deref()
+        .is_none_or(|method| method.eq_ignore_ascii_case(&request.method));
+    url_matches && method_matches
+}
+
+/// 将脚本返回值规范化为 v2 用量数据
+///
+/// 支持三种形式：单对象、对象数组，以及 `{ "version": 2, "data": [...] }` 包装；
+/// 版本也可在单项中以 `schemaVersion` 声明，未声明时按 v1 处理。v1 的顶层额度
+/// 会转换为单个分桶，v2 仅提供分桶时以第一个分桶回填顶层额度，保证新旧界面都能展示。
+fn normalize_result(result: Value) -> Result<Vec<UsageData>, AppError> {
+    let items = unwrap_versioned(result)?;
+    validate_result(&items)?;
+
+    let items = match items {
+        Value::Array(items) => items,
+        single => vec![single],
+    };
+
+    items
+        .into_iter()
+        .map(|mut item| {
+            if let Some(v) = item.get("schemaVersion").filter(|v| !v.is_null()) {
+                parse_schema_version(v)?;
+            }
+            normalize_timestamps(&mut item)?;
+            let mut usage: UsageData = serde_json::from_value(item).map_err(|e| {
+                AppError::localized(
+                    "usage_script.data_format_error",
+                    format!("数据格式错误: {e}"),
+                    format!("Data format error: {e}"),
+                )
+            })?;
+            normalize_usage(&mut usage);
+            Ok(usage)
+        })
+        .collect()
+}
+
+/// 拆出 `{ version, data }` 包装中的数据，并校验声明的版本
+fn unwrap_versioned(mut result: Value) -> Result<Value, AppError> {
+    let is_wrapper = result
+        .as_object()
+        .is_some_and(|obj| obj.contains_key("data") && obj.contains_key("version"));
+    if !is_wrapper {
+        return Ok(result);
+    }
+
+    parse_schema_version(&result["version"])?;
+    Ok(result
+        .as_object_mut()
+        .and_then(|obj| obj.remove("data"))
+        .unwrap_or(Value::Null))
+}
+
+fn parse_schema_version(value: &Value) -> Result<u32, AppError> {
+    match value.as_u64() {
+        Some(v @ 1..=2) => Ok(v as u32),
+        _ => Err(AppError::localized(
+            "usage_script.unsupported_schema_version",
+            format!("不支持的用量数据版本: {value}（当前支持 1-{USAGE_SCHEMA_VERSION}）"),
+            format!(
+                "Unsupported usage schema version: {value} (supported: 1-{USAGE_SCHEMA_VERSION})"
+            ),
+        )),
+    }
+}
+
+/// 将 resetAt（秒、毫秒或 RFC 3339 字符串）统一为 Unix 秒
+fn normalize_timestamps(item: &mut Value) -> Result<(), AppError> {
+    normalize_reset_at(item)?;
+    if let Some(buckets) = item.get_mut("buckets").and_then(Value::as_array_mut) {
+        for bucket in buckets {
+            normalize_reset_at(bucket)?;
+        }
+    }
+    Ok(())
+}
+
+fn normalize_reset_at(obj: &mut Value) -> Result<(), AppError> {
+    let Some(raw) = obj.get_mut("resetAt") else {
+        return Ok(());
+    };
+    let seconds = match &*raw {
+        Value::Null => return Ok(()),
+        Value::Number(n) => n.as_f64().map(|v| {
+            // 大于 1e12 视为毫秒
+            if v.abs() >= 1e12 {
+                (v / 1000.0) as i64
+            } else {
+                v as i64
+            }
+        }),
+        Value::String(s) => chrono::DateTime::parse_from_rfc3339(s.trim())
+            .ok()
+            .map(|dt| dt.timestamp()),
+        _ => None,
+    };
+    let seconds = seconds.ok_or_else(|| {
+        AppError::localized(
+            "usage_script.reset_at_invalid",
+            format!("resetAt 必须是时间戳或 RFC 3339 时间字符串: {raw}"),
+            format!("resetAt must be a timestamp or an RFC 3339 string: {raw}"),
+        )
+    })?;
+    *raw = Value::from(seconds);
+    Ok(())
+}
+
+fn normalize_usage(usage: &mut UsageData) {
+    usage.schema_version = USAGE_SCHEMA_VERSION;
+    usage.currency = usage
+        .currency
+        .as_deref()
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .map(str::to_ascii_uppercase);
+
+    fill_missing_amount(&mut usage.total, &mut usage.used, &mut usage.remaining);
+    for bucket in usage.buckets.iter_mut() {
+        fill_missing_amount(&mut bucket.total, &mut bucket.used, &mut bucket.remaining);
+    }
+
+    let has_top_level = usage.total.is_some() || usage.used.is_some() || usage.remaining.is_some();
+    if usage.buckets.is_empty() {
+        if has_top_level {
+            usage.buckets.push(UsageBucket {
+                name: None,
+                total: usage.total,
+                used: usage.used,
+                remaining: usage.remaining,
+                unit: usage.unit.clone(),
+                reset_at: usage.reset_at,
+                display: None,
+            });
+        }
+    } else if !has_top_level {
+        let first = &usage.buckets[0];
+        usage.total = first.total;
+        usage.used = first.used;
+        usage.remaining = first.remaining;
+        if usage.unit.is_none() {
+            usage.unit = first.unit.clone();
+        }
+        if usage.reset_at.is_none() {
+            usage.reset_at = first.reset_at;
+        }
+    }
+}
+
+/// 三项额度缺一项时推算补全；total 为 -1 表示不限额，不参与推算
+fn fill_missing_amount(
+    total: &mut Option<f64>,
+    used: &mut Option<f64>,
+    remaining: &mut Option<f64>,
+) {
+    match (*total, *used, *remaining) {
+        (Some(t), Some(u), None) if t >= 0.0 => *remaining = Some(t - u),
+        (Some(t), None, Some(r)) if t >= 0.0 => *used = Some(t - r),
+        (None, Some(u), Some(r)) => *total = Some(u + r),
+        _ => {}
+    }
+}
+
 /// 验证脚本返回值（支持单对象或数组）
 fn validate_result(result: &Value) -> Result<(), AppError> {
     // 如果是数组，验证每个元素
@@ -391,6 +694,205 @@ fn validate_single_usage(result: &Value) -> Result<(), AppError> {
             "extra must be string or null",
         ));
     }
+    if obj.contains_key("currency")
+        && !result["currency"].is_null()
+        && !result["currency"].is_string()
+    {
+        return Err(AppError::localized(
+            "usage_script.currency_type_error",
+            "currency 必须是字符串或 null",
+            "currency must be string or null",
+        ));
+    }
+
+    match obj.get("buckets") {
+        None | Some(Value::Null) => Ok(()),
+        Some(Value::Array(buckets)) => buckets
+            .iter()
+            .enumerate()
+            .try_for_each(|(idx, bucket)| validate_bucket(bucket, idx)),
+        Some(_) => Err(AppError::localized(
+            "usage_script.buckets_type_error",
+            "buckets 必须是数组或 null",
+            "buckets must be array or null",
+        )),
+    }
+}
+
+/// 验证单个额度分桶
+fn validate_bucket(bucket: &Value, idx: usize) -> Result<(), AppError> {
+    let invalid = |field: &str, expected_zh: &str, expected_en: &str| {
+        AppError::localized(
+            "usage_script.bucket_type_error",
+            format!("buckets[{idx}].{field} 必须是{expected_zh}或 null"),
+            format!("buckets[{idx}].{field} must be {expected_en} or null"),
+        )
+    };
+
+    let obj = bucket.as_object().ok_or_else(|| {
+        AppError::localized(
+            "usage_script.bucket_must_be_object",
+            format!("buckets[{idx}] 必须是对象"),
+            format!("buckets[{idx}] must be an object"),
+        )
+    })?;
+
+    for (field, value) in obj {
+        let valid = match field.as_str() {
+            "total" | "used" | "remaining" => value.is_null() || value.is_number(),
+            "name" | "unit" => value.is_null() || value.is_string(),
+            // 具体格式在规范化时校验
+            "resetAt" => value.is_null() || value.is_number() || value.is_string(),
+            _ => true,
+        };
+        if !valid {
+            return Err(match field.as_str() {
+                "total" | "used" | "remaining" => invalid(field, "数字", "number"),
+                "resetAt" => invalid(field, "数字、字符串", "number, string"),
+                _ => invalid(field, "字符串", "string"),
+            });
+        }
+    }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn v1_output_is_normalized_into_a_single_bucket() {
+        let usage = normalize_result(json!({
+            "planName": "Pro",
+            "total": 100.0,
+            "used": 30.0,
+            "unit": "USD"
+        }))
+        .unwrap();
+
+        assert_eq!(usage.len(), 1);
+        let usage = &usage[0];
+        assert_eq!(usage.schema_version, USAGE_SCHEMA_VERSION);
+        assert_eq!(usage.remaining, Some(70.0));
+        assert_eq!(
+            usage.buckets,
+            vec![UsageBucket {
+                name: None,
+                total: Some(100.0),
+                used: Some(30.0),
+                remaining: Some(70.0),
+                unit: Some("USD".to_string()),
+                reset_at: None,
+                display: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn v2_wrapper_with_buckets_backfills_top_level_fields() {
+        let usage = normalize_result(json!({
+            "version": 2,
+            "data": [{
+                "planName": "Team",
+                "currency": " cny ",
+                "buckets": [
+                    { "name": "daily", "total": 10, "remaining": 4, "resetAt": "2025-01-02T00:00:00Z" },
+                    { "name": "monthly", "total": 300, "used": 120, "resetAt": 1735776000000_i64 }
+                ]
+            }]
+        }))
+        .unwrap();
+
+        let usage = &usage[0];
+        assert_eq!(usage.currency.as_deref(), Some("CNY"));
+        assert_eq!(usage.buckets[0].used, Some(6.0));
+        assert_eq!(usage.buckets[0].reset_at, Some(1_735_776_000));
+        assert_eq!(usage.buckets[1].reset_at, Some(1_735_776_000));
+        assert_eq!(usage.total, Some(10.0));
+        assert_eq!(usage.reset_at, Some(1_735_776_000));
+    }
+
+    const SCRIPT: &str = r#"({
+        request: {
+            url: "{{baseUrl}}/user/balance",
+            method: "GET",
+            headers: { "Authorization": "Bearer {{apiKey}}" }
+        },
+        extractor: function (response) {
+            return { planName: response.plan, remaining: response.balance, unit: "USD" };
+        }
+    })"#;
+
+    fn mock(url: Option<&str>, status: u16, body: Value) -> MockHttpResponse {
+        MockHttpResponse {
+            url: url.map(str::to_string),
+            method: None,
+            status,
+            body,
+        }
+    }
+
+    #[test]
+    fn dry_run_uses_the_first_matching_mock() {
+        let mocks = [
+            mock(Some("/other"), 200, json!({})),
+            mock(
+                Some("/user/balance"),
+                200,
+                json!({ "plan": "Pro", "balance": 12.5 }),
+            ),
+        ];
+        let result = dry_run_usage_script(
+            SCRIPT,
+            &mocks,
+            None,
+            Some("https://relay.example.com"),
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(result.matched_mock, 1);
+        assert_eq!(result.request.url, "https://relay.example.com/user/balance");
+        assert_eq!(result.request.headers["Authorization"], "Bearer ***");
+        let data = result.data.expect("usage data");
+        assert_eq!(data[0].plan_name.as_deref(), Some("Pro"));
+        assert_eq!(data[0].remaining, Some(12.5));
+    }
+
+    #[test]
+    fn dry_run_reports_mock_http_errors_and_missing_mocks() {
+        let result = dry_run_usage_script(
+            SCRIPT,
+            &[mock(None, 401, json!("invalid key"))],
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(result.data.is_none());
+        assert!(result.error.unwrap().contains("401"));
+
+        assert!(dry_run_usage_script(
+            SCRIPT,
+            &[mock(Some("/nope"), 200, json!({}))],
+            None,
+            None,
+            None,
+            None
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn rejects_unsupported_versions_and_malformed_buckets() {
+        assert!(normalize_result(json!({ "version": 3, "data": [] })).is_err());
+        assert!(normalize_result(json!({ "schemaVersion": 9, "total": 1 })).is_err());
+        assert!(normalize_result(json!({ "buckets": {} })).is_err());
+        assert!(normalize_result(json!({ "buckets": [{ "total": "10" }] })).is_err());
+        assert!(normalize_result(json!({ "resetAt": "tomorrow" })).is_err());
+    }
+}
@@ -0,0 +1,106 @@
+use cli_hub_lib::{
+    update_settings, AppSettings, ConfirmAction, ConfirmationInput, ConfirmationLevel,
+    ConfirmationPolicies, ConfirmationService,
+};
+
+#[path = "support.rs"]
+mod support;
+use support::{ensure_test_home, reset_test_fs, test_mutex};
+
+fn set_policies(policies: ConfirmationPolicies) {
+    let settings = AppSettings {
+        confirmation_policies: policies,
+        ..AppSettings::default()
+    };
+    update_settings(settings).expect("update settings");
+}
+
+fn input(token: Option<String>, text: Option<&str>) -> ConfirmationInput {
+    ConfirmationInput::new(token, text.map(str::to_string), None)
+}
+
+#[test]
+fn default_policy_requires_no_confirmation() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+    ensure_test_home();
+
+    let challenge = ConfirmationService::request(ConfirmAction::DeleteProvider, "claude:p1", "P1")
+        .expect("request confirmation");
+    assert_eq!(challenge.level, ConfirmationLevel::None);
+    assert!(challenge.token.is_none());
+    ConfirmationService::verify(
+        ConfirmAction::DeleteProvider,
+        "claude:p1",
+        &input(None, None),
+    )
+    .expect("no confirmation needed by default");
+}
+
+#[test]
+fn type_name_policy_enforces_token_round_trip() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+    ensure_test_home();
+    set_policies(ConfirmationPolicies {
+        delete_mcp_server: ConfirmationLevel::TypeName,
+        ..ConfirmationPolicies::default()
+    });
+
+    let action = ConfirmAction::DeleteMcpServer;
+    assert!(
+        ConfirmationService::verify(action, "fetch", &input(None, None)).is_err(),
+        "missing token must be rejected"
+    );
+
+    let challenge = ConfirmationService::request(action, "fetch", "mcp-server-fetch")
+        .expect("request confirmation");
+    assert_eq!(challenge.expected_text.as_deref(), Some("mcp-server-fetch"));
+    let token = challenge.token.expect("token issued");
+
+    // 令牌绑定资源，且校验失败后即失效
+    assert!(ConfirmationService::verify(action, "git", &input(Some(token.clone()), None)).is_err());
+    assert!(ConfirmationService::verify(
+        action,
+        "fetch",
+        &input(Some(token), Some("mcp-server-fetch"))
+    )
+    .is_err());
+
+    let token = ConfirmationService::request(action, "fetch", "mcp-server-fetch")
+        .expect("request confirmation")
+        .token
+        .expect("token issued");
+    assert!(
+        ConfirmationService::verify(action, "fetch", &input(Some(token.clone()), Some("fetch")))
+            .is_err(),
+        "wrong name must be rejected"
+    );
+
+    let token = ConfirmationService::request(action, "fetch", "mcp-server-fetch")
+        .expect("request confirmation")
+        .token
+        .expect("token issued");
+    ConfirmationService::verify(
+        action,
+        "fetch",
+        &input(Some(token.clone()), Some(" mcp-server-fetch ")),
+    )
+    .expect("matching name accepted");
+    assert!(
+        ConfirmationService::verify(
+            action,
+            "fetch",
+            &input(Some(token), Some("mcp-server-fetch"))
+        )
+        .is_err(),
+        "tokens are single use"
+    );
+
+    ConfirmationService::verify(
+        action,
+        "fetch",
+        &ConfirmationInput::new(None, None, Some(true)),
+    )
+    .expect("explicit force bypasses confirmation");
+}
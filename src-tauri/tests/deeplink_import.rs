@@ -1,6 +1,10 @@
 use std::sync::Arc;
 
-use cli_hub_lib::{import_provider_from_deeplink, parse_deeplink_url, AppState, Database};
+use base64::prelude::*;
+use cli_hub_lib::{
+    get_claude_mcp_path, import_bundle_from_deeplink, import_provider_from_deeplink,
+    parse_deeplink_url, AppState, Database,
+};
 
 #[path = "support.rs"]
 mod support;
@@ -17,7 +21,7 @@ fn deeplink_import_claude_provider_persists_to_db() {
 
     let db = Arc::new(Database::memory().expect("create memory db"));
 
-    let state = AppState { db: db.clone() };
+    let state = AppState::new(db.clone());
 
     let provider_id = import_provider_from_deeplink(&state, request.clone())
         .expect("import provider from deeplink");
@@ -54,7 +58,7 @@ fn deeplink_import_codex_provider_builds_auth_and_config() {
 
     let db = Arc::new(Database::memory().expect("create memory db"));
 
-    let state = AppState { db: db.clone() };
+    let state = AppState::new(db.clone());
 
     let provider_id = import_provider_from_deeplink(&state, request.clone())
         .expect("import provider from deeplink");
@@ -86,3 +90,101 @@ fn deeplink_import_codex_provider_builds_auth_and_config() {
         "config.toml content should contain model setting"
     );
 }
+
+fn bundle_url(manifest: &str) -> String {
+    format!(
+        "clihub://v1/import?resource=bundle&config={}",
+        BASE64_URL_SAFE_NO_PAD.encode(manifest)
+    )
+}
+
+#[test]
+fn deeplink_import_bundle_creates_all_resources() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    let manifest = r#"{
+        "provider": {"app": "claude", "name": "Bundle Vendor", "endpoint": "https://api.vendor.example", "apiKey": "sk-bundle"},
+        "mcp": {"apps": "claude", "mcpServers": {"vendor-docs": {"type": "stdio", "command": "npx"}}},
+        "prompt": {"app": "claude", "name": "Vendor Rules", "content": "Be concise", "enabled": true}
+    }"#;
+    let request = parse_deeplink_url(&bundle_url(manifest)).expect("parse bundle url");
+
+    let db = Arc::new(Database::memory().expect("create memory db"));
+    let state = AppState::new(db.clone());
+
+    let result = import_bundle_from_deeplink(&state, request).expect("import bundle");
+
+    let provider_id = result.provider_id.expect("provider imported");
+    let providers = db.get_all_providers("claude").expect("get providers");
+    assert!(providers.contains_key(&provider_id));
+
+    assert_eq!(result.mcp_ids, vec!["vendor-docs".to_string()]);
+    let servers = db.get_all_mcp_servers().expect("get mcp servers");
+    assert!(servers["vendor-docs"].apps.claude);
+
+    let prompt_id = result.prompt_id.expect("prompt imported");
+    let prompts = db.get_prompts("claude").expect("get prompts");
+    assert!(prompts[&prompt_id].enabled);
+}
+
+#[test]
+fn deeplink_import_bundle_rolls_back_on_failure() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    // 非对象的 MCP 定义会在写入 live 配置时失败，整个 bundle 应被回滚
+    let manifest = r#"{
+        "provider": {"app": "claude", "name": "Bundle Vendor", "endpoint": "https://api.vendor.example", "apiKey": "sk-bundle"},
+        "mcp": {"apps": "claude", "mcpServers": {"broken": "not-an-object"}}
+    }"#;
+    let request = parse_deeplink_url(&bundle_url(manifest)).expect("parse bundle url");
+
+    let db = Arc::new(Database::memory().expect("create memory db"));
+    let state = AppState::new(db.clone());
+
+    let err = import_bundle_from_deeplink(&state, request).expect_err("bundle should fail");
+    assert!(err.to_string().contains("broken"));
+
+    assert!(db
+        .get_all_providers("claude")
+        .expect("get providers")
+        .is_empty());
+    assert!(db
+        .get_all_mcp_servers()
+        .expect("get mcp servers")
+        .is_empty());
+}
+
+#[test]
+fn deeplink_import_bundle_rollback_removes_live_mcp_entries() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    // vendor-docs 已写入 ~/.claude.json 后 broken 才失败，回滚需把它从 live 配置中移除
+    let manifest = r#"{
+        "mcp": {"apps": "claude", "mcpServers": {
+            "broken": "not-an-object",
+            "vendor-docs": {"type": "stdio", "command": "npx"}
+        }}
+    }"#;
+    let request = parse_deeplink_url(&bundle_url(manifest)).expect("parse bundle url");
+
+    let db = Arc::new(Database::memory().expect("create memory db"));
+    let state = AppState::new(db.clone());
+
+    import_bundle_from_deeplink(&state, request).expect_err("bundle should fail");
+
+    assert!(db
+        .get_all_mcp_servers()
+        .expect("get mcp servers")
+        .is_empty());
+    let live = std::fs::read_to_string(get_claude_mcp_path()).unwrap_or_default();
+    assert!(
+        !live.contains("vendor-docs"),
+        "rolled back server left in live config: {live}"
+    );
+}
@@ -0,0 +1,73 @@
+use serde_json::json;
+
+use cli_hub_lib::test_support::{fixture_mcp, fixture_prompt, fixture_provider, AppStateFixture};
+use cli_hub_lib::{
+    get_claude_settings_path, read_json_file, AppSettings, AppType, ConfirmAction,
+    ConfirmationLevel, ConfirmationPolicies, ConfirmationService, McpService, PromptService,
+    ProviderService,
+};
+
+#[path = "support.rs"]
+mod support;
+use support::{ensure_test_home, reset_test_fs, test_mutex};
+
+#[test]
+fn fixture_seeds_providers_mcp_and_prompts() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+    ensure_test_home();
+
+    let state = AppStateFixture::new()
+        .seed_current_provider(
+            AppType::Claude,
+            fixture_provider("p1", json!({ "env": { "ANTHROPIC_AUTH_TOKEN": "k1" } })),
+        )
+        .seed_provider(
+            AppType::Claude,
+            fixture_provider("p2", json!({ "env": { "ANTHROPIC_AUTH_TOKEN": "k2" } })),
+        )
+        .seed_mcp(fixture_mcp("fetch", &[AppType::Claude]))
+        .seed_prompt(AppType::Codex, fixture_prompt("rules", "be concise"))
+        .build();
+
+    let providers = ProviderService::list(&state, AppType::Claude).expect("list providers");
+    assert_eq!(providers.len(), 2);
+    assert_eq!(
+        ProviderService::current(&state, AppType::Claude).expect("current provider"),
+        "p1"
+    );
+
+    let servers = McpService::get_all_servers(&state).expect("list mcp servers");
+    assert!(servers["fetch"].apps.claude);
+
+    let prompts = PromptService::get_prompts(&state, AppType::Codex).expect("list prompts");
+    assert_eq!(prompts["rules"].content, "be concise");
+
+    // 夹具构建的状态可以直接驱动业务流程
+    ProviderService::switch(&state, AppType::Claude, "p2").expect("switch provider");
+    let live: serde_json::Value =
+        read_json_file(&get_claude_settings_path()).expect("read live settings");
+    assert_eq!(live["env"]["ANTHROPIC_AUTH_TOKEN"], "k2");
+}
+
+#[test]
+fn fixture_applies_settings_on_build() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+    ensure_test_home();
+
+    let _state = AppStateFixture::new()
+        .with_settings(AppSettings {
+            confirmation_policies: ConfirmationPolicies {
+                delete_provider: ConfirmationLevel::TypeName,
+                ..ConfirmationPolicies::default()
+            },
+            ..AppSettings::default()
+        })
+        .build();
+
+    assert_eq!(
+        ConfirmationService::level_for(ConfirmAction::DeleteProvider),
+        ConfirmationLevel::TypeName
+    );
+}
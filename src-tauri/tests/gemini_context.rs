@@ -0,0 +1,76 @@
+use std::fs;
+
+use cli_hub_lib::GeminiContextService;
+use serde_json::{json, Value};
+
+#[path = "support.rs"]
+mod support;
+use support::{create_test_state, ensure_test_home, reset_test_fs, test_mutex};
+
+#[test]
+fn enabling_context_file_writes_file_and_updates_settings() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+    let home = ensure_test_home();
+    let gemini_dir = home.join(".gemini");
+    let settings_path = gemini_dir.join("settings.json");
+
+    // 用户手动添加的文件名与其他设置项需保留
+    fs::create_dir_all(&gemini_dir).expect("create gemini dir");
+    fs::write(
+        &settings_path,
+        serde_json::to_string(&json!({
+            "context": { "fileName": ["GEMINI.md", "TEAM.md"] },
+            "security": { "auth": { "selectedType": "oauth-personal" } }
+        }))
+        .unwrap(),
+    )
+    .expect("seed settings.json");
+
+    let state = create_test_state().expect("create test state");
+    GeminiContextService::upsert(&state, "CONVENTIONS.md", "# Conventions".to_string())
+        .expect("create context file");
+    assert!(!gemini_dir.join("CONVENTIONS.md").exists());
+
+    GeminiContextService::set_enabled(&state, "CONVENTIONS.md", true).expect("enable");
+    assert_eq!(
+        fs::read_to_string(gemini_dir.join("CONVENTIONS.md")).unwrap(),
+        "# Conventions"
+    );
+
+    let settings: Value =
+        serde_json::from_str(&fs::read_to_string(&settings_path).unwrap()).unwrap();
+    assert_eq!(
+        settings["context"]["fileName"],
+        json!(["GEMINI.md", "TEAM.md", "CONVENTIONS.md"])
+    );
+    assert_eq!(
+        settings["security"]["auth"]["selectedType"],
+        "oauth-personal"
+    );
+
+    assert!(
+        GeminiContextService::delete(&state, "CONVENTIONS.md").is_err(),
+        "enabled files cannot be deleted"
+    );
+
+    // 停用时回填磁盘上的修改并移除文件
+    fs::write(gemini_dir.join("CONVENTIONS.md"), "# Edited").unwrap();
+    GeminiContextService::set_enabled(&state, "CONVENTIONS.md", false).expect("disable");
+    assert!(!gemini_dir.join("CONVENTIONS.md").exists());
+
+    let files = GeminiContextService::list(&state).expect("list");
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].content, "# Edited");
+    assert!(!files[0].enabled);
+
+    let settings: Value =
+        serde_json::from_str(&fs::read_to_string(&settings_path).unwrap()).unwrap();
+    assert_eq!(
+        settings["context"]["fileName"],
+        json!(["GEMINI.md", "TEAM.md"])
+    );
+
+    GeminiContextService::delete(&state, "CONVENTIONS.md").expect("delete disabled file");
+    assert!(GeminiContextService::list(&state).unwrap().is_empty());
+}
@@ -5,7 +5,7 @@ use serde_json::json;
 
 use cli_hub_lib::{
     get_claude_mcp_path, get_claude_settings_path, import_default_config_test_hook, AppError,
-    AppType, McpApps, McpServer, McpService, MultiAppConfig,
+    AppType, McpApps, McpServer, McpService, McpSortUpdate, MultiAppConfig,
 };
 
 #[path = "support.rs"]
@@ -245,3 +245,349 @@ fn set_mcp_enabled_for_codex_writes_live_config() {
         "codex config should include the enabled server definition"
     );
 }
+
+#[test]
+fn duplicate_mcp_server_copies_spec_and_app_flags() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+
+    let mut config = MultiAppConfig::default();
+    config.mcp.servers = Some(HashMap::new());
+    config.mcp.servers.as_mut().unwrap().insert(
+        "github".into(),
+        McpServer {
+            id: "github".to_string(),
+            name: "github".to_string(),
+            server: json!({
+                "type": "stdio",
+                "command": "npx",
+                "args": ["-y", "@modelcontextprotocol/server-github"],
+                "env": { "GITHUB_PERSONAL_ACCESS_TOKEN": "org-a" }
+            }),
+            apps: McpApps {
+                claude: true,
+                codex: false,
+                gemini: false,
+            },
+            description: None,
+            homepage: None,
+            docs: None,
+            tags: vec!["git".to_string()],
+        },
+    );
+
+    let state = create_test_state_with_config(&config).expect("create test state");
+
+    let copy = McpService::duplicate_server(&state, "github", "github-org-b")
+        .expect("duplicate should succeed");
+    assert_eq!(copy.name, "github-org-b");
+
+    let servers = state.db.get_all_mcp_servers().expect("get all mcp servers");
+    let original = servers.get("github").expect("original kept");
+    let cloned = servers.get("github-org-b").expect("clone saved");
+    assert_eq!(cloned.server, original.server);
+    assert_eq!(cloned.apps, original.apps);
+    assert_eq!(cloned.tags, original.tags);
+
+    let claude_json = fs::read_to_string(get_claude_mcp_path()).expect("read ~/.claude.json");
+    assert!(
+        claude_json.contains("github-org-b"),
+        "clone should be synced to apps enabled on the original"
+    );
+
+    assert!(McpService::duplicate_server(&state, "github", "github").is_err());
+    assert!(McpService::duplicate_server(&state, "missing", "other").is_err());
+    assert!(McpService::duplicate_server(&state, "github", "  ").is_err());
+}
+
+#[test]
+fn mcp_env_vars_are_edited_in_place_and_masked() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+
+    let mut config = MultiAppConfig::default();
+    config.mcp.servers = Some(HashMap::new());
+    config.mcp.servers.as_mut().unwrap().insert(
+        "github".into(),
+        McpServer {
+            id: "github".to_string(),
+            name: "github".to_string(),
+            server: json!({
+                "type": "stdio",
+                "command": "npx",
+                "env": { "GITHUB_PERSONAL_ACCESS_TOKEN": "ghp_1234567890abcdef" }
+            }),
+            apps: McpApps {
+                claude: true,
+                codex: false,
+                gemini: false,
+            },
+            description: None,
+            homepage: None,
+            docs: None,
+            tags: Vec::new(),
+        },
+    );
+
+    let state = create_test_state_with_config(&config).expect("create test state");
+
+    let updated =
+        McpService::set_env_var(&state, "github", "GITHUB_HOST", "github.example.com")
+            .expect("set env var");
+    assert_eq!(updated.server["command"], "npx");
+    assert_eq!(updated.server["env"]["GITHUB_HOST"], "github.example.com");
+
+    let masked = McpService::masked(&updated);
+    assert_eq!(
+        masked.server["env"]["GITHUB_PERSONAL_ACCESS_TOKEN"],
+        "ghp_…cdef"
+    );
+
+    // 回传遮蔽值不会覆盖真实密钥
+    McpService::upsert_server(&state, masked).expect("upsert masked server");
+    let stored = state.db.get_all_mcp_servers().expect("get all mcp servers");
+    assert_eq!(
+        stored["github"].server["env"]["GITHUB_PERSONAL_ACCESS_TOKEN"],
+        "ghp_1234567890abcdef"
+    );
+
+    McpService::remove_env_var(&state, "github", "GITHUB_HOST").expect("remove env var");
+    let removed = McpService::remove_env_var(&state, "github", "GITHUB_PERSONAL_ACCESS_TOKEN")
+        .expect("remove last env var");
+    assert!(removed.server.get("env").is_none());
+
+    assert!(McpService::set_env_var(&state, "github", "BAD KEY", "x").is_err());
+    assert!(McpService::set_env_var(&state, "missing", "KEY", "x").is_err());
+}
+
+#[test]
+fn import_mcp_quarantines_invalid_entries_and_retries_after_fix() {
+    use support::create_test_state;
+
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    let claude_json = json!({
+        "mcpServers": {
+            "echo": { "type": "stdio", "command": "echo" },
+            "broken": { "type": "http" }
+        }
+    });
+    fs::write(
+        get_claude_mcp_path(),
+        serde_json::to_string_pretty(&claude_json).expect("serialize claude mcp"),
+    )
+    .expect("seed ~/.claude.json");
+
+    let state = create_test_state().expect("create test state");
+    McpService::import_from_claude(&state).expect("import mcp from claude succeeds");
+
+    let quarantined = McpService::list_quarantined(&state).expect("list quarantined");
+    assert_eq!(quarantined.len(), 1, "invalid entry should be quarantined");
+    let entry = &quarantined[0];
+    assert_eq!(entry.id, "claude:broken");
+    assert!(entry.raw.contains("\"http\""), "raw content should be kept");
+    assert!(entry.error.contains("url"), "unexpected error: {}", entry.error);
+
+    // 未修正时重试仍失败，条目保留在隔离区
+    assert!(McpService::retry_quarantined(&state, &entry.id, None).is_err());
+    assert_eq!(McpService::list_quarantined(&state).unwrap().len(), 1);
+
+    let fixed = r#"{ "type": "http", "url": "https://mcp.example.com" }"#;
+    let server = McpService::retry_quarantined(&state, &entry.id, Some(fixed.to_string()))
+        .expect("retry with corrected content succeeds");
+    assert_eq!(server.id, "broken");
+    assert!(server.apps.claude);
+
+    let servers = state.db.get_all_mcp_servers().expect("get all mcp servers");
+    assert!(servers.contains_key("echo") && servers.contains_key("broken"));
+    assert!(McpService::list_quarantined(&state).unwrap().is_empty());
+}
+
+#[test]
+fn sync_all_enabled_only_rewrites_changed_codex_entries() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    let codex_dir = home.join(".codex");
+    fs::create_dir_all(&codex_dir).expect("create codex dir");
+    let original = "model = \"gpt-5\"\n\n[mcp_servers.echo]\n# keep this note\ncommand = \"echo\"\ntype = \"stdio\"\n";
+    fs::write(codex_dir.join("config.toml"), original).expect("seed config.toml");
+
+    let server = |id: &str, command: &str| McpServer {
+        id: id.to_string(),
+        name: id.to_string(),
+        server: json!({ "type": "stdio", "command": command }),
+        apps: McpApps {
+            claude: false,
+            codex: true,
+            gemini: false,
+        },
+        description: None,
+        homepage: None,
+        docs: None,
+        tags: Vec::new(),
+    };
+    let mut config = MultiAppConfig::default();
+    config.ensure_app(&AppType::Codex);
+    config.mcp.servers = Some(HashMap::from([("echo".to_string(), server("echo", "echo"))]));
+    let state = create_test_state_with_config(&config).expect("create test state");
+
+    let toml_path = cli_hub_lib::get_codex_config_path();
+    McpService::sync_all_enabled(&state).expect("sync unchanged servers");
+    assert_eq!(
+        fs::read_to_string(&toml_path).expect("read codex config"),
+        original,
+        "entries already in sync should not be rewritten"
+    );
+
+    McpService::upsert_server(&state, server("fetch", "uvx")).expect("add second server");
+    let text = fs::read_to_string(&toml_path).expect("read codex config");
+    assert!(text.contains("# keep this note"), "untouched entry keeps comments");
+    assert!(text.contains("[mcp_servers.fetch]"));
+
+    let mut disabled = server("fetch", "uvx");
+    disabled.apps.codex = false;
+    McpService::upsert_server(&state, disabled).expect("disable for codex");
+    let text = fs::read_to_string(&toml_path).expect("read codex config");
+    assert!(
+        !text.contains("[mcp_servers.fetch]"),
+        "disabling an app removes the entry from its live config"
+    );
+    assert!(text.contains("# keep this note"));
+}
+
+#[test]
+fn codex_mcp_sync_keeps_restorable_config_backups() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    let codex_dir = home.join(".codex");
+    fs::create_dir_all(&codex_dir).expect("create codex dir");
+    let original = "# hand-maintained\nmodel = \"gpt-5\"\n";
+    fs::write(codex_dir.join("config.toml"), original).expect("seed config.toml");
+
+    let mut config = MultiAppConfig::default();
+    config.ensure_app(&AppType::Codex);
+    config.mcp.servers = Some(HashMap::from([(
+        "echo".to_string(),
+        McpServer {
+            id: "echo".to_string(),
+            name: "echo".to_string(),
+            server: json!({ "type": "stdio", "command": "echo" }),
+            apps: McpApps::default(),
+            description: None,
+            homepage: None,
+            docs: None,
+            tags: Vec::new(),
+        },
+    )]));
+    let state = create_test_state_with_config(&config).expect("create test state");
+
+    McpService::toggle_app(&state, "echo", AppType::Codex, true).expect("enable for codex");
+    let toml_path = cli_hub_lib::get_codex_config_path();
+    assert!(fs::read_to_string(&toml_path).unwrap().contains("[mcp_servers.echo]"));
+
+    let backups = cli_hub_lib::list_codex_config_backups().expect("list backups");
+    assert_eq!(backups.len(), 1, "one backup taken before the write");
+    assert_eq!(fs::read_to_string(&backups[0].path).unwrap(), original);
+
+    cli_hub_lib::restore_codex_config_backup(&backups[0].id).expect("restore backup");
+    assert_eq!(fs::read_to_string(&toml_path).unwrap(), original);
+    assert_eq!(
+        cli_hub_lib::list_codex_config_backups().unwrap().len(),
+        2,
+        "the replaced file is backed up before restoring"
+    );
+    assert!(cli_hub_lib::restore_codex_config_backup("../config").is_err());
+}
+
+#[test]
+fn mcp_sort_order_controls_live_config_entry_order() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    let codex_dir = home.join(".codex");
+    fs::create_dir_all(&codex_dir).expect("create codex dir");
+    fs::write(
+        codex_dir.join("config.toml"),
+        "model = \"gpt-5\"\n\n[mcp_servers.manual]\ncommand = \"hand-added\"\n",
+    )
+    .expect("seed config.toml");
+
+    let server = |id: &str| McpServer {
+        id: id.to_string(),
+        name: id.to_string(),
+        server: json!({ "type": "stdio", "command": id, "env": { "KEY": "v" } }),
+        apps: McpApps {
+            claude: true,
+            codex: true,
+            gemini: false,
+        },
+        description: None,
+        homepage: None,
+        docs: None,
+        tags: Vec::new(),
+    };
+    let mut config = MultiAppConfig::default();
+    config.ensure_app(&AppType::Claude);
+    config.ensure_app(&AppType::Codex);
+    config.mcp.servers = Some(
+        ["alpha", "beta", "gamma"]
+            .into_iter()
+            .map(|id| (id.to_string(), server(id)))
+            .collect(),
+    );
+    let state = create_test_state_with_config(&config).expect("create test state");
+
+    let updates = ["gamma", "alpha", "beta"]
+        .into_iter()
+        .enumerate()
+        .map(|(sort_index, id)| McpSortUpdate {
+            id: id.to_string(),
+            sort_index,
+        })
+        .collect();
+    McpService::update_sort_order(&state, updates).expect("update sort order");
+
+    let ids: Vec<String> = state
+        .db
+        .get_all_mcp_servers()
+        .expect("get all mcp servers")
+        .into_keys()
+        .collect();
+    assert_eq!(ids, ["gamma", "alpha", "beta"]);
+
+    // Editing a server must not reset its position
+    McpService::upsert_server(&state, server("gamma")).expect("re-save server");
+    let first = state.db.get_all_mcp_servers().unwrap().into_keys().next();
+    assert_eq!(first.as_deref(), Some("gamma"));
+
+    let claude: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(get_claude_mcp_path()).expect("read ~/.claude.json"),
+    )
+    .expect("parse ~/.claude.json");
+    let claude_ids: Vec<&String> = claude["mcpServers"]
+        .as_object()
+        .expect("mcpServers object")
+        .keys()
+        .collect();
+    assert_eq!(claude_ids, ["gamma", "alpha", "beta"]);
+
+    let text =
+        fs::read_to_string(cli_hub_lib::get_codex_config_path()).expect("read codex config");
+    let position = |header: &str| {
+        text.find(header)
+            .unwrap_or_else(|| panic!("{header} missing from:\n{text}"))
+    };
+    assert!(position("[mcp_servers.gamma]") < position("[mcp_servers.alpha]"));
+    assert!(position("[mcp_servers.alpha]") < position("[mcp_servers.beta]"));
+    assert!(
+        position("[mcp_servers.beta.env]") < position("[mcp_servers.manual]"),
+        "nested tables stay with their server and hand-added entries follow the managed ones"
+    );
+}
@@ -0,0 +1,80 @@
+use cli_hub_lib::{get_claude_settings_path, AppType, CsvColumnMapping, ProviderCsvImportService};
+
+#[path = "support.rs"]
+mod support;
+use support::{create_test_state, reset_test_fs, test_mutex};
+
+fn mapping() -> CsvColumnMapping {
+    CsvColumnMapping {
+        name: "Name".to_string(),
+        endpoint: "Endpoint".to_string(),
+        api_key: "Key".to_string(),
+        app: Some("App".to_string()),
+        default_app: Some("claude".to_string()),
+        homepage: None,
+        model: None,
+        notes: Some("Notes".to_string()),
+    }
+}
+
+#[test]
+fn csv_import_adds_valid_rows_and_reports_row_errors() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+
+    let state = create_test_state().expect("create test state");
+    let csv = "\
+Name,Endpoint,Key,App,Notes
+Relay A,https://relay-a.example.com,sk-aaaa-1111,,\"team, shared\"
+Relay B,https://relay-b.example.com,sk-bbbb-2222,codex,
+relay a,https://relay-a2.example.com,sk-cccc-3333,claude,
+Relay C,not-a-url,sk-dddd-4444,gemini,
+";
+
+    let preview = ProviderCsvImportService::preview(&state, csv, &mapping()).expect("preview");
+    assert_eq!(preview.len(), 4);
+    assert!(preview[0].error.is_none());
+    assert!(!preview[0].api_key_masked.contains("aaaa-1111"));
+    assert!(
+        preview[2].error.is_some(),
+        "duplicate name within the sheet"
+    );
+    assert!(preview[3].error.is_some(), "invalid endpoint");
+    assert!(
+        state
+            .db
+            .get_all_providers("claude")
+            .expect("providers")
+            .is_empty(),
+        "preview must not write"
+    );
+
+    let result = ProviderCsvImportService::import(&state, csv, &mapping()).expect("import");
+    assert_eq!(result.imported.len(), 2);
+    let failed_rows: Vec<usize> = result.failed.iter().map(|f| f.row).collect();
+    assert_eq!(failed_rows, vec![4, 5]);
+
+    let claude = state
+        .db
+        .get_all_providers("claude")
+        .expect("claude providers");
+    let relay_a = claude.values().next().expect("relay a imported");
+    assert_eq!(relay_a.name, "Relay A");
+    assert_eq!(relay_a.notes.as_deref(), Some("team, shared"));
+    assert_eq!(
+        relay_a.settings_config["env"]["ANTHROPIC_AUTH_TOKEN"],
+        "sk-aaaa-1111"
+    );
+
+    // 首个供应商自动成为当前供应商并写入 live 配置
+    let current = state
+        .db
+        .get_current_provider(AppType::Claude.as_str())
+        .expect("current provider");
+    assert_eq!(current.as_deref(), Some(relay_a.id.as_str()));
+    assert!(get_claude_settings_path().exists());
+
+    // 再次导入时已存在的名称全部被跳过
+    let again = ProviderCsvImportService::import(&state, csv, &mapping()).expect("reimport");
+    assert!(again.imported.is_empty());
+}
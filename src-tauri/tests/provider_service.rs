@@ -2,7 +2,7 @@ use serde_json::json;
 
 use cli_hub_lib::{
     get_claude_settings_path, read_json_file, write_codex_live_atomic, AppError, AppType,
-    MultiAppConfig, Provider, ProviderMeta, ProviderService,
+    MultiAppConfig, Provider, ProviderMeta, ProviderService, SyncScope, SyncScopeMode,
 };
 
 #[path = "support.rs"]
@@ -397,6 +397,64 @@ fn provider_service_switch_claude_updates_live_and_state() {
     );
 }
 
+#[test]
+fn provider_service_switch_records_usage_stats() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    let mut config = MultiAppConfig::default();
+    {
+        let manager = config
+            .get_manager_mut(&AppType::Claude)
+            .expect("claude manager");
+        for id in ["a", "b"] {
+            manager.providers.insert(
+                id.to_string(),
+                Provider::with_id(
+                    id.to_string(),
+                    id.to_string(),
+                    json!({ "env": { "ANTHROPIC_API_KEY": format!("{id}-key") } }),
+                    None,
+                ),
+            );
+        }
+        manager.current = "a".to_string();
+    }
+    let state = create_test_state_with_config(&config).expect("create test state");
+
+    for id in ["b", "a", "b"] {
+        ProviderService::switch(&state, AppType::Claude, id).expect("switch should succeed");
+    }
+
+    let providers = state
+        .db
+        .get_all_providers(AppType::Claude.as_str())
+        .expect("get all providers");
+    let b = providers.get("b").expect("provider b");
+    assert_eq!(b.switch_count, 2);
+    assert!(b.last_switched_at.is_some());
+    assert_eq!(providers.get("a").expect("provider a").switch_count, 1);
+
+    // 编辑供应商不应覆盖使用统计
+    let mut edited = b.clone();
+    edited.name = "renamed".to_string();
+    edited.switch_count = 0;
+    edited.last_switched_at = None;
+    state
+        .db
+        .save_provider(AppType::Claude.as_str(), &edited)
+        .expect("save provider");
+    let reloaded = state
+        .db
+        .get_all_providers(AppType::Claude.as_str())
+        .expect("get all providers");
+    let b = reloaded.get("b").expect("provider b");
+    assert_eq!(b.name, "renamed");
+    assert_eq!(b.switch_count, 2);
+    assert!(b.last_switched_at.is_some());
+}
+
 #[test]
 fn provider_service_switch_missing_provider_returns_error() {
     let _guard = test_mutex().lock().expect("acquire test mutex");
@@ -617,3 +675,69 @@ fn provider_service_delete_current_provider_returns_error() {
         other => panic!("expected Config/Message error, got {other:?}"),
     }
 }
+
+#[test]
+fn provider_service_switch_claude_respects_credentials_sync_scope() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    let settings_path = get_claude_settings_path();
+    std::fs::create_dir_all(settings_path.parent().unwrap()).expect("create claude dir");
+    std::fs::write(
+        &settings_path,
+        serde_json::to_string_pretty(&json!({
+            "env": {
+                "ANTHROPIC_API_KEY": "old-key",
+                "ANTHROPIC_MODEL": "user-picked-model"
+            },
+            "permissions": { "allow": ["Bash"] }
+        }))
+        .unwrap(),
+    )
+    .expect("seed claude settings");
+
+    let mut config = MultiAppConfig::default();
+    {
+        let manager = config
+            .get_manager_mut(&AppType::Claude)
+            .expect("claude manager");
+        let mut provider = Provider::with_id(
+            "scoped".to_string(),
+            "Scoped".to_string(),
+            json!({
+                "env": {
+                    "ANTHROPIC_AUTH_TOKEN": "new-token",
+                    "ANTHROPIC_BASE_URL": "https://relay.example",
+                    "ANTHROPIC_MODEL": "provider-model"
+                }
+            }),
+            None,
+        );
+        provider.meta = Some(ProviderMeta {
+            sync_scope: Some(SyncScope {
+                mode: SyncScopeMode::Credentials,
+                keys: Vec::new(),
+            }),
+            ..ProviderMeta::default()
+        });
+        manager.providers.insert("scoped".to_string(), provider);
+    }
+
+    let state = create_test_state_with_config(&config).expect("create test state");
+    ProviderService::switch(&state, AppType::Claude, "scoped").expect("switch should succeed");
+
+    let live: serde_json::Value = read_json_file(&settings_path).expect("read live settings");
+    assert_eq!(
+        live,
+        json!({
+            "env": {
+                "ANTHROPIC_AUTH_TOKEN": "new-token",
+                "ANTHROPIC_BASE_URL": "https://relay.example",
+                "ANTHROPIC_MODEL": "user-picked-model"
+            },
+            "permissions": { "allow": ["Bash"] }
+        }),
+        "credentials scope should only touch credential keys"
+    );
+}
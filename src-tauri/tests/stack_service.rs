@@ -0,0 +1,63 @@
+use std::fs;
+
+use cli_hub_lib::{get_claude_mcp_path, AppType, StackService};
+
+#[path = "support.rs"]
+mod support;
+use support::{create_test_state, ensure_test_home, reset_test_fs, test_mutex};
+
+#[test]
+fn apply_stack_adds_mcp_and_prompt_then_skips_on_repeat() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+    let home = ensure_test_home();
+
+    let state = create_test_state().expect("create test state");
+    let stack = StackService::resolve("Python dev").expect("resolve stack by name");
+
+    let report =
+        StackService::apply_mcp_and_prompt(&state, stack, &[AppType::Claude]).expect("apply stack");
+    assert!(report.failed.is_empty(), "no failures: {:?}", report.failed);
+    assert_eq!(report.added.len(), stack.mcp_servers.len() + 1);
+
+    let servers = state.db.get_all_mcp_servers().expect("load mcp servers");
+    let fetch = servers.get("fetch").expect("fetch server added");
+    assert!(fetch.apps.claude);
+    assert!(!fetch.apps.codex);
+
+    let claude_json = fs::read_to_string(get_claude_mcp_path()).expect("read ~/.claude.json");
+    assert!(claude_json.contains("mcp-server-fetch"));
+
+    let prompts = state.db.get_prompts("claude").expect("load prompts");
+    assert!(prompts.get("stack-python-dev").is_some_and(|p| p.enabled));
+    let claude_md =
+        fs::read_to_string(home.join(".claude").join("CLAUDE.md")).expect("read CLAUDE.md");
+    assert!(claude_md.contains("Python development"));
+
+    let again = StackService::apply_mcp_and_prompt(&state, stack, &[AppType::Claude])
+        .expect("apply stack again");
+    assert!(again.added.is_empty());
+    assert_eq!(again.skipped.len(), stack.mcp_servers.len() + 1);
+
+    // 扩展到新的应用时只补齐缺失部分
+    let codex =
+        StackService::apply_mcp_and_prompt(&state, stack, &[AppType::Claude, AppType::Codex])
+            .expect("apply stack to codex");
+    let mcp_added: Vec<_> = codex
+        .added
+        .iter()
+        .filter(|item| item.apps == vec!["codex".to_string()])
+        .collect();
+    assert_eq!(mcp_added.len(), stack.mcp_servers.len() + 1);
+}
+
+#[test]
+fn apply_stack_rejects_unknown_stack_and_empty_apps() {
+    assert!(StackService::resolve("no-such-stack").is_err());
+
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+    let state = create_test_state().expect("create test state");
+    let stack = StackService::resolve("web-dev").expect("resolve stack by id");
+    assert!(StackService::apply_mcp_and_prompt(&state, stack, &[]).is_err());
+}
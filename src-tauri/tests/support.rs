@@ -49,7 +49,7 @@ pub fn test_mutex() -> &'static Mutex<()> {
 /// 创建测试用的 AppState，包含一个空的数据库
 pub fn create_test_state() -> Result<AppState, Box<dyn std::error::Error>> {
     let db = Database::init()?;
-    Ok(AppState { db: Arc::new(db) })
+    Ok(AppState::new(Arc::new(db)))
 }
 
 /// 创建测试用的 AppState，并从 MultiAppConfig 迁移数据
@@ -58,5 +58,5 @@ pub fn create_test_state_with_config(
 ) -> Result<AppState, Box<dyn std::error::Error>> {
     let db = Database::init()?;
     db.migrate_from_json(config)?;
-    Ok(AppState { db: Arc::new(db) })
+    Ok(AppState::new(Arc::new(db)))
 }
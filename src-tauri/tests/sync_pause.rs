@@ -0,0 +1,92 @@
+use serde_json::json;
+
+use cli_hub_lib::{
+    get_claude_mcp_path, get_claude_settings_path, read_json_file, AppType, McpApps, McpServer,
+    McpService, MultiAppConfig, Provider, ProviderService, SyncPauseService,
+};
+
+#[path = "support.rs"]
+mod support;
+use support::{create_test_state_with_config, ensure_test_home, reset_test_fs, test_mutex};
+
+#[test]
+fn paused_management_defers_live_writes_until_resume() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    let mut config = MultiAppConfig::default();
+    {
+        let manager = config
+            .get_manager_mut(&AppType::Claude)
+            .expect("claude manager");
+        for id in ["a", "b"] {
+            manager.providers.insert(
+                id.to_string(),
+                Provider::with_id(
+                    id.to_string(),
+                    id.to_string(),
+                    json!({ "env": { "ANTHROPIC_API_KEY": format!("{id}-key") } }),
+                    None,
+                ),
+            );
+        }
+        manager.current = "a".to_string();
+    }
+    let state = create_test_state_with_config(&config).expect("create test state");
+    ProviderService::switch(&state, AppType::Claude, "a").expect("initial switch");
+
+    SyncPauseService::pause(&state).expect("pause management");
+
+    ProviderService::switch(&state, AppType::Claude, "b").expect("switch while paused");
+    let mut apps = McpApps::default();
+    apps.claude = true;
+    McpService::upsert_server(
+        &state,
+        McpServer {
+            id: "fetch".to_string(),
+            name: "fetch".to_string(),
+            server: json!({ "type": "stdio", "command": "echo" }),
+            apps,
+            description: None,
+            homepage: None,
+            docs: None,
+            tags: Vec::new(),
+        },
+    )
+    .expect("upsert mcp while paused");
+
+    // 数据库已更新，但 live 配置保持不变
+    assert_eq!(
+        state
+            .db
+            .get_current_provider(AppType::Claude.as_str())
+            .expect("current provider")
+            .as_deref(),
+        Some("b")
+    );
+    let live: serde_json::Value =
+        read_json_file(&get_claude_settings_path()).expect("read claude settings");
+    assert_eq!(live["env"]["ANTHROPIC_API_KEY"], "a-key");
+    assert!(
+        !get_claude_mcp_path().exists(),
+        "mcp sync should be deferred"
+    );
+
+    let preview = SyncPauseService::preview(&state).expect("preview resume");
+    let claude = preview
+        .iter()
+        .find(|p| p.app_type == "claude")
+        .expect("claude preview");
+    assert!(claude.provider_changed);
+    assert_eq!(claude.provider_id.as_deref(), Some("b"));
+    assert_eq!(claude.mcp_to_add, vec!["fetch".to_string()]);
+
+    SyncPauseService::resume(&state).expect("resume management");
+
+    let live: serde_json::Value =
+        read_json_file(&get_claude_settings_path()).expect("read claude settings");
+    assert_eq!(live["env"]["ANTHROPIC_API_KEY"], "b-key");
+    let mcp: serde_json::Value = read_json_file(&get_claude_mcp_path()).expect("read mcp");
+    assert!(mcp["mcpServers"].get("fetch").is_some());
+}
@@ -70,6 +70,7 @@ function App() {
   // 监听来自托盘菜单的切换事件；当托盘切换到当前 app 时刷新列表。
   useEffect(() => {
     let unsubscribe: (() => void) | undefined;
+    let unsubscribeFailover: (() => void) | undefined;
 
     const setupListener = async () => {
       try {
@@ -80,6 +81,14 @@ function App() {
             }
           },
         );
+        // 后台自动改用备用端点后，当前应用的供应商配置已变化
+        unsubscribeFailover = await providersApi.onEndpointFailover(
+          async (events) => {
+            if (events.some((event) => event.app === activeApp)) {
+              await refetch();
+            }
+          },
+        );
       } catch (error) {
         console.error("[App] Failed to subscribe provider switch event", error);
       }
@@ -88,6 +97,7 @@ function App() {
     setupListener();
     return () => {
       unsubscribe?.();
+      unsubscribeFailover?.();
     };
   }, [activeApp, refetch]);
 
@@ -168,9 +168,14 @@ export function DeepLinkImportDialog() {
             type: "all",
           });
           toast.success(t("deeplink.skillImportSuccess"), {
-            description: t("deeplink.skillImportSuccessDescription", {
-              repo: request.repo,
-            }),
+            description: result.installed
+              ? t("deeplink.skillInstallSuccessDescription", {
+                  repo: request.repo,
+                  directory: result.installed,
+                })
+              : t("deeplink.skillImportSuccessDescription", {
+                  repo: request.repo,
+                }),
           });
         }
       } else if (isMcpImportResult(result)) {
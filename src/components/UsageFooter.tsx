@@ -3,7 +3,7 @@ import { RefreshCw, AlertCircle, Clock } from "lucide-react";
 import { useTranslation } from "react-i18next";
 import { type AppId } from "@/lib/api";
 import { useUsageQuery } from "@/lib/query/queries";
-import { UsageData, Provider } from "@/types";
+import { UsageData, UsageBucket, Provider } from "@/types";
 
 interface UsageFooterProps {
   provider: Provider;
@@ -228,104 +228,150 @@ const UsagePlanItem: React.FC<{ data: UsageData }> = ({ data }) => {
     total,
     used,
     remaining,
-    unit,
+    currency,
+    buckets = [],
   } = data;
+  const unit = data.unit ?? currency;
 
   // 判断套餐是否失效（isValid 为 false 或未定义时视为有效）
   const isExpired = isValid === false;
 
   return (
-    <div className="flex items-center gap-3">
-      {/* 标题部分：25% */}
-      <div
-        className="text-xs text-gray-500 dark:text-gray-400 min-w-0"
-        style={{ width: "25%" }}
-      >
-        {planName ? (
-          <span
-            className={`font-medium truncate block ${isExpired ? "text-red-500 dark:text-red-400" : ""}`}
-            title={planName}
-          >
-            💰 {planName}
-          </span>
-        ) : (
-          <span className="opacity-50">—</span>
-        )}
-      </div>
-
-      {/* 扩展字段：30% */}
-      <div
-        className="text-xs text-gray-500 dark:text-gray-400 min-w-0 flex items-center gap-2"
-        style={{ width: "30%" }}
-      >
-        {extra && (
-          <span
-            className={`truncate ${isExpired ? "text-red-500 dark:text-red-400" : ""}`}
-            title={extra}
-          >
-            {extra}
-          </span>
-        )}
-        {isExpired && (
-          <span className="text-red-500 dark:text-red-400 font-medium text-[10px] px-1.5 py-0.5 bg-red-50 dark:bg-red-900/20 rounded flex-shrink-0">
-            {invalidMessage || t("usage.invalid")}
-          </span>
-        )}
-      </div>
-
-      {/* 用量信息：45% */}
-      <div
-        className="flex items-center justify-end gap-2 text-xs flex-shrink-0"
-        style={{ width: "45%" }}
-      >
-        {/* 总额度 */}
-        {total !== undefined && (
-          <>
-            <span className="text-gray-500 dark:text-gray-400">
-              {t("usage.total")}
-            </span>
-            <span className="tabular-nums text-gray-600 dark:text-gray-400">
-              {total === -1 ? "∞" : total.toFixed(2)}
-            </span>
-            <span className="text-gray-400 dark:text-gray-600">|</span>
-          </>
-        )}
-
-        {/* 已用额度 */}
-        {used !== undefined && (
-          <>
-            <span className="text-gray-500 dark:text-gray-400">
-              {t("usage.used")}
-            </span>
-            <span className="tabular-nums text-gray-600 dark:text-gray-400">
-              {used.toFixed(2)}
+    <div className="flex flex-col gap-1.5">
+      <div className="flex items-center gap-3">
+        {/* 标题部分：25% */}
+        <div
+          className="text-xs text-gray-500 dark:text-gray-400 min-w-0"
+          style={{ width: "25%" }}
+        >
+          {planName ? (
+            <span
+              className={`font-medium truncate block ${isExpired ? "text-red-500 dark:text-red-400" : ""}`}
+              title={planName}
+            >
+              💰 {planName}
             </span>
-            <span className="text-gray-400 dark:text-gray-600">|</span>
-          </>
-        )}
+          ) : (
+            <span className="opacity-50">—</span>
+          )}
+        </div>
 
-        {/* 剩余额度 - 突出显示 */}
-        {remaining !== undefined && (
-          <>
-            <span className="text-gray-500 dark:text-gray-400">
-              {t("usage.remaining")}
-            </span>
+        {/* 扩展字段：30% */}
+        <div
+          className="text-xs text-gray-500 dark:text-gray-400 min-w-0 flex items-center gap-2"
+          style={{ width: "30%" }}
+        >
+          {extra && (
             <span
-              className={`font-semibold tabular-nums ${
-                isExpired
-                  ? "text-red-500 dark:text-red-400"
-                  : remaining < (total || remaining) * 0.1
-                    ? "text-orange-500 dark:text-orange-400"
-                    : "text-green-600 dark:text-green-400"
-              }`}
+              className={`truncate ${isExpired ? "text-red-500 dark:text-red-400" : ""}`}
+              title={extra}
             >
-              {remaining.toFixed(2)}
+              {extra}
             </span>
-          </>
-        )}
+          )}
+          {isExpired && (
+            <span className="text-red-500 dark:text-red-400 font-medium text-[10px] px-1.5 py-0.5 bg-red-50 dark:bg-red-900/20 rounded flex-shrink-0">
+              {invalidMessage || t("usage.invalid")}
+            </span>
+          )}
+        </div>
 
-        {unit && (
-          <span className="text-gray-500 dark:text-gray-400">{unit}</span>
+        {/* 用量信息：45% */}
+        <div
+          className="flex items-center justify-end gap-2 text-xs flex-shrink-0"
+          style={{ width: "45%" }}
+        >
+          {/* 总额度 */}
+          {total !== undefined && (
+            <>
+              <span className="text-gray-500 dark:text-gray-400">
+                {t("usage.total")}
+              </span>
+              <span className="tabular-nums text-gray-600 dark:text-gray-400">
+                {total === -1 ? "∞" : total.toFixed(2)}
+              </span>
+              <span className="text-gray-400 dark:text-gray-600">|</span>
+            </>
+          )}
+
+          {/* 已用额度 */}
+          {used !== undefined && (
+            <>
+              <span className="text-gray-500 dark:text-gray-400">
+                {t("usage.used")}
+              </span>
+              <span className="tabular-nums text-gray-600 dark:text-gray-400">
+                {used.toFixed(2)}
+              </span>
+              <span className="text-gray-400 dark:text-gray-600">|</span>
+            </>
+          )}
+
+          {/* 剩余额度 - 突出显示 */}
+          {remaining !== undefined && (
+            <>
+              <span className="text-gray-500 dark:text-gray-400">
+                {t("usage.remaining")}
+              </span>
+              <span
+                className={`font-semibold tabular-nums ${
+                  isExpired
+                    ? "text-red-500 dark:text-red-400"
+                    : remaining < (total || remaining) * 0.1
+                      ? "text-orange-500 dark:text-orange-400"
+                      : "text-green-600 dark:text-green-400"
+                }`}
+              >
+                {remaining.toFixed(2)}
+              </span>
+            </>
+          )}
+
+          {unit && (
+            <span className="text-gray-500 dark:text-gray-400">{unit}</span>
+          )}
+        </div>
+      </div>
+
+      {/* 多个额度分桶时逐项展示（单个分桶与上方汇总一致） */}
+      {buckets.length > 1 &&
+        buckets.map((bucket, index) => (
+          <UsageBucketRow key={index} bucket={bucket} fallbackUnit={unit} />
+        ))}
+    </div>
+  );
+};
+
+const UsageBucketRow: React.FC<{
+  bucket: UsageBucket;
+  fallbackUnit?: string;
+}> = ({ bucket, fallbackUnit }) => {
+  const { t } = useTranslation();
+  const unit = bucket.unit ?? fallbackUnit;
+
+  return (
+    <div className="flex items-center justify-between gap-2 pl-4 text-[11px] text-gray-500 dark:text-gray-400">
+      <span className="truncate">{bucket.name || "—"}</span>
+      <div className="flex items-center gap-2 tabular-nums">
+        {bucket.used !== undefined && (
+          <span>
+            {bucket.used.toFixed(2)}
+            {bucket.total !== undefined &&
+              ` / ${bucket.total === -1 ? "∞" : bucket.total.toFixed(2)}`}
+          </span>
+        )}
+        {bucket.used === undefined && bucket.remaining !== undefined && (
+          <span>
+            {t("usage.remaining")} {bucket.remaining.toFixed(2)}
+          </span>
+        )}
+        {unit && <span>{unit}</span>}
+        {bucket.resetAt !== undefined && (
+          <span className="text-gray-400 dark:text-gray-500">
+            {t("usage.resetsAt", {
+              time: new Date(bucket.resetAt * 1000).toLocaleString(),
+            })}
+          </span>
         )}
       </div>
     </div>
@@ -50,7 +50,11 @@ export function SkillConfirmation({
 
       <div className="text-blue-600 dark:text-blue-400 text-sm bg-blue-50 dark:bg-blue-950/30 p-3 rounded border border-blue-200 dark:border-blue-800">
         <p>ℹ️ {t("deeplink.skill.hint")}</p>
-        <p className="mt-1">{t("deeplink.skill.hintDetail")}</p>
+        <p className="mt-1">
+          {request.install
+            ? t("deeplink.skill.installHint")
+            : t("deeplink.skill.hintDetail")}
+        </p>
       </div>
     </div>
   );
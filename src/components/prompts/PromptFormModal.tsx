@@ -84,6 +84,7 @@ const PromptFormModal: React.FC<PromptFormModalProps> = ({
         enabled: initialData?.enabled || false,
         createdAt: initialData?.createdAt || timestamp,
         updatedAt: timestamp,
+        version: initialData?.version,
       };
       await onSave(id, prompt);
       onClose();
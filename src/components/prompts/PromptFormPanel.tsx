@@ -76,6 +76,7 @@ const PromptFormPanel: React.FC<PromptFormPanelProps> = ({
         enabled: initialData?.enabled || false,
         createdAt: initialData?.createdAt || timestamp,
         updatedAt: timestamp,
+        version: initialData?.version,
       };
       await onSave(id, prompt);
       onClose();
@@ -174,6 +174,20 @@ export function ProviderCard({
               >
                 {t("provider.currentlyUsing")}
               </span>
+              {!isCurrent && provider.lastSwitchedAt && (
+                <span
+                  className="text-xs text-muted-foreground"
+                  title={t("provider.switchCount", {
+                    count: provider.switchCount ?? 0,
+                  })}
+                >
+                  {t("provider.lastUsed", {
+                    date: new Date(
+                      provider.lastSwitchedAt * 1000,
+                    ).toLocaleDateString(),
+                  })}
+                </span>
+              )}
             </div>
 
             {displayUrl && (
@@ -314,6 +314,8 @@ const EndpointSpeedTest: React.FC<EndpointSpeedTestProps> = ({
     try {
       const results = await vscodeApi.testApiEndpoints(urls, {
         timeoutSecs: ENDPOINT_TIMEOUT_SECS[appId],
+        appId,
+        providerId,
       });
 
       const resultMap = new Map(
@@ -363,7 +365,7 @@ const EndpointSpeedTest: React.FC<EndpointSpeedTestProps> = ({
     } finally {
       setIsTesting(false);
     }
-  }, [entries, autoSelect, appId, normalizedSelected, onChange, t]);
+  }, [entries, autoSelect, appId, providerId, normalizedSelected, onChange, t]);
 
   const handleSelect = useCallback(
     (url: string) => {
@@ -0,0 +1,163 @@
+import { useCallback, useEffect, useState } from "react";
+import { History, Loader2, RotateCcw, Trash2 } from "lucide-react";
+import { toast } from "sonner";
+import { useTranslation } from "react-i18next";
+import { Button } from "@/components/ui/button";
+import { ConfirmDialog } from "@/components/ConfirmDialog";
+import { settingsApi } from "@/lib/api";
+import type { DbBackupInfo } from "@/lib/api/settings";
+
+interface DbBackupListProps {
+  /** Changes whenever a new backup may exist (e.g. after an import) */
+  refreshKey: string;
+  onRestored?: () => void | Promise<void>;
+}
+
+type PendingAction = { kind: "restore" | "delete"; backup: DbBackupInfo };
+
+function formatSize(bytes: number): string {
+  if (bytes < 1024) return `${bytes} B`;
+  if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KB`;
+  return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
+}
+
+export function DbBackupList({ refreshKey, onRestored }: DbBackupListProps) {
+  const { t } = useTranslation();
+  const [backups, setBackups] = useState<DbBackupInfo[]>([]);
+  const [busyId, setBusyId] = useState<string | null>(null);
+  const [pending, setPending] = useState<PendingAction | null>(null);
+
+  const load = useCallback(async () => {
+    try {
+      setBackups(await settingsApi.listDbBackups());
+    } catch (error) {
+      console.error("[DbBackupList] Failed to list backups", error);
+    }
+  }, []);
+
+  useEffect(() => {
+    void load();
+  }, [load, refreshKey]);
+
+  const runPending = useCallback(async () => {
+    if (!pending) return;
+    const { kind, backup } = pending;
+    setPending(null);
+    setBusyId(backup.id);
+    try {
+      if (kind === "restore") {
+        const result = await settingsApi.restoreDbBackup(backup.id);
+        toast.success(
+          t("settings.dbBackups.restored", {
+            defaultValue: "已恢复备份，恢复前的数据已另存为 {{id}}",
+            id: result.backupId,
+          }),
+        );
+        await onRestored?.();
+      } else {
+        await settingsApi.deleteDbBackup(backup.id);
+        toast.success(
+          t("settings.dbBackups.deleted", { defaultValue: "备份已删除" }),
+        );
+      }
+    } catch (error) {
+      const message =
+        error instanceof Error ? error.message : String(error ?? "");
+      toast.error(message);
+    } finally {
+      setBusyId(null);
+      await load();
+    }
+  }, [load, onRestored, pending, t]);
+
+  return (
+    <div className="space-y-2">
+      <div className="flex items-center gap-2 text-sm font-medium text-foreground">
+        <History className="h-4 w-4" />
+        {t("settings.dbBackups.title", { defaultValue: "自动备份" })}
+      </div>
+      {backups.length === 0 ? (
+        <p className="text-xs text-muted-foreground">
+          {t("settings.dbBackups.empty", {
+            defaultValue: "导入 SQL 或恢复备份前会自动备份当前数据库",
+          })}
+        </p>
+      ) : (
+        <ul className="space-y-1.5">
+          {backups.map((backup) => (
+            <li
+              key={backup.id}
+              className="flex items-center gap-3 rounded-lg border border-white/10 px-3 py-2 text-xs"
+            >
+              <div className="min-w-0 flex-1">
+                <p className="font-medium text-foreground">
+                  {new Date(backup.createdAt * 1000).toLocaleString()}
+                </p>
+                <p className="truncate text-muted-foreground">
+                  {t(`settings.dbBackups.origin.${backup.origin}`)} ·{" "}
+                  {formatSize(backup.size)} ·{" "}
+                  <span className="font-mono">{backup.id}</span>
+                </p>
+              </div>
+              {busyId === backup.id ? (
+                <Loader2 className="h-4 w-4 animate-spin" />
+              ) : (
+                <>
+                  <Button
+                    type="button"
+                    size="sm"
+                    variant="outline"
+                    disabled={busyId !== null}
+                    onClick={() => setPending({ kind: "restore", backup })}
+                  >
+                    <RotateCcw className="mr-1 h-3.5 w-3.5" />
+                    {t("settings.dbBackups.restore", {
+                      defaultValue: "恢复",
+                    })}
+                  </Button>
+                  <Button
+                    type="button"
+                    size="sm"
+                    variant="ghost"
+                    disabled={busyId !== null}
+                    onClick={() => setPending({ kind: "delete", backup })}
+                    aria-label={t("common.delete")}
+                  >
+                    <Trash2 className="h-3.5 w-3.5" />
+                  </Button>
+                </>
+              )}
+            </li>
+          ))}
+        </ul>
+      )}
+
+      <ConfirmDialog
+        isOpen={pending !== null}
+        title={
+          pending?.kind === "restore"
+            ? t("settings.dbBackups.restoreTitle", {
+                defaultValue: "恢复数据库备份",
+              })
+            : t("settings.dbBackups.deleteTitle", {
+                defaultValue: "删除数据库备份",
+              })
+        }
+        message={
+          pending?.kind === "restore"
+            ? t("settings.dbBackups.restoreMessage", {
+                defaultValue:
+                  "当前数据将被 {{id}} 覆盖。恢复前会先自动备份当前数据库。",
+                id: pending.backup.id,
+              })
+            : t("settings.dbBackups.deleteMessage", {
+                defaultValue: "确定删除备份 {{id}}？此操作无法撤销。",
+                id: pending?.backup.id ?? "",
+              })
+        }
+        onConfirm={() => void runPending()}
+        onCancel={() => setPending(null)}
+      />
+    </div>
+  );
+}
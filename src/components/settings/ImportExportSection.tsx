@@ -10,6 +10,7 @@ import {
 import { Button } from "@/components/ui/button";
 import { useTranslation } from "react-i18next";
 import type { ImportStatus } from "@/hooks/useImportExport";
+import { DbBackupList } from "@/components/settings/DbBackupList";
 
 interface ImportExportSectionProps {
   status: ImportStatus;
@@ -21,6 +22,7 @@ interface ImportExportSectionProps {
   onImport: () => Promise<void>;
   onExport: () => Promise<void>;
   onClear: () => void;
+  onBackupRestored?: () => void | Promise<void>;
 }
 
 export function ImportExportSection({
@@ -33,6 +35,7 @@ export function ImportExportSection({
   onImport,
   onExport,
   onClear,
+  onBackupRestored,
 }: ImportExportSectionProps) {
   const { t } = useTranslation();
 
@@ -118,6 +121,8 @@ export function ImportExportSection({
           errorMessage={errorMessage}
           backupId={backupId}
         />
+
+        <DbBackupList refreshKey={status} onRestored={onBackupRestored} />
       </div>
     </section>
   );
@@ -215,6 +215,7 @@ export function SettingsPage({
                     onImport={importConfig}
                     onExport={exportConfig}
                     onClear={clearSelection}
+                    onBackupRestored={onImportSuccess}
                   />
                   <div className="pt-6 border-t border-gray-200 dark:border-white/10">
                     <Button
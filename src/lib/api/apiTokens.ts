@@ -0,0 +1,33 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export type ApiTokenScope = "readOnly" | "switchOnly" | "full";
+
+export interface ApiToken {
+  id: string;
+  name: string;
+  scope: ApiTokenScope;
+  prefix: string;
+  createdAt: number;
+  lastUsedAt?: number;
+  revokedAt?: number;
+}
+
+export interface CreatedApiToken {
+  token: ApiToken;
+  // 明文令牌，仅在创建时返回一次
+  secret: string;
+}
+
+export const apiTokensApi = {
+  async create(name: string, scope: ApiTokenScope): Promise<CreatedApiToken> {
+    return await invoke("create_api_token", { name, scope });
+  },
+
+  async list(): Promise<ApiToken[]> {
+    return await invoke("list_api_tokens");
+  },
+
+  async revoke(id: string): Promise<boolean> {
+    return await invoke("revoke_api_token", { id });
+  },
+};
@@ -1,5 +1,6 @@
 // 配置相关 API
 import { invoke } from "@tauri-apps/api/core";
+import type { ClaudeSettingsLayer } from "@/types";
 
 export type AppType = "claude" | "codex" | "gemini";
 
@@ -47,3 +48,250 @@ export async function setCommonConfigSnippet(
 ): Promise<void> {
   return invoke("set_common_config_snippet", { appType, snippet });
 }
+
+export type McpConvertDirection = "jsonToToml" | "tomlToJson";
+
+export interface ConversionError {
+  message: string;
+  line?: number;
+  column?: number;
+  path?: string;
+}
+
+export interface ConversionResult {
+  content?: string;
+  error?: ConversionError;
+}
+
+/**
+ * MCP 配置在 JSON（Claude/Gemini）与 TOML（Codex）之间转换
+ * @returns 转换结果；内容有误时 error 中包含行列位置
+ */
+export async function convertMcpConfig(
+  direction: McpConvertDirection,
+  content: string,
+): Promise<ConversionResult> {
+  return invoke<ConversionResult>("convert_mcp_config", {
+    direction,
+    content,
+  });
+}
+
+/**
+ * 将供应商配置转换为另一个应用的格式
+ */
+export async function convertProviderSettings(
+  appTypeFrom: AppType,
+  appTypeTo: AppType,
+  content: string,
+): Promise<ConversionResult> {
+  return invoke<ConversionResult>("convert_provider_settings", {
+    appTypeFrom,
+    appTypeTo,
+    content,
+  });
+}
+
+export interface CodexConfigBackup {
+  id: string;
+  path: string;
+  size: number;
+  createdAt: number;
+}
+
+/**
+ * 列出 MCP 同步前自动保存的 Codex config.toml 备份（最新的在前）
+ */
+export async function listCodexConfigBackups(): Promise<CodexConfigBackup[]> {
+  return invoke<CodexConfigBackup[]>("list_codex_config_backups");
+}
+
+/**
+ * 用指定备份恢复 Codex config.toml
+ */
+export async function restoreCodexConfigBackup(id: string): Promise<boolean> {
+  return invoke<boolean>("restore_codex_config_backup", { id });
+}
+
+export interface ClaudeLayerInfo {
+  layer: ClaudeSettingsLayer;
+  path: string;
+  exists: boolean;
+  // CLI Hub 当前写入的层级
+  target: boolean;
+  error?: string;
+}
+
+export interface ClaudeSettingOverride {
+  // 如 env.ANTHROPIC_BASE_URL
+  key: string;
+  value: unknown;
+  overriddenBy: ClaudeSettingsLayer;
+  effective: unknown;
+}
+
+export interface ClaudeEffectiveSettings {
+  layers: ClaudeLayerInfo[];
+  merged: Record<string, unknown>;
+  sources: Record<string, ClaudeSettingsLayer>;
+  overrides: ClaudeSettingOverride[];
+}
+
+/**
+ * 获取 Claude 各配置层合并后的实际配置，以及被更高优先级层覆盖的键
+ */
+export async function getClaudeEffectiveSettings(): Promise<ClaudeEffectiveSettings> {
+  return invoke<ClaudeEffectiveSettings>("get_claude_effective_settings");
+}
+
+export type ClaudeHookEvent =
+  | "PreToolUse"
+  | "PostToolUse"
+  | "UserPromptSubmit"
+  | "Notification"
+  | "Stop"
+  | "SubagentStop"
+  | "PreCompact"
+  | "SessionStart"
+  | "SessionEnd";
+
+export interface ClaudeHook {
+  event: ClaudeHookEvent;
+  // 正则表达式，为空或 "*" 匹配全部；仅部分事件支持
+  matcher?: string;
+  command: string;
+  // 超时秒数
+  timeout?: number;
+}
+
+/**
+ * 列出 Claude 钩子；不传 providerId 时为通用配置片段中的全局钩子
+ */
+export async function listClaudeHooks(
+  providerId?: string,
+): Promise<ClaudeHook[]> {
+  return invoke<ClaudeHook[]>("list_claude_hooks", { providerId });
+}
+
+/**
+ * 新增钩子并同步到 settings.json，返回该范围内的全部钩子
+ */
+export async function addClaudeHook(
+  hook: ClaudeHook,
+  providerId?: string,
+): Promise<ClaudeHook[]> {
+  return invoke<ClaudeHook[]>("add_claude_hook", { providerId, hook });
+}
+
+/**
+ * 修改 listClaudeHooks 返回列表中指定位置的钩子
+ */
+export async function updateClaudeHook(
+  index: number,
+  hook: ClaudeHook,
+  providerId?: string,
+): Promise<ClaudeHook[]> {
+  return invoke<ClaudeHook[]>("update_claude_hook", {
+    providerId,
+    index,
+    hook,
+  });
+}
+
+/**
+ * 删除 listClaudeHooks 返回列表中指定位置的钩子
+ */
+export async function removeClaudeHook(
+  index: number,
+  providerId?: string,
+): Promise<ClaudeHook[]> {
+  return invoke<ClaudeHook[]>("remove_claude_hook", { providerId, index });
+}
+
+export type ClaudePermissionList = "allow" | "deny" | "ask";
+
+export type ClaudePermissionMode =
+  | "default"
+  | "acceptEdits"
+  | "plan"
+  | "bypassPermissions";
+
+export interface ClaudePermissions {
+  // 规则格式为 Tool、Tool(参数) 或 mcp__server
+  allow: string[];
+  deny: string[];
+  ask: string[];
+  defaultMode?: ClaudePermissionMode;
+}
+
+export interface ClaudePermissionPreset {
+  id: string;
+  name: string;
+  description: string;
+  permissions: ClaudePermissions;
+}
+
+/**
+ * 获取 Claude 工具权限；不传 providerId 时为通用配置片段中的全局权限
+ */
+export async function getClaudePermissions(
+  providerId?: string,
+): Promise<ClaudePermissions> {
+  return invoke<ClaudePermissions>("get_claude_permissions", { providerId });
+}
+
+/**
+ * 整体保存工具权限并同步到 settings.json
+ */
+export async function setClaudePermissions(
+  permissions: ClaudePermissions,
+  providerId?: string,
+): Promise<ClaudePermissions> {
+  return invoke<ClaudePermissions>("set_claude_permissions", {
+    providerId,
+    permissions,
+  });
+}
+
+export async function addClaudePermissionRule(
+  list: ClaudePermissionList,
+  rule: string,
+  providerId?: string,
+): Promise<ClaudePermissions> {
+  return invoke<ClaudePermissions>("add_claude_permission_rule", {
+    providerId,
+    list,
+    rule,
+  });
+}
+
+export async function removeClaudePermissionRule(
+  list: ClaudePermissionList,
+  rule: string,
+  providerId?: string,
+): Promise<ClaudePermissions> {
+  return invoke<ClaudePermissions>("remove_claude_permission_rule", {
+    providerId,
+    list,
+    rule,
+  });
+}
+
+export async function listClaudePermissionPresets(): Promise<
+  ClaudePermissionPreset[]
+> {
+  return invoke<ClaudePermissionPreset[]>("list_claude_permission_presets");
+}
+
+/**
+ * 把预设（如只读模式）合并进现有规则
+ */
+export async function applyClaudePermissionPreset(
+  presetId: string,
+  providerId?: string,
+): Promise<ClaudePermissions> {
+  return invoke<ClaudePermissions>("apply_claude_permission_preset", {
+    providerId,
+    presetId,
+  });
+}
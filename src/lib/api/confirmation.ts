@@ -0,0 +1,39 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { ConfirmationLevel } from "@/types";
+
+export type ConfirmAction =
+  | "deleteProvider"
+  | "deleteMcpServer"
+  | "importSql"
+  | "restoreBackup";
+
+export interface ConfirmationChallenge {
+  action: ConfirmAction;
+  resource: string;
+  level: ConfirmationLevel;
+  token?: string;
+  // typeName 级别下需要用户输入的名称
+  expectedText?: string;
+  expiresAt: number;
+}
+
+// 随破坏性命令提交的确认信息
+export interface ConfirmationArgs {
+  confirmationToken?: string;
+  confirmationText?: string;
+  // 自动化场景显式跳过确认
+  force?: boolean;
+}
+
+export const confirmationApi = {
+  /**
+   * 申请确认令牌
+   * @param resource 供应商为 `app:id`，MCP 为服务器 ID，其余为文件路径或备份 ID
+   */
+  async request(
+    action: ConfirmAction,
+    resource: string,
+  ): Promise<ConfirmationChallenge> {
+    return await invoke("request_confirmation", { action, resource });
+  },
+};
@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import type { AppId } from "./types";
 
 export type ResourceType = "provider" | "prompt" | "mcp" | "skill";
 
@@ -22,6 +23,11 @@ export interface DeepLinkImportRequest {
   sonnetModel?: string;
   opusModel?: string;
 
+  // Provider extras (v1.1)
+  usageScript?: string;
+  endpoints?: string;
+  partnerPromotionKey?: string;
+
   // Prompt fields
   content?: string;
   description?: string;
@@ -34,11 +40,16 @@ export interface DeepLinkImportRequest {
   directory?: string;
   branch?: string;
   skillsPath?: string;
+  // Install `directory` right after registering the repo
+  install?: boolean;
 
   // Config file fields
   config?: string;
   configFormat?: string;
   configUrl?: string;
+
+  // Confirmation token issued by the backend when the link was received
+  token?: string;
 }
 
 export interface McpImportResult {
@@ -59,7 +70,26 @@ export type ImportResult =
       importedIds: string[];
       failed: Array<{ id: string; error: string }>;
     }
-  | { type: "skill"; key: string };
+  | { type: "skill"; key: string; installed?: string | null };
+
+// Progress of a skill installed by a deep link (`skill-install-progress` event)
+export interface SkillInstallProgress {
+  repo: string;
+  directory: string;
+  stage: "fetching" | "installing" | "done" | "failed";
+  error?: string;
+}
+
+// An `${ASK}` placeholder in a shared MCP link that the recipient must fill in
+export interface McpSecretPrompt {
+  serverId: string;
+  section: "env" | "headers";
+  key: string;
+}
+
+export interface McpSecretAnswer extends McpSecretPrompt {
+  value: string;
+}
 
 export const deeplinkApi = {
   /**
@@ -85,12 +115,58 @@ export const deeplinkApi = {
 
   /**
    * Import a resource from a deep link request (unified handler)
+   *
+   * The backend imports the quarantined request matching `request.token`;
+   * edits made to `request` here are ignored.
    * @param request The deep link import request
+   * @param answers The user's values for `${ASK}` placeholders (MCP only)
    * @returns Import result based on resource type
    */
   importFromDeeplink: async (
     request: DeepLinkImportRequest,
+    answers?: McpSecretAnswer[],
   ): Promise<ImportResult> => {
-    return invoke("import_from_deeplink_unified", { request });
+    return invoke("import_from_deeplink_unified", { request, answers });
+  },
+
+  /**
+   * Build a shareable MCP import link; credential-like env vars and headers
+   * are replaced with `${ASK}` placeholders
+   * @param ids MCP server IDs to include
+   * @param apps Apps the recipient should enable the servers for
+   */
+  generateMcpDeeplink: async (
+    ids: string[],
+    apps: AppId[],
+  ): Promise<string> => {
+    return invoke("generate_mcp_deeplink", { ids, apps });
+  },
+
+  /**
+   * List the placeholders in an MCP deep link that need the user's own values
+   */
+  getMcpDeeplinkSecrets: async (
+    request: DeepLinkImportRequest,
+  ): Promise<McpSecretPrompt[]> => {
+    return invoke("get_mcp_deeplink_secrets", { request });
+  },
+
+  /**
+   * Write the user's values over the placeholders for preview; to import,
+   * pass the same answers to `importFromDeeplink`
+   */
+  fillMcpDeeplinkSecrets: async (
+    request: DeepLinkImportRequest,
+    answers: McpSecretAnswer[],
+  ): Promise<DeepLinkImportRequest> => {
+    return invoke("fill_mcp_deeplink_secrets", { request, answers });
+  },
+
+  /**
+   * Reject a deep link that is waiting for confirmation
+   * @param token Confirmation token from the parsed request
+   */
+  dismissDeeplink: async (token: string): Promise<boolean> => {
+    return invoke("dismiss_deeplink_import", { token });
   },
 };
@@ -1,5 +1,6 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { EnvConflict, BackupInfo } from "@/types/env";
+import type { EnvConflict, BackupInfo, ShellFileDiff } from "@/types/env";
+import type { ConfirmationArgs } from "./confirmation";
 
 /**
  * 环境变量管理 API
@@ -31,8 +32,53 @@ export async function deleteEnvVars(
  * 从备份文件恢复环境变量
  * @param backupPath 备份文件路径
  */
-export async function restoreEnvBackup(backupPath: string): Promise<void> {
-  return invoke<void>("restore_env_backup", { backupPath });
+export async function restoreEnvBackup(
+  backupPath: string,
+  confirmation?: ConfirmationArgs,
+): Promise<void> {
+  return invoke<void>("restore_env_backup", { backupPath, ...confirmation });
+}
+
+/**
+ * 列出环境变量备份（按时间倒序）
+ */
+export async function listEnvBackups(): Promise<BackupInfo[]> {
+  return invoke<BackupInfo[]>("list_env_backups");
+}
+
+/**
+ * 从备份中仅恢复选中的环境变量
+ * @param backupId 备份 ID
+ * @param vars 要恢复的变量名，为空时恢复全部
+ */
+export async function restoreEnvVars(
+  backupId: string,
+  vars: string[],
+  confirmation?: ConfirmationArgs,
+): Promise<void> {
+  return invoke<void>("restore_env_vars", { backupId, vars, ...confirmation });
+}
+
+/**
+ * 预览删除环境变量对 shell 配置文件的修改
+ */
+export async function previewEnvVarsDeletion(
+  conflicts: EnvConflict[],
+): Promise<ShellFileDiff[]> {
+  return invoke<ShellFileDiff[]>("preview_env_vars_deletion", { conflicts });
+}
+
+/**
+ * 预览从备份恢复环境变量对 shell 配置文件的修改
+ */
+export async function previewEnvVarsRestore(
+  backupId: string,
+  vars: string[],
+): Promise<ShellFileDiff[]> {
+  return invoke<ShellFileDiff[]>("preview_env_vars_restore", {
+    backupId,
+    vars,
+  });
 }
 
 /**
@@ -1,10 +1,54 @@
 export type { AppId } from "./types";
+export { apiTokensApi } from "./apiTokens";
 export { providersApi } from "./providers";
 export { settingsApi } from "./settings";
+export { confirmationApi } from "./confirmation";
 export { mcpApi } from "./mcp";
-export { promptsApi } from "./prompts";
+export { migrationApi } from "./migration";
+export { promptsApi, isEncryptedPrompt } from "./prompts";
+export { quickActionsApi } from "./quickActions";
+export { sessionsApi } from "./sessions";
+export { stacksApi } from "./stacks";
 export { usageApi } from "./usage";
 export { vscodeApi } from "./vscode";
 export * as configApi from "./config";
-export type { ProviderSwitchEvent } from "./providers";
-export type { Prompt } from "./prompts";
+export type { ApiToken, ApiTokenScope, CreatedApiToken } from "./apiTokens";
+export type {
+  DebugProxyStatus,
+  PresetRefreshResult,
+  ProviderSwitchEvent,
+  ProviderTemplate,
+  ProxyLogEntry,
+  SecurityFinding,
+  SecuritySeverity,
+  TemplatePlaceholder,
+} from "./providers";
+export type {
+  LegacyConfigReport,
+  LegacyItem,
+  LegacyItemRef,
+  LegacyMigrationResult,
+} from "./migration";
+export type {
+  ConfirmAction,
+  ConfirmationArgs,
+  ConfirmationChallenge,
+} from "./confirmation";
+export type { McpSortUpdate } from "./mcp";
+export type {
+  Prompt,
+  PromptSummary,
+  GeminiContextFile,
+  CodexAgentsLevel,
+  CodexAgentsFile,
+  CodexEffectiveAgents,
+  PromptSchedule,
+  PromptScheduleEvent,
+  PromptsChangedEvent,
+} from "./prompts";
+export type {
+  LauncherAction,
+  LauncherManifest,
+  QuickAction,
+  QuickActionOutcome,
+} from "./quickActions";
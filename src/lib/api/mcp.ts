@@ -5,8 +5,19 @@ import type {
   McpServerSpec,
   McpServersMap,
   McpStatus,
+  McpBridge,
+  McpProfile,
+  McpTransport,
+  McpTransportConversion,
+  QuarantinedMcpEntry,
 } from "@/types";
 import type { AppId } from "./types";
+import type { ConfirmationArgs } from "./confirmation";
+
+export interface McpSortUpdate {
+  id: string;
+  sortIndex: number;
+}
 
 export const mcpApi = {
   async getStatus(): Promise<McpStatus> {
@@ -32,6 +43,19 @@ export const mcpApi = {
     return await invoke("validate_mcp_command", { cmd });
   },
 
+  async convertTransport(
+    spec: McpServerSpec,
+    target: McpTransport,
+    options?: { bridge?: McpBridge; port?: number },
+  ): Promise<McpTransportConversion> {
+    return await invoke("convert_mcp_transport", {
+      spec,
+      target,
+      bridge: options?.bridge,
+      port: options?.port,
+    });
+  },
+
   /**
    * @deprecated 使用 getAllServers() 代替（v3.7.0+）
    */
@@ -91,8 +115,31 @@ export const mcpApi = {
   /**
    * 获取所有 MCP 服务器（统一结构）
    */
-  async getAllServers(): Promise<McpServersMap> {
-    return await invoke("get_mcp_servers");
+  async getAllServers(masked = false): Promise<McpServersMap> {
+    return await invoke("get_mcp_servers", { masked });
+  },
+
+  /**
+   * 设置单个环境变量，返回的服务器 env 默认已遮蔽
+   */
+  async setEnvVar(
+    id: string,
+    key: string,
+    value: string,
+    reveal = false,
+  ): Promise<McpServer> {
+    return await invoke("set_mcp_env_var", { id, key, value, reveal });
+  },
+
+  /**
+   * 删除单个环境变量
+   */
+  async removeEnvVar(
+    id: string,
+    key: string,
+    reveal = false,
+  ): Promise<McpServer> {
+    return await invoke("remove_mcp_env_var", { id, key, reveal });
   },
 
   /**
@@ -105,8 +152,11 @@ export const mcpApi = {
   /**
    * 删除 MCP 服务器
    */
-  async deleteUnifiedServer(id: string): Promise<boolean> {
-    return await invoke("delete_mcp_server", { id });
+  async deleteUnifiedServer(
+    id: string,
+    confirmation?: ConfirmationArgs,
+  ): Promise<boolean> {
+    return await invoke("delete_mcp_server", { id, ...confirmation });
   },
 
   /**
@@ -119,4 +169,64 @@ export const mcpApi = {
   ): Promise<void> {
     return await invoke("toggle_mcp_app", { serverId, app, enabled });
   },
+
+  /**
+   * 更新 MCP 服务器排序，live 配置中的条目随之按新顺序写出
+   */
+  async updateSortOrder(updates: McpSortUpdate[]): Promise<boolean> {
+    return await invoke("update_mcp_sort_order", { updates });
+  },
+
+  /**
+   * 列出 MCP 方案（指定应用时只返回该应用的方案）
+   */
+  async listProfiles(app?: AppId): Promise<McpProfile[]> {
+    return await invoke("list_mcp_profiles", { app });
+  },
+
+  /**
+   * 将应用当前的启用状态保存为方案（同名覆盖）
+   */
+  async saveCurrentAsProfile(name: string, app: AppId): Promise<McpProfile> {
+    return await invoke("save_current_as_profile", { name, app });
+  },
+
+  /**
+   * 应用方案，返回更新后的服务器列表
+   */
+  async applyProfile(name: string, app: AppId): Promise<McpServersMap> {
+    return await invoke("apply_mcp_profile", { name, app });
+  },
+
+  async deleteProfile(name: string, app: AppId): Promise<boolean> {
+    return await invoke("delete_mcp_profile", { name, app });
+  },
+
+  /**
+   * 复制 MCP 服务器为新 ID（配置与应用启用状态一并复制）
+   */
+  async duplicateServer(id: string, newId: string): Promise<McpServer> {
+    return await invoke("duplicate_mcp_server", { id, newId });
+  },
+
+  /**
+   * 列出导入时被隔离的 MCP 条目
+   */
+  async listQuarantined(): Promise<QuarantinedMcpEntry[]> {
+    return await invoke("list_quarantined_mcp");
+  },
+
+  /**
+   * 使用修正后的内容重试导入隔离条目（不传 raw 时按原始内容重试）
+   */
+  async retryQuarantined(id: string, raw?: string): Promise<McpServer> {
+    return await invoke("retry_quarantined_mcp", { id, raw });
+  },
+
+  /**
+   * 放弃隔离条目
+   */
+  async discardQuarantined(id: string): Promise<void> {
+    return await invoke("discard_quarantined_mcp", { id });
+  },
 };
@@ -0,0 +1,53 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export type LegacyItemKind =
+  | "provider"
+  | "mcpServer"
+  | "prompt"
+  | "skill"
+  | "skillRepo"
+  | "commonConfig";
+
+export interface LegacyItemRef {
+  kind: LegacyItemKind;
+  app?: string;
+  id: string;
+}
+
+export interface LegacyItem extends LegacyItemRef {
+  name: string;
+  valid: boolean;
+  issue?: string;
+  // 数据库中已有同一条目，迁移会覆盖
+  inDatabase: boolean;
+}
+
+export interface LegacyConfigReport {
+  path: string;
+  found: boolean;
+  archivedPath?: string;
+  items: LegacyItem[];
+}
+
+export interface LegacyMigrationResult {
+  migrated: LegacyItemRef[];
+  skipped: Array<LegacyItemRef & { reason: string }>;
+  // 全部有效条目迁移完成后 config.json 的归档位置
+  archivedPath?: string;
+}
+
+export const migrationApi = {
+  async analyze(): Promise<LegacyConfigReport> {
+    return await invoke("analyze_legacy_config");
+  },
+
+  async migrateSelected(
+    items: LegacyItemRef[],
+  ): Promise<LegacyMigrationResult> {
+    return await invoke("migrate_selected", { items });
+  },
+
+  async restoreArchived(): Promise<string> {
+    return await invoke("restore_legacy_config");
+  },
+};
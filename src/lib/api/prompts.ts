@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import type { AppId } from "./types";
 
 export interface Prompt {
@@ -9,6 +10,115 @@ export interface Prompt {
   enabled: boolean;
   createdAt?: number;
   updatedAt?: number;
+  // 乐观锁版本号，保存时带回以检测并发修改
+  version?: number;
+}
+
+// 不含正文的提示词摘要
+export interface PromptSummary {
+  id: string;
+  name: string;
+  description?: string;
+  enabled: boolean;
+  createdAt?: number;
+  updatedAt?: number;
+  contentLength: number;
+  // 正文已加密，列表中显示锁标记
+  encrypted: boolean;
+}
+
+// 加密正文的前缀，与后端 prompt_crypto 保持一致
+const ENCRYPTED_PREFIX = "clihub-enc:v1:";
+
+export function isEncryptedPrompt(content: string): boolean {
+  return content.startsWith(ENCRYPTED_PREFIX);
+}
+
+// Gemini 额外上下文文件（与 GEMINI.md 一同加载）
+export interface GeminiContextFile {
+  fileName: string;
+  content: string;
+  enabled: boolean;
+  createdAt?: number;
+  updatedAt?: number;
+}
+
+// Codex AGENTS.md 层级：全局 ~/.codex/AGENTS.md 或项目目录
+export type CodexAgentsLevel = "global" | "project";
+
+export interface CodexAgentsFile {
+  level: CodexAgentsLevel;
+  path: string;
+  exists: boolean;
+  size: number;
+}
+
+// Codex 在项目目录下实际读到的合并指令
+export interface CodexEffectiveAgents {
+  files: CodexAgentsFile[];
+  merged: string;
+  truncated: boolean;
+}
+
+export interface SlashCommandFile {
+  promptId: string;
+  command: string;
+  path: string;
+}
+
+export interface SlashCommandConflict {
+  promptId: string;
+  command: string;
+  path: string;
+  duplicateInBatch: boolean;
+}
+
+export interface SlashCommandExportResult {
+  written: SlashCommandFile[];
+  conflicts: SlashCommandConflict[];
+}
+
+export interface SlashCommandImportResult {
+  importedIds: string[];
+  skipped: string[];
+}
+
+export type TokenizerFamily = "claude" | "openAi" | "gemini";
+
+// 近似 token 估算结果，不同家族的分词器差异较大，仅供参考
+export interface PromptTokenEstimate {
+  promptId?: string;
+  family: TokenizerFamily;
+  model?: string;
+  characters: number;
+  tokens: number;
+  contextWindow: number;
+  contextPercent: number;
+}
+
+// 按时段自动切换启用的提示词，每个应用一个方案
+export interface PromptSchedule {
+  app: AppId;
+  // 1 为周一、7 为周日；为空表示每天
+  days?: number[];
+  start: string;
+  end: string;
+  promptId: string;
+  // 时段外启用的提示词，未设置时时段外保持不变
+  otherwisePromptId?: string;
+  // 调度器最近一次应用的阶段，只读
+  appliedPhase?: boolean;
+}
+
+export interface PromptsChangedEvent {
+  appType: AppId;
+  enabledId?: string | null;
+  changed: string[];
+}
+
+export interface PromptScheduleEvent {
+  appType: AppId;
+  promptId: string;
 }
 
 export const promptsApi = {
@@ -16,10 +126,60 @@ export const promptsApi = {
     return await invoke("get_prompts", { app });
   },
 
+  async getPromptSummaries(
+    app: AppId,
+    limit?: number,
+    offset?: number,
+  ): Promise<PromptSummary[]> {
+    return await invoke("get_prompt_summaries", { app, limit, offset });
+  },
+
+  async getPromptContent(app: AppId, id: string): Promise<string> {
+    return await invoke("get_prompt_content", { app, id });
+  },
+
   async upsertPrompt(app: AppId, id: string, prompt: Prompt): Promise<void> {
     return await invoke("upsert_prompt", { app, id, prompt });
   },
 
+  // 不传口令时使用本机密钥加密
+  async encryptPrompt(
+    app: AppId,
+    id: string,
+    passphrase?: string,
+  ): Promise<Prompt> {
+    return await invoke("encrypt_prompt", { app, id, passphrase });
+  },
+
+  async decryptPrompt(
+    app: AppId,
+    id: string,
+    passphrase?: string,
+  ): Promise<Prompt> {
+    return await invoke("decrypt_prompt", { app, id, passphrase });
+  },
+
+  // 口令只在本次运行中保留，返回明文
+  async unlockPrompt(
+    app: AppId,
+    id: string,
+    passphrase: string,
+  ): Promise<string> {
+    return await invoke("unlock_prompt", { app, id, passphrase });
+  },
+
+  async estimateTokens(
+    app: AppId,
+    promptIdOrContent: string,
+    model?: string,
+  ): Promise<PromptTokenEstimate> {
+    return await invoke("estimate_prompt_tokens", {
+      app,
+      promptIdOrContent,
+      model,
+    });
+  },
+
   async deletePrompt(app: AppId, id: string): Promise<void> {
     return await invoke("delete_prompt", { app, id });
   },
@@ -32,7 +192,115 @@ export const promptsApi = {
     return await invoke("import_prompt_from_file", { app });
   },
 
+  async exportToSlashCommands(
+    app: AppId,
+    ids: string[],
+    overwrite = false,
+  ): Promise<SlashCommandExportResult> {
+    return await invoke("export_prompts_to_slash_commands", {
+      app,
+      ids,
+      overwrite,
+    });
+  },
+
+  async importSlashCommands(app: AppId): Promise<SlashCommandImportResult> {
+    return await invoke("import_slash_commands", { app });
+  },
+
   async getCurrentFileContent(app: AppId): Promise<string | null> {
     return await invoke("get_current_prompt_file_content", { app });
   },
+
+  async getGeminiContextFiles(): Promise<GeminiContextFile[]> {
+    return await invoke("get_gemini_context_files");
+  },
+
+  async upsertGeminiContextFile(
+    fileName: string,
+    content: string,
+  ): Promise<GeminiContextFile> {
+    return await invoke("upsert_gemini_context_file", { fileName, content });
+  },
+
+  async setGeminiContextFileEnabled(
+    fileName: string,
+    enabled: boolean,
+  ): Promise<void> {
+    return await invoke("set_gemini_context_file_enabled", {
+      fileName,
+      enabled,
+    });
+  },
+
+  async deleteGeminiContextFile(fileName: string): Promise<void> {
+    return await invoke("delete_gemini_context_file", { fileName });
+  },
+
+  async detectCodexAgentsFiles(
+    projectDir?: string,
+  ): Promise<CodexAgentsFile[]> {
+    return await invoke("detect_codex_agents_files", { projectDir });
+  },
+
+  async writeCodexAgentsFile(
+    level: CodexAgentsLevel,
+    content: string,
+    projectDir?: string,
+  ): Promise<string> {
+    return await invoke("write_codex_agents_file", {
+      level,
+      projectDir,
+      content,
+    });
+  },
+
+  async importCodexAgentsFile(
+    level: CodexAgentsLevel,
+    projectDir?: string,
+  ): Promise<string> {
+    return await invoke("import_codex_agents_file", { level, projectDir });
+  },
+
+  async getCodexEffectiveAgents(
+    projectDir?: string,
+  ): Promise<CodexEffectiveAgents> {
+    return await invoke("get_codex_effective_agents", { projectDir });
+  },
+
+  // 一次事务更新多个提示词的启用状态，最多一项可设为启用；返回更新后启用的提示词
+  async setPromptsEnabled(
+    app: AppId,
+    changes: Array<[id: string, enabled: boolean]>,
+  ): Promise<string | null> {
+    return await invoke("set_prompts_enabled", { app, changes });
+  },
+
+  async onPromptsChanged(
+    handler: (event: PromptsChangedEvent) => void,
+  ): Promise<UnlistenFn> {
+    return await listen("prompts-changed", (event) => {
+      handler(event.payload as PromptsChangedEvent);
+    });
+  },
+
+  async listSchedules(app?: AppId): Promise<PromptSchedule[]> {
+    return await invoke("list_prompt_schedules", { app });
+  },
+
+  async saveSchedule(schedule: PromptSchedule): Promise<PromptSchedule> {
+    return await invoke("save_prompt_schedule", { schedule });
+  },
+
+  async deleteSchedule(app: AppId): Promise<boolean> {
+    return await invoke("delete_prompt_schedule", { app });
+  },
+
+  async onScheduleApplied(
+    handler: (event: PromptScheduleEvent) => void,
+  ): Promise<UnlistenFn> {
+    return await listen("prompt-schedule-applied", (event) => {
+      handler(event.payload as PromptScheduleEvent);
+    });
+  },
 };
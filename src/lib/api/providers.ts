@@ -1,18 +1,314 @@
 import { invoke } from "@tauri-apps/api/core";
 import { listen, type UnlistenFn } from "@tauri-apps/api/event";
-import type { Provider } from "@/types";
+import type {
+  LocalModelConfig,
+  ModelFidelity,
+  Provider,
+  ProviderErrorNote,
+} from "@/types";
 import type { AppId } from "./types";
+import type { ConfirmationArgs } from "./confirmation";
 
 export interface ProviderSortUpdate {
   id: string;
   sortIndex: number;
 }
 
+export interface LocalModel {
+  name: string;
+  size?: number;
+  modifiedAt?: string;
+}
+
+export interface LocalModelStatus {
+  baseUrl: string;
+  running: boolean;
+  version?: string;
+  models: LocalModel[];
+  error?: string;
+}
+
+export interface TemplatePlaceholder {
+  key: string;
+  label: string;
+  example: string;
+  // 密钥类占位符，使用密码输入框
+  secret: boolean;
+  defaultValue?: string;
+}
+
+// 需要补全数值后才能创建供应商的模板，settingsConfig 中含 ${KEY} 占位符
+export interface ProviderTemplate {
+  id: string;
+  app: AppId;
+  name: string;
+  websiteUrl: string;
+  apiKeyUrl?: string;
+  icon?: string;
+  settingsConfig: Record<string, unknown>;
+  placeholders: TemplatePlaceholder[];
+  // builtin：随应用内置；remote：来自远程预设索引
+  source: "builtin" | "remote";
+}
+
+export interface PresetRefreshResult {
+  refreshed: boolean;
+  remoteCount: number;
+  fetchedAt?: number;
+}
+
+export type SecuritySeverity = "info" | "warning" | "critical";
+
+// 供应商安全检查发现的问题，按严重程度从高到低排列
+export interface SecurityFinding {
+  kind:
+    | "insecureEndpoint"
+    | "reusedKey"
+    | "keyInNotes"
+    | "invalidCertificate";
+  severity: SecuritySeverity;
+  app: AppId;
+  providerId: string;
+  providerName: string;
+  message: string;
+  url?: string;
+  // 使用同一密钥的其他供应商（app:id）
+  related?: string[];
+}
+
+// 调试代理记录的一次请求，不含请求头
+export interface ProxyLogEntry {
+  id: number;
+  app: AppId;
+  providerId: string;
+  startedAt: number;
+  method: string;
+  path: string;
+  status?: number;
+  durationMs: number;
+  requestBytes: number;
+  responseBytes?: number;
+  requestModel?: string;
+  responseModel?: string;
+  // 响应模型与请求不一致，可能被中转替换
+  modelMismatch: boolean;
+  streaming: boolean;
+  error?: string;
+  // 仅在供应商开启 captureBodies 时记录
+  requestBody?: string;
+  responseBody?: string;
+}
+
+export interface DebugProxyStatus {
+  app: AppId;
+  providerId: string;
+  upstream: string;
+  proxyUrl: string;
+  captureBodies: boolean;
+}
+
+export interface ProviderCredentialsInvalidEvent {
+  appType: AppId;
+  providerId: string;
+  status: number;
+}
+
+// 后台探测发现当前端点不可用并自动改用备用端点
+export interface EndpointFailoverEvent {
+  app: AppId;
+  providerId: string;
+  providerName: string;
+  fromUrl: string;
+  toUrl: string;
+}
+
 export interface ProviderSwitchEvent {
   appType: AppId;
   providerId: string;
 }
 
+export interface CsvColumnMapping {
+  name: string;
+  endpoint: string;
+  apiKey: string;
+  app?: string;
+  defaultApp?: AppId;
+  homepage?: string;
+  model?: string;
+  notes?: string;
+}
+
+export interface CsvProviderRow {
+  row: number;
+  app?: string;
+  name: string;
+  endpoint: string;
+  apiKeyMasked: string;
+  error?: string;
+}
+
+export interface CsvImportResult {
+  imported: Array<{ row: number; app: AppId; id: string }>;
+  failed: Array<{ row: number; error: string }>;
+}
+
+// 托盘菜单刷新统计：合并的重建请求与只移动勾选的次数
+export interface TrayMenuStats {
+  requested: number;
+  coalesced: number;
+  rebuilds: number;
+  partialUpdates: number;
+  lastRebuildMs: number;
+}
+
+// 组织供应商清单导入结果，条目格式为 `app:id`
+export interface ManagedImportSummary {
+  org: string;
+  added: string[];
+  updated: string[];
+  released: string[];
+}
+
+export type ProviderImportConflict = "rename" | "replace" | "skip";
+
+export interface ProviderImportResult {
+  app: AppId;
+  // 导入后的 id；跳过时为本地已有供应商的 id
+  id: string;
+  renamed: boolean;
+  replaced: boolean;
+  skipped: boolean;
+  // 文档不含密钥，导入后需要补填 API Key
+  needsSecrets: boolean;
+}
+
+export interface ProviderSchemaField {
+  path: string;
+  type: "object" | "string" | "toml";
+  required: boolean;
+  requiredWhen?: string;
+  description: string;
+  example?: unknown;
+}
+
+export interface ProviderSchema {
+  appType: AppId;
+  rootType: "object";
+  fields: ProviderSchemaField[];
+  syncScopeSections: string[];
+  example: Record<string, unknown>;
+}
+
+export interface CodexLoginStatus {
+  accountId?: string;
+  email?: string;
+  expiresAt?: number;
+  lastRefresh?: string;
+  hasRefreshToken: boolean;
+  expired: boolean;
+}
+
+// Gemini 模型路由，写入供应商 config（settings.json）
+export interface GeminiModelRouting {
+  defaultModel?: string;
+  fallbackModel?: string;
+  // 任务名 -> 模型
+  taskModels?: Record<string, string>;
+}
+
+export type ApiKeyVendor = "anthropic" | "openAi" | "google";
+
+// 由 API Key 生成的供应商草稿，确认后再调用 add 保存
+export interface ProviderDraftResult {
+  app: AppId;
+  id: string;
+  name: string;
+  source: "env" | "onePassword";
+}
+
+export interface KeyProviderDraft {
+  app: AppId;
+  vendor: ApiKeyVendor;
+  provider: Provider;
+}
+
+export interface RelayEntry {
+  domains: string[];
+  name: string;
+  homepage?: string;
+  icon?: string;
+  iconColor?: string;
+  usagePreset?: string;
+  docsUrl?: string;
+}
+
+export type SwitchCheckKind =
+  | "providerExists"
+  | "credentials"
+  | "healthProbe"
+  | "sessionGuard"
+  | "diskWritable"
+  | "accessWindow"
+  | "settingsOverride";
+
+export type SwitchCheckStatus = "passed" | "warning" | "failed" | "skipped";
+
+export interface SwitchCheckResult {
+  kind: SwitchCheckKind;
+  status: SwitchCheckStatus;
+  message?: string;
+}
+
+export interface SwitchValidation {
+  app: AppId;
+  providerId: string;
+  checks: SwitchCheckResult[];
+  canSwitch: boolean;
+}
+
+export type FileChangeStatus = "created" | "modified" | "deleted";
+
+export interface DiffLine {
+  kind: "context" | "added" | "removed";
+  text: string;
+}
+
+export interface DiffHunk {
+  oldStart: number;
+  newStart: number;
+  lines: DiffLine[];
+}
+
+export interface FileDiff {
+  path: string;
+  status: FileChangeStatus;
+  added: number;
+  removed: number;
+  hunks: DiffHunk[];
+}
+
+export interface SwitchPreview {
+  app: AppId;
+  providerId: string;
+  providerName: string;
+  files: FileDiff[];
+}
+
+export interface SwitchOutcome {
+  app: AppId;
+  providerId: string;
+  providerName: string;
+  previousId?: string;
+  changed: boolean;
+}
+
+export interface SharePageResult {
+  path: string;
+  link: string;
+  qrIncluded: boolean;
+  secretIncluded: boolean;
+}
+
 export const providersApi = {
   async getAll(appId: AppId): Promise<Record<string, Provider>> {
     return await invoke("get_providers", { app: appId });
@@ -22,6 +318,30 @@ export const providersApi = {
     return await invoke("get_current_provider", { app: appId });
   },
 
+  async describeSchema(appId: AppId): Promise<ProviderSchema> {
+    return await invoke("describe_provider_schema", { app: appId });
+  },
+
+  async captureCodexLogin(id: string): Promise<Provider> {
+    return await invoke("capture_codex_login", { id });
+  },
+
+  async getCodexLoginStatus(id: string): Promise<CodexLoginStatus | null> {
+    return await invoke("get_codex_login_status", { id });
+  },
+
+  async refreshMetadata(id: string, appId: AppId): Promise<Provider> {
+    return await invoke("refresh_provider_metadata", { app: appId, id });
+  },
+
+  async getRelayDirectory(): Promise<RelayEntry[]> {
+    return await invoke("get_relay_directory");
+  },
+
+  async updateRelayDirectory(url: string): Promise<number> {
+    return await invoke("update_relay_directory", { url });
+  },
+
   async add(provider: Provider, appId: AppId): Promise<boolean> {
     return await invoke("add_provider", { provider, app: appId });
   },
@@ -30,12 +350,77 @@ export const providersApi = {
     return await invoke("update_provider", { provider, app: appId });
   },
 
-  async delete(id: string, appId: AppId): Promise<boolean> {
-    return await invoke("delete_provider", { id, app: appId });
+  async delete(
+    id: string,
+    appId: AppId,
+    confirmation?: ConfirmationArgs,
+  ): Promise<boolean> {
+    return await invoke("delete_provider", {
+      id,
+      app: appId,
+      ...confirmation,
+    });
+  },
+
+  async switch(id: string, appId: AppId, force = false): Promise<boolean> {
+    return await invoke("switch_provider", { id, app: appId, force });
+  },
+
+  async switchByName(name: string, appId: AppId): Promise<SwitchOutcome> {
+    return await invoke("switch_provider_by_name", { name, app: appId });
   },
 
-  async switch(id: string, appId: AppId): Promise<boolean> {
-    return await invoke("switch_provider", { id, app: appId });
+  async validateSwitch(
+    id: string,
+    appId: AppId,
+    probe = false,
+  ): Promise<SwitchValidation> {
+    return await invoke("validate_switch", { id, app: appId, probe });
+  },
+
+  async previewSwitch(id: string, appId: AppId): Promise<SwitchPreview> {
+    return await invoke("preview_switch", { id, app: appId });
+  },
+
+  // 失败记录按时间倒序，每个供应商最多保留 50 条
+  async getErrorHistory(
+    id: string,
+    appId: AppId,
+  ): Promise<ProviderErrorNote[]> {
+    return await invoke("get_provider_error_history", { id, app: appId });
+  },
+
+  async generateSharePage(
+    id: string,
+    appId: AppId,
+    filePath: string,
+    includeSecret = false,
+  ): Promise<SharePageResult> {
+    return await invoke("generate_provider_share_page", {
+      id,
+      app: appId,
+      filePath,
+      includeSecret,
+    });
+  },
+
+  async exportProvider(
+    id: string,
+    appId: AppId,
+    includeSecrets = false,
+  ): Promise<string> {
+    return await invoke("export_provider", {
+      app: appId,
+      id,
+      includeSecrets,
+    });
+  },
+
+  async importProvider(
+    document: string,
+    onConflict: ProviderImportConflict = "rename",
+  ): Promise<ProviderImportResult> {
+    return await invoke("import_provider", { document, onConflict });
   },
 
   async importDefault(appId: AppId): Promise<boolean> {
@@ -46,6 +431,10 @@ export const providersApi = {
     return await invoke("update_tray_menu");
   },
 
+  async getTrayMenuStats(): Promise<TrayMenuStats> {
+    return await invoke("get_tray_menu_stats");
+  },
+
   async updateSortOrder(
     updates: ProviderSortUpdate[],
     appId: AppId,
@@ -53,6 +442,122 @@ export const providersApi = {
     return await invoke("update_providers_sort_order", { updates, app: appId });
   },
 
+  async previewFromUrl(
+    url: string,
+    mapping: CsvColumnMapping,
+  ): Promise<CsvProviderRow[]> {
+    return await invoke("preview_providers_from_url", { url, mapping });
+  },
+
+  async importFromUrl(
+    url: string,
+    mapping: CsvColumnMapping,
+  ): Promise<CsvImportResult> {
+    return await invoke("import_providers_from_url", { url, mapping });
+  },
+
+  // 载荷为 {name, endpoint, apiKey, app, model} 的 JSON 数组，或同名表头的 CSV
+  async importBulk(payload: string): Promise<CsvImportResult> {
+    return await invoke("import_providers_bulk", { payload });
+  },
+
+  // 清单中不再出现的供应商会解除组织管理
+  async importManagedProviders(
+    filePath: string,
+  ): Promise<ManagedImportSummary> {
+    return await invoke("import_managed_providers", { filePath });
+  },
+
+  async detectLocalModels(baseUrl?: string): Promise<LocalModelStatus> {
+    return await invoke("detect_local_models", { baseUrl });
+  },
+
+  async buildLocalModelSettings(
+    appId: AppId,
+    config: LocalModelConfig,
+  ): Promise<Record<string, any>> {
+    return await invoke("build_local_model_settings", { app: appId, config });
+  },
+
+  async checkLocalModelHealth(config: LocalModelConfig): Promise<boolean> {
+    return await invoke("check_local_model_health", { config });
+  },
+
+  async getGeminiModelRouting(providerId: string): Promise<GeminiModelRouting> {
+    return await invoke("get_gemini_model_routing", { providerId });
+  },
+
+  async setGeminiModelRouting(
+    providerId: string,
+    routing: GeminiModelRouting,
+  ): Promise<Provider> {
+    return await invoke("set_gemini_model_routing", { providerId, routing });
+  },
+
+  async createFromKey(key: string, name?: string): Promise<KeyProviderDraft> {
+    return await invoke("create_provider_from_key", { key, name });
+  },
+
+  // 按设置中的首次导入来源，为尚无供应商的应用生成待确认的草稿
+  async detectDrafts(): Promise<ProviderDraftResult[]> {
+    return await invoke("detect_provider_drafts");
+  },
+
+  async markReviewed(id: string, appId: AppId): Promise<boolean> {
+    return await invoke("mark_provider_reviewed", { id, app: appId });
+  },
+
+  async listTemplates(app?: AppId): Promise<ProviderTemplate[]> {
+    return await invoke("list_provider_templates", { app });
+  },
+
+  // 默认忽略缓存有效期立即拉取；签名校验失败时保留原有缓存
+  async refreshPresets(force = true): Promise<PresetRefreshResult> {
+    return await invoke("refresh_provider_presets", { force });
+  },
+
+  async getSecurityFindings(probeTls = false): Promise<SecurityFinding[]> {
+    return await invoke("get_security_findings", { probeTls });
+  },
+
+  async getProxyLog(app?: AppId, limit?: number): Promise<ProxyLogEntry[]> {
+    return await invoke("get_proxy_log", { app, limit });
+  },
+
+  async clearProxyLog(): Promise<void> {
+    return await invoke("clear_proxy_log");
+  },
+
+  async getDebugProxies(): Promise<DebugProxyStatus[]> {
+    return await invoke("get_debug_proxies");
+  },
+
+  async recheckModelFidelity(
+    app: AppId,
+    providerId: string,
+  ): Promise<ModelFidelity> {
+    return await invoke("recheck_model_fidelity", { app, providerId });
+  },
+
+  async getMissingPlaceholders(
+    templateId: string,
+    values: Record<string, string>,
+  ): Promise<TemplatePlaceholder[]> {
+    return await invoke("get_template_missing_placeholders", {
+      templateId,
+      values,
+    });
+  },
+
+  // 仍有必填占位符为空时会以 MISSING_PLACEHOLDERS 错误拒绝，见 parseMissingPlaceholders
+  async instantiateTemplate(
+    templateId: string,
+    values: Record<string, string>,
+    name?: string,
+  ): Promise<Provider> {
+    return await invoke("instantiate_template", { templateId, values, name });
+  },
+
   async onSwitched(
     handler: (event: ProviderSwitchEvent) => void,
   ): Promise<UnlistenFn> {
@@ -61,4 +566,20 @@ export const providersApi = {
       handler(payload);
     });
   },
+
+  async onEndpointFailover(
+    handler: (events: EndpointFailoverEvent[]) => void,
+  ): Promise<UnlistenFn> {
+    return await listen("endpoint-failover", (event) => {
+      handler(event.payload as EndpointFailoverEvent[]);
+    });
+  },
+
+  async onCredentialsInvalid(
+    handler: (event: ProviderCredentialsInvalidEvent) => void,
+  ): Promise<UnlistenFn> {
+    return await listen("provider-credentials-invalid", (event) => {
+      handler(event.payload as ProviderCredentialsInvalidEvent);
+    });
+  },
 };
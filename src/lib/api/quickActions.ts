@@ -0,0 +1,68 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import type { AppId } from "./types";
+
+export type QuickActionKind =
+  | "switchProvider"
+  | "toggleMcp"
+  | "enablePrompt"
+  | "openConfigFolder";
+
+export interface QuickAction {
+  id: string;
+  kind: QuickActionKind;
+  app: AppId;
+  targetId?: string;
+  label: string;
+  enable?: boolean;
+  score: number;
+}
+
+export interface QuickActionOutcome {
+  kind: QuickActionKind;
+  app: AppId;
+  targetId?: string;
+  openPath?: string;
+}
+
+export interface LauncherAction {
+  id: string;
+  kind: QuickActionKind;
+  app: AppId;
+  targetId?: string;
+  title: string;
+  // clihub://v1/action?id=... 打开即执行
+  deeplink: string;
+  // 在终端中触发同一操作的 argv
+  command: string[];
+}
+
+export interface LauncherManifest {
+  format: "cli-hub-launcher";
+  version: number;
+  generatedAt: number;
+  actions: LauncherAction[];
+}
+
+export const quickActionsApi = {
+  async search(query: string, limit?: number): Promise<QuickAction[]> {
+    return await invoke("get_quick_actions", { query, limit });
+  },
+
+  async execute(id: string): Promise<QuickActionOutcome> {
+    return await invoke("execute_quick_action", { id });
+  },
+
+  async getLauncherManifest(): Promise<LauncherManifest> {
+    return await invoke("get_launcher_manifest");
+  },
+
+  // 启动器通过深链接执行操作后触发（切换供应商仍走 provider-switched）
+  async onLauncherAction(
+    handler: (outcome: QuickActionOutcome) => void,
+  ): Promise<UnlistenFn> {
+    return await listen("launcher-action", (event) => {
+      handler(event.payload as QuickActionOutcome);
+    });
+  },
+};
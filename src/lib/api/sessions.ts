@@ -0,0 +1,44 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { AppId } from "./types";
+
+export interface ProviderSwitchEntry {
+  providerId: string;
+  providerName: string;
+  switchedAt: number;
+}
+
+export interface CliSessionSummary {
+  id: string;
+  project?: string;
+  startedAt?: number;
+  updatedAt?: number;
+  firstPrompt?: string;
+  provider?: ProviderSwitchEntry;
+}
+
+export interface CliSessionMessage {
+  role: "user" | "assistant";
+  text: string;
+  timestamp?: number;
+  providerId?: string;
+}
+
+export interface CliSessionPage {
+  id: string;
+  page: number;
+  pageSize: number;
+  totalMessages: number;
+  totalPages: number;
+  provider?: ProviderSwitchEntry;
+  messages: CliSessionMessage[];
+}
+
+export const sessionsApi = {
+  async list(appId: AppId): Promise<CliSessionSummary[]> {
+    return await invoke("list_cli_sessions", { app: appId });
+  },
+
+  async get(appId: AppId, id: string, page = 0): Promise<CliSessionPage> {
+    return await invoke("get_cli_session", { app: appId, id, page });
+  },
+};
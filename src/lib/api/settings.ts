@@ -1,6 +1,8 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { Settings } from "@/types";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import type { Settings, SettingsOverrides, SettingsProfile } from "@/types";
 import type { AppId } from "./types";
+import type { ConfirmationArgs } from "./confirmation";
 
 export interface ConfigTransferResult {
   success: boolean;
@@ -9,6 +11,222 @@ export interface ConfigTransferResult {
   backupId?: string;
 }
 
+export type ExportDomain = "providers" | "mcp" | "prompts";
+
+export interface SqlExportOptions {
+  /** Empty or omitted exports the whole database */
+  domains?: ExportDomain[];
+  redactSecrets?: boolean;
+}
+
+/** What created the backup; "unknown" when no manifest records it */
+export type DbBackupOrigin = "sqlImport" | "preRestore" | "unknown";
+
+export interface DbBackupInfo {
+  id: string;
+  size: number;
+  createdAt: number;
+  origin: DbBackupOrigin;
+  checksumRecorded: boolean;
+}
+
+export interface BackupVerification {
+  id: string;
+  /** null when the backup predates checksum recording */
+  checksumMatches: boolean | null;
+  integrityOk: boolean;
+  integrityError?: string;
+  schemaVersion: number;
+  supportedSchemaVersion: number;
+  schemaCompatible: boolean;
+  ok: boolean;
+}
+
+export interface RestoreChange {
+  added: number;
+  removed: number;
+  changed: number;
+}
+
+export interface RestorePreview {
+  id: string;
+  verification: BackupVerification;
+  providers: RestoreChange;
+  mcpServers: RestoreChange;
+  prompts: RestoreChange;
+}
+
+export interface ExternalBackupStatus {
+  lastSuccessAt?: number;
+  lastFile?: string;
+  lastFailureAt?: number;
+  lastError?: string;
+}
+
+export interface OrphanCleanupReport {
+  endpoints: { providerId: string; appType: string; url: string }[];
+  /** Unknown keys, kept as `orphaned:<key>` after a real run */
+  settingKeys: string[];
+  dryRun: boolean;
+}
+
+export type StartupStage =
+  | "firstImport"
+  | "providerUpgrade"
+  | "integrityCheck"
+  | "configRepair"
+  | "skillService"
+  | "ready";
+
+export interface StartupProgress {
+  stage: StartupStage;
+  done: boolean;
+  message?: string;
+}
+
+export interface MigrationStatus {
+  running: boolean;
+  phase?: "schema" | "json";
+  // schema 阶段为目标版本（如 "v6"）或 "cleanup"，json 阶段为迁移的数据类别
+  step?: string;
+  percent: number;
+  error?: string;
+}
+
+export interface ProviderRewrite {
+  app: string;
+  providerId: string;
+  providerName: string;
+  migrations: string[];
+}
+
+export interface ProviderUpgradeReport {
+  appVersion: string;
+  upgradedAt: number;
+  // 应用 -> [原版本, 新版本]
+  versions: Record<string, [number, number]>;
+  rewritten: ProviderRewrite[];
+}
+
+export type ProviderIssueKind =
+  | "invalidSettingsJson"
+  | "invalidSettingsShape"
+  | "invalidMeta"
+  | "noCurrentProvider"
+  | "multipleCurrentProviders";
+
+export interface StatusHealth {
+  credentialsValid?: boolean;
+  latencyMs?: number;
+  inAccessWindow: boolean;
+  usageError?: string;
+}
+
+export interface AppStatus {
+  app: string;
+  providerId?: string;
+  providerName?: string;
+  // 最近一次用量查询中占用比例最高的额度（0-100）
+  quotaUsedPercent?: number;
+  quotaUsedText?: string; // 格式化后的占用比例
+  usageCheckedAt?: number;
+  health?: StatusHealth;
+}
+
+export interface StatusSummary {
+  generatedAt: number;
+  managementPaused: boolean;
+  apps: AppStatus[];
+}
+
+export interface WriteTarget {
+  path: string;
+  exists: boolean;
+  writable: boolean;
+  error?: string;
+}
+
+// 各应用配置目录的写权限；不可写时写入操作返回 READ_ONLY_TARGET 错误
+export interface AppWriteCapability {
+  app: AppId;
+  writable: boolean;
+  targets: WriteTarget[];
+  hints?: string[];
+}
+
+export interface ProviderIntegrityIssue {
+  appType: string;
+  providerId?: string;
+  kind: ProviderIssueKind;
+  message: string;
+  // 是否已在启动时自动修复
+  fixed: boolean;
+}
+
+export type DbLocationWarning = "networkFilesystem" | "syncedFolder";
+
+export interface DbLocationInfo {
+  path: string;
+  isDefault: boolean;
+  exists: boolean;
+  filesystem?: string;
+  warnings: DbLocationWarning[];
+}
+
+export interface DbMoveResult {
+  from: string;
+  to: string;
+  replacedBackup?: string;
+  warnings: DbLocationWarning[];
+  restartRequired: boolean;
+}
+
+export interface VcsExportSummary {
+  dir: string;
+  providers: number;
+  mcpServers: number;
+  prompts: number;
+  written: string[];
+  removed: string[];
+}
+
+// 脱敏后的诊断包，不含供应商、MCP 与提示词内容
+export interface DiagnosticsBundle {
+  path: string;
+  files: string[];
+  size: number;
+}
+
+export interface TransferSession {
+  address: string;
+  code: string;
+  expiresAt: number;
+}
+
+export interface CorruptedLiveFile {
+  path: string;
+  error: string;
+  backupPath?: string;
+}
+
+export interface LiveConfigRepairReport {
+  appType: AppId;
+  files: CorruptedLiveFile[];
+  providerId?: string;
+  mcpSynced: boolean;
+}
+
+export interface ResumeSyncPreview {
+  appType: AppId;
+  providerId?: string;
+  providerChanged: boolean;
+  liveSettings?: Record<string, any>;
+  targetSettings?: Record<string, any>;
+  mcpToAdd: string[];
+  mcpToRemove: string[];
+  promptChanged: boolean;
+}
+
 export const settingsApi = {
   async get(): Promise<Settings> {
     return await invoke("get_settings");
@@ -18,6 +236,21 @@ export const settingsApi = {
     return await invoke("save_settings", { settings });
   },
 
+  async getProfile(profile?: string): Promise<SettingsProfile> {
+    return await invoke("get_settings_profile", { profile });
+  },
+
+  async getEffective(): Promise<Settings> {
+    return await invoke("get_effective_settings");
+  },
+
+  async saveOverrides(
+    profile: string,
+    overrides: SettingsOverrides,
+  ): Promise<boolean> {
+    return await invoke("save_settings_overrides", { profile, overrides });
+  },
+
   async restart(): Promise<boolean> {
     return await invoke("restart_app");
   },
@@ -62,6 +295,41 @@ export const settingsApi = {
     return await invoke("set_app_config_dir_override", { path });
   },
 
+  async exportAppSettings(filePath: string): Promise<boolean> {
+    return await invoke("export_app_settings", { filePath });
+  },
+
+  async importAppSettings(filePath: string): Promise<Settings> {
+    return await invoke("import_app_settings", { filePath });
+  },
+
+  async getDbLocation(): Promise<DbLocationInfo> {
+    return await invoke("get_db_location");
+  },
+
+  async checkDbLocation(path: string): Promise<DbLocationInfo> {
+    return await invoke("check_db_location", { path });
+  },
+
+  // 成功后数据库保持只读直到重启，调用方应随即 restart()
+  async moveDatabase(path: string | null): Promise<DbMoveResult> {
+    return await invoke("move_database", { path });
+  },
+
+  async getDemoMode(): Promise<boolean> {
+    return await invoke("get_demo_mode");
+  },
+
+  // 安全模式下后台任务与 live 配置写入均被停用，界面应提示用户修复后正常重启
+  async getSafeMode(): Promise<boolean> {
+    return await invoke("get_safe_mode");
+  },
+
+  // 下次启动生效，需配合 restart() 使用
+  async setDemoMode(enabled: boolean): Promise<boolean> {
+    return await invoke("set_demo_mode", { enabled });
+  },
+
   async applyClaudePluginConfig(options: {
     official: boolean;
   }): Promise<boolean> {
@@ -77,12 +345,84 @@ export const settingsApi = {
     return await invoke("open_file_dialog");
   },
 
-  async exportConfigToFile(filePath: string): Promise<ConfigTransferResult> {
-    return await invoke("export_config_to_file", { filePath });
+  async exportConfigToFile(
+    filePath: string,
+    options?: SqlExportOptions,
+  ): Promise<ConfigTransferResult> {
+    return await invoke("export_config_to_file", { filePath, options });
+  },
+
+  async importConfigFromFile(
+    filePath: string,
+    confirmation?: ConfirmationArgs,
+  ): Promise<ConfigTransferResult> {
+    return await invoke("import_config_from_file", {
+      filePath,
+      ...confirmation,
+    });
+  },
+
+  async listDbBackups(): Promise<DbBackupInfo[]> {
+    return await invoke("list_db_backups");
+  },
+
+  async verifyBackup(id: string): Promise<BackupVerification> {
+    return await invoke("verify_backup", { id });
+  },
+
+  async previewRestore(id: string): Promise<RestorePreview> {
+    return await invoke("preview_restore", { id });
+  },
+
+  async restoreDbBackup(
+    id: string,
+    confirmation?: ConfirmationArgs,
+  ): Promise<ConfigTransferResult & { restoredFrom: string }> {
+    return await invoke("restore_db_backup", { id, ...confirmation });
+  },
+
+  async deleteDbBackup(id: string): Promise<boolean> {
+    return await invoke("delete_db_backup", { id });
+  },
+
+  async startTransferServer(): Promise<TransferSession> {
+    return await invoke("start_transfer_server");
+  },
+
+  async stopTransferServer(): Promise<boolean> {
+    return await invoke("stop_transfer_server");
+  },
+
+  async receiveTransfer(
+    address: string,
+    code: string,
+    confirmation?: ConfirmationArgs,
+  ): Promise<ConfigTransferResult> {
+    return await invoke("receive_transfer", {
+      address,
+      code,
+      ...confirmation,
+    });
+  },
+
+  async writeVcsExport(dir: string): Promise<VcsExportSummary> {
+    return await invoke("write_vcs_export", { dir });
   },
 
-  async importConfigFromFile(filePath: string): Promise<ConfigTransferResult> {
-    return await invoke("import_config_from_file", { filePath });
+  async createDiagnosticsBundle(filePath: string): Promise<DiagnosticsBundle> {
+    return await invoke("create_diagnostics_bundle", { filePath });
+  },
+
+  async backupNowToExternal(): Promise<ExternalBackupStatus> {
+    return await invoke("backup_now_to_external");
+  },
+
+  async getExternalBackupStatus(): Promise<ExternalBackupStatus> {
+    return await invoke("get_external_backup_status");
+  },
+
+  async cleanupOrphanedData(dryRun = false): Promise<OrphanCleanupReport> {
+    return await invoke("cleanup_orphaned_data", { dryRun });
   },
 
   async syncCurrentProvidersLive(): Promise<void> {
@@ -115,4 +455,106 @@ export const settingsApi = {
   async getAutoLaunchStatus(): Promise<boolean> {
     return await invoke("get_auto_launch_status");
   },
+
+  async checkLiveConfig(appId: AppId): Promise<CorruptedLiveFile[]> {
+    return await invoke("check_live_config", { app: appId });
+  },
+
+  async repairLiveConfig(appId: AppId): Promise<LiveConfigRepairReport> {
+    return await invoke("repair_live_config", { app: appId });
+  },
+
+  async onLiveConfigRepaired(
+    handler: (report: LiveConfigRepairReport) => void,
+  ): Promise<UnlistenFn> {
+    return await listen<LiveConfigRepairReport>(
+      "live-config-repaired",
+      (event) => handler(event.payload),
+    );
+  },
+
+  async isStartupReady(): Promise<boolean> {
+    return await invoke("is_startup_ready");
+  },
+
+  async waitForStartup(): Promise<boolean> {
+    return await invoke("wait_for_startup");
+  },
+
+  async onStartupProgress(
+    handler: (progress: StartupProgress) => void,
+  ): Promise<UnlistenFn> {
+    return await listen<StartupProgress>("startup-progress", (event) =>
+      handler(event.payload),
+    );
+  },
+
+  async getMigrationStatus(): Promise<MigrationStatus> {
+    return await invoke("get_migration_status");
+  },
+
+  async onMigrationProgress(
+    handler: (status: MigrationStatus) => void,
+  ): Promise<UnlistenFn> {
+    return await listen<MigrationStatus>("migration-progress", (event) =>
+      handler(event.payload),
+    );
+  },
+
+  async getProviderUpgradeReport(): Promise<ProviderUpgradeReport | null> {
+    return await invoke("get_provider_upgrade_report");
+  },
+
+  async onProviderUpgrade(
+    handler: (report: ProviderUpgradeReport) => void,
+  ): Promise<UnlistenFn> {
+    return await listen<ProviderUpgradeReport>(
+      "provider-settings-upgraded",
+      (event) => handler(event.payload),
+    );
+  },
+
+  async getStatusSummary(): Promise<StatusSummary> {
+    return await invoke("get_status_summary");
+  },
+
+  async getWriteCapabilities(): Promise<AppWriteCapability[]> {
+    return await invoke("get_write_capabilities");
+  },
+
+  async getInitWarnings(): Promise<ProviderIntegrityIssue[]> {
+    return await invoke("get_init_warnings");
+  },
+
+  async onInitWarnings(
+    handler: (issues: ProviderIntegrityIssue[]) => void,
+  ): Promise<UnlistenFn> {
+    return await listen<ProviderIntegrityIssue[]>("init-warnings", (event) =>
+      handler(event.payload),
+    );
+  },
+
+  async getManagementPaused(): Promise<boolean> {
+    return await invoke("get_management_paused");
+  },
+
+  async pauseManagement(): Promise<boolean> {
+    return await invoke("pause_management");
+  },
+
+  async previewResumeManagement(): Promise<ResumeSyncPreview[]> {
+    return await invoke("preview_resume_management");
+  },
+
+  async resumeManagement(): Promise<ResumeSyncPreview[]> {
+    return await invoke("resume_management");
+  },
+
+  async onManagementPausedChanged(
+    handler: (paused: boolean) => void,
+  ): Promise<UnlistenFn> {
+    return await listen<boolean>("management-paused-changed", (event) =>
+      handler(event.payload),
+    );
+  },
 };
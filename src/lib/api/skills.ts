@@ -13,6 +13,13 @@ export interface Skill {
   skillsPath?: string; // 技能所在的子目录路径，如 "skills"
 }
 
+export interface ScannedSkill {
+  directory: string;
+  name: string;
+  description: string;
+  source: string; // "repo:owner/name" 或 "unmanaged"
+}
+
 export interface SkillRepo {
   owner: string;
   name: string;
@@ -21,6 +28,15 @@ export interface SkillRepo {
   skillsPath?: string; // 可选：技能所在的子目录路径，如 "skills"
 }
 
+// 最近一次 GitHub 响应中的限流状态
+export interface GitHubRateLimit {
+  limit?: number;
+  remaining?: number;
+  resetAt?: number; // Unix 秒
+  authenticated: boolean;
+  updatedAt: number;
+}
+
 export const skillsApi = {
   async getAll(): Promise<Skill[]> {
     return await invoke("get_skills");
@@ -34,6 +50,10 @@ export const skillsApi = {
     return await invoke("uninstall_skill", { directory });
   },
 
+  async scanInstalled(): Promise<ScannedSkill[]> {
+    return await invoke("scan_installed_skills");
+  },
+
   async getRepos(): Promise<SkillRepo[]> {
     return await invoke("get_skill_repos");
   },
@@ -45,4 +65,8 @@ export const skillsApi = {
   async removeRepo(owner: string, name: string): Promise<boolean> {
     return await invoke("remove_skill_repo", { owner, name });
   },
+
+  async getGithubRateLimit(): Promise<GitHubRateLimit | null> {
+    return await invoke("get_github_rate_limit");
+  },
 };
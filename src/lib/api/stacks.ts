@@ -0,0 +1,45 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { AppId } from "./types";
+
+export interface StackDefinition {
+  id: string;
+  name: string;
+  description: string;
+  mcpServers: Array<{
+    id: string;
+    name: string;
+    server: Record<string, unknown>;
+    homepage: string;
+  }>;
+  prompt?: { id: string; name: string; content: string };
+  skills: Array<{
+    directory: string;
+    repoOwner: string;
+    repoName: string;
+    repoBranch: string;
+  }>;
+}
+
+export interface StackItemReport {
+  kind: "mcp" | "prompt" | "skill";
+  id: string;
+  apps: AppId[];
+  reason?: string;
+}
+
+export interface StackApplyReport {
+  stack: string;
+  added: StackItemReport[];
+  skipped: StackItemReport[];
+  failed: StackItemReport[];
+}
+
+export const stacksApi = {
+  async list(): Promise<StackDefinition[]> {
+    return await invoke("list_stacks");
+  },
+
+  async apply(name: string, apps: AppId[]): Promise<StackApplyReport> {
+    return await invoke("apply_stack", { name, apps });
+  },
+};
@@ -1,9 +1,41 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { UsageResult } from "@/types";
+import type { UsageData, UsageResult } from "@/types";
 import type { AppId } from "./types";
 import i18n from "@/i18n";
 
+// 试运行时代替真实接口的响应；url/method 为空时匹配任意请求
+export interface MockHttpResponse {
+  url?: string;
+  method?: string;
+  status?: number;
+  body: unknown;
+}
+
+export interface UsageDryRunResult {
+  request: {
+    url: string;
+    method: string;
+    headers: Record<string, string>;
+    body?: string;
+  };
+  matchedMock: number;
+  data?: UsageData[];
+  error?: string;
+}
+
+export interface UsageDryRunOptions {
+  apiKey?: string;
+  baseUrl?: string;
+  accessToken?: string;
+  userId?: string;
+}
+
 export const usageApi = {
+  // 取消该供应商进行中的用量查询（被取消的查询返回 success: false）
+  async cancel(providerId: string): Promise<boolean> {
+    return await invoke("cancel_usage_query", { providerId });
+  },
+
   async query(providerId: string, appId: AppId): Promise<UsageResult> {
     try {
       return await invoke("queryProviderUsage", {
@@ -62,4 +94,16 @@ export const usageApi = {
       };
     }
   },
+
+  async dryRunScript(
+    scriptCode: string,
+    mockResponses: MockHttpResponse[],
+    options: UsageDryRunOptions = {},
+  ): Promise<UsageDryRunResult> {
+    return await invoke("dry_run_usage_script", {
+      scriptCode,
+      mockResponses,
+      ...options,
+    });
+  },
 };
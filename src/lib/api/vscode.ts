@@ -1,5 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { CustomEndpoint } from "@/types";
+import type { CustomEndpoint, EndpointImportResult } from "@/types";
 import type { AppId } from "./types";
 
 export interface EndpointLatencyResult {
@@ -9,18 +9,80 @@ export interface EndpointLatencyResult {
   error?: string;
 }
 
+// 后台探测得到的端点可用性（score 0-1，越高越稳定）
+export interface EndpointHealth {
+  url: string;
+  score: number;
+  latencyMs?: number;
+  lastError?: string;
+  checkedAt: number;
+}
+
+export interface InferenceLatencyResult {
+  url: string;
+  model: string;
+  status?: number;
+  firstTokenMs?: number;
+  totalMs?: number;
+  outputTokens?: number;
+  tokensPerSec?: number;
+  // 响应中声明的模型，与 model 不同说明中转换了模型
+  servedModel?: string;
+  error?: string;
+}
+
+export interface LiveFieldChange {
+  path: string;
+  before: unknown | null;
+  after: unknown | null;
+}
+
+export interface LiveMergePreview {
+  changes: LiveFieldChange[];
+  settingsConfig: Record<string, unknown>;
+  applied: boolean;
+}
+
 export const vscodeApi = {
   async getLiveProviderSettings(appId: AppId) {
     return await invoke("read_live_provider_settings", { app: appId });
   },
 
+  async mergeLiveIntoProvider(
+    appId: AppId,
+    providerId: string,
+    paths: string[],
+    dryRun = false,
+  ): Promise<LiveMergePreview> {
+    return await invoke("merge_live_into_provider", {
+      app: appId,
+      id: providerId,
+      paths,
+      dryRun,
+    });
+  },
+
   async testApiEndpoints(
     urls: string[],
-    options?: { timeoutSecs?: number },
+    options?: { timeoutSecs?: number; appId?: AppId; providerId?: string },
   ): Promise<EndpointLatencyResult[]> {
     return await invoke("test_api_endpoints", {
       urls,
       timeoutSecs: options?.timeoutSecs,
+      app: options?.appId,
+      providerId: options?.providerId,
+    });
+  },
+
+  async testInferenceLatency(
+    appId: AppId,
+    providerId: string,
+    timeoutSecs?: number,
+  ): Promise<InferenceLatencyResult> {
+    return await invoke("test_inference_latency", {
+      app: appId,
+      providerId,
+      timeoutSecs,
     });
   },
 
@@ -34,6 +96,16 @@ export const vscodeApi = {
     });
   },
 
+  async getEndpointHealth(
+    appId: AppId,
+    providerId: string,
+  ): Promise<EndpointHealth[]> {
+    return await invoke("get_endpoint_health", {
+      app: appId,
+      providerId: providerId,
+    });
+  },
+
   async addCustomEndpoint(
     appId: AppId,
     providerId: string,
@@ -58,6 +130,28 @@ export const vscodeApi = {
     });
   },
 
+  async exportCustomEndpoints(
+    appId: AppId,
+    providerId: string,
+  ): Promise<string> {
+    return await invoke("export_custom_endpoints", {
+      app: appId,
+      providerId: providerId,
+    });
+  },
+
+  async importCustomEndpoints(
+    appId: AppId,
+    providerId: string,
+    snippet: string,
+  ): Promise<EndpointImportResult> {
+    return await invoke("import_custom_endpoints", {
+      app: appId,
+      providerId: providerId,
+      snippet,
+    });
+  },
+
   async updateEndpointLastUsed(
     appId: AppId,
     providerId: string,
@@ -0,0 +1,30 @@
+/**
+ * 乐观锁冲突：保存时记录已被其他窗口修改
+ */
+export interface VersionConflictError<T = unknown> {
+  code: "VERSION_CONFLICT";
+  resource: "provider" | "prompt";
+  id: string;
+  expectedVersion: number;
+  currentVersion: number;
+  // 数据库中的最新记录；已被删除时为 null
+  current: T | null;
+}
+
+/**
+ * 解析后端返回的冲突错误，非冲突错误返回 null
+ */
+export function parseVersionConflict<T = unknown>(
+  error: unknown,
+): VersionConflictError<T> | null {
+  const message = error instanceof Error ? error.message : String(error);
+  try {
+    const parsed = JSON.parse(message);
+    if (parsed?.code === "VERSION_CONFLICT") {
+      return parsed as VersionConflictError<T>;
+    }
+  } catch {
+    // 不是 JSON 格式
+  }
+  return null;
+}
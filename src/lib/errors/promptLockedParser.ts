@@ -0,0 +1,24 @@
+/**
+ * 提示词以口令加密且本次运行尚未解锁
+ */
+export interface PromptLockedError {
+  code: "PROMPT_LOCKED";
+  app: string;
+  id: string;
+}
+
+/**
+ * 解析后端返回的未解锁错误，其他错误返回 null；调用方据此弹出口令输入
+ */
+export function parsePromptLocked(error: unknown): PromptLockedError | null {
+  const message = error instanceof Error ? error.message : String(error);
+  try {
+    const parsed = JSON.parse(message);
+    if (parsed?.code === "PROMPT_LOCKED") {
+      return parsed as PromptLockedError;
+    }
+  } catch {
+    // 不是 JSON 格式
+  }
+  return null;
+}
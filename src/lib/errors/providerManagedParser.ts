@@ -0,0 +1,27 @@
+/**
+ * 供应商由组织统一管理，本地编辑或删除被拒绝
+ */
+export interface ProviderManagedError {
+  code: "PROVIDER_MANAGED";
+  app: string;
+  id: string;
+  org: string;
+}
+
+/**
+ * 解析后端返回的组织管理错误，其他错误返回 null
+ */
+export function parseProviderManaged(
+  error: unknown,
+): ProviderManagedError | null {
+  const message = error instanceof Error ? error.message : String(error);
+  try {
+    const parsed = JSON.parse(message);
+    if (parsed?.code === "PROVIDER_MANAGED") {
+      return parsed as ProviderManagedError;
+    }
+  } catch {
+    // 不是 JSON 格式
+  }
+  return null;
+}
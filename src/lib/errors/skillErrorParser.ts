@@ -37,6 +37,8 @@ function getErrorI18nKey(code: string): string {
     SKILL_DIR_NOT_FOUND: "skills.error.skillDirNotFound",
     EMPTY_ARCHIVE: "skills.error.emptyArchive",
     GET_HOME_DIR_FAILED: "skills.error.getHomeDirFailed",
+    GITHUB_RATE_LIMITED: "skills.error.rateLimited",
+    NETWORK_ERROR: "skills.error.networkErrorDetail",
   };
 
   return mapping[code] || "skills.error.unknownError";
@@ -52,6 +54,7 @@ function getSuggestionI18nKey(suggestion: string): string {
     retryLater: "skills.error.suggestion.retryLater",
     checkRepoUrl: "skills.error.suggestion.checkRepoUrl",
     checkPermission: "skills.error.suggestion.checkPermission",
+    addGithubToken: "skills.error.suggestion.addGithubToken",
     http403: "skills.error.http403",
     http404: "skills.error.http404",
     http429: "skills.error.http429",
@@ -87,8 +90,18 @@ export function formatSkillError(
   // 获取错误消息的 i18n key
   const errorKey = getErrorI18nKey(code);
 
+  // 限流错误附带重置时间（Unix 秒），转换为本地时间显示
+  const params: Record<string, string> = { ...context };
+  if (code === "GITHUB_RATE_LIMITED") {
+    const resetAt = Number(context.resetAt);
+    params.resetTime =
+      Number.isFinite(resetAt) && resetAt > 0
+        ? new Date(resetAt * 1000).toLocaleTimeString()
+        : "-";
+  }
+
   // 构建描述（错误消息 + 建议）
-  let description = t(errorKey, context);
+  let description = t(errorKey, params);
 
   // 如果有建议，追加到描述中
   if (suggestion) {
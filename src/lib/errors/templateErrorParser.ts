@@ -0,0 +1,28 @@
+import type { TemplatePlaceholder } from "@/lib/api/providers";
+
+/**
+ * 按模板创建供应商时仍有必填占位符未填写
+ */
+export interface MissingPlaceholdersError {
+  code: "MISSING_PLACEHOLDERS";
+  templateId: string;
+  missing: TemplatePlaceholder[];
+}
+
+/**
+ * 解析后端返回的缺失占位符错误，其他错误返回 null
+ */
+export function parseMissingPlaceholders(
+  error: unknown,
+): MissingPlaceholdersError | null {
+  const message = error instanceof Error ? error.message : String(error);
+  try {
+    const parsed = JSON.parse(message);
+    if (parsed?.code === "MISSING_PLACEHOLDERS") {
+      return parsed as MissingPlaceholdersError;
+    }
+  } catch {
+    // 不是 JSON 格式
+  }
+  return null;
+}
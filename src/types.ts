@@ -23,6 +23,11 @@ export interface Provider {
   // 图标配置
   icon?: string; // 图标名称（如 "openai", "anthropic"）
   iconColor?: string; // 图标颜色（Hex 格式，如 "#00A67E"）
+  // 使用统计（由后端维护，保存时忽略）
+  lastSwitchedAt?: number; // 最近一次切换时间（秒）
+  switchCount?: number; // 累计切换次数
+  // 乐观锁版本号（由后端维护），编辑保存时原样带回
+  version?: number;
 }
 
 export interface AppConfig {
@@ -37,6 +42,13 @@ export interface CustomEndpoint {
   lastUsed?: number;
 }
 
+// 导入自定义端点的结果
+export interface EndpointImportResult {
+  added: string[];
+  // 供应商已存在的端点
+  skipped: string[];
+}
+
 // 端点候选项（用于端点测速弹窗）
 export interface EndpointCandidate {
   id?: string;
@@ -65,8 +77,29 @@ export interface UsageScript {
   };
 }
 
+// 后端按界面语言格式化好的用量文本
+export interface UsageDisplay {
+  total?: string;
+  used?: string;
+  remaining?: string;
+  usedPercent?: string; // 如 62.5%
+  resetAt?: string; // 本地时区的重置时间
+}
+
 // 单个套餐用量数据
+// 额度分桶（如每日、每月额度）
+export interface UsageBucket {
+  name?: string;
+  total?: number;
+  used?: number;
+  remaining?: number;
+  unit?: string;
+  resetAt?: number; // 重置时间（Unix 秒）
+  display?: UsageDisplay;
+}
+
 export interface UsageData {
+  schemaVersion?: number; // 后端规范化后的结构版本（当前为 2）
   planName?: string; // 套餐名称（可选）
   extra?: string; // 扩展字段，可自由补充需要展示的文本（可选）
   isValid?: boolean; // 套餐是否有效（可选）
@@ -75,6 +108,10 @@ export interface UsageData {
   used?: number; // 已用额度（可选）
   remaining?: number; // 剩余额度（可选）
   unit?: string; // 单位（可选）
+  currency?: string; // 货币代码（可选），如 USD
+  resetAt?: number; // 额度重置时间（Unix 秒，可选）
+  buckets?: UsageBucket[]; // 额度分桶，v1 输出会转换为单个分桶
+  display?: UsageDisplay; // 查询成功后由后端填充
 }
 
 // 用量查询结果（支持多套餐）
@@ -94,6 +131,173 @@ export interface ProviderMeta {
   isPartner?: boolean;
   // 合作伙伴促销 key（用于后端识别 PackyCode 等）
   partnerPromotionKey?: string;
+  // Claude 接入方式（直连 / Bedrock / Vertex）
+  claudeFlavor?: ClaudeFlavorConfig;
+  // 本地模型（Ollama）供应商，切换前检查本地端点
+  localModel?: LocalModelConfig;
+  // 最近一次探测到凭证失效的记录
+  credentialStatus?: CredentialStatus;
+  // 最近一次测速结果（最快的可用端点），用于按延迟排序
+  latency?: ProviderLatency;
+  // 文档链接（由中转目录自动补全）
+  docsUrl?: string;
+  // 推荐的用量查询模板键
+  usagePreset?: string;
+  // Codex 认证方式（缺省按 auth 内容推断）
+  codexAuthMode?: CodexAuthMode;
+  // 可用时段，时段外切换需要强制确认
+  accessWindow?: AccessWindow;
+  // 本地调试代理：转发到真实端点并记录请求元数据
+  debugProxy?: DebugProxyConfig;
+  // 最近一次模型一致性检查（中转是否换成了其他模型）
+  modelFidelity?: ModelFidelity;
+  // 由组织统一下发：只能切换，不能在本地编辑或删除
+  managedBy?: string;
+  // 首次导入时从环境变量或 1Password 生成，尚待确认
+  pendingReview?: "env" | "onePassword";
+  // 最近一次切换、健康检查或用量查询失败
+  lastError?: ProviderErrorNote;
+}
+
+export interface ProviderErrorNote {
+  operation: "switch" | "healthCheck" | "usage";
+  message: string;
+  // Unix 毫秒
+  occurredAt: number;
+}
+
+export interface ModelFidelity {
+  requestedModel: string;
+  servedModel: string;
+  mismatch: boolean;
+  checkedAt: number;
+  // probe 为测试请求，proxy 为调试代理观测
+  source: "probe" | "proxy";
+}
+
+export interface DebugProxyConfig {
+  enabled: boolean;
+  // 同时记录请求/响应正文（截断到 64 KiB）
+  captureBodies?: boolean;
+}
+
+// 供应商可用时段（本地时间）
+export interface AccessWindow {
+  // 1 为周一、7 为周日；为空表示每天
+  days?: number[];
+  // HH:MM，结束早于开始表示跨越午夜
+  start: string;
+  end: string;
+  // 时段结束时自动切换到的供应商
+  fallbackProviderId?: string;
+}
+
+export type CodexAuthMode = "api-key" | "chatgpt-login";
+
+export interface CredentialStatus {
+  valid: boolean;
+  checkedAt: number;
+  httpStatus?: number;
+}
+
+export interface ProviderLatency {
+  latencyMs: number;
+  testedAt: number;
+}
+
+export interface LocalModelConfig {
+  baseUrl: string;
+  model: string;
+  // Claude 使用时的 Anthropic 兼容代理地址
+  proxyUrl?: string;
+}
+
+export type ClaudeFlavor = "direct" | "bedrock" | "vertex";
+
+// Claude 接入方式配置，切换时由后端生成 CLAUDE_CODE_USE_* 等环境变量
+export interface ClaudeFlavorConfig {
+  flavor: ClaudeFlavor;
+  // Bedrock 为 AWS_REGION，Vertex 为 CLOUD_ML_REGION
+  region?: string;
+  // Vertex 项目 ID
+  projectId?: string;
+  // Bedrock 使用的 AWS profile
+  awsProfile?: string;
+}
+
+// 切换供应商前的 API Key 探测策略
+export type CredentialProbeMode = "off" | "warn" | "abort";
+
+// 供应商列表排序方式
+export type ProviderSortMode =
+  | "manual"
+  | "recentlyUsed"
+  | "latency"
+  | "alphabetical"
+  | "category";
+
+// Claude 配置层级，优先级 user < local < managed；managed 只读
+export type ClaudeSettingsLayer = "user" | "local" | "managed";
+
+export type NotificationCategory =
+  | "firstImport"
+  | "migration"
+  | "backup"
+  | "mcpSync"
+  | "accessWindow"
+  | "endpointFailover"
+  | "promptSchedule";
+
+export type TrayDensity = "comfortable" | "compact";
+
+// auto：macOS 用单色模板图标，其他平台用彩色应用图标
+export type TrayIconStyle = "auto" | "colored" | "monochrome";
+
+// 按配置档覆盖的设置项，未设置的字段沿用全局设置
+export interface SettingsOverrides {
+  minimizeToTrayOnClose?: boolean;
+  language?: "zh" | "en";
+  trayDensity?: TrayDensity;
+}
+
+export interface SettingsProfile {
+  profile?: string;
+  overrides: SettingsOverrides;
+}
+
+export interface FirstImportSources {
+  // 不读取 shell 中的 ANTHROPIC_* / OPENAI_* 环境变量
+  skipEnv?: boolean;
+  // 通过 1Password CLI（op）读取的条目名称，为空时不调用 op
+  onePasswordItems?: string[];
+}
+
+export interface NotificationSettings {
+  enabled: boolean;
+  disabledCategories?: NotificationCategory[];
+}
+
+export interface ProviderSortModes {
+  claude?: ProviderSortMode;
+  codex?: ProviderSortMode;
+  gemini?: ProviderSortMode;
+}
+
+// 破坏性操作的确认级别
+export type ConfirmationLevel = "none" | "confirm" | "typeName";
+
+export interface ConfirmationPolicies {
+  deleteProvider?: ConfirmationLevel;
+  deleteMcpServer?: ConfirmationLevel;
+  importSql?: ConfirmationLevel;
+  restoreBackup?: ConfirmationLevel;
+}
+
+// 定期导出到外部目录的设置
+export interface ExternalBackupSettings {
+  dir?: string;
+  intervalHours?: number;
+  retain?: number;
 }
 
 // 应用设置类型（用于设置对话框与 Tauri API）
@@ -118,6 +322,28 @@ export interface Settings {
   customEndpointsClaude?: Record<string, CustomEndpoint>;
   // Codex 自定义端点列表
   customEndpointsCodex?: Record<string, CustomEndpoint>;
+  // 定期导出到外部目录（可选）
+  externalBackup?: ExternalBackupSettings;
+  // 破坏性操作的确认策略
+  confirmationPolicies?: ConfirmationPolicies;
+  // 切换供应商前探测 API Key 是否有效
+  credentialProbe?: CredentialProbeMode;
+  // 各应用的供应商排序方式（默认手动排序）
+  providerSort?: ProviderSortModes;
+  // 后台任务结果的系统通知（默认关闭）
+  notifications?: NotificationSettings;
+  // CLI Hub 写入 Claude 配置的层级（默认 settings.json）
+  claudeSettingsLayer?: ClaudeSettingsLayer;
+  // 当前端点持续不可用时自动改用备用端点（默认关闭）
+  endpointFailover?: boolean;
+  // 托盘菜单密度：compact 时每个应用只显示当前供应商
+  trayDensity?: TrayDensity;
+  // 托盘图标样式（默认 auto）
+  trayIconStyle?: TrayIconStyle;
+  // 技能下载使用的 GitHub Token（提高 API 配额，可访问私有仓库）
+  githubToken?: string;
+  // 首次导入时 live 配置缺失的应用从哪些来源生成草稿供应商
+  firstImportSources?: FirstImportSources;
   // 安全设置（兼容未来扩展）
   security?: {
     auth?: {
@@ -142,6 +368,18 @@ export interface McpServerSpec {
   [key: string]: any;
 }
 
+// MCP 传输方式转换（stdio ⇄ http/sse）
+export type McpTransport = "stdio" | "http" | "sse";
+export type McpBridge = "supergateway" | "mcp-proxy";
+
+export interface McpTransportConversion {
+  spec: McpServerSpec;
+  // stdio 暴露为 HTTP 时需单独启动的网关命令
+  launchCommand?: string;
+  launchEnv?: Record<string, string>;
+  requiredBinaries: { name: string; available: boolean }[];
+}
+
 // v3.7.0: MCP 服务器应用启用状态
 export interface McpApps {
   claude: boolean;
@@ -168,6 +406,24 @@ export interface McpServer {
 // MCP 服务器映射（id -> McpServer）
 export type McpServersMap = Record<string, McpServer>;
 
+// MCP 方案：某应用下一组启用的服务器
+export interface McpProfile {
+  name: string;
+  app: "claude" | "codex" | "gemini";
+  servers: string[];
+  updatedAt: number;
+}
+
+// 导入时无法解析的 MCP 条目（隔离区）
+export interface QuarantinedMcpEntry {
+  id: string; // `<app>:<serverId>`
+  appType: string;
+  serverId: string;
+  raw: string; // Claude/Gemini 为 JSON，Codex 为 TOML 片段
+  error: string;
+  createdAt: number;
+}
+
 // MCP 配置状态
 export interface McpStatus {
   userConfigPath: string;
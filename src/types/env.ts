@@ -20,10 +20,44 @@ export interface EnvConflict {
  * 备份信息
  */
 export interface BackupInfo {
+  /** 备份 ID，用于选择性恢复 */
+  id: string;
   /** 备份文件路径 */
   backupPath: string;
   /** 备份时间戳 */
   timestamp: string;
   /** 被备份的环境变量冲突列表 */
   conflicts: EnvConflict[];
+  /** 删除前的精确快照 */
+  entries: EnvBackupEntry[];
+}
+
+/**
+ * shell 配置文件中的一行
+ */
+export interface ShellLine {
+  /** 行号（从 1 开始） */
+  lineNumber: number;
+  content: string;
+}
+
+/**
+ * 单个变量删除前的状态
+ */
+export interface EnvBackupEntry {
+  varName: string;
+  varValue: string;
+  sourceType: "system" | "file";
+  sourcePath: string;
+  /** shell 文件中被删除的原始行 */
+  lines?: ShellLine[];
+}
+
+/**
+ * 修改 shell 配置文件前的差异预览
+ */
+export interface ShellFileDiff {
+  filePath: string;
+  removed: ShellLine[];
+  added: ShellLine[];
 }